@@ -0,0 +1,73 @@
+//! Reference implementation of a remote signer of the validator set update
+//! vote extensions, serving the signature requests of a ledger configured
+//! with `eth_signer = "remote"`. See [`namada_apps::node::ledger::eth_signer`]
+//! for the protocol.
+//!
+//! Usage: `namada-eth-signer --socket <PATH> --key-file <PATH>`, where the
+//! key file contains the encoded Ethereum hot secret key of the validator,
+//! or its Ethereum cold secret key, when serving the requests of a ledger
+//! configured with `eth_cold_signer_socket`.
+
+use std::os::unix::net::UnixListener;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use namada::types::key::common;
+use namada_apps::logging;
+use namada_apps::node::ledger::eth_signer::serve_signature_request;
+use tracing_subscriber::filter::LevelFilter;
+
+fn main() -> Result<()> {
+    // init error reporting
+    color_eyre::install()?;
+
+    // init logging
+    logging::init_from_env_or(LevelFilter::INFO)?;
+
+    let (socket, key_file) = parse_args()?;
+    let encoded_key =
+        std::fs::read_to_string(&key_file).wrap_err_with(|| {
+            format!("Failed to read the key file {}", key_file.display())
+        })?;
+    let eth_hot_key = common::SecretKey::from_str(encoded_key.trim())
+        .map_err(|err| eyre!("Invalid Ethereum hot key: {err}"))?;
+
+    let listener = UnixListener::bind(&socket).wrap_err_with(|| {
+        format!("Failed to bind the socket {}", socket.display())
+    })?;
+    tracing::info!(socket = %socket.display(), "Serving signature requests");
+    for stream in listener.incoming() {
+        let result = stream.and_then(|mut stream| {
+            serve_signature_request(&mut stream, &eth_hot_key)
+        });
+        match result {
+            Ok(()) => tracing::info!("Signed a vote extension digest"),
+            Err(err) => {
+                tracing::error!(%err, "Failed to serve a signature request")
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parse the `--socket` and `--key-file` arguments.
+fn parse_args() -> Result<(PathBuf, PathBuf)> {
+    let mut socket = None;
+    let mut key_file = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| eyre!("Missing the value of {arg}"))?;
+        match arg.as_str() {
+            "--socket" => socket = Some(PathBuf::from(value)),
+            "--key-file" => key_file = Some(PathBuf::from(value)),
+            _ => return Err(eyre!("Unexpected argument {arg}")),
+        }
+    }
+    Ok((
+        socket.ok_or_else(|| eyre!("Missing the --socket argument"))?,
+        key_file.ok_or_else(|| eyre!("Missing the --key-file argument"))?,
+    ))
+}
@@ -38,6 +38,22 @@ pub fn main() -> Result<()> {
                 ledger::rollback(chain_ctx.config.ledger)
                     .wrap_err("Failed to rollback the Namada node")?;
             }
+            cmds::Ledger::VerifyGovernance(_) => {
+                let chain_ctx = ctx.take_chain_or_exit();
+                let is_consistent =
+                    ledger::verify_governance_storage(chain_ctx.config.ledger)
+                        .wrap_err("Failed to verify the governance storage")?;
+                if !is_consistent {
+                    cli::safe_exit(1)
+                }
+            }
+            cmds::Ledger::ExportGovernance(cmds::LedgerExportGovernance(
+                args,
+            )) => {
+                let chain_ctx = ctx.take_chain_or_exit();
+                ledger::export_governance(chain_ctx.config.ledger, args)
+                    .wrap_err("Failed to export the governance storage")?;
+            }
         },
         cmds::NamadaNode::Config(sub) => match sub {
             cmds::Config::Gen(cmds::ConfigGen) => {
@@ -13,7 +13,7 @@ pub mod relayer;
 mod utils;
 pub mod wallet;
 
-use clap::{ArgGroup, ArgMatches, ColorChoice};
+use clap::{ArgAction, ArgGroup, ArgMatches, ColorChoice};
 use color_eyre::eyre::Result;
 use namada::types::io::StdIo;
 use utils::*;
@@ -162,19 +162,23 @@ pub mod cmds {
     pub enum NamadaRelayer {
         EthBridgePool(EthBridgePool),
         ValidatorSet(ValidatorSet),
+        VerifyArchivedProof(VerifyArchivedProof),
     }
 
     impl Cmd for NamadaRelayer {
         fn add_sub(app: App) -> App {
             app.subcommand(EthBridgePool::def())
                 .subcommand(ValidatorSet::def())
+                .subcommand(VerifyArchivedProof::def())
         }
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             let eth_bridge_pool =
                 SubCmd::parse(matches).map(Self::EthBridgePool);
             let validator_set = SubCmd::parse(matches).map(Self::ValidatorSet);
-            eth_bridge_pool.or(validator_set)
+            let verify_archived_proof =
+                SubCmd::parse(matches).map(Self::VerifyArchivedProof);
+            eth_bridge_pool.or(validator_set).or(verify_archived_proof)
         }
     }
 
@@ -259,14 +263,19 @@ pub mod cmds {
                 .subcommand(QueryResult::def().display_order(5))
                 .subcommand(QueryRawBytes::def().display_order(5))
                 .subcommand(QueryProposal::def().display_order(5))
+                .subcommand(QueryProposalCode::def().display_order(5))
                 .subcommand(QueryProposalVotes::def().display_order(5))
                 .subcommand(QueryProposalResult::def().display_order(5))
+                .subcommand(QueryProposalExecution::def().display_order(5))
+                .subcommand(WatchProposal::def().display_order(5))
+                .subcommand(ExportProposalVotes::def().display_order(5))
                 .subcommand(QueryProtocolParameters::def().display_order(5))
                 .subcommand(QueryPgf::def().display_order(5))
                 .subcommand(QueryValidatorState::def().display_order(5))
                 .subcommand(QueryCommissionRate::def().display_order(5))
                 .subcommand(QueryRewards::def().display_order(5))
                 .subcommand(QueryMetaData::def().display_order(5))
+                .subcommand(ValidatorSetCheck::def().display_order(5))
                 // Actions
                 .subcommand(SignTx::def().display_order(6))
                 .subcommand(ShieldedSync::def().display_order(6))
@@ -337,10 +346,19 @@ pub mod cmds {
             let query_result = Self::parse_with_ctx(matches, QueryResult);
             let query_raw_bytes = Self::parse_with_ctx(matches, QueryRawBytes);
             let query_proposal = Self::parse_with_ctx(matches, QueryProposal);
+            let query_proposal_code =
+                Self::parse_with_ctx(matches, QueryProposalCode);
+            let validator_set_check =
+                Self::parse_with_ctx(matches, ValidatorSetCheck);
             let query_proposal_votes =
                 Self::parse_with_ctx(matches, QueryProposalVotes);
             let query_proposal_result =
                 Self::parse_with_ctx(matches, QueryProposalResult);
+            let query_proposal_execution =
+                Self::parse_with_ctx(matches, QueryProposalExecution);
+            let watch_proposal = Self::parse_with_ctx(matches, WatchProposal);
+            let export_proposal_votes =
+                Self::parse_with_ctx(matches, ExportProposalVotes);
             let query_protocol_parameters =
                 Self::parse_with_ctx(matches, QueryProtocolParameters);
             let query_pgf = Self::parse_with_ctx(matches, QueryPgf);
@@ -397,13 +415,18 @@ pub mod cmds {
                 .or(query_result)
                 .or(query_raw_bytes)
                 .or(query_proposal)
+                .or(query_proposal_code)
                 .or(query_proposal_votes)
                 .or(query_proposal_result)
+                .or(query_proposal_execution)
+                .or(watch_proposal)
+                .or(export_proposal_votes)
                 .or(query_protocol_parameters)
                 .or(query_pgf)
                 .or(query_validator_state)
                 .or(query_commission)
                 .or(query_metadata)
+                .or(validator_set_check)
                 .or(query_account)
                 .or(sign_tx)
                 .or(shielded_sync)
@@ -483,13 +506,18 @@ pub mod cmds {
         QueryBondedStake(QueryBondedStake),
         QueryCommissionRate(QueryCommissionRate),
         QueryMetaData(QueryMetaData),
+        ValidatorSetCheck(ValidatorSetCheck),
         QuerySlashes(QuerySlashes),
         QueryDelegations(QueryDelegations),
         QueryFindValidator(QueryFindValidator),
         QueryRawBytes(QueryRawBytes),
         QueryProposal(QueryProposal),
+        QueryProposalCode(QueryProposalCode),
         QueryProposalVotes(QueryProposalVotes),
         QueryProposalResult(QueryProposalResult),
+        QueryProposalExecution(QueryProposalExecution),
+        WatchProposal(WatchProposal),
+        ExportProposalVotes(ExportProposalVotes),
         QueryProtocolParameters(QueryProtocolParameters),
         QueryPgf(QueryPgf),
         QueryValidatorState(QueryValidatorState),
@@ -840,6 +868,8 @@ pub mod cmds {
         Reset(LedgerReset),
         DumpDb(LedgerDumpDb),
         RollBack(LedgerRollBack),
+        VerifyGovernance(LedgerVerifyGovernance),
+        ExportGovernance(LedgerExportGovernance),
     }
 
     impl SubCmd for Ledger {
@@ -852,10 +882,16 @@ pub mod cmds {
                 let dump_db = SubCmd::parse(matches).map(Self::DumpDb);
                 let rollback = SubCmd::parse(matches).map(Self::RollBack);
                 let run_until = SubCmd::parse(matches).map(Self::RunUntil);
+                let verify_governance =
+                    SubCmd::parse(matches).map(Self::VerifyGovernance);
+                let export_governance =
+                    SubCmd::parse(matches).map(Self::ExportGovernance);
                 run.or(reset)
                     .or(dump_db)
                     .or(rollback)
                     .or(run_until)
+                    .or(verify_governance)
+                    .or(export_governance)
                     // The `run` command is the default if no sub-command given
                     .or(Some(Self::Run(LedgerRun(args::LedgerRun {
                         start_time: None,
@@ -874,6 +910,8 @@ pub mod cmds {
                 .subcommand(LedgerReset::def())
                 .subcommand(LedgerDumpDb::def())
                 .subcommand(LedgerRollBack::def())
+                .subcommand(LedgerVerifyGovernance::def())
+                .subcommand(LedgerExportGovernance::def())
         }
     }
 
@@ -975,6 +1013,48 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct LedgerVerifyGovernance;
+
+    impl SubCmd for LedgerVerifyGovernance {
+        const CMD: &'static str = "verify-governance";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|_matches| Self)
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD).about(
+                "Check the consistency of the governance storage at the last \
+                 committed block. The storage is not modified.",
+            )
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct LedgerExportGovernance(pub args::LedgerExportGovernance);
+
+    impl SubCmd for LedgerExportGovernance {
+        const CMD: &'static str = "export-governance";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                Self(args::LedgerExportGovernance::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Export the governance storage at a committed block \
+                     height to newline-delimited JSON files, with a manifest \
+                     including the state root at that height. The storage is \
+                     not modified.",
+                )
+                .add_args::<args::LedgerExportGovernance>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub enum Config {
         Gen(ConfigGen),
@@ -1102,6 +1182,32 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct QueryProposalCode(pub args::QueryProposalCode<args::CliTypes>);
+
+    impl SubCmd for QueryProposalCode {
+        const CMD: &'static str = "query-proposal-code";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryProposalCode(args::QueryProposalCode::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Download the wasm code of a proposal, verified against \
+                     its hash.",
+                )
+                .arg_required_else_help(true)
+                .add_args::<args::QueryProposalCode<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryProposalResult(
         pub args::QueryProposalResult<args::CliTypes>,
@@ -1126,6 +1232,91 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct QueryProposalExecution(
+        pub args::QueryProposalExecution<args::CliTypes>,
+    );
+
+    impl SubCmd for QueryProposalExecution {
+        const CMD: &'static str = "query-proposal-execution";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryProposalExecution(args::QueryProposalExecution::parse(
+                    matches,
+                ))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the outcome of the execution of the code of a \
+                     passed proposal, including the verdicts of the VPs it \
+                     triggered.",
+                )
+                .arg_required_else_help(true)
+                .add_args::<args::QueryProposalExecution<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct WatchProposal(pub args::WatchProposal<args::CliTypes>);
+
+    impl SubCmd for WatchProposal {
+        const CMD: &'static str = "watch-proposal";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                WatchProposal(args::WatchProposal::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Wait for a proposal to conclude, then print its outcome \
+                     and where its funds went.",
+                )
+                .arg_required_else_help(true)
+                .add_args::<args::WatchProposal<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct ExportProposalVotes(
+        pub args::ExportProposalVotes<args::CliTypes>,
+    );
+
+    impl SubCmd for ExportProposalVotes {
+        const CMD: &'static str = "export-proposal-votes";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                ExportProposalVotes(args::ExportProposalVotes::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Export the votes cast on a proposal to a file, weighted \
+                     by the stake of their voters.",
+                )
+                .arg_required_else_help(true)
+                .add_args::<args::ExportProposalVotes<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryProtocolParameters(
         pub args::QueryProtocolParameters<args::CliTypes>,
@@ -1807,6 +1998,30 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct ValidatorSetCheck(pub args::ValidatorSetCheck<args::CliTypes>);
+
+    impl SubCmd for ValidatorSetCheck {
+        const CMD: &'static str = "validator-set-check";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                ValidatorSetCheck(args::ValidatorSetCheck::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Compare the validator set signed over in validator set \
+                     updates, as computed by the local node, against the one \
+                     computed by a remote node. Exits with an error if they \
+                     diverge.",
+                )
+                .add_args::<args::ValidatorSetCheck<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QuerySlashes(pub args::QuerySlashes<args::CliTypes>);
 
@@ -2164,6 +2379,7 @@ pub mod cmds {
         JoinNetwork(JoinNetwork),
         FetchWasms(FetchWasms),
         ValidateWasm(ValidateWasm),
+        DumpSchemas(DumpSchemas),
         InitNetwork(InitNetwork),
         DeriveGenesisAddresses(DeriveGenesisAddresses),
         GenesisBond(GenesisBond),
@@ -2187,6 +2403,8 @@ pub mod cmds {
                 let fetch_wasms = SubCmd::parse(matches).map(Self::FetchWasms);
                 let validate_wasm =
                     SubCmd::parse(matches).map(Self::ValidateWasm);
+                let dump_schemas =
+                    SubCmd::parse(matches).map(Self::DumpSchemas);
                 let init_network =
                     SubCmd::parse(matches).map(Self::InitNetwork);
                 let derive_addresses =
@@ -2211,6 +2429,7 @@ pub mod cmds {
                 join_network
                     .or(fetch_wasms)
                     .or(validate_wasm)
+                    .or(dump_schemas)
                     .or(init_network)
                     .or(derive_addresses)
                     .or(genesis_bond)
@@ -2231,6 +2450,7 @@ pub mod cmds {
                 .subcommand(JoinNetwork::def())
                 .subcommand(FetchWasms::def())
                 .subcommand(ValidateWasm::def())
+                .subcommand(DumpSchemas::def())
                 .subcommand(InitNetwork::def())
                 .subcommand(DeriveGenesisAddresses::def())
                 .subcommand(GenesisBond::def())
@@ -2307,6 +2527,28 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct DumpSchemas(pub args::DumpSchemas);
+
+    impl SubCmd for DumpSchemas {
+        const CMD: &'static str = "dump-schemas";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::DumpSchemas::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Write the Borsh schemas of the governance and vote \
+                     extension types to a JSON file, for code generation.",
+                )
+                .add_args::<args::DumpSchemas>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct InitNetwork(pub args::InitNetwork);
 
@@ -2878,6 +3120,29 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct VerifyArchivedProof(pub args::VerifyArchivedProof);
+
+    impl SubCmd for VerifyArchivedProof {
+        const CMD: &'static str = "verify-archived-proof";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::VerifyArchivedProof::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Verify the signatures of an Ethereum bridge proof \
+                     archived by a node, against the Ethereum addresses of \
+                     the validators recorded in it.",
+                )
+                .add_args::<args::VerifyArchivedProof>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct PkToTmAddress(pub args::PkToTmAddress);
 
@@ -2960,7 +3225,7 @@ pub mod args {
 
     use super::context::*;
     use super::utils::*;
-    use super::{ArgGroup, ArgMatches};
+    use super::{ArgAction, ArgGroup, ArgMatches};
     use crate::client::utils::PRE_GENESIS_DIR;
     use crate::config::genesis::GenesisAddress;
     use crate::config::{self, Action, ActionAtHeight};
@@ -3014,6 +3279,7 @@ pub mod args {
     pub const CHANNEL_ID: Arg<ChannelId> = arg("channel-id");
     pub const CODE_PATH: Arg<PathBuf> = arg("code-path");
     pub const CODE_PATH_OPT: ArgOpt<PathBuf> = CODE_PATH.opt();
+    pub const CODE_PATHS: ArgMulti<PathBuf, GlobStar> = arg_multi("code-path");
     pub const COMMISSION_RATE: Arg<Dec> = arg("commission-rate");
     pub const COMMISSION_RATE_OPT: ArgOpt<Dec> = COMMISSION_RATE.opt();
     pub const CONSENSUS_TIMEOUT_COMMIT: ArgDefault<Timeout> = arg_default(
@@ -3103,6 +3369,8 @@ pub mod args {
             Url::from_str(raw).unwrap()
         }));
     pub const LIST_FIND_ADDRESSES_ONLY: ArgFlag = flag("addr");
+    pub const LOCAL_LEDGER_ADDRESS: ArgDefaultFromCtx<ConfigRpcAddress> =
+        arg_default_from_ctx("local-node", DefaultFn(|| "".to_string()));
     pub const LIST_FIND_KEYS_ONLY: ArgFlag = flag("keys");
     pub const LOCALHOST: ArgFlag = flag("localhost");
     pub const MAX_COMMISSION_RATE_CHANGE: Arg<Dec> =
@@ -3115,6 +3383,7 @@ pub mod args {
     pub const NO_CONVERSIONS: ArgFlag = flag("no-conversions");
     pub const NUT: ArgFlag = flag("nut");
     pub const OUT_FILE_PATH_OPT: ArgOpt<PathBuf> = arg_opt("out-file-path");
+    pub const OUT_PATH: Arg<PathBuf> = arg("out");
     pub const OUTPUT: ArgOpt<PathBuf> = arg_opt("output");
     pub const OUTPUT_FOLDER_PATH: ArgOpt<PathBuf> =
         arg_opt("output-folder-path");
@@ -3206,6 +3475,8 @@ pub mod args {
         arg_opt("eth-hot-key");
     pub const VALUE: Arg<String> = arg("value");
     pub const VOTER_OPT: ArgOpt<WalletAddress> = arg_opt("voter");
+    pub const VOTE_EXPORT_FORMAT: ArgDefault<VoteExportFormat> =
+        arg_default("format", DefaultFn(|| VoteExportFormat::Csv));
     pub const VIEWING_KEY: Arg<WalletViewingKey> = arg("key");
     pub const VIEWING_KEYS: ArgMulti<WalletViewingKey, GlobStar> =
         arg_multi("viewing-keys");
@@ -3213,6 +3484,7 @@ pub mod args {
     pub const WALLET_ALIAS_FORCE: ArgFlag = flag("wallet-alias-force");
     pub const WASM_CHECKSUMS_PATH: Arg<PathBuf> = arg("wasm-checksums-path");
     pub const WASM_DIR: ArgOpt<PathBuf> = arg_opt("wasm-dir");
+    pub const WATCH_TIMEOUT: ArgOpt<Duration> = arg_opt("timeout");
     pub const WEBSITE_OPT: ArgOpt<String> = arg_opt("website");
     pub const TX_PATH: Arg<PathBuf> = arg("tx-path");
     pub const TX_PATH_OPT: ArgOpt<PathBuf> = TX_PATH.opt();
@@ -3376,6 +3648,34 @@ pub mod args {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct LedgerExportGovernance {
+        pub block_height: Option<BlockHeight>,
+        pub out_dir: PathBuf,
+    }
+
+    impl Args for LedgerExportGovernance {
+        fn parse(matches: &ArgMatches) -> Self {
+            let block_height = BLOCK_HEIGHT_OPT.parse(matches);
+            let out_dir = OUT_PATH.parse(matches);
+            Self {
+                block_height,
+                out_dir,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(BLOCK_HEIGHT_OPT.def().help(
+                "The block height to export. It must be retained by the \
+                 node's storage. Defaults to the latest committed block.",
+            ))
+            .arg(OUT_PATH.def().help(
+                "Path of the directory to write the export to. Existing \
+                 export files in the directory aren't overwritten.",
+            ))
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct UpdateLocalConfig {
         pub config_path: PathBuf,
@@ -4752,6 +5052,20 @@ pub mod args {
                 is_offline: self.is_offline,
                 is_pgf_stewards: self.is_pgf_stewards,
                 is_pgf_funding: self.is_pgf_funding,
+                code_chunks: self
+                    .code_chunks
+                    .iter()
+                    .map(|path| {
+                        std::fs::read(path).unwrap_or_else(|err| {
+                            eprintln!(
+                                "Couldn't read the proposal code file {}: {}",
+                                path.display(),
+                                err
+                            );
+                            safe_exit(1)
+                        })
+                    })
+                    .collect(),
                 tx_code_path: self.tx_code_path,
             }
         }
@@ -4764,6 +5078,7 @@ pub mod args {
             let is_offline = PROPOSAL_OFFLINE.parse(matches);
             let is_pgf_stewards = PROPOSAL_PGF_STEWARD.parse(matches);
             let is_pgf_funding = PROPOSAL_PGF_FUNDING.parse(matches);
+            let code_chunks = CODE_PATHS.parse(matches);
             let tx_code_path = PathBuf::from(TX_INIT_PROPOSAL);
 
             Self {
@@ -4773,6 +5088,7 @@ pub mod args {
                 is_offline,
                 is_pgf_stewards,
                 is_pgf_funding,
+                code_chunks,
             }
         }
 
@@ -4827,6 +5143,21 @@ pub mod args {
                             PROPOSAL_PGF_STEWARD.name,
                         ]),
                 )
+                .arg(
+                    CODE_PATHS
+                        .def()
+                        .action(ArgAction::Append)
+                        .help(
+                            "The paths to the wasm code chunks of a default \
+                             proposal, executed in order after its code. Can \
+                             be repeated.",
+                        )
+                        .conflicts_with_all([
+                            PROPOSAL_OFFLINE.name,
+                            PROPOSAL_PGF_FUNDING.name,
+                            PROPOSAL_PGF_STEWARD.name,
+                        ]),
+                )
         }
     }
 
@@ -4950,6 +5281,175 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<QueryProposalCode<SdkTypes>> for QueryProposalCode<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> QueryProposalCode<SdkTypes> {
+            QueryProposalCode::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                proposal_id: self.proposal_id,
+                output: self.output,
+            }
+        }
+    }
+
+    impl Args for QueryProposalCode<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let proposal_id = PROPOSAL_ID.parse(matches);
+            let output = OUT_PATH.parse(matches);
+
+            Self {
+                query,
+                proposal_id,
+                output,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(PROPOSAL_ID.def().help("The proposal identifier."))
+                .arg(
+                    OUT_PATH
+                        .def()
+                        .help("The file the proposal code is written to."),
+                )
+        }
+    }
+
+    impl CliToSdk<QueryProposalExecution<SdkTypes>>
+        for QueryProposalExecution<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> QueryProposalExecution<SdkTypes> {
+            QueryProposalExecution::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                proposal_id: self.proposal_id,
+            }
+        }
+    }
+
+    impl Args for QueryProposalExecution<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let proposal_id = PROPOSAL_ID.parse(matches);
+
+            Self { query, proposal_id }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(PROPOSAL_ID.def().help("The proposal identifier."))
+        }
+    }
+
+    impl CliToSdk<WatchProposal<SdkTypes>> for WatchProposal<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> WatchProposal<SdkTypes> {
+            WatchProposal::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                proposal_id: self.proposal_id,
+                timeout: self.timeout,
+            }
+        }
+    }
+
+    impl Args for WatchProposal<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let proposal_id = PROPOSAL_ID.parse(matches);
+            let timeout = WATCH_TIMEOUT.parse(matches).map(|dur| dur.0);
+
+            Self {
+                query,
+                proposal_id,
+                timeout,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(PROPOSAL_ID.def().help("The proposal identifier."))
+                .arg(WATCH_TIMEOUT.def().help(
+                    "How long to wait for the proposal to conclude, e.g. \
+                     \"2h\". Defaults to one day.",
+                ))
+        }
+    }
+
+    impl CliToSdk<ExportProposalVotes<SdkTypes>> for ExportProposalVotes<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> ExportProposalVotes<SdkTypes> {
+            ExportProposalVotes::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                proposal_id: self.proposal_id,
+                format: self.format,
+                output: self.output,
+            }
+        }
+    }
+
+    impl Args for ExportProposalVotes<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let proposal_id = PROPOSAL_ID.parse(matches);
+            let format = VOTE_EXPORT_FORMAT.parse(matches);
+            let output = OUT_PATH.parse(matches);
+
+            Self {
+                query,
+                proposal_id,
+                format,
+                output,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(PROPOSAL_ID.def().help("The proposal identifier."))
+                .arg(VOTE_EXPORT_FORMAT.def().help(
+                    "The format of the exported votes, either \"csv\" or \
+                     \"json\". Defaults to \"csv\".",
+                ))
+                .arg(OUT_PATH.def().help("The file the votes are written to."))
+        }
+    }
+
+    impl CliToSdk<ValidatorSetCheck<SdkTypes>> for ValidatorSetCheck<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> ValidatorSetCheck<SdkTypes> {
+            let query = self.query.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+            ValidatorSetCheck::<SdkTypes> {
+                query,
+                local_ledger_address: chain_ctx.get(&self.local_ledger_address),
+                epoch: self.epoch,
+            }
+        }
+    }
+
+    impl Args for ValidatorSetCheck<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let local_ledger_address = LOCAL_LEDGER_ADDRESS.parse(matches);
+            let epoch = EPOCH.parse(matches);
+
+            Self {
+                query,
+                local_ledger_address,
+                epoch,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(LOCAL_LEDGER_ADDRESS.def().help(
+                    "Address of the local ledger node, whose validator set is \
+                     checked against the one of the node given by `--node`. \
+                     Defaults to the RPC address of the local ledger \
+                     configuration.",
+                ))
+                .arg(EPOCH.def().help(
+                    "The epoch at which the validator set is signed over. \
+                     Defaults to the current epoch of the local node.",
+                ))
+        }
+    }
+
     impl CliToSdk<QueryProposalVotes<SdkTypes>> for QueryProposalVotes<CliTypes> {
         fn to_sdk(self, ctx: &mut Context) -> QueryProposalVotes<SdkTypes> {
             QueryProposalVotes::<SdkTypes> {
@@ -6091,7 +6591,16 @@ pub mod args {
                 signatures: self
                     .signatures
                     .iter()
-                    .map(|path| std::fs::read(path).unwrap())
+                    .map(|path| {
+                        std::fs::read(path).unwrap_or_else(|err| {
+                            eprintln!(
+                                "Couldn't read the signature file {}: {}",
+                                path.display(),
+                                err
+                            );
+                            safe_exit(1)
+                        })
+                    })
                     .collect(),
                 disposable_signing_key: self.disposable_signing_key,
                 tx_reveal_code_path: self.tx_reveal_code_path,
@@ -6851,6 +7360,25 @@ pub mod args {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct VerifyArchivedProof {
+        pub path: PathBuf,
+    }
+
+    impl Args for VerifyArchivedProof {
+        fn parse(matches: &ArgMatches) -> Self {
+            let path = PATH.parse(matches);
+            Self { path }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(
+                PATH.def()
+                    .help("The path of the file of the archived proof."),
+            )
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct DefaultBaseDir {}
 
@@ -6900,6 +7428,26 @@ pub mod args {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct DumpSchemas {
+        pub out_path: PathBuf,
+    }
+
+    impl Args for DumpSchemas {
+        fn parse(matches: &ArgMatches) -> Self {
+            let out_path = OUT_PATH.parse(matches);
+            Self { out_path }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(
+                OUT_PATH
+                    .def()
+                    .help("The path of the JSON file to write the schemas to."),
+            )
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct InitNetwork {
         pub templates_path: PathBuf,
@@ -7325,6 +7873,7 @@ pub enum NamadaRelayer {
     EthBridgePoolWithCtx(Box<(cmds::EthBridgePoolWithCtx, Context)>),
     EthBridgePoolWithoutCtx(cmds::EthBridgePoolWithoutCtx),
     ValidatorSet(cmds::ValidatorSet),
+    VerifyArchivedProof(args::VerifyArchivedProof),
 }
 
 pub fn namada_relayer_cli() -> Result<NamadaRelayer> {
@@ -7347,6 +7896,9 @@ pub fn namada_relayer_cli() -> Result<NamadaRelayer> {
             cmds::NamadaRelayer::ValidatorSet(sub_cmd) => {
                 Ok(NamadaRelayer::ValidatorSet(sub_cmd))
             }
+            cmds::NamadaRelayer::VerifyArchivedProof(
+                cmds::VerifyArchivedProof(args),
+            ) => Ok(NamadaRelayer::VerifyArchivedProof(args)),
         },
         None => {
             let mut app = app;
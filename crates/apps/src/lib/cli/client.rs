@@ -581,6 +581,25 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         rpc::query_and_print_metadata(&namada, args).await;
                     }
+                    Sub::ValidatorSetCheck(ValidatorSetCheck(args)) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.query.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        // the local node is deliberately not required to be
+                        // synced, since a stale local state is precisely
+                        // what this command should surface
+                        let local_client = C::from_tendermint_address(
+                            &args.local_ledger_address,
+                        );
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::check_validator_set(&namada, &local_client, args)
+                            .await;
+                    }
                     Sub::QuerySlashes(QuerySlashes(args)) => {
                         let chain_ctx = ctx.borrow_mut_chain_or_exit();
                         let ledger_address =
@@ -665,6 +684,18 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         rpc::query_proposal(&namada, args).await;
                     }
+                    Sub::QueryProposalCode(QueryProposalCode(args)) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.query.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_proposal_code(&namada, args).await;
+                    }
                     Sub::QueryProposalResult(QueryProposalResult(args)) => {
                         let chain_ctx = ctx.borrow_mut_chain_or_exit();
                         let ledger_address =
@@ -677,6 +708,44 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         rpc::query_proposal_result(&namada, args).await;
                     }
+                    Sub::QueryProposalExecution(QueryProposalExecution(
+                        args,
+                    )) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.query.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_proposal_execution(&namada, args).await;
+                    }
+                    Sub::WatchProposal(WatchProposal(args)) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.query.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::watch_proposal(&namada, args).await;
+                    }
+                    Sub::ExportProposalVotes(ExportProposalVotes(args)) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.query.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::export_proposal_votes(&namada, args).await;
+                    }
                     Sub::QueryProposalVotes(QueryProposalVotes(args)) => {
                         let chain_ctx = ctx.borrow_mut_chain_or_exit();
                         let ledger_address =
@@ -766,6 +835,9 @@ impl CliApi {
                 Utils::ValidateWasm(ValidateWasm(args)) => {
                     utils::validate_wasm(args)
                 }
+                Utils::DumpSchemas(DumpSchemas(args)) => {
+                    utils::dump_schemas(args)
+                }
                 Utils::InitNetwork(InitNetwork(args)) => {
                     utils::init_network(global_args, args)
                 }
@@ -1,12 +1,16 @@
-use color_eyre::eyre::Result;
+use color_eyre::eyre::{eyre, Result};
 use namada::types::io::Io;
 use namada_sdk::eth_bridge::{bridge_pool, validator_set};
+use namada_sdk::{display_line, edisplay_line};
 
 use crate::cli;
 use crate::cli::api::{CliApi, CliClient};
 use crate::cli::args::{CliToSdk, CliToSdkCtxless};
 use crate::cli::cmds::*;
 use crate::cli::utils::get_eth_rpc_client;
+use crate::node::ledger::shell::proof_archive::{
+    read_archived_proof, ArchivedProofKind,
+};
 
 impl CliApi {
     pub async fn handle_relayer_command<C>(
@@ -141,7 +145,59 @@ impl CliApi {
                     .await?;
                 }
             },
+            cli::NamadaRelayer::VerifyArchivedProof(args) => {
+                verify_archived_proof(&io, args)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Verify the signatures of the archived proof in the given file, against
+/// the Ethereum hot key addresses of the validators recorded in it.
+fn verify_archived_proof(
+    io: &impl Io,
+    args: cli::args::VerifyArchivedProof,
+) -> Result<()> {
+    let proof = read_archived_proof(&args.path).map_err(|err| {
+        eyre!(
+            "Failed to read the archived proof at {}: {err}",
+            args.path.display()
+        )
+    })?;
+    match &proof.kind {
+        ArchivedProofKind::ValidatorSetUpdate { epoch, .. } => display_line!(
+            io,
+            "Proof of the validator set update to epoch {epoch}, archived at \
+             height {} of chain {}.",
+            proof.height,
+            proof.chain_id
+        ),
+        ArchivedProofKind::BridgePoolRoot { root, nonce, .. } => {
+            display_line!(
+                io,
+                "Proof of the Bridge pool root {root} with nonce {nonce}, \
+                 archived at height {} of chain {}.",
+                proof.height,
+                proof.chain_id
+            )
         }
+    }
+    display_line!(io, "Contributing validators: {}", proof.validators.len());
+    let verification = proof.verify();
+    display_line!(io, "Valid signatures: {}", verification.valid.len());
+    for addr_book in &verification.invalid {
+        edisplay_line!(
+            io,
+            "Invalid signature of the validator with Ethereum hot key address \
+             {}",
+            addr_book.hot_key_addr
+        );
+    }
+    if verification.is_valid() {
+        display_line!(io, "The archived proof is valid.");
         Ok(())
+    } else {
+        Err(eyre!("The archived proof is invalid."))
     }
 }
@@ -6,6 +6,7 @@ use std::fs::{self, read_dir};
 use std::io;
 use std::iter::Iterator;
 use std::str::FromStr;
+use std::time::Duration;
 
 use borsh::BorshDeserialize;
 use data_encoding::HEXLOWER;
@@ -27,9 +28,11 @@ use namada::governance::storage::proposal::{
     StoragePgfFunding, StorageProposal,
 };
 use namada::governance::utils::{
-    compute_proposal_result, ProposalVotes, TallyType, TallyVote, VotePower,
+    compute_proposal_result, ProposalVotes, TallyResult, TallyType, TallyVote,
+    VotePower,
 };
 use namada::ledger::events::Event;
+use namada::ledger::gas::Gas;
 use namada::ledger::ibc::storage::{
     ibc_denom_key, ibc_denom_key_prefix, is_ibc_denom_key,
 };
@@ -898,6 +901,227 @@ pub async fn query_proposal(context: &impl Namada, args: args::QueryProposal) {
     }
 }
 
+/// Query the wasm code of a proposal and write it to a file
+pub async fn query_proposal_code(
+    context: &impl Namada,
+    args: args::QueryProposalCode,
+) {
+    let code = match namada_sdk::rpc::query_proposal_code(
+        context.client(),
+        args.proposal_id,
+    )
+    .await
+    {
+        Ok(Some(code)) => code,
+        Ok(None) => {
+            edisplay_line!(
+                context.io(),
+                "Proposal {} has no wasm code.",
+                args.proposal_id
+            );
+            cli::safe_exit(1)
+        }
+        Err(err) => {
+            edisplay_line!(
+                context.io(),
+                "Failed to query the code of proposal {}: {}",
+                args.proposal_id,
+                err
+            );
+            cli::safe_exit(1)
+        }
+    };
+    if let Err(err) = std::fs::write(&args.output, &code) {
+        edisplay_line!(
+            context.io(),
+            "Failed to write the proposal code to {}: {}",
+            args.output.to_string_lossy(),
+            err
+        );
+        cli::safe_exit(1)
+    }
+    display_line!(
+        context.io(),
+        "Wrote {} bytes of verified code with hash {} to {}.",
+        code.len(),
+        Hash::sha256(&code),
+        args.output.to_string_lossy()
+    );
+}
+
+/// Query the outcome of the execution of the code of a proposal
+pub async fn query_proposal_execution(
+    context: &impl Namada,
+    args: args::QueryProposalExecution,
+) {
+    match namada_sdk::rpc::query_proposal_execution_result(
+        context.client(),
+        args.proposal_id,
+    )
+    .await
+    {
+        Ok(Some(execution_result)) => {
+            display_line!(
+                context.io(),
+                "Proposal Id: {}\nGas used: {}\n{}",
+                args.proposal_id,
+                Gas::from(execution_result.gas_used),
+                execution_result
+            );
+        }
+        Ok(None) => {
+            display_line!(
+                context.io(),
+                "The code of proposal {} wasn't executed.",
+                args.proposal_id
+            );
+        }
+        Err(err) => {
+            edisplay_line!(
+                context.io(),
+                "Failed to query the execution of proposal {}: {}",
+                args.proposal_id,
+                err
+            );
+            cli::safe_exit(1)
+        }
+    }
+}
+
+/// Write the weighted votes cast on a proposal to a file
+pub async fn export_proposal_votes(
+    context: &impl Namada,
+    args: args::ExportProposalVotes,
+) {
+    let file = match fs::File::create(&args.output) {
+        Ok(file) => file,
+        Err(err) => {
+            edisplay_line!(
+                context.io(),
+                "Failed to create {}: {}",
+                args.output.to_string_lossy(),
+                err
+            );
+            cli::safe_exit(1)
+        }
+    };
+    match namada_sdk::rpc::export_proposal_votes(
+        context.client(),
+        args.proposal_id,
+        args.format,
+        io::BufWriter::new(file),
+    )
+    .await
+    {
+        Ok(count) => display_line!(
+            context.io(),
+            "Wrote {} votes of proposal {} to {}.",
+            count,
+            args.proposal_id,
+            args.output.to_string_lossy()
+        ),
+        Err(err) => {
+            edisplay_line!(
+                context.io(),
+                "Failed to export the votes of proposal {}: {}",
+                args.proposal_id,
+                err
+            );
+            cli::safe_exit(1)
+        }
+    }
+}
+
+/// Wait for a proposal to conclude, then print its outcome and where its
+/// funds went
+pub async fn watch_proposal(context: &impl Namada, args: args::WatchProposal) {
+    // Proposals usually conclude within a few epochs
+    const DEFAULT_TIMEOUT: Duration = Duration::from_secs(24 * 60 * 60);
+
+    display_line!(
+        context.io(),
+        "Waiting for proposal {} to conclude...",
+        args.proposal_id
+    );
+    let outcome = match namada_sdk::rpc::wait_proposal_concluded(
+        context.client(),
+        args.proposal_id,
+        args.timeout.unwrap_or(DEFAULT_TIMEOUT),
+    )
+    .await
+    {
+        Ok(outcome) => outcome,
+        Err(err) => {
+            edisplay_line!(
+                context.io(),
+                "Failed to wait for proposal {} to conclude: {}",
+                args.proposal_id,
+                err
+            );
+            cli::safe_exit(1)
+        }
+    };
+
+    display_line!(context.io(), "Proposal Id: {}", outcome.id);
+    display_line!(context.io(), "Tally result: {}", outcome.tally);
+    if let Some(basis_points) = outcome.basis_points {
+        display_line!(context.io(), "Votes cast: {}", basis_points);
+    }
+    if outcome.expired {
+        display_line!(context.io(), "Rejected as it expired.");
+    }
+    if let Some(field) = &outcome.malformed {
+        display_line!(context.io(), "Rejected as its {} is malformed.", field);
+    }
+    if let Some(id) = outcome.conflicts_with {
+        display_line!(
+            context.io(),
+            "Rejected as it conflicts with proposal {}.",
+            id
+        );
+    }
+    if outcome.tally == TallyResult::Passed {
+        display_line!(
+            context.io(),
+            "Executed: {}",
+            if outcome.executed { "yes" } else { "no" }
+        );
+    }
+    if let Some(funds) = &outcome.funds {
+        display_line!(
+            context.io(),
+            "Funds returned to {}: {}\nFunds burned: {}\nFunds moved to the \
+             treasury: {}",
+            funds.author,
+            funds.returned.to_string_native(),
+            funds.burned.to_string_native(),
+            funds.to_treasury.to_string_native()
+        );
+    }
+}
+
+/// Check the validator set signed over by the local node against the one
+/// computed by a remote node, exiting with an error on divergence.
+pub async fn check_validator_set(
+    context: &impl Namada,
+    local_client: &(impl Client + Sync),
+    args: args::ValidatorSetCheck,
+) {
+    let divergences =
+        namada_sdk::eth_bridge::validator_set::check_validator_set(
+            local_client,
+            context.client(),
+            context.io(),
+            args.epoch,
+        )
+        .await;
+    match divergences {
+        Ok(divergences) if divergences.is_empty() => {}
+        // the divergences or the error have already been displayed
+        _ => cli::safe_exit(1),
+    }
+}
+
 /// Query proposal by Id
 pub async fn query_proposal_by_id<C: namada::ledger::queries::Client + Sync>(
     client: &C,
@@ -1492,6 +1716,167 @@ pub async fn query_protocol_parameters(
         "",
         governance_parameters.min_proposal_grace_epochs
     );
+    display_line!(
+        context.io(),
+        "{:4}Max. proposal lifetime: {}",
+        "",
+        governance_parameters.max_proposal_lifetime
+    );
+    display_line!(
+        context.io(),
+        "{:4}Proposal base deposit: {}",
+        "",
+        governance_parameters.base_deposit.to_string_native()
+    );
+    display_line!(
+        context.io(),
+        "{:4}Proposal deposit per byte: {}",
+        "",
+        governance_parameters.deposit_per_byte.to_string_native()
+    );
+    display_line!(
+        context.io(),
+        "{:4}Max. voting power fraction per voter: {}",
+        "",
+        governance_parameters
+            .max_voting_power_fraction
+            .map(|fraction| fraction.to_string())
+            .unwrap_or_else(|| "none".to_string())
+    );
+    display_line!(
+        context.io(),
+        "{:4}Treasury high-water mark: {}",
+        "",
+        governance_parameters
+            .treasury_high_water_mark
+            .map(|mark| mark.to_string_native())
+            .unwrap_or_else(|| "none".to_string())
+    );
+    display_line!(
+        context.io(),
+        "{:4}Min. proposal author stake: {}",
+        "",
+        governance_parameters
+            .min_proposal_author_stake
+            .to_string_native()
+    );
+    display_line!(
+        context.io(),
+        "{:4}Treasury history retention: {} epochs",
+        "",
+        governance_parameters.treasury_history_retention
+    );
+    display_line!(
+        context.io(),
+        "{:4}Treasury inflation rate: {} per epoch",
+        "",
+        governance_parameters.treasury_inflation_rate
+    );
+    display_line!(
+        context.io(),
+        "{:4}Min. proposal voting period: {} blocks",
+        "",
+        governance_parameters.min_proposal_voting_period_blocks
+    );
+    display_line!(
+        context.io(),
+        "{:4}Max. proposal voting period: {} blocks",
+        "",
+        governance_parameters.max_proposal_voting_period_blocks
+    );
+    display_line!(
+        context.io(),
+        "{:4}Emergency validator quorum: {}",
+        "",
+        governance_parameters
+            .emergency_validator_quorum
+            .map(|quorum| format!("{quorum} validators"))
+            .unwrap_or_else(|| "none".to_string())
+    );
+    display_line!(
+        context.io(),
+        "{:4}Max. proposals concluded per block: {}",
+        "",
+        governance_parameters
+            .max_proposals_per_block
+            .map(|max| max.to_string())
+            .unwrap_or_else(|| "none".to_string())
+    );
+    display_line!(
+        context.io(),
+        "{:4}Parameter conflict policy: {}",
+        "",
+        governance_parameters.parameter_conflict_policy
+    );
+    display_line!(
+        context.io(),
+        "{:4}Strict proposal code validation: {}",
+        "",
+        governance_parameters.strict_proposal_code_validation
+    );
+    display_line!(
+        context.io(),
+        "{:4}Rejected proposals refund ratio: {}",
+        "",
+        governance_parameters.rejected_refund_ratio
+    );
+    for (kind, ratio) in &governance_parameters.rejected_refund_ratios {
+        display_line!(
+            context.io(),
+            "{:4}Rejected {} proposals refund ratio: {}",
+            "",
+            kind,
+            ratio
+        );
+    }
+    match &governance_parameters.council {
+        Some(council) => {
+            display_line!(
+                context.io(),
+                "{:4}Council threshold: {} of {} members weighing {}",
+                "",
+                council.threshold,
+                council.members.len(),
+                council
+                    .total_weight()
+                    .map(|total_weight| total_weight.to_string())
+                    .unwrap_or_else(|| "over the maximum".to_string())
+            );
+            for member in &council.members {
+                display_line!(
+                    context.io(),
+                    "{:6}Council member: {} (weight {})",
+                    "",
+                    member,
+                    council.weight(member)
+                );
+            }
+        }
+        None => display_line!(context.io(), "{:4}Council: none", ""),
+    }
+    display_line!(
+        context.io(),
+        "{:4}Max. treasury balance: {}",
+        "",
+        governance_parameters
+            .max_treasury_balance
+            .map(|max| max.to_string_native())
+            .unwrap_or_else(|| "none".to_string())
+    );
+    let tie_breaks = governance_parameters.tie_breaks;
+    display_line!(context.io(), "{:4}Tie-breaks:", "");
+    display_line!(
+        context.io(),
+        "{:6}One half over one third: {}",
+        "",
+        tie_breaks.one_half_over_one_third
+    );
+    display_line!(
+        context.io(),
+        "{:6}Less one half over one third nay: {}",
+        "",
+        tie_breaks.less_one_half_over_one_third_nay
+    );
 
     let pgf_parameters = query_pgf_parameters(context.client()).await;
     display_line!(context.io(), "\nPublic Goods Funding Parameters");
@@ -2944,6 +3329,7 @@ pub async fn compute_offline_proposal_votes(
         validator_voting_power,
         delegators_vote,
         delegator_voting_power,
+        governors: HashMap::default(),
     }
 }
 
@@ -3003,5 +3389,6 @@ pub async fn compute_proposal_votes<
         validator_voting_power,
         delegators_vote,
         delegator_voting_power,
+        governors: HashMap::default(),
     }
 }
@@ -1080,6 +1080,15 @@ where
                     error::TxSubmitError::InvalidProposal(e.to_string())
                 })?;
 
+        display_line!(
+            namada.io(),
+            "The proposal will lock a deposit of {} native tokens.",
+            proposal
+                .proposal
+                .required_deposit(&governance_parameters, 0)
+                .to_string_native()
+        );
+
         submit_reveal_aux(namada, args.tx.clone(), &proposal.proposal.author)
             .await?;
 
@@ -1110,17 +1119,31 @@ where
                 error::TxSubmitError::InvalidProposal(e.to_string())
             })?;
 
+        display_line!(
+            namada.io(),
+            "The proposal will lock a deposit of {} native tokens.",
+            proposal
+                .proposal
+                .required_deposit(&governance_parameters, 0)
+                .to_string_native()
+        );
+
         submit_reveal_aux(namada, args.tx.clone(), &proposal.proposal.author)
             .await?;
 
         tx::build_pgf_stewards_proposal(namada, &args, proposal).await?
     } else {
-        let proposal = DefaultProposal::try_from(args.proposal_data.as_ref())
-            .map_err(|e| {
+        let mut proposal = DefaultProposal::try_from(
+            args.proposal_data.as_ref(),
+        )
+        .map_err(|e| {
             error::TxSubmitError::FailedGovernaneProposalDeserialize(
                 e.to_string(),
             )
         })?;
+        proposal
+            .code_chunks
+            .extend(args.code_chunks.iter().cloned());
         let author_balane = rpc::get_token_balance(
             namada.client(),
             &namada.native_token(),
@@ -1138,6 +1161,14 @@ where
                 error::TxSubmitError::InvalidProposal(e.to_string())
             })?;
 
+        display_line!(
+            namada.io(),
+            "The proposal will lock a deposit of {} native tokens.",
+            proposal
+                .required_deposit(&governance_parameters)
+                .to_string_native()
+        );
+
         submit_reveal_aux(namada, args.tx.clone(), &proposal.proposal.author)
             .await?;
 
@@ -1149,7 +1180,16 @@ where
     } else {
         sign(namada, &mut tx_builder, &args.tx, signing_data).await?;
 
-        namada.submit(tx_builder, &args.tx).await?;
+        let result = namada.submit(tx_builder, &args.tx).await?;
+        if let ProcessTxResponse::Applied(resp) = result {
+            for proposal_id in &resp.proposal_ids {
+                display_line!(
+                    namada.io(),
+                    "Proposal initialized with id {}.",
+                    proposal_id
+                );
+            }
+        }
     }
 
     Ok(())
@@ -4,12 +4,22 @@ use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
+use borsh::schema::{Definition, Fields};
+use borsh::schema_container_of;
 use borsh_ext::BorshSerializeExt;
 use color_eyre::owo_colors::OwoColorize;
 use flate2::read::GzDecoder;
 use flate2::write::GzEncoder;
 use flate2::Compression;
 use itertools::Either;
+use namada::governance::storage::proposal::{
+    InitProposalData, StorageProposal, VoteProposalData,
+};
+use namada::governance::storage::vote::{ProposalVote, VoteReceipt};
+use namada::governance::utils::{
+    ArchivedProposalResult, ProposalResult, TallyResult, TallyType, Vote,
+};
+use namada::ledger::governance::utils::ProposalEvent;
 use namada::types::chain::ChainId;
 use namada::types::dec::Dec;
 use namada::types::key::*;
@@ -17,6 +27,10 @@ use namada::types::string_encoding::StringEncoded;
 use namada::types::token;
 use namada::types::uint::Uint;
 use namada::vm::validate_untrusted_wasm;
+use namada::vote_ext::{
+    bridge_pool_roots, ethereum_events, validator_set_update, EthereumTxData,
+    VoteExtension,
+};
 use namada_sdk::wallet::{alias, Wallet};
 use prost::bytes::Bytes;
 use serde_json::json;
@@ -368,6 +382,131 @@ pub fn validate_wasm(args::ValidateWasm { code_path }: args::ValidateWasm) {
     }
 }
 
+/// Write the Borsh schemas of the governance and vote extension types to a
+/// JSON file. The file lists the declarations of the dumped types under
+/// `types`, and the definitions of these types and of all the types they
+/// depend on under `definitions`.
+pub fn dump_schemas(args::DumpSchemas { out_path }: args::DumpSchemas) {
+    let containers = [
+        schema_container_of::<InitProposalData>(),
+        schema_container_of::<VoteProposalData>(),
+        schema_container_of::<StorageProposal>(),
+        schema_container_of::<ProposalVote>(),
+        schema_container_of::<VoteReceipt>(),
+        schema_container_of::<Vote>(),
+        schema_container_of::<TallyType>(),
+        schema_container_of::<TallyResult>(),
+        schema_container_of::<ProposalResult>(),
+        schema_container_of::<ArchivedProposalResult>(),
+        schema_container_of::<ProposalEvent>(),
+        schema_container_of::<VoteExtension>(),
+        schema_container_of::<EthereumTxData>(),
+        schema_container_of::<ethereum_events::SignedVext>(),
+        schema_container_of::<ethereum_events::VextDigest>(),
+        schema_container_of::<bridge_pool_roots::MultiSignedVext>(),
+        schema_container_of::<validator_set_update::VextDigest>(),
+    ];
+    let mut types = vec![];
+    let mut definitions = serde_json::Map::new();
+    for container in &containers {
+        types.push(container.declaration().clone());
+        for (declaration, definition) in container.definitions() {
+            definitions.insert(
+                declaration.clone(),
+                schema_definition_json(definition),
+            );
+        }
+    }
+    let schemas = json!({
+        "types": types,
+        "definitions": definitions,
+    });
+    let contents = serde_json::to_vec_pretty(&schemas)
+        .expect("Borsh schemas should be serializable to JSON");
+    match fs::write(&out_path, contents) {
+        Ok(()) => println!(
+            "Wrote the Borsh schemas of {} types to {}",
+            types.len(),
+            out_path.to_string_lossy()
+        ),
+        Err(err) => {
+            eprintln!(
+                "Failed to write the Borsh schemas to {}: {err}",
+                out_path.to_string_lossy()
+            );
+            safe_exit(1)
+        }
+    }
+}
+
+/// Convert a Borsh schema definition to JSON
+fn schema_definition_json(definition: &Definition) -> serde_json::Value {
+    match definition {
+        Definition::Primitive(size) => json!({
+            "kind": "primitive",
+            "size": size,
+        }),
+        Definition::Sequence {
+            length_width,
+            length_range,
+            elements,
+        } => json!({
+            "kind": "sequence",
+            "length_width": length_width,
+            "length_range": [length_range.start(), length_range.end()],
+            "elements": elements,
+        }),
+        Definition::Tuple { elements } => json!({
+            "kind": "tuple",
+            "elements": elements,
+        }),
+        Definition::Enum {
+            tag_width,
+            variants,
+        } => {
+            let variants: Vec<_> = variants
+                .iter()
+                .map(|(discriminant, name, declaration)| {
+                    json!({
+                        "discriminant": discriminant,
+                        "name": name,
+                        "type": declaration,
+                    })
+                })
+                .collect();
+            json!({
+                "kind": "enum",
+                "tag_width": tag_width,
+                "variants": variants,
+            })
+        }
+        Definition::Struct { fields } => match fields {
+            Fields::NamedFields(fields) => {
+                let fields: Vec<_> = fields
+                    .iter()
+                    .map(|(name, declaration)| {
+                        json!({
+                            "name": name,
+                            "type": declaration,
+                        })
+                    })
+                    .collect();
+                json!({
+                    "kind": "struct",
+                    "fields": fields,
+                })
+            }
+            Fields::UnnamedFields(fields) => json!({
+                "kind": "tuple_struct",
+                "fields": fields,
+            }),
+            Fields::Empty => json!({
+                "kind": "unit_struct",
+            }),
+        },
+    }
+}
+
 /// Length of a Tendermint Node ID in bytes
 const TENDERMINT_NODE_ID_LENGTH: usize = 20;
 
@@ -0,0 +1,54 @@
+//! Runtime configuration of the storage change feed, which publishes the
+//! changes to a set of watched storage keys as events.
+use namada::types::address::{Address, GOV, MULTITOKEN};
+use namada::types::storage::{DbKeySeg, KeySeg};
+use serde::{Deserialize, Serialize};
+
+/// The default maximum number of storage changes published per block.
+pub const DEFAULT_MAX_CHANGES_PER_BLOCK: usize = 1000;
+
+/// The segment of a watched key prefix matching any key segment.
+pub const WILDCARD_SEGMENT: &str = "*";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Config {
+    /// The prefixes of the storage keys whose changes are published. The
+    /// segments of a prefix are separated by `/`, and a `*` segment
+    /// matches any key segment. The governance storage, and the balances
+    /// of the governance and treasury accounts, are watched by default.
+    pub watched_key_prefixes: Vec<String>,
+    /// The maximum number of storage changes published per block. Any
+    /// change beyond this limit is dropped and counted, such that a block
+    /// writing many watched keys can't flood subscribers.
+    pub max_changes_per_block: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let treasury = namada::governance::pgf::ADDRESS;
+        Self {
+            watched_key_prefixes: vec![
+                raw_key_seg(&GOV),
+                balance_of_any_token(&GOV),
+                balance_of_any_token(&treasury),
+            ],
+            max_changes_per_block: DEFAULT_MAX_CHANGES_PER_BLOCK,
+        }
+    }
+}
+
+/// The raw key segment of an address.
+fn raw_key_seg(address: &Address) -> String {
+    DbKeySeg::AddressSeg(address.clone()).raw()
+}
+
+/// The prefix of the balance keys of the given owner, in any token.
+fn balance_of_any_token(owner: &Address) -> String {
+    [
+        raw_key_seg(&MULTITOKEN),
+        WILDCARD_SEGMENT.to_owned(),
+        namada::token::storage_key::BALANCE_STORAGE_KEY.to_owned(),
+        raw_key_seg(owner),
+    ]
+    .join("/")
+}
@@ -1,4 +1,6 @@
 //! Runtime configuration for a validator node.
+use std::path::PathBuf;
+
 #[allow(unused_imports)]
 use namada::types::ethereum_events::EthereumEvent;
 use serde::{Deserialize, Serialize};
@@ -10,6 +12,25 @@ pub const DEFAULT_ORACLE_RPC_ENDPOINT: &str = "http://127.0.0.1:8545";
 /// the oracle and the shell can hold.
 pub const ORACLE_CHANNEL_BUFFER_SIZE: usize = 1000;
 
+/// The default timeout of the requests to a remote Ethereum signer, in
+/// milliseconds.
+pub const DEFAULT_ETH_SIGNER_TIMEOUT_MS: u64 = 1000;
+
+/// The signer of the validator set update vote extensions.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum EthSigner {
+    /// Sign with the Ethereum hot key found in the wallet of the validator.
+    #[default]
+    Local,
+    /// Request the signatures from the service listening on the Unix socket
+    /// specified in the `eth_signer_socket` setting, e.g. the front of an
+    /// HSM holding the Ethereum hot key.
+    Remote,
+}
+
 /// The mode in which to run the Ethereum bridge.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Mode {
@@ -37,6 +58,33 @@ pub struct Config {
     /// ledger subprocesses. This is the number of Ethereum events that
     /// can be held in the channel. The default is 1000.
     pub channel_buffer_size: usize,
+    /// Whether to serve previews of the validator set updates of the
+    /// bridge, for testing purposes. Disabled by default.
+    #[serde(default)]
+    pub enable_valset_preview: bool,
+    /// The signer of the validator set update vote extensions of this
+    /// validator. Defaults to signing locally.
+    #[serde(default)]
+    pub eth_signer: EthSigner,
+    /// The Unix socket of the remote Ethereum signer, required when
+    /// `eth_signer` is `"remote"`.
+    #[serde(default)]
+    pub eth_signer_socket: Option<PathBuf>,
+    /// The time to wait for a signature from the remote Ethereum signer,
+    /// in milliseconds, past which the validator set update vote extension
+    /// is skipped.
+    #[serde(default = "default_eth_signer_timeout_ms")]
+    pub eth_signer_timeout_ms: u64,
+    /// The Unix socket of the remote signer holding the Ethereum cold key
+    /// of this validator, with which it signs off on the updates of the
+    /// bridge contracts scheduled by governance. Without it, the validator
+    /// doesn't vote on these updates.
+    #[serde(default)]
+    pub eth_cold_signer_socket: Option<PathBuf>,
+}
+
+fn default_eth_signer_timeout_ms() -> u64 {
+    DEFAULT_ETH_SIGNER_TIMEOUT_MS
 }
 
 impl Default for Config {
@@ -45,6 +93,11 @@ impl Default for Config {
             mode: Mode::RemoteEndpoint,
             oracle_rpc_endpoint: DEFAULT_ORACLE_RPC_ENDPOINT.to_owned(),
             channel_buffer_size: ORACLE_CHANNEL_BUFFER_SIZE,
+            eth_cold_signer_socket: None,
+            enable_valset_preview: false,
+            eth_signer: EthSigner::Local,
+            eth_signer_socket: None,
+            eth_signer_timeout_ms: DEFAULT_ETH_SIGNER_TIMEOUT_MS,
         }
     }
 }
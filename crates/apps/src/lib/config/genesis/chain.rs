@@ -13,7 +13,7 @@ use namada::types::dec::Dec;
 use namada::types::hash::Hash;
 use namada::types::key::{common, RefTo};
 use namada::types::time::{DateTimeUtc, DurationNanos, Rfc3339String};
-use namada::types::token::Amount;
+use namada::types::token::{Amount, NATIVE_MAX_DECIMAL_PLACES};
 use namada_sdk::types::address::InternalAddress;
 use namada_sdk::wallet::store::AddressVpType;
 use namada_sdk::wallet::{pre_genesis, Wallet};
@@ -403,6 +403,25 @@ impl Finalized {
             max_proposal_period,
             max_proposal_content_size,
             min_proposal_grace_epochs,
+            max_proposal_lifetime,
+            base_deposit,
+            deposit_per_byte,
+            max_voting_power_fraction,
+            treasury_high_water_mark,
+            min_proposal_author_stake,
+            treasury_history_retention,
+            treasury_inflation_rate,
+            min_proposal_voting_period_blocks,
+            max_proposal_voting_period_blocks,
+            emergency_validator_quorum,
+            max_proposals_per_block,
+            parameter_conflict_policy,
+            strict_proposal_code_validation,
+            rejected_refund_ratio,
+            rejected_refund_ratios,
+            council,
+            max_treasury_balance,
+            tie_breaks,
         } = self.parameters.gov_params.clone();
         namada::governance::parameters::GovernanceParameters {
             min_proposal_fund: Amount::native_whole(min_proposal_fund),
@@ -411,6 +430,31 @@ impl Finalized {
             max_proposal_content_size,
             min_proposal_grace_epochs,
             min_proposal_voting_period,
+            max_proposal_lifetime,
+            base_deposit: Amount::native_whole(base_deposit),
+            deposit_per_byte: deposit_per_byte
+                .scale(NATIVE_MAX_DECIMAL_PLACES)
+                .expect("The proposal deposit per byte must fit an amount"),
+            max_voting_power_fraction,
+            treasury_high_water_mark: treasury_high_water_mark
+                .map(Amount::native_whole),
+            min_proposal_author_stake: Amount::native_whole(
+                min_proposal_author_stake,
+            ),
+            treasury_history_retention,
+            treasury_inflation_rate,
+            min_proposal_voting_period_blocks,
+            max_proposal_voting_period_blocks,
+            emergency_validator_quorum,
+            max_proposals_per_block,
+            parameter_conflict_policy,
+            strict_proposal_code_validation,
+            rejected_refund_ratio,
+            rejected_refund_ratios,
+            council,
+            max_treasury_balance: max_treasury_balance
+                .map(Amount::native_whole),
+            tie_breaks,
         }
     }
 
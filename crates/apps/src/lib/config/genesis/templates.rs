@@ -8,6 +8,9 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use namada::eth_bridge::storage::parameters::{
     Contracts, Erc20WhitelistEntry, MinimumConfirmations,
 };
+use namada::governance::parameters::{
+    Council, ParameterConflictPolicy, TieBreaks,
+};
 use namada::types::address::Address;
 use namada::types::chain::ProposalBytes;
 use namada::types::dec::Dec;
@@ -433,6 +436,73 @@ pub struct GovernanceParams {
     pub max_proposal_content_size: u64,
     /// Minimum number of epoch between end and grace epoch
     pub min_proposal_grace_epochs: u64,
+    /// Maximum number of epochs a proposal can stay undecided
+    pub max_proposal_lifetime: u64,
+    /// Deposit in whole native tokens locked by every proposal
+    pub base_deposit: u64,
+    /// Deposit in native tokens locked for each byte of a proposal
+    pub deposit_per_byte: token::DenominatedAmount,
+    /// Maximum fraction of the total voting power counted for any single
+    /// voter in a tally. When not set, voting power is not capped
+    pub max_voting_power_fraction: Option<Dec>,
+    /// Treasury balance, in whole native tokens, above which a warning event
+    /// is emitted when proposal funds are moved to the treasury. When not
+    /// set, no warning is emitted
+    pub treasury_high_water_mark: Option<u64>,
+    /// Minimum amount of whole native tokens that the author of a proposal
+    /// must have bonded to be allowed to submit it
+    pub min_proposal_author_stake: u64,
+    /// Number of epochs after which the snapshots of the treasury balance
+    /// are pruned from storage
+    pub treasury_history_retention: u64,
+    /// Fraction of the total native token supply minted to the treasury at
+    /// each epoch
+    pub treasury_inflation_rate: Dec,
+    /// Minimum number of blocks of the voting period of proposals voted on
+    /// over a range of block heights
+    pub min_proposal_voting_period_blocks: u64,
+    /// Maximum number of blocks of the voting period of proposals voted on
+    /// over a range of block heights
+    pub max_proposal_voting_period_blocks: u64,
+    /// Minimum number of distinct validators voting yay for emergency
+    /// proposals to pass. When not set, they are tallied by voting power
+    pub emergency_validator_quorum: Option<u64>,
+    /// Maximum number of proposals concluded in a single block, the ones
+    /// due in excess being deferred to the next blocks. When not set, all
+    /// the due proposals are concluded at once
+    pub max_proposals_per_block: Option<u64>,
+    /// How to handle proposals setting a protocol parameter already set by
+    /// another proposal concluded in the same epoch. When not set, both
+    /// proposals are applied in execution order
+    #[serde(default)]
+    pub parameter_conflict_policy: ParameterConflictPolicy,
+    /// Whether the code of a proposal must be a well-formed wasm module at
+    /// submission. When not set, malformed code only fails once executed
+    #[serde(default)]
+    pub strict_proposal_code_validation: bool,
+    /// Fraction of the funds of a rejected proposal returned to its author,
+    /// the rest being burned. When not set, the funds are fully burned
+    #[serde(default)]
+    pub rejected_refund_ratio: Dec,
+    /// Fractions of the funds of rejected proposals returned to their
+    /// authors, by proposal type, e.g. `pgf_payment`, overriding
+    /// `rejected_refund_ratio`
+    #[serde(default)]
+    pub rejected_refund_ratios: BTreeMap<String, Dec>,
+    /// The council whose members voting yay must weigh at least its
+    /// threshold, for council proposals to pass. When not set, council
+    /// proposals can't be submitted
+    #[serde(default)]
+    pub council: Option<Council>,
+    /// Treasury balance, in whole native tokens, above which the proposal
+    /// funds moved to the treasury are burned instead. When not set, the
+    /// treasury balance is not capped
+    #[serde(default)]
+    pub max_treasury_balance: Option<u64>,
+    /// How the ties of the tallies are broken, by tally type. When not set,
+    /// ties are rejected
+    #[serde(default)]
+    pub tie_breaks: TieBreaks,
 }
 
 #[derive(
@@ -1,8 +1,10 @@
 //! Node and client configuration
 
+pub mod change_feed;
 pub mod ethereum_bridge;
 pub mod genesis;
 pub mod global;
+pub mod proof_archive;
 pub mod utils;
 
 use std::collections::HashMap;
@@ -46,6 +48,16 @@ pub struct Config {
 pub struct ValidatorLocalConfig {
     pub accepted_gas_tokens:
         HashMap<namada::types::address::Address, namada::types::token::Amount>,
+    /// Gas consumed per second by the wasm code of governance proposals on
+    /// this node, used to estimate the execution time of the proposals
+    /// concluded in the blocks it proposes.
+    #[serde(default)]
+    pub proposal_gas_per_second: Option<u64>,
+    /// Maximum estimated execution time, in seconds, of the wasm code of
+    /// the governance proposals concluded in a block proposed by this node,
+    /// above which a warning is logged.
+    #[serde(default)]
+    pub max_proposal_execution_secs: Option<u64>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -93,6 +105,12 @@ pub struct Ledger {
     pub shell: Shell,
     pub cometbft: TendermintConfig,
     pub ethereum_bridge: ethereum_bridge::ledger::Config,
+    #[serde(default)]
+    pub change_feed: change_feed::Config,
+    /// The sink to which the Ethereum bridge proofs completed on chain are
+    /// archived, if any.
+    #[serde(default)]
+    pub proof_archive: Option<proof_archive::Config>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -119,6 +137,14 @@ pub struct Shell {
     pub action_at_height: Option<ActionAtHeight>,
     /// Specify if tendermint is started as validator, fullnode or seednode
     pub tendermint_mode: TendermintMode,
+    /// Whether to reject from the mempool the governance votes submitted
+    /// after the voting period of their proposal. Enabled by default.
+    #[serde(default = "default_reject_late_votes")]
+    pub reject_late_votes: bool,
+}
+
+fn default_reject_late_votes() -> bool {
+    true
 }
 
 impl Ledger {
@@ -147,9 +173,12 @@ impl Ledger {
                 cometbft_dir: COMETBFT_DIR.into(),
                 action_at_height: None,
                 tendermint_mode: mode,
+                reject_late_votes: default_reject_late_votes(),
             },
             cometbft: tendermint_config,
             ethereum_bridge: ethereum_bridge::ledger::Config::default(),
+            change_feed: change_feed::Config::default(),
+            proof_archive: None,
         }
     }
 
@@ -0,0 +1,19 @@
+//! Runtime configuration of the archival of the Ethereum bridge proofs
+//! completed on chain, such as validator set update and Bridge pool root
+//! proofs, to an external sink.
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// The sink of the archived proofs, e.g.
+/// `proof_archive = { kind = "dir", path = "/var/lib/namada/proofs" }`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Config {
+    /// Write each proof to its own file in the given directory, which is
+    /// created if it doesn't exist.
+    Dir {
+        /// The path of the directory.
+        path: PathBuf,
+    },
+}
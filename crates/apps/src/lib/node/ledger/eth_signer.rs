@@ -0,0 +1,267 @@
+//! Remote signing of the validator set update vote extensions.
+//!
+//! Validators keeping their Ethereum hot key out of the ledger process,
+//! e.g. on an HSM, configure the ledger with `eth_signer = "remote"` and
+//! run a signer service listening on a Unix socket. A signature is
+//! requested over a new connection to the socket, on which the ledger
+//! writes the 32 bytes Keccak digest of a validator set update vote
+//! extension, then shuts down its writing half. The signer replies with
+//! the Borsh encoded [`common::Signature`] of the digest, made with the
+//! Ethereum hot key of the validator, and closes the connection.
+//!
+//! The requests are bounded by a timeout, such that an unresponsive
+//! signer only causes the vote extension to be skipped, rather than
+//! blocking consensus.
+//!
+//! The same protocol is used to sign the contract addresses vote
+//! extensions with the Ethereum cold key of the validator, by a signer
+//! listening on the socket set in the `eth_cold_signer_socket` setting.
+
+use std::io::{self, Read, Write};
+use std::net::Shutdown;
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use borsh::BorshDeserialize;
+use borsh_ext::BorshSerializeExt;
+use namada::types::hash::KeccakHasher;
+use namada::types::keccak::KeccakHash;
+use namada::types::key::{common, SigScheme};
+use thiserror::Error;
+
+use crate::config::ethereum_bridge::ledger::{Config, EthSigner};
+
+/// The maximum length of the response of a signer. Signatures are far
+/// shorter, such that larger responses are rejected early.
+const MAX_RESPONSE_LEN: usize = 1024;
+
+/// Errors of the requests to a remote Ethereum signer.
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Failed to connect to the remote Ethereum signer: {0}")]
+    Connect(io::Error),
+    #[error("Failed to communicate with the remote Ethereum signer: {0}")]
+    Io(#[from] io::Error),
+    #[error("The remote Ethereum signer didn't reply within {0:?}")]
+    Timeout(Duration),
+    #[error("Invalid response of the remote Ethereum signer: {0}")]
+    InvalidResponse(String),
+}
+
+/// Client of a remote Ethereum signer, listening on a Unix socket.
+#[derive(Clone, Debug)]
+pub struct RemoteEthSigner {
+    /// The Unix socket of the signer
+    socket: PathBuf,
+    /// The time to wait for a signature
+    timeout: Duration,
+}
+
+impl RemoteEthSigner {
+    /// Create a client of the signer listening on the given socket.
+    pub fn new(socket: PathBuf, timeout: Duration) -> Self {
+        Self { socket, timeout }
+    }
+
+    /// Create the client of the remote signer set in the Ethereum bridge
+    /// config, if any. Panics if the remote signer has no socket.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        match config.eth_signer {
+            EthSigner::Local => None,
+            EthSigner::Remote => {
+                let socket = config.eth_signer_socket.clone().expect(
+                    "The `eth_signer_socket` setting is required by a remote \
+                     Ethereum signer",
+                );
+                Some(Self::new(
+                    socket,
+                    Duration::from_millis(config.eth_signer_timeout_ms),
+                ))
+            }
+        }
+    }
+
+    /// Create the client of the remote signer holding the Ethereum cold
+    /// key of the validator, set in the Ethereum bridge config, if any.
+    pub fn cold_from_config(config: &Config) -> Option<Self> {
+        config.eth_cold_signer_socket.clone().map(|socket| {
+            Self::new(
+                socket,
+                Duration::from_millis(config.eth_signer_timeout_ms),
+            )
+        })
+    }
+
+    /// Request the signature of the given digest.
+    pub fn sign_digest(
+        &self,
+        digest: &KeccakHash,
+    ) -> Result<common::Signature, Error> {
+        let mut stream =
+            UnixStream::connect(&self.socket).map_err(Error::Connect)?;
+        request_signature(&mut stream, digest, self.timeout)
+    }
+}
+
+/// Request the signature of the given digest over a connection to a
+/// signer, waiting at most `timeout` for the whole exchange.
+pub fn request_signature(
+    stream: &mut UnixStream,
+    digest: &KeccakHash,
+    timeout: Duration,
+) -> Result<common::Signature, Error> {
+    let deadline = Instant::now() + timeout;
+    let remaining = || {
+        deadline
+            .checked_duration_since(Instant::now())
+            .filter(|remaining| !remaining.is_zero())
+            .ok_or(Error::Timeout(timeout))
+    };
+    let map_timeout = |err: io::Error| match err.kind() {
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => {
+            Error::Timeout(timeout)
+        }
+        _ => Error::Io(err),
+    };
+
+    stream.set_write_timeout(Some(remaining()?))?;
+    stream.write_all(&digest.0).map_err(map_timeout)?;
+    stream.shutdown(Shutdown::Write)?;
+
+    let mut response = vec![];
+    let mut buf = [0; 128];
+    loop {
+        stream.set_read_timeout(Some(remaining()?))?;
+        match stream.read(&mut buf).map_err(map_timeout)? {
+            0 => break,
+            len => response.extend_from_slice(&buf[..len]),
+        }
+        if response.len() > MAX_RESPONSE_LEN {
+            return Err(Error::InvalidResponse(format!(
+                "the response exceeds {MAX_RESPONSE_LEN} bytes"
+            )));
+        }
+    }
+    common::Signature::try_from_slice(&response)
+        .map_err(|err| Error::InvalidResponse(err.to_string()))
+}
+
+/// Serve a single signature request received over a connection to a
+/// signer, signing the requested digest with the given Ethereum hot key.
+pub fn serve_signature_request(
+    stream: &mut UnixStream,
+    eth_hot_key: &common::SecretKey,
+) -> io::Result<()> {
+    let mut digest = [0; 32];
+    stream.read_exact(&mut digest)?;
+    let sig = common::SigScheme::sign_with_hasher::<KeccakHasher>(
+        eth_hot_key,
+        KeccakHash(digest),
+    );
+    stream.write_all(&sig.serialize_to_vec())?;
+    stream.shutdown(Shutdown::Write)
+}
+
+#[cfg(test)]
+mod test {
+    use std::os::unix::net::UnixListener;
+    use std::thread;
+
+    use namada::types::key::testing::gen_keypair;
+    use namada::types::key::{secp256k1, RefTo};
+
+    use super::*;
+
+    /// Generate an Ethereum hot key
+    fn eth_hot_key() -> common::SecretKey {
+        common::SecretKey::Secp256k1(gen_keypair::<secp256k1::SigScheme>())
+    }
+
+    /// Test that a signature is obtained from a signer over a socketpair,
+    /// which verifies against the Ethereum hot key
+    #[test]
+    fn test_signature_over_socketpair() {
+        let sk = eth_hot_key();
+        let (mut client, mut server) = UnixStream::pair().unwrap();
+        let signer = {
+            let sk = sk.clone();
+            thread::spawn(move || serve_signature_request(&mut server, &sk))
+        };
+
+        let digest = KeccakHash([7; 32]);
+        let sig =
+            request_signature(&mut client, &digest, Duration::from_secs(5))
+                .unwrap();
+        signer.join().unwrap().unwrap();
+        common::SigScheme::verify_signature_with_hasher::<KeccakHasher>(
+            &sk.ref_to(),
+            &digest,
+            &sig,
+        )
+        .unwrap();
+    }
+
+    /// Test that an unresponsive signer times out the request
+    #[test]
+    fn test_unresponsive_signer_times_out() {
+        let (mut client, _server) = UnixStream::pair().unwrap();
+        let timeout = Duration::from_millis(50);
+
+        let result =
+            request_signature(&mut client, &KeccakHash([7; 32]), timeout);
+        assert!(matches!(result, Err(Error::Timeout(t)) if t == timeout));
+    }
+
+    /// Test that a malformed response of a signer is rejected
+    #[test]
+    fn test_malformed_response_rejected() {
+        let (mut client, mut server) = UnixStream::pair().unwrap();
+        let signer = thread::spawn(move || {
+            let mut digest = [0; 32];
+            server.read_exact(&mut digest)?;
+            server.write_all(b"not a signature")
+        });
+
+        let result = request_signature(
+            &mut client,
+            &KeccakHash([7; 32]),
+            Duration::from_secs(5),
+        );
+        signer.join().unwrap().unwrap();
+        assert!(matches!(result, Err(Error::InvalidResponse(_))));
+    }
+
+    /// Test the client of a signer listening on a Unix socket, and that a
+    /// missing signer fails to connect
+    #[test]
+    fn test_remote_signer_over_unix_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("eth-signer.sock");
+        let signer =
+            RemoteEthSigner::new(socket.clone(), Duration::from_secs(5));
+        let digest = KeccakHash([7; 32]);
+        assert!(matches!(
+            signer.sign_digest(&digest),
+            Err(Error::Connect(_))
+        ));
+
+        let sk = eth_hot_key();
+        let listener = UnixListener::bind(&socket).unwrap();
+        let server = {
+            let sk = sk.clone();
+            thread::spawn(move || {
+                let (mut stream, _) = listener.accept()?;
+                serve_signature_request(&mut stream, &sk)
+            })
+        };
+        let sig = signer.sign_digest(&digest).unwrap();
+        server.join().unwrap().unwrap();
+        common::SigScheme::verify_signature_with_hasher::<KeccakHasher>(
+            &sk.ref_to(),
+            &digest,
+            &sig,
+        )
+        .unwrap();
+    }
+}
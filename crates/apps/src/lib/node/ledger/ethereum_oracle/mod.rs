@@ -13,7 +13,7 @@ use namada::eth_bridge::ethers;
 use namada::eth_bridge::ethers::providers::{Http, Middleware, Provider};
 use namada::eth_bridge::oracle::config::Config;
 use namada::types::control_flow::time::{Constant, Duration, Instant, Sleep};
-use namada::types::ethereum_events::EthereumEvent;
+use namada::types::ethereum_events::{EthAddress, EthereumEvent};
 use namada::types::ethereum_structs;
 use namada_sdk::eth_bridge::{eth_syncing_status_timeout, SyncStatus};
 use num256::Uint256;
@@ -187,8 +187,9 @@ pub struct Oracle<C = Provider<Http>> {
     /// The client that talks to the Ethereum fullnode
     client: C,
     /// A channel for sending processed and confirmed
-    /// events to the ledger process
-    sender: BoundedSender<EthereumEvent>,
+    /// events to the ledger process, along with the address
+    /// of the contract they were observed from
+    sender: BoundedSender<(EthAddress, EthereumEvent)>,
     /// The most recently processed block is recorded here.
     last_processed_block: last_processed_block::Sender,
     /// How long the oracle should wait between checking blocks
@@ -204,7 +205,7 @@ impl<C: RpcClient> Oracle<C> {
     /// has been sent a configuration via the passed in `control` channel.
     pub fn new(
         client_or_url: Either<C, &str>,
-        sender: BoundedSender<EthereumEvent>,
+        sender: BoundedSender<(EthAddress, EthereumEvent)>,
         last_processed_block: last_processed_block::Sender,
         backoff: Duration,
         ceiling: Duration,
@@ -223,19 +224,23 @@ impl<C: RpcClient> Oracle<C> {
         }
     }
 
-    /// Send a series of [`EthereumEvent`]s to the Namada
-    /// ledger. Returns a boolean indicating that all sent
-    /// successfully. If false is returned, the receiver
-    /// has hung up.
+    /// Send a series of [`EthereumEvent`]s observed from the
+    /// given contract to the Namada ledger. Returns a boolean
+    /// indicating that all sent successfully. If false is
+    /// returned, the receiver has hung up.
     ///
     /// N.B. this will block if the internal channel buffer
     /// is full.
-    async fn send(&self, events: Vec<EthereumEvent>) -> bool {
+    async fn send(
+        &self,
+        contract: EthAddress,
+        events: Vec<EthereumEvent>,
+    ) -> bool {
         if self.sender.is_closed() {
             return false;
         }
         for event in events.into_iter() {
-            if self.sender.send(event).await.is_err() {
+            if self.sender.send((contract, event)).await.is_err() {
                 return false;
             }
         }
@@ -282,7 +287,7 @@ async fn await_initial_configuration(
 /// processes and forwards Ethereum events to the ledger
 pub fn run_oracle<C: RpcClient>(
     url: impl AsRef<str>,
-    sender: BoundedSender<EthereumEvent>,
+    sender: BoundedSender<(EthAddress, EthereumEvent)>,
     control: control::Receiver,
     last_processed_block: last_processed_block::Sender,
     spawner: &mut AbortableSpawner,
@@ -501,9 +506,10 @@ async fn process_events_in_block<C: RpcClient>(
     // of confirmations
     for codec in event_codecs() {
         let sig = codec.event_signature();
-        let addr: Address = match codec.kind() {
-            EventKind::Bridge => config.bridge_contract.into(),
+        let contract = match codec.kind() {
+            EventKind::Bridge => config.bridge_contract,
         };
+        let addr: Address = contract.into();
         tracing::debug!(
             ?block_to_process,
             ?addr,
@@ -573,7 +579,7 @@ async fn process_events_in_block<C: RpcClient>(
                  confirmations and will be sent onwards"
             );
         }
-        if !oracle.send(confirmed).await {
+        if !oracle.send(contract, confirmed).await {
             return Err(Error::Channel(sig.into(), addr));
         }
     }
@@ -639,7 +645,7 @@ mod test_oracle {
     struct TestPackage {
         oracle: TestOracle,
         controller: Web3Controller,
-        eth_recv: tokio::sync::mpsc::Receiver<EthereumEvent>,
+        eth_recv: tokio::sync::mpsc::Receiver<(EthAddress, EthereumEvent)>,
         control_sender: control::Sender,
         blocks_processed_recv: tokio::sync::mpsc::UnboundedReceiver<Uint256>,
     }
@@ -909,8 +915,10 @@ mod test_oracle {
         // increase block height so first event is confirmed but second is
         // not.
         controller.apply_cmd(TestCmd::NewHeight(Uint256::from(200u32)));
-        // check the correct event is received
-        let event = eth_recv.recv().await.expect("Test failed");
+        // check the correct event is received, tagged with the
+        // address of the bridge contract it was observed from
+        let (contract, event) = eth_recv.recv().await.expect("Test failed");
+        assert_eq!(contract, Config::default().bridge_contract);
         if let EthereumEvent::TransfersToNamada { nonce, transfers } = event {
             assert_eq!(nonce, 0.into());
             assert!(transfers.is_empty());
@@ -932,7 +940,7 @@ mod test_oracle {
         // increase block height so second event is confirmed
         controller.apply_cmd(TestCmd::NewHeight(Uint256::from(250u32)));
         // check correct event is received
-        let event = eth_recv.recv().await.expect("Test failed");
+        let (_, event) = eth_recv.recv().await.expect("Test failed");
         if let EthereumEvent::TransfersToEthereum { mut transfers, .. } = event
         {
             assert_eq!(transfers.len(), 1);
@@ -1,9 +1,10 @@
 use std::net::SocketAddr;
 
 use borsh::BorshDeserialize;
-use namada::types::ethereum_events::EthereumEvent;
+use namada::types::ethereum_events::{EthAddress, EthereumEvent};
 use tokio::sync::mpsc::Sender as BoundedSender;
 use tokio::sync::oneshot::{Receiver, Sender};
+use tokio::sync::watch;
 use warp::reply::WithStatus;
 use warp::Filter;
 
@@ -16,10 +17,12 @@ const EVENTS_POST_ENDPOINT: &str = "eth_events";
 /// Starts a [`warp::Server`] that listens for Borsh-serialized Ethereum events
 /// and then forwards them to `sender`. It shuts down if a signal is sent on the
 /// `abort_recv` channel. Accepts the receive-half of an oracle control channel
-/// (`control_recv`) that will be kept alive until shutdown.
+/// (`control_recv`) that will be kept alive until shutdown. Events are
+/// forwarded as if observed from the bridge contract of the last configuration
+/// received on `control_recv`.
 pub async fn serve(
     listen_addr: String,
-    sender: BoundedSender<EthereumEvent>,
+    sender: BoundedSender<(EthAddress, EthereumEvent)>,
     mut control_recv: oracle::control::Receiver,
     abort_recv: Receiver<Sender<()>>,
 ) {
@@ -27,10 +30,14 @@ pub async fn serve(
         .parse()
         .expect("Failed to parse the events endpoint listen address");
     tracing::info!(?listen_addr, "Ethereum event endpoint is starting");
+    let (bridge_contract_send, bridge_contract_recv) = watch::channel(None);
     let eth_events = warp::post()
         .and(warp::path(EVENTS_POST_ENDPOINT))
         .and(warp::body::bytes())
-        .then(move |bytes: bytes::Bytes| send(bytes, sender.clone()));
+        .then(move |bytes: bytes::Bytes| {
+            let bridge_contract = *bridge_contract_recv.borrow();
+            send(bytes, bridge_contract, sender.clone())
+        });
 
     let (_, future) = warp::serve(eth_events).bind_with_graceful_shutdown(
         listen_addr,
@@ -39,14 +46,18 @@ pub async fn serve(
                 ?listen_addr,
                 "Starting to listen for Borsh-serialized Ethereum events"
             );
-            let control_recv_discarder = tokio::spawn(async move {
+            let control_recv_tracker = tokio::spawn(async move {
                 while let Some(command) = control_recv.recv().await {
                     tracing::debug!(
                         ?command,
-                        "Events endpoint received an oracle command which \
-                         will be ignored since we are not running a real \
-                         oracle"
-                    )
+                        "Events endpoint received an oracle command, of which \
+                         only the bridge contract will be tracked since we \
+                         are not running a real oracle"
+                    );
+                    let oracle::control::Command::UpdateConfig(config) =
+                        command;
+                    bridge_contract_send
+                        .send_replace(Some(config.bridge_contract));
                 }
             });
             match abort_recv.await {
@@ -67,7 +78,7 @@ pub async fn serve(
                 ?listen_addr,
                 "Stopping listening for Borsh-serialized Ethereum events"
             );
-            control_recv_discarder.abort();
+            control_recv_tracker.abort();
         },
     );
     future.await
@@ -76,9 +87,20 @@ pub async fn serve(
 /// Callback to send out events from the oracle
 async fn send(
     bytes: bytes::Bytes,
-    sender: BoundedSender<EthereumEvent>,
+    bridge_contract: Option<EthAddress>,
+    sender: BoundedSender<(EthAddress, EthereumEvent)>,
 ) -> WithStatus<&'static str> {
     tracing::info!(len = bytes.len(), "Received request");
+    let Some(bridge_contract) = bridge_contract else {
+        tracing::warn!(
+            "Couldn't handle request, since no oracle configuration has been \
+             received yet"
+        );
+        return warp::reply::with_status(
+            "Service unavailable",
+            warp::http::StatusCode::SERVICE_UNAVAILABLE,
+        );
+    };
     let event = match EthereumEvent::try_from_slice(&bytes) {
         Ok(event) => event,
         Err(error) => {
@@ -90,7 +112,7 @@ async fn send(
         }
     };
     tracing::debug!("Serialized event - {:#?}", event);
-    match sender.send(event).await {
+    match sender.send((bridge_contract, event)).await {
         Ok(()) => warp::reply::with_status("OK", warp::http::StatusCode::OK),
         Err(error) => {
             tracing::warn!(?error, "Couldn't send event");
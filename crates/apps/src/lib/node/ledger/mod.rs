@@ -1,5 +1,6 @@
 mod abortable;
 mod broadcaster;
+pub mod eth_signer;
 pub mod ethereum_oracle;
 pub mod shell;
 pub mod shims;
@@ -64,6 +65,9 @@ const ENV_VAR_RAYON_THREADS: &str = "NAMADA_RAYON_THREADS";
 //     }
 //```
 impl Shell {
+    /// Queue the proposals committed to be concluded at the last committed
+    /// epoch. The whole queue is only reconciled with storage once, when the
+    /// shell is started.
     fn load_proposals(&mut self) {
         let proposals_key = governance_storage::get_commiting_proposals_prefix(
             self.wl_storage.storage.last_epoch.0,
@@ -229,6 +233,49 @@ pub fn rollback(config: config::Ledger) -> Result<(), shell::Error> {
     shell::rollback(config)
 }
 
+/// Check the consistency of the governance storage of the node, reporting
+/// the inconsistencies found
+pub fn verify_governance_storage(
+    config: config::Ledger,
+) -> Result<bool, shell::Error> {
+    let report = shell::verify_governance_storage(config)?;
+    println!("Checked {} governance proposals.", report.proposals_checked);
+    for inconsistency in &report.inconsistencies {
+        println!("{inconsistency}");
+    }
+    if report.is_consistent() {
+        println!("The governance storage is consistent.");
+    } else {
+        println!(
+            "Found {} inconsistencies in the governance storage.",
+            report.inconsistencies.len()
+        );
+    }
+    Ok(report.is_consistent())
+}
+
+/// Export the governance storage of the node at a committed height to a
+/// directory, for external auditing
+pub fn export_governance(
+    config: config::Ledger,
+    args::LedgerExportGovernance {
+        block_height,
+        out_dir,
+    }: args::LedgerExportGovernance,
+) -> Result<(), shell::governance_export::ExportError> {
+    let manifest = shell::export_governance(config, block_height, &out_dir)?;
+    println!(
+        "Exported {} governance storage records at height {}, with state root \
+         {}, in {} chunks to {}.",
+        manifest.records,
+        manifest.height,
+        manifest.state_root,
+        manifest.chunks.len(),
+        out_dir.to_string_lossy()
+    );
+    Ok(())
+}
+
 /// Runs and monitors a few concurrent tasks.
 ///
 /// This includes:
@@ -16,8 +16,11 @@
 //! In the current implementation, we allocate space for transactions
 //! in the following order of preference:
 //!
-//! - First, we allot space for DKG encrypted txs. We allow DKG encrypted txs to
-//!   take up at most 1/3 of the total block space.
+//! - First, we reserve space for the vote extension protocol txs that must be
+//!   included in the block, if any. This space is taken out of the total block
+//!   space before any other kind of tx is allotted space.
+//! - Next, we allot space for DKG encrypted txs. We allow DKG encrypted txs to
+//!   take up at most 1/3 of the remaining block space.
 //! - Next, we allot space for DKG decrypted txs. Decrypted txs take up as much
 //!   space as needed. We will see, shortly, why in practice this is fine.
 //! - Finally, we allot space for protocol txs. Protocol txs get half of the
@@ -174,6 +177,20 @@ impl<M> BlockAllocator<states::BuildingEncryptedTxBatch<M>> {
             decrypted_txs: TxBin::default(),
         }
     }
+
+    /// Reserve `bytes` of the block space for protocol txs, before
+    /// any other kind of tx is allotted space. DKG encrypted txs may
+    /// take up at most 1/3 of the block space left after the
+    /// reservation.
+    ///
+    /// The reserved space is capped at the total block space.
+    pub fn reserve_protocol_space(&mut self, bytes: u64) {
+        let reserved = bytes.min(self.block.allotted);
+        self.protocol_txs = TxBin::init(reserved);
+        self.encrypted_txs.space = TxBin::init(
+            threshold::ONE_THIRD.over(self.block.allotted - reserved),
+        );
+    }
 }
 
 impl<State> BlockAllocator<State> {
@@ -397,6 +414,46 @@ mod tests {
         );
     }
 
+    /// Check that the space reserved for protocol txs is taken out of
+    /// the block space before encrypted txs are allotted space, and
+    /// handed to protocol txs on top of any space left over.
+    #[test]
+    fn test_reserved_protocol_space() {
+        const BLOCK_SIZE: u64 = 90;
+        const BLOCK_GAS: u64 = 1_000;
+
+        let mut alloc = BsaWrapperTxs::init(BLOCK_SIZE, BLOCK_GAS);
+        alloc.reserve_protocol_space(30);
+
+        // encrypted txs get 1/3 of the unreserved space
+        assert_eq!(alloc.encrypted_txs.space.allotted, 20);
+        assert!(alloc.try_alloc(BlockResources::new(&[0; 20], 0)).is_ok());
+        assert_matches!(
+            alloc.try_alloc(BlockResources::new(&[0; 1], 0)),
+            Err(AllocFailure::Rejected { .. })
+        );
+
+        // decrypted txs can't take up the reserved space
+        let mut alloc = alloc.next_state();
+        assert_eq!(alloc.decrypted_txs.allotted, BLOCK_SIZE - 30 - 20);
+        assert!(alloc.try_alloc(&[0; 15]).is_ok());
+
+        // protocol txs get the reserved space, plus whatever is left
+        let mut alloc = alloc.next_state();
+        assert_eq!(alloc.protocol_txs.allotted, BLOCK_SIZE - 20 - 15);
+        assert!(alloc.try_alloc(&[0; 55]).is_ok());
+        assert_matches!(
+            alloc.try_alloc(&[0; 1]),
+            Err(AllocFailure::Rejected { .. })
+        );
+
+        // the reservation is capped at the block space
+        let mut alloc = BsaNoWrapperTxs::init(BLOCK_SIZE, BLOCK_GAS);
+        alloc.reserve_protocol_space(BLOCK_SIZE + 1);
+        assert_eq!(alloc.protocol_txs.allotted, BLOCK_SIZE);
+        assert_eq!(alloc.uninitialized_space_in_bytes(), 0);
+    }
+
     // Test that we cannot include encrypted txs in a block
     // when the state invariants banish them from inclusion.
     #[test]
@@ -24,9 +24,11 @@ impl NextStateImpl for BlockAllocator<BuildingDecryptedTxBatch> {
     fn next_state_impl(mut self) -> Self::Next {
         self.decrypted_txs.shrink_to_fit();
 
-        // the remaining space is allocated to protocol txs
+        // the remaining space is allocated to protocol txs, on
+        // top of any space reserved for them beforehand
         let remaining_free_space = self.uninitialized_space_in_bytes();
-        self.protocol_txs = TxBin::init(remaining_free_space);
+        self.protocol_txs =
+            TxBin::init(self.protocol_txs.allotted + remaining_free_space);
 
         // cast state
         let Self {
@@ -89,6 +89,25 @@ fn next_state<Mode>(
     }
 }
 
+impl EncryptedTxBatchAllocator {
+    /// Reserve `bytes` of the block space for protocol txs, before
+    /// any encrypted txs are allocated.
+    ///
+    /// Refer to [`BlockAllocator::reserve_protocol_space`] for more
+    /// details.
+    #[inline]
+    pub fn reserve_protocol_space(&mut self, bytes: u64) {
+        match self {
+            EncryptedTxBatchAllocator::WithEncryptedTxs(state) => {
+                state.reserve_protocol_space(bytes)
+            }
+            EncryptedTxBatchAllocator::WithoutEncryptedTxs(state) => {
+                state.reserve_protocol_space(bytes)
+            }
+        }
+    }
+}
+
 impl TryAlloc for EncryptedTxBatchAllocator {
     type Resources<'tx> = BlockResources<'tx>;
 
@@ -0,0 +1,215 @@
+//! Publication of the changes of watched storage keys.
+//!
+//! Once a block is finalized, the changes that its write log is about to
+//! commit to the storage keys watched by the node are published as
+//! [`StorageChange`] events. These are returned to CometBFT along with the
+//! other events of the block, such that clients can subscribe to them, and
+//! added to the event log of the shell.
+
+use std::collections::BTreeMap;
+
+use data_encoding::HEXUPPER;
+use namada::ledger::events::{attributes, Event, EventLevel, EventType};
+use namada::state::write_log::StorageModification;
+use namada::state::{DBIter, StorageHasher, WlStorage, DB};
+use namada::types::hash::Hash;
+use namada::types::storage::{BlockHeight, Key, KeySeg};
+
+use crate::config::change_feed::{Config, WILDCARD_SEGMENT};
+
+/// The change of the value of a watched storage key in some block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageChange {
+    /// The storage key whose value changed.
+    pub key: Key,
+    /// The hash of the value of the key before the block, if any.
+    pub old_value_hash: Option<Hash>,
+    /// The value of the key after the block, or `None` if it was deleted.
+    pub new_value: Option<Vec<u8>>,
+    /// The height of the block that changed the value.
+    pub height: BlockHeight,
+}
+
+impl From<StorageChange> for Event {
+    fn from(change: StorageChange) -> Self {
+        let mut event = Event {
+            event_type: EventType::StorageChange,
+            level: EventLevel::Block,
+            attributes: Default::default(),
+        };
+        event[attributes::STORAGE_KEY] = change.key.to_string();
+        event["old_value_hash"] = change
+            .old_value_hash
+            .map(|hash| hash.to_string())
+            .unwrap_or_default();
+        event["new_value"] = change
+            .new_value
+            .map(|value| HEXUPPER.encode(&value))
+            .unwrap_or_default();
+        event["height"] = change.height.to_string();
+        event
+    }
+}
+
+/// Publishes the changes of the storage keys matching the key prefixes
+/// watched by the node.
+#[derive(Debug)]
+pub struct ChangeFeed {
+    /// The segments of each watched key prefix.
+    watched_key_prefixes: Vec<Vec<String>>,
+    /// The maximum number of changes published per block.
+    max_changes_per_block: usize,
+    /// The number of changes dropped since the node started, as they
+    /// exceeded the maximum number of changes of their block.
+    dropped_changes: u64,
+}
+
+impl ChangeFeed {
+    /// Create a new change feed from the node's config.
+    pub fn new(config: &Config) -> Self {
+        let watched_key_prefixes = config
+            .watched_key_prefixes
+            .iter()
+            .map(|prefix| {
+                prefix
+                    .split(namada::types::storage::KEY_SEGMENT_SEPARATOR)
+                    .map(str::to_owned)
+                    .collect()
+            })
+            .collect();
+        Self {
+            watched_key_prefixes,
+            max_changes_per_block: config.max_changes_per_block,
+            dropped_changes: 0,
+        }
+    }
+
+    /// Get the number of changes dropped since the node started.
+    pub fn dropped_changes(&self) -> u64 {
+        self.dropped_changes
+    }
+
+    /// Check if the given key matches any of the watched key prefixes.
+    pub fn is_watched(&self, key: &Key) -> bool {
+        self.watched_key_prefixes.iter().any(|prefix| {
+            prefix.len() <= key.segments.len()
+                && prefix.iter().zip(&key.segments).all(|(pattern, seg)| {
+                    pattern == WILDCARD_SEGMENT || *pattern == seg.raw()
+                })
+        })
+    }
+
+    /// Collect the changes of the watched keys in the block write log, which
+    /// is about to be committed at the given height, as events. Writes that
+    /// leave the value of a key unchanged are left out.
+    pub fn collect_changes<D, H>(
+        &mut self,
+        wl_storage: &WlStorage<D, H>,
+        height: BlockHeight,
+    ) -> Vec<Event>
+    where
+        D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+        H: StorageHasher + Sync + 'static,
+    {
+        if self.watched_key_prefixes.is_empty() {
+            return vec![];
+        }
+        // sort the changes by key, such that the same changes are dropped
+        // on every node
+        let modifications: BTreeMap<_, _> = wl_storage
+            .write_log
+            .iter_block_modifications()
+            .filter(|(key, _)| self.is_watched(key))
+            .collect();
+
+        let mut events = vec![];
+        let mut dropped = 0_u64;
+        for (key, modification) in modifications {
+            let new_value = match modification {
+                StorageModification::Write { value } => Some(value.clone()),
+                StorageModification::Delete => None,
+                StorageModification::InitAccount { vp_code_hash } => {
+                    Some(vp_code_hash.0.to_vec())
+                }
+                StorageModification::Temp { .. } => continue,
+            };
+            let old_value_hash = match wl_storage.storage.read(key) {
+                Ok((old_value, _gas)) => old_value.map(Hash::sha256),
+                Err(err) => {
+                    tracing::error!(
+                        %key,
+                        "Failed to read the committed value of a watched \
+                         storage key: {err}"
+                    );
+                    None
+                }
+            };
+            if old_value_hash == new_value.as_ref().map(Hash::sha256) {
+                continue;
+            }
+            if events.len() == self.max_changes_per_block {
+                dropped += 1;
+                continue;
+            }
+            events.push(
+                StorageChange {
+                    key: key.clone(),
+                    old_value_hash,
+                    new_value,
+                    height,
+                }
+                .into(),
+            );
+        }
+
+        if dropped > 0 {
+            self.dropped_changes += dropped;
+            tracing::warn!(
+                "Dropped {dropped} changes of watched storage keys at height \
+                 {height}, exceeding the maximum of {} changes per block. {} \
+                 changes were dropped since the node started.",
+                self.max_changes_per_block,
+                self.dropped_changes
+            );
+            let mut event = Event {
+                event_type: EventType::StorageChange,
+                level: EventLevel::Block,
+                attributes: Default::default(),
+            };
+            event["height"] = height.to_string();
+            event["dropped_changes"] = dropped.to_string();
+            events.push(event);
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use namada::token::storage_key::balance_key;
+    use namada::types::address::testing::established_address_1;
+    use namada::types::address::GOV;
+
+    use super::*;
+
+    /// Test that the default config watches the governance storage and
+    /// the balances of the governance and treasury accounts, in any token.
+    #[test]
+    fn test_default_watched_keys() {
+        let feed = ChangeFeed::new(&Config::default());
+        let token = established_address_1();
+
+        assert!(
+            feed.is_watched(
+                &namada::governance::storage::keys::get_counter_key()
+            )
+        );
+        assert!(feed.is_watched(&balance_key(&token, &GOV)));
+        assert!(feed.is_watched(&balance_key(
+            &token,
+            &namada::governance::pgf::ADDRESS
+        )));
+        assert!(!feed.is_watched(&balance_key(&token, &token)));
+        assert!(!feed.is_watched(&Key::from(token.to_db_key())));
+    }
+}
@@ -0,0 +1,485 @@
+//! Ordered updates of the subsystems of the ledger at a new epoch.
+//!
+//! The relative order of the updates run at the start of a new epoch is
+//! consensus-critical, e.g. the treasury balance is snapshotted once the
+//! funds of the concluded proposals have been moved and the treasury
+//! inflation has been minted. Each subsystem implements
+//! [`EpochTransition`], and the order in which they're run is the order of
+//! the variants of [`EpochHook`], such that new epoch logic is explicitly
+//! slotted among the existing updates.
+//!
+//! The updates depending on the votes of the block, such as the processing
+//! of slashes and the PoS inflation, are run later on in `FinalizeBlock`.
+
+use std::fmt;
+
+use namada::eth_bridge::storage::parameters as eth_bridge_params;
+use namada::ledger::pos::namada_proof_of_stake;
+use namada::proof_of_stake::storage::read_pos_params;
+use namada::token::conversion::update_allowed_conversions;
+use namada::types::ethereum_structs::EthBridgeEvent;
+use namada::types::storage::Epoch;
+
+use super::governance::{
+    execute_governance_proposals, mint_treasury_inflation,
+};
+use super::phase_timing::{Phase, PhaseTimer};
+use super::*;
+
+/// The hooks run at the start of a new epoch, in the order of their
+/// variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EpochHook {
+    /// Update of the allowed conversions of the MASP.
+    MaspConversions,
+    /// Conclusion of the governance proposals reaching their grace epoch.
+    Governance,
+    /// Minting of the treasury inflation.
+    TreasuryInflation,
+    /// Snapshot of the treasury balance.
+    TreasurySnapshot,
+    /// Activation of the Ethereum bridge contracts scheduled by governance.
+    BridgeContracts,
+    /// Copy of the epoched PoS data to the pipeline epoch.
+    PosEpochedData,
+}
+
+impl EpochHook {
+    /// All the hooks, in the order in which they're run.
+    pub const ALL: [EpochHook; 6] = [
+        EpochHook::MaspConversions,
+        EpochHook::Governance,
+        EpochHook::TreasuryInflation,
+        EpochHook::TreasurySnapshot,
+        EpochHook::BridgeContracts,
+        EpochHook::PosEpochedData,
+    ];
+
+    /// Return a human readable name of this hook.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            EpochHook::MaspConversions => "masp_conversions",
+            EpochHook::Governance => "governance",
+            EpochHook::TreasuryInflation => "treasury_inflation",
+            EpochHook::TreasurySnapshot => "treasury_snapshot",
+            EpochHook::BridgeContracts => "bridge_contracts",
+            EpochHook::PosEpochedData => "pos_epoched_data",
+        }
+    }
+
+    /// Get the subsystem update run by this hook.
+    fn transition<D, H>(&self) -> &'static dyn EpochTransition<D, H>
+    where
+        D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+        H: StorageHasher + Sync + 'static,
+    {
+        match self {
+            EpochHook::MaspConversions => &MaspConversions,
+            EpochHook::Governance => &Governance,
+            EpochHook::TreasuryInflation => &TreasuryInflation,
+            EpochHook::TreasurySnapshot => &TreasurySnapshot,
+            EpochHook::BridgeContracts => &BridgeContracts,
+            EpochHook::PosEpochedData => &PosEpochedData,
+        }
+    }
+}
+
+impl fmt::Display for EpochHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The update of a subsystem at the start of a new epoch.
+pub trait EpochTransition<D, H>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    /// The hook running this update.
+    fn hook(&self) -> EpochHook;
+
+    /// Update the subsystem, at the start of the given epoch.
+    fn on_new_epoch(
+        &self,
+        shell: &mut Shell<D, H>,
+        response: &mut shim::response::FinalizeBlock,
+        current_epoch: Epoch,
+    ) -> Result<()>;
+}
+
+/// Run the hooks of every subsystem at the start of the given epoch, in
+/// the order of [`EpochHook::ALL`].
+pub fn run_epoch_hooks<D, H>(
+    shell: &mut Shell<D, H>,
+    response: &mut shim::response::FinalizeBlock,
+    current_epoch: Epoch,
+) -> Result<()>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    let transitions = EpochHook::ALL.map(|hook| hook.transition());
+    run_transitions(shell, response, current_epoch, &transitions)
+}
+
+/// Run the given transitions in order, stopping at the first failure,
+/// whose error is tagged with the hook of the failing transition.
+fn run_transitions<D, H>(
+    shell: &mut Shell<D, H>,
+    response: &mut shim::response::FinalizeBlock,
+    current_epoch: Epoch,
+    transitions: &[&dyn EpochTransition<D, H>],
+) -> Result<()>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    for transition in transitions {
+        let hook = transition.hook();
+        let _span =
+            tracing::debug_span!("epoch_hook", %hook, epoch = %current_epoch)
+                .entered();
+        transition
+            .on_new_epoch(shell, response, current_epoch)
+            .map_err(|err| Error::EpochHook(hook, Box::new(err)))?;
+    }
+    Ok(())
+}
+
+struct MaspConversions;
+
+impl<D, H> EpochTransition<D, H> for MaspConversions
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    fn hook(&self) -> EpochHook {
+        EpochHook::MaspConversions
+    }
+
+    fn on_new_epoch(
+        &self,
+        shell: &mut Shell<D, H>,
+        _response: &mut shim::response::FinalizeBlock,
+        _current_epoch: Epoch,
+    ) -> Result<()> {
+        update_allowed_conversions(&mut shell.wl_storage)?;
+        Ok(())
+    }
+}
+
+struct Governance;
+
+impl<D, H> EpochTransition<D, H> for Governance
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    fn hook(&self) -> EpochHook {
+        EpochHook::Governance
+    }
+
+    fn on_new_epoch(
+        &self,
+        shell: &mut Shell<D, H>,
+        response: &mut shim::response::FinalizeBlock,
+        current_epoch: Epoch,
+    ) -> Result<()> {
+        let proposals_result = execute_governance_proposals(shell, response)?;
+        if !proposals_result.is_empty() {
+            tracing::debug!(
+                result = %proposals_result.to_json(),
+                "Concluded governance proposals at epoch {current_epoch}"
+            );
+        }
+        Ok(())
+    }
+}
+
+struct TreasuryInflation;
+
+impl<D, H> EpochTransition<D, H> for TreasuryInflation
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    fn hook(&self) -> EpochHook {
+        EpochHook::TreasuryInflation
+    }
+
+    fn on_new_epoch(
+        &self,
+        shell: &mut Shell<D, H>,
+        response: &mut shim::response::FinalizeBlock,
+        _current_epoch: Epoch,
+    ) -> Result<()> {
+        mint_treasury_inflation(shell, response)
+    }
+}
+
+struct TreasurySnapshot;
+
+impl<D, H> EpochTransition<D, H> for TreasurySnapshot
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    fn hook(&self) -> EpochHook {
+        EpochHook::TreasurySnapshot
+    }
+
+    /// Snapshot the treasury balance, once the funds of the proposals
+    /// decided at this epoch have been moved and the treasury inflation has
+    /// been minted
+    fn on_new_epoch(
+        &self,
+        shell: &mut Shell<D, H>,
+        _response: &mut shim::response::FinalizeBlock,
+        current_epoch: Epoch,
+    ) -> Result<()> {
+        namada::governance::storage::snapshot_treasury_balance(
+            &mut shell.wl_storage,
+            current_epoch,
+        )?;
+        Ok(())
+    }
+}
+
+struct BridgeContracts;
+
+impl<D, H> EpochTransition<D, H> for BridgeContracts
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    fn hook(&self) -> EpochHook {
+        EpochHook::BridgeContracts
+    }
+
+    /// Switch to the Ethereum bridge contracts scheduled by governance, once
+    /// their activation epoch is reached, emitting an event with the
+    /// addresses of the old and new contracts
+    fn on_new_epoch(
+        &self,
+        shell: &mut Shell<D, H>,
+        response: &mut shim::response::FinalizeBlock,
+        current_epoch: Epoch,
+    ) -> Result<()> {
+        if let Some((old_contracts, new_contracts)) =
+            eth_bridge_params::apply_scheduled_contracts_update(
+                &mut shell.wl_storage,
+                current_epoch,
+            )?
+        {
+            tracing::info!(
+                ?old_contracts,
+                ?new_contracts,
+                "Activated new Ethereum bridge contracts at epoch \
+                 {current_epoch}"
+            );
+            response.events.push(
+                EthBridgeEvent::new_contracts_activated(
+                    current_epoch,
+                    old_contracts.native_erc20,
+                    new_contracts.native_erc20,
+                    old_contracts.bridge.address,
+                    new_contracts.bridge.address,
+                )
+                .into(),
+            );
+        }
+        Ok(())
+    }
+}
+
+struct PosEpochedData;
+
+impl<D, H> EpochTransition<D, H> for PosEpochedData
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    fn hook(&self) -> EpochHook {
+        EpochHook::PosEpochedData
+    }
+
+    fn on_new_epoch(
+        &self,
+        shell: &mut Shell<D, H>,
+        _response: &mut shim::response::FinalizeBlock,
+        current_epoch: Epoch,
+    ) -> Result<()> {
+        let _timer = PhaseTimer::new(Phase::PosUpdates);
+        let pos_params = read_pos_params(&shell.wl_storage)?;
+
+        // Copy the new_epoch + pipeline_len - 1 validator set into
+        // new_epoch + pipeline_len
+        namada_proof_of_stake::validator_set_update::copy_validator_sets_and_positions(
+            &mut shell.wl_storage,
+            &pos_params,
+            current_epoch,
+            current_epoch + pos_params.pipeline_len,
+        )?;
+
+        // Compute the total stake of the consensus validator set and record
+        // it in storage
+        namada_proof_of_stake::compute_and_store_total_consensus_stake(
+            &mut shell.wl_storage,
+            current_epoch,
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+
+    use namada::state::mockdb::MockDB;
+    use namada::types::ethereum_events::EthAddress;
+
+    use super::*;
+    use crate::node::ledger::shell::test_utils::setup;
+
+    /// A transition recording the hooks run, which fails if `fail` is set
+    struct Recorder<'log> {
+        hook: EpochHook,
+        fail: bool,
+        log: &'log RefCell<Vec<EpochHook>>,
+    }
+
+    impl<'log, D, H> EpochTransition<D, H> for Recorder<'log>
+    where
+        D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+        H: StorageHasher + Sync + 'static,
+    {
+        fn hook(&self) -> EpochHook {
+            self.hook
+        }
+
+        fn on_new_epoch(
+            &self,
+            _shell: &mut Shell<D, H>,
+            _response: &mut shim::response::FinalizeBlock,
+            _current_epoch: Epoch,
+        ) -> Result<()> {
+            self.log.borrow_mut().push(self.hook);
+            if self.fail {
+                return Err(Error::BadProposal(0, "mock failure".to_string()));
+            }
+            Ok(())
+        }
+    }
+
+    /// Test that the hooks are run in the order of their variants, by the
+    /// transitions of the matching subsystems
+    #[test]
+    fn test_epoch_hooks_order() {
+        let mut sorted = EpochHook::ALL;
+        sorted.sort();
+        assert_eq!(sorted, EpochHook::ALL);
+        // governance must conclude the proposals before the treasury
+        // balance is snapshotted
+        assert!(EpochHook::Governance < EpochHook::TreasurySnapshot);
+        assert!(EpochHook::TreasuryInflation < EpochHook::TreasurySnapshot);
+
+        for hook in EpochHook::ALL {
+            let transition = hook.transition::<MockDB, Sha256Hasher>();
+            assert_eq!(transition.hook(), hook);
+        }
+    }
+
+    /// Test that the transitions are run in order, up to the first failing
+    /// one, whose error is tagged with its hook
+    #[test]
+    fn test_failing_epoch_hook() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let mut response = shim::response::FinalizeBlock::default();
+        let log = RefCell::new(vec![]);
+        let recorder = |hook, fail| Recorder {
+            hook,
+            fail,
+            log: &log,
+        };
+        let transitions = [
+            recorder(EpochHook::Governance, false),
+            recorder(EpochHook::TreasuryInflation, true),
+            recorder(EpochHook::TreasurySnapshot, false),
+        ];
+        let transitions: Vec<&dyn EpochTransition<MockDB, Sha256Hasher>> =
+            transitions
+                .iter()
+                .map(|transition| transition as &dyn EpochTransition<_, _>)
+                .collect();
+
+        let err =
+            run_transitions(&mut shell, &mut response, Epoch(1), &transitions)
+                .unwrap_err();
+        assert_eq!(
+            log.into_inner(),
+            vec![EpochHook::Governance, EpochHook::TreasuryInflation]
+        );
+        assert!(matches!(
+            &err,
+            Error::EpochHook(EpochHook::TreasuryInflation, source)
+                if matches!(**source, Error::BadProposal(0, _))
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Error in the treasury_inflation epoch hook: Error executing \
+             proposal 0: mock failure"
+        );
+    }
+
+    /// Test that the activation of the Ethereum bridge contracts scheduled
+    /// by governance emits an event with the old and new addresses, at the
+    /// activation epoch rather than when the update is scheduled
+    #[test]
+    fn test_bridge_contracts_activation_event() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let old_contracts =
+            eth_bridge_params::read_contracts(&shell.wl_storage)
+                .expect("Test failed");
+        let new_contracts = eth_bridge_params::Contracts {
+            native_erc20: EthAddress([1; 20]),
+            bridge: eth_bridge_params::UpgradeableContract {
+                address: EthAddress([2; 20]),
+                version: old_contracts.bridge.version.next(),
+            },
+        };
+        let activation_epoch = Epoch(2);
+        eth_bridge_params::schedule_contracts_update(
+            &mut shell.wl_storage,
+            eth_bridge_params::ScheduledContractsUpdate {
+                activation_epoch,
+                contracts: new_contracts,
+                bridge_pool_nonce: None,
+            },
+        )
+        .expect("Test failed");
+
+        let mut response = shim::response::FinalizeBlock::default();
+        BridgeContracts
+            .on_new_epoch(&mut shell, &mut response, Epoch(1))
+            .expect("Test failed");
+        assert!(response.events.is_empty());
+
+        BridgeContracts
+            .on_new_epoch(&mut shell, &mut response, activation_epoch)
+            .expect("Test failed");
+        let [event] = &response.events[..] else {
+            panic!("Expected a single event, got {:?}", response.events);
+        };
+        assert_eq!(
+            EthBridgeEvent::try_from(event).expect("Test failed"),
+            EthBridgeEvent::new_contracts_activated(
+                activation_epoch,
+                old_contracts.native_erc20,
+                new_contracts.native_erc20,
+                old_contracts.bridge.address,
+                new_contracts.bridge.address,
+            )
+        );
+    }
+}
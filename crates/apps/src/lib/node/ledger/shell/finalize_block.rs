@@ -3,9 +3,12 @@
 use data_encoding::HEXUPPER;
 use masp_primitives::merkle_tree::CommitmentTree;
 use masp_primitives::sapling::Node;
+use namada::ethereum_bridge::protocol::validation::timing as vext_timing;
+use namada::ethereum_bridge::storage::parameters::read_wrapped_supply_backfill_height;
+use namada::ethereum_bridge::storage::wrapped_erc20s;
 use namada::governance::pgf::inflation as pgf_inflation;
-use namada::ledger::events::EventType;
-use namada::ledger::gas::{GasMetering, TxGasMeter};
+use namada::ledger::events::{attributes, EventType};
+use namada::ledger::gas::{BlockGasMeter, Gas, GasMetering, TxGasMeter};
 use namada::ledger::pos::namada_proof_of_stake;
 use namada::ledger::protocol::{self, WrapperArgs};
 use namada::proof_of_stake::storage::{
@@ -17,14 +20,18 @@ use namada::state::write_log::StorageModification;
 use namada::state::{
     ResultExt, StorageRead, StorageWrite, EPOCH_SWITCH_BLOCKS_DELAY,
 };
-use namada::token::conversion::update_allowed_conversions;
 use namada::tx::data::protocol::ProtocolTxType;
 use namada::types::key::tm_raw_hash_to_string;
 use namada::types::storage::{BlockHash, BlockResults, Epoch, Header};
 use namada::vote_ext::ethereum_events::MultiSignedEthEvent;
 use namada::vote_ext::ethereum_tx_data_variants;
 
-use super::governance::execute_governance_proposals;
+use super::epoch_hooks::run_epoch_hooks;
+use super::governance::{
+    execute_deferred_proposals, execute_height_based_proposals,
+    find_initialized_proposals,
+};
+use super::phase_timing::{self, Phase, PhaseTimer};
 use super::*;
 use crate::facade::tendermint::abci::types::{Misbehavior, VoteInfo};
 use crate::node::ledger::shell::stats::InternalStats;
@@ -96,31 +103,56 @@ where
                 .expect("Failed tx hashes finalization")
         }
 
+        // Backfill the minted supply of the wrapped Ethereum assets at the
+        // upgrade height set by governance, from which they are minted and
+        // burned through the token module
+        if read_wrapped_supply_backfill_height(&self.wl_storage)?
+            == Some(height)
+        {
+            wrapped_erc20s::backfill_supply(&mut self.wl_storage)?;
+        }
+
         let pos_params =
             namada_proof_of_stake::storage::read_pos_params(&self.wl_storage)?;
 
-        if new_epoch {
-            update_allowed_conversions(&mut self.wl_storage)?;
-
-            execute_governance_proposals(self, &mut response)?;
+        // Tracks the gas consumed by the governance proposals and the
+        // transactions of the block
+        self.wl_storage.storage.block.gas = BlockGasMeter::default();
 
-            // Copy the new_epoch + pipeline_len - 1 validator set into
-            // new_epoch + pipeline_len
-            namada_proof_of_stake::validator_set_update::copy_validator_sets_and_positions(
-                &mut self.wl_storage,
-                &pos_params,
-                current_epoch,
-                current_epoch + pos_params.pipeline_len,
-            )?;
+        if new_epoch {
+            // The order of the updates of the subsystems at a new epoch is
+            // fixed by the epoch hooks
+            run_epoch_hooks(self, &mut response, current_epoch)?;
+        } else {
+            // Conclude the proposals deferred from the previous block, as
+            // more proposals were due than can be concluded per block
+            let proposals_result =
+                execute_deferred_proposals(self, &mut response)?;
+            if !proposals_result.is_empty() {
+                tracing::debug!(
+                    result = %proposals_result.to_json(),
+                    "Concluded deferred governance proposals at height \
+                     {height}"
+                );
+            }
+        }
 
-            // Compute the total stake of the consensus validator set and record
-            // it in storage
-            namada_proof_of_stake::compute_and_store_total_consensus_stake(
-                &mut self.wl_storage,
-                current_epoch,
-            )?;
+        // Conclude the proposals whose voting period, expressed in block
+        // heights, ends at this height
+        let proposals_result =
+            execute_height_based_proposals(self, &mut response)?;
+        if !proposals_result.is_empty() {
+            tracing::debug!(
+                result = %proposals_result.to_json(),
+                "Concluded governance proposals at height {height}"
+            );
         }
 
+        let pos_span =
+            tracing::debug_span!("pos_updates", epoch = %current_epoch)
+                .entered();
+        let pos_timer = PhaseTimer::new(Phase::PosUpdates);
+
         // Get the actual votes from cometBFT in the preferred format
         let votes = pos_votes_from_abci(&self.wl_storage, &req.votes);
 
@@ -189,6 +221,8 @@ where
                 current_epoch,
             )?;
         }
+        drop(pos_timer);
+        drop(pos_span);
 
         let mut stats = InternalStats::default();
 
@@ -240,6 +274,7 @@ where
                 tx_event["info"] =
                     format!("Tx rejected: {}", &processed_tx.result.info);
                 tx_event["gas_used"] = "0".into();
+                self.record_tx_gas(tx_index, Gas::default());
                 response.events.push(tx_event);
                 continue;
             }
@@ -262,6 +297,7 @@ where
                 tx_event["info"] =
                     format!("Tx rejected: {}", &processed_tx.result.info);
                 tx_event["gas_used"] = "0".into();
+                self.record_tx_gas(tx_index, Gas::default());
                 response.events.push(tx_event);
                 // if the rejected tx was decrypted, remove it
                 // from the queue of txs to be processed
@@ -352,6 +388,7 @@ where
                     ProtocolTxType::BridgePoolVext
                     | ProtocolTxType::BridgePool
                     | ProtocolTxType::ValSetUpdateVext
+                    | ProtocolTxType::ContractAddressesVext
                     | ProtocolTxType::ValidatorSetUpdate => (
                         Event::new_tx_event(&tx, height.0),
                         None,
@@ -416,6 +453,17 @@ where
                 },
             };
 
+            // Protocol txs apply the vote extensions aggregated by the
+            // Ethereum bridge
+            let vext_aggregation =
+                matches!(tx_header.tx_type, TxType::Protocol(_)).then(|| {
+                    let span = tracing::debug_span!(
+                        "aggregate_vote_extensions",
+                        tx_index
+                    )
+                    .entered();
+                    (span, PhaseTimer::new(Phase::VextAggregation))
+                });
             let tx_result = protocol::check_tx_allowed(&tx, &self.wl_storage)
                 .and_then(|()| {
                     protocol::dispatch_tx(
@@ -434,6 +482,7 @@ where
                     )
                 })
                 .map_err(Error::TxApply);
+            drop(vext_aggregation);
             match tx_result {
                 Ok(result) => {
                     if result.is_accepted() {
@@ -469,6 +518,19 @@ where
                                 tx_event["is_valid_masp_tx"] =
                                     format!("{}", tx_index);
                             }
+                            // Let the authors of the proposals submitted
+                            // by this tx learn the ids assigned to them
+                            let proposal_ids = find_initialized_proposals(
+                                &self.wl_storage,
+                                &result.changed_keys,
+                            )?;
+                            if !proposal_ids.is_empty() {
+                                tx_event[attributes::PROPOSAL_ID] =
+                                    proposal_ids
+                                        .map(|id| id.to_string())
+                                        .collect::<Vec<_>>()
+                                        .join(",");
+                            }
                             changed_keys
                                 .extend(result.changed_keys.iter().cloned());
                             stats.increment_successful_txs();
@@ -528,6 +590,7 @@ where
                         tx_event["code"] = ResultCode::InvalidTx.into();
                     }
                     tx_event["gas_used"] = result.gas_used.to_string();
+                    self.record_tx_gas(tx_index, result.gas_used);
                     tx_event["info"] = "Check inner_tx for result.".to_string();
                     tx_event["inner_tx"] = result.to_string();
                 }
@@ -572,8 +635,9 @@ where
                     stats.increment_errored_txs();
                     self.wl_storage.drop_tx();
 
-                    tx_event["gas_used"] =
-                        tx_gas_meter.get_tx_consumed_gas().to_string();
+                    let gas_used = tx_gas_meter.get_tx_consumed_gas();
+                    tx_event["gas_used"] = gas_used.to_string();
+                    self.record_tx_gas(tx_index, gas_used);
                     tx_event["info"] = msg.to_string();
                     if let EventType::Accepted = tx_event.event_type {
                         // If wrapper, invalid tx error code
@@ -607,6 +671,26 @@ where
         tracing::info!("{}", stats);
         tracing::info!("{}", stats.format_tx_executed());
 
+        let vext_timings = vext_timing::take_block_timings();
+        if !vext_timings.is_empty() {
+            tracing::info!(
+                "Vote extension validation timings: {}",
+                vext_timings
+            );
+        }
+
+        let phase_timings = phase_timing::take_block_timings();
+        if !phase_timings.is_empty() {
+            tracing::info!(
+                "Slowest FinalizeBlock phases of block {height}: {}",
+                phase_timings
+            );
+            tracing::debug!(
+                "Cumulative FinalizeBlock phase timings: {:?}",
+                phase_timing::cumulative_timings()
+            );
+        }
+
         // Update the MASP commitment tree anchor if the tree was updated
         let tree_key = token::storage_key::masp_commitment_tree_key();
         if let Some(StorageModification::Write { value }) =
@@ -632,6 +716,17 @@ where
             native_block_proposer_address,
         )?;
 
+        // publish the changes of the watched storage keys. The block write
+        // log is not modified past this point, so these are the changes that
+        // will be committed.
+        let storage_changes =
+            self.change_feed.collect_changes(&self.wl_storage, height);
+        response.events.extend(storage_changes);
+
+        // archive the Ethereum bridge proofs completed in this block
+        self.proof_archiver
+            .archive_completed_proofs(&self.wl_storage, height);
+
         self.event_log_mut().log_events(response.events.clone());
         tracing::debug!("End finalize_block {height} of epoch {current_epoch}");
 
@@ -789,6 +884,20 @@ where
             .delete_tx_hash(wrapper_tx.header_hash())
             .expect("Error while deleting tx hash from storage");
     }
+
+    /// Record the gas consumed by the tx at the given position in the block,
+    /// next to its result code.
+    fn record_tx_gas(&mut self, tx_index: usize, gas: Gas) {
+        let tx_index = tx_index
+            .try_into()
+            .expect("transaction index out of bounds");
+        self.wl_storage
+            .storage
+            .block
+            .gas
+            .add_tx_gas(tx_index, gas)
+            .expect("The gas consumed by a block cannot overflow");
+    }
 }
 
 /// Convert ABCI vote info to PoS vote info. Any info which fails the conversion
@@ -868,9 +977,17 @@ mod test_finalize_block {
         self, get_key_from_hash, get_nonce_key, get_signed_root_key,
     };
     use namada::eth_bridge::storage::min_confirmations_key;
+    use namada::eth_bridge::storage::parameters::set_wrapped_supply_backfill_height;
     use namada::ethereum_bridge::storage::wrapped_erc20s;
-    use namada::governance::storage::keys::get_proposal_execution_key;
+    use namada::governance::pgf::ADDRESS as pgf_address;
+    use namada::governance::storage::keys::{
+        get_base_deposit_key, get_committing_proposals_key,
+        get_deposit_per_byte_key, get_max_proposal_lifetime_key,
+        get_max_treasury_balance_key, get_treasury_high_water_mark_key,
+        get_treasury_inflation_rate_key,
+    };
     use namada::governance::storage::proposal::ProposalType;
+    use namada::governance::utils::TallyResult;
     use namada::governance::{InitProposalData, VoteProposalData};
     use namada::ledger::gas::VpGasMeter;
     use namada::ledger::native_vp::parameters::ParametersVp;
@@ -915,10 +1032,13 @@ mod test_finalize_block {
     use test_log::test;
 
     use super::*;
+    use crate::config::change_feed::Config as ChangeFeedConfig;
     use crate::facade::tendermint::abci::types::{
         Misbehavior, Validator, VoteInfo,
     };
     use crate::node::ledger::oracle::control::Command;
+    use crate::node::ledger::shell::change_feed::ChangeFeed;
+    use crate::node::ledger::shell::governance::execute_governance_proposals;
     use crate::node::ledger::shell::test_utils::*;
     use crate::node::ledger::shims::abcipp_shim_types::shim::request::{
         FinalizeBlock, ProcessedTx,
@@ -1267,6 +1387,52 @@ mod test_finalize_block {
         assert_eq!(counter, 2);
     }
 
+    /// Test that the gas consumed by each tx of a block is recorded next to
+    /// its result, matches the gas reported in its event, and sums up to the
+    /// gas consumed by the whole block
+    #[test]
+    fn test_block_gas_per_tx() {
+        let (mut shell, _, _, _) = setup();
+        let keypair = gen_keypair();
+        let mut processed_txs = vec![];
+
+        // Add unshielded balance for fee payment
+        let balance_key = token::storage_key::balance_key(
+            &shell.wl_storage.storage.native_token,
+            &Address::from(&keypair.ref_to()),
+        );
+        shell
+            .wl_storage
+            .storage
+            .write(&balance_key, Amount::native_whole(1000).serialize_to_vec())
+            .unwrap();
+
+        for _ in 0..2 {
+            let (_, processed_tx) = mk_wrapper_tx(&shell, &keypair);
+            processed_txs.push(processed_tx);
+        }
+        let events = shell
+            .finalize_block(FinalizeBlock {
+                txs: processed_txs,
+                ..Default::default()
+            })
+            .expect("Test failed");
+        assert_eq!(events.len(), 2);
+
+        let block_gas = &shell.wl_storage.storage.block.gas;
+        let mut sum = Gas::default();
+        for (index, event) in events.iter().enumerate() {
+            let tx_gas = block_gas
+                .get_tx_gas(index as u32)
+                .expect("The gas of each tx must be recorded");
+            assert!(tx_gas > Gas::default());
+            assert_eq!(event["gas_used"], tx_gas.to_string());
+            sum = sum.checked_add(tx_gas).unwrap();
+        }
+        assert_eq!(block_gas.iter_txs_gas().count(), 2);
+        assert_eq!(sum, block_gas.get_block_consumed_gas());
+    }
+
     /// Test if a rejected protocol tx is applied and emits
     /// the correct event
     #[test]
@@ -1279,6 +1445,7 @@ mod test_finalize_block {
         let tx = EthereumTxData::EthereumEvents(ethereum_events::VextDigest {
             signatures: Default::default(),
             events: vec![],
+            bridge_contracts: Default::default(),
         })
         .sign(&protocol_key, shell.chain_id.clone())
         .to_bytes();
@@ -1320,7 +1487,9 @@ mod test_finalize_block {
             nonce: 0u64.into(),
             transfers: vec![],
         };
-        tokio_test::block_on(oracle.send(event.clone())).expect("Test failed");
+        let bridge_contract = shell.bridge_contract();
+        tokio_test::block_on(oracle.send((bridge_contract, event.clone())))
+            .expect("Test failed");
         let [queued_event]: [EthereumEvent; 1] =
             shell.new_ethereum_events().try_into().expect("Test failed");
         assert_eq!(queued_event, event);
@@ -1330,6 +1499,7 @@ mod test_finalize_block {
             block_height: shell.wl_storage.storage.get_last_block_height(),
             ethereum_events: vec![event.clone()],
             validator_addr: address.clone(),
+            bridge_contract,
         }
         .sign(&protocol_key);
 
@@ -1342,14 +1512,14 @@ mod test_finalize_block {
                 )]),
             };
 
+            let key =
+                (address, shell.wl_storage.storage.get_last_block_height());
             let digest = ethereum_events::VextDigest {
-                signatures: vec![(
-                    (address, shell.wl_storage.storage.get_last_block_height()),
-                    ext.sig,
-                )]
-                .into_iter()
-                .collect(),
+                signatures: vec![(key.clone(), ext.sig)].into_iter().collect(),
                 events: vec![signed],
+                bridge_contracts: vec![(key, ext.data.bridge_contract)]
+                    .into_iter()
+                    .collect(),
             };
             ProcessedTx {
                 tx: EthereumTxData::EthereumEvents(digest)
@@ -1399,7 +1569,9 @@ mod test_finalize_block {
             nonce: 0u64.into(),
             transfers: vec![],
         };
-        tokio_test::block_on(oracle.send(event.clone())).expect("Test failed");
+        let bridge_contract = shell.bridge_contract();
+        tokio_test::block_on(oracle.send((bridge_contract, event.clone())))
+            .expect("Test failed");
         let [queued_event]: [EthereumEvent; 1] =
             shell.new_ethereum_events().try_into().expect("Test failed");
         assert_eq!(queued_event, event);
@@ -1409,6 +1581,7 @@ mod test_finalize_block {
             block_height: shell.wl_storage.storage.get_last_block_height(),
             ethereum_events: vec![event],
             validator_addr: address,
+            bridge_contract,
         }
         .sign(&protocol_key);
         let processed_tx = ProcessedTx {
@@ -1511,6 +1684,55 @@ mod test_finalize_block {
         }
     }
 
+    #[test]
+    /// Test that the minted supply of the wrapped Ethereum assets is
+    /// backfilled from their balances exactly once, at the upgrade height
+    /// set by governance.
+    fn test_wrapped_supply_backfill_at_upgrade_height() {
+        let (mut shell, _, _, _) = setup();
+        let token = wrapped_erc20s::token(&EthAddress([0xff; 20]));
+        let owner_key = token::storage_key::balance_key(
+            &token,
+            &crate::wallet::defaults::bertha_address(),
+        );
+        let supply_key = token::storage_key::minted_balance_key(&token);
+        let read_supply = |shell: &TestShell| -> Option<Amount> {
+            shell.wl_storage.read(&supply_key).expect("Test failed")
+        };
+
+        // a balance written before the supply was tracked
+        shell
+            .wl_storage
+            .write(&owner_key, Amount::from(10))
+            .expect("Test failed");
+        let backfill_height =
+            shell.wl_storage.storage.get_last_block_height() + 2;
+        set_wrapped_supply_backfill_height(
+            &mut shell.wl_storage,
+            backfill_height,
+        )
+        .expect("Test failed");
+
+        // nothing is backfilled before the upgrade height
+        shell.finalize_and_commit(None);
+        assert_eq!(read_supply(&shell), None);
+
+        shell.finalize_and_commit(None);
+        assert_eq!(
+            shell.wl_storage.storage.get_last_block_height(),
+            backfill_height
+        );
+        assert_eq!(read_supply(&shell), Some(Amount::from(10)));
+
+        // nor is it backfilled again past the upgrade height
+        shell
+            .wl_storage
+            .write(&owner_key, Amount::from(15))
+            .expect("Test failed");
+        shell.finalize_and_commit(None);
+        assert_eq!(read_supply(&shell), Some(Amount::from(10)));
+    }
+
     #[test]
     /// Test that adding a new erc20 transfer to the bridge pool
     /// increments the pool's nonce.
@@ -1591,6 +1813,7 @@ mod test_finalize_block {
                         .storage
                         .get_last_block_height(),
                     ethereum_events: vec![ethereum_event],
+                    bridge_contract: shell.bridge_contract(),
                 }
                 .sign(&protocol_key);
                 assert!(ext.verify(&protocol_key.ref_to()).is_ok());
@@ -1659,6 +1882,9 @@ mod test_finalize_block {
                 voting_start_epoch: Epoch::default(),
                 voting_end_epoch: Epoch::default().next(),
                 grace_epoch: Epoch::default().next(),
+                voting_heights: None,
+                priority: None,
+                code_chunks: vec![],
                 r#type: ProposalType::Default(None),
             };
 
@@ -1667,6 +1893,7 @@ mod test_finalize_block {
                 proposal,
                 vec![],
                 None,
+                vec![],
             )
             .unwrap();
 
@@ -1783,6 +2010,509 @@ mod test_finalize_block {
         }
     }
 
+    /// Test that a proposal queued for longer than the maximum proposal
+    /// lifetime is rejected before reaching its voting end epoch, that its
+    /// funds are moved to the treasury and that an expired event is emitted.
+    #[test]
+    fn test_expired_proposal_is_rejected() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let validator = shell.mode.get_validator_address().unwrap().clone();
+        let native_token = shell.wl_storage.storage.native_token.clone();
+
+        shell
+            .wl_storage
+            .write(&get_max_proposal_lifetime_key(), 2_u64)
+            .unwrap();
+
+        // A proposal whose voting end epoch is far beyond its max lifetime
+        let proposal_id = 0;
+        let grace_epoch = Epoch(1006);
+        let proposal = InitProposalData {
+            id: proposal_id,
+            content: Hash::default(),
+            author: validator,
+            voting_start_epoch: Epoch::default(),
+            voting_end_epoch: Epoch(1000),
+            grace_epoch,
+            voting_heights: None,
+            priority: None,
+            code_chunks: vec![],
+            r#type: ProposalType::Default(None),
+        };
+        namada::governance::init_proposal(
+            &mut shell.wl_storage,
+            proposal,
+            vec![],
+            None,
+            vec![],
+        )
+        .unwrap();
+        let funds =
+            namada::governance::storage::get_parameters(&shell.wl_storage)
+                .unwrap()
+                .min_proposal_fund;
+        let treasury_pre =
+            token::read_balance(&shell.wl_storage, &native_token, &pgf_address)
+                .unwrap();
+
+        // Within the max lifetime, the proposal is left untouched
+        shell.wl_storage.storage.block.epoch = Epoch(2);
+        let mut response = shim::response::FinalizeBlock::default();
+        execute_governance_proposals(&mut shell, &mut response).unwrap();
+        assert!(response.events.is_empty());
+        assert!(
+            namada::governance::storage::get_proposal_result(
+                &shell.wl_storage,
+                proposal_id
+            )
+            .unwrap()
+            .is_none()
+        );
+
+        // Past the max lifetime, the proposal expires
+        shell.wl_storage.storage.block.epoch = Epoch(3);
+        let mut response = shim::response::FinalizeBlock::default();
+        execute_governance_proposals(&mut shell, &mut response).unwrap();
+        assert_eq!(response.events.len(), 1);
+        assert_eq!(response.events[0]["proposal_id"], proposal_id.to_string());
+        assert_eq!(response.events[0][attributes::EXPIRED], "true");
+
+        let result = namada::governance::storage::get_proposal_result(
+            &shell.wl_storage,
+            proposal_id,
+        )
+        .unwrap()
+        .expect("The expired proposal must have a result");
+        assert!(matches!(result.result, TallyResult::Rejected));
+        assert!(
+            !shell
+                .wl_storage
+                .has_key(&get_committing_proposals_key(
+                    proposal_id,
+                    grace_epoch.0
+                ))
+                .unwrap()
+        );
+        let treasury_post =
+            token::read_balance(&shell.wl_storage, &native_token, &pgf_address)
+                .unwrap();
+        assert_eq!(treasury_post, treasury_pre + funds);
+
+        // The proposal is expired only once
+        let mut response = shim::response::FinalizeBlock::default();
+        execute_governance_proposals(&mut shell, &mut response).unwrap();
+        assert!(response.events.is_empty());
+    }
+
+    /// Test that a warning event is emitted when the funds of an expired
+    /// proposal raise the treasury balance to its high-water mark, and not
+    /// when the balance stays below it.
+    #[test]
+    fn test_treasury_high_water_mark_warning() {
+        for (margin, expect_warning) in [(0, true), (1, false)] {
+            let (mut shell, _broadcaster, _, _eth_control) = setup();
+            let validator = shell.mode.get_validator_address().unwrap().clone();
+            let native_token = shell.wl_storage.storage.native_token.clone();
+
+            shell
+                .wl_storage
+                .write(&get_max_proposal_lifetime_key(), 2_u64)
+                .unwrap();
+            let proposal = InitProposalData {
+                id: 0,
+                content: Hash::default(),
+                author: validator,
+                voting_start_epoch: Epoch::default(),
+                voting_end_epoch: Epoch(1000),
+                grace_epoch: Epoch(1006),
+                voting_heights: None,
+                priority: None,
+                code_chunks: vec![],
+                r#type: ProposalType::Default(None),
+            };
+            namada::governance::init_proposal(
+                &mut shell.wl_storage,
+                proposal,
+                vec![],
+                None,
+                vec![],
+            )
+            .unwrap();
+
+            let funds =
+                namada::governance::storage::get_parameters(&shell.wl_storage)
+                    .unwrap()
+                    .min_proposal_fund;
+            let treasury_pre = token::read_balance(
+                &shell.wl_storage,
+                &native_token,
+                &pgf_address,
+            )
+            .unwrap();
+            let high_water_mark =
+                treasury_pre + funds + Amount::native_whole(margin);
+            shell
+                .wl_storage
+                .write(
+                    &get_treasury_high_water_mark_key(),
+                    Some(high_water_mark),
+                )
+                .unwrap();
+
+            // The proposal expires, moving its funds to the treasury
+            shell.wl_storage.storage.block.epoch = Epoch(3);
+            let mut response = shim::response::FinalizeBlock::default();
+            execute_governance_proposals(&mut shell, &mut response).unwrap();
+
+            let warnings: Vec<_> = response
+                .events
+                .iter()
+                .filter(|event| event.get(attributes::WARNING).is_some())
+                .collect();
+            if expect_warning {
+                assert_eq!(warnings.len(), 1);
+                assert_eq!(
+                    warnings[0][attributes::WARNING],
+                    "treasury_high_water_mark"
+                );
+                assert_eq!(
+                    warnings[0]["treasury_balance"],
+                    (treasury_pre + funds).to_string_native()
+                );
+            } else {
+                assert!(warnings.is_empty());
+            }
+            // The funds were moved regardless
+            assert_eq!(
+                token::read_balance(
+                    &shell.wl_storage,
+                    &native_token,
+                    &pgf_address
+                )
+                .unwrap(),
+                treasury_pre + funds
+            );
+        }
+    }
+
+    /// Test that the funds of an expired proposal raising the treasury
+    /// balance over its maximum are burned in excess of the maximum, and
+    /// that the balance already held above the maximum isn't burned.
+    #[test]
+    fn test_treasury_overflow_burned() {
+        for (cap_above_pre, expect_burned) in [
+            (Some(Amount::native_whole(200)), Amount::native_whole(300)),
+            (None, Amount::native_whole(500)),
+        ] {
+            let (mut shell, _broadcaster, _, _eth_control) = setup();
+            let validator = shell.mode.get_validator_address().unwrap().clone();
+            let native_token = shell.wl_storage.storage.native_token.clone();
+
+            shell
+                .wl_storage
+                .write(&get_max_proposal_lifetime_key(), 2_u64)
+                .unwrap();
+            let proposal = InitProposalData {
+                id: 0,
+                content: Hash::default(),
+                author: validator,
+                voting_start_epoch: Epoch::default(),
+                voting_end_epoch: Epoch(1000),
+                grace_epoch: Epoch(1006),
+                voting_heights: None,
+                priority: None,
+                code_chunks: vec![],
+                r#type: ProposalType::Default(None),
+            };
+            namada::governance::init_proposal(
+                &mut shell.wl_storage,
+                proposal,
+                vec![],
+                None,
+                vec![],
+            )
+            .unwrap();
+
+            let funds =
+                namada::governance::storage::get_parameters(&shell.wl_storage)
+                    .unwrap()
+                    .min_proposal_fund;
+            assert_eq!(funds, Amount::native_whole(500));
+            token::credit_tokens(
+                &mut shell.wl_storage,
+                &native_token,
+                &pgf_address,
+                Amount::native_whole(1_000),
+            )
+            .unwrap();
+            let treasury_pre = token::read_balance(
+                &shell.wl_storage,
+                &native_token,
+                &pgf_address,
+            )
+            .unwrap();
+            // Without a margin, the treasury is already over its maximum
+            let max_treasury_balance = match cap_above_pre {
+                Some(margin) => treasury_pre + margin,
+                None => treasury_pre - Amount::native_whole(1),
+            };
+            shell
+                .wl_storage
+                .write(
+                    &get_max_treasury_balance_key(),
+                    Some(max_treasury_balance),
+                )
+                .unwrap();
+            let supply_pre =
+                token::read_total_supply(&shell.wl_storage, &native_token)
+                    .unwrap();
+
+            // The proposal expires, moving its funds to the treasury
+            shell.wl_storage.storage.block.epoch = Epoch(3);
+            let mut response = shim::response::FinalizeBlock::default();
+            execute_governance_proposals(&mut shell, &mut response).unwrap();
+
+            let burned: Vec<_> = response
+                .events
+                .iter()
+                .filter(|event| event.get("treasury_overflow_burned").is_some())
+                .collect();
+            assert_eq!(burned.len(), 1);
+            assert_eq!(
+                burned[0]["treasury_overflow_burned"],
+                expect_burned.to_string_native()
+            );
+            assert_eq!(
+                burned[0]["max_treasury_balance"],
+                max_treasury_balance.to_string_native()
+            );
+            let treasury_post = token::read_balance(
+                &shell.wl_storage,
+                &native_token,
+                &pgf_address,
+            )
+            .unwrap();
+            assert_eq!(treasury_post, treasury_pre + funds - expect_burned);
+            assert_eq!(
+                burned[0]["treasury_balance"],
+                treasury_post.to_string_native()
+            );
+            assert_eq!(
+                token::read_total_supply(&shell.wl_storage, &native_token)
+                    .unwrap(),
+                supply_pre - expect_burned
+            );
+        }
+    }
+
+    /// Test that the treasury inflation is minted to the treasury at the
+    /// first block of a new epoch, and that it's reported in an event along
+    /// with the new total supply
+    #[test]
+    fn test_treasury_inflation_at_epoch_change() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        shell
+            .wl_storage
+            .write(&get_treasury_inflation_rate_key(), Dec::new(1, 2).unwrap())
+            .unwrap();
+
+        let epoch = shell.start_new_epoch(None);
+        let minted = namada::governance::storage::get_treasury_inflation_at(
+            &shell.wl_storage,
+            epoch,
+        )
+        .unwrap()
+        .expect("Test failed");
+        assert!(!minted.is_zero());
+
+        let events: Vec<_> = shell
+            .event_log()
+            .iter()
+            .filter(|event| event.get("treasury_inflation").is_some())
+            .collect();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["treasury_inflation"], minted.to_string_native());
+        assert_eq!(events[0][attributes::EPOCH], epoch.to_string());
+        assert!(events[0].get("total_supply").is_some());
+    }
+
+    /// Test that a proposal locks a deposit scaling with its size and that
+    /// exactly the locked deposit is refunded on execution, even if the
+    /// deposit parameters changed in between.
+    #[test]
+    fn test_proposal_deposit_refund_after_param_change() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let validator = shell.mode.get_validator_address().unwrap().clone();
+        let native_token = shell.wl_storage.storage.native_token.clone();
+
+        shell
+            .wl_storage
+            .write(&get_base_deposit_key(), Amount::native_whole(400))
+            .unwrap();
+        shell
+            .wl_storage
+            .write(&get_deposit_per_byte_key(), Amount::native_whole(1))
+            .unwrap();
+
+        let proposal_id = 0;
+        let content = vec![0_u8; 150];
+        let code = vec![0_u8; 50];
+        let proposal = InitProposalData {
+            id: proposal_id,
+            content: Hash::default(),
+            author: validator.clone(),
+            voting_start_epoch: Epoch::default(),
+            voting_end_epoch: Epoch::default().next(),
+            grace_epoch: Epoch::default().next(),
+            voting_heights: None,
+            priority: None,
+            code_chunks: vec![],
+            r#type: ProposalType::Default(Some(Hash::default())),
+        };
+        let author_balance_pre =
+            token::read_balance(&shell.wl_storage, &native_token, &validator)
+                .unwrap();
+        namada::governance::init_proposal(
+            &mut shell.wl_storage,
+            proposal,
+            content,
+            Some(code),
+            vec![],
+        )
+        .unwrap();
+
+        // base deposit plus 200 bytes of content and code
+        let locked_funds = Amount::native_whole(600);
+        assert_eq!(
+            namada::governance::storage::facade::ProposalStorageReader::new(
+                &shell.wl_storage
+            )
+            .funds(proposal_id)
+            .unwrap(),
+            Some(locked_funds)
+        );
+        let author_balance =
+            token::read_balance(&shell.wl_storage, &native_token, &validator)
+                .unwrap();
+        assert_eq!(author_balance, author_balance_pre - locked_funds);
+
+        // Vote to accept the proposal (there's only one validator, so its
+        // vote decides)
+        let vote = VoteProposalData {
+            id: proposal_id,
+            vote: ProposalVote::Yay,
+            voter: validator.clone(),
+            delegations: vec![validator.clone()],
+        };
+        namada::governance::vote_proposal(&mut shell.wl_storage, vote).unwrap();
+
+        // The deposit parameters change before the proposal is executed
+        shell
+            .wl_storage
+            .write(&get_base_deposit_key(), Amount::native_whole(1_000))
+            .unwrap();
+        shell
+            .wl_storage
+            .write(&get_deposit_per_byte_key(), Amount::native_whole(10))
+            .unwrap();
+
+        shell.proposal_data.insert(proposal_id);
+        shell.wl_storage.storage.block.epoch = Epoch::default().next();
+        let mut response = shim::response::FinalizeBlock::default();
+        execute_governance_proposals(&mut shell, &mut response).unwrap();
+
+        let result = namada::governance::storage::get_proposal_result(
+            &shell.wl_storage,
+            proposal_id,
+        )
+        .unwrap()
+        .unwrap();
+        assert!(matches!(result.result, TallyResult::Passed));
+        let author_balance_post =
+            token::read_balance(&shell.wl_storage, &native_token, &validator)
+                .unwrap();
+        assert_eq!(author_balance_post, author_balance_pre);
+    }
+
+    /// Test that the changes of the watched storage keys are published as
+    /// events of the block that commits them, leaving out writes that don't
+    /// change the value of a key, and that changes over the maximum per
+    /// block are dropped.
+    #[test]
+    fn test_storage_change_feed() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let watched_key = get_max_proposal_lifetime_key();
+        let storage_change_events = |events: Vec<Event>| {
+            events
+                .into_iter()
+                .filter(|event| event.event_type == EventType::StorageChange)
+                .collect::<Vec<_>>()
+        };
+
+        let old_value: u64 =
+            shell.wl_storage.read(&watched_key).unwrap().unwrap();
+        let new_value = old_value + 1;
+        shell.wl_storage.write(&watched_key, new_value).unwrap();
+        // not a watched key
+        shell
+            .wl_storage
+            .write(&min_confirmations_key(), MinimumConfirmations::default())
+            .unwrap();
+
+        let events = storage_change_events(
+            shell
+                .finalize_block(FinalizeBlock {
+                    txs: vec![],
+                    ..Default::default()
+                })
+                .expect("Test failed"),
+        );
+        let [event]: [Event; 1] = events.try_into().expect("Test failed");
+        assert_eq!(
+            event.get(attributes::STORAGE_KEY),
+            Some(&watched_key.to_string())
+        );
+        assert_eq!(
+            event.get("old_value_hash"),
+            Some(&Hash::sha256(old_value.serialize_to_vec()).to_string())
+        );
+        assert_eq!(
+            event.get("new_value"),
+            Some(&HEXUPPER.encode(&new_value.serialize_to_vec()))
+        );
+        shell.commit();
+
+        // writing the same value again is not a change
+        shell.wl_storage.write(&watched_key, new_value).unwrap();
+        let events = shell
+            .finalize_block(FinalizeBlock {
+                txs: vec![],
+                ..Default::default()
+            })
+            .expect("Test failed");
+        assert!(storage_change_events(events).is_empty());
+        shell.commit();
+
+        // changes over the maximum per block are dropped
+        shell.change_feed = ChangeFeed::new(&ChangeFeedConfig {
+            max_changes_per_block: 0,
+            ..Default::default()
+        });
+        shell.wl_storage.write(&watched_key, old_value).unwrap();
+        let events = storage_change_events(
+            shell
+                .finalize_block(FinalizeBlock {
+                    txs: vec![],
+                    ..Default::default()
+                })
+                .expect("Test failed"),
+        );
+        let [event]: [Event; 1] = events.try_into().expect("Test failed");
+        assert_eq!(event.get(attributes::STORAGE_KEY), None);
+        assert_eq!(event.get("dropped_changes"), Some(&"1".to_string()));
+        assert_eq!(shell.change_feed.dropped_changes(), 1);
+    }
+
     /// A unit test for PoS inflationary rewards
     #[test]
     fn test_inflation_accounting() {
@@ -5090,11 +5820,11 @@ mod test_finalize_block {
     async fn test_eth_bridge_param_updates() {
         let (mut shell, _broadcaster, _, mut control_receiver) =
             setup_at_height(3u64);
-        let proposal_execution_key = get_proposal_execution_key(0);
-        shell
-            .wl_storage
-            .write(&proposal_execution_key, 0u64)
-            .expect("Test failed.");
+        namada::governance::storage::write_pending_execution(
+            &mut shell.wl_storage,
+            0,
+        )
+        .expect("Test failed.");
         let mut tx = Tx::new(shell.chain_id.clone(), None);
         tx.add_code_from_hash(Hash::default(), None).add_data(0u64);
         let new_min_confirmations = MinimumConfirmations::from(unsafe {
@@ -1,38 +1,227 @@
-use std::collections::HashMap;
+use std::any::Any;
+use std::collections::BTreeSet;
+use std::panic::{self, AssertUnwindSafe};
 
+use namada::eth_bridge::storage as bridge_storage;
+use namada::eth_bridge::storage::parameters::{
+    read_contracts, schedule_contracts_update, set_bridge_paused, Contracts,
+    ScheduledContractsUpdate, UpgradeableContract,
+};
+use namada::governance::parameters::ParameterConflictPolicy;
 use namada::governance::pgf::storage::keys as pgf_storage;
 use namada::governance::pgf::storage::steward::StewardDetail;
 use namada::governance::pgf::{storage as pgf, ADDRESS};
-use namada::governance::storage::keys as gov_storage;
+use namada::governance::storage::facade::ProposalStorageReader;
 use namada::governance::storage::proposal::{
-    AddRemove, PGFAction, PGFTarget, ProposalType, StoragePgfFunding,
+    AddRemove, BridgeContractsUpdate, BridgePauseAction, EthHotKeyRotation,
+    PGFAction, PGFTarget, ParameterChange, ProposalType, StoragePgfFunding,
+    TreasurySpend, TxOrVp, UpdateWasmAllowlist,
 };
+use namada::governance::storage::{code as gov_code, keys as gov_storage};
 use namada::governance::utils::{
-    compute_proposal_result, ProposalVotes, TallyResult, TallyType, TallyVote,
-    VotePower,
+    dec_fraction_of, AppliedParameterChange, ProposalExecutionResult,
+    ProposalOutput, ProposalResult, ReleasedFunds, TallyBasisPoints,
+    TallyResult, TallyType, TieBreakSeed, VpVerdict,
 };
 use namada::governance::{storage as gov_api, ADDRESS as gov_address};
-use namada::ledger::governance::utils::ProposalEvent;
-use namada::ledger::pos::BondId;
+use namada::ledger::events::{self, EventBuilder, EventLevel, EventType};
+use namada::ledger::gas::{
+    GasMetering, MEMORY_ACCESS_GAS_PER_BYTE, STORAGE_ACCESS_GAS_PER_BYTE,
+};
+use namada::ledger::governance::utils::{
+    treasury_high_water_mark_event, treasury_inflation_event,
+    treasury_overflow_burned_event, ProposalEvent,
+};
 use namada::ledger::protocol;
-use namada::proof_of_stake::bond_amount;
-use namada::proof_of_stake::parameters::PosParams;
-use namada::proof_of_stake::storage::read_total_stake;
-use namada::state::{DBIter, StorageHasher, StorageWrite, DB};
+use namada::proof_of_stake::queries::{
+    compute_proposal_tally, compute_proposal_votes, tally_proposal_votes,
+};
+use namada::proof_of_stake::storage::{
+    read_consensus_validator_set_addresses, read_pos_params,
+    validator_eth_cold_key_handle, validator_eth_hot_key_handle,
+};
+use namada::state::{DBIter, ResultExt, StorageHasher, StorageWrite, DB};
 use namada::tx::{Code, Data};
 use namada::types::address::Address;
 use namada::types::encode;
-use namada::types::storage::Epoch;
+use namada::types::storage::{Epoch, Key};
 use namada::{ibc, token};
-use namada_sdk::proof_of_stake::storage::read_validator_stake;
+use serde::Serialize;
 
-use super::utils::force_read;
+use super::phase_timing::{Phase, PhaseTimer};
 use super::*;
 
-#[derive(Default)]
+/// The ids of the governance proposals concluded in some block, by outcome.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
 pub struct ProposalsResult {
     passed: Vec<u64>,
     rejected: Vec<u64>,
+    expired: Vec<u64>,
+}
+
+impl ProposalsResult {
+    /// Get the ids of the proposals that passed.
+    pub fn passed(&self) -> &[u64] {
+        &self.passed
+    }
+
+    /// Get the ids of the proposals that were rejected.
+    pub fn rejected(&self) -> &[u64] {
+        &self.rejected
+    }
+
+    /// Get the ids of the proposals that expired before being tallied.
+    pub fn expired(&self) -> &[u64] {
+        &self.expired
+    }
+
+    /// Check if no proposal was concluded.
+    pub fn is_empty(&self) -> bool {
+        self.passed.is_empty()
+            && self.rejected.is_empty()
+            && self.expired.is_empty()
+    }
+
+    /// Render the result as JSON, for debugging and scripting.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self)
+            .expect("Serializing a proposals result must not fail")
+    }
+}
+
+/// The proposals on which the in-memory queue of the proposals due at the
+/// next epoch change disagrees with the proposals queued in storage.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ProposalQueueMismatch {
+    /// The proposals queued in storage but missing from the in-memory queue,
+    /// which would have never been concluded
+    pub missing: BTreeSet<u64>,
+    /// The proposals of the in-memory queue which are not queued in storage,
+    /// which would have been concluded again
+    pub unexpected: BTreeSet<u64>,
+}
+
+impl ProposalQueueMismatch {
+    /// Check if the in-memory queue matches storage.
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.unexpected.is_empty()
+    }
+}
+
+impl<D, H> Shell<D, H>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    /// Compare `proposal_data` with the proposals queued in storage, i.e.
+    /// the ones committed up to the last committed epoch that haven't been
+    /// concluded yet.
+    pub fn check_proposal_queue(&self) -> Result<ProposalQueueMismatch> {
+        let queued = gov_api::get_unconcluded_proposals(
+            &self.wl_storage,
+            self.wl_storage.storage.last_epoch,
+        )?;
+        Ok(ProposalQueueMismatch {
+            missing: queued.difference(&self.proposal_data).copied().collect(),
+            unexpected: self
+                .proposal_data
+                .difference(&queued)
+                .copied()
+                .collect(),
+        })
+    }
+
+    /// Rebuild `proposal_data` from the proposals queued in storage. Returns
+    /// the mismatch between the previous in-memory queue and storage.
+    pub fn reconcile_proposal_queue(
+        &mut self,
+    ) -> Result<ProposalQueueMismatch> {
+        let mismatch = self.check_proposal_queue()?;
+        for id in &mismatch.unexpected {
+            self.proposal_data.remove(id);
+        }
+        self.proposal_data.extend(mismatch.missing.iter().copied());
+        Ok(mismatch)
+    }
+}
+
+/// Estimated gas to conclude a governance proposal, regardless of its votes,
+/// reading about 1KiB of proposal fields and PoS data from storage.
+pub const TALLY_GAS_PER_PROPOSAL: u64 = 1_024 * STORAGE_ACCESS_GAS_PER_BYTE;
+
+/// Estimated gas to tally a single vote of a governance proposal, reading
+/// about 256 bytes of the vote and the stake of the voter from storage.
+pub const TALLY_GAS_PER_VOTE: u64 = 256 * STORAGE_ACCESS_GAS_PER_BYTE;
+
+/// Estimated gas per byte of the wasm code of a governance proposal, to
+/// read the code from storage and load it in memory.
+pub const CODE_GAS_PER_BYTE: u64 =
+    STORAGE_ACCESS_GAS_PER_BYTE + MEMORY_ACCESS_GAS_PER_BYTE;
+
+/// Estimated cost of finalizing the governance proposals queued for the
+/// next epoch change, in gas units.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ProposalFinalizeCost {
+    /// The number of queued proposals
+    pub proposals: u64,
+    /// The number of votes cast on the queued proposals
+    pub votes: u64,
+    /// The estimated gas to tally the votes of the queued proposals
+    pub tally_gas: u64,
+    /// The estimated gas to execute the wasm code of the queued proposals
+    pub code_gas: u64,
+}
+
+impl ProposalFinalizeCost {
+    /// The total estimated gas to finalize the queued proposals.
+    pub fn total_gas(&self) -> u64 {
+        self.tally_gas.saturating_add(self.code_gas)
+    }
+}
+
+/// Estimate the cost of finalizing the governance proposals queued for the
+/// next epoch change, i.e. the ones in `proposal_data` and the ones deferred
+/// from the last block, without executing them.
+///
+/// The tally of each proposal is estimated from its number of votes, and
+/// the execution of its wasm code, if any, from the size of the code,
+/// capped at the maximum block gas. This is a rough metric, meant for
+/// operators to be alerted of expensive epoch changes ahead of time.
+pub fn estimate_proposal_finalize_cost<D, H>(
+    shell: &Shell<D, H>,
+) -> Result<ProposalFinalizeCost>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    let max_block_gas = parameters::get_max_block_gas(&shell.wl_storage)?;
+    let mut queued = shell.proposal_data.clone();
+    queued.extend(gov_api::get_deferred_proposals(&shell.wl_storage)?);
+
+    let mut cost = ProposalFinalizeCost::default();
+    for id in queued {
+        let votes =
+            gov_api::get_proposal_votes(&shell.wl_storage, id)?.len() as u64;
+        let code_len = gov_api::get_proposal_code(&shell.wl_storage, id)?
+            .map_or(0, |code| code.len() as u64)
+            + ProposalStorageReader::new(&shell.wl_storage)
+                .code_chunks(id)?
+                .iter()
+                .map(|chunk| chunk.len() as u64)
+                .sum::<u64>();
+        cost.proposals += 1;
+        cost.votes += votes;
+        cost.tally_gas = cost.tally_gas.saturating_add(
+            TALLY_GAS_PER_PROPOSAL
+                .saturating_add(votes.saturating_mul(TALLY_GAS_PER_VOTE)),
+        );
+        cost.code_gas = cost.code_gas.saturating_add(
+            code_len
+                .saturating_mul(CODE_GAS_PER_BYTE)
+                .min(max_block_gas),
+        );
+    }
+    Ok(cost)
 }
 
 pub fn execute_governance_proposals<D, H>(
@@ -43,396 +232,4276 @@ where
     D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
     H: StorageHasher + Sync + 'static,
 {
+    let _span = tracing::debug_span!(
+        "execute_governance_proposals",
+        epoch = %shell.wl_storage.storage.block.epoch,
+    )
+    .entered();
     let mut proposals_result = ProposalsResult::default();
+    let native_token = shell.wl_storage.get_native_token()?;
+    let treasury_pre =
+        token::read_balance(&shell.wl_storage, &native_token, &ADDRESS)?;
 
-    for id in std::mem::take(&mut shell.proposal_data) {
-        let proposal_funds_key = gov_storage::get_funds_key(id);
-        let proposal_end_epoch_key = gov_storage::get_voting_end_epoch_key(id);
-        let proposal_type_key = gov_storage::get_proposal_type_key(id);
-        let proposal_author_key = gov_storage::get_author_key(id);
-
-        let funds: token::Amount =
-            force_read(&shell.wl_storage, &proposal_funds_key)?;
-        let proposal_end_epoch: Epoch =
-            force_read(&shell.wl_storage, &proposal_end_epoch_key)?;
-        let proposal_type: ProposalType =
-            force_read(&shell.wl_storage, &proposal_type_key)?;
-        let proposal_author: Address =
-            force_read(&shell.wl_storage, &proposal_author_key)?;
-
-        let is_steward = pgf::is_steward(&shell.wl_storage, &proposal_author)?;
-
-        let params = read_pos_params(&shell.wl_storage)?;
-        let total_voting_power =
-            read_total_stake(&shell.wl_storage, &params, proposal_end_epoch)?;
-
-        let tally_type = TallyType::from(proposal_type.clone(), is_steward);
-        let votes = compute_proposal_votes(
-            &shell.wl_storage,
-            &params,
+    // The proposals still deferred from the blocks of the last epoch are due
+    // along with the ones reaching their grace epoch
+    let mut due = std::mem::take(&mut shell.proposal_data);
+    due.extend(gov_api::get_deferred_proposals(&shell.wl_storage)?);
+    // The queue was loaded from the proposals committed at the last epoch,
+    // recorded for it to be rebuilt from storage after a restart
+    gov_api::write_concluded_epoch(
+        &mut shell.wl_storage,
+        shell.wl_storage.storage.last_epoch,
+    )?;
+    for id in defer_excess_proposals(shell, due)? {
+        contain_proposal_panic(
+            shell,
+            response,
+            &mut proposals_result,
             id,
-            proposal_end_epoch,
+            |shell, response, proposals_result| {
+                conclude_governance_proposal(
+                    shell,
+                    response,
+                    proposals_result,
+                    &native_token,
+                    id,
+                )
+            },
         )?;
-        let proposal_result =
-            compute_proposal_result(votes, total_voting_power, tally_type);
-        gov_api::write_proposal_result(
-            &mut shell.wl_storage,
+    }
+
+    let current_epoch = shell.wl_storage.storage.block.epoch;
+    for (id, grace_epoch) in
+        gov_api::get_expired_proposals(&shell.wl_storage, current_epoch)?
+    {
+        if gov_api::is_funds_released(&shell.wl_storage, id)? {
+            tracing::warn!(
+                "The funds of expired governance proposal {} were already \
+                 released. The proposal is dequeued without being expired \
+                 again.",
+                id
+            );
+            gov_api::dequeue_expired_proposal(
+                &mut shell.wl_storage,
+                id,
+                grace_epoch,
+            )?;
+            continue;
+        }
+        if let Some(field) = find_malformed_field(&shell.wl_storage, id) {
+            tracing::warn!(
+                "Expired governance proposal {} is malformed, as its {} is \
+                 missing or can't be decoded. It has been rejected without \
+                 being tallied.",
+                id,
+                field
+            );
+            gov_api::dequeue_expired_proposal(
+                &mut shell.wl_storage,
+                id,
+                grace_epoch,
+            )?;
+            let mut proposal_event: Event =
+                ProposalEvent::malformed_proposal_event(id, field).into();
+            proposal_event[events::attributes::EPOCH] =
+                current_epoch.to_string();
+            response.events.push(proposal_event);
+            proposals_result.expired.push(id);
+            continue;
+        }
+
+        expire_governance_proposal(
+            shell,
+            response,
             id,
-            proposal_result,
+            grace_epoch,
+            current_epoch,
         )?;
+        let mut proposal_event: Event =
+            ProposalEvent::expired_proposal_event(id).into();
+        proposal_event[events::attributes::EPOCH] = current_epoch.to_string();
+        response.events.push(proposal_event);
+        proposals_result.expired.push(id);
 
-        let transfer_address = match proposal_result.result {
-            TallyResult::Passed => {
-                let proposal_event = match proposal_type {
-                    ProposalType::Default(_) => {
-                        let proposal_code =
-                            gov_api::get_proposal_code(&shell.wl_storage, id)?;
-                        let result = execute_default_proposal(
-                            shell,
-                            id,
-                            proposal_code.clone(),
-                        )?;
-                        tracing::info!(
-                            "Governance proposal (default {} wasm) {} has \
-                             been executed ({}) and passed.",
-                            if proposal_code.is_some() {
-                                "with"
-                            } else {
-                                "without"
-                            },
-                            id,
-                            result
-                        );
+        tracing::info!(
+            "Governance proposal {} has been queued for longer than the \
+             maximum proposal lifetime and was rejected.",
+            id
+        );
+    }
 
-                        ProposalEvent::default_proposal_event(
-                            id,
-                            proposal_code.is_some(),
-                            result,
-                        )
-                        .into()
-                    }
-                    ProposalType::PGFSteward(stewards) => {
-                        let result = execute_pgf_steward_proposal(
-                            &mut shell.wl_storage,
-                            stewards,
-                        )?;
-                        tracing::info!(
-                            "Governance proposal (pgf stewards){} has been \
-                             executed and passed.",
-                            id
-                        );
+    burn_treasury_overflow(shell, response, &native_token, treasury_pre)?;
 
-                        ProposalEvent::pgf_steward_proposal_event(id, result)
-                            .into()
-                    }
-                    ProposalType::PGFPayment(payments) => {
-                        let native_token =
-                            &shell.wl_storage.get_native_token()?;
-                        let result = execute_pgf_funding_proposal(
-                            &mut shell.wl_storage,
-                            native_token,
-                            payments,
-                            id,
-                        )?;
-                        tracing::info!(
-                            "Governance proposal (pgf funding) {} has been \
-                             executed and passed.",
-                            id
-                        );
+    // Purely advisory, funds keep flowing into the treasury regardless
+    if let Some(high_water_mark) =
+        gov_api::get_treasury_high_water_mark(&shell.wl_storage)?
+    {
+        let treasury_post =
+            token::read_balance(&shell.wl_storage, &native_token, &ADDRESS)?;
+        if treasury_pre < high_water_mark && treasury_post >= high_water_mark {
+            tracing::warn!(
+                "The treasury balance of {} reached its high-water mark of {}.",
+                treasury_post.to_string_native(),
+                high_water_mark.to_string_native()
+            );
+            let mut event =
+                treasury_high_water_mark_event(treasury_post, high_water_mark);
+            event[events::attributes::EPOCH] = current_epoch.to_string();
+            response.events.push(event);
+        }
+    }
 
-                        for ibc_event in
-                            shell.wl_storage.write_log_mut().take_ibc_events()
-                        {
-                            let mut event = Event::from(ibc_event.clone());
-                            // Add the height for IBC event query
-                            let height = shell
-                                .wl_storage
-                                .storage
-                                .get_last_block_height()
-                                + 1;
-                            event["height"] = height.to_string();
-                            response.events.push(event);
-                        }
+    Ok(proposals_result)
+}
 
-                        ProposalEvent::pgf_payments_proposal_event(id, result)
-                            .into()
-                    }
-                };
-                response.events.push(proposal_event);
-                proposals_result.passed.push(id);
+/// Burn the funds moved to the treasury while concluding proposals, given
+/// the balance of the treasury before, in excess of the maximum treasury
+/// balance, if any. The balance held before isn't burned, even if above the
+/// maximum, only the inflows that would raise it further.
+fn burn_treasury_overflow<D, H>(
+    shell: &mut Shell<D, H>,
+    response: &mut shim::response::FinalizeBlock,
+    native_token: &Address,
+    treasury_pre: token::Amount,
+) -> Result<()>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    let Some(max_treasury_balance) =
+        gov_api::get_max_treasury_balance(&shell.wl_storage)?
+    else {
+        return Ok(());
+    };
+    let treasury_post =
+        token::read_balance(&shell.wl_storage, native_token, &ADDRESS)?;
+    let inflows = treasury_post.checked_sub(treasury_pre).unwrap_or_default();
+    let overflow = treasury_post
+        .checked_sub(max_treasury_balance)
+        .unwrap_or_default()
+        .min(inflows);
+    if overflow.is_zero() {
+        return Ok(());
+    }
+    token::burn_tokens(
+        &mut shell.wl_storage,
+        native_token,
+        &ADDRESS,
+        overflow,
+    )?;
+    shell.wl_storage.commit_tx();
 
-                gov_api::get_proposal_author(&shell.wl_storage, id)?
-            }
-            TallyResult::Rejected => {
-                if let ProposalType::PGFPayment(_) = proposal_type {
-                    if proposal_result.two_thirds_nay_over_two_thirds_total() {
-                        pgf::remove_steward(
-                            &mut shell.wl_storage,
-                            &proposal_author,
-                        )?;
+    let treasury_balance = treasury_post - overflow;
+    tracing::info!(
+        "Burned {} tokens moved to the treasury in excess of its maximum \
+         balance of {}.",
+        overflow.to_string_native(),
+        max_treasury_balance.to_string_native()
+    );
+    let mut event = treasury_overflow_burned_event(
+        overflow,
+        treasury_balance,
+        max_treasury_balance,
+    );
+    event[events::attributes::EPOCH] =
+        shell.wl_storage.storage.block.epoch.to_string();
+    response.events.push(event);
+    Ok(())
+}
 
-                        tracing::info!(
-                            "Governance proposal {} was rejected with 2/3 of \
-                             nay votes over 2/3 of the total voting power. If \
-                             {} is a steward, it's being removed from the \
-                             stewards set.",
-                            id,
-                            proposal_author
-                        );
-                    }
-                }
-                let proposal_event =
-                    ProposalEvent::rejected_proposal_event(id).into();
-                response.events.push(proposal_event);
-                proposals_result.rejected.push(id);
-
-                tracing::info!(
-                    "Governance proposal {} has been executed and rejected.",
-                    id
-                );
+/// Mint the treasury inflation of the current epoch, if any, emitting an
+/// event with the minted amount and the new total supply of the native token
+pub fn mint_treasury_inflation<D, H>(
+    shell: &mut Shell<D, H>,
+    response: &mut shim::response::FinalizeBlock,
+) -> Result<()>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    let current_epoch = shell.wl_storage.storage.block.epoch;
+    let Some((minted, total_supply)) =
+        gov_api::mint_treasury_inflation(&mut shell.wl_storage, current_epoch)?
+    else {
+        return Ok(());
+    };
+    tracing::info!(
+        "Minting {} tokens of treasury inflation into the treasury (total \
+         supply {}).",
+        minted.to_string_native(),
+        total_supply.to_string_native()
+    );
+    let mut event = treasury_inflation_event(minted, total_supply);
+    event[events::attributes::EPOCH] = current_epoch.to_string();
+    response.events.push(event);
+    Ok(())
+}
 
-                None
-            }
-        };
+/// Conclude the governance proposals whose voting period, expressed in block
+/// heights, ends at the current block height. Unlike the proposals voted on
+/// over epochs, these are concluded as soon as their voting period ends,
+/// rather than at an epoch change.
+pub fn execute_height_based_proposals<D, H>(
+    shell: &mut Shell<D, H>,
+    response: &mut shim::response::FinalizeBlock,
+) -> Result<ProposalsResult>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    let height = shell.wl_storage.storage.block.height;
+    let _span = tracing::debug_span!("execute_height_based_proposals", %height)
+        .entered();
+    let mut proposals_result = ProposalsResult::default();
+    let native_token = shell.wl_storage.get_native_token()?;
 
-        let native_token = shell.wl_storage.get_native_token()?;
-        if let Some(address) = transfer_address {
-            token::transfer(
-                &mut shell.wl_storage,
-                &native_token,
-                &gov_address,
-                &address,
-                funds,
-            )?;
-        } else {
-            token::burn_tokens(
-                &mut shell.wl_storage,
-                &native_token,
-                &gov_address,
-                funds,
-            )?;
-        }
+    let concluding =
+        gov_api::get_proposals_concluding_at_height(&shell.wl_storage, height)?;
+    for id in
+        order_by_priority(&shell.wl_storage, concluding.into_iter().collect())
+    {
+        contain_proposal_panic(
+            shell,
+            response,
+            &mut proposals_result,
+            id,
+            |shell, response, proposals_result| {
+                conclude_governance_proposal(
+                    shell,
+                    response,
+                    proposals_result,
+                    &native_token,
+                    id,
+                )
+            },
+        )?;
     }
 
     Ok(proposals_result)
 }
 
-fn compute_proposal_votes<S>(
-    storage: &S,
-    params: &PosParams,
-    proposal_id: u64,
-    epoch: Epoch,
-) -> namada::state::StorageResult<ProposalVotes>
+/// Conclude the governance proposals deferred from the previous block, as
+/// more proposals were due than the maximum number of proposals concluded
+/// per block. Deferred proposals are concluded in the following blocks of
+/// the epoch, in id order, up to the same maximum per block.
+pub fn execute_deferred_proposals<D, H>(
+    shell: &mut Shell<D, H>,
+    response: &mut shim::response::FinalizeBlock,
+) -> Result<ProposalsResult>
 where
-    S: StorageRead,
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
 {
-    let votes = gov_api::get_proposal_votes(storage, proposal_id)?;
-
-    let mut validators_vote: HashMap<Address, TallyVote> = HashMap::default();
-    let mut validator_voting_power: HashMap<Address, VotePower> =
-        HashMap::default();
-    let mut delegators_vote: HashMap<Address, TallyVote> = HashMap::default();
-    let mut delegator_voting_power: HashMap<
-        Address,
-        HashMap<Address, VotePower>,
-    > = HashMap::default();
-
-    for vote in votes {
-        if vote.is_validator() {
-            let validator = vote.validator.clone();
-            let vote_data = vote.data.clone();
-
-            let validator_stake =
-                read_validator_stake(storage, params, &validator, epoch)
-                    .unwrap_or_default();
-
-            validators_vote.insert(validator.clone(), vote_data.into());
-            validator_voting_power.insert(validator, validator_stake);
-        } else {
-            let validator = vote.validator.clone();
-            let delegator = vote.delegator.clone();
-            let vote_data = vote.data.clone();
+    let mut proposals_result = ProposalsResult::default();
+    let due = gov_api::get_deferred_proposals(&shell.wl_storage)?;
+    if due.is_empty() {
+        return Ok(proposals_result);
+    }
+    let height = shell.wl_storage.storage.block.height;
+    let _span =
+        tracing::debug_span!("execute_deferred_proposals", %height).entered();
+    let native_token = shell.wl_storage.get_native_token()?;
 
-            let bond_id = BondId {
-                source: delegator.clone(),
-                validator: validator.clone(),
-            };
-            let delegator_stake = bond_amount(storage, &bond_id, epoch);
-
-            if let Ok(stake) = delegator_stake {
-                delegators_vote.insert(delegator.clone(), vote_data.into());
-                delegator_voting_power
-                    .entry(delegator)
-                    .or_default()
-                    .insert(validator, stake);
-            } else {
-                continue;
-            }
-        }
+    for id in defer_excess_proposals(shell, due)? {
+        contain_proposal_panic(
+            shell,
+            response,
+            &mut proposals_result,
+            id,
+            |shell, response, proposals_result| {
+                conclude_governance_proposal(
+                    shell,
+                    response,
+                    proposals_result,
+                    &native_token,
+                    id,
+                )
+            },
+        )?;
     }
 
-    Ok(ProposalVotes {
-        validators_vote,
-        validator_voting_power,
-        delegators_vote,
-        delegator_voting_power,
-    })
+    Ok(proposals_result)
 }
 
-fn execute_default_proposal<D, H>(
+/// Split the ids of the due proposals between the ones concluded in the
+/// current block, which are returned in execution order, and the ones
+/// deferred to the next block, which are written to storage. The split only
+/// depends on the committed state, such that every validator concludes the
+/// same proposals in the same block.
+fn defer_excess_proposals<D, H>(
     shell: &mut Shell<D, H>,
-    id: u64,
-    proposal_code: Option<Vec<u8>>,
-) -> namada::state::StorageResult<bool>
+    due: BTreeSet<u64>,
+) -> Result<Vec<u64>>
 where
     D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
     H: StorageHasher + Sync + 'static,
 {
-    if let Some(code) = proposal_code {
-        let pending_execution_key = gov_storage::get_proposal_execution_key(id);
-        shell.wl_storage.write(&pending_execution_key, ())?;
-
-        let mut tx = Tx::from_type(TxType::Decrypted(DecryptedTx::Decrypted));
-        tx.header.chain_id = shell.chain_id.clone();
-        tx.set_data(Data::new(encode(&id)));
-        tx.set_code(Code::new(code, None));
-
-        let tx_result = protocol::dispatch_tx(
-            tx,
-            &[], /*  this is used to compute the fee
-                  * based on the code size. We dont
-                  * need it here. */
-            TxIndex::default(),
-            &mut TxGasMeter::new_from_sub_limit(u64::MAX.into()), /* No gas limit for governance proposal */
-            &mut shell.wl_storage,
-            &mut shell.vp_wasm_cache,
-            &mut shell.tx_wasm_cache,
-            None,
-        );
-        shell
-            .wl_storage
-            .storage
-            .delete(&pending_execution_key)
-            .expect("Should be able to delete the storage.");
-        match tx_result {
-            Ok(tx_result) => {
-                if tx_result.is_accepted() {
-                    shell.wl_storage.commit_tx();
-                    Ok(true)
-                } else {
-                    Ok(false)
-                }
-            }
-            Err(_) => {
-                shell.wl_storage.drop_tx();
-                Ok(false)
-            }
-        }
-    } else {
+    let max_proposals_per_block =
+        gov_api::get_max_proposals_per_block(&shell.wl_storage)?;
+    let (concluded, deferred) = split_due_proposals(
+        order_by_priority(&shell.wl_storage, due),
+        max_proposals_per_block,
+    );
+    if !deferred.is_empty() {
         tracing::info!(
-            "Governance proposal {} doesn't have any associated proposal code.",
-            id
+            "Deferring the governance proposals {:?} to the next block, as at \
+             most {} proposals are concluded per block.",
+            deferred,
+            concluded.len()
         );
-        Ok(true)
     }
+    gov_api::write_deferred_proposals(&mut shell.wl_storage, &deferred)?;
+    Ok(concluded)
 }
 
-fn execute_pgf_steward_proposal<S>(
-    storage: &mut S,
-    stewards: BTreeSet<AddRemove<Address>>,
-) -> Result<bool>
+/// Split the ids of the due proposals, in execution order, between the
+/// ones concluded in the current block and the ones deferred to the next
+/// block, such that at most `max_proposals_per_block` proposals are
+/// concluded, if set. At least one proposal is concluded per block, such
+/// that deferred proposals are eventually concluded.
+pub fn split_due_proposals(
+    mut due: Vec<u64>,
+    max_proposals_per_block: Option<u64>,
+) -> (Vec<u64>, BTreeSet<u64>) {
+    let Some(max) = max_proposals_per_block else {
+        return (due, BTreeSet::new());
+    };
+    let max = usize::try_from(max.max(1)).unwrap_or(usize::MAX);
+    if due.len() <= max {
+        return (due, BTreeSet::new());
+    }
+    let deferred = due.split_off(max).into_iter().collect();
+    (due, deferred)
+}
+
+/// Order the ids of the given proposals by decreasing execution priority,
+/// then by increasing id. Proposals without a priority, or whose priority
+/// can't be read, have the lowest priority, such that the order only
+/// depends on the committed state.
+pub fn order_by_priority<S>(storage: &S, ids: BTreeSet<u64>) -> Vec<u64>
 where
-    S: StorageRead + StorageWrite,
+    S: StorageRead,
 {
-    for action in stewards {
-        match action {
-            AddRemove::Add(address) => {
-                pgf_storage::stewards_handle().insert(
-                    storage,
-                    address.to_owned(),
-                    StewardDetail::base(address),
-                )?;
-            }
-            AddRemove::Remove(address) => {
-                pgf_storage::stewards_handle().remove(storage, &address)?;
-            }
-        }
-    }
+    let reader = ProposalStorageReader::new(storage);
+    let mut ordered: Vec<(u8, u64)> = ids
+        .into_iter()
+        .map(|id| {
+            let priority = reader.priority(id).ok().flatten();
+            (priority.unwrap_or_default(), id)
+        })
+        .collect();
+    ordered.sort_by(|(priority_a, id_a), (priority_b, id_b)| {
+        priority_b.cmp(priority_a).then(id_a.cmp(id_b))
+    });
+    ordered.into_iter().map(|(_, id)| id).collect()
+}
 
-    Ok(true)
+/// Run `conclude` on the proposal with the given id, containing any panic
+/// raised in the process, such that a single faulty proposal can't prevent
+/// the block from being finalized. The storage writes, events and results
+/// of a proposal whose processing panicked are reverted, leaving it
+/// undecided with its funds locked for manual handling, and a processing
+/// error event is emitted in their place.
+fn contain_proposal_panic<D, H, F>(
+    shell: &mut Shell<D, H>,
+    response: &mut shim::response::FinalizeBlock,
+    proposals_result: &mut ProposalsResult,
+    id: u64,
+    conclude: F,
+) -> Result<()>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+    F: FnOnce(
+        &mut Shell<D, H>,
+        &mut shim::response::FinalizeBlock,
+        &mut ProposalsResult,
+    ) -> Result<()>,
+{
+    let write_log = shell.wl_storage.write_log.clone();
+    let block_gas = shell.wl_storage.storage.block.gas.clone();
+    let num_events = response.events.len();
+    let prev_result = proposals_result.clone();
+
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+        conclude(shell, response, proposals_result)
+    }));
+    let payload = match outcome {
+        Ok(result) => return result,
+        Err(payload) => payload,
+    };
+    let error = panic_message(payload.as_ref());
+    tracing::error!(
+        "Processing governance proposal {} panicked: {}. The proposal has \
+         been skipped, and its funds remain locked.",
+        id,
+        error
+    );
+
+    shell.wl_storage.write_log = write_log;
+    shell.wl_storage.storage.block.gas = block_gas;
+    response.events.truncate(num_events);
+    *proposals_result = prev_result;
+
+    let mut proposal_event: Event =
+        ProposalEvent::processing_error_event(id, &error).into();
+    proposal_event[events::attributes::EPOCH] =
+        shell.wl_storage.storage.block.epoch.to_string();
+    response.events.push(proposal_event);
+    Ok(())
 }
 
-fn execute_pgf_funding_proposal<D, H>(
-    storage: &mut WlStorage<D, H>,
-    token: &Address,
-    fundings: BTreeSet<PGFAction>,
-    proposal_id: u64,
-) -> Result<bool>
+/// Get the message of a panic from its payload
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|msg| msg.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// Tally the votes of a proposal, execute it if it passed, and return or
+/// burn its locked funds accordingly. Malformed proposals are rejected
+/// without being tallied.
+fn conclude_governance_proposal<D, H>(
+    shell: &mut Shell<D, H>,
+    response: &mut shim::response::FinalizeBlock,
+    proposals_result: &mut ProposalsResult,
+    native_token: &Address,
+    id: u64,
+) -> Result<()>
 where
     D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
     H: StorageHasher + Sync + 'static,
 {
-    for funding in fundings {
-        match funding {
-            PGFAction::Continuous(action) => match action {
-                AddRemove::Add(target) => {
-                    pgf_storage::fundings_handle().insert(
-                        storage,
-                        target.target().clone(),
-                        StoragePgfFunding::new(target.clone(), proposal_id),
-                    )?;
-                    tracing::info!(
-                        "Added/Updated ContinousPgf from proposal id {}: set \
-                         {} to {}.",
-                        proposal_id,
-                        target.amount().to_string_native(),
-                        target.target()
-                    );
-                }
-                AddRemove::Remove(target) => {
-                    pgf_storage::fundings_handle()
-                        .remove(storage, &target.target())?;
-                    tracing::info!(
-                        "Removed ContinousPgf from proposal id {}: set {} to \
-                         {}.",
-                        proposal_id,
-                        target.amount().to_string_native(),
-                        target.target()
-                    );
-                }
-            },
-            PGFAction::Retro(target) => {
-                let result = match &target {
-                    PGFTarget::Internal(target) => token::transfer(
-                        storage,
-                        token,
+    let _span = tracing::debug_span!("governance_proposal", id).entered();
+    if gov_api::is_funds_released(&shell.wl_storage, id)? {
+        tracing::warn!(
+            "The funds of governance proposal {} were already released. The \
+             proposal has already been concluded and is skipped.",
+            id
+        );
+        return Ok(());
+    }
+    if let Some(field) = find_malformed_field(&shell.wl_storage, id) {
+        tracing::warn!(
+            "Governance proposal {} is malformed, as its {} is missing or \
+             can't be decoded. It has been rejected without being tallied.",
+            id,
+            field
+        );
+        let mut proposal_event: Event =
+            ProposalEvent::malformed_proposal_event(id, field).into();
+        proposal_event[events::attributes::EPOCH] =
+            shell.wl_storage.storage.block.epoch.to_string();
+        response.events.push(proposal_event);
+        proposals_result.rejected.push(id);
+        return Ok(());
+    }
+
+    let proposal_store = ProposalStorageReader::new(&shell.wl_storage);
+    let funds = proposal_store
+        .funds(id)?
+        .expect("Proposal funds must be present.");
+    // The stake of voters is snapshotted at the voting end epoch, or at the
+    // epoch containing the voting start height
+    let tally_epoch = match proposal_store.voting_heights(id)? {
+        Some(voting_heights) => shell
+            .wl_storage
+            .storage
+            .block
+            .pred_epochs
+            .get_epoch(voting_heights.start)
+            .expect("The epoch of the voting start height must be known."),
+        None => proposal_store
+            .voting_end(id)?
+            .expect("Proposal voting end epoch must be present."),
+    };
+    let proposal_type = proposal_store
+        .proposal_type(id)?
+        .expect("Proposal type must be present.");
+    let proposal_author = proposal_store
+        .author(id)?
+        .expect("Proposal author must be present.");
+
+    let tally_timer = PhaseTimer::new(Phase::ProposalTally);
+    let is_steward = pgf::is_steward(&shell.wl_storage, &proposal_author)?;
+
+    let params = read_pos_params(&shell.wl_storage)?;
+
+    let emergency_validator_quorum =
+        gov_api::get_emergency_validator_quorum(&shell.wl_storage)?;
+    let council_threshold = gov_api::get_council(&shell.wl_storage)?
+        .map(|council| council.threshold);
+    let tally_type = TallyType::from(
+        proposal_type.clone(),
+        is_steward,
+        emergency_validator_quorum,
+        council_threshold,
+    );
+    // Ties broken at random are drawn from the hash of this block, which
+    // every node agrees on
+    let tie_break_seed =
+        TieBreakSeed::new(&shell.wl_storage.storage.block.hash, id);
+    let strategy = gov_api::get_tie_breaks(&shell.wl_storage)?
+        .strategy(tally_type, tie_break_seed);
+    let votes =
+        compute_proposal_votes(&shell.wl_storage, &params, id, tally_epoch)?;
+    // The participation of the consensus validators at the tally epoch is
+    // recorded from the votes in hand
+    let participation = votes.validators_participation(
+        read_consensus_validator_set_addresses(&shell.wl_storage, tally_epoch)?,
+    );
+    let outcome = tally_proposal_votes(
+        &shell.wl_storage,
+        &params,
+        &votes,
+        tally_epoch,
+        strategy,
+    )?;
+    drop(tally_timer);
+
+    let current_epoch = shell.wl_storage.storage.block.epoch;
+    let parameter_keys = parameter_targets(&proposal_type);
+    let conflict = match outcome.result {
+        TallyResult::Passed if !parameter_keys.is_empty() => {
+            let changes = gov_api::get_parameter_changes(
+                &shell.wl_storage,
+                current_epoch,
+            )?;
+            parameter_keys.iter().find_map(|key| {
+                changes.get(&key.to_string()).map(|id| (key, *id))
+            })
+        }
+        _ => None,
+    };
+    let conflicting_id = conflict.map(|(_, conflicting_id)| conflicting_id);
+    let conflict_policy = match conflicting_id {
+        Some(_) => gov_api::get_parameter_conflict_policy(&shell.wl_storage)?,
+        None => ParameterConflictPolicy::default(),
+    };
+
+    // A passed proposal rejected for setting the same protocol parameter as
+    // a proposal concluded earlier in the epoch is recorded as rejected,
+    // along with the proposal it conflicts with
+    let proposal_result = match conflicting_id {
+        Some(conflicting_id)
+            if conflict_policy == ParameterConflictPolicy::RejectLater =>
+        {
+            gov_api::write_proposal_conflict(
+                &mut shell.wl_storage,
+                id,
+                conflicting_id,
+            )?;
+            ProposalResult {
+                result: TallyResult::Rejected,
+                ..outcome.into()
+            }
+        }
+        _ => outcome.into(),
+    };
+    gov_api::write_proposal_result(&mut shell.wl_storage, id, proposal_result)?;
+    if let Some(seed) = outcome.tie_break {
+        tracing::info!(
+            "The tally of governance proposal {} landed on a tie, broken at \
+             random from the hash of block {}: {}",
+            id,
+            seed.block_hash,
+            proposal_result.result
+        );
+        gov_api::write_proposal_tie_break(&mut shell.wl_storage, id, seed)?;
+    }
+    gov_api::write_proposal_archive(
+        &mut shell.wl_storage,
+        id,
+        shell.wl_storage.storage.block.epoch,
+        proposal_result,
+    )?;
+    gov_api::write_participation(
+        &mut shell.wl_storage,
+        shell.wl_storage.storage.block.epoch,
+        id,
+        participation,
+    )?;
+    // The records of the tally are committed before the code of the
+    // proposal is executed, which drops the current tx if the code is
+    // rejected
+    shell.wl_storage.commit_tx();
+
+    // Rejected proposals refund a fraction of their funds to their author,
+    // set by proposal type
+    let refund_ratio =
+        gov_api::get_refund_ratio_by_type(&shell.wl_storage, &proposal_type)?;
+    let refund_address = proposal_author.clone();
+
+    let execution_timer = PhaseTimer::new(Phase::ProposalExecution);
+    let transfer_address = match (outcome.result, conflicting_id) {
+        (TallyResult::Passed, Some(conflicting_id))
+            if conflict_policy == ParameterConflictPolicy::RejectLater =>
+        {
+            tracing::warn!(
+                "Governance proposal {} passed, but it was rejected as it \
+                 sets the same protocol parameter as proposal {}, concluded \
+                 earlier in the epoch.",
+                id,
+                conflicting_id
+            );
+            let mut proposal_event: Event =
+                ProposalEvent::conflicting_proposal_event(id, conflicting_id)
+                    .into();
+            proposal_event[events::attributes::EPOCH] =
+                current_epoch.to_string();
+            record_basis_points(&mut proposal_event, outcome.basis_points);
+            response.events.push(proposal_event);
+            proposals_result.rejected.push(id);
+
+            // The proposal did pass, hence its funds are returned
+            Some(proposal_author)
+        }
+        (TallyResult::Passed, _) => {
+            // Set if the code of the proposal panicked
+            let mut panicked = false;
+            let mut proposal_event: Event = match proposal_type {
+                ProposalType::Default(_) | ProposalType::Council(_) => {
+                    // only check the presence and size of the code here, it's
+                    // loaded once it's about to be executed
+                    let code_len = shell.storage_key_len(
+                        &gov_storage::get_proposal_code_key(id),
+                    );
+                    let result = execute_default_proposal(shell, id, code_len)?;
+                    tracing::info!(
+                        "Governance proposal ({} {} wasm) {} has been \
+                         executed ({}) and passed.",
+                        proposal_type.kind(),
+                        if code_len.is_some() {
+                            "with"
+                        } else {
+                            "without"
+                        },
+                        id,
+                        result
+                    );
+
+                    panicked = gov_api::get_proposal_execution_result(
+                        &shell.wl_storage,
+                        id,
+                    )?
+                    .map_or(false, |execution| execution.panicked);
+                    // The output of the code is only found if the code was
+                    // accepted, its writes being dropped otherwise
+                    let output = take_proposal_output(shell, id);
+                    let mut proposal_event: Event = if panicked {
+                        ProposalEvent::panicked_proposal_event(id).into()
+                    } else {
+                        ProposalEvent::default_proposal_event(
+                            id,
+                            code_len.is_some(),
+                            result,
+                        )
+                        .into()
+                    };
+                    if let Some(output) = output {
+                        let output = Event::proposal(id).output(output).build();
+                        proposal_event.attributes.extend(output.attributes);
+                    }
+                    proposal_event
+                }
+                ProposalType::PGFSteward(stewards) => {
+                    let result = execute_pgf_steward_proposal(
+                        &mut shell.wl_storage,
+                        stewards,
+                    )?;
+                    tracing::info!(
+                        "Governance proposal (pgf stewards){} has been \
+                         executed and passed.",
+                        id
+                    );
+
+                    ProposalEvent::pgf_steward_proposal_event(id, result).into()
+                }
+                ProposalType::PGFPayment(payments) => {
+                    let native_token = &shell.wl_storage.get_native_token()?;
+                    let result = execute_pgf_funding_proposal(
+                        &mut shell.wl_storage,
+                        native_token,
+                        payments,
+                        id,
+                    )?;
+                    tracing::info!(
+                        "Governance proposal (pgf funding) {} has been \
+                         executed and passed.",
+                        id
+                    );
+
+                    for ibc_event in
+                        shell.wl_storage.write_log_mut().take_ibc_events()
+                    {
+                        let mut event = Event::from(ibc_event.clone());
+                        // Add the height for IBC event query
+                        let height =
+                            shell.wl_storage.storage.get_last_block_height()
+                                + 1;
+                        event["height"] = height.to_string();
+                        response.events.push(event);
+                    }
+
+                    ProposalEvent::pgf_payments_proposal_event(id, result)
+                        .into()
+                }
+                ProposalType::ETHBridgeContracts(update) => {
+                    let old_contracts = execute_eth_bridge_contracts_proposal(
+                        &mut shell.wl_storage,
+                        update,
+                    )?;
+                    tracing::info!(
+                        "Governance proposal (ethereum bridge contracts) {} \
+                         has been executed and passed. The new contracts are \
+                         in effect from epoch {}.",
+                        id,
+                        update.activation_epoch
+                    );
+
+                    ProposalEvent::eth_bridge_contracts_proposal_event(
+                        id,
+                        old_contracts.native_erc20,
+                        old_contracts.bridge.address,
+                        &update,
+                    )
+                    .into()
+                }
+                ProposalType::ETHBridgePause(action) => {
+                    execute_eth_bridge_pause_proposal(
+                        &mut shell.wl_storage,
+                        action,
+                    )?;
+                    tracing::info!(
+                        "Governance proposal (ethereum bridge pause) {} has \
+                         been executed and passed. Bridge action: {}.",
+                        id,
+                        action
+                    );
+
+                    ProposalEvent::eth_bridge_pause_proposal_event(id, action)
+                        .into()
+                }
+                ProposalType::UpdateWasmAllowlist(update) => {
+                    let (allowlist, applied) = execute_wasm_allowlist_proposal(
+                        &mut shell.wl_storage,
+                        &update,
+                    )?;
+                    // Compiled modules of removed code are of no further
+                    // use
+                    if applied {
+                        for hash in &update.remove {
+                            match update.kind {
+                                TxOrVp::Tx => shell.tx_wasm_cache.evict(hash),
+                                TxOrVp::Vp => shell.vp_wasm_cache.evict(hash),
+                            }
+                        }
+                    }
+                    tracing::info!(
+                        "Governance proposal (wasm allowlist) {} has been \
+                         executed and passed. The {} allowlist has {} code \
+                         hashes.",
+                        id,
+                        update.kind,
+                        allowlist.len()
+                    );
+
+                    ProposalEvent::wasm_allowlist_proposal_event(
+                        id,
+                        update.kind,
+                        &allowlist,
+                    )
+                    .into()
+                }
+                ProposalType::ETHHotKeyRotation(rotation) => {
+                    let current_epoch = shell.wl_storage.storage.block.epoch;
+                    let result = execute_eth_hot_key_rotation_proposal(
+                        &mut shell.wl_storage,
+                        &rotation,
+                        current_epoch,
+                    )?;
+                    if result {
+                        tracing::warn!(
+                            "Governance proposal (ethereum hot key rotation) \
+                             {} has been executed and passed. The Ethereum \
+                             hot key of validator {} is rotated from epoch {}.",
+                            id,
+                            rotation.validator,
+                            rotation.activation_epoch
+                        );
+                    } else {
+                        tracing::error!(
+                            "Governance proposal (ethereum hot key rotation) \
+                             {} has passed, but the Ethereum hot key of \
+                             validator {} could not be rotated.",
+                            id,
+                            rotation.validator
+                        );
+                    }
+
+                    ProposalEvent::eth_hot_key_rotation_proposal_event(
+                        id, &rotation, result,
+                    )
+                    .into()
+                }
+                ProposalType::TreasurySpend(spend) => {
+                    let treasury_balance = token::read_balance(
+                        &shell.wl_storage,
+                        &spend.token,
                         &ADDRESS,
-                        &target.target,
-                        target.amount,
-                    ),
-                    PGFTarget::Ibc(target) => {
-                        ibc::transfer_over_ibc(storage, token, &ADDRESS, target)
+                    )?;
+                    let result = execute_treasury_spend_proposal(
+                        &mut shell.wl_storage,
+                        &spend,
+                        treasury_balance,
+                    )?;
+                    if result {
+                        tracing::info!(
+                            "Governance proposal (treasury spend) {} has been \
+                             executed and passed. Sent {} of token {} from \
+                             the treasury to {}.",
+                            id,
+                            spend.amount,
+                            spend.token,
+                            spend.recipient
+                        );
+                    } else {
+                        tracing::warn!(
+                            "Governance proposal (treasury spend) {} has \
+                             passed, but the treasury balance of {} of token \
+                             {} can't cover its spend of {}.",
+                            id,
+                            treasury_balance,
+                            spend.token,
+                            spend.amount
+                        );
                     }
-                };
-                match result {
-                    Ok(()) => tracing::info!(
-                        "Execute RetroPgf from proposal id {}: sent {} to {}.",
-                        proposal_id,
-                        target.amount().to_string_native(),
-                        target.target()
-                    ),
-                    Err(e) => tracing::warn!(
-                        "Error in RetroPgf transfer from proposal id {}, \
-                         amount {} to {}: {}",
-                        proposal_id,
-                        target.amount().to_string_native(),
-                        target.target(),
-                        e
-                    ),
+
+                    ProposalEvent::treasury_spend_proposal_event(
+                        id,
+                        &spend,
+                        treasury_balance,
+                        result,
+                    )
+                    .into()
+                }
+                ProposalType::ParameterChange(change) => {
+                    let changes = execute_parameter_change_proposal(
+                        &mut shell.wl_storage,
+                        id,
+                        change,
+                    )?;
+                    tracing::info!(
+                        "Governance proposal (parameter change) {} has been \
+                         executed and passed. Changed {} protocol parameters.",
+                        id,
+                        changes.len()
+                    );
+
+                    ProposalEvent::parameter_change_proposal_event(id, &changes)
+                        .into()
+                }
+            };
+            proposal_event[events::attributes::EPOCH] =
+                shell.wl_storage.storage.block.epoch.to_string();
+            record_basis_points(&mut proposal_event, outcome.basis_points);
+            if let Some(gas_used) =
+                shell.wl_storage.storage.block.gas.get_proposal_gas(id)
+            {
+                proposal_event[events::attributes::GAS_USED] =
+                    gas_used.to_string();
+            }
+            response.events.push(proposal_event);
+            proposals_result.passed.push(id);
+
+            if let Some((key, conflicting_id)) = conflict {
+                tracing::warn!(
+                    "Governance proposal {} overrode the protocol parameter \
+                     {} set by proposal {}, concluded earlier in the epoch.",
+                    id,
+                    key,
+                    conflicting_id
+                );
+                let mut event: Event =
+                    ProposalEvent::parameter_conflict_event(id, conflicting_id)
+                        .into();
+                event[events::attributes::EPOCH] = current_epoch.to_string();
+                response.events.push(event);
+            }
+            for key in &parameter_keys {
+                gov_api::write_parameter_change(
+                    &mut shell.wl_storage,
+                    current_epoch,
+                    key,
+                    id,
+                )?;
+            }
+
+            // The funds of a proposal whose code panicked go to the treasury
+            // instead of its author
+            if panicked {
+                Some(ADDRESS)
+            } else {
+                Some(proposal_author)
+            }
+        }
+        (TallyResult::Rejected, _) => {
+            if let ProposalType::PGFPayment(_) = proposal_type {
+                if proposal_result.two_thirds_nay_over_two_thirds_total() {
+                    pgf::remove_steward(
+                        &mut shell.wl_storage,
+                        &proposal_author,
+                    )?;
+
+                    tracing::info!(
+                        "Governance proposal {} was rejected with 2/3 of nay \
+                         votes over 2/3 of the total voting power. If {} is a \
+                         steward, it's being removed from the stewards set.",
+                        id,
+                        proposal_author
+                    );
                 }
             }
+            let mut proposal_event: Event =
+                ProposalEvent::rejected_proposal_event(id).into();
+            proposal_event[events::attributes::EPOCH] =
+                shell.wl_storage.storage.block.epoch.to_string();
+            record_basis_points(&mut proposal_event, outcome.basis_points);
+            response.events.push(proposal_event);
+            proposals_result.rejected.push(id);
+
+            tracing::info!(
+                "Governance proposal {} has been executed and rejected.",
+                id
+            );
+
+            None
+        }
+    };
+    drop(execution_timer);
+
+    let released = if funds.is_zero() {
+        check_zero_funds(shell, response, id)?;
+        ReleasedFunds::default()
+    } else if let Some(address) = transfer_address {
+        token::transfer(
+            &mut shell.wl_storage,
+            native_token,
+            &gov_address,
+            &address,
+            funds,
+        )?;
+        if address == ADDRESS {
+            ReleasedFunds {
+                to_treasury: funds,
+                ..Default::default()
+            }
+        } else {
+            ReleasedFunds {
+                returned: funds,
+                ..Default::default()
+            }
+        }
+    } else {
+        let refund = dec_fraction_of(funds, refund_ratio).unwrap_or_default();
+        if !refund.is_zero() {
+            token::transfer(
+                &mut shell.wl_storage,
+                native_token,
+                &gov_address,
+                &refund_address,
+                refund,
+            )?;
+        }
+        token::burn_tokens(
+            &mut shell.wl_storage,
+            native_token,
+            &gov_address,
+            funds - refund,
+        )?;
+        ReleasedFunds {
+            returned: refund,
+            burned: funds - refund,
+            ..Default::default()
+        }
+    };
+
+    mark_funds_released(shell, id, released)
+}
+
+/// Record the shares of the votes cast on a tallied proposal in the event of
+/// its conclusion
+fn record_basis_points(event: &mut Event, basis_points: TallyBasisPoints) {
+    let shares = EventBuilder::new(EventType::Proposal, EventLevel::Block)
+        .basis_points(basis_points)
+        .build();
+    event.attributes.extend(shares.attributes);
+}
+
+/// Read and delete the structured result written by the code of a passed
+/// proposal, if any. An output that can't be read is ignored.
+fn take_proposal_output<D, H>(
+    shell: &mut Shell<D, H>,
+    id: u64,
+) -> Option<ProposalOutput>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    gov_api::take_proposal_output(&mut shell.wl_storage, id).unwrap_or_else(
+        |err| {
+            tracing::warn!(
+                "The output of governance proposal {} can't be read, it won't \
+                 be reported: {}",
+                id,
+                err
+            );
+            None
+        },
+    )
+}
+
+/// Flag the funds of a proposal as released, recording where they went, and
+/// commit the current tx of the write log, such that the effects of the
+/// proposal, the movement of its funds and the flag are all applied
+/// together. A proposal whose flag is set is never concluded again, even if
+/// its id is found due once more, e.g. when replaying a block interrupted
+/// by a crash.
+fn mark_funds_released<D, H>(
+    shell: &mut Shell<D, H>,
+    id: u64,
+    released: ReleasedFunds,
+) -> Result<()>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    gov_api::write_funds_released(&mut shell.wl_storage, id, released)?;
+    shell.wl_storage.commit_tx();
+    Ok(())
+}
+
+/// Get the storage keys of the protocol parameters set by a proposal of the
+/// given type, if any. Passed proposals setting the same parameter conflict
+/// with each other when concluded in the same epoch.
+fn parameter_targets(proposal_type: &ProposalType) -> Vec<Key> {
+    match proposal_type {
+        ProposalType::UpdateWasmAllowlist(update) => vec![match update.kind {
+            TxOrVp::Tx => {
+                namada::parameters::storage::get_tx_allowlist_storage_key()
+            }
+            TxOrVp::Vp => {
+                namada::parameters::storage::get_vp_allowlist_storage_key()
+            }
+        }],
+        ProposalType::ETHBridgeContracts(_) => {
+            vec![bridge_storage::scheduled_contracts_update_key()]
+        }
+        ProposalType::ETHBridgePause(_) => {
+            vec![bridge_storage::bridge_paused_key()]
+        }
+        ProposalType::ParameterChange(change) => {
+            change.changes.iter().map(|(key, _)| key.clone()).collect()
         }
+        ProposalType::Default(_)
+        | ProposalType::PGFSteward(_)
+        | ProposalType::PGFPayment(_)
+        | ProposalType::ETHHotKeyRotation(_)
+        | ProposalType::Council(_)
+        | ProposalType::TreasurySpend(_) => vec![],
     }
+}
 
-    Ok(true)
+/// Handle a proposal concluded without any locked funds, in which case
+/// there's nothing to transfer or burn. This is expected if governance
+/// doesn't require any deposit for a proposal of the same type and size,
+/// otherwise the proposal is flagged with an advisory event, as its deposit
+/// should have been enforced on submission. Either way, the proposal is
+/// concluded normally.
+fn check_zero_funds<D, H>(
+    shell: &Shell<D, H>,
+    response: &mut shim::response::FinalizeBlock,
+    id: u64,
+) -> Result<()>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    if is_zero_deposit_allowed(shell, id)? {
+        tracing::debug!(
+            "Governance proposal {} has no locked funds, as it doesn't \
+             require any deposit.",
+            id
+        );
+        return Ok(());
+    }
+    tracing::warn!(
+        "Governance proposal {} has no locked funds, although it requires a \
+         deposit.",
+        id
+    );
+    let mut event: Event =
+        ProposalEvent::unexpected_zero_funds_event(id).into();
+    event[events::attributes::EPOCH] =
+        shell.wl_storage.storage.block.epoch.to_string();
+    response.events.push(event);
+    Ok(())
+}
+
+/// Check if the deposit that governance requires for a proposal of the same
+/// type and size as the given one is zero. The size of a proposal is that of
+/// its content and code, the latter only being present in default proposals.
+fn is_zero_deposit_allowed<D, H>(shell: &Shell<D, H>, id: u64) -> Result<bool>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    let proposal_size: usize = [
+        gov_storage::get_content_key(id),
+        gov_storage::get_proposal_code_key(id),
+    ]
+    .iter()
+    .filter_map(|key| shell.storage_key_len(key))
+    .sum();
+    let required_deposit =
+        gov_api::get_required_deposit(&shell.wl_storage, proposal_size as u64)?;
+    Ok(required_deposit.is_zero())
+}
+
+/// Find the first field of a proposal, among those read to conclude it, that
+/// is either missing from storage or can't be decoded. A stale execution
+/// marker of the proposal makes it malformed too. Only cheap storage reads
+/// are performed, such that this can be checked ahead of FinalizeBlock.
+pub fn find_malformed_field<S>(storage: &S, id: u64) -> Option<&'static str>
+where
+    S: StorageRead,
+{
+    let proposal_store = ProposalStorageReader::new(storage);
+    // Proposals voted on over block heights have no voting epochs
+    let voting_period = if storage
+        .has_key(&gov_storage::keys::get_voting_start_height_key(id))
+        .unwrap_or_default()
+    {
+        vec![(
+            "voting heights",
+            matches!(proposal_store.voting_heights(id), Ok(Some(_))),
+        )]
+    } else {
+        vec![
+            (
+                "voting start epoch",
+                matches!(proposal_store.voting_start(id), Ok(Some(_))),
+            ),
+            (
+                "voting end epoch",
+                matches!(proposal_store.voting_end(id), Ok(Some(_))),
+            ),
+        ]
+    };
+    let fields = [
+        ("funds", matches!(proposal_store.funds(id), Ok(Some(_)))),
+        (
+            "type",
+            matches!(proposal_store.proposal_type(id), Ok(Some(_))),
+        ),
+        ("author", matches!(proposal_store.author(id), Ok(Some(_)))),
+        // A marker left over from an interrupted execution
+        (
+            "pending execution",
+            !storage
+                .has_key(&gov_storage::get_proposal_execution_key(id))
+                .unwrap_or(true),
+        ),
+    ];
+    voting_period
+        .into_iter()
+        .chain(fields)
+        .find_map(|(field, is_valid)| (!is_valid).then_some(field))
+}
+
+/// Find the proposals concluding at the next epoch change, i.e. the ones
+/// queued for execution at the last committed epoch, which are malformed.
+/// Returns the id of each malformed proposal along with its first malformed
+/// field.
+pub fn find_malformed_concluding_proposals<D, H>(
+    wl_storage: &WlStorage<D, H>,
+) -> Result<Vec<(u64, &'static str)>>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    let last_epoch = wl_storage.storage.last_epoch.0;
+    let prefix = gov_storage::get_commiting_proposals_prefix(last_epoch);
+    let mut malformed = vec![];
+    for entry in namada::state::iter_prefix_bytes(wl_storage, &prefix)? {
+        let (key, _) = entry?;
+        // skip the keys of epochs sharing a string prefix with the last epoch
+        if gov_storage::get_commit_proposal_epoch(&key) != Some(last_epoch) {
+            continue;
+        }
+        let Some(id) = gov_storage::get_commit_proposal_id(&key) else {
+            continue;
+        };
+        if let Some(field) = find_malformed_field(wl_storage, id) {
+            malformed.push((id, field));
+        }
+    }
+    Ok(malformed)
+}
+
+/// Find the ids of the proposals initialized by the tx whose writes are in
+/// the tx write log, from its change of the proposal counter. The counter
+/// before the tx is read from the block write log or from storage, hence the
+/// ids assigned to the proposals of the txs applied earlier in the same block
+/// are not attributed to this tx. Returns an empty range if the tx didn't
+/// change the counter.
+pub fn find_initialized_proposals<D, H>(
+    wl_storage: &WlStorage<D, H>,
+    changed_keys: &BTreeSet<Key>,
+) -> namada::state::StorageResult<std::ops::Range<u64>>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    let counter_key = gov_storage::get_counter_key();
+    if !changed_keys.contains(&counter_key) {
+        return Ok(0..0);
+    }
+    let pre_counter = match wl_storage.write_log.read_pre(&counter_key).0 {
+        Some(StorageModification::Write { value }) => Some(value.clone()),
+        Some(_) => None,
+        None => {
+            wl_storage
+                .storage
+                .read(&counter_key)
+                .into_storage_result()?
+                .0
+        }
+    };
+    let pre_counter = pre_counter
+        .map(|value| u64::try_from_slice(&value))
+        .transpose()
+        .into_storage_result()?
+        .unwrap_or_default();
+    let post_counter: u64 = wl_storage.read(&counter_key)?.unwrap_or_default();
+    Ok(pre_counter..post_counter)
+}
+
+/// Force-reject a proposal that outlived the maximum proposal lifetime
+/// before reaching its grace epoch. The proposal is tallied with the votes
+/// cast so far, it's removed from the queue of proposals to be executed and
+/// its locked funds are moved to the treasury.
+fn expire_governance_proposal<D, H>(
+    shell: &mut Shell<D, H>,
+    response: &mut shim::response::FinalizeBlock,
+    id: u64,
+    grace_epoch: Epoch,
+    current_epoch: Epoch,
+) -> Result<()>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    let proposal_store = ProposalStorageReader::new(&shell.wl_storage);
+    let funds = proposal_store
+        .funds(id)?
+        .expect("Proposal funds must be present.");
+    let proposal_type = proposal_store
+        .proposal_type(id)?
+        .expect("Proposal type must be present.");
+    let proposal_author = proposal_store
+        .author(id)?
+        .expect("Proposal author must be present.");
+
+    let is_steward = pgf::is_steward(&shell.wl_storage, &proposal_author)?;
+
+    let params = read_pos_params(&shell.wl_storage)?;
+
+    let emergency_validator_quorum =
+        gov_api::get_emergency_validator_quorum(&shell.wl_storage)?;
+    let council_threshold = gov_api::get_council(&shell.wl_storage)?
+        .map(|council| council.threshold);
+    let tally_type = TallyType::from(
+        proposal_type,
+        is_steward,
+        emergency_validator_quorum,
+        council_threshold,
+    );
+    let outcome = compute_proposal_tally(
+        &shell.wl_storage,
+        &params,
+        id,
+        current_epoch,
+        tally_type,
+    )?;
+    let proposal_result = ProposalResult {
+        result: TallyResult::Rejected,
+        ..outcome.into()
+    };
+    gov_api::write_proposal_result(&mut shell.wl_storage, id, proposal_result)?;
+    gov_api::write_proposal_archive(
+        &mut shell.wl_storage,
+        id,
+        current_epoch,
+        proposal_result,
+    )?;
+    gov_api::dequeue_expired_proposal(&mut shell.wl_storage, id, grace_epoch)?;
+
+    let released = if funds.is_zero() {
+        check_zero_funds(shell, response, id)?;
+        ReleasedFunds::default()
+    } else {
+        let native_token = shell.wl_storage.get_native_token()?;
+        token::transfer(
+            &mut shell.wl_storage,
+            &native_token,
+            &gov_address,
+            &ADDRESS,
+            funds,
+        )?;
+        ReleasedFunds {
+            to_treasury: funds,
+            ..Default::default()
+        }
+    };
+
+    mark_funds_released(shell, id, released)
+}
+
+/// Execute the code of a default proposal, given the length of the code
+/// found in storage, if any. The code is only loaded from storage if it
+/// doesn't exceed the maximum proposal code size, otherwise the execution
+/// fails. The same goes for each of the code chunks bundled after the code,
+/// which are executed as further steps of the proposal.
+fn execute_default_proposal<D, H>(
+    shell: &mut Shell<D, H>,
+    id: u64,
+    code_len: Option<usize>,
+) -> namada::state::StorageResult<bool>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    let Some(code_len) = code_len else {
+        tracing::info!(
+            "Governance proposal {} doesn't have any associated proposal code.",
+            id
+        );
+        return Ok(true);
+    };
+    let max_code_size = gov_api::get_max_proposal_code_size(&shell.wl_storage)?;
+    let chunk_count =
+        ProposalStorageReader::new(&shell.wl_storage).code_chunk_count(id)?;
+    let step_lens: Vec<usize> = std::iter::once(code_len)
+        .chain((0..chunk_count).map(|idx| {
+            shell
+                .storage_key_len(&gov_storage::get_proposal_code_chunk_key(
+                    id, idx,
+                ))
+                .unwrap_or_default()
+        }))
+        .collect();
+    for (step, step_len) in step_lens.into_iter().enumerate() {
+        if step_len as u64 > max_code_size {
+            let error = format!(
+                "The code of step {} of governance proposal {id} ({step_len} \
+                 bytes) exceeds the maximum proposal code size of \
+                 {max_code_size} bytes, it won't be executed.",
+                step + 1
+            );
+            tracing::error!("{error}");
+            gov_api::write_proposal_execution_result(
+                &mut shell.wl_storage,
+                id,
+                &ProposalExecutionResult::failed(0, error),
+            )?;
+            return Ok(false);
+        }
+    }
+    let reader = ProposalStorageReader::new(&shell.wl_storage);
+    let code = reader.code(id)?.ok_or_else(|| {
+        namada::state::StorageError::new_const("Missing proposal code")
+    })?;
+    let chunks = reader.code_chunks(id)?;
+    // Each chunk must still match the hash stored along with it
+    for (idx, chunk) in chunks.iter().enumerate() {
+        let hash = reader.code_chunk_hash(id, idx as u64)?;
+        if hash != Some(Hash::sha256(chunk)) {
+            let error = format!(
+                "The code chunk {idx} of governance proposal {id} doesn't \
+                 match its hash, it won't be executed."
+            );
+            tracing::error!("{error}");
+            gov_api::write_proposal_execution_result(
+                &mut shell.wl_storage,
+                id,
+                &ProposalExecutionResult::failed(0, error),
+            )?;
+            return Ok(false);
+        }
+    }
+    // The compressed steps are only decompressed once about to be executed,
+    // and must not exceed the maximum proposal code size once decompressed
+    let steps = match std::iter::once(code)
+        .chain(chunks)
+        .map(|code| gov_code::decode_code(code, max_code_size))
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(steps) => steps,
+        Err(err) => {
+            let error = format!(
+                "The code of governance proposal {id} can't be decoded, it \
+                 won't be executed: {err}"
+            );
+            tracing::error!("{error}");
+            gov_api::write_proposal_execution_result(
+                &mut shell.wl_storage,
+                id,
+                &ProposalExecutionResult::failed(0, error),
+            )?;
+            return Ok(false);
+        }
+    };
+
+    let txs = steps
+        .into_iter()
+        .map(|code| {
+            let mut tx =
+                Tx::from_type(TxType::Decrypted(DecryptedTx::Decrypted));
+            tx.header.chain_id = shell.chain_id.clone();
+            tx.set_data(Data::new(encode(&id)));
+            tx.set_code(Code::new(code, None));
+            tx
+        })
+        .collect();
+
+    execute_proposal_tx(shell, id, txs)
+}
+
+/// Apply the txs carrying the code of the proposal with the given id, one
+/// per step of the proposal, in order.
+///
+/// The tx is applied in its own tx write log, layered on top of the block
+/// write log and of the committed storage. The block write log already holds
+/// the writes of the proposals executed earlier in the same block, so both
+/// the proposal code and the VPs it triggers observe them, regardless of the
+/// read path. If the tx is accepted, its writes are committed to the block
+/// write log and become visible to the proposals executed after it, otherwise
+/// they are dropped, such that a rejected proposal never leaks any write.
+///
+/// The steps of a proposal are atomic: the writes of an accepted step are
+/// precommitted, such that the code of the next steps reads them, but they
+/// are only committed to the block write log once all the steps are
+/// accepted. The first rejected step stops the execution and drops the
+/// writes of all the steps. The steps share a single gas meter.
+///
+/// The execution marker of the proposal, which VPs check to authorize
+/// governance changes, is only visible while the tx is applied. It is scoped
+/// to the proposal and to the current block height. The proposal fails if a
+/// marker is already present, rather than reusing it.
+///
+/// A panic raised while applying a step, e.g. by a host function, is
+/// contained and handled as a rejection of the step, such that the writes of
+/// the proposal are dropped. The execution result and the proposal event
+/// are flagged as panicked.
+///
+/// The detailed outcome of the execution, including the verdict of each VP
+/// triggered by the tx, is written under
+/// [`gov_storage::get_proposal_execution_result_key`].
+fn execute_proposal_tx<D, H>(
+    shell: &mut Shell<D, H>,
+    id: u64,
+    txs: Vec<Tx>,
+) -> namada::state::StorageResult<bool>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    let pending_execution_key = gov_storage::get_proposal_execution_key(id);
+    if shell.wl_storage.has_key(&pending_execution_key)? {
+        let error = format!(
+            "Found a stale execution marker of governance proposal {id}, its \
+             code won't be executed."
+        );
+        tracing::error!("{error}");
+        gov_api::write_proposal_execution_result(
+            &mut shell.wl_storage,
+            id,
+            &ProposalExecutionResult::failed(0, error),
+        )?;
+        return Ok(false);
+    }
+    gov_api::write_pending_execution(&mut shell.wl_storage, id)?;
+
+    // No gas limit for governance proposal
+    let mut tx_gas_meter = TxGasMeter::new_from_sub_limit(u64::MAX.into());
+    let steps = txs.len();
+    let mut execution_result = ProposalExecutionResult {
+        accepted: true,
+        ..Default::default()
+    };
+    for (step, tx) in txs.into_iter().enumerate() {
+        let tx_result = panic::catch_unwind(AssertUnwindSafe(|| {
+            protocol::dispatch_tx(
+                tx,
+                &[], /*  this is used to compute the fee
+                      * based on the code size. We dont
+                      * need it here. */
+                TxIndex::default(),
+                &mut tx_gas_meter,
+                &mut shell.wl_storage,
+                &mut shell.vp_wasm_cache,
+                &mut shell.tx_wasm_cache,
+                None,
+            )
+        }));
+        // The gas of a rejected or failed proposal is accounted for too,
+        // since its code was run nonetheless
+        execution_result.gas_used = tx_gas_meter.get_tx_consumed_gas().into();
+        match tx_result {
+            Err(payload) => {
+                let error = panic_message(payload.as_ref());
+                tracing::error!(
+                    "The code of governance proposal {} panicked: {}. The \
+                     code has been rejected.",
+                    id,
+                    error
+                );
+                // The panic may have poisoned the in-memory wasm caches
+                shell.vp_wasm_cache.recover_poisoned();
+                shell.tx_wasm_cache.recover_poisoned();
+                execution_result.accepted = false;
+                execution_result.panicked = true;
+                execution_result.error =
+                    Some(format!("The proposal code panicked: {error}"));
+            }
+            Ok(Ok(tx_result)) => {
+                execution_result.accepted = tx_result.is_accepted();
+                execution_result.vps.extend(
+                    tx_result.vps_result.verdicts().map(
+                        |(address, accepted, error)| VpVerdict {
+                            address: address.clone(),
+                            accepted,
+                            error: error.map(str::to_string),
+                        },
+                    ),
+                );
+            }
+            Ok(Err(err)) => {
+                execution_result.accepted = false;
+                execution_result.error = Some(err.to_string());
+            }
+        }
+        if !execution_result.accepted {
+            if steps > 1 {
+                let error = format!(
+                    "Step {} of {steps} of governance proposal {id} was \
+                     rejected",
+                    step + 1
+                );
+                tracing::error!("{error}");
+                execution_result.error = Some(match execution_result.error {
+                    Some(err) => format!("{error}: {err}"),
+                    None => error,
+                });
+            }
+            break;
+        }
+        if step + 1 < steps {
+            shell.wl_storage.write_log_mut().precommit_tx();
+        }
+    }
+    shell
+        .wl_storage
+        .storage
+        .block
+        .gas
+        .add_proposal_gas(id, execution_result.gas_used.into())
+        .into_storage_result()?;
+    if execution_result.accepted {
+        shell.wl_storage.commit_tx();
+    } else {
+        shell.wl_storage.drop_tx();
+    }
+    shell.wl_storage.delete(&pending_execution_key)?;
+    gov_api::write_proposal_execution_result(
+        &mut shell.wl_storage,
+        id,
+        &execution_result,
+    )?;
+    Ok(execution_result.accepted)
+}
+
+fn execute_eth_bridge_contracts_proposal<S>(
+    storage: &mut S,
+    update: BridgeContractsUpdate,
+) -> namada::state::StorageResult<Contracts>
+where
+    S: StorageRead + StorageWrite,
+{
+    let old_contracts = read_contracts(storage)?;
+    // a redeployed bridge contract is a new version of the contract
+    let bridge = if old_contracts.bridge.address == update.bridge {
+        old_contracts.bridge
+    } else {
+        UpgradeableContract {
+            address: update.bridge,
+            version: old_contracts.bridge.version.next(),
+        }
+    };
+    schedule_contracts_update(
+        storage,
+        ScheduledContractsUpdate {
+            activation_epoch: update.activation_epoch,
+            contracts: Contracts {
+                native_erc20: update.native_erc20,
+                bridge,
+            },
+            bridge_pool_nonce: update.bridge_pool_nonce,
+        },
+    )?;
+    Ok(old_contracts)
+}
+
+fn execute_eth_bridge_pause_proposal<S>(
+    storage: &mut S,
+    action: BridgePauseAction,
+) -> namada::state::StorageResult<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    set_bridge_paused(storage, action.is_pause())
+}
+
+/// Rotate the Ethereum hot key of a validator, returning whether the
+/// rotation was applied. The checks of the governance VP are repeated, as
+/// the validator or its keys may have changed since the proposal was
+/// submitted, and a rotation that is no longer valid is not applied.
+fn execute_eth_hot_key_rotation_proposal<S>(
+    storage: &mut S,
+    rotation: &EthHotKeyRotation,
+    current_epoch: Epoch,
+) -> namada::state::StorageResult<bool>
+where
+    S: StorageRead + StorageWrite,
+{
+    if !rotation.has_eth_key()
+        || !namada::proof_of_stake::is_validator(storage, &rotation.validator)?
+    {
+        return Ok(false);
+    }
+    let params = read_pos_params(storage)?;
+    // the new key can only be set at the pipeline epoch, as the keys of
+    // the epochs before it may already have been signed over, and the keys
+    // past it can't be read until the key is updated again
+    if rotation.activation_epoch != current_epoch + params.pipeline_len {
+        return Ok(false);
+    }
+    let hot_key_handle = validator_eth_hot_key_handle(&rotation.validator);
+    let hot_key =
+        hot_key_handle.get(storage, rotation.activation_epoch, &params)?;
+    let cold_key = validator_eth_cold_key_handle(&rotation.validator).get(
+        storage,
+        rotation.activation_epoch,
+        &params,
+    )?;
+    if hot_key.as_ref() == Some(&rotation.eth_hot_key)
+        || cold_key.as_ref() == Some(&rotation.eth_hot_key)
+    {
+        return Ok(false);
+    }
+    hot_key_handle.set(
+        storage,
+        rotation.eth_hot_key.clone(),
+        current_epoch,
+        params.pipeline_len,
+    )?;
+    Ok(true)
+}
+
+/// Transfer the funds of a treasury spend out of the treasury, returning
+/// whether the spend was applied. A spend that the given balance of the
+/// treasury can't cover is not applied, without failing the block.
+fn execute_treasury_spend_proposal<S>(
+    storage: &mut S,
+    spend: &TreasurySpend,
+    treasury_balance: token::Amount,
+) -> namada::state::StorageResult<bool>
+where
+    S: StorageRead + StorageWrite,
+{
+    if treasury_balance < spend.amount {
+        return Ok(false);
+    }
+    token::transfer(
+        storage,
+        &spend.token,
+        &ADDRESS,
+        &spend.recipient,
+        spend.amount,
+    )?;
+    Ok(true)
+}
+
+/// Write the new values of the protocol parameters changed by a proposal,
+/// returning their values before and after the change. The writes are
+/// applied without any wasm, under the execution marker of the proposal like
+/// the code of the other proposals, such that governance authorizes them.
+fn execute_parameter_change_proposal<S>(
+    storage: &mut S,
+    id: u64,
+    change: ParameterChange,
+) -> namada::state::StorageResult<Vec<AppliedParameterChange>>
+where
+    S: StorageRead + StorageWrite,
+{
+    gov_api::write_pending_execution(storage, id)?;
+    let mut applied = Vec::with_capacity(change.changes.len());
+    for (key, new_value) in change.changes {
+        let old_value = storage.read_bytes(&key)?;
+        storage.write_bytes(&key, &new_value)?;
+        applied.push(AppliedParameterChange {
+            key,
+            old_value,
+            new_value,
+        });
+    }
+    storage.delete(&gov_storage::get_proposal_execution_key(id))?;
+    Ok(applied)
+}
+
+/// Apply the update of a wasm allowlist proposal, returning the resulting
+/// allowlist and whether the update was applied. An update that would
+/// empty the allowlist, allowing any code, is refused.
+fn execute_wasm_allowlist_proposal<S>(
+    storage: &mut S,
+    update: &UpdateWasmAllowlist,
+) -> namada::state::StorageResult<(Vec<String>, bool)>
+where
+    S: StorageRead + StorageWrite,
+{
+    let allowlist = match update.kind {
+        TxOrVp::Tx => namada::parameters::update_tx_allowlist(
+            storage,
+            &update.add,
+            &update.remove,
+        )?,
+        TxOrVp::Vp => namada::parameters::update_vp_allowlist(
+            storage,
+            &update.add,
+            &update.remove,
+        )?,
+    };
+    // The hashes to remove are only left in the allowlist if the update was
+    // refused
+    let applied = !update
+        .remove
+        .iter()
+        .any(|hash| allowlist.contains(&hash.to_string().to_lowercase()));
+    if !applied {
+        tracing::warn!(
+            "The update of the {} allowlist was refused, as it would remove \
+             every code hash from it, allowing any {} code.",
+            update.kind,
+            update.kind
+        );
+    }
+    Ok((allowlist, applied))
+}
+
+fn execute_pgf_steward_proposal<S>(
+    storage: &mut S,
+    stewards: BTreeSet<AddRemove<Address>>,
+) -> Result<bool>
+where
+    S: StorageRead + StorageWrite,
+{
+    for action in stewards {
+        match action {
+            AddRemove::Add(address) => {
+                pgf_storage::stewards_handle().insert(
+                    storage,
+                    address.to_owned(),
+                    StewardDetail::base(address),
+                )?;
+            }
+            AddRemove::Remove(address) => {
+                pgf_storage::stewards_handle().remove(storage, &address)?;
+            }
+        }
+    }
+
+    Ok(true)
+}
+
+fn execute_pgf_funding_proposal<D, H>(
+    storage: &mut WlStorage<D, H>,
+    token: &Address,
+    fundings: BTreeSet<PGFAction>,
+    proposal_id: u64,
+) -> Result<bool>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    for funding in fundings {
+        match funding {
+            PGFAction::Continuous(action) => match action {
+                AddRemove::Add(target) => {
+                    pgf_storage::fundings_handle().insert(
+                        storage,
+                        target.target().clone(),
+                        StoragePgfFunding::new(target.clone(), proposal_id),
+                    )?;
+                    tracing::info!(
+                        "Added/Updated ContinousPgf from proposal id {}: set \
+                         {} to {}.",
+                        proposal_id,
+                        target.amount().to_string_native(),
+                        target.target()
+                    );
+                }
+                AddRemove::Remove(target) => {
+                    pgf_storage::fundings_handle()
+                        .remove(storage, &target.target())?;
+                    tracing::info!(
+                        "Removed ContinousPgf from proposal id {}: set {} to \
+                         {}.",
+                        proposal_id,
+                        target.amount().to_string_native(),
+                        target.target()
+                    );
+                }
+            },
+            PGFAction::Retro(target) => {
+                let result = match &target {
+                    PGFTarget::Internal(target) => token::transfer(
+                        storage,
+                        token,
+                        &ADDRESS,
+                        &target.target,
+                        target.amount,
+                    ),
+                    PGFTarget::Ibc(target) => {
+                        ibc::transfer_over_ibc(storage, token, &ADDRESS, target)
+                    }
+                };
+                match result {
+                    Ok(()) => tracing::info!(
+                        "Execute RetroPgf from proposal id {}: sent {} to {}.",
+                        proposal_id,
+                        target.amount().to_string_native(),
+                        target.target()
+                    ),
+                    Err(e) => tracing::warn!(
+                        "Error in RetroPgf transfer from proposal id {}, \
+                         amount {} to {}: {}",
+                        proposal_id,
+                        target.amount().to_string_native(),
+                        target.target(),
+                        e
+                    ),
+                }
+            }
+        }
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+    use std::str::FromStr;
+
+    use borsh_ext::BorshSerializeExt;
+    use namada::eth_bridge::protocol::validation::validator_set_update::validate_valset_upd_vext;
+    use namada::eth_bridge::protocol::validation::{
+        ExtensionKind, InvalidVextReason,
+    };
+    use namada::governance::storage::proposal::{
+        PendingExecution, VotingHeights,
+    };
+    use namada::governance::{InitProposalData, VoteProposalData};
+    use namada::ledger::pos::PosQueries;
+    use namada::proof_of_stake::storage::{
+        read_validator_stake, update_total_deltas, update_validator_deltas,
+    };
+    use namada::state::merkle_tree::MerkleRoot;
+    use namada::types::address::testing::{
+        established_address_1, established_address_2,
+    };
+    use namada::types::dec::Dec;
+    use namada::types::hash::Hash;
+    use namada::types::key::testing::gen_keypair;
+    use namada::types::key::{common, secp256k1, RefTo};
+    use namada::types::storage::{BlockHeight, Key, KeySeg};
+    use namada::vm::host_env::testing::with_tx_write_panicking;
+    use namada::vote_ext::validator_set_update;
+    use namada_sdk::eth_bridge::EthBridgeQueries;
+    use namada_sdk::governance::ProposalVote;
+    use namada_test_utils::tx_data::TxWriteData;
+    use namada_test_utils::TestWasms;
+
+    use super::*;
+    use crate::node::ledger::shell::test_utils::{setup, TestShell};
+
+    /// Make the tx of a proposal running the given test wasm
+    fn proposal_tx(shell: &TestShell, wasm: TestWasms, data: Vec<u8>) -> Tx {
+        let mut tx = Tx::from_type(TxType::Decrypted(DecryptedTx::Decrypted));
+        tx.header.chain_id = shell.chain_id.clone();
+        tx.set_data(Data::new(data));
+        tx.set_code(Code::new(wasm.read_bytes(), None));
+        tx
+    }
+
+    /// Execute, in the same block, a proposal reading a key, a proposal
+    /// writing it, the reading proposal once again and a rejected proposal.
+    /// Returns the acceptance of each proposal and the final value of the
+    /// key.
+    fn execute_interacting_proposals() -> (Vec<bool>, Option<Vec<u8>>) {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+
+        // An account accepting any change, that owns the key written by a
+        // proposal and read by another one
+        let owner = established_address_1();
+        let vp_code = TestWasms::VpAlwaysTrue.read_bytes();
+        let vp_hash = Hash::sha256(&vp_code);
+        shell
+            .wl_storage
+            .storage
+            .write(
+                &Key::wasm_code_len(&vp_hash),
+                (vp_code.len() as u64).serialize_to_vec(),
+            )
+            .unwrap();
+        shell
+            .wl_storage
+            .storage
+            .write(&Key::wasm_code(&vp_hash), vp_code)
+            .unwrap();
+        shell
+            .wl_storage
+            .storage
+            .write(&Key::validity_predicate(&owner), vp_hash.serialize_to_vec())
+            .unwrap();
+
+        let key = Key::from(owner.to_db_key())
+            .push(&"proposal_state".to_string())
+            .unwrap();
+        let value = b"written by proposal A".to_vec().serialize_to_vec();
+        let write_tx = proposal_tx(
+            &shell,
+            TestWasms::TxWriteStorageKey,
+            TxWriteData {
+                key: key.clone(),
+                value: value.clone(),
+            }
+            .serialize_to_vec(),
+        );
+        let read_tx = proposal_tx(
+            &shell,
+            TestWasms::TxReadStorageKey,
+            key.serialize_to_vec(),
+        );
+        // The owner of this key has no VP, so the proposal gets rejected
+        let rejected_key = Key::from(established_address_2().to_db_key())
+            .push(&"proposal_state".to_string())
+            .unwrap();
+        let rejected_tx = proposal_tx(
+            &shell,
+            TestWasms::TxWriteStorageKey,
+            TxWriteData {
+                key: rejected_key.clone(),
+                value,
+            }
+            .serialize_to_vec(),
+        );
+
+        let accepted = vec![
+            execute_proposal_tx(&mut shell, 0, vec![read_tx.clone()]).unwrap(),
+            execute_proposal_tx(&mut shell, 1, vec![write_tx]).unwrap(),
+            execute_proposal_tx(&mut shell, 2, vec![read_tx]).unwrap(),
+            execute_proposal_tx(&mut shell, 3, vec![rejected_tx]).unwrap(),
+        ];
+
+        // Rejected proposals leave no trace in the block write log
+        assert!(!shell.wl_storage.has_key(&rejected_key).unwrap());
+        // The execution markers are only visible while executing
+        for id in 0..4 {
+            let pending_execution_key =
+                gov_storage::get_proposal_execution_key(id);
+            assert!(!shell.wl_storage.has_key(&pending_execution_key).unwrap());
+        }
+        // The gas of every proposal is accounted for in the block, whether
+        // the proposal got accepted or not
+        let block_gas = &shell.wl_storage.storage.block.gas;
+        let proposals_gas = (0..4)
+            .map(|id| block_gas.get_proposal_gas(id).unwrap())
+            .try_fold(Gas::default(), |acc, gas| acc.checked_add(gas))
+            .unwrap();
+        assert!(proposals_gas > Gas::default());
+        assert_eq!(proposals_gas, block_gas.get_block_consumed_gas());
+
+        let final_value = shell.wl_storage.read_bytes(&key).unwrap();
+        (accepted, final_value)
+    }
+
+    /// Test that the code of a proposal observes the writes of the proposals
+    /// executed before it in the same block, identically on every node
+    #[test]
+    fn test_proposal_sees_prior_proposal_writes() {
+        let (accepted, final_value) = execute_interacting_proposals();
+        // The first read happens before the key is written
+        assert_eq!(accepted, vec![false, true, true, false]);
+        assert_eq!(
+            final_value,
+            Some(b"written by proposal A".to_vec().serialize_to_vec())
+        );
+
+        // Another node executing the same proposals ends up in the same state
+        assert_eq!(execute_interacting_proposals(), (accepted, final_value));
+    }
+
+    /// Test that the steps of a proposal see the writes of the steps before
+    /// them, and that a rejected step drops the writes of all the steps
+    #[test]
+    fn test_proposal_steps_are_atomic() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+
+        // An account accepting any change
+        let owner = established_address_1();
+        let vp_code = TestWasms::VpAlwaysTrue.read_bytes();
+        let vp_hash = Hash::sha256(&vp_code);
+        shell
+            .wl_storage
+            .storage
+            .write(
+                &Key::wasm_code_len(&vp_hash),
+                (vp_code.len() as u64).serialize_to_vec(),
+            )
+            .unwrap();
+        shell
+            .wl_storage
+            .storage
+            .write(&Key::wasm_code(&vp_hash), vp_code)
+            .unwrap();
+        shell
+            .wl_storage
+            .storage
+            .write(&Key::validity_predicate(&owner), vp_hash.serialize_to_vec())
+            .unwrap();
+
+        let key = Key::from(owner.to_db_key())
+            .push(&"proposal_state".to_string())
+            .unwrap();
+        let write_tx = proposal_tx(
+            &shell,
+            TestWasms::TxWriteStorageKey,
+            TxWriteData {
+                key: key.clone(),
+                value: b"written by step 1".to_vec().serialize_to_vec(),
+            }
+            .serialize_to_vec(),
+        );
+        let read_tx = proposal_tx(
+            &shell,
+            TestWasms::TxReadStorageKey,
+            key.serialize_to_vec(),
+        );
+        // The owner of this key has no VP, so the step gets rejected
+        let rejected_tx = proposal_tx(
+            &shell,
+            TestWasms::TxWriteStorageKey,
+            TxWriteData {
+                key: Key::from(established_address_2().to_db_key())
+                    .push(&"proposal_state".to_string())
+                    .unwrap(),
+                value: b"rejected".to_vec().serialize_to_vec(),
+            }
+            .serialize_to_vec(),
+        );
+
+        // The second step reads the key written by the first one, but the
+        // third step is rejected, so nothing is committed
+        assert!(
+            !execute_proposal_tx(
+                &mut shell,
+                0,
+                vec![write_tx.clone(), read_tx.clone(), rejected_tx],
+            )
+            .unwrap()
+        );
+        assert!(!shell.wl_storage.has_key(&key).unwrap());
+        let pending_execution_key = gov_storage::get_proposal_execution_key(0);
+        assert!(!shell.wl_storage.has_key(&pending_execution_key).unwrap());
+        let execution_result =
+            gov_api::get_proposal_execution_result(&shell.wl_storage, 0)
+                .unwrap()
+                .expect("Test failed");
+        assert!(!execution_result.accepted);
+        assert!(
+            execution_result
+                .error
+                .expect("Test failed")
+                .contains("Step 3 of 3")
+        );
+        assert!(execution_result.gas_used > 0);
+
+        // Once all the steps are accepted, their writes are committed
+        assert!(
+            execute_proposal_tx(&mut shell, 1, vec![write_tx, read_tx])
+                .unwrap()
+        );
+        assert_eq!(
+            shell.wl_storage.read_bytes(&key).unwrap(),
+            Some(b"written by step 1".to_vec().serialize_to_vec())
+        );
+        let execution_result =
+            gov_api::get_proposal_execution_result(&shell.wl_storage, 1)
+                .unwrap()
+                .expect("Test failed");
+        assert!(execution_result.accepted);
+        assert!(execution_result.error.is_none());
+    }
+
+    /// Test that the outcome of the execution of a proposal records the
+    /// VP which rejected its code
+    #[test]
+    fn test_proposal_execution_result_records_rejecting_vp() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+
+        // An account rejecting any change
+        let owner = established_address_1();
+        let vp_code = TestWasms::VpAlwaysFalse.read_bytes();
+        let vp_hash = Hash::sha256(&vp_code);
+        shell
+            .wl_storage
+            .storage
+            .write(
+                &Key::wasm_code_len(&vp_hash),
+                (vp_code.len() as u64).serialize_to_vec(),
+            )
+            .unwrap();
+        shell
+            .wl_storage
+            .storage
+            .write(&Key::wasm_code(&vp_hash), vp_code)
+            .unwrap();
+        shell
+            .wl_storage
+            .storage
+            .write(&Key::validity_predicate(&owner), vp_hash.serialize_to_vec())
+            .unwrap();
+
+        let key = Key::from(owner.to_db_key())
+            .push(&"proposal_state".to_string())
+            .unwrap();
+        let tx = proposal_tx(
+            &shell,
+            TestWasms::TxWriteStorageKey,
+            TxWriteData {
+                key: key.clone(),
+                value: b"rejected".to_vec().serialize_to_vec(),
+            }
+            .serialize_to_vec(),
+        );
+        assert!(!execute_proposal_tx(&mut shell, 0, vec![tx]).unwrap());
+        assert!(!shell.wl_storage.has_key(&key).unwrap());
+
+        let execution_result =
+            gov_api::get_proposal_execution_result(&shell.wl_storage, 0)
+                .unwrap()
+                .expect("Test failed");
+        assert!(!execution_result.accepted);
+        assert!(execution_result.error.is_none());
+        assert!(execution_result.gas_used > 0);
+        let rejected: Vec<_> = execution_result
+            .rejected_vps()
+            .map(|verdict| &verdict.address)
+            .collect();
+        assert_eq!(rejected, vec![&owner]);
+
+        // Proposals without code are never executed
+        assert!(
+            gov_api::get_proposal_execution_result(&shell.wl_storage, 1)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    /// Test that the malformed code of a proposal submitted without strict
+    /// proposal code validation only fails once executed
+    #[test]
+    fn test_malformed_proposal_code_fails_at_execution() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        assert!(
+            !gov_api::get_strict_proposal_code_validation(&shell.wl_storage)
+                .unwrap()
+        );
+
+        let mut tx = Tx::from_type(TxType::Decrypted(DecryptedTx::Decrypted));
+        tx.header.chain_id = shell.chain_id.clone();
+        tx.set_data(Data::new(encode(&0_u64)));
+        tx.set_code(Code::new(b"not a wasm module".to_vec(), None));
+        assert!(!execute_proposal_tx(&mut shell, 0, vec![tx]).unwrap());
+
+        let execution_result =
+            gov_api::get_proposal_execution_result(&shell.wl_storage, 0)
+                .unwrap()
+                .expect("Test failed");
+        assert!(!execution_result.accepted);
+        assert!(execution_result.vps.is_empty());
+        assert!(execution_result.error.is_some());
+    }
+
+    /// Test that the tally of a passed proposal whose code is rejected is
+    /// still recorded, archived and accounted for in the participation of
+    /// its epoch
+    #[test]
+    fn test_rejected_proposal_code_keeps_tally_records() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        init_committed_code_proposal(
+            &mut shell,
+            0,
+            Some(b"not a wasm module".to_vec()),
+        );
+        let epoch = Epoch::default().next();
+        shell.wl_storage.storage.block.epoch = epoch;
+        let mut response = shim::response::FinalizeBlock::default();
+        let result =
+            execute_governance_proposals(&mut shell, &mut response).unwrap();
+        assert_eq!(result.passed, vec![0]);
+        let execution_result =
+            gov_api::get_proposal_execution_result(&shell.wl_storage, 0)
+                .unwrap()
+                .expect("Test failed");
+        assert!(!execution_result.accepted);
+
+        let proposal_result =
+            gov_api::get_proposal_result(&shell.wl_storage, 0)
+                .unwrap()
+                .expect("Test failed");
+        assert_eq!(proposal_result.result, TallyResult::Passed);
+        let archive =
+            gov_api::get_proposal_archive(&shell.wl_storage, epoch, 0)
+                .unwrap()
+                .expect("Test failed");
+        assert_eq!(archive.result, proposal_result);
+        assert!(
+            gov_api::get_participation_at(&shell.wl_storage, epoch)
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    /// Conclude a proposal corrupted by `corrupt` in the first block of a
+    /// new epoch. Returns the emitted events, and the merkle root once the
+    /// block is committed.
+    fn conclude_corrupted_proposal(
+        corrupt: impl Fn(&mut TestShell, u64),
+    ) -> (Vec<Event>, MerkleRoot) {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let validator = shell.mode.get_validator_address().unwrap().clone();
+
+        let proposal_id = 0;
+        let proposal = InitProposalData {
+            id: proposal_id,
+            content: Hash::default(),
+            author: validator,
+            voting_start_epoch: Epoch::default(),
+            voting_end_epoch: Epoch::default().next(),
+            grace_epoch: Epoch::default().next(),
+            voting_heights: None,
+            priority: None,
+            code_chunks: vec![],
+            r#type: ProposalType::Default(None),
+        };
+        namada::governance::init_proposal(
+            &mut shell.wl_storage,
+            proposal,
+            vec![],
+            None,
+            vec![],
+        )
+        .unwrap();
+        corrupt(&mut shell, proposal_id);
+
+        shell.proposal_data.insert(proposal_id);
+        shell.wl_storage.storage.block.epoch = Epoch::default().next();
+        let mut response = shim::response::FinalizeBlock::default();
+        execute_governance_proposals(&mut shell, &mut response).unwrap();
+
+        shell.wl_storage.commit_block().unwrap();
+        (response.events, shell.wl_storage.storage.merkle_root())
+    }
+
+    /// Test that a proposal missing any of the keys read to conclude it, or
+    /// holding an undecodable value under one of them, is rejected with a
+    /// malformed event, and that every node converges on the same state
+    #[test]
+    fn test_malformed_proposal_rejected() {
+        type KeyFn = fn(u64) -> Key;
+        let required_keys: [(KeyFn, &str); 5] = [
+            (gov_storage::get_funds_key, "funds"),
+            (
+                gov_storage::get_voting_start_epoch_key,
+                "voting start epoch",
+            ),
+            (gov_storage::get_voting_end_epoch_key, "voting end epoch"),
+            (gov_storage::get_proposal_type_key, "type"),
+            (gov_storage::get_author_key, "author"),
+        ];
+
+        for (get_key, field) in required_keys {
+            let delete = |shell: &mut TestShell, id| {
+                shell.wl_storage.delete(&get_key(id)).unwrap();
+            };
+            let (events, root) = conclude_corrupted_proposal(delete);
+            assert_eq!(events.len(), 1, "{field}");
+            assert_eq!(events[0][events::attributes::MALFORMED], field);
+            assert_eq!(
+                events[0][events::attributes::TALLY_RESULT],
+                TallyResult::Rejected.to_string()
+            );
+
+            // another node concluding the same proposal reaches the same
+            // state
+            let (other_events, other_root) =
+                conclude_corrupted_proposal(delete);
+            assert_eq!(other_events, events);
+            assert_eq!(other_root, root, "{field}");
+        }
+
+        let garble = |shell: &mut TestShell, id| {
+            shell
+                .wl_storage
+                .write_bytes(&gov_storage::get_funds_key(id), [0xff])
+                .unwrap();
+        };
+        let (events, root) = conclude_corrupted_proposal(garble);
+        assert_eq!(events[0][events::attributes::MALFORMED], "funds");
+        assert_eq!(conclude_corrupted_proposal(garble), (events, root));
+
+        let leave_marker = |shell: &mut TestShell, id| {
+            gov_api::write_pending_execution(&mut shell.wl_storage, id)
+                .unwrap();
+        };
+        let (events, root) = conclude_corrupted_proposal(leave_marker);
+        assert_eq!(
+            events[0][events::attributes::MALFORMED],
+            "pending execution"
+        );
+        assert_eq!(conclude_corrupted_proposal(leave_marker), (events, root));
+    }
+
+    /// Test that a user tx replaying the code and data of a proposal is
+    /// rejected by the VPs, unless the execution marker of the proposal
+    /// was written at the current height and scoped to the proposal, and
+    /// that the shell doesn't execute a proposal over a stale marker
+    #[test]
+    fn test_proposal_code_replay_rejected() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        shell.wl_storage.storage.block.height = BlockHeight(5);
+        let tx = proposal_tx(&shell, TestWasms::TxProposalCode, encode(&0_u64));
+        let apply_user_tx = |shell: &mut TestShell| {
+            let mut tx_gas_meter =
+                TxGasMeter::new_from_sub_limit(u64::MAX.into());
+            let result = protocol::dispatch_tx(
+                tx.clone(),
+                &[],
+                TxIndex::default(),
+                &mut tx_gas_meter,
+                &mut shell.wl_storage,
+                &mut shell.vp_wasm_cache,
+                &mut shell.tx_wasm_cache,
+                None,
+            );
+            shell.wl_storage.drop_tx();
+            matches!(result, Ok(result) if result.is_accepted())
+        };
+        let pending_execution_key = gov_storage::get_proposal_execution_key(0);
+
+        assert!(!apply_user_tx(&mut shell));
+
+        // a marker left over from a previous block
+        shell
+            .wl_storage
+            .write(
+                &pending_execution_key,
+                PendingExecution {
+                    proposal_id: 0,
+                    height: BlockHeight(4),
+                },
+            )
+            .unwrap();
+        assert!(!apply_user_tx(&mut shell));
+        assert!(!execute_proposal_tx(&mut shell, 0, vec![tx.clone()]).unwrap());
+
+        // a marker scoped to another proposal
+        shell
+            .wl_storage
+            .write(
+                &pending_execution_key,
+                PendingExecution {
+                    proposal_id: 1,
+                    height: BlockHeight(5),
+                },
+            )
+            .unwrap();
+        assert!(!apply_user_tx(&mut shell));
+
+        shell.wl_storage.delete(&pending_execution_key).unwrap();
+        assert!(execute_proposal_tx(&mut shell, 0, vec![tx.clone()]).unwrap());
+        assert!(!shell.wl_storage.has_key(&pending_execution_key).unwrap());
+        assert!(!apply_user_tx(&mut shell));
+    }
+
+    /// Init two proposals carrying code, accepted by the only validator,
+    /// and a proposal rejected by it. Their ids are inserted in
+    /// `proposal_data` in the given order, before concluding them. Returns
+    /// the result of the execution, the emitted events and the merkle root
+    /// once the block is committed.
+    fn conclude_proposals_in_order(
+        order: [u64; 3],
+    ) -> (ProposalsResult, Vec<Event>, MerkleRoot) {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let validator = shell.mode.get_validator_address().unwrap().clone();
+        let fixture = [
+            (Some(TestWasms::TxProposalCode), ProposalVote::Yay),
+            (None, ProposalVote::Nay),
+            (Some(TestWasms::TxProposalMaspRewards), ProposalVote::Yay),
+        ];
+
+        for (proposal_id, (wasm, vote)) in (0..).zip(fixture) {
+            let code = wasm.map(|wasm| wasm.read_bytes());
+            let proposal = InitProposalData {
+                id: proposal_id,
+                content: Hash::default(),
+                author: validator.clone(),
+                voting_start_epoch: Epoch::default(),
+                voting_end_epoch: Epoch::default().next(),
+                grace_epoch: Epoch::default().next(),
+                voting_heights: None,
+                priority: None,
+                code_chunks: vec![],
+                r#type: ProposalType::Default(
+                    code.as_ref().map(|code| Hash::sha256(code)),
+                ),
+            };
+            namada::governance::init_proposal(
+                &mut shell.wl_storage,
+                proposal,
+                vec![],
+                code,
+                vec![],
+            )
+            .unwrap();
+            let vote = VoteProposalData {
+                id: proposal_id,
+                vote,
+                voter: validator.clone(),
+                delegations: vec![validator.clone()],
+            };
+            namada::governance::vote_proposal(&mut shell.wl_storage, vote)
+                .unwrap();
+        }
+
+        for proposal_id in order {
+            shell.proposal_data.insert(proposal_id);
+        }
+        shell.wl_storage.storage.block.epoch = Epoch::default().next();
+        let mut response = shim::response::FinalizeBlock::default();
+        let result =
+            execute_governance_proposals(&mut shell, &mut response).unwrap();
+
+        shell.wl_storage.commit_block().unwrap();
+        (
+            result,
+            response.events,
+            shell.wl_storage.storage.merkle_root(),
+        )
+    }
+
+    /// Test that nodes conclude the proposals of an epoch in the same order,
+    /// regardless of the order in which their ids were collected, such that
+    /// they reach identical results and committed state
+    #[test]
+    fn test_proposals_concluded_deterministically() {
+        let (result, events, root) = conclude_proposals_in_order([0, 1, 2]);
+        assert_eq!(result.passed, vec![0, 2]);
+        assert_eq!(result.rejected, vec![1]);
+        // both code proposals got executed successfully, the event flags
+        // being inverted
+        assert_eq!(
+            events
+                .iter()
+                .filter(|event| {
+                    event[events::attributes::HAS_PROPOSAL_CODE] == "0"
+                        && event[events::attributes::PROPOSAL_CODE_EXIT_STATUS]
+                            == "0"
+                })
+                .count(),
+            2
+        );
+
+        for order in [[2, 1, 0], [1, 2, 0], [2, 0, 1]] {
+            let (other_result, other_events, other_root) =
+                conclude_proposals_in_order(order);
+            assert_eq!(other_result, result, "{order:?}");
+            assert_eq!(other_events, events, "{order:?}");
+            assert_eq!(other_root, root, "{order:?}");
+        }
+    }
+
+    /// Init a default proposal carrying the given code, accepted by the
+    /// only validator, and commit it to the DB
+    fn init_committed_code_proposal(
+        shell: &mut TestShell,
+        proposal_id: u64,
+        code: Option<Vec<u8>>,
+    ) {
+        let validator = shell.mode.get_validator_address().unwrap().clone();
+        let proposal = InitProposalData {
+            id: proposal_id,
+            content: Hash::default(),
+            author: validator.clone(),
+            voting_start_epoch: Epoch::default(),
+            voting_end_epoch: Epoch::default().next(),
+            grace_epoch: Epoch::default().next(),
+            voting_heights: None,
+            priority: None,
+            code_chunks: vec![],
+            r#type: ProposalType::Default(code.as_ref().map(Hash::sha256)),
+        };
+        namada::governance::init_proposal(
+            &mut shell.wl_storage,
+            proposal,
+            vec![],
+            code,
+            vec![],
+        )
+        .unwrap();
+        let vote = VoteProposalData {
+            id: proposal_id,
+            vote: ProposalVote::Yay,
+            voter: validator.clone(),
+            delegations: vec![validator],
+        };
+        namada::governance::vote_proposal(&mut shell.wl_storage, vote).unwrap();
+        shell.wl_storage.commit_block().unwrap();
+        shell.proposal_data.insert(proposal_id);
+    }
+
+    /// Test that the code of a default proposal is only read from storage
+    /// when it's about to be executed, and never if the proposal has no
+    /// code or if its code exceeds the maximum proposal code size
+    #[test]
+    fn test_proposal_code_read_lazily() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let code = TestWasms::TxProposalCode.read_bytes();
+        init_committed_code_proposal(&mut shell, 0, None);
+        init_committed_code_proposal(&mut shell, 1, Some(code.clone()));
+
+        shell.wl_storage.storage.block.epoch = Epoch::default().next();
+        let mut response = shim::response::FinalizeBlock::default();
+        execute_governance_proposals(&mut shell, &mut response).unwrap();
+        let db = &shell.wl_storage.storage.db;
+        assert_eq!(
+            db.subspace_val_reads(&gov_storage::get_proposal_code_key(0)),
+            0
+        );
+        assert!(
+            db.subspace_val_reads(&gov_storage::get_proposal_code_key(1)) > 0
+        );
+        // the event flags are inverted
+        let code_flags = |event: &Event| {
+            (
+                event[events::attributes::HAS_PROPOSAL_CODE].clone(),
+                event[events::attributes::PROPOSAL_CODE_EXIT_STATUS].clone(),
+            )
+        };
+        assert_eq!(code_flags(&response.events[0]), ("1".into(), "0".into()));
+        assert_eq!(code_flags(&response.events[1]), ("0".into(), "0".into()));
+
+        // a proposal whose code exceeds the maximum size fails without its
+        // code ever being read
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        init_committed_code_proposal(&mut shell, 0, Some(code.clone()));
+        shell
+            .wl_storage
+            .write(
+                &gov_storage::get_max_proposal_code_size_key(),
+                code.len() as u64 - 1,
+            )
+            .unwrap();
+
+        shell.wl_storage.storage.block.epoch = Epoch::default().next();
+        let mut response = shim::response::FinalizeBlock::default();
+        let result =
+            execute_governance_proposals(&mut shell, &mut response).unwrap();
+        assert_eq!(result.passed, vec![0]);
+        assert_eq!(
+            shell
+                .wl_storage
+                .storage
+                .db
+                .subspace_val_reads(&gov_storage::get_proposal_code_key(0)),
+            0
+        );
+        assert_eq!(code_flags(&response.events[0]), ("0".into(), "1".into()));
+    }
+
+    /// Test that the compressed code of a proposal is decompressed before
+    /// being executed, and that compressed code exceeding the maximum
+    /// proposal code size once decompressed isn't executed
+    #[test]
+    fn test_compressed_proposal_code_execution() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let code = TestWasms::TxProposalCode.read_bytes();
+        let max_code_size =
+            gov_api::get_max_proposal_code_size(&shell.wl_storage).unwrap();
+        let bomb_code = vec![0; max_code_size as usize + 1];
+        init_committed_code_proposal(
+            &mut shell,
+            0,
+            Some(gov_code::compress_code(&code)),
+        );
+        init_committed_code_proposal(
+            &mut shell,
+            1,
+            Some(gov_code::compress_code(&bomb_code)),
+        );
+
+        shell.wl_storage.storage.block.epoch = Epoch::default().next();
+        let mut response = shim::response::FinalizeBlock::default();
+        let result =
+            execute_governance_proposals(&mut shell, &mut response).unwrap();
+        assert_eq!(result.passed, vec![0, 1]);
+        let execution_result = |id| {
+            gov_api::get_proposal_execution_result(&shell.wl_storage, id)
+                .unwrap()
+                .expect("Test failed")
+        };
+        // The decompressed code of the first proposal was executed
+        assert!(execution_result(0).accepted);
+        assert_eq!(
+            shell
+                .wl_storage
+                .read::<u64>(&gov_storage::get_min_proposal_grace_epoch_key())
+                .unwrap(),
+            Some(9)
+        );
+        let bomb_result = execution_result(1);
+        assert!(!bomb_result.accepted);
+        assert_eq!(bomb_result.gas_used, 0);
+        assert!(
+            bomb_result
+                .error
+                .expect("Test failed")
+                .contains("exceeds the maximum size")
+        );
+    }
+
+    /// Test that a panic raised by a host function while executing the code
+    /// of a passed proposal is contained, deterministically: the code is
+    /// rejected without leaking any write and the proposal event is flagged
+    /// as panicked, while the next proposals are executed as usual
+    #[test]
+    fn test_panicking_proposal_code_is_contained() {
+        let code = TestWasms::TxProposalCode.read_bytes();
+        // The key written by the proposal code
+        let grace_key = gov_storage::get_min_proposal_grace_epoch_key();
+        let conclude_panicking = || {
+            let (mut shell, _broadcaster, _, _eth_control) = setup();
+            init_committed_code_proposal(&mut shell, 0, Some(code.clone()));
+            shell.wl_storage.storage.block.epoch = Epoch::default().next();
+            let native_token = shell.wl_storage.get_native_token().unwrap();
+            let author = shell.mode.get_validator_address().unwrap().clone();
+            let funds = shell
+                .wl_storage
+                .read::<token::Amount>(&gov_storage::get_funds_key(0))
+                .unwrap()
+                .expect("Test failed");
+            let balances = |shell: &TestShell| {
+                let balance = |owner: &Address| {
+                    token::read_balance(&shell.wl_storage, &native_token, owner)
+                        .unwrap()
+                };
+                (balance(&ADDRESS), balance(&author))
+            };
+            let (treasury_pre, author_pre) = balances(&shell);
+            let mut response = shim::response::FinalizeBlock::default();
+            let result = with_tx_write_panicking(|| {
+                execute_governance_proposals(&mut shell, &mut response)
+            })
+            .unwrap();
+            assert_eq!(result.passed, vec![0]);
+            // The funds of the proposal go to the treasury, not its author
+            let (treasury_post, author_post) = balances(&shell);
+            assert!(!funds.is_zero());
+            assert_eq!(treasury_post, treasury_pre + funds);
+            assert_eq!(author_post, author_pre);
+            let execution_result =
+                gov_api::get_proposal_execution_result(&shell.wl_storage, 0)
+                    .unwrap()
+                    .expect("Test failed");
+            (shell, response.events, execution_result)
+        };
+
+        let (mut shell, events, execution_result) = conclude_panicking();
+        assert!(!execution_result.accepted);
+        assert!(execution_result.panicked);
+        assert!(
+            execution_result
+                .error
+                .as_ref()
+                .expect("Test failed")
+                .contains("tx_write deliberately panicked")
+        );
+        assert_ne!(shell.wl_storage.read::<u64>(&grace_key).unwrap(), Some(9));
+        assert!(
+            !shell
+                .wl_storage
+                .has_key(&gov_storage::get_proposal_execution_key(0))
+                .unwrap()
+        );
+        assert_eq!(events[0][events::attributes::PANICKED], "true");
+        assert_eq!(
+            events[0][events::attributes::PROPOSAL_CODE_EXIT_STATUS],
+            "1"
+        );
+
+        // Containing the panic gives the same outcome on every run
+        let (_shell, other_events, other_execution_result) =
+            conclude_panicking();
+        assert_eq!(other_events, events);
+        assert_eq!(other_execution_result, execution_result);
+
+        // The next proposal is executed as usual
+        init_committed_code_proposal(&mut shell, 1, Some(code));
+        let mut response = shim::response::FinalizeBlock::default();
+        let result =
+            execute_governance_proposals(&mut shell, &mut response).unwrap();
+        assert_eq!(result.passed, vec![1]);
+        assert_eq!(shell.wl_storage.read::<u64>(&grace_key).unwrap(), Some(9));
+        assert!(
+            !response.events[0]
+                .attributes
+                .contains_key(events::attributes::PANICKED)
+        );
+    }
+
+    /// Init a proposal of the given type, accepted by the only validator,
+    /// and conclude it
+    fn pass_proposal(
+        shell: &mut TestShell,
+        proposal_id: u64,
+        r#type: ProposalType,
+    ) -> Vec<Event> {
+        let (result, events) =
+            conclude_voted_proposal(shell, proposal_id, r#type);
+        assert_eq!(result.passed, vec![proposal_id]);
+        events
+    }
+
+    /// Submit a proposal of the given type voted yay by the validator of the
+    /// shell, and conclude it in the current epoch
+    fn conclude_voted_proposal(
+        shell: &mut TestShell,
+        proposal_id: u64,
+        r#type: ProposalType,
+    ) -> (ProposalsResult, Vec<Event>) {
+        let validator = shell.mode.get_validator_address().unwrap().clone();
+        let proposal = InitProposalData {
+            id: proposal_id,
+            content: Hash::default(),
+            author: validator.clone(),
+            voting_start_epoch: Epoch::default(),
+            voting_end_epoch: Epoch::default().next(),
+            grace_epoch: Epoch::default().next(),
+            voting_heights: None,
+            priority: None,
+            code_chunks: vec![],
+            r#type,
+        };
+        namada::governance::init_proposal(
+            &mut shell.wl_storage,
+            proposal,
+            vec![],
+            None,
+            vec![],
+        )
+        .unwrap();
+        let vote = VoteProposalData {
+            id: proposal_id,
+            vote: ProposalVote::Yay,
+            voter: validator.clone(),
+            delegations: vec![validator],
+        };
+        namada::governance::vote_proposal(&mut shell.wl_storage, vote).unwrap();
+
+        shell.proposal_data.insert(proposal_id);
+        let mut response = shim::response::FinalizeBlock::default();
+        let result =
+            execute_governance_proposals(shell, &mut response).unwrap();
+        (result, response.events)
+    }
+
+    /// Test that validator set update vote extensions are rejected once a
+    /// proposal pausing the Ethereum bridge passes, and accepted again once
+    /// a proposal resuming it passes
+    #[test]
+    fn test_eth_bridge_pause_proposal() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let signing_epoch = shell.wl_storage.storage.get_current_epoch().0;
+        let voting_powers = shell
+            .wl_storage
+            .ethbridge_queries()
+            .get_consensus_eth_addresses(Some(signing_epoch.next()))
+            .iter()
+            .map(|(eth_addr_book, _, voting_power)| {
+                (eth_addr_book, voting_power)
+            })
+            .collect();
+        let ext = validator_set_update::Vext {
+            voting_powers,
+            signing_epoch,
+            validator_addr: shell.mode.get_validator_address().unwrap().clone(),
+        }
+        .sign(shell.mode.get_eth_bridge_keypair().unwrap());
+        assert_eq!(
+            validate_valset_upd_vext(&shell.wl_storage, &ext, signing_epoch),
+            Ok(())
+        );
+
+        let events = pass_proposal(
+            &mut shell,
+            0,
+            ProposalType::ETHBridgePause(BridgePauseAction::Pause),
+        );
+        assert_eq!(events[0]["bridge_action"], "Pause");
+        assert!(shell.wl_storage.ethbridge_queries().is_bridge_paused());
+        assert_eq!(
+            validate_valset_upd_vext(&shell.wl_storage, &ext, signing_epoch),
+            Err(InvalidVextReason::BridgePaused
+                .of(ExtensionKind::ValidatorSetUpdate))
+        );
+
+        let events = pass_proposal(
+            &mut shell,
+            1,
+            ProposalType::ETHBridgePause(BridgePauseAction::Resume),
+        );
+        assert_eq!(events[0]["bridge_action"], "Resume");
+        assert!(!shell.wl_storage.ethbridge_queries().is_bridge_paused());
+        assert_eq!(
+            validate_valset_upd_vext(&shell.wl_storage, &ext, signing_epoch),
+            Ok(())
+        );
+    }
+
+    /// Build a validator set update vote extension of the validator of the
+    /// shell, signed over the given epoch with the given key
+    fn signed_valset_upd(
+        shell: &TestShell,
+        signing_epoch: Epoch,
+        sk: &common::SecretKey,
+    ) -> validator_set_update::SignedVext {
+        let voting_powers = shell
+            .wl_storage
+            .ethbridge_queries()
+            .get_consensus_eth_addresses(Some(signing_epoch.next()))
+            .iter()
+            .map(|(eth_addr_book, _, voting_power)| {
+                (eth_addr_book, voting_power)
+            })
+            .collect();
+        validator_set_update::Vext {
+            voting_powers,
+            signing_epoch,
+            validator_addr: shell.mode.get_validator_address().unwrap().clone(),
+        }
+        .sign(sk)
+    }
+
+    /// Test that an Ethereum hot key rotation proposal is only applied at
+    /// the pipeline epoch, and that validator set update vote extensions
+    /// signed with the new hot key are accepted from its activation epoch
+    /// onwards, while those signed with the old key are rejected
+    #[test]
+    fn test_eth_hot_key_rotation_proposal() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let validator = shell.mode.get_validator_address().unwrap().clone();
+        let old_sk = shell.mode.get_eth_bridge_keypair().unwrap().clone();
+        let new_sk =
+            common::SecretKey::Secp256k1(gen_keypair::<secp256k1::SigScheme>());
+        let current_epoch = shell.wl_storage.storage.get_current_epoch().0;
+        let params = read_pos_params(&shell.wl_storage).unwrap();
+        let activation_epoch = current_epoch + params.pipeline_len;
+
+        // a rotation before the pipeline epoch is not applied
+        let events = pass_proposal(
+            &mut shell,
+            0,
+            ProposalType::ETHHotKeyRotation(EthHotKeyRotation {
+                validator: validator.clone(),
+                eth_hot_key: new_sk.ref_to(),
+                activation_epoch: current_epoch.next(),
+            }),
+        );
+        assert_eq!(
+            events[0][events::attributes::PROPOSAL_CODE_EXIT_STATUS],
+            "1"
+        );
+        assert_eq!(
+            shell
+                .wl_storage
+                .pos_queries()
+                .read_validator_eth_hot_key(&validator, Some(activation_epoch)),
+            Some(old_sk.ref_to())
+        );
+
+        let events = pass_proposal(
+            &mut shell,
+            1,
+            ProposalType::ETHHotKeyRotation(EthHotKeyRotation {
+                validator: validator.clone(),
+                eth_hot_key: new_sk.ref_to(),
+                activation_epoch,
+            }),
+        );
+        assert_eq!(
+            events[0][events::attributes::PROPOSAL_CODE_EXIT_STATUS],
+            "0"
+        );
+        assert_eq!(events[0]["validator"], validator.to_string());
+        assert_eq!(events[0]["activation_epoch"], activation_epoch.to_string());
+        let pos_queries = shell.wl_storage.pos_queries();
+        assert_eq!(
+            pos_queries.read_validator_eth_hot_key(
+                &validator,
+                Some(activation_epoch.prev())
+            ),
+            Some(old_sk.ref_to())
+        );
+        assert_eq!(
+            pos_queries
+                .read_validator_eth_hot_key(&validator, Some(activation_epoch)),
+            Some(new_sk.ref_to())
+        );
+
+        // the old key remains in use until the activation epoch
+        let ext = signed_valset_upd(&shell, current_epoch, &old_sk);
+        assert_eq!(
+            validate_valset_upd_vext(&shell.wl_storage, &ext, current_epoch),
+            Ok(())
+        );
+
+        while shell.wl_storage.storage.get_current_epoch().0 < activation_epoch
+        {
+            shell.start_new_epoch(None);
+        }
+        let ext = signed_valset_upd(&shell, activation_epoch, &new_sk);
+        assert_eq!(
+            validate_valset_upd_vext(&shell.wl_storage, &ext, activation_epoch),
+            Ok(())
+        );
+        let ext = signed_valset_upd(&shell, activation_epoch, &old_sk);
+        assert_eq!(
+            validate_valset_upd_vext(&shell.wl_storage, &ext, activation_epoch),
+            Err(InvalidVextReason::VerifySigFailed
+                .of(ExtensionKind::ValidatorSetUpdate))
+        );
+    }
+
+    /// Test that a passed treasury spend proposal moves its funds from the
+    /// treasury to its recipient
+    #[test]
+    fn test_treasury_spend_proposal() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let native_token = shell.wl_storage.get_native_token().unwrap();
+        let recipient = established_address_1();
+        token::credit_tokens(
+            &mut shell.wl_storage,
+            &native_token,
+            &ADDRESS,
+            token::Amount::native_whole(1_000),
+        )
+        .unwrap();
+
+        let spend = TreasurySpend {
+            recipient: recipient.clone(),
+            amount: token::Amount::native_whole(400),
+            token: native_token.clone(),
+        };
+        let events =
+            pass_proposal(&mut shell, 0, ProposalType::TreasurySpend(spend));
+        assert_eq!(events[0]["proposal_code_exit_status"], "0");
+        assert_eq!(events[0]["recipient"], recipient.to_string());
+        assert_eq!(
+            events[0]["treasury_balance"],
+            token::Amount::native_whole(1_000).to_string()
+        );
+        assert!(
+            !events[0]
+                .attributes
+                .contains_key(events::attributes::WARNING)
+        );
+        assert_eq!(
+            token::read_balance(&shell.wl_storage, &native_token, &ADDRESS)
+                .unwrap(),
+            token::Amount::native_whole(600)
+        );
+        assert_eq!(
+            token::read_balance(&shell.wl_storage, &native_token, &recipient)
+                .unwrap(),
+            token::Amount::native_whole(400)
+        );
+    }
+
+    /// Test that a passed treasury spend proposal the treasury can't cover
+    /// is flagged, without moving any funds nor failing the block
+    #[test]
+    fn test_treasury_spend_proposal_over_budget() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let native_token = shell.wl_storage.get_native_token().unwrap();
+        let recipient = established_address_1();
+        token::credit_tokens(
+            &mut shell.wl_storage,
+            &native_token,
+            &ADDRESS,
+            token::Amount::native_whole(100),
+        )
+        .unwrap();
+
+        let spend = TreasurySpend {
+            recipient: recipient.clone(),
+            amount: token::Amount::native_whole(101),
+            token: native_token.clone(),
+        };
+        let events =
+            pass_proposal(&mut shell, 0, ProposalType::TreasurySpend(spend));
+        assert_eq!(events[0]["tally_result"], "passed");
+        assert_eq!(events[0]["proposal_code_exit_status"], "1");
+        assert_eq!(
+            events[0][events::attributes::WARNING],
+            "insufficient_treasury_funds"
+        );
+        assert_eq!(
+            events[0]["treasury_balance"],
+            token::Amount::native_whole(100).to_string()
+        );
+        assert_eq!(
+            token::read_balance(&shell.wl_storage, &native_token, &ADDRESS)
+                .unwrap(),
+            token::Amount::native_whole(100)
+        );
+        assert!(
+            token::read_balance(&shell.wl_storage, &native_token, &recipient)
+                .unwrap()
+                .is_zero()
+        );
+    }
+
+    /// Test that a passed parameter change proposal writes the new epoch
+    /// duration without any wasm, reports the old and new values in its
+    /// event, and that the next epoch is scheduled with the new duration
+    #[test]
+    fn test_parameter_change_proposal() {
+        use namada::ledger::events::ProposalOutcome;
+        use namada::parameters::EpochDuration;
+        use namada::types::time::{DateTimeUtc, DurationSecs};
+
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let key = namada::parameters::storage::get_epoch_duration_storage_key();
+        let old_duration = namada::parameters::read_epoch_duration_parameter(
+            &shell.wl_storage,
+        )
+        .unwrap();
+        let new_duration = EpochDuration {
+            min_num_of_blocks: 42,
+            min_duration: DurationSecs(1234),
+        };
+        assert_ne!(old_duration, new_duration);
+
+        let change = ParameterChange {
+            changes: vec![(key.clone(), new_duration.serialize_to_vec())],
+        };
+        let events =
+            pass_proposal(&mut shell, 0, ProposalType::ParameterChange(change));
+        let outcome = ProposalOutcome::try_from(&events[0]).unwrap();
+        assert!(outcome.executed);
+        assert_eq!(
+            outcome.parameter_changes,
+            vec![AppliedParameterChange {
+                key,
+                old_value: Some(old_duration.serialize_to_vec()),
+                new_value: new_duration.serialize_to_vec(),
+            }]
+        );
+        assert_eq!(
+            namada::parameters::read_epoch_duration_parameter(
+                &shell.wl_storage
+            )
+            .unwrap(),
+            new_duration
+        );
+        assert!(
+            !shell
+                .wl_storage
+                .has_key(&gov_storage::get_proposal_execution_key(0))
+                .unwrap()
+        );
+
+        // the next epoch is scheduled with the new duration
+        shell.wl_storage.storage.update_epoch_blocks_delay = Some(1);
+        let height = BlockHeight(100);
+        let time = DateTimeUtc::now();
+        assert!(shell.wl_storage.update_epoch(height, time).unwrap());
+        assert_eq!(
+            shell.wl_storage.storage.next_epoch_min_start_height,
+            height + 42
+        );
+        assert_eq!(
+            shell.wl_storage.storage.next_epoch_min_start_time,
+            time + DurationSecs(1234)
+        );
+    }
+
+    /// Test that a wasm allowlist proposal both adds and removes code hashes
+    /// from the tx allowlist, and that the compiled modules of the removed
+    /// code are evicted from the tx wasm cache
+    #[test]
+    fn test_wasm_allowlist_proposal_add_and_remove() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let tx_code = TestWasms::TxNoOp.read_bytes();
+        let removed = Hash::sha256(&tx_code);
+        let kept = Hash::sha256(b"kept");
+        let added = Hash::sha256(b"added");
+        namada::parameters::update_tx_allowlist_parameter(
+            &mut shell.wl_storage,
+            vec![removed.to_string(), kept.to_string()],
+        )
+        .unwrap();
+        shell.tx_wasm_cache.compile_or_fetch(&tx_code).unwrap();
+        assert!(shell.tx_wasm_cache.fetch(&removed).unwrap().is_some());
+
+        let events = pass_proposal(
+            &mut shell,
+            0,
+            ProposalType::UpdateWasmAllowlist(UpdateWasmAllowlist {
+                add: vec![added],
+                remove: vec![removed],
+                kind: TxOrVp::Tx,
+            }),
+        );
+
+        let expected = vec![
+            kept.to_string().to_lowercase(),
+            added.to_string().to_lowercase(),
+        ];
+        let allowlist: Vec<String> = shell
+            .wl_storage
+            .read(&namada::parameters::storage::get_tx_allowlist_storage_key())
+            .unwrap()
+            .unwrap();
+        assert_eq!(allowlist, expected);
+        assert_eq!(events[0]["allowlist_kind"], "tx");
+        assert_eq!(
+            events[0]["allowlist_hash"],
+            Hash::sha256(expected.serialize_to_vec()).to_string()
+        );
+        assert!(
+            namada::parameters::is_tx_allowed(&shell.wl_storage, &added)
+                .unwrap()
+        );
+        assert!(
+            !namada::parameters::is_tx_allowed(&shell.wl_storage, &removed)
+                .unwrap()
+        );
+        assert!(shell.tx_wasm_cache.fetch(&removed).unwrap().is_none());
+    }
+
+    /// Test that removing a code hash that isn't in the VP allowlist leaves
+    /// the allowlist unchanged
+    #[test]
+    fn test_wasm_allowlist_proposal_remove_missing_hash() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let present = Hash::sha256(b"present");
+        let missing = Hash::sha256(b"missing");
+        namada::parameters::update_vp_allowlist_parameter(
+            &mut shell.wl_storage,
+            vec![present.to_string()],
+        )
+        .unwrap();
+
+        let events = pass_proposal(
+            &mut shell,
+            0,
+            ProposalType::UpdateWasmAllowlist(UpdateWasmAllowlist {
+                add: vec![],
+                remove: vec![missing],
+                kind: TxOrVp::Vp,
+            }),
+        );
+
+        let expected = vec![present.to_string().to_lowercase()];
+        let allowlist: Vec<String> = shell
+            .wl_storage
+            .read(&namada::parameters::storage::get_vp_allowlist_storage_key())
+            .unwrap()
+            .unwrap();
+        assert_eq!(allowlist, expected);
+        assert_eq!(events[0]["allowlist_kind"], "vp");
+        assert_eq!(
+            events[0]["allowlist_hash"],
+            Hash::sha256(expected.serialize_to_vec()).to_string()
+        );
+    }
+
+    /// Test that a wasm allowlist proposal removing every code hash from the
+    /// tx allowlist, which would allow any tx code, is refused
+    #[test]
+    fn test_wasm_allowlist_proposal_cannot_empty_allowlist() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let tx_code = TestWasms::TxNoOp.read_bytes();
+        let removed = Hash::sha256(&tx_code);
+        let other = Hash::sha256(b"other");
+        namada::parameters::update_tx_allowlist_parameter(
+            &mut shell.wl_storage,
+            vec![removed.to_string()],
+        )
+        .unwrap();
+        shell.tx_wasm_cache.compile_or_fetch(&tx_code).unwrap();
+
+        let events = pass_proposal(
+            &mut shell,
+            0,
+            ProposalType::UpdateWasmAllowlist(UpdateWasmAllowlist {
+                add: vec![],
+                remove: vec![removed],
+                kind: TxOrVp::Tx,
+            }),
+        );
+
+        let expected = vec![removed.to_string().to_lowercase()];
+        let allowlist: Vec<String> = shell
+            .wl_storage
+            .read(&namada::parameters::storage::get_tx_allowlist_storage_key())
+            .unwrap()
+            .unwrap();
+        assert_eq!(allowlist, expected);
+        assert_eq!(
+            events[0]["allowlist_hash"],
+            Hash::sha256(expected.serialize_to_vec()).to_string()
+        );
+        assert!(
+            !namada::parameters::is_tx_allowed(&shell.wl_storage, &other)
+                .unwrap()
+        );
+        assert!(shell.tx_wasm_cache.fetch(&removed).unwrap().is_some());
+    }
+
+    /// Test that a proposal whose processing panics is skipped, with its
+    /// writes reverted and a processing error event emitted, while the
+    /// following proposals are concluded as usual
+    #[test]
+    fn test_panicking_proposal_is_contained() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let key = Key::from(established_address_1().to_db_key())
+            .push(&"proposal_state".to_string())
+            .unwrap();
+        let mut response = shim::response::FinalizeBlock::default();
+        let mut proposals_result = ProposalsResult::default();
+
+        contain_proposal_panic(
+            &mut shell,
+            &mut response,
+            &mut proposals_result,
+            0,
+            |shell, response, proposals_result| {
+                shell.wl_storage.write(&key, 1u64).unwrap();
+                response
+                    .events
+                    .push(ProposalEvent::rejected_proposal_event(0).into());
+                proposals_result.rejected.push(0);
+                panic!("mock decode bug")
+            },
+        )
+        .unwrap();
+
+        assert!(!shell.wl_storage.has_key(&key).unwrap());
+        assert!(proposals_result.rejected.is_empty());
+        assert_eq!(response.events.len(), 1);
+        assert_eq!(
+            response.events[0][events::attributes::PROCESSING_ERROR],
+            "mock decode bug"
+        );
+        assert!(
+            !response.events[0]
+                .attributes
+                .contains_key(events::attributes::TALLY_RESULT)
+        );
+
+        // The next proposal is processed normally
+        let events = pass_proposal(&mut shell, 1, ProposalType::Default(None));
+        assert_eq!(events.len(), 1);
+        assert!(
+            !events[0]
+                .attributes
+                .contains_key(events::attributes::PROCESSING_ERROR)
+        );
+    }
+
+    /// Test that a proposal voted on over a range of block heights is
+    /// concluded at its voting end height, regardless of epoch changes
+    #[test]
+    fn test_height_based_proposal_concluded_at_end_height() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let validator = shell.mode.get_validator_address().unwrap().clone();
+        let voting_heights = VotingHeights {
+            start: BlockHeight(1),
+            end: BlockHeight(10),
+        };
+        let proposal = InitProposalData {
+            id: 0,
+            content: Hash::default(),
+            author: validator.clone(),
+            voting_start_epoch: Epoch::default(),
+            voting_end_epoch: Epoch::default(),
+            grace_epoch: Epoch::default(),
+            voting_heights: Some(voting_heights),
+            priority: None,
+            code_chunks: vec![],
+            r#type: ProposalType::Default(None),
+        };
+        namada::governance::init_proposal(
+            &mut shell.wl_storage,
+            proposal,
+            vec![],
+            None,
+            vec![],
+        )
+        .unwrap();
+        assert_eq!(find_malformed_field(&shell.wl_storage, 0), None);
+        let vote = VoteProposalData {
+            id: 0,
+            vote: ProposalVote::Yay,
+            voter: validator.clone(),
+            delegations: vec![validator],
+        };
+        namada::governance::vote_proposal(&mut shell.wl_storage, vote).unwrap();
+
+        // Nothing concludes before the voting end height
+        shell.wl_storage.storage.block.height = BlockHeight(9);
+        let mut response = shim::response::FinalizeBlock::default();
+        let result =
+            execute_height_based_proposals(&mut shell, &mut response).unwrap();
+        assert!(result.passed.is_empty() && result.rejected.is_empty());
+        assert!(response.events.is_empty());
+
+        shell.wl_storage.storage.block.height = voting_heights.end;
+        let result =
+            execute_height_based_proposals(&mut shell, &mut response).unwrap();
+        assert_eq!(result.passed, vec![0]);
+        assert_eq!(response.events.len(), 1);
+        assert!(
+            ProposalStorageReader::new(&shell.wl_storage)
+                .result(0)
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    /// Test that the ids of the proposals initialized by each tx of a block
+    /// are attributed to that tx only, in the order the txs are applied.
+    #[test]
+    fn test_initialized_proposal_ids_per_tx() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let counter_key = gov_storage::get_counter_key();
+        let counter: u64 = shell
+            .wl_storage
+            .read(&counter_key)
+            .expect("Test failed")
+            .unwrap_or_default();
+        let changed_keys = BTreeSet::from([counter_key.clone()]);
+
+        // a tx initializing a single proposal
+        shell
+            .wl_storage
+            .write_log
+            .write(&counter_key, (counter + 1).serialize_to_vec())
+            .expect("Test failed");
+        let ids = find_initialized_proposals(&shell.wl_storage, &changed_keys)
+            .expect("Test failed");
+        assert_eq!(ids, counter..counter + 1);
+        shell.wl_storage.commit_tx();
+
+        // a later tx of the same block initializing two proposals
+        shell
+            .wl_storage
+            .write_log
+            .write(&counter_key, (counter + 3).serialize_to_vec())
+            .expect("Test failed");
+        let ids = find_initialized_proposals(&shell.wl_storage, &changed_keys)
+            .expect("Test failed");
+        assert_eq!(ids, counter + 1..counter + 3);
+        shell.wl_storage.commit_tx();
+
+        // a tx that didn't change the counter
+        let ids =
+            find_initialized_proposals(&shell.wl_storage, &BTreeSet::new())
+                .expect("Test failed");
+        assert!(ids.is_empty());
+    }
+
+    /// Test that the JSON rendering of the proposals result is stable, as
+    /// operators script against it.
+    #[test]
+    fn test_proposals_result_json() {
+        assert_eq!(
+            ProposalsResult::default().to_json(),
+            r#"{"passed":[],"rejected":[],"expired":[]}"#
+        );
+        let result = ProposalsResult {
+            passed: vec![0, 3],
+            rejected: vec![1],
+            expired: vec![2],
+        };
+        assert_eq!(
+            result.to_json(),
+            r#"{"passed":[0,3],"rejected":[1],"expired":[2]}"#
+        );
+        assert_eq!(result.passed(), &[0, 3]);
+        assert_eq!(result.rejected(), &[1]);
+        assert_eq!(result.expired(), &[2]);
+        assert!(!result.is_empty());
+    }
+
+    /// Test that a proposal submitted without a deposit, as governance
+    /// doesn't require any, is concluded without moving any funds nor
+    /// being flagged
+    #[test]
+    fn test_zero_deposit_proposal_concluded() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        for key in [
+            gov_storage::get_min_proposal_fund_key(),
+            gov_storage::get_base_deposit_key(),
+            gov_storage::get_deposit_per_byte_key(),
+        ] {
+            shell
+                .wl_storage
+                .write(&key, token::Amount::zero())
+                .expect("Test failed");
+        }
+        let native_token = shell.wl_storage.get_native_token().unwrap();
+        let gov_balance_pre =
+            token::read_balance(&shell.wl_storage, &native_token, &gov_address)
+                .expect("Test failed");
+
+        let events = pass_proposal(&mut shell, 0, ProposalType::Default(None));
+        assert!(
+            shell
+                .wl_storage
+                .read::<token::Amount>(&gov_storage::get_funds_key(0))
+                .expect("Test failed")
+                .expect("Test failed")
+                .is_zero()
+        );
+        assert!(events.iter().all(|event| {
+            !event.attributes.contains_key(events::attributes::WARNING)
+        }));
+        let gov_balance_post =
+            token::read_balance(&shell.wl_storage, &native_token, &gov_address)
+                .expect("Test failed");
+        assert_eq!(gov_balance_post, gov_balance_pre);
+    }
+
+    /// Test that a proposal without any locked funds, although governance
+    /// requires a deposit for it, is flagged and still concluded
+    #[test]
+    fn test_unexpected_zero_funds_flagged() {
+        let zero_funds = |shell: &mut TestShell, id| {
+            shell
+                .wl_storage
+                .write(&gov_storage::get_funds_key(id), token::Amount::zero())
+                .unwrap();
+        };
+        let (events, _root) = conclude_corrupted_proposal(zero_funds);
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[0][events::attributes::WARNING],
+            "unexpected_zero_funds"
+        );
+        assert_eq!(events[0][events::attributes::PROPOSAL_ID], "0");
+        assert_eq!(
+            events[1][events::attributes::TALLY_RESULT],
+            TallyResult::Rejected.to_string()
+        );
+    }
+
+    /// Test that the funds of rejected proposals are partially refunded to
+    /// their author, by the refund ratio set for their type or else by the
+    /// default one, the rest being burned, and that the split is recorded
+    #[test]
+    fn test_rejected_proposal_refund_ratio_by_type() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let validator = shell.mode.get_validator_address().unwrap().clone();
+        let native_token = shell.wl_storage.get_native_token().unwrap();
+        shell
+            .wl_storage
+            .write(
+                &gov_storage::get_rejected_refund_ratio_key(),
+                Dec::from_str("0.25").unwrap(),
+            )
+            .unwrap();
+        shell
+            .wl_storage
+            .write(
+                &gov_storage::get_rejected_refund_ratios_key(),
+                BTreeMap::from([
+                    ("default".to_string(), Dec::from_str("0.5").unwrap()),
+                    ("pgf_steward".to_string(), Dec::one()),
+                ]),
+            )
+            .unwrap();
+        let funds = gov_api::get_parameters(&shell.wl_storage)
+            .unwrap()
+            .min_proposal_fund;
+
+        let cases = [
+            (ProposalType::Default(None), Dec::from_str("0.5").unwrap()),
+            (ProposalType::PGFSteward(BTreeSet::new()), Dec::one()),
+            // no refund ratio is set for this type
+            (
+                ProposalType::PGFPayment(BTreeSet::new()),
+                Dec::from_str("0.25").unwrap(),
+            ),
+        ];
+        for (proposal_id, (r#type, ratio)) in cases.into_iter().enumerate() {
+            let proposal_id = proposal_id as u64;
+            let author_pre = token::read_balance(
+                &shell.wl_storage,
+                &native_token,
+                &validator,
+            )
+            .unwrap();
+            let supply_pre =
+                token::read_total_supply(&shell.wl_storage, &native_token)
+                    .unwrap();
+
+            // Nobody votes, so the proposal is rejected
+            let proposal = InitProposalData {
+                id: proposal_id,
+                content: Hash::default(),
+                author: validator.clone(),
+                voting_start_epoch: Epoch::default(),
+                voting_end_epoch: Epoch::default().next(),
+                grace_epoch: Epoch::default().next(),
+                voting_heights: None,
+                priority: None,
+                code_chunks: vec![],
+                r#type,
+            };
+            namada::governance::init_proposal(
+                &mut shell.wl_storage,
+                proposal,
+                vec![],
+                None,
+                vec![],
+            )
+            .unwrap();
+            shell.proposal_data.insert(proposal_id);
+            let mut response = shim::response::FinalizeBlock::default();
+            let result =
+                execute_governance_proposals(&mut shell, &mut response)
+                    .unwrap();
+            assert_eq!(result.rejected(), &[proposal_id]);
+
+            let refund = dec_fraction_of(funds, ratio).unwrap();
+            let author_post = token::read_balance(
+                &shell.wl_storage,
+                &native_token,
+                &validator,
+            )
+            .unwrap();
+            assert_eq!(author_post, author_pre - funds + refund);
+            let supply_post =
+                token::read_total_supply(&shell.wl_storage, &native_token)
+                    .unwrap();
+            assert_eq!(supply_post, supply_pre - (funds - refund));
+            assert_eq!(
+                gov_api::get_released_funds(&shell.wl_storage, proposal_id)
+                    .unwrap(),
+                Some(ReleasedFunds {
+                    returned: refund,
+                    burned: funds - refund,
+                    to_treasury: token::Amount::zero(),
+                })
+            );
+        }
+    }
+
+    /// Test that concluding a passed proposal twice over the same state,
+    /// as when replaying a block interrupted by a crash, returns its funds
+    /// to its author only once
+    #[test]
+    fn test_passed_proposal_funds_released_once() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let validator = shell.mode.get_validator_address().unwrap().clone();
+        let native_token = shell.wl_storage.get_native_token().unwrap();
+        // Governance holds other funds, from which a replayed transfer
+        // would succeed
+        token::credit_tokens(
+            &mut shell.wl_storage,
+            &native_token,
+            &gov_address,
+            token::Amount::native_whole(1_000),
+        )
+        .unwrap();
+        let author_pre =
+            token::read_balance(&shell.wl_storage, &native_token, &validator)
+                .unwrap();
+        let gov_pre =
+            token::read_balance(&shell.wl_storage, &native_token, &gov_address)
+                .unwrap();
+
+        pass_proposal(&mut shell, 0, ProposalType::Default(None));
+        assert!(gov_api::is_funds_released(&shell.wl_storage, 0).unwrap());
+        let balances = |shell: &TestShell| {
+            (
+                token::read_balance(
+                    &shell.wl_storage,
+                    &native_token,
+                    &validator,
+                )
+                .unwrap(),
+                token::read_balance(
+                    &shell.wl_storage,
+                    &native_token,
+                    &gov_address,
+                )
+                .unwrap(),
+            )
+        };
+        // The deposit was locked and returned
+        assert_eq!(balances(&shell), (author_pre, gov_pre));
+
+        // The replay skips the concluded proposal
+        shell.proposal_data.insert(0);
+        let mut response = shim::response::FinalizeBlock::default();
+        let result =
+            execute_governance_proposals(&mut shell, &mut response).unwrap();
+        assert!(result.is_empty());
+        assert!(response.events.is_empty());
+        assert_eq!(balances(&shell), (author_pre, gov_pre));
+    }
+
+    /// Test that the queue of the proposals due at the next epoch change is
+    /// rebuilt from storage after a restart in the middle of the voting
+    /// period of a proposal, without concluding a proposal twice
+    #[test]
+    fn test_proposal_queue_rebuilt_after_restart() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let validator = shell.mode.get_validator_address().unwrap().clone();
+        for (proposal_id, voting_end_epoch) in [(0, Epoch(1)), (1, Epoch(2))] {
+            let proposal = InitProposalData {
+                id: proposal_id,
+                content: Hash::default(),
+                author: validator.clone(),
+                voting_start_epoch: Epoch::default(),
+                voting_end_epoch,
+                grace_epoch: voting_end_epoch.next(),
+                voting_heights: None,
+                priority: None,
+                code_chunks: vec![],
+                r#type: ProposalType::Default(None),
+            };
+            namada::governance::init_proposal(
+                &mut shell.wl_storage,
+                proposal,
+                vec![],
+                None,
+                vec![],
+            )
+            .unwrap();
+            let vote = VoteProposalData {
+                id: proposal_id,
+                vote: ProposalVote::Yay,
+                voter: validator.clone(),
+                delegations: vec![validator.clone()],
+            };
+            namada::governance::vote_proposal(&mut shell.wl_storage, vote)
+                .unwrap();
+        }
+        let restart = |shell: &mut TestShell| {
+            shell.proposal_data.clear();
+            shell.reconcile_proposal_queue().unwrap()
+        };
+
+        // No proposal is due while both are being voted on
+        assert!(restart(&mut shell).is_empty());
+        assert!(shell.proposal_data.is_empty());
+
+        // The first proposal reached its grace epoch, the node restarts
+        // while the second one is still being voted on
+        shell.wl_storage.storage.last_epoch = Epoch(2);
+        let mismatch = restart(&mut shell);
+        assert_eq!(mismatch.missing, BTreeSet::from([0]));
+        assert!(mismatch.unexpected.is_empty());
+        assert_eq!(shell.proposal_data, BTreeSet::from([0]));
+
+        // A proposal queued in memory only is detected and dropped
+        shell.proposal_data.insert(1);
+        let mismatch = shell.check_proposal_queue().unwrap();
+        assert!(mismatch.missing.is_empty());
+        assert_eq!(mismatch.unexpected, BTreeSet::from([1]));
+        shell.reconcile_proposal_queue().unwrap();
+        assert_eq!(shell.proposal_data, BTreeSet::from([0]));
+
+        shell.wl_storage.storage.block.epoch = Epoch(3);
+        let mut response = shim::response::FinalizeBlock::default();
+        let result =
+            execute_governance_proposals(&mut shell, &mut response).unwrap();
+        assert_eq!(result.passed, vec![0]);
+        assert_eq!(
+            gov_api::get_concluded_epoch(&shell.wl_storage).unwrap(),
+            Some(Epoch(2))
+        );
+
+        // The concluded proposal isn't queued again after another restart
+        assert!(restart(&mut shell).is_empty());
+        assert!(shell.proposal_data.is_empty());
+
+        // The second proposal is queued once it reaches its grace epoch
+        shell.wl_storage.storage.last_epoch = Epoch(3);
+        restart(&mut shell);
+        assert_eq!(shell.proposal_data, BTreeSet::from([1]));
+    }
+
+    /// Test that the estimated cost of finalizing the queued proposals
+    /// grows with the number of queued proposals, their votes and the
+    /// size of their code
+    #[test]
+    fn test_estimate_proposal_finalize_cost() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let validator = shell.mode.get_validator_address().unwrap().clone();
+        let queue_proposal = |shell: &mut TestShell, code_len: usize| {
+            let proposal_id = shell.proposal_data.len() as u64;
+            let (r#type, code) = if code_len == 0 {
+                (ProposalType::Default(None), None)
+            } else {
+                (
+                    ProposalType::Default(Some(Hash::default())),
+                    Some(vec![0; code_len]),
+                )
+            };
+            let proposal = InitProposalData {
+                id: proposal_id,
+                content: Hash::default(),
+                author: validator.clone(),
+                voting_start_epoch: Epoch::default(),
+                voting_end_epoch: Epoch(1),
+                grace_epoch: Epoch(2),
+                voting_heights: None,
+                priority: None,
+                code_chunks: vec![],
+                r#type,
+            };
+            namada::governance::init_proposal(
+                &mut shell.wl_storage,
+                proposal,
+                vec![],
+                code,
+                vec![],
+            )
+            .unwrap();
+            shell.proposal_data.insert(proposal_id);
+            proposal_id
+        };
+        let estimate =
+            |shell: &TestShell| estimate_proposal_finalize_cost(shell).unwrap();
+
+        assert_eq!(estimate(&shell), ProposalFinalizeCost::default());
+
+        // A proposal without code, nor votes
+        let id = queue_proposal(&mut shell, 0);
+        let no_votes = estimate(&shell);
+        assert_eq!(no_votes.proposals, 1);
+        assert_eq!(no_votes.tally_gas, TALLY_GAS_PER_PROPOSAL);
+        assert_eq!(no_votes.code_gas, 0);
+
+        // The tally cost is scaled by the number of votes
+        let vote = VoteProposalData {
+            id,
+            vote: ProposalVote::Yay,
+            voter: validator.clone(),
+            delegations: vec![validator.clone()],
+        };
+        namada::governance::vote_proposal(&mut shell.wl_storage, vote).unwrap();
+        let one_vote = estimate(&shell);
+        assert_eq!(one_vote.votes, 1);
+        assert_eq!(one_vote.tally_gas, no_votes.tally_gas + TALLY_GAS_PER_VOTE);
+
+        // The code cost is scaled by the size of the code
+        queue_proposal(&mut shell, 1_000);
+        let small_code = estimate(&shell);
+        assert_eq!(small_code.proposals, 2);
+        assert_eq!(small_code.code_gas, 1_000 * CODE_GAS_PER_BYTE);
+        assert!(small_code.total_gas() > one_vote.total_gas());
+        queue_proposal(&mut shell, 10_000);
+        let large_code = estimate(&shell);
+        assert_eq!(large_code.code_gas, 11_000 * CODE_GAS_PER_BYTE);
+        assert!(large_code.total_gas() > small_code.total_gas());
+
+        // Deferred proposals are queued as well
+        shell.proposal_data.remove(&id);
+        assert!(estimate(&shell).total_gas() < large_code.total_gas());
+        gov_api::write_deferred_proposals(
+            &mut shell.wl_storage,
+            &BTreeSet::from([id]),
+        )
+        .unwrap();
+        assert_eq!(estimate(&shell), large_code);
+    }
+
+    /// Test that expiring a proposal twice over the same state moves its
+    /// funds to the treasury only once
+    #[test]
+    fn test_expired_proposal_funds_released_once() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let validator = shell.mode.get_validator_address().unwrap().clone();
+        let native_token = shell.wl_storage.get_native_token().unwrap();
+        shell
+            .wl_storage
+            .write(&gov_storage::get_max_proposal_lifetime_key(), 2_u64)
+            .unwrap();
+        token::credit_tokens(
+            &mut shell.wl_storage,
+            &native_token,
+            &gov_address,
+            token::Amount::native_whole(1_000),
+        )
+        .unwrap();
+
+        let proposal_id = 0;
+        let grace_epoch = Epoch(1006);
+        let proposal = InitProposalData {
+            id: proposal_id,
+            content: Hash::default(),
+            author: validator,
+            voting_start_epoch: Epoch::default(),
+            voting_end_epoch: Epoch(1000),
+            grace_epoch,
+            voting_heights: None,
+            priority: None,
+            code_chunks: vec![],
+            r#type: ProposalType::Default(None),
+        };
+        namada::governance::init_proposal(
+            &mut shell.wl_storage,
+            proposal,
+            vec![],
+            None,
+            vec![],
+        )
+        .unwrap();
+        let funds = gov_api::get_parameters(&shell.wl_storage)
+            .unwrap()
+            .min_proposal_fund;
+        let treasury_pre =
+            token::read_balance(&shell.wl_storage, &native_token, &ADDRESS)
+                .unwrap();
+
+        shell.wl_storage.storage.block.epoch = Epoch(3);
+        let mut response = shim::response::FinalizeBlock::default();
+        let result =
+            execute_governance_proposals(&mut shell, &mut response).unwrap();
+        assert_eq!(result.expired(), &[proposal_id]);
+        assert!(
+            gov_api::is_funds_released(&shell.wl_storage, proposal_id).unwrap()
+        );
+
+        // The proposal is found queued once again, as if its dequeuing
+        // was lost
+        let committing_key = gov_storage::get_committing_proposals_key(
+            proposal_id,
+            grace_epoch.0,
+        );
+        shell.wl_storage.write(&committing_key, ()).unwrap();
+        let mut response = shim::response::FinalizeBlock::default();
+        let result =
+            execute_governance_proposals(&mut shell, &mut response).unwrap();
+        assert!(result.is_empty());
+        assert!(response.events.is_empty());
+        assert!(!shell.wl_storage.has_key(&committing_key).unwrap());
+
+        let treasury_post =
+            token::read_balance(&shell.wl_storage, &native_token, &ADDRESS)
+                .unwrap();
+        assert_eq!(treasury_post, treasury_pre + funds);
+    }
+
+    /// Test that at most the maximum number of proposals per block are
+    /// concluded, in execution order, and that at least one is concluded per
+    /// block
+    #[test]
+    fn test_split_due_proposals() {
+        let due = vec![2, 5, 7];
+        assert_eq!(
+            split_due_proposals(due.clone(), None),
+            (due.clone(), BTreeSet::new())
+        );
+        assert_eq!(
+            split_due_proposals(due.clone(), Some(2)),
+            (vec![2, 5], BTreeSet::from([7]))
+        );
+        assert_eq!(
+            split_due_proposals(due.clone(), Some(0)),
+            (vec![2], BTreeSet::from([5, 7]))
+        );
+        assert_eq!(
+            split_due_proposals(due.clone(), Some(3)),
+            (due, BTreeSet::new())
+        );
+    }
+
+    /// Test that the proposals due in excess of the maximum number of
+    /// proposals per block are deferred to the next block of the epoch,
+    /// by id order
+    #[test]
+    fn test_proposals_deferred_across_blocks() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        shell
+            .wl_storage
+            .write(&gov_storage::get_max_proposals_per_block_key(), Some(2_u64))
+            .unwrap();
+        for proposal_id in [2, 0, 1] {
+            init_committed_code_proposal(&mut shell, proposal_id, None);
+        }
+
+        // the first block of the epoch concludes the first two proposals
+        shell.wl_storage.storage.block.epoch = Epoch::default().next();
+        let mut response = shim::response::FinalizeBlock::default();
+        let result =
+            execute_governance_proposals(&mut shell, &mut response).unwrap();
+        assert_eq!(result.passed(), &[0, 1]);
+        assert_eq!(
+            gov_api::get_deferred_proposals(&shell.wl_storage).unwrap(),
+            BTreeSet::from([2])
+        );
+        assert!(
+            gov_api::get_proposal_result(&shell.wl_storage, 2)
+                .unwrap()
+                .is_none()
+        );
+        shell.wl_storage.commit_block().unwrap();
+
+        // the next block concludes the deferred proposal
+        let mut response = shim::response::FinalizeBlock::default();
+        let result =
+            execute_deferred_proposals(&mut shell, &mut response).unwrap();
+        assert_eq!(result.passed(), &[2]);
+        assert!(
+            !shell
+                .wl_storage
+                .has_key(&gov_storage::get_deferred_proposals_key())
+                .unwrap()
+        );
+        shell.wl_storage.commit_block().unwrap();
+
+        // no proposal is left to conclude in the following blocks
+        let mut response = shim::response::FinalizeBlock::default();
+        let result =
+            execute_deferred_proposals(&mut shell, &mut response).unwrap();
+        assert!(result.is_empty());
+        assert!(response.events.is_empty());
+    }
+
+    /// Init a low priority proposal and a high priority one with a later
+    /// id, inserted in `proposal_data` in the given order, and conclude
+    /// them, returning the ids of the concluded proposals, in the order of
+    /// their events, and the ids of the deferred proposals
+    fn conclude_prioritized_proposals(
+        order: [u64; 2],
+        max_proposals_per_block: Option<u64>,
+    ) -> (Vec<String>, BTreeSet<u64>) {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        shell
+            .wl_storage
+            .write(
+                &gov_storage::get_max_proposals_per_block_key(),
+                max_proposals_per_block,
+            )
+            .unwrap();
+        init_committed_code_proposal(&mut shell, 0, None);
+        init_committed_code_proposal(&mut shell, 1, None);
+        namada::governance::storage::facade::ProposalStorageWriter::new(
+            &mut shell.wl_storage,
+        )
+        .set_priority(1, 10)
+        .unwrap();
+        shell.wl_storage.commit_block().unwrap();
+
+        shell.proposal_data.clear();
+        for proposal_id in order {
+            shell.proposal_data.insert(proposal_id);
+        }
+        shell.wl_storage.storage.block.epoch = Epoch::default().next();
+        let mut response = shim::response::FinalizeBlock::default();
+        execute_governance_proposals(&mut shell, &mut response).unwrap();
+        let concluded = response
+            .events
+            .iter()
+            .filter(|event| {
+                event
+                    .attributes
+                    .contains_key(events::attributes::TALLY_RESULT)
+            })
+            .map(|event| event[events::attributes::PROPOSAL_ID].clone())
+            .collect();
+        let deferred =
+            gov_api::get_deferred_proposals(&shell.wl_storage).unwrap();
+        (concluded, deferred)
+    }
+
+    /// Test that a high priority proposal is executed before a low priority
+    /// proposal with an earlier id, regardless of the order in which their
+    /// ids were collected, and that it isn't the one deferred to the next
+    /// block
+    #[test]
+    fn test_proposals_executed_by_priority() {
+        for order in [[0, 1], [1, 0]] {
+            let (concluded, deferred) =
+                conclude_prioritized_proposals(order, None);
+            assert_eq!(concluded, vec!["1", "0"], "{order:?}");
+            assert!(deferred.is_empty());
+
+            let (concluded, deferred) =
+                conclude_prioritized_proposals(order, Some(1));
+            assert_eq!(concluded, vec!["1"], "{order:?}");
+            assert_eq!(deferred, BTreeSet::from([0]));
+        }
+    }
+
+    /// Test that the structured result written by the code of a proposal is
+    /// taken once the code is accepted, and that only the code of the
+    /// proposal may write it
+    #[test]
+    fn test_proposal_output_taken() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let output =
+            ProposalOutput::from([("minted".to_string(), "100".to_string())]);
+        let output_key = gov_storage::get_proposal_output_key(0);
+        let write_output = proposal_tx(
+            &shell,
+            TestWasms::TxWriteStorageKey,
+            TxWriteData {
+                key: output_key.clone(),
+                value: output.serialize_to_vec(),
+            }
+            .serialize_to_vec(),
+        );
+
+        assert!(
+            execute_proposal_tx(&mut shell, 0, vec![write_output.clone()])
+                .unwrap()
+        );
+        assert_eq!(take_proposal_output(&mut shell, 0), Some(output.clone()));
+        assert!(!shell.wl_storage.has_key(&output_key).unwrap());
+        assert_eq!(take_proposal_output(&mut shell, 0), None);
+
+        // the code of another proposal is rejected
+        assert!(
+            !execute_proposal_tx(&mut shell, 1, vec![write_output]).unwrap()
+        );
+        assert!(!shell.wl_storage.has_key(&output_key).unwrap());
+    }
+
+    /// Test that the vote of a validator slashed to zero after voting is
+    /// counted towards its participation in the proposal, but carries no
+    /// voting power in the tally
+    #[test]
+    fn test_vote_of_validator_slashed_to_zero() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        init_committed_code_proposal(&mut shell, 0, None);
+        let validator = shell.mode.get_validator_address().unwrap().clone();
+        let params = read_pos_params(&shell.wl_storage).unwrap();
+        let epoch = shell.wl_storage.storage.block.epoch;
+
+        // Slash the whole stake of the validator
+        let stake =
+            read_validator_stake(&shell.wl_storage, &params, &validator, epoch)
+                .unwrap();
+        assert!(!stake.is_zero());
+        update_validator_deltas(
+            &mut shell.wl_storage,
+            &params,
+            &validator,
+            -stake.change(),
+            epoch,
+            Some(0),
+        )
+        .unwrap();
+        update_total_deltas(
+            &mut shell.wl_storage,
+            &params,
+            -stake.change(),
+            epoch,
+            Some(0),
+        )
+        .unwrap();
+
+        let votes =
+            compute_proposal_votes(&shell.wl_storage, &params, 0, epoch)
+                .unwrap();
+        let participation = votes.validators_participation([validator.clone()]);
+        assert!(participation[&validator].voted);
+
+        let weighted_votes = votes.weighted_votes();
+        assert_eq!(weighted_votes.len(), 1);
+        assert!(weighted_votes[0].vote.is_yay());
+        assert!(weighted_votes[0].voting_power.is_zero());
+        assert!(!weighted_votes[0].staked_validator);
+
+        let outcome = tally_proposal_votes(
+            &shell.wl_storage,
+            &params,
+            &votes,
+            epoch,
+            TallyType::OneHalfOverOneThird,
+        )
+        .unwrap();
+        assert!(outcome.votes.yay_voting_power.is_zero());
+        assert_eq!(outcome.votes.yay_validators, 0);
+        assert_eq!(outcome.basis_points, TallyBasisPoints::default());
+    }
+
+    /// Test that the event of a tallied proposal carries the shares of the
+    /// votes cast on it
+    #[test]
+    fn test_proposal_event_basis_points() {
+        use namada::ledger::events::ProposalOutcome;
+
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let events = pass_proposal(&mut shell, 0, ProposalType::Default(None));
+        assert_eq!(events.len(), 1);
+        let outcome = ProposalOutcome::try_from(&events[0]).unwrap();
+        assert_eq!(
+            outcome.basis_points,
+            Some(TallyBasisPoints {
+                yay: TallyBasisPoints::WHOLE,
+                nay: 0,
+                abstain: 0,
+            })
+        );
+        assert_eq!(
+            events[0][events::attributes::TALLY_PERCENTAGES],
+            "yay 100.00%, nay 0.00%, abstain 0.00%"
+        );
+    }
+
+    /// Test that a proposal resuming the Ethereum bridge, concluded in the
+    /// same epoch as a proposal pausing it, is either applied with a
+    /// warning or rejected, depending on the parameter conflict policy, and
+    /// that proposals concluded in a later epoch don't conflict
+    #[test]
+    fn test_conflicting_parameter_proposals() {
+        use namada::ledger::events::ProposalOutcome;
+
+        for policy in [
+            ParameterConflictPolicy::ApplyInOrder,
+            ParameterConflictPolicy::RejectLater,
+        ] {
+            let (mut shell, _broadcaster, _, _eth_control) = setup();
+            shell
+                .wl_storage
+                .write(
+                    &gov_storage::get_parameter_conflict_policy_key(),
+                    policy,
+                )
+                .unwrap();
+            let events = pass_proposal(
+                &mut shell,
+                0,
+                ProposalType::ETHBridgePause(BridgePauseAction::Pause),
+            );
+            assert_eq!(events.len(), 1);
+
+            let (result, events) = conclude_voted_proposal(
+                &mut shell,
+                1,
+                ProposalType::ETHBridgePause(BridgePauseAction::Resume),
+            );
+            let outcome = ProposalOutcome::try_from(&events[0]).unwrap();
+            let proposal_result =
+                gov_api::get_proposal_result(&shell.wl_storage, 1)
+                    .unwrap()
+                    .unwrap();
+            match policy {
+                ParameterConflictPolicy::ApplyInOrder => {
+                    assert_eq!(result.passed, vec![1]);
+                    assert_eq!(outcome.tally, TallyResult::Passed);
+                    assert_eq!(outcome.conflicts_with, None);
+                    assert_eq!(proposal_result.result, TallyResult::Passed);
+                    assert_eq!(
+                        gov_api::get_proposal_conflict(&shell.wl_storage, 1)
+                            .unwrap(),
+                        None
+                    );
+                    assert_eq!(
+                        events[1][events::attributes::WARNING],
+                        "parameter_conflict"
+                    );
+                    assert_eq!(events[1][events::attributes::CONFLICT], "0");
+                    assert!(
+                        !shell
+                            .wl_storage
+                            .ethbridge_queries()
+                            .is_bridge_paused()
+                    );
+                }
+                ParameterConflictPolicy::RejectLater => {
+                    assert_eq!(result.rejected, vec![1]);
+                    assert_eq!(outcome.tally, TallyResult::Rejected);
+                    assert_eq!(outcome.conflicts_with, Some(0));
+                    // the rejection is recorded in the result of the
+                    // proposal, and in its archive, along with the
+                    // conflicting proposal
+                    assert_eq!(proposal_result.result, TallyResult::Rejected);
+                    assert_eq!(
+                        gov_api::get_proposal_conflict(&shell.wl_storage, 1)
+                            .unwrap(),
+                        Some(0)
+                    );
+                    let archived = gov_api::get_proposal_archive(
+                        &shell.wl_storage,
+                        shell.wl_storage.storage.block.epoch,
+                        1,
+                    )
+                    .unwrap()
+                    .unwrap();
+                    assert_eq!(archived.result, proposal_result);
+                    assert_eq!(events.len(), 1);
+                    assert!(
+                        shell.wl_storage.ethbridge_queries().is_bridge_paused()
+                    );
+                }
+            }
+
+            // the parameter changes of previous epochs are disregarded
+            shell.wl_storage.storage.block.epoch =
+                shell.wl_storage.storage.block.epoch.next();
+            let events = pass_proposal(
+                &mut shell,
+                2,
+                ProposalType::ETHBridgePause(BridgePauseAction::Pause),
+            );
+            assert_eq!(events.len(), 1);
+            assert!(shell.wl_storage.ethbridge_queries().is_bridge_paused());
+        }
+    }
 }
@@ -0,0 +1,358 @@
+//! Offline export of the governance storage at a committed height.
+//!
+//! Every key under the governance address, as it was at the requested
+//! height, is written to newline-delimited JSON chunks. The values whose
+//! type is pinned by the governance storage facade are decoded, the others
+//! are hex encoded. A manifest lists the chunks with their hashes, together
+//! with the height and the state root at that height, such that auditors
+//! can check an export against the chain.
+
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+
+use data_encoding::HEXLOWER;
+use namada::governance::storage::facade::decode_value_as_json;
+use namada::governance::ADDRESS as gov_address;
+use namada::state::{DBIter, State, StorageHasher, DB};
+use namada::types::hash::Hash;
+use namada::types::storage::{BlockHeight, Key, KeySeg};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Maximum number of records written to each chunk of an export.
+pub const RECORDS_PER_CHUNK: usize = 10_000;
+
+/// The name of the manifest file of an export.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Errors of the governance export.
+#[derive(Error, Debug)]
+pub enum ExportError {
+    #[error("Storage error: {0}")]
+    Storage(#[from] namada::state::Error),
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("JSON encoding error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error(
+        "Height {height} hasn't been committed yet, the last committed height \
+         is {last_height}"
+    )]
+    HeightNotCommitted {
+        height: BlockHeight,
+        last_height: BlockHeight,
+    },
+    #[error("The state at height {0} is no longer retained by the node")]
+    HeightNotRetained(BlockHeight),
+}
+
+/// The manifest of an export, written to [`MANIFEST_FILE`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportManifest {
+    /// The ID of the exported chain.
+    pub chain_id: String,
+    /// The height of the exported state.
+    pub height: BlockHeight,
+    /// The root of the Merkle tree at the exported height.
+    pub state_root: String,
+    /// The total number of exported records.
+    pub records: u64,
+    /// The chunks of the export, in order.
+    pub chunks: Vec<ExportChunk>,
+}
+
+/// A chunk of an export, as listed in its manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportChunk {
+    /// The name of the chunk file, relative to the export directory.
+    pub file: String,
+    /// The number of records in the chunk.
+    pub records: u64,
+    /// The SHA-256 hash of the contents of the chunk file.
+    pub sha256: String,
+}
+
+/// A storage key and its value, as a line of a chunk.
+#[derive(Debug, Serialize)]
+struct ExportRecord<'a> {
+    key: &'a str,
+    /// The decoded value, if its type is known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<serde_json::Value>,
+    /// The hex encoded value, if its type isn't known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    raw: Option<String>,
+}
+
+/// Export the governance storage at the given height, or at the last
+/// committed height if `None`, to the given directory. The directory is
+/// created if needed, and existing export files are never overwritten.
+pub fn export_governance<D, H>(
+    storage: &State<D, H>,
+    height: Option<BlockHeight>,
+    out_dir: &Path,
+    records_per_chunk: usize,
+) -> Result<ExportManifest, ExportError>
+where
+    D: DB + for<'iter> DBIter<'iter>,
+    H: StorageHasher,
+{
+    let last_height = storage.get_last_block_height();
+    let height = height.unwrap_or(last_height);
+    if height > last_height {
+        return Err(ExportError::HeightNotCommitted {
+            height,
+            last_height,
+        });
+    }
+    let oldest_height = match storage.storage_read_past_height_limit {
+        Some(limit) if limit < last_height.0 => {
+            BlockHeight(last_height.0 - limit)
+        }
+        _ => BlockHeight::first(),
+    };
+    if height < oldest_height {
+        return Err(ExportError::HeightNotRetained(height));
+    }
+    let state_root = match storage.get_merkle_tree(height, None) {
+        Ok(tree) => tree.root(),
+        Err(namada::state::Error::NoMerkleTree { .. }) => {
+            return Err(ExportError::HeightNotRetained(height));
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    // The keys present at the exported height are either still present, or
+    // were deleted at a later height, in which case their old value is
+    // recorded in the diffs of that height
+    let prefix = Key::from(gov_address.to_db_key());
+    let mut keys: BTreeSet<String> = storage
+        .iter_prefix(&prefix)
+        .0
+        .map(|(key, _value, _gas)| key)
+        .collect();
+    for diff_height in (height.0 + 1)..=last_height.0 {
+        keys.extend(
+            storage
+                .db
+                .iter_old_diffs(BlockHeight(diff_height), Some(&prefix))
+                .map(|(key, _value, _gas)| key),
+        );
+    }
+
+    std::fs::create_dir_all(out_dir)?;
+    let mut chunks = vec![];
+    let mut chunk = String::new();
+    let mut chunk_records = 0_u64;
+    let mut records = 0_u64;
+    for key_str in &keys {
+        let key = Key::parse(key_str).expect("the key should be parsable");
+        let Some(bytes) = storage.read_with_height(&key, height)?.0 else {
+            continue;
+        };
+        let value = decode_value_as_json(&key, &bytes);
+        let record = ExportRecord {
+            key: key_str,
+            raw: value.is_none().then(|| HEXLOWER.encode(&bytes)),
+            value,
+        };
+        chunk.push_str(&serde_json::to_string(&record)?);
+        chunk.push('\n');
+        chunk_records += 1;
+        records += 1;
+        if chunk_records as usize == records_per_chunk {
+            chunks.push(write_chunk(
+                out_dir,
+                chunks.len(),
+                &chunk,
+                chunk_records,
+            )?);
+            chunk.clear();
+            chunk_records = 0;
+        }
+    }
+    if chunk_records > 0 {
+        chunks.push(write_chunk(out_dir, chunks.len(), &chunk, chunk_records)?);
+    }
+
+    let manifest = ExportManifest {
+        chain_id: storage.chain_id.to_string(),
+        height,
+        state_root: state_root.to_string(),
+        records,
+        chunks,
+    };
+    let manifest_json = serde_json::to_string_pretty(&manifest)?;
+    create_new_file(&out_dir.join(MANIFEST_FILE))?
+        .write_all(manifest_json.as_bytes())?;
+    Ok(manifest)
+}
+
+/// Write the chunk with the given index to the export directory.
+fn write_chunk(
+    out_dir: &Path,
+    index: usize,
+    contents: &str,
+    records: u64,
+) -> Result<ExportChunk, ExportError> {
+    let file = format!("governance_{index:05}.jsonl");
+    create_new_file(&out_dir.join(&file))?.write_all(contents.as_bytes())?;
+    Ok(ExportChunk {
+        file,
+        records,
+        sha256: Hash::sha256(contents.as_bytes()).to_string(),
+    })
+}
+
+fn create_new_file(path: &Path) -> io::Result<File> {
+    File::options().write(true).create_new(true).open(path)
+}
+
+#[cfg(test)]
+mod test {
+    use namada::governance::storage::keys as gov_storage;
+    use namada::state::merkle_tree::MerkleRoot;
+    use namada::token::Amount;
+    use namada::types::chain::ChainId;
+    use namada::types::storage::BlockHash;
+    use namada::types::{address, encode, storage};
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::node::ledger::shell::is_merklized_storage_key;
+    use crate::node::ledger::storage::PersistentStorage;
+
+    /// Commit a block at the given height, after applying `f` to the
+    /// storage, returning the state root of the block
+    fn commit_block(
+        storage: &mut PersistentStorage,
+        height: u64,
+        f: impl FnOnce(&mut PersistentStorage),
+    ) -> MerkleRoot {
+        storage
+            .begin_block(BlockHash::default(), BlockHeight(height))
+            .unwrap();
+        if height == 1 {
+            storage.block.pred_epochs.new_epoch(BlockHeight(1));
+        }
+        f(storage);
+        let root = storage.merkle_root();
+        storage.commit_block(PersistentStorage::batch()).unwrap();
+        root
+    }
+
+    /// Test the export of a small chain, at a height whose state was later
+    /// changed, against its manifest
+    #[test]
+    fn test_export_governance() {
+        let db_dir = TempDir::new().unwrap();
+        let out_dir = TempDir::new().unwrap();
+        let mut storage = PersistentStorage::open(
+            db_dir.path(),
+            ChainId::default(),
+            address::nam(),
+            None,
+            None,
+            is_merklized_storage_key,
+        );
+        let counter_key = gov_storage::get_counter_key();
+        let funds_key = gov_storage::get_funds_key(0);
+        let code_key = gov_storage::get_proposal_code_key(0);
+        let other_key = storage::Key::parse("other").unwrap();
+
+        commit_block(&mut storage, 1, |storage| {
+            storage.write(&counter_key, encode(&1_u64)).unwrap();
+            storage
+                .write(&funds_key, encode(&Amount::native_whole(500)))
+                .unwrap();
+            storage.write(&code_key, vec![1, 2, 3]).unwrap();
+            storage.write(&other_key, vec![0]).unwrap();
+        });
+        let root = commit_block(&mut storage, 2, |storage| {
+            storage.write(&counter_key, encode(&2_u64)).unwrap();
+        });
+        commit_block(&mut storage, 3, |storage| {
+            storage.write(&counter_key, encode(&3_u64)).unwrap();
+            storage.delete(&funds_key).unwrap();
+        });
+
+        let manifest = export_governance(
+            &storage,
+            Some(BlockHeight(2)),
+            out_dir.path(),
+            2,
+        )
+        .unwrap();
+        assert_eq!(manifest.height, BlockHeight(2));
+        assert_eq!(manifest.state_root, root.to_string());
+        assert_eq!(manifest.records, 3);
+        assert_eq!(
+            manifest
+                .chunks
+                .iter()
+                .map(|c| c.records)
+                .collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+
+        // the manifest on disk matches the returned one, and the hashes of
+        // the chunks match their contents
+        let manifest_json =
+            std::fs::read(out_dir.path().join(MANIFEST_FILE)).unwrap();
+        let read_manifest: ExportManifest =
+            serde_json::from_slice(&manifest_json).unwrap();
+        assert_eq!(read_manifest, manifest);
+        let mut lines = vec![];
+        for chunk in &manifest.chunks {
+            let contents =
+                std::fs::read_to_string(out_dir.path().join(&chunk.file))
+                    .unwrap();
+            assert_eq!(
+                Hash::sha256(contents.as_bytes()).to_string(),
+                chunk.sha256
+            );
+            lines.extend(contents.lines().map(|line| {
+                serde_json::from_str::<serde_json::Value>(line).unwrap()
+            }));
+        }
+
+        // the funds deleted at a later height are exported, typed values
+        // are decoded and others are hex encoded
+        let record = |key: &Key| {
+            lines
+                .iter()
+                .find(|line| line["key"] == key.to_string())
+                .cloned()
+                .unwrap()
+        };
+        assert_eq!(record(&counter_key)["value"], serde_json::json!(2));
+        assert_eq!(
+            record(&funds_key)["value"],
+            serde_json::to_value(Amount::native_whole(500)).unwrap()
+        );
+        assert_eq!(record(&code_key)["raw"], "010203");
+        assert!(record(&code_key).get("value").is_none());
+
+        // exports never overwrite existing files
+        assert!(matches!(
+            export_governance(
+                &storage,
+                Some(BlockHeight(2)),
+                out_dir.path(),
+                2
+            ),
+            Err(ExportError::Io(_))
+        ));
+        assert!(matches!(
+            export_governance(
+                &storage,
+                Some(BlockHeight(4)),
+                out_dir.path(),
+                2
+            ),
+            Err(ExportError::HeightNotCommitted { .. })
+        ));
+    }
+}
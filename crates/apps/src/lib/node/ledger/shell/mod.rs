@@ -6,22 +6,26 @@
 //! (unless we can simply overwrite them in the next block).
 //! More info in <https://github.com/anoma/namada/issues/362>.
 pub mod block_alloc;
+pub mod change_feed;
+pub mod epoch_hooks;
 mod finalize_block;
 mod governance;
+pub mod governance_export;
 mod init_chain;
 pub use init_chain::InitChainValidation;
 use namada_sdk::tx::data::GasLimit;
+pub mod phase_timing;
 pub mod prepare_proposal;
 pub mod process_proposal;
+pub mod proof_archive;
 pub(super) mod queries;
 mod stats;
 #[cfg(any(test, feature = "testing"))]
 #[allow(dead_code)]
 pub mod testing;
-pub mod utils;
 mod vote_extensions;
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::convert::{TryFrom, TryInto};
 use std::mem;
 use std::path::{Path, PathBuf};
@@ -33,8 +37,15 @@ use borsh_ext::BorshSerializeExt;
 use masp_primitives::transaction::Transaction;
 use namada::core::hints;
 use namada::ethereum_bridge::protocol::validation::bridge_pool_roots::validate_bp_roots_vext;
+use namada::ethereum_bridge::protocol::validation::contract_addresses::validate_contract_addresses_vext;
 use namada::ethereum_bridge::protocol::validation::ethereum_events::validate_eth_events_vext;
+use namada::ethereum_bridge::protocol::validation::timing as vext_timing;
 use namada::ethereum_bridge::protocol::validation::validator_set_update::validate_valset_upd_vext;
+use namada::governance::storage::consistency::{
+    self as governance_consistency, ConsistencyReport,
+};
+use namada::governance::storage::facade::ProposalStorageReader;
+use namada::governance::VoteProposalData;
 use namada::ledger::events::log::EventLog;
 use namada::ledger::events::Event;
 use namada::ledger::gas::{Gas, TxGasMeter};
@@ -45,14 +56,14 @@ use namada::ledger::protocol::{
     apply_wasm_tx, get_fee_unshielding_transaction,
     get_transfer_hash_from_storage, ShellParams,
 };
-use namada::ledger::{parameters, pos, protocol};
+use namada::ledger::{parameters, pos, protocol, WriteSurfaceCache};
 use namada::parameters::validate_tx_bytes;
 use namada::proof_of_stake::slashing::{process_slashes, slash};
 use namada::proof_of_stake::storage::read_pos_params;
 use namada::proof_of_stake::{self};
 use namada::state::tx_queue::{ExpiredTx, TxInQueue};
 use namada::state::wl_storage::WriteLogAndStorage;
-use namada::state::write_log::WriteLog;
+use namada::state::write_log::{StorageModification, WriteLog};
 use namada::state::{
     DBIter, Sha256Hasher, State, StorageHasher, StorageRead, TempWlStorage,
     WlStorage, DB, EPOCH_SWITCH_BLOCKS_DELAY,
@@ -64,7 +75,8 @@ use namada::tx::{Section, Tx};
 use namada::types::address;
 use namada::types::address::Address;
 use namada::types::chain::ChainId;
-use namada::types::ethereum_events::EthereumEvent;
+use namada::types::ethereum_events::{EthAddress, EthereumEvent};
+use namada::types::hash::Hash;
 use namada::types::key::*;
 use namada::types::storage::{BlockHeight, Key, TxIndex};
 use namada::types::time::DateTimeUtc;
@@ -76,12 +88,14 @@ use namada_sdk::tendermint::AppHash;
 use thiserror::Error;
 use tokio::sync::mpsc::{Receiver, UnboundedSender};
 
+use self::governance_export::{ExportError, ExportManifest};
 use super::ethereum_oracle::{self as oracle, last_processed_block};
 use crate::config::{self, genesis, TendermintMode, ValidatorLocalConfig};
 use crate::facade::tendermint::abci::types::{Misbehavior, MisbehaviorKind};
 use crate::facade::tendermint::v0_37::abci::{request, response};
 use crate::facade::tendermint::{self, validator};
 use crate::facade::tendermint_proto::v0_37::crypto::public_key;
+use crate::node::ledger::eth_signer::RemoteEthSigner;
 use crate::node::ledger::shims::abcipp_shim_types::shim;
 use crate::node::ledger::shims::abcipp_shim_types::shim::response::TxResult;
 use crate::node::ledger::{storage, tendermint_node};
@@ -100,6 +114,13 @@ fn key_to_tendermint(
     }
 }
 
+/// Env. var to record the validation timings of vote extensions
+pub const ENV_VAR_VEXT_TIMING: &str = "NAMADA_VEXT_TIMING";
+
+/// The number of epochs after the voting period of a governance proposal
+/// during which the votes on it are still admitted to the mempool
+pub const LATE_VOTE_GRACE_EPOCHS: u64 = 1;
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("Error removing the DB data: {0}")]
@@ -128,6 +149,8 @@ pub enum Error {
     Storage(#[from] namada::state::StorageError),
     #[error("Transaction replay attempt: {0}")]
     ReplayAttempt(String),
+    #[error("Error in the {0} epoch hook: {1}")]
+    EpochHook(epoch_hooks::EpochHook, Box<Error>),
 }
 
 impl From<Error> for TxResult {
@@ -169,6 +192,58 @@ pub fn rollback(config: config::Ledger) -> Result<()> {
         .map_err(|e| Error::Storage(namada::state::StorageError::new(e)))
 }
 
+/// Open the storage of the node in read-only mode, loaded at the last
+/// committed block.
+fn open_storage_read_only(
+    config: config::Ledger,
+) -> std::result::Result<storage::PersistentStorage, namada::state::Error> {
+    let chain_id = config.chain_id;
+    let db_path = config.shell.db_dir(&chain_id);
+    let chain_dir = config.shell.base_dir.join(chain_id.as_str());
+    let genesis = genesis::chain::Finalized::read_toml_files(&chain_dir)
+        .expect("Missing genesis files");
+    let db = storage::open_read_only(db_path)?;
+    let mut storage = storage::PersistentStorage::open_with_db(
+        db,
+        chain_id,
+        genesis.get_native_token().clone(),
+        config.shell.storage_read_past_height_limit,
+        is_merklized_storage_key,
+    );
+    storage.load_last_state()?;
+    Ok(storage)
+}
+
+/// Check the consistency of the governance storage at the last committed
+/// block. The storage is opened in read-only mode.
+pub fn verify_governance_storage(
+    config: config::Ledger,
+) -> Result<ConsistencyReport> {
+    let storage = open_storage_read_only(config)
+        .map_err(|e| Error::Storage(namada::state::StorageError::new(e)))?;
+    let report = governance_consistency::verify_governance_storage(
+        &TempWlStorage::new(&storage),
+    )?;
+    Ok(report)
+}
+
+/// Export the governance storage at the given height, or at the last
+/// committed one, to the given directory. The storage is opened in
+/// read-only mode.
+pub fn export_governance(
+    config: config::Ledger,
+    height: Option<BlockHeight>,
+    out_dir: &Path,
+) -> std::result::Result<ExportManifest, ExportError> {
+    let storage = open_storage_read_only(config)?;
+    governance_export::export_governance(
+        &storage,
+        height,
+        out_dir,
+        governance_export::RECORDS_PER_CHUNK,
+    )
+}
+
 #[derive(Debug)]
 #[allow(dead_code, clippy::large_enum_variant)]
 pub(super) enum ShellMode {
@@ -186,17 +261,19 @@ pub(super) enum ShellMode {
 /// and queueing them up for inclusion in vote extensions
 #[derive(Debug)]
 pub(super) struct EthereumReceiver {
-    channel: Receiver<EthereumEvent>,
-    queue: BTreeSet<EthereumEvent>,
+    channel: Receiver<(EthAddress, EthereumEvent)>,
+    /// The queued events, along with the address of the contract
+    /// they were observed from
+    queue: BTreeMap<EthereumEvent, EthAddress>,
 }
 
 impl EthereumReceiver {
     /// Create a new [`EthereumReceiver`] from a channel connected
     /// to an Ethereum oracle
-    pub fn new(channel: Receiver<EthereumEvent>) -> Self {
+    pub fn new(channel: Receiver<(EthAddress, EthereumEvent)>) -> Self {
         Self {
             channel,
-            queue: BTreeSet::new(),
+            queue: BTreeMap::new(),
         }
     }
 
@@ -213,8 +290,9 @@ impl EthereumReceiver {
     {
         let mut new_events = 0;
         let mut filtered_events = 0;
-        while let Ok(eth_event) = self.channel.try_recv() {
-            if keep_event(&eth_event) && self.queue.insert(eth_event) {
+        while let Ok((contract, eth_event)) = self.channel.try_recv() {
+            if keep_event(&eth_event) && !self.queue.contains_key(&eth_event) {
+                self.queue.insert(eth_event, contract);
                 new_events += 1;
             } else {
                 filtered_events += 1;
@@ -229,9 +307,26 @@ impl EthereumReceiver {
         }
     }
 
+    /// Drop the queued events that were not observed from the given
+    /// bridge contract, since a vote extension can only carry events
+    /// observed from the contract in effect at its epoch.
+    pub fn retain_observed_from(&mut self, bridge_contract: &EthAddress) {
+        let queued_events = self.queue.len();
+        self.queue.retain(|_, contract| contract == bridge_contract);
+        let dropped_events = queued_events - self.queue.len();
+        if dropped_events > 0 {
+            tracing::warn!(
+                dropped_events,
+                ?bridge_contract,
+                "dropped Ethereum events observed from a bridge contract no \
+                 longer in effect"
+            );
+        }
+    }
+
     /// Get a copy of the queue
     pub fn get_events(&self) -> Vec<EthereumEvent> {
-        self.queue.iter().cloned().collect()
+        self.queue.keys().cloned().collect()
     }
 
     /// Remove the given [`EthereumEvent`] from the queue, if present.
@@ -356,10 +451,30 @@ where
     /// limit the how many block heights in the past can the storage be
     /// queried for reading values.
     storage_read_past_height_limit: Option<u64>,
+    /// Taken from config `ethereum_bridge.enable_valset_preview`. When set,
+    /// enables the previews of the validator set updates of the bridge.
+    enable_valset_preview: bool,
+    /// Taken from config `ethereum_bridge.eth_signer`. When set, the
+    /// validator set update vote extensions are signed by a remote signer.
+    eth_signer: Option<RemoteEthSigner>,
+    /// Taken from config `ethereum_bridge.eth_cold_signer_socket`. When
+    /// set, the contract addresses vote extensions are signed with the
+    /// Ethereum cold key held by this remote signer.
+    eth_cold_signer: Option<RemoteEthSigner>,
+    /// Taken from config `shell.reject_late_votes`. When set, the
+    /// governance votes submitted after the voting period of their proposal
+    /// are rejected from the mempool.
+    reject_late_votes: bool,
     /// Proposal execution tracking
     pub proposal_data: BTreeSet<u64>,
     /// Log of events emitted by `FinalizeBlock` ABCI calls.
     event_log: EventLog,
+    /// Cache of the write surfaces of the proposals queried so far.
+    write_surface_cache: WriteSurfaceCache,
+    /// Feed of the changes of the storage keys watched by the node.
+    pub(crate) change_feed: change_feed::ChangeFeed,
+    /// Archiver of the Ethereum bridge proofs completed on chain.
+    pub(crate) proof_archiver: proof_archive::ProofArchiver,
 }
 
 /// Merkle tree storage key filter. Return `false` for keys that shouldn't be
@@ -379,7 +494,7 @@ pub struct EthereumOracleChannels {
 
 impl EthereumOracleChannels {
     pub fn new(
-        events_receiver: Receiver<EthereumEvent>,
+        events_receiver: Receiver<(EthAddress, EthereumEvent)>,
         control_sender: oracle::control::Sender,
         last_processed_block_receiver: last_processed_block::Receiver,
     ) -> Self {
@@ -409,11 +524,24 @@ where
         tx_wasm_compilation_cache: u64,
     ) -> Self {
         let chain_id = config.chain_id;
+        let change_feed = change_feed::ChangeFeed::new(&config.change_feed);
+        let proof_archiver =
+            proof_archive::ProofArchiver::new(config.proof_archive.as_ref());
         let db_path = config.shell.db_dir(&chain_id);
         let base_dir = config.shell.base_dir;
         let mode = config.shell.tendermint_mode;
         let storage_read_past_height_limit =
             config.shell.storage_read_past_height_limit;
+        let enable_valset_preview =
+            config.ethereum_bridge.enable_valset_preview;
+        let eth_signer = RemoteEthSigner::from_config(&config.ethereum_bridge);
+        let eth_cold_signer =
+            RemoteEthSigner::cold_from_config(&config.ethereum_bridge);
+        let reject_late_votes = config.shell.reject_late_votes;
+        vext_timing::set_enabled(match std::env::var(ENV_VAR_VEXT_TIMING) {
+            Ok(val) => val.to_ascii_lowercase().trim() == "true",
+            _ => false,
+        });
         if !Path::new(&base_dir).is_dir() {
             std::fs::create_dir(&base_dir)
                 .expect("Creating directory for Namada should not fail");
@@ -533,11 +661,30 @@ where
                 tx_wasm_compilation_cache as usize,
             ),
             storage_read_past_height_limit,
+            enable_valset_preview,
+            eth_signer,
+            eth_cold_signer,
+            reject_late_votes,
             proposal_data: BTreeSet::new(),
             // TODO: config event log params
             event_log: EventLog::default(),
+            write_surface_cache: WriteSurfaceCache::default(),
+            change_feed,
+            proof_archiver,
         };
         shell.update_eth_oracle(&Default::default());
+        // Rebuild the queue of the proposals due at the next epoch change,
+        // which isn't persisted across restarts
+        match shell.reconcile_proposal_queue() {
+            Ok(mismatch) => tracing::debug!(
+                "Loaded {} governance proposals due at the next epoch change",
+                mismatch.missing.len()
+            ),
+            Err(err) => tracing::error!(
+                "Failed to load the governance proposals queued in storage: {}",
+                err
+            ),
+        }
         shell
     }
 
@@ -620,6 +767,29 @@ where
         }
     }
 
+    /// Check if a storage key is present in the write log or in storage
+    /// dropping any error, without reading its value
+    pub fn has_storage_key(&self, key: &Key) -> bool {
+        self.storage_key_len(key).is_some()
+    }
+
+    /// Get the length in bytes of the value of a storage key in the write
+    /// log or in storage dropping any error, without reading the value
+    pub fn storage_key_len(&self, key: &Key) -> Option<usize> {
+        match self.wl_storage.write_log.read(key).0 {
+            Some(StorageModification::Write { value })
+            | Some(StorageModification::Temp { value }) => Some(value.len()),
+            Some(StorageModification::InitAccount { vp_code_hash }) => {
+                Some(vp_code_hash.0.len())
+            }
+            Some(StorageModification::Delete) => None,
+            None => match self.wl_storage.storage.value_len(key) {
+                Ok((len, _gas)) => len,
+                Err(_) => None,
+            },
+        }
+    }
+
     /// Apply PoS slashes from the evidence
     fn record_slashes_from_evidence(&mut self) {
         if !self.byzantine_validators.is_empty() {
@@ -928,8 +1098,12 @@ where
                 );
                 return;
             }
-            let Some(config) = EthereumOracleConfig::read(&self.wl_storage) else {
-                tracing::info!("Not starting oracle as the Ethereum bridge config couldn't be found in storage");
+            let Some(config) = EthereumOracleConfig::read(&self.wl_storage)
+            else {
+                tracing::info!(
+                    "Not starting oracle as the Ethereum bridge config \
+                     couldn't be found in storage"
+                );
                 return;
             };
             let active =
@@ -1166,6 +1340,31 @@ where
                         response.priority = i64::MAX;
                     }
                 }
+                ProtocolTxType::ContractAddressesVext => {
+                    let ext = try_vote_extension!(
+                        "contract addresses",
+                        response,
+                        ethereum_tx_data_variants::ContractAddressesVext::try_from(
+                            &tx
+                        ),
+                    );
+                    if let Err(err) = validate_contract_addresses_vext(
+                        &self.wl_storage,
+                        &ext,
+                        // n.b. much like validator set updates, only
+                        // accept contract addresses signed up to the
+                        // last committed epoch
+                        self.wl_storage.storage.last_epoch,
+                    ) {
+                        response.code = ResultCode::InvalidVoteExtension.into();
+                        response.log = format!(
+                            "{INVALID_MSG}: Invalid contract addresses vote \
+                             extension: {err}",
+                        );
+                    } else {
+                        response.log = String::from(VALID_MSG);
+                    }
+                }
                 _ => {
                     response.code = ResultCode::InvalidTx.into();
                     response.log = format!(
@@ -1235,6 +1434,15 @@ where
                     return response;
                 }
 
+                // Late governance votes
+                if self.reject_late_votes {
+                    if let Err(msg) = self.check_late_vote(&tx) {
+                        response.code = ResultCode::InvalidTx.into();
+                        response.log = format!("{INVALID_MSG}: {msg}");
+                        return response;
+                    }
+                }
+
                 // Validate wrapper fees
                 if let Err(e) = mempool_fee_check(
                     &wrapper,
@@ -1269,6 +1477,54 @@ where
         response
     }
 
+    /// Check if the given tx is a vote on a governance proposal whose voting
+    /// period is over. This check is best-effort, sparing block space for
+    /// the votes bound to be rejected by the governance VP: any tx that
+    /// isn't recognized as a vote by its code hash, that can't be decoded,
+    /// or whose proposal can't be read, passes it, and so do the votes on
+    /// proposals voted on by block height.
+    fn check_late_vote(&self, tx: &Tx) -> std::result::Result<(), String> {
+        let vote_code_hash: Option<Hash> = self
+            .wl_storage
+            .read(&Key::wasm_hash(namada_sdk::tx::TX_VOTE_PROPOSAL))
+            .ok()
+            .flatten();
+        let code_hash = tx
+            .get_section(tx.code_sechash())
+            .and_then(|section| Section::code_sec(section.as_ref()))
+            .map(|code| code.code.hash());
+        if vote_code_hash.is_none() || code_hash != vote_code_hash {
+            return Ok(());
+        }
+        let Some(vote) = tx
+            .data()
+            .and_then(|data| VoteProposalData::try_from_slice(&data).ok())
+        else {
+            return Ok(());
+        };
+        let proposal = ProposalStorageReader::new(&self.wl_storage);
+        if !matches!(proposal.voting_heights(vote.id), Ok(None)) {
+            return Ok(());
+        }
+        let Ok(Some(voting_end)) = proposal.voting_end(vote.id) else {
+            return Ok(());
+        };
+        // The last epoch of the voting period is the one preceding its end
+        // epoch. Only the votes submitted strictly after it, with one epoch
+        // of grace, are rejected, such that a vote submitted at the last
+        // block of the voting period is never rejected here
+        let current_epoch = self.wl_storage.storage.last_epoch;
+        let last_voting_epoch = voting_end.0.saturating_sub(1);
+        if current_epoch.0 > last_voting_epoch + LATE_VOTE_GRACE_EPOCHS {
+            return Err(format!(
+                "Vote on governance proposal {} submitted at epoch {}, after \
+                 the end of its voting period at epoch {}",
+                vote.id, current_epoch, voting_end
+            ));
+        }
+        Ok(())
+    }
+
     fn get_abci_validator_updates<F, V>(
         &self,
         is_genesis: bool,
@@ -1714,7 +1970,7 @@ mod test_utils {
         ) -> (
             Self,
             UnboundedReceiver<Vec<u8>>,
-            Sender<EthereumEvent>,
+            Sender<(EthAddress, EthereumEvent)>,
             Receiver<oracle::control::Command>,
         ) {
             let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
@@ -1755,7 +2011,7 @@ mod test_utils {
         pub fn new() -> (
             Self,
             UnboundedReceiver<Vec<u8>>,
-            Sender<EthereumEvent>,
+            Sender<(EthAddress, EthereumEvent)>,
             Receiver<oracle::control::Command>,
         ) {
             Self::new_at_height(BlockHeight(1))
@@ -1866,6 +2122,17 @@ mod test_utils {
                 DateTimeUtc::now();
         }
 
+        /// Get the address of the Ethereum bridge contract in effect at
+        /// the last committed epoch.
+        pub fn bridge_contract(&self) -> EthAddress {
+            self.wl_storage
+                .ethbridge_queries()
+                .get_contracts_at(self.wl_storage.storage.last_epoch)
+                .expect("Test failed")
+                .bridge
+                .address
+        }
+
         /// Simultaneously call the `FinalizeBlock` and
         /// `Commit` handlers.
         pub fn finalize_and_commit(&mut self, req: Option<FinalizeBlock>) {
@@ -1930,7 +2197,7 @@ mod test_utils {
     ) -> (
         TestShell,
         UnboundedReceiver<Vec<u8>>,
-        Sender<EthereumEvent>,
+        Sender<(EthAddress, EthereumEvent)>,
         Receiver<oracle::control::Command>,
     ) {
         let (mut test, receiver, eth_sender, control_receiver) =
@@ -1987,7 +2254,7 @@ mod test_utils {
     ) -> (
         TestShell,
         UnboundedReceiver<Vec<u8>>,
-        Sender<EthereumEvent>,
+        Sender<(EthAddress, EthereumEvent)>,
         Receiver<oracle::control::Command>,
     ) {
         let last_height = last_height.into();
@@ -2003,7 +2270,7 @@ mod test_utils {
     pub(super) fn setup() -> (
         TestShell,
         UnboundedReceiver<Vec<u8>>,
-        Sender<EthereumEvent>,
+        Sender<(EthAddress, EthereumEvent)>,
         Receiver<oracle::control::Command>,
     ) {
         setup_with_cfg(SetupCfg::<u64>::default())
@@ -2219,6 +2486,8 @@ mod test_utils {
 #[cfg(test)]
 mod shell_tests {
     use namada::core::ledger::replay_protection;
+    use namada::governance::storage::keys as gov_storage;
+    use namada::state::StorageWrite;
     use namada::token::read_denom;
     use namada::tx::data::protocol::{ProtocolTx, ProtocolTxType};
     use namada::tx::data::{Fee, WrapperTx};
@@ -2232,6 +2501,7 @@ mod shell_tests {
         bridge_pool_roots, ethereum_events, ethereum_tx_data_variants,
     };
     use namada::{parameters, token};
+    use namada_sdk::governance::ProposalVote;
 
     use super::*;
     use crate::node::ledger::shell::test_utils;
@@ -2351,6 +2621,7 @@ mod shell_tests {
                     validator_addr: wallet::defaults::validator_address(),
                     block_height: LAST_HEIGHT,
                     ethereum_events: vec![ethereum_event],
+                    bridge_contract: shell.bridge_contract(),
                 }
                 .sign(&protocol_key);
                 assert!(ext.verify(&protocol_key.ref_to()).is_ok());
@@ -2381,6 +2652,7 @@ mod shell_tests {
                     validator_addr: wallet::defaults::validator_address(),
                     block_height: LAST_HEIGHT,
                     ethereum_events: vec![e1, e2],
+                    bridge_contract: shell.bridge_contract(),
                 }
                 .sign(&protocol_key);
                 assert!(ext.verify(&protocol_key.ref_to()).is_ok());
@@ -2419,6 +2691,7 @@ mod shell_tests {
                 validator_addr: address.clone(),
                 block_height: shell.wl_storage.storage.get_last_block_height(),
                 ethereum_events: vec![ethereum_event],
+                bridge_contract: shell.bridge_contract(),
             }
             .sign(protocol_key)
             .into(),
@@ -2432,6 +2705,7 @@ mod shell_tests {
         let bp_vext = EthereumTxData::BridgePoolVext(
             bridge_pool_roots::Vext {
                 block_height: shell.wl_storage.storage.get_last_block_height(),
+                root_height: None,
                 validator_addr: address,
                 sig,
             }
@@ -2472,6 +2746,7 @@ mod shell_tests {
                 validator_addr,
                 block_height: LAST_HEIGHT,
                 ethereum_events: vec![ethereum_event],
+                bridge_contract: shell.bridge_contract(),
             }
             .sign(&protocol_key);
             assert!(ext.verify(&protocol_key.ref_to()).is_ok());
@@ -2505,6 +2780,7 @@ mod shell_tests {
                 validator_addr,
                 block_height: LAST_HEIGHT,
                 ethereum_events: vec![ethereum_event],
+                bridge_contract: shell.bridge_contract(),
             }
             .sign(&protocol_key);
             assert!(ext.verify(&protocol_key.ref_to()).is_ok());
@@ -2798,6 +3074,84 @@ mod shell_tests {
         assert_eq!(result.code, ResultCode::ExpiredTx.into());
     }
 
+    /// Check that the votes on a governance proposal are only rejected from
+    /// the mempool after the voting period of the proposal, with one epoch
+    /// of grace, and only if the filter is enabled
+    #[test]
+    fn test_late_vote_rejected() {
+        let (mut shell, _recv, _, _) = test_utils::setup();
+
+        let keypair = super::test_utils::gen_keypair();
+
+        // The vote txs are recognized by the hash of their code
+        let vote_code = b"tx_vote_proposal".to_vec();
+        shell
+            .wl_storage
+            .write(
+                &Key::wasm_hash(namada_sdk::tx::TX_VOTE_PROPOSAL),
+                Hash::sha256(&vote_code),
+            )
+            .expect("Test failed");
+        // The voting period of the proposal spans up to epoch 2
+        shell
+            .wl_storage
+            .write(&gov_storage::get_voting_end_epoch_key(0), Epoch(3))
+            .expect("Test failed");
+
+        let vote = VoteProposalData {
+            id: 0,
+            vote: ProposalVote::Yay,
+            voter: address::testing::established_address_1(),
+            delegations: vec![],
+        };
+        let mut wrapper =
+            Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+                Fee {
+                    amount_per_gas_unit: DenominatedAmount::native(
+                        token::Amount::from_uint(100, 0)
+                            .expect("This can't fail"),
+                    ),
+                    token: shell.wl_storage.storage.native_token.clone(),
+                },
+                keypair.ref_to(),
+                Epoch(0),
+                GAS_LIMIT_MULTIPLIER.into(),
+                None,
+            ))));
+        wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.set_code(Code::new(vote_code, None));
+        wrapper.set_data(Data::new(vote.serialize_to_vec()));
+        wrapper.add_section(Section::Signature(Signature::new(
+            wrapper.sechashes(),
+            [(0, keypair)].into_iter().collect(),
+            None,
+        )));
+        let late_vote_log = "Mempool validation failed: Vote on governance \
+                             proposal 0 submitted at epoch 4, after the end \
+                             of its voting period at epoch 3";
+
+        // The last block of the voting period, and the following epoch of
+        // grace, may still see the vote accepted by the governance VP
+        for (epoch, late) in [(2, false), (3, false), (4, true)] {
+            shell.wl_storage.storage.last_epoch = Epoch(epoch);
+            let result = shell.mempool_validate(
+                wrapper.to_bytes().as_ref(),
+                MempoolTxType::NewTransaction,
+            );
+            assert_eq!(result.log == late_vote_log, late);
+            if late {
+                assert_eq!(result.code, ResultCode::InvalidTx.into());
+            }
+        }
+
+        shell.reject_late_votes = false;
+        let result = shell.mempool_validate(
+            wrapper.to_bytes().as_ref(),
+            MempoolTxType::NewTransaction,
+        );
+        assert_ne!(result.log, late_vote_log);
+    }
+
     /// Check that a tx requiring more gas than the block limit gets rejected
     #[test]
     fn test_exceeding_max_block_gas_tx() {
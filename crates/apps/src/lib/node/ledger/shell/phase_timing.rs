@@ -0,0 +1,228 @@
+//! Timing instrumentation of the phases of `FinalizeBlock`.
+//!
+//! The duration of each [`Phase`] of block finalization is added both to
+//! the timings of the current block, drained with [`take_block_timings`]
+//! once the block is finalized, and to cumulative totals, exposed as
+//! metrics with [`cumulative_timings`].
+//!
+//! Phases are timed independently of the `tracing` spans wrapping them,
+//! such that the timings are available even when the spans are filtered
+//! out by the subscriber.
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Timings of the current block, since they were last drained.
+static BLOCK_TIMINGS: Mutex<BlockTimings> = Mutex::new(BlockTimings::new());
+
+/// Cumulative timings of each [`Phase`] since the node started, in
+/// microseconds.
+static CUMULATIVE_MICROS: [AtomicU64; Phase::ALL.len()] = [
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+];
+
+/// Number of phases listed in the per-block summary log.
+pub const SUMMARY_LEN: usize = 3;
+
+/// A phase of `FinalizeBlock`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Tallying the votes of the governance proposals.
+    ProposalTally,
+    /// Executing the governance proposals that passed, including the
+    /// wasm code of default proposals.
+    ProposalExecution,
+    /// Updates of the epoched PoS data, such as the validator sets,
+    /// slashes, inflation and liveness data.
+    PosUpdates,
+    /// Application of the protocol txs aggregating the vote extensions
+    /// of the Ethereum bridge.
+    VextAggregation,
+}
+
+impl Phase {
+    /// All the phases of `FinalizeBlock`.
+    pub const ALL: [Phase; 4] = [
+        Phase::ProposalTally,
+        Phase::ProposalExecution,
+        Phase::PosUpdates,
+        Phase::VextAggregation,
+    ];
+
+    /// Return a human readable name of this phase.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Phase::ProposalTally => "proposal_tally",
+            Phase::ProposalExecution => "proposal_execution",
+            Phase::PosUpdates => "pos_updates",
+            Phase::VextAggregation => "vext_aggregation",
+        }
+    }
+
+    const fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+/// Time spent in each [`Phase`] of a block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockTimings {
+    durations: [Duration; Phase::ALL.len()],
+}
+
+impl BlockTimings {
+    /// Create a new set of empty timings.
+    pub const fn new() -> Self {
+        Self {
+            durations: [Duration::ZERO; Phase::ALL.len()],
+        }
+    }
+
+    /// Get the time spent in the given phase.
+    pub fn get(&self, phase: Phase) -> Duration {
+        self.durations[phase.index()]
+    }
+
+    /// Return up to `n` phases, from the slowest to the fastest, leaving
+    /// out the phases that weren't run.
+    pub fn slowest(&self, n: usize) -> Vec<(Phase, Duration)> {
+        let mut phases: Vec<_> = Phase::ALL
+            .iter()
+            .map(|&phase| (phase, self.get(phase)))
+            .filter(|(_, duration)| !duration.is_zero())
+            .collect();
+        phases.sort_by(|(_, a), (_, b)| b.cmp(a));
+        phases.truncate(n);
+        phases
+    }
+
+    /// Check if no phase has been timed.
+    pub fn is_empty(&self) -> bool {
+        self.durations.iter().all(Duration::is_zero)
+    }
+}
+
+impl fmt::Display for BlockTimings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (phase, duration)) in
+            self.slowest(SUMMARY_LEN).into_iter().enumerate()
+        {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}: {:?}", phase.as_str(), duration)?;
+        }
+        Ok(())
+    }
+}
+
+/// Guard timing a [`Phase`], from its creation until it is dropped.
+#[must_use]
+pub struct PhaseTimer {
+    phase: Phase,
+    start: Instant,
+}
+
+impl PhaseTimer {
+    /// Start timing the given phase.
+    #[inline]
+    pub fn new(phase: Phase) -> Self {
+        Self {
+            phase,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for PhaseTimer {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+        let index = self.phase.index();
+        BLOCK_TIMINGS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .durations[index] += elapsed;
+        let micros = u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX);
+        CUMULATIVE_MICROS[index].fetch_add(micros, Ordering::Relaxed);
+    }
+}
+
+/// Run `f`, adding its duration to the timings of the given [`Phase`].
+#[inline]
+pub fn record<T>(phase: Phase, f: impl FnOnce() -> T) -> T {
+    let _timer = PhaseTimer::new(phase);
+    f()
+}
+
+/// Drain the timings recorded since the last call to this function.
+pub fn take_block_timings() -> BlockTimings {
+    std::mem::take(
+        &mut *BLOCK_TIMINGS.lock().unwrap_or_else(PoisonError::into_inner),
+    )
+}
+
+/// Get the cumulative time spent in each [`Phase`] since the node started.
+pub fn cumulative_timings() -> [(Phase, Duration); Phase::ALL.len()] {
+    Phase::ALL.map(|phase| {
+        let micros = CUMULATIVE_MICROS[phase.index()].load(Ordering::Relaxed);
+        (phase, Duration::from_micros(micros))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that the slowest phases are listed first, and that phases
+    /// that weren't run are left out.
+    #[test]
+    fn test_slowest_phases() {
+        let mut timings = BlockTimings::new();
+        timings.durations[Phase::ProposalTally.index()] =
+            Duration::from_millis(5);
+        timings.durations[Phase::PosUpdates.index()] =
+            Duration::from_millis(20);
+        timings.durations[Phase::VextAggregation.index()] =
+            Duration::from_millis(1);
+
+        assert_eq!(
+            timings.slowest(2),
+            vec![
+                (Phase::PosUpdates, Duration::from_millis(20)),
+                (Phase::ProposalTally, Duration::from_millis(5)),
+            ]
+        );
+        assert_eq!(timings.slowest(SUMMARY_LEN).len(), 3);
+        assert_eq!(
+            timings.to_string(),
+            "pos_updates: 20ms, proposal_tally: 5ms, vext_aggregation: 1ms"
+        );
+    }
+
+    /// Test that recorded timings are added to the cumulative timings.
+    #[test]
+    fn test_record_timings() {
+        let cumulative_pre = cumulative_timings();
+        let value = record(Phase::ProposalExecution, || {
+            std::thread::sleep(Duration::from_millis(2));
+            1
+        });
+        assert_eq!(value, 1);
+
+        // other tests may finalize blocks concurrently, and drain the block
+        // timings, hence only the cumulative timings are checked
+        let (phase, cumulative_post) =
+            cumulative_timings()[Phase::ProposalExecution.index()];
+        assert_eq!(phase, Phase::ProposalExecution);
+        assert!(
+            cumulative_post
+                >= cumulative_pre[Phase::ProposalExecution.index()].1
+                    + Duration::from_millis(2)
+        );
+    }
+}
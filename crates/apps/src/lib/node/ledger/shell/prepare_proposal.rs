@@ -1,8 +1,12 @@
 //! Implementation of the [`RequestPrepareProposal`] ABCI++ method for the Shell
 
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+
 use masp_primitives::transaction::Transaction;
 use namada::core::hints;
 use namada::gas::TxGasMeter;
+use namada::ledger::pos::PosQueries;
 use namada::ledger::protocol;
 use namada::ledger::storage::tx_queue::TxInQueue;
 use namada::proof_of_stake::storage::find_validator_by_raw_hash;
@@ -11,9 +15,13 @@ use namada::tx::data::{DecryptedTx, TxType, WrapperTx};
 use namada::tx::Tx;
 use namada::types::address::Address;
 use namada::types::key::tm_raw_hash_to_string;
+use namada::types::storage::{BlockHeight, Epoch};
 use namada::types::time::DateTimeUtc;
 use namada::vm::wasm::{TxCache, VpCache};
 use namada::vm::WasmCacheAccess;
+use namada::vote_ext::EthereumTxData;
+use namada_sdk::eth_bridge::EthBridgeQueries;
+use thiserror::Error;
 
 use super::super::*;
 use super::block_alloc::states::{
@@ -27,6 +35,48 @@ use crate::facade::tendermint_proto::v0_37::abci::RequestPrepareProposal;
 use crate::node::ledger::shell::ShellMode;
 use crate::node::ledger::shims::abcipp_shim_types::shim::{response, TxBytes};
 
+/// Number of block proposals whose mandatory vote extensions alone
+/// exceeded the vote extension budget of the block.
+static OVERSIZED_VEXT_PROPOSALS: AtomicU64 = AtomicU64::new(0);
+
+/// Get the number of block proposals built by this node, whose mandatory
+/// vote extensions alone exceeded the vote extension budget of the block.
+pub fn oversized_vext_proposals() -> u64 {
+    OVERSIZED_VEXT_PROPOSALS.load(Ordering::Relaxed)
+}
+
+/// Error returned when the vote extensions that must be included in a
+/// block proposal exceed the vote extension budget of the block.
+#[derive(Debug, Error)]
+#[error(
+    "The mandatory vote extensions take up {mandatory_bytes} bytes, exceeding \
+     the budget of {budget} bytes"
+)]
+struct VextBudgetExceeded {
+    /// The mandatory vote extension protocol txs.
+    mandatory: Vec<TxBytes>,
+    /// The total size of the mandatory vote extensions, in bytes.
+    mandatory_bytes: u64,
+    /// The vote extension budget of the block, in bytes.
+    budget: u64,
+}
+
+/// Priority of a vote extension signed before the last block height, or
+/// the last epoch for validator set updates.
+///
+/// These extensions only help other validators catch up on past votes,
+/// hence they are trimmed from a proposal before any mandatory extension,
+/// in decreasing order of priority. Lower values have a higher priority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum CatchUpPriority {
+    /// A validator set update, signed the given number of epochs ago.
+    ValsetUpd { epochs_behind: u64 },
+    /// A Bridge pool root, signed the given number of blocks ago.
+    BridgePoolRoot { blocks_behind: u64 },
+    /// Ethereum events, voted on the given number of blocks ago.
+    EthEvents { blocks_behind: u64 },
+}
+
 impl<D, H> Shell<D, H>
 where
     D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
@@ -49,7 +99,25 @@ where
         } = self.mode
         {
             // start counting allotted space for txs
-            let alloc = self.get_encrypted_txs_allocator();
+            let mut alloc = self.get_encrypted_txs_allocator();
+
+            // reserve space for the vote extensions before
+            // filling the block with txs from the mempool
+            let vexts = match self.select_vote_extensions(&req.txs) {
+                Ok(vexts) => {
+                    alloc.reserve_protocol_space(total_bytes(&vexts));
+                    vexts
+                }
+                Err(err) => {
+                    OVERSIZED_VEXT_PROPOSALS.fetch_add(1, Ordering::Relaxed);
+                    tracing::error!(
+                        proposal_height = ?self.get_current_decision_height(),
+                        "{err}. The vote extensions that don't fit in the \
+                         block will be dropped from the proposal."
+                    );
+                    err.mandatory
+                }
+            };
 
             // add encrypted txs
             let tm_raw_hash_string =
@@ -76,9 +144,13 @@ where
             txs.append(&mut decrypted_txs);
 
             // add vote extension protocol txs
-            let mut protocol_txs = self.build_protocol_txs(alloc, &req.txs);
+            let mut protocol_txs = self.build_protocol_txs(alloc, vexts);
             txs.append(&mut protocol_txs);
 
+            if let Some(local_config) = local_config {
+                self.check_proposal_execution_budget(local_config);
+            }
+
             txs
         } else {
             vec![]
@@ -93,6 +165,92 @@ where
         response::PrepareProposal { txs }
     }
 
+    /// Estimate the execution time of the wasm code of the governance
+    /// proposals concluded in the proposed block, as the number of proposals
+    /// times the maximum block gas, converted to seconds with the gas per
+    /// second calibrated in the local config of the validator. The proposals
+    /// concluded in the block are fixed by the maximum number of proposals
+    /// per block, hence a warning is logged if the estimate exceeds the
+    /// configured maximum, such that the operator may have it lowered.
+    fn check_proposal_execution_budget(
+        &self,
+        local_config: &ValidatorLocalConfig,
+    ) {
+        let (Some(gas_per_second), Some(max_secs)) = (
+            local_config.proposal_gas_per_second,
+            local_config.max_proposal_execution_secs,
+        ) else {
+            return;
+        };
+        let due = match self.proposals_due_in_next_block() {
+            Ok(due) => due,
+            Err(err) => {
+                tracing::error!(
+                    "Failed to read the governance proposals due in the next \
+                     block: {err}"
+                );
+                return;
+            }
+        };
+        if due.is_empty() {
+            return;
+        }
+        let (Ok(max_proposals_per_block), Ok(max_block_gas)) = (
+            namada::governance::storage::get_max_proposals_per_block(
+                &self.wl_storage,
+            ),
+            namada::parameters::get_max_block_gas(&self.wl_storage),
+        ) else {
+            return;
+        };
+        let (concluded, deferred) = super::governance::split_due_proposals(
+            super::governance::order_by_priority(&self.wl_storage, due),
+            max_proposals_per_block,
+        );
+        let estimated_secs = estimate_proposal_execution_secs(
+            concluded.len() as u64,
+            max_block_gas,
+            gas_per_second,
+        );
+        tracing::debug!(
+            concluded = concluded.len(),
+            deferred = deferred.len(),
+            "Estimated the execution of the governance proposals concluded in \
+             the proposed block to take up to {estimated_secs}s"
+        );
+        if estimated_secs > max_secs {
+            tracing::warn!(
+                "The governance proposals {:?} concluded in the proposed \
+                 block may take up to {}s to execute, exceeding the maximum \
+                 of {}s of the validator's local config.",
+                concluded,
+                estimated_secs,
+                max_secs
+            );
+        }
+    }
+
+    /// Get the ids of the governance proposals due in the next block, i.e.
+    /// the ones deferred from the last block, and the ones reaching their
+    /// grace epoch if the next block begins a new epoch.
+    fn proposals_due_in_next_block(
+        &self,
+    ) -> namada::state::StorageResult<BTreeSet<u64>> {
+        let mut due = namada::governance::storage::get_deferred_proposals(
+            &self.wl_storage,
+        )?;
+        if matches!(self.wl_storage.storage.update_epoch_blocks_delay, Some(1))
+        {
+            due.extend(
+                namada::governance::storage::get_proposals_committing_at_epoch(
+                    &self.wl_storage,
+                    self.wl_storage.storage.last_epoch,
+                )?,
+            );
+        }
+        Ok(due)
+    }
+
     /// Depending on the current block height offset within the epoch,
     /// transition state accordingly, return a block space allocator
     /// with or without encrypted txs.
@@ -257,24 +415,59 @@ where
         (txs, alloc)
     }
 
-    /// Builds a batch of protocol transactions.
-    fn build_protocol_txs(
+    /// Select the vote extension protocol txs to propose, among the
+    /// ones in `txs`.
+    ///
+    /// Vote extensions signed at the last block height, or at the last
+    /// epoch for validator set updates, are mandatory. The remaining ones
+    /// are included as long as the total size of the extensions stays
+    /// within the budget of the block, in order of [`CatchUpPriority`].
+    ///
+    /// The budget is the maximum size of the vote extensions set by
+    /// governance, capped at the maximum size of a block proposal. An
+    /// error is returned if the mandatory extensions alone exceed it.
+    fn select_vote_extensions(
         &self,
-        mut alloc: BlockAllocator<BuildingProtocolTxBatch>,
         txs: &[TxBytes],
-    ) -> Vec<TxBytes> {
+    ) -> Result<Vec<TxBytes>, VextBudgetExceeded> {
         if self.wl_storage.storage.last_block.is_none() {
             // genesis should not contain vote extensions.
             //
             // this is because we have not decided any block through
             // consensus yet (hence height 0), which in turn means we
             // have not committed any vote extensions to a block either.
-            return vec![];
+            return Ok(vec![]);
+        }
+
+        let last_height = self.wl_storage.storage.get_last_block_height();
+        let last_epoch = self.wl_storage.storage.last_epoch;
+        let mut mandatory = vec![];
+        let mut catch_up = vec![];
+        for tx_bytes in self.deserialize_vote_extensions(txs) {
+            match catch_up_priority(&tx_bytes, last_height, last_epoch) {
+                Some(priority) => catch_up.push((priority, tx_bytes)),
+                None => mandatory.push(tx_bytes),
+            }
         }
 
-        let deserialized_iter = self.deserialize_vote_extensions(txs);
+        let max_proposal_bytes =
+            self.wl_storage.pos_queries().get_max_proposal_bytes().get();
+        let budget = self
+            .wl_storage
+            .ethbridge_queries()
+            .get_max_vext_digest_bytes()
+            .map_or(max_proposal_bytes, |max| max.min(max_proposal_bytes));
+        select_vexts_within_budget(mandatory, catch_up, budget)
+    }
 
-        deserialized_iter.take_while(|tx_bytes|
+    /// Builds a batch of protocol transactions, out of the
+    /// vote extensions selected for the proposal.
+    fn build_protocol_txs(
+        &self,
+        mut alloc: BlockAllocator<BuildingProtocolTxBatch>,
+        vexts: Vec<TxBytes>,
+    ) -> Vec<TxBytes> {
+        vexts.into_iter().take_while(|tx_bytes|
             alloc.try_alloc(&tx_bytes[..])
                 .map_or_else(
                     |status| match status {
@@ -316,6 +509,98 @@ where
     }
 }
 
+/// Get the [`CatchUpPriority`] of a vote extension protocol tx, or
+/// [`None`] if the extension is mandatory.
+fn catch_up_priority(
+    tx_bytes: &[u8],
+    last_height: BlockHeight,
+    last_epoch: Epoch,
+) -> Option<CatchUpPriority> {
+    let tx = Tx::try_from(tx_bytes).ok()?;
+    match (&tx).try_into().ok()? {
+        EthereumTxData::EthEventsVext(ext) => {
+            let blocks_behind =
+                last_height.0.saturating_sub(ext.data.block_height.0);
+            (blocks_behind > 0)
+                .then_some(CatchUpPriority::EthEvents { blocks_behind })
+        }
+        EthereumTxData::BridgePoolVext(ext) => {
+            let blocks_behind =
+                last_height.0.saturating_sub(ext.data.block_height.0);
+            (blocks_behind > 0)
+                .then_some(CatchUpPriority::BridgePoolRoot { blocks_behind })
+        }
+        EthereumTxData::ValSetUpdateVext(ext) => {
+            let epochs_behind =
+                last_epoch.0.saturating_sub(ext.data.signing_epoch.0);
+            (epochs_behind > 0)
+                .then_some(CatchUpPriority::ValsetUpd { epochs_behind })
+        }
+        _ => None,
+    }
+}
+
+/// Select the vote extensions to propose, such that their total size
+/// doesn't exceed `budget`.
+///
+/// All the `mandatory` extensions are selected, followed by as many
+/// `catch_up` extensions as fit in the remaining budget, in order of
+/// [`CatchUpPriority`]. Extensions with the same priority are ordered
+/// by their bytes, such that the selection is deterministic.
+fn select_vexts_within_budget(
+    mandatory: Vec<TxBytes>,
+    mut catch_up: Vec<(CatchUpPriority, TxBytes)>,
+    budget: u64,
+) -> Result<Vec<TxBytes>, VextBudgetExceeded> {
+    let mandatory_bytes = total_bytes(&mandatory);
+    if mandatory_bytes > budget {
+        return Err(VextBudgetExceeded {
+            mandatory,
+            mandatory_bytes,
+            budget,
+        });
+    }
+    catch_up.sort();
+    let mut used = mandatory_bytes;
+    let mut selected = mandatory;
+    let mut trimmed = 0usize;
+    for (_, tx_bytes) in catch_up {
+        let size = tx_bytes.len() as u64;
+        if used + size <= budget {
+            used += size;
+            selected.push(tx_bytes);
+        } else {
+            trimmed += 1;
+        }
+    }
+    if trimmed > 0 {
+        tracing::debug!(
+            trimmed,
+            budget,
+            "Trimmed catch-up vote extensions exceeding the vote extension \
+             budget from the current proposal"
+        );
+    }
+    Ok(selected)
+}
+
+/// Return the total size of the given txs, in bytes.
+fn total_bytes(txs: &[TxBytes]) -> u64 {
+    txs.iter().map(|tx| tx.len() as u64).sum()
+}
+
+/// Estimate the execution time, in seconds, of the wasm code of the given
+/// number of governance proposals, each consuming up to `max_gas`.
+fn estimate_proposal_execution_secs(
+    num_proposals: u64,
+    max_gas: u64,
+    gas_per_second: u64,
+) -> u64 {
+    let secs = u128::from(num_proposals) * u128::from(max_gas)
+        / u128::from(gas_per_second.max(1));
+    u64::try_from(secs).unwrap_or(u64::MAX)
+}
+
 // Validity checks on a wrapper tx
 #[allow(clippy::too_many_arguments)]
 fn validate_wrapper_bytes<D, H, CA>(
@@ -431,6 +716,7 @@ mod test_prepare_proposal {
     use std::collections::BTreeSet;
 
     use borsh_ext::BorshSerializeExt;
+    use namada::eth_bridge::storage::parameters::set_max_vext_digest_bytes;
     use namada::ledger::gas::Gas;
     use namada::ledger::pos::PosQueries;
     use namada::ledger::replay_protection;
@@ -542,6 +828,7 @@ mod test_prepare_proposal {
                 validator_addr,
                 block_height: LAST_HEIGHT,
                 ethereum_events: vec![],
+                bridge_contract: shell.bridge_contract(),
             }
             .sign(&protocol_key);
             assert!(ext.verify(&protocol_key.ref_to()).is_ok());
@@ -574,6 +861,7 @@ mod test_prepare_proposal {
                     validator_addr,
                     block_height: height,
                     ethereum_events: vec![],
+                    bridge_contract: shell.bridge_contract(),
                 }
                 .sign(&protocol_key);
                 assert!(ext.verify(&protocol_key.ref_to()).is_ok());
@@ -613,6 +901,7 @@ mod test_prepare_proposal {
                 validator_addr,
                 block_height: LAST_HEIGHT,
                 ethereum_events: vec![],
+                bridge_contract: shell.bridge_contract(),
             }
             .sign(&protocol_key);
             assert!(ext.verify(&protocol_key.ref_to()).is_ok());
@@ -746,6 +1035,7 @@ mod test_prepare_proposal {
                 validator_addr,
                 block_height: LAST_HEIGHT,
                 ethereum_events: vec![ethereum_event],
+                bridge_contract: shell.bridge_contract(),
             }
             .sign(&protocol_key);
             assert!(ext.verify(&protocol_key.ref_to()).is_ok());
@@ -1187,6 +1477,8 @@ mod test_prepare_proposal {
                     namada::types::address::nam(),
                     Amount::from(1),
                 )]),
+                proposal_gas_per_second: None,
+                max_proposal_execution_secs: None,
             });
         }
 
@@ -1293,6 +1585,8 @@ mod test_prepare_proposal {
                     namada::types::address::nam(),
                     Amount::from(100),
                 )]),
+                proposal_gas_per_second: None,
+                max_proposal_execution_secs: None,
             });
         }
 
@@ -1482,6 +1776,7 @@ mod test_prepare_proposal {
                     validator_addr: validator_addr.clone(),
                     block_height: LAST_HEIGHT,
                     ethereum_events: vec![ethereum_event],
+                    bridge_contract: shell.bridge_contract(),
                 }
                 .sign(&protocol_key);
                 assert!(ext.verify(&protocol_key.ref_to()).is_ok());
@@ -1530,6 +1825,7 @@ mod test_prepare_proposal {
                     validator_addr,
                     block_height: LAST_HEIGHT,
                     ethereum_events: vec![event1, event2.clone()],
+                    bridge_contract: shell.bridge_contract(),
                 }
                 .sign(&protocol_key);
                 assert!(ext.verify(&protocol_key.ref_to()).is_ok());
@@ -1562,4 +1858,121 @@ mod test_prepare_proposal {
             assert_eq!(found_event, event2);
         }
     }
+
+    /// Test that catch-up vote extensions are trimmed in order of
+    /// priority when the vote extensions exceed their budget, and that
+    /// the mandatory ones alone must fit in it.
+    #[test]
+    fn test_select_vexts_within_budget() {
+        let tx = |byte: u8, len: usize| TxBytes::from(vec![byte; len]);
+        let mandatory = vec![tx(0, 40), tx(1, 40)];
+        let catch_up = vec![
+            (CatchUpPriority::EthEvents { blocks_behind: 1 }, tx(2, 30)),
+            (CatchUpPriority::ValsetUpd { epochs_behind: 2 }, tx(3, 30)),
+            (
+                CatchUpPriority::BridgePoolRoot { blocks_behind: 1 },
+                tx(4, 10),
+            ),
+            (CatchUpPriority::ValsetUpd { epochs_behind: 1 }, tx(5, 30)),
+        ];
+        let select = |catch_up: &[_], budget| {
+            select_vexts_within_budget(
+                mandatory.clone(),
+                catch_up.to_vec(),
+                budget,
+            )
+        };
+
+        // everything fits
+        let selected = select(&catch_up, 180).expect("Test failed");
+        let mut expected = mandatory.clone();
+        expected.extend([tx(5, 30), tx(3, 30), tx(4, 10), tx(2, 30)]);
+        assert_eq!(selected, expected);
+
+        // the catch-up extensions with the lowest priority are trimmed
+        // first, while smaller ones may still fill the remaining budget
+        expected.pop();
+        assert_eq!(select(&catch_up, 150).expect("Test failed"), expected);
+        expected.remove(3);
+        assert_eq!(select(&catch_up, 120).expect("Test failed"), expected);
+        assert_eq!(select(&catch_up, 80).expect("Test failed"), mandatory);
+
+        // the selection doesn't depend on the order of the extensions
+        let mut reversed = catch_up.clone();
+        reversed.reverse();
+        assert_eq!(
+            select(&reversed, 150).expect("Test failed"),
+            select(&catch_up, 150).expect("Test failed"),
+        );
+
+        // the mandatory extensions alone exceed the budget
+        let err = select(&catch_up, 79).expect_err("Test failed");
+        assert_eq!(err.mandatory, mandatory);
+        assert_eq!(err.mandatory_bytes, 80);
+        assert_eq!(err.budget, 79);
+    }
+
+    /// Test that the vote extensions included in a proposal shrink to
+    /// the budget set by governance, trimming the extensions signed at
+    /// past heights first, and that a proposal whose mandatory extensions
+    /// exceed the budget is flagged.
+    #[test]
+    fn test_prepare_proposal_vext_budget() {
+        const LAST_HEIGHT: BlockHeight = BlockHeight(3);
+
+        let (mut shell, _recv, _, _) = test_utils::setup_at_height(LAST_HEIGHT);
+        let (protocol_key, _) = wallet::defaults::validator_keys();
+        let validator_addr = wallet::defaults::validator_address();
+        let vext_tx = |block_height: BlockHeight| -> TxBytes {
+            let ext = ethereum_events::Vext {
+                validator_addr: validator_addr.clone(),
+                block_height,
+                ethereum_events: vec![EthereumEvent::TransfersToNamada {
+                    nonce: 0u64.into(),
+                    transfers: vec![],
+                }],
+                bridge_contract: shell.bridge_contract(),
+            }
+            .sign(&protocol_key);
+            EthereumTxData::EthEventsVext(ext.into())
+                .sign(&protocol_key, shell.chain_id.clone())
+                .to_bytes()
+                .into()
+        };
+        let mandatory = vext_tx(LAST_HEIGHT);
+        let behind_one = vext_tx(LAST_HEIGHT.prev_height());
+        let behind_two = vext_tx(BlockHeight(LAST_HEIGHT.0 - 2));
+        let req = || RequestPrepareProposal {
+            txs: vec![
+                behind_two.clone(),
+                mandatory.clone(),
+                behind_one.clone(),
+            ],
+            ..Default::default()
+        };
+
+        // without a budget, all the extensions are proposed
+        assert_eq!(shell.prepare_proposal(req()).txs.len(), 3);
+
+        // only the most recent catch-up extension fits in the budget
+        set_max_vext_digest_bytes(
+            &mut shell.wl_storage,
+            (mandatory.len() + behind_one.len()) as u64,
+        )
+        .expect("Test failed");
+        assert_eq!(
+            shell.prepare_proposal(req()).txs,
+            vec![mandatory.clone(), behind_one.clone()]
+        );
+
+        // the mandatory extensions alone exceed the budget
+        set_max_vext_digest_bytes(
+            &mut shell.wl_storage,
+            mandatory.len() as u64 - 1,
+        )
+        .expect("Test failed");
+        let oversized = oversized_vext_proposals();
+        assert_eq!(shell.prepare_proposal(req()).txs, vec![mandatory]);
+        assert!(oversized_vext_proposals() > oversized);
+    }
 }
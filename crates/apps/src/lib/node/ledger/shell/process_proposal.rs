@@ -3,9 +3,11 @@
 
 use data_encoding::HEXUPPER;
 use namada::core::hints;
+use namada::ethereum_bridge::protocol::validation::batch::ValidatorDetailsCache;
 use namada::ethereum_bridge::protocol::validation::bridge_pool_roots::validate_bp_roots_vext;
-use namada::ethereum_bridge::protocol::validation::ethereum_events::validate_eth_events_vext;
-use namada::ethereum_bridge::protocol::validation::validator_set_update::validate_valset_upd_vext;
+use namada::ethereum_bridge::protocol::validation::contract_addresses::validate_contract_addresses_vext_with_cache;
+use namada::ethereum_bridge::protocol::validation::ethereum_events::validate_eth_events_vext_with_cache;
+use namada::ethereum_bridge::protocol::validation::validator_set_update::validate_valset_upd_vext_with_cache;
 use namada::ledger::pos::PosQueries;
 use namada::ledger::protocol::get_fee_unshielding_transaction;
 use namada::ledger::storage::tx_queue::TxInQueue;
@@ -16,6 +18,7 @@ use namada::tx::data::protocol::ProtocolTxType;
 use namada::vote_ext::ethereum_tx_data_variants;
 
 use super::block_alloc::{BlockSpace, EncryptedTxsBins};
+use super::governance::find_malformed_concluding_proposals;
 use super::*;
 use crate::facade::tendermint_proto::v0_37::abci::RequestProcessProposal;
 use crate::node::ledger::shell::block_alloc::{AllocFailure, TxBin};
@@ -39,6 +42,9 @@ pub struct ValidationMeta {
     pub decrypted_queue_has_remaining_txs: bool,
     /// Check if a block has decrypted txs.
     pub has_decrypted_txs: bool,
+    /// The details of the consensus validators looked up while validating
+    /// the vote extensions of a block, read once per epoch.
+    pub validators: ValidatorDetailsCache,
 }
 
 impl<D, H> From<&WlStorage<D, H>> for ValidationMeta
@@ -59,6 +65,7 @@ where
             has_decrypted_txs: false,
             encrypted_txs_bins: encrypted_txs_bin,
             txs_bin,
+            validators: ValidatorDetailsCache::default(),
         }
     }
 }
@@ -142,6 +149,34 @@ where
             );
         }
 
+        // Governance proposals concluding in the first block of an epoch
+        // must be readable. Malformed proposals don't make the block invalid,
+        // as they are deterministically rejected in FinalizeBlock.
+        let is_new_epoch_block = matches!(
+            self.wl_storage.storage.update_epoch_blocks_delay,
+            Some(1)
+        );
+        if is_new_epoch_block {
+            match find_malformed_concluding_proposals(&self.wl_storage) {
+                Ok(malformed) => {
+                    for (id, field) in malformed {
+                        tracing::warn!(
+                            proposal_id = id,
+                            field,
+                            height = req.height,
+                            "Governance proposal concluding in the proposed \
+                             block is malformed, and will be rejected"
+                        );
+                    }
+                }
+                Err(err) => tracing::error!(
+                    height = req.height,
+                    "Failed to check the governance proposals concluding in \
+                     the proposed block: {err}"
+                ),
+            }
+        }
+
         let will_reject_proposal = invalid_txs || has_remaining_decrypted_txs;
         (
             if will_reject_proposal {
@@ -349,12 +384,13 @@ where
                         ethereum_tx_data_variants::EthEventsVext::try_from(&tx)
                             .map_err(|err| err.to_string())
                             .and_then(|ext| {
-                                validate_eth_events_vext(
+                                validate_eth_events_vext_with_cache(
                                     &self.wl_storage,
                                     &ext.0,
                                     self.wl_storage
                                         .storage
                                         .get_last_block_height(),
+                                    &mut metadata.validators,
                                 )
                                 .map(|_| TxResult {
                                     code: ResultCode::Ok.into(),
@@ -407,7 +443,7 @@ where
                         )
                         .map_err(|err| err.to_string())
                         .and_then(|ext| {
-                            validate_valset_upd_vext(
+                            validate_valset_upd_vext_with_cache(
                                 &self.wl_storage,
                                 &ext,
                                 // n.b. only accept validator set updates
@@ -416,6 +452,7 @@ where
                                 // validators
                                 // of the next epoch)
                                 self.wl_storage.storage.get_current_epoch().0,
+                                &mut metadata.validators,
                             )
                             .map(|_| TxResult {
                                 code: ResultCode::Ok.into(),
@@ -437,6 +474,35 @@ where
                             }
                         })
                     }
+                    ProtocolTxType::ContractAddressesVext => {
+                        ethereum_tx_data_variants::ContractAddressesVext::try_from(
+                            &tx,
+                        )
+                        .map_err(|err| err.to_string())
+                        .and_then(|ext| {
+                            validate_contract_addresses_vext_with_cache(
+                                &self.wl_storage,
+                                &ext,
+                                self.wl_storage.storage.get_current_epoch().0,
+                                &mut metadata.validators,
+                            )
+                            .map(|_| TxResult {
+                                code: ResultCode::Ok.into(),
+                                info: "Process Proposal accepted this \
+                                       transaction"
+                                    .into(),
+                            })
+                            .map_err(|err| err.to_string())
+                        })
+                        .unwrap_or_else(|err| TxResult {
+                            code: ResultCode::InvalidVoteExtension.into(),
+                            info: format!(
+                                "Process proposal rejected this proposal \
+                                 because one of the included contract \
+                                 addresses vote extensions was invalid: {err}"
+                            ),
+                        })
+                    }
                     ProtocolTxType::EthereumEvents
                     | ProtocolTxType::BridgePool
                     | ProtocolTxType::ValidatorSetUpdate => TxResult {
@@ -708,6 +774,7 @@ mod test_process_proposal {
             validator_addr: addr.clone(),
             block_height: shell.wl_storage.storage.get_last_block_height(),
             ethereum_events: vec![event],
+            bridge_contract: shell.bridge_contract(),
         }
         .sign(protocol_key);
         let tx = EthereumTxData::EthEventsVext(ext.into())
@@ -757,6 +824,7 @@ mod test_process_proposal {
         .sig;
         let vote_ext = bridge_pool_roots::Vext {
             block_height: shell.wl_storage.storage.get_last_block_height(),
+            root_height: None,
             validator_addr: addr.clone(),
             sig,
         }
@@ -836,6 +904,7 @@ mod test_process_proposal {
                 validator_addr: addr.clone(),
                 block_height: LAST_HEIGHT,
                 ethereum_events: vec![event.clone()],
+                bridge_contract: shell.bridge_contract(),
             }
             .sign(&protocol_key);
             assert!(ext.verify(&protocol_key.ref_to()).is_ok());
@@ -866,6 +935,7 @@ mod test_process_proposal {
                 validator_addr: addr.clone(),
                 block_height: INVALID_HEIGHT,
                 ethereum_events: vec![event.clone()],
+                bridge_contract: shell.bridge_contract(),
             }
             .sign(&protocol_key);
             assert!(ext.verify(&protocol_key.ref_to()).is_ok());
@@ -895,6 +965,7 @@ mod test_process_proposal {
                 validator_addr: addr.clone(),
                 block_height: LAST_HEIGHT,
                 ethereum_events: vec![event.clone()],
+                bridge_contract: shell.bridge_contract(),
             }
             .sign(&protocol_key);
             assert!(ext.verify(&protocol_key.ref_to()).is_ok());
@@ -1654,9 +1725,13 @@ mod test_process_proposal {
         let protocol_tx = EthereumTxData::EthEventsVext({
             let bertha_key = wallet::defaults::bertha_keypair();
             let bertha_addr = wallet::defaults::bertha_address();
-            ethereum_events::Vext::empty(1234_u64.into(), bertha_addr)
-                .sign(&bertha_key)
-                .into()
+            ethereum_events::Vext::empty(
+                1234_u64.into(),
+                bertha_addr,
+                shell.bridge_contract(),
+            )
+            .sign(&bertha_key)
+            .into()
         })
         .sign(protocol_key, wrong_chain_id.clone());
 
@@ -2203,6 +2278,7 @@ mod test_process_proposal {
                     validator_addr: wallet::defaults::validator_address(),
                     block_height: LAST_HEIGHT,
                     ethereum_events: vec![ethereum_event],
+                    bridge_contract: shell.bridge_contract(),
                 }
                 .sign(&protocol_key);
                 assert!(ext.verify(&protocol_key.ref_to()).is_ok());
@@ -2231,6 +2307,7 @@ mod test_process_proposal {
                     validator_addr: wallet::defaults::validator_address(),
                     block_height: LAST_HEIGHT,
                     ethereum_events: vec![e1, e2],
+                    bridge_contract: shell.bridge_contract(),
                 }
                 .sign(&protocol_key);
                 assert!(ext.verify(&protocol_key.ref_to()).is_ok());
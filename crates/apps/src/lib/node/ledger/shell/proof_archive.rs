@@ -0,0 +1,602 @@
+//! Archival of the Ethereum bridge proofs completed on chain.
+//!
+//! Once a validator set update or Bridge pool root proof is complete, the
+//! node serializes it, along with the epoch or nonce it pertains to and the
+//! validators who contributed to it, as an [`ArchivedProof`]. Proofs are
+//! handed to a [`ProofSink`] over a bounded queue, and written to it in a
+//! background thread, such that a slow sink never stalls block execution.
+//! Proofs that don't fit in the queue are dropped and counted.
+//!
+//! Archived proofs can be checked offline, against the Ethereum addresses
+//! of the validators recorded in them, with `namadar
+//! verify-archived-proof`.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::thread::{self, JoinHandle};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use borsh_ext::BorshSerializeExt;
+use namada::eth_bridge::protocol::transactions::votes::Votes;
+use namada::eth_bridge::storage::proof::EthereumProof;
+use namada::eth_bridge::storage::vote_tallies::{self, BridgePoolRoot};
+use namada::state::{DBIter, StorageHasher, StorageRead, WlStorage, DB};
+use namada::types::chain::ChainId;
+use namada::types::ethereum_events::Uint;
+use namada::types::keccak::KeccakHash;
+use namada::types::key::{secp256k1, Signable, SignableEthMessage};
+use namada::types::storage::{BlockHeight, Epoch};
+use namada::vote_ext::bridge_pool_roots::bp_root_digest;
+use namada::vote_ext::validator_set_update::{
+    compute_domain_separated_digest, compute_signable_digest, EthAddrBook,
+    VotingPowersMap,
+};
+use namada_sdk::eth_bridge::EthBridgeQueries;
+
+use crate::config::proof_archive::Config;
+
+/// The maximum number of proofs waiting to be written to the sink.
+pub const QUEUE_CAPACITY: usize = 64;
+
+/// The data signed over in an [`ArchivedProof`].
+#[derive(Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub enum ArchivedProofKind {
+    /// A proof of the validator set update to the given epoch, signed by
+    /// the validators of the epoch before it.
+    ValidatorSetUpdate {
+        /// The epoch of the new validator set.
+        epoch: Epoch,
+        /// The voting powers of the new validator set.
+        voting_powers: VotingPowersMap,
+        /// The chain the signatures are separated to, if any.
+        domain: Option<ChainId>,
+    },
+    /// A proof of the Bridge pool root with the given nonce.
+    BridgePoolRoot {
+        /// The signed Bridge pool root.
+        root: KeccakHash,
+        /// The nonce of the Bridge pool.
+        nonce: Uint,
+        /// The height at which the root was computed.
+        root_height: BlockHeight,
+        /// Whether the root height is bound to the signatures.
+        height_bound: bool,
+    },
+}
+
+/// A complete Ethereum bridge proof, as written to a [`ProofSink`].
+#[derive(Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct ArchivedProof {
+    /// The chain the proof was completed on.
+    pub chain_id: ChainId,
+    /// The height of the block in which the proof was archived.
+    pub height: BlockHeight,
+    /// The data signed over.
+    pub kind: ArchivedProofKind,
+    /// The signatures of the proof, by the Ethereum addresses of the
+    /// signing validators.
+    pub signatures: HashMap<EthAddrBook, secp256k1::Signature>,
+    /// The validators who contributed to the proof, along with the height
+    /// of their vote.
+    pub validators: Votes,
+}
+
+/// The outcome of the verification of the signatures of an
+/// [`ArchivedProof`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProofVerification {
+    /// The address books of the valid signatures, in ascending order.
+    pub valid: Vec<EthAddrBook>,
+    /// The address books of the invalid signatures, in ascending order.
+    pub invalid: Vec<EthAddrBook>,
+}
+
+impl ProofVerification {
+    /// Check if the proof holds at least one signature, and all of its
+    /// signatures are valid.
+    pub fn is_valid(&self) -> bool {
+        !self.valid.is_empty() && self.invalid.is_empty()
+    }
+}
+
+impl ArchivedProof {
+    /// The name of the file of this proof in a [`DirSink`]. A proof is
+    /// always archived under the same name, such that archiving it again
+    /// overwrites the previous copy.
+    pub fn file_name(&self) -> String {
+        match &self.kind {
+            ArchivedProofKind::ValidatorSetUpdate { epoch, .. } => {
+                format!("valset-upd-{}.proof", epoch.0)
+            }
+            ArchivedProofKind::BridgePoolRoot { nonce, .. } => {
+                format!("bp-root-{nonce}.proof")
+            }
+        }
+    }
+
+    /// Compute the digest signed by the Ethereum hot keys of the
+    /// validators, prefixed with the Ethereum signed message header.
+    pub fn signed_digest(&self) -> KeccakHash {
+        match &self.kind {
+            ArchivedProofKind::ValidatorSetUpdate {
+                epoch,
+                voting_powers,
+                domain,
+            } => {
+                let signing_epoch = epoch.prev();
+                match domain {
+                    Some(chain_id) => compute_domain_separated_digest(
+                        voting_powers,
+                        signing_epoch,
+                        chain_id,
+                    ),
+                    None => {
+                        compute_signable_digest(voting_powers, signing_epoch)
+                    }
+                }
+            }
+            ArchivedProofKind::BridgePoolRoot {
+                root,
+                nonce,
+                root_height,
+                height_bound,
+            } => SignableEthMessage::as_signable(&bp_root_digest(
+                root,
+                *nonce,
+                height_bound.then_some(*root_height),
+            )),
+        }
+    }
+
+    /// Verify the signatures of this proof, against the Ethereum hot key
+    /// addresses they are recorded under.
+    pub fn verify(&self) -> ProofVerification {
+        let digest = self.signed_digest();
+        let mut verification = ProofVerification::default();
+        for (addr_book, sig) in &self.signatures {
+            match sig.recover_eth_address(&digest.0) {
+                Ok(addr) if addr == addr_book.hot_key_addr => {
+                    verification.valid.push(addr_book.clone())
+                }
+                _ => verification.invalid.push(addr_book.clone()),
+            }
+        }
+        verification.valid.sort();
+        verification.invalid.sort();
+        verification
+    }
+}
+
+/// Read an [`ArchivedProof`] from the file at the given path.
+pub fn read_archived_proof(path: &Path) -> std::io::Result<ArchivedProof> {
+    let bytes = fs::read(path)?;
+    ArchivedProof::try_from_slice(&bytes)
+}
+
+/// An external store of archived proofs. Implement this trait to archive
+/// proofs to other backends, e.g. object storage or a webhook.
+pub trait ProofSink: Send + 'static {
+    /// Write the given proof to this sink. This runs in the background
+    /// thread of the archiver, hence it may block.
+    fn archive(&mut self, proof: &ArchivedProof) -> std::io::Result<()>;
+}
+
+/// A [`ProofSink`] writing each proof to its own file in a directory.
+#[derive(Debug, Clone)]
+pub struct DirSink {
+    path: PathBuf,
+}
+
+impl DirSink {
+    /// Create a sink writing to the directory at the given path.
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl ProofSink for DirSink {
+    fn archive(&mut self, proof: &ArchivedProof) -> std::io::Result<()> {
+        fs::create_dir_all(&self.path)?;
+        // write to a temporary file first, such that a proof file is never
+        // left half written
+        let file_path = self.path.join(proof.file_name());
+        let tmp_path = file_path.with_extension("tmp");
+        fs::write(&tmp_path, proof.serialize_to_vec())?;
+        fs::rename(tmp_path, file_path)
+    }
+}
+
+/// The queue of proofs to write, and the thread writing them to a sink.
+#[derive(Debug)]
+struct Queue {
+    sender: SyncSender<ArchivedProof>,
+    writer: JoinHandle<()>,
+}
+
+/// Archives the Ethereum bridge proofs completed on chain to a sink, if
+/// configured.
+#[derive(Debug, Default)]
+pub struct ProofArchiver {
+    /// The queue of proofs to write, if a sink is configured.
+    queue: Option<Queue>,
+    /// The number of proofs dropped since the node started, as the queue
+    /// was full.
+    dropped_proofs: u64,
+    /// The latest epoch whose validator set update proof was archived.
+    last_valset_upd_epoch: Option<Epoch>,
+    /// The nonce of the latest Bridge pool root proof archived.
+    last_bp_root_nonce: Option<Uint>,
+}
+
+impl ProofArchiver {
+    /// Create a new archiver from the node's config. No proofs are
+    /// archived without a configured sink.
+    pub fn new(config: Option<&Config>) -> Self {
+        match config {
+            Some(Config::Dir { path }) => {
+                Self::with_sink(DirSink::new(path.clone()), QUEUE_CAPACITY)
+            }
+            None => Self::default(),
+        }
+    }
+
+    /// Create a new archiver writing to the given sink, with at most
+    /// `capacity` proofs waiting to be written.
+    pub fn with_sink(mut sink: impl ProofSink, capacity: usize) -> Self {
+        let (sender, receiver) = mpsc::sync_channel::<ArchivedProof>(capacity);
+        let writer = thread::Builder::new()
+            .name("proof-archive".into())
+            .spawn(move || {
+                for proof in receiver {
+                    if let Err(err) = sink.archive(&proof) {
+                        tracing::error!(
+                            file_name = %proof.file_name(),
+                            "Failed to archive an Ethereum bridge proof: {err}"
+                        );
+                    }
+                }
+            })
+            .expect("Spawning the proof archive thread should not fail");
+        Self {
+            queue: Some(Queue { sender, writer }),
+            ..Self::default()
+        }
+    }
+
+    /// Get the number of proofs dropped since the node started.
+    pub fn dropped_proofs(&self) -> u64 {
+        self.dropped_proofs
+    }
+
+    /// Stop accepting proofs, and wait until the proofs in the queue are
+    /// written to the sink.
+    pub fn shutdown(&mut self) {
+        if let Some(Queue { sender, writer }) = self.queue.take() {
+            drop(sender);
+            if writer.join().is_err() {
+                tracing::error!("The proof archive thread panicked");
+            }
+        }
+    }
+
+    /// Archive the validator set update and Bridge pool root proofs that
+    /// were completed since the last call, as of the block about to be
+    /// committed at the given height.
+    ///
+    /// Since the archiver doesn't persist the proofs it archived, the
+    /// latest proofs are archived again once the node restarts, which
+    /// overwrites their previous copy.
+    pub fn archive_completed_proofs<D, H>(
+        &mut self,
+        wl_storage: &WlStorage<D, H>,
+        height: BlockHeight,
+    ) where
+        D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+        H: StorageHasher + Sync + 'static,
+    {
+        if self.queue.is_none() {
+            return;
+        }
+        let (current_epoch, _) = wl_storage.storage.get_current_epoch();
+        for epoch in [current_epoch, current_epoch.next()] {
+            if epoch.0 == 0 || Some(epoch) <= self.last_valset_upd_epoch {
+                continue;
+            }
+            match read_valset_upd_proof(wl_storage, epoch, height) {
+                Ok(Some(proof)) => {
+                    self.last_valset_upd_epoch = Some(epoch);
+                    self.enqueue(proof);
+                }
+                Ok(None) => {}
+                Err(err) => tracing::error!(
+                    %epoch,
+                    "Failed to read a validator set update proof to \
+                     archive: {err}"
+                ),
+            }
+        }
+        match read_bp_root_proof(wl_storage, height) {
+            Ok(Some(proof)) => {
+                let ArchivedProofKind::BridgePoolRoot { nonce, .. } =
+                    proof.kind
+                else {
+                    unreachable!("We read a Bridge pool root proof")
+                };
+                if Some(nonce) != self.last_bp_root_nonce {
+                    self.last_bp_root_nonce = Some(nonce);
+                    self.enqueue(proof);
+                }
+            }
+            Ok(None) => {}
+            Err(err) => tracing::error!(
+                "Failed to read a Bridge pool root proof to archive: {err}"
+            ),
+        }
+    }
+
+    /// Hand a proof to the writer thread, or drop it if the queue is full.
+    fn enqueue(&mut self, proof: ArchivedProof) {
+        let Some(queue) = &self.queue else {
+            return;
+        };
+        let file_name = proof.file_name();
+        match queue.sender.try_send(proof) {
+            Ok(()) => {
+                tracing::debug!(%file_name, "Queued an Ethereum bridge proof")
+            }
+            Err(err) => {
+                self.dropped_proofs += 1;
+                let reason = match err {
+                    TrySendError::Full(_) => "the archive queue is full",
+                    TrySendError::Disconnected(_) => {
+                        "the archive thread stopped"
+                    }
+                };
+                tracing::warn!(
+                    %file_name,
+                    "Dropped an Ethereum bridge proof, as {reason}. {} proofs \
+                     were dropped since the node started.",
+                    self.dropped_proofs
+                );
+            }
+        }
+    }
+}
+
+/// Read the validator set update proof to the given epoch, if it is
+/// complete.
+fn read_valset_upd_proof<D, H>(
+    wl_storage: &WlStorage<D, H>,
+    epoch: Epoch,
+    height: BlockHeight,
+) -> namada::state::StorageResult<Option<ArchivedProof>>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    if !wl_storage.ethbridge_queries().valset_upd_seen(epoch) {
+        return Ok(None);
+    }
+    let keys = vote_tallies::Keys::from(&epoch);
+    let Some(proof) =
+        wl_storage.read::<EthereumProof<VotingPowersMap>>(&keys.body())?
+    else {
+        return Ok(None);
+    };
+    let validators: Votes =
+        wl_storage.read(&keys.seen_by())?.unwrap_or_default();
+    let domain = wl_storage
+        .ethbridge_queries()
+        .valset_upd_signing_domain(epoch.prev());
+    Ok(Some(ArchivedProof {
+        chain_id: wl_storage.storage.chain_id.clone(),
+        height,
+        kind: ArchivedProofKind::ValidatorSetUpdate {
+            epoch,
+            voting_powers: proof.data,
+            domain,
+        },
+        signatures: proof.signatures,
+        validators,
+    }))
+}
+
+/// Read the latest signed Bridge pool root proof, if any.
+fn read_bp_root_proof<D, H>(
+    wl_storage: &WlStorage<D, H>,
+    height: BlockHeight,
+) -> namada::state::StorageResult<Option<ArchivedProof>>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    let Some((proof, root_height)) =
+        wl_storage.ethbridge_queries().get_signed_bridge_pool_root()
+    else {
+        return Ok(None);
+    };
+    let proof = BridgePoolRoot(proof);
+    let keys = vote_tallies::Keys::from((&proof, root_height));
+    let validators: Votes =
+        wl_storage.read(&keys.seen_by())?.unwrap_or_default();
+    let BridgePoolRoot(EthereumProof {
+        signatures,
+        data: (root, nonce),
+    }) = proof;
+    let height_bound = wl_storage
+        .ethbridge_queries()
+        .is_bp_root_height_bound_at(root_height);
+    Ok(Some(ArchivedProof {
+        chain_id: wl_storage.storage.chain_id.clone(),
+        height,
+        kind: ArchivedProofKind::BridgePoolRoot {
+            root,
+            nonce,
+            root_height,
+            height_bound,
+        },
+        signatures,
+        validators,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::Receiver;
+
+    use assert_matches::assert_matches;
+    use namada::state::StorageWrite;
+    use namada::tendermint_rpc::HttpClient;
+    use namada::vote_ext::validator_set_update;
+
+    use super::*;
+    use crate::cli::api::CliApi;
+    use crate::cli::{args, NamadaRelayer};
+    use crate::node::ledger::shell::test_utils::{self, TestShell};
+    use crate::node::ledger::shell::testing::utils::TestingIo;
+
+    /// Write a complete validator set update proof to the next epoch,
+    /// signed by the validator of the test shell.
+    fn write_valset_upd_proof(shell: &mut TestShell) -> Epoch {
+        let validator_addr =
+            shell.mode.get_validator_address().unwrap().clone();
+        let eth_bridge_key =
+            shell.mode.get_eth_bridge_keypair().expect("Test failed");
+        let signing_epoch = shell.wl_storage.storage.get_current_epoch().0;
+        let next_epoch = signing_epoch.next();
+        let voting_powers: VotingPowersMap = shell
+            .wl_storage
+            .ethbridge_queries()
+            .get_consensus_eth_addresses(Some(next_epoch))
+            .iter()
+            .map(|(eth_addr_book, _, voting_power)| {
+                (eth_addr_book, voting_power)
+            })
+            .collect();
+        let ext = validator_set_update::Vext {
+            voting_powers: voting_powers.clone(),
+            validator_addr: validator_addr.clone(),
+            signing_epoch,
+        }
+        .sign_in_domain(eth_bridge_key, None);
+
+        let mut proof = EthereumProof::new(voting_powers.clone());
+        let addr_book = voting_powers.keys().next().expect("Test failed");
+        proof.attach_signature(addr_book.clone(), ext.sig.clone());
+        let keys = vote_tallies::Keys::from(&next_epoch);
+        shell.wl_storage.write(&keys.body(), proof).unwrap();
+        shell.wl_storage.write(&keys.seen(), true).unwrap();
+        shell
+            .wl_storage
+            .write(
+                &keys.seen_by(),
+                Votes::from([(validator_addr, BlockHeight(1))]),
+            )
+            .unwrap();
+        next_epoch
+    }
+
+    /// Run `namadar verify-archived-proof` on the file at the given path.
+    fn verify_archived_proof(path: &Path) -> color_eyre::eyre::Result<()> {
+        let cmd =
+            NamadaRelayer::VerifyArchivedProof(args::VerifyArchivedProof {
+                path: path.to_owned(),
+            });
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        rt.block_on(CliApi::handle_relayer_command::<HttpClient>(
+            None, cmd, TestingIo,
+        ))
+    }
+
+    /// Test that a completed validator set update proof is archived to the
+    /// directory sink once, and that its signatures verify against the
+    /// archived address book.
+    #[test]
+    fn test_archive_valset_upd_proof_to_dir() {
+        let (mut shell, _recv, _, _) = test_utils::setup_at_height(1);
+        let epoch = write_valset_upd_proof(&mut shell);
+        let dir = tempfile::tempdir().unwrap();
+        let mut archiver = ProofArchiver::new(Some(&Config::Dir {
+            path: dir.path().to_owned(),
+        }));
+
+        archiver.archive_completed_proofs(&shell.wl_storage, BlockHeight(2));
+        // the proof was already archived
+        archiver.archive_completed_proofs(&shell.wl_storage, BlockHeight(3));
+        archiver.shutdown();
+
+        let file_path =
+            dir.path().join(format!("valset-upd-{}.proof", epoch.0));
+        let proof = read_archived_proof(&file_path).expect("Test failed");
+        assert_eq!(proof.height, BlockHeight(2));
+        assert_eq!(proof.chain_id, shell.wl_storage.storage.chain_id);
+        assert_matches!(
+            &proof.kind,
+            ArchivedProofKind::ValidatorSetUpdate { epoch: e, domain: None, .. }
+                if *e == epoch
+        );
+        assert_eq!(proof.validators.len(), 1);
+        let verification = proof.verify();
+        assert!(verification.is_valid());
+        assert_eq!(verification.valid.len(), 1);
+        assert_eq!(archiver.dropped_proofs(), 0);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+        assert!(verify_archived_proof(&file_path).is_ok());
+
+        // a signature over another epoch's validator set doesn't verify
+        let mut tampered = proof;
+        let ArchivedProofKind::ValidatorSetUpdate { epoch, .. } =
+            &mut tampered.kind
+        else {
+            unreachable!()
+        };
+        *epoch = epoch.next();
+        let verification = tampered.verify();
+        assert!(!verification.is_valid());
+        assert_eq!(verification.invalid.len(), 1);
+        let tampered_dir = tempfile::tempdir().unwrap();
+        DirSink::new(tampered_dir.path().to_owned())
+            .archive(&tampered)
+            .unwrap();
+        let file_path = tampered_dir.path().join(tampered.file_name());
+        assert!(verify_archived_proof(&file_path).is_err());
+    }
+
+    /// A sink that blocks until it is signalled.
+    struct BlockingSink(Receiver<()>);
+
+    impl ProofSink for BlockingSink {
+        fn archive(&mut self, _: &ArchivedProof) -> std::io::Result<()> {
+            _ = self.0.recv();
+            Ok(())
+        }
+    }
+
+    /// Test that proofs which don't fit in the queue of the archiver are
+    /// dropped and counted, rather than blocking.
+    #[test]
+    fn test_full_queue_drops_proofs() {
+        let (mut shell, _recv, _, _) = test_utils::setup_at_height(1);
+        write_valset_upd_proof(&mut shell);
+        let proof = read_valset_upd_proof(
+            &shell.wl_storage,
+            shell.wl_storage.storage.get_current_epoch().0.next(),
+            BlockHeight(2),
+        )
+        .unwrap()
+        .expect("Test failed");
+        let (unblock, blocked) = mpsc::channel();
+        let mut archiver = ProofArchiver::with_sink(BlockingSink(blocked), 1);
+
+        // the sink holds at most one proof, and the queue another one
+        for _ in 0..3 {
+            archiver.enqueue(proof.clone());
+        }
+        assert!(archiver.dropped_proofs() >= 1);
+
+        drop(unblock);
+        archiver.shutdown();
+    }
+}
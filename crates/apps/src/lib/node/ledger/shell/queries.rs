@@ -1,7 +1,9 @@
 //! Shell methods for querying state
 
-use namada::ledger::dry_run_tx;
 use namada::ledger::queries::{RequestCtx, ResponseQuery};
+use namada::ledger::{
+    dry_run_tx, proposal_write_surface, write_surface_proposal_id,
+};
 use namada::token;
 use namada::types::address::Address;
 
@@ -24,11 +26,14 @@ where
             vp_wasm_cache: self.vp_wasm_cache.read_only(),
             tx_wasm_cache: self.tx_wasm_cache.read_only(),
             storage_read_past_height_limit: self.storage_read_past_height_limit,
+            enable_valset_preview: self.enable_valset_preview,
         };
 
         // Invoke the root RPC handler - returns borsh-encoded data on success
         let result = if query.path == "/shell/dry_run_tx" {
             dry_run_tx(ctx, &query)
+        } else if let Some(id) = write_surface_proposal_id(&query.path) {
+            proposal_write_surface(ctx, &self.write_surface_cache, id)
         } else {
             namada::ledger::queries::handle_path(ctx, &query)
         };
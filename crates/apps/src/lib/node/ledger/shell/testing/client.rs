@@ -80,6 +80,9 @@ pub fn run(
                 cmds::NamadaRelayer::ValidatorSet(sub_cmd) => {
                     NamadaRelayer::ValidatorSet(sub_cmd)
                 }
+                cmds::NamadaRelayer::VerifyArchivedProof(
+                    cmds::VerifyArchivedProof(args),
+                ) => NamadaRelayer::VerifyArchivedProof(args),
             };
             rt.block_on(CliApi::handle_relayer_command(
                 Some(node),
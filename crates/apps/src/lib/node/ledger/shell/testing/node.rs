@@ -11,11 +11,14 @@ use data_encoding::HEXUPPER;
 use itertools::Either;
 use lazy_static::lazy_static;
 use namada::eth_bridge::oracle::config::Config as OracleConfig;
-use namada::ledger::dry_run_tx;
 use namada::ledger::events::log::dumb_queries;
+use namada::ledger::events::{attributes, Event, EventLevel, EventType};
 use namada::ledger::queries::{
     EncodedResponseQuery, RequestCtx, RequestQuery, Router, RPC,
 };
+use namada::ledger::{
+    dry_run_tx, proposal_write_surface, write_surface_proposal_id,
+};
 use namada::proof_of_stake::pos_queries::PosQueries;
 use namada::proof_of_stake::storage::{
     read_consensus_validator_set_addresses_with_stake,
@@ -27,7 +30,7 @@ use namada::tendermint::abci::response::Info;
 use namada::tendermint::abci::types::VoteInfo;
 use namada::tendermint_rpc::SimpleRequest;
 use namada::types::control_flow::time::Duration;
-use namada::types::ethereum_events::EthereumEvent;
+use namada::types::ethereum_events::{EthAddress, EthereumEvent};
 use namada::types::ethereum_structs;
 use namada::types::hash::Hash;
 use namada::types::key::tm_consensus_key_raw_hash;
@@ -150,8 +153,9 @@ pub struct MockServicesController {
     /// Handler to the Ethereum oracle sender channel.
     ///
     /// Bypasses the Ethereum oracle service and sends
-    /// events directly to the [`Shell`].
-    pub eth_events: mpsc::Sender<EthereumEvent>,
+    /// events directly to the [`Shell`], along with the
+    /// address of the contract they were observed from.
+    pub eth_events: mpsc::Sender<(EthAddress, EthereumEvent)>,
     /// Transaction broadcaster handle.
     pub tx_broadcaster: mpsc::UnboundedSender<Vec<u8>>,
 }
@@ -249,6 +253,11 @@ pub struct MockNode {
     pub keep_temp: bool,
     pub results: Arc<Mutex<Vec<NodeResults>>>,
     pub blocks: Arc<Mutex<HashMap<BlockHeight, block::Response>>>,
+    /// The block-level events emitted at each height
+    pub block_events: Arc<Mutex<HashMap<BlockHeight, Vec<Event>>>>,
+    /// Whether `block_search` finds blocks by their block-level events,
+    /// like a node whose event index is available
+    pub index_block_events: bool,
     pub services: Arc<MockServices>,
     pub auto_drive_services: bool,
 }
@@ -439,6 +448,7 @@ impl MockNode {
         };
 
         let resp = locked.finalize_block(req).expect("Test failed");
+        self.record_block_events(height, &resp.events);
         let mut error_codes = resp
             .events
             .into_iter()
@@ -504,6 +514,19 @@ impl MockNode {
         );
     }
 
+    /// Record the block-level events emitted at the given height, which
+    /// unlike tx events don't carry the height they were emitted at
+    fn record_block_events(&self, height: BlockHeight, events: &[Event]) {
+        let events: Vec<_> = events
+            .iter()
+            .filter(|event| event.level == EventLevel::Block)
+            .cloned()
+            .collect();
+        if !events.is_empty() {
+            self.block_events.lock().unwrap().insert(height, events);
+        }
+    }
+
     /// Advance to a block height that allows
     /// txs
     fn advance_to_allowed_block(&self) {
@@ -583,6 +606,7 @@ impl MockNode {
 
         // process the results
         let resp = locked.finalize_block(req).unwrap();
+        self.record_block_events(height, &resp.events);
         let mut error_codes = resp
             .events
             .into_iter()
@@ -714,9 +738,12 @@ impl<'a> Client for &'a MockNode {
             vp_wasm_cache: borrowed.vp_wasm_cache.read_only(),
             tx_wasm_cache: borrowed.tx_wasm_cache.read_only(),
             storage_read_past_height_limit: None,
+            enable_valset_preview: false,
         };
         if request.path == "/shell/dry_run_tx" {
             dry_run_tx(ctx, &request)
+        } else if let Some(id) = write_surface_proposal_id(&request.path) {
+            proposal_write_surface(ctx, &borrowed.write_surface_cache, id)
         } else {
             rpc.handle(ctx, &request)
         }
@@ -813,21 +840,52 @@ impl<'a> Client for &'a MockNode {
     ) -> Result<tendermint_rpc::endpoint::block_search::Response, RpcError>
     {
         self.drive_mock_services_bg().await;
-        let matcher = parse_tm_query(query);
-        let borrowed = self.shell.lock().unwrap();
-        // we store an index into the event log as a block
-        // height in the response of the query... VERY NAISSSE
-        let matching_events = borrowed.event_log().iter().enumerate().flat_map(
-            |(index, event)| {
-                if matcher.matches(event) {
-                    Some(EncodedEvent(index as u64))
-                } else {
-                    None
+        let heights: Vec<u64> = match parse_proposal_query(&query) {
+            Some(proposal_id) => {
+                if !self.index_block_events {
+                    return Err(RpcError::client_internal(
+                        "Block events are not indexed".to_string(),
+                    ));
                 }
-            },
-        );
-        let blocks = matching_events
-            .map(|encoded_event| namada::tendermint_rpc::endpoint::block::Response {
+                let mut heights: Vec<_> = self
+                    .block_events
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .filter(|(_, events)| {
+                        events.iter().any(|event| {
+                            event.event_type == EventType::Proposal
+                                && event.get(attributes::PROPOSAL_ID)
+                                    == Some(&proposal_id)
+                        })
+                    })
+                    .map(|(height, _)| height.0)
+                    .collect();
+                heights.sort_unstable();
+                heights
+            }
+            None => {
+                let matcher = parse_tm_query(query);
+                let borrowed = self.shell.lock().unwrap();
+                // we store an index into the event log as a block
+                // height in the response of the query... VERY NAISSSE
+                borrowed
+                    .event_log()
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(index, event)| {
+                        if matcher.matches(event) {
+                            Some(index as u64)
+                        } else {
+                            None
+                        }
+                    })
+                    .collect()
+            }
+        };
+        let blocks = heights
+            .into_iter()
+            .map(|height| namada::tendermint_rpc::endpoint::block::Response {
                 block_id: Default::default(),
                 block: namada::tendermint_proto::types::Block {
                     header: Some(namada::tendermint_proto::types::Header {
@@ -836,7 +894,7 @@ impl<'a> Client for &'a MockNode {
                             app: 0,
                         }),
                         chain_id: "Namada".try_into().unwrap(),
-                        height: encoded_event.0 as i64,
+                        height: height as i64,
                         time: None,
                         last_block_id: None,
                         last_commit_hash: vec![],
@@ -853,7 +911,7 @@ impl<'a> Client for &'a MockNode {
                     data: Default::default(),
                     evidence: Default::default(),
                     last_commit: Some(namada::tendermint_proto::types::Commit {
-                        height: encoded_event.0 as i64,
+                        height: height as i64,
                         round: 0,
                         block_id: Some(namada::tendermint_proto::types::BlockId {
                             hash: vec![0u8; 32],
@@ -886,19 +944,28 @@ impl<'a> Client for &'a MockNode {
         let height = height.into();
         let encoded_event = EncodedEvent(height.value());
         let locked = self.shell.lock().unwrap();
+        let block_events = self.block_events.lock().unwrap();
         let events: Vec<_> = locked
             .event_log()
             .iter()
             .flat_map(|event| {
-                if usize::from_str(event.attributes.get("height").unwrap())
-                    .unwrap()
-                    == encoded_event.log_index()
-                {
+                // Block-level events are looked up by height below
+                let event_height = event
+                    .attributes
+                    .get("height")
+                    .map(|height| usize::from_str(height).unwrap());
+                if event_height == Some(encoded_event.log_index()) {
                     Some(event)
                 } else {
                     None
                 }
             })
+            .chain(
+                block_events
+                    .get(&BlockHeight(height.value()))
+                    .into_iter()
+                    .flatten(),
+            )
             .map(|event| namada::tendermint::abci::Event {
                 kind: event.event_type.to_string(),
                 attributes: event
@@ -999,6 +1066,25 @@ fn parse_tm_query(
     }
 }
 
+/// Get the id of the proposal matched by a query built with
+/// [`proposal_concluded`](namada::ledger::events::proposal_concluded), if
+/// that's the kind of the query.
+fn parse_proposal_query(
+    query: &namada::tendermint_rpc::query::Query,
+) -> Option<String> {
+    const PROPOSAL_QUERY_REGEX_STR: &str =
+        r"^tm\.event = 'NewBlock' AND proposal\.proposal_id = '(\d+)'$";
+
+    lazy_static! {
+        /// Compiled regular expression used to parse proposal queries.
+        static ref PROPOSAL_QUERY_REGEX: Regex = Regex::new(PROPOSAL_QUERY_REGEX_STR).unwrap();
+    }
+
+    PROPOSAL_QUERY_REGEX
+        .captures(&query.to_string())
+        .map(|captures| captures[1].to_string())
+}
+
 /// A Namada event log index and event type encoded as
 /// a Tendermint block height.
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
@@ -5,17 +5,22 @@ pub mod eth_events;
 pub mod val_set_update;
 
 use namada::ethereum_bridge::protocol::transactions::bridge_pool_roots::sign_bridge_pool_root;
+use namada::ethereum_bridge::protocol::transactions::contract_addresses::contract_addresses_to_sign;
 use namada::ethereum_bridge::protocol::transactions::ethereum_events::sign_ethereum_events;
-use namada::ethereum_bridge::protocol::transactions::validator_set_update::sign_validator_set_update;
+use namada::ethereum_bridge::protocol::transactions::validator_set_update::{
+    sign_validator_set_update, valset_upd_to_sign,
+};
 pub use namada::ethereum_bridge::protocol::validation::VoteExtensionError;
+use namada::ledger::pos::PosQueries;
 use namada::tx::Signed;
 use namada::vote_ext::{
-    bridge_pool_roots, ethereum_events, validator_set_update, EthereumTxData,
-    VoteExtension,
+    bridge_pool_roots, contract_addresses, ethereum_events,
+    validator_set_update, EthereumTxData, VoteExtension,
 };
 use namada_sdk::eth_bridge::EthBridgeQueries;
 
 use super::*;
+use crate::node::ledger::eth_signer::RemoteEthSigner;
 use crate::node::ledger::shims::abcipp_shim_types::shim::TxBytes;
 
 /// Message to be passed to `.expect()` calls in this module.
@@ -37,6 +42,7 @@ where
                 .extend_vote_with_bp_roots()
                 .map(namada::vote_ext::bridge_pool_roots::SignedVext),
             validator_set_update: self.extend_vote_with_valset_update(),
+            contract_addresses: self.extend_vote_with_contract_addresses(),
         }
     }
 
@@ -106,6 +112,9 @@ where
             .mode
             .get_validator_address()
             .expect(VALIDATOR_EXPECT_MSG);
+        if let Some(signer) = self.eth_signer.as_ref() {
+            return self.sign_valset_update_remotely(signer, validator_addr);
+        }
         let eth_hot_key = self
             .mode
             .get_eth_bridge_keypair()
@@ -113,6 +122,120 @@ where
         sign_validator_set_update(&self.wl_storage, validator_addr, eth_hot_key)
     }
 
+    /// Sign the validator set update vote extension of the given validator
+    /// with a remote signer. The vote extension is skipped if the signer
+    /// fails to return a valid signature in time.
+    fn sign_valset_update_remotely(
+        &self,
+        signer: &RemoteEthSigner,
+        validator_addr: &Address,
+    ) -> Option<validator_set_update::SignedVext> {
+        let ext = valset_upd_to_sign(&self.wl_storage, validator_addr)?;
+        let domain = self
+            .wl_storage
+            .ethbridge_queries()
+            .valset_upd_signing_domain(ext.signing_epoch);
+        let digest = ext.signable_digest(domain.as_ref());
+        let sig = match signer.sign_digest(&digest) {
+            Ok(sig) => sig,
+            Err(err) => {
+                tracing::error!(
+                    %err,
+                    signing_epoch = ?ext.signing_epoch,
+                    "Failed to sign the validator set update vote extension \
+                     with the remote Ethereum signer, skipping it"
+                );
+                return None;
+            }
+        };
+        let signed =
+            validator_set_update::SignedVext(Signed::new_from(ext, sig));
+        let eth_hot_key =
+            self.wl_storage.pos_queries().read_validator_eth_hot_key(
+                validator_addr,
+                Some(signed.data.signing_epoch),
+            )?;
+        if let Err(err) = signed.verify_in_domain(&eth_hot_key, domain.as_ref())
+        {
+            tracing::error!(
+                %err,
+                signing_epoch = ?signed.data.signing_epoch,
+                "The remote Ethereum signer returned an invalid signature of \
+                 the validator set update vote extension, skipping it"
+            );
+            return None;
+        }
+        Some(signed)
+    }
+
+    /// Extend PreCommit votes with [`contract_addresses::Vext`] instances.
+    ///
+    /// The extension is signed with the Ethereum hot key of the validator,
+    /// either locally or by its remote signer, and with its Ethereum cold
+    /// key, by its remote cold key signer. Without a cold key signer, the
+    /// extension is skipped, as it would be rejected for lacking a cold key
+    /// signature.
+    pub fn extend_vote_with_contract_addresses(
+        &self,
+    ) -> Option<contract_addresses::SignedVext> {
+        let validator_addr = self
+            .mode
+            .get_validator_address()
+            .expect(VALIDATOR_EXPECT_MSG);
+        let ext = match contract_addresses_to_sign(
+            &self.wl_storage,
+            validator_addr,
+        ) {
+            Ok(ext) => ext?,
+            Err(err) => {
+                tracing::error!(
+                    %err,
+                    "Failed to read the scheduled Ethereum bridge \
+                     contracts update, skipping the contract addresses \
+                     vote extension"
+                );
+                return None;
+            }
+        };
+        let Some(cold_signer) = self.eth_cold_signer.as_ref() else {
+            tracing::debug!(
+                signing_epoch = ?ext.signing_epoch,
+                "No remote Ethereum cold key signer is configured, skipping \
+                 the contract addresses vote extension"
+            );
+            return None;
+        };
+        let digest =
+            contract_addresses::SerializeWithAbiEncode::as_signable(&ext);
+        let hot_sig = match self.eth_signer.as_ref() {
+            Some(signer) => signer.sign_digest(&digest),
+            None => {
+                let eth_hot_key = self
+                    .mode
+                    .get_eth_bridge_keypair()
+                    .expect(VALIDATOR_EXPECT_MSG);
+                Ok(ext.sign(eth_hot_key).hot.sig)
+            }
+        };
+        let signed = hot_sig.and_then(|hot_sig| {
+            Ok(contract_addresses::SignedVext {
+                cold_sig: Some(cold_signer.sign_digest(&digest)?),
+                hot: Signed::new_from(ext, hot_sig),
+            })
+        });
+        match signed {
+            Ok(signed) => Some(signed),
+            Err(err) => {
+                tracing::error!(
+                    %err,
+                    "Failed to sign the contract addresses vote extension \
+                     with the remote Ethereum signers, skipping it"
+                );
+                None
+            }
+        }
+    }
+
     /// Given a slice of [`TxBytes`], return an iterator over the
     /// ones we could deserialize to vote extension protocol txs.
     pub fn deserialize_vote_extensions<'shell>(
@@ -162,6 +285,15 @@ where
                         .valset_upd_seen(ext.data.signing_epoch.next()))
                     .then(|| tx_bytes.clone())
                 }
+                EthereumTxData::ContractAddressesVext(ext) => {
+                    // likewise, only include the contract addresses
+                    // whose proof is still incomplete
+                    (!self
+                        .wl_storage
+                        .ethbridge_queries()
+                        .contract_addresses_seen(ext.data.signing_epoch))
+                    .then(|| tx_bytes.clone())
+                }
                 _ => None,
             }
         })
@@ -177,6 +309,7 @@ pub fn iter_protocol_txs(
         ethereum_events,
         bridge_pool_root,
         validator_set_update,
+        contract_addresses,
     } = ext;
     [
         ethereum_events.map(|e| {
@@ -186,6 +319,7 @@ pub fn iter_protocol_txs(
         }),
         bridge_pool_root.map(EthereumTxData::BridgePoolVext),
         validator_set_update.map(EthereumTxData::ValSetUpdateVext),
+        contract_addresses.map(EthereumTxData::ContractAddressesVext),
     ]
     .into_iter()
     .flatten()
@@ -1,6 +1,7 @@
 //! Extend Tendermint votes with signatures of the Ethereum
 //! bridge pool root and nonce seen by a quorum of validators.
 use itertools::Itertools;
+use namada::ethereum_bridge::protocol::validation::bridge_pool_roots::validate_bp_roots_vext_batch;
 use namada::state::{DBIter, StorageHasher, DB};
 use namada::tx::Signed;
 
@@ -16,6 +17,8 @@ where
     /// and returns another iterator. The latter yields
     /// valid Bridge pool root vote extensions, or the reason why these
     /// are invalid, in the form of a `VoteExtensionError`.
+    ///
+    /// The signatures of the vote extensions are verified in parallel.
     #[inline]
     pub fn validate_bp_roots_vext_list<'iter>(
         &'iter self,
@@ -27,14 +30,16 @@ where
             VoteExtensionError,
         >,
     > + 'iter {
-        vote_extensions.into_iter().map(|vote_extension| {
-            validate_bp_roots_vext(
-                &self.wl_storage,
-                &vote_extension,
-                self.wl_storage.storage.get_last_block_height(),
-            )?;
-            Ok(vote_extension)
-        })
+        let vote_extensions: Vec<_> = vote_extensions.into_iter().collect();
+        let results = validate_bp_roots_vext_batch(
+            &self.wl_storage,
+            &vote_extensions,
+            self.wl_storage.storage.get_last_block_height(),
+        );
+        vote_extensions
+            .into_iter()
+            .zip(results)
+            .map(|(vote_extension, result)| result.map(|()| vote_extension))
     }
 
     /// Takes a list of signed Bridge pool root vote extensions,
@@ -56,7 +61,15 @@ where
 
 #[cfg(test)]
 mod test_bp_vote_extensions {
+    use namada::eth_bridge::storage::parameters::{
+        apply_scheduled_contracts_update, read_contracts,
+        schedule_contracts_update, set_bp_root_height_binding_epoch,
+        ScheduledContractsUpdate,
+    };
     use namada::ethereum_bridge::protocol::validation::bridge_pool_roots::validate_bp_roots_vext;
+    use namada::ethereum_bridge::protocol::validation::{
+        ExtensionKind, InvalidVextReason,
+    };
     use namada::ethereum_bridge::storage::bridge_pool::get_key_from_hash;
     use namada::ethereum_bridge::storage::eth_bridge_queries::EthBridgeQueries;
     use namada::ledger::pos::PosQueries;
@@ -77,6 +90,7 @@ mod test_bp_vote_extensions {
     use namada::types::storage::BlockHeight;
     use namada::types::token;
     use namada::vote_ext::bridge_pool_roots;
+    use namada::vote_ext::bridge_pool_roots::bp_root_digest;
 
     use crate::node::ledger::shell::test_utils::*;
     use crate::node::ledger::shims::abcipp_shim_types::shim::request::FinalizeBlock;
@@ -160,6 +174,7 @@ mod test_bp_vote_extensions {
         let sig = Signed::<_, SignableEthMessage>::new(&hot_key, to_sign).sig;
         let vote_ext = bridge_pool_roots::Vext {
             block_height: shell.wl_storage.storage.get_last_block_height(),
+            root_height: None,
             validator_addr: bertha_address(),
             sig,
         }
@@ -200,6 +215,7 @@ mod test_bp_vote_extensions {
         .sig;
         let vote_ext = bridge_pool_roots::Vext {
             block_height: shell.wl_storage.storage.get_last_block_height(),
+            root_height: None,
             validator_addr: address,
             sig,
         }
@@ -240,6 +256,7 @@ mod test_bp_vote_extensions {
         .sig;
         let vote_ext = bridge_pool_roots::Vext {
             block_height: shell.wl_storage.storage.get_last_block_height(),
+            root_height: None,
             validator_addr: address,
             sig,
         }
@@ -273,6 +290,7 @@ mod test_bp_vote_extensions {
             Signed::<_, SignableEthMessage>::new(&signing_key, to_sign).sig;
         let bp_root = bridge_pool_roots::Vext {
             block_height: shell.wl_storage.storage.get_last_block_height(),
+            root_height: None,
             validator_addr: address,
             sig,
         }
@@ -307,6 +325,7 @@ mod test_bp_vote_extensions {
         .sig;
         let bp_root = bridge_pool_roots::Vext {
             block_height: shell.wl_storage.storage.get_last_block_height(),
+            root_height: None,
             validator_addr: address,
             sig,
         }
@@ -331,6 +350,7 @@ mod test_bp_vote_extensions {
         .sig;
         let bp_root = bridge_pool_roots::Vext {
             block_height: height,
+            root_height: None,
             validator_addr: address,
             sig,
         }
@@ -379,6 +399,7 @@ mod test_bp_vote_extensions {
         .sig;
         let bp_root = bridge_pool_roots::Vext {
             block_height: shell.wl_storage.storage.get_last_block_height(),
+            root_height: None,
             validator_addr: address,
             sig,
         }
@@ -407,6 +428,7 @@ mod test_bp_vote_extensions {
         .sig;
         let bp_root = bridge_pool_roots::Vext {
             block_height: shell.wl_storage.storage.get_last_block_height(),
+            root_height: None,
             validator_addr: address,
             sig,
         }
@@ -462,6 +484,7 @@ mod test_bp_vote_extensions {
         .sig;
         let bp_root = bridge_pool_roots::Vext {
             block_height: 2.into(),
+            root_height: None,
             validator_addr: address.clone(),
             sig,
         }
@@ -482,6 +505,7 @@ mod test_bp_vote_extensions {
         .sig;
         let bp_root = bridge_pool_roots::Vext {
             block_height: 3.into(),
+            root_height: None,
             validator_addr: address,
             sig,
         }
@@ -537,6 +561,7 @@ mod test_bp_vote_extensions {
         .sig;
         let bp_root = bridge_pool_roots::Vext {
             block_height: 3.into(),
+            root_height: None,
             validator_addr: address,
             sig,
         }
@@ -550,4 +575,210 @@ mod test_bp_vote_extensions {
             .is_err()
         );
     }
+
+    /// Sign the given Bridge pool root and nonce with the keys of the
+    /// validator of `shell`, in a vext issued at `block_height`.
+    fn sign_bp_root(
+        shell: &TestShell,
+        block_height: BlockHeight,
+        root: [u8; 32],
+        nonce: Uint,
+    ) -> Signed<bridge_pool_roots::Vext> {
+        sign_bound_bp_root(shell, block_height, root, nonce, None)
+    }
+
+    /// Like [`sign_bp_root`], but binding the signature to `root_height`.
+    fn sign_bound_bp_root(
+        shell: &TestShell,
+        block_height: BlockHeight,
+        root: [u8; 32],
+        nonce: Uint,
+        root_height: Option<BlockHeight>,
+    ) -> Signed<bridge_pool_roots::Vext> {
+        let to_sign = bp_root_digest(&KeccakHash(root), nonce, root_height);
+        let sig = Signed::<_, SignableEthMessage>::new(
+            shell.mode.get_eth_bridge_keypair().expect("Test failed"),
+            to_sign,
+        )
+        .sig;
+        bridge_pool_roots::Vext {
+            block_height,
+            root_height,
+            validator_addr: shell.mode.get_validator_address().unwrap().clone(),
+            sig,
+        }
+        .sign(shell.mode.get_protocol_key().expect("Test failed"))
+        .0
+    }
+
+    /// Test that both signature formats of Bridge pool roots are accepted
+    /// until the height binding takes effect, after which only roots
+    /// signed together with their height are accepted.
+    #[test]
+    fn test_bp_root_height_binding_transition() {
+        let (mut shell, _recv, _, _oracle_control_recv) = setup_at_height(1u64);
+        shell.wl_storage.storage.block.height = 2.into();
+        let key = get_key_from_hash(&KeccakHash([1; 32]));
+        let height = shell.wl_storage.storage.block.height;
+        shell.wl_storage.write(&key, height).expect("Test failed");
+        shell.commit();
+
+        // before the binding epoch, both formats are accepted
+        let legacy_root = sign_bp_root(&shell, 2.into(), [1; 32], 0.into());
+        let bound_root = sign_bound_bp_root(
+            &shell,
+            2.into(),
+            [1; 32],
+            0.into(),
+            Some(2.into()),
+        );
+        for root in [&legacy_root, &bound_root] {
+            assert_eq!(
+                validate_bp_roots_vext(
+                    &shell.wl_storage,
+                    root,
+                    shell.get_current_decision_height()
+                ),
+                Ok(())
+            );
+        }
+        // but a root must be bound to the height it was signed at
+        let misbound_root = sign_bound_bp_root(
+            &shell,
+            2.into(),
+            [1; 32],
+            0.into(),
+            Some(1.into()),
+        );
+        assert_eq!(
+            validate_bp_roots_vext(
+                &shell.wl_storage,
+                &misbound_root,
+                shell.get_current_decision_height()
+            ),
+            Err(InvalidVextReason::InvalidBPRootHeight
+                .of(ExtensionKind::BridgePoolRoot))
+        );
+
+        // once the binding takes effect, legacy roots are rejected
+        let epoch = shell.wl_storage.storage.block.epoch;
+        set_bp_root_height_binding_epoch(&mut shell.wl_storage, epoch)
+            .expect("Test failed");
+        shell.commit();
+        assert_eq!(
+            validate_bp_roots_vext(
+                &shell.wl_storage,
+                &legacy_root,
+                shell.get_current_decision_height()
+            ),
+            Err(InvalidVextReason::InvalidBPRootHeight
+                .of(ExtensionKind::BridgePoolRoot))
+        );
+        assert_eq!(
+            validate_bp_roots_vext(
+                &shell.wl_storage,
+                &bound_root,
+                shell.get_current_decision_height()
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            shell.extend_vote_with_bp_roots().expect("Test failed"),
+            bound_root
+        );
+    }
+
+    /// Test that once governance realigns the Bridge pool nonce with a
+    /// redeployed bridge contract, the roots signed under the old nonce
+    /// are rejected, and the transfers pending across the switchover are
+    /// covered by the roots signed under the new nonce.
+    #[test]
+    fn test_bp_roots_across_nonce_realignment() {
+        let (mut shell, _recv, _, _oracle_control_recv) = setup_at_height(1u64);
+
+        // a transfer is pending before the switchover
+        shell.wl_storage.storage.block.height = 2.into();
+        let key = get_key_from_hash(&KeccakHash([1; 32]));
+        let height = shell.wl_storage.storage.block.height;
+        shell.wl_storage.write(&key, height).expect("Test failed");
+        shell.commit();
+        let old_root = sign_bp_root(&shell, 2.into(), [1; 32], Uint::from(0));
+        assert_eq!(
+            validate_bp_roots_vext(
+                &shell.wl_storage,
+                &old_root,
+                shell.get_current_decision_height()
+            ),
+            Ok(())
+        );
+
+        // the redeployed bridge contract is activated, and its nonce
+        // adopted by the Bridge pool
+        shell.wl_storage.storage.block.height = 3.into();
+        let new_nonce = Uint::from(5);
+        let activation_epoch = shell.wl_storage.storage.block.epoch;
+        let contracts = read_contracts(&shell.wl_storage).expect("Test failed");
+        schedule_contracts_update(
+            &mut shell.wl_storage,
+            ScheduledContractsUpdate {
+                activation_epoch,
+                contracts,
+                bridge_pool_nonce: Some(new_nonce),
+            },
+        )
+        .expect("Test failed");
+        assert!(
+            apply_scheduled_contracts_update(
+                &mut shell.wl_storage,
+                activation_epoch
+            )
+            .expect("Test failed")
+            .is_some()
+        );
+        shell.commit();
+
+        // the root signed under the old nonce can no longer be relayed
+        assert_eq!(
+            validate_bp_roots_vext(
+                &shell.wl_storage,
+                &old_root,
+                shell.get_current_decision_height()
+            ),
+            Err(InvalidVextReason::StaleBridgePoolNonce
+                .of(ExtensionKind::BridgePoolRoot))
+        );
+        // the pending transfer is still covered by the latest root, which
+        // is only accepted if signed under the new nonce
+        assert_eq!(
+            shell
+                .wl_storage
+                .ethbridge_queries()
+                .get_bridge_pool_root_at_height(3.into())
+                .unwrap(),
+            KeccakHash([1; 32])
+        );
+        let stale_nonce_root =
+            sign_bp_root(&shell, 3.into(), [1; 32], Uint::from(0));
+        assert!(
+            validate_bp_roots_vext(
+                &shell.wl_storage,
+                &stale_nonce_root,
+                shell.get_current_decision_height()
+            )
+            .is_err()
+        );
+        let new_root = sign_bp_root(&shell, 3.into(), [1; 32], new_nonce);
+        assert_eq!(
+            validate_bp_roots_vext(
+                &shell.wl_storage,
+                &new_root,
+                shell.get_current_decision_height()
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            shell.extend_vote_with_bp_roots().expect("Test failed"),
+            new_root
+        );
+    }
 }
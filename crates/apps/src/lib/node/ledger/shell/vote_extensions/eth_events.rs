@@ -2,6 +2,7 @@
 
 use std::collections::{BTreeMap, HashMap};
 
+use namada::ethereum_bridge::protocol::validation::ethereum_events::validate_eth_events_vext_batch;
 use namada::state::{DBIter, StorageHasher, DB};
 use namada::tx::Signed;
 use namada::types::ethereum_events::EthereumEvent;
@@ -17,7 +18,9 @@ where
     H: StorageHasher + Sync + 'static,
 {
     /// Checks the channel from the Ethereum oracle monitoring
-    /// the fullnode and retrieves all seen Ethereum events.
+    /// the fullnode and retrieves all seen Ethereum events, which
+    /// were observed from the bridge contract in effect at the
+    /// last committed epoch.
     pub fn new_ethereum_events(&mut self) -> Vec<EthereumEvent> {
         let queries = self.wl_storage.ethbridge_queries();
         match &mut self.mode {
@@ -31,6 +34,23 @@ where
                 ethereum_receiver.fill_queue(|event| {
                     queries.validate_eth_event_nonce(event)
                 });
+                if queries.is_bridge_active() {
+                    match queries
+                        .get_contracts_at(self.wl_storage.storage.last_epoch)
+                    {
+                        Ok(contracts) => ethereum_receiver
+                            .retain_observed_from(&contracts.bridge.address),
+                        Err(err) => {
+                            tracing::error!(
+                                %err,
+                                "Failed to read the Ethereum bridge \
+                                 contracts, not voting for new Ethereum \
+                                 events"
+                            );
+                            return vec![];
+                        }
+                    }
+                }
                 ethereum_receiver.get_events()
             }
             _ => vec![],
@@ -41,6 +61,8 @@ where
     /// and returns another iterator. The latter yields
     /// valid Ethereum events vote extensions, or the reason why these
     /// are invalid, in the form of a `VoteExtensionError`.
+    ///
+    /// The signatures of the vote extensions are verified in parallel.
     #[inline]
     pub fn validate_eth_events_vext_list<'iter>(
         &'iter self,
@@ -52,14 +74,16 @@ where
             VoteExtensionError,
         >,
     > + 'iter {
-        vote_extensions.into_iter().map(|vote_extension| {
-            validate_eth_events_vext(
-                &self.wl_storage,
-                &vote_extension,
-                self.wl_storage.storage.get_last_block_height(),
-            )?;
-            Ok(vote_extension)
-        })
+        let vote_extensions: Vec<_> = vote_extensions.into_iter().collect();
+        let results = validate_eth_events_vext_batch(
+            &self.wl_storage,
+            &vote_extensions,
+            self.wl_storage.storage.get_last_block_height(),
+        );
+        vote_extensions
+            .into_iter()
+            .zip(results)
+            .map(|(vote_extension, result)| result.map(|()| vote_extension))
     }
 
     /// Takes a list of signed Ethereum events vote extensions,
@@ -92,12 +116,14 @@ where
 
         let mut event_observers = BTreeMap::new();
         let mut signatures = HashMap::new();
+        let mut bridge_contracts = HashMap::new();
 
         for vote_extension in
             self.filter_invalid_eth_events_vexts(vote_extensions)
         {
             let validator_addr = vote_extension.data.validator_addr;
             let block_height = vote_extension.data.block_height;
+            let bridge_contract = vote_extension.data.bridge_contract;
 
             // register all ethereum events seen by `validator_addr`
             for ev in vote_extension.data.ethereum_events {
@@ -111,6 +137,7 @@ where
             let sig = vote_extension.sig;
 
             let key = (addr, block_height);
+            bridge_contracts.insert(key.clone(), bridge_contract);
             tracing::debug!(
                 ?key,
                 ?sig,
@@ -134,7 +161,11 @@ where
             .map(|(event, signers)| MultiSignedEthEvent { event, signers })
             .collect();
 
-        Some(ethereum_events::VextDigest { events, signatures })
+        Some(ethereum_events::VextDigest {
+            events,
+            signatures,
+            bridge_contracts,
+        })
     }
 }
 
@@ -144,6 +175,12 @@ mod test_vote_extensions {
 
     use borsh_ext::BorshSerializeExt;
     use namada::eth_bridge::storage::bridge_pool;
+    use namada::eth_bridge::storage::parameters::{
+        read_contracts, schedule_contracts_update, ScheduledContractsUpdate,
+    };
+    use namada::ethereum_bridge::protocol::validation::{
+        ExtensionKind, InvalidVextReason,
+    };
     use namada::ledger::eth_bridge::EthBridgeQueries;
     use namada::ledger::pos::PosQueries;
     use namada::proof_of_stake::storage::{
@@ -153,6 +190,7 @@ mod test_vote_extensions {
     use namada::proof_of_stake::types::WeightedValidator;
     use namada::state::collections::lazy_map::{NestedSubKey, SubKey};
     use namada::tendermint::abci::types::VoteInfo;
+    use namada::tx::Signed;
     use namada::types::address::testing::gen_established_address;
     use namada::types::ethereum_events::{
         EthAddress, EthereumEvent, TransferToEthereum, Uint,
@@ -278,6 +316,7 @@ mod test_vote_extensions {
     #[test]
     fn test_get_eth_events() {
         let (mut shell, _, oracle, _) = setup();
+        let bridge_contract = shell.bridge_contract();
         let event_1 = EthereumEvent::TransfersToEthereum {
             nonce: 0.into(),
             transfers: vec![TransferToEthereum {
@@ -308,9 +347,9 @@ mod test_vote_extensions {
         };
 
         // send valid events
-        tokio_test::block_on(oracle.send(event_1.clone()))
+        tokio_test::block_on(oracle.send((bridge_contract, event_1.clone())))
             .expect("Test failed");
-        tokio_test::block_on(oracle.send(event_3.clone()))
+        tokio_test::block_on(oracle.send((bridge_contract, event_3.clone())))
             .expect("Test failed");
 
         let got_events: [EthereumEvent; 2] =
@@ -327,12 +366,13 @@ mod test_vote_extensions {
         // the same block height on ethereum. this is because we
         // require a confirmation eth event on namada to increment
         // the bridge pool nonce. this event should get ignored
-        tokio_test::block_on(oracle.send(event_2)).expect("Test failed");
+        tokio_test::block_on(oracle.send((bridge_contract, event_2)))
+            .expect("Test failed");
 
         // check that we queue and de-duplicate events
-        tokio_test::block_on(oracle.send(event_3.clone()))
+        tokio_test::block_on(oracle.send((bridge_contract, event_3.clone())))
             .expect("Test failed");
-        tokio_test::block_on(oracle.send(event_4.clone()))
+        tokio_test::block_on(oracle.send((bridge_contract, event_4.clone())))
             .expect("Test failed");
 
         let got_events: [EthereumEvent; 3] =
@@ -344,6 +384,32 @@ mod test_vote_extensions {
         assert_eq!(expected_events, got_events);
     }
 
+    /// Test that only the Ethereum events observed from the bridge contract
+    /// in effect are retrieved to be voted on
+    #[test]
+    fn test_get_eth_events_from_bridge_contract() {
+        let (mut shell, _, oracle, _) = setup();
+        let bridge_contract = shell.bridge_contract();
+        let other_contract = EthAddress([42; 20]);
+        let event_1 = EthereumEvent::TransfersToNamada {
+            nonce: 0.into(),
+            transfers: vec![],
+        };
+        let event_2 = EthereumEvent::TransfersToNamada {
+            nonce: 1.into(),
+            transfers: vec![],
+        };
+
+        tokio_test::block_on(oracle.send((other_contract, event_1)))
+            .expect("Test failed");
+        tokio_test::block_on(oracle.send((bridge_contract, event_2.clone())))
+            .expect("Test failed");
+
+        let got_events: [EthereumEvent; 1] =
+            shell.new_ethereum_events().try_into().expect("Test failed");
+        assert_eq!(got_events, [event_2]);
+    }
+
     /// Test that Ethereum events signed by a non-validator are rejected
     #[test]
     fn test_eth_events_must_be_signed_by_validator() {
@@ -368,6 +434,7 @@ mod test_vote_extensions {
             }],
             block_height: shell.get_current_decision_height(),
             validator_addr: address.clone(),
+            bridge_contract: shell.bridge_contract(),
         }
         .sign(&signing_key);
         assert!(
@@ -408,6 +475,7 @@ mod test_vote_extensions {
             }],
             block_height: signed_height,
             validator_addr: address,
+            bridge_contract: shell.bridge_contract(),
         }
         .sign(shell.mode.get_protocol_key().expect("Test failed"));
 
@@ -496,6 +564,81 @@ mod test_vote_extensions {
         );
     }
 
+    /// Test that Ethereum events vote extensions are only valid if observed
+    /// from the bridge contract in effect at their epoch, including those
+    /// signed before an update of the contracts scheduled by governance.
+    #[test]
+    fn test_validate_eth_events_vext_bridge_contract() {
+        let (mut shell, _recv, _, _oracle_control_recv) = setup_at_height(3u64);
+        let sign_vext = |shell: &TestShell, bridge_contract| {
+            let address = shell
+                .mode
+                .get_validator_address()
+                .expect("Test failed")
+                .clone();
+            ethereum_events::Vext {
+                ethereum_events: vec![],
+                block_height: shell.wl_storage.storage.get_last_block_height(),
+                validator_addr: address,
+                bridge_contract,
+            }
+            .sign(shell.mode.get_protocol_key().expect("Test failed"))
+        };
+        let validate =
+            |shell: &TestShell, vote_ext: &Signed<ethereum_events::Vext>| {
+                validate_eth_events_vext(
+                    &shell.wl_storage,
+                    vote_ext,
+                    shell.get_current_decision_height(),
+                )
+            };
+        let old_contract = shell.bridge_contract();
+        let new_contract = EthAddress([42; 20]);
+
+        let pre_activation = sign_vext(&shell, old_contract);
+        assert_eq!(validate(&shell, &pre_activation), Ok(()));
+        assert_eq!(
+            validate(&shell, &sign_vext(&shell, new_contract)),
+            Err(InvalidVextReason::UnexpectedBridgeContract
+                .of(ExtensionKind::EthereumEvents))
+        );
+
+        // governance switches to a new bridge contract at the next epoch
+        let mut contracts =
+            read_contracts(&shell.wl_storage).expect("Test failed");
+        contracts.bridge.address = new_contract;
+        contracts.bridge.version = contracts.bridge.version.next();
+        schedule_contracts_update(
+            &mut shell.wl_storage,
+            ScheduledContractsUpdate {
+                activation_epoch: Epoch(1),
+                contracts,
+                bridge_pool_nonce: None,
+            },
+        )
+        .expect("Test failed");
+        shell.commit();
+        assert_eq!(shell.start_new_epoch(None).0, 1);
+        assert_eq!(
+            shell
+                .wl_storage
+                .pos_queries()
+                .get_epoch(shell.wl_storage.storage.get_last_block_height()),
+            Some(Epoch(1))
+        );
+        assert_eq!(shell.bridge_contract(), new_contract);
+
+        // the events observed before the switch remain valid
+        assert_eq!(validate(&shell, &pre_activation), Ok(()));
+        // but new events must be observed from the new contract
+        assert_eq!(validate(&shell, &sign_vext(&shell, new_contract)), Ok(()));
+        assert_eq!(
+            validate(&shell, &sign_vext(&shell, old_contract)),
+            Err(InvalidVextReason::UnexpectedBridgeContract
+                .of(ExtensionKind::EthereumEvents))
+        );
+    }
+
     /// Test for ABCI++ that an [`ethereum_events::Vext`] that incorrectly
     /// labels what block it was included on in a vote extension is
     /// rejected. For ABCI+, test that it is rejected if the block height is
@@ -518,6 +661,7 @@ mod test_vote_extensions {
             }],
             block_height: shell.wl_storage.storage.get_last_block_height(),
             validator_addr: address.clone(),
+            bridge_contract: shell.bridge_contract(),
         };
 
         ethereum_events.block_height =
@@ -554,6 +698,7 @@ mod test_vote_extensions {
             }],
             block_height: shell.wl_storage.storage.get_last_block_height(),
             validator_addr: address.clone(),
+            bridge_contract: shell.bridge_contract(),
         }
         .sign(shell.mode.get_protocol_key().expect("Test failed"));
 
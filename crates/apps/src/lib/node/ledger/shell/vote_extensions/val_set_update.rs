@@ -3,6 +3,7 @@
 
 use std::collections::HashMap;
 
+use namada::ethereum_bridge::protocol::validation::validator_set_update::validate_valset_upd_vext_batch;
 use namada::state::{DBIter, StorageHasher, DB};
 use namada::vote_ext::validator_set_update;
 
@@ -18,6 +19,8 @@ where
     /// and returns another iterator. The latter yields
     /// valid validator set update vote extensions, or the reason why these
     /// are invalid, in the form of a `VoteExtensionError`.
+    ///
+    /// The signatures of the vote extensions are verified in parallel.
     #[inline]
     pub fn validate_valset_upd_vext_list(
         &self,
@@ -29,14 +32,16 @@ where
             VoteExtensionError,
         >,
     > + '_ {
-        vote_extensions.into_iter().map(|vote_extension| {
-            validate_valset_upd_vext(
-                &self.wl_storage,
-                &vote_extension,
-                self.wl_storage.storage.get_current_epoch().0,
-            )?;
-            Ok(vote_extension)
-        })
+        let vote_extensions: Vec<_> = vote_extensions.into_iter().collect();
+        let results = validate_valset_upd_vext_batch(
+            &self.wl_storage,
+            &vote_extensions,
+            self.wl_storage.storage.get_current_epoch().0,
+        );
+        vote_extensions
+            .into_iter()
+            .zip(results)
+            .map(|(vote_extension, result)| result.map(|()| vote_extension))
     }
 
     /// Takes a list of signed validator set update vote extensions,
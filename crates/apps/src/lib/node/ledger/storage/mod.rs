@@ -10,6 +10,7 @@ use arse_merkle_tree::traits::Hasher;
 use arse_merkle_tree::H256;
 use blake2b_rs::{Blake2b, Blake2bBuilder};
 use namada::state::{State, StorageHasher};
+pub use rocksdb::open_read_only;
 
 #[derive(Default)]
 pub struct PersistentStorageHasher(Blake2bHasher);
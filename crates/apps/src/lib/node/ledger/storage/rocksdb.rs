@@ -25,6 +25,7 @@
 //!   - `old/{dyn}`: value from predecessor block height
 //! - `block`: block state
 //!   - `results/{h}`: block results at height `h`
+//!   - `gas/{h}`: gas consumed by the block at height `h`
 //!   - `h`: for each block at height `h`:
 //!     - `tree`: merkle tree
 //!       - `root`: root hash
@@ -50,6 +51,7 @@ use data_encoding::HEXLOWER;
 use itertools::Either;
 use namada::eth_bridge::storage::proof::BridgePoolRootProof;
 use namada::ledger::eth_bridge::storage::bridge_pool;
+use namada::ledger::gas::BlockGasMeter;
 use namada::ledger::replay_protection;
 use namada::ledger::storage::tx_queue::TxQueue;
 use namada::state::merkle_tree::{base_tree_key_prefix, subtree_key_prefix};
@@ -91,9 +93,9 @@ const REPLAY_PROTECTION_CF: &str = "replay_protection";
 const OLD_DIFF_PREFIX: &str = "old";
 const NEW_DIFF_PREFIX: &str = "new";
 
-/// RocksDB handle
+/// RocksDB handle, and whether it was opened in read-only mode
 #[derive(Debug)]
-pub struct RocksDB(rocksdb::DB);
+pub struct RocksDB(rocksdb::DB, bool);
 
 /// DB Handle for batch writes.
 #[derive(Default)]
@@ -191,13 +193,38 @@ pub fn open(
     ));
 
     rocksdb::DB::open_cf_descriptors(&db_opts, path, cfs)
-        .map(RocksDB)
+        .map(|db| RocksDB(db, false))
         .map_err(|e| Error::DBError(e.into_string()))
 }
 
+/// Open RocksDB for the DB in read-only mode. Nothing can be written to
+/// the DB, which is never flushed, such that it can be read from while a
+/// node holds it open.
+pub fn open_read_only(path: impl AsRef<Path>) -> Result<RocksDB> {
+    let mut db_opts = Options::default();
+    set_max_open_files(&mut db_opts);
+    rocksdb::DB::open_cf_for_read_only(
+        &db_opts,
+        path,
+        [
+            SUBSPACE_CF,
+            DIFFS_CF,
+            STATE_CF,
+            BLOCK_CF,
+            REPLAY_PROTECTION_CF,
+        ],
+        false,
+    )
+    .map(|db| RocksDB(db, true))
+    .map_err(|e| Error::DBError(e.into_string()))
+}
+
 impl Drop for RocksDB {
     fn drop(&mut self) {
-        self.flush(true).expect("flush failed");
+        // a read-only DB has nothing to flush
+        if !self.1 {
+            self.flush(true).expect("flush failed");
+        }
     }
 }
 
@@ -550,6 +577,7 @@ impl RocksDB {
         let block_cf = self.get_column_family(BLOCK_CF)?;
         tracing::info!("Removing last block results");
         batch.delete_cf(block_cf, format!("results/{}", last_block.height));
+        batch.delete_cf(block_cf, format!("gas/{}", last_block.height));
 
         // Delete the tx hashes included in the last block
         let reprot_cf = self.get_column_family(REPLAY_PROTECTION_CF)?;
@@ -930,6 +958,7 @@ impl DB for RocksDB {
             update_epoch_blocks_delay,
             address_gen,
             results,
+            gas,
             conversion_state,
             tx_queue,
             ethereum_height,
@@ -1107,6 +1136,11 @@ impl DB for RocksDB {
                 .0
                 .put_cf(block_cf, results_path, types::encode(&results));
         }
+        // Block gas
+        {
+            let gas_path = format!("gas/{}", height.raw());
+            batch.0.put_cf(block_cf, gas_path, types::encode(gas));
+        }
         // Predecessor block epochs
         {
             let key = prefix_key
@@ -1155,6 +1189,24 @@ impl DB for RocksDB {
         }
     }
 
+    fn read_block_gas(
+        &self,
+        height: BlockHeight,
+    ) -> Result<Option<BlockGasMeter>> {
+        let block_cf = self.get_column_family(BLOCK_CF)?;
+        let gas_path = format!("gas/{}", height.raw());
+        match self
+            .0
+            .get_cf(block_cf, gas_path)
+            .map_err(|e| Error::DBError(e.into_string()))?
+        {
+            Some(bytes) => {
+                Ok(Some(types::decode(bytes).map_err(Error::CodingError)?))
+            }
+            None => Ok(None),
+        }
+    }
+
     fn read_merkle_tree_stores(
         &self,
         epoch: Epoch,
@@ -1250,6 +1302,16 @@ impl DB for RocksDB {
             .map_err(|e| Error::DBError(e.into_string()))
     }
 
+    fn read_subspace_val_len(&self, key: &Key) -> Result<Option<usize>> {
+        let subspace_cf = self.get_column_family(SUBSPACE_CF)?;
+        // a pinned slice points into the block cache, so the value is not
+        // copied
+        self.0
+            .get_pinned_cf(subspace_cf, key.to_string())
+            .map(|val| val.map(|val| val.len()))
+            .map_err(|e| Error::DBError(e.into_string()))
+    }
+
     fn read_subspace_val_with_height(
         &self,
         key: &Key,
@@ -1858,6 +1920,37 @@ mod test {
             .expect("Block should have been written");
     }
 
+    /// Test that a DB held open by a node can be read from in read-only
+    /// mode, which can't write to it and doesn't flush it on drop.
+    #[test]
+    fn test_open_read_only() {
+        let dir = tempdir().unwrap();
+        let mut db = open(dir.path(), None).unwrap();
+        let key = Key::parse("test").unwrap();
+        db.write_subspace_val(BlockHeight(1), &key, vec![1_u8, 1, 1, 1], true)
+            .unwrap();
+
+        let mut read_only_db = open_read_only(dir.path()).unwrap();
+        assert_eq!(
+            read_only_db.read_subspace_val(&key).unwrap(),
+            Some(vec![1_u8, 1, 1, 1])
+        );
+        assert!(
+            read_only_db
+                .write_subspace_val(BlockHeight(2), &key, vec![2_u8], true)
+                .is_err()
+        );
+        drop(read_only_db);
+
+        // the DB is still usable by the node
+        db.write_subspace_val(BlockHeight(2), &key, vec![2_u8, 2, 2, 2], true)
+            .unwrap();
+        assert_eq!(
+            db.read_subspace_val(&key).unwrap(),
+            Some(vec![2_u8, 2, 2, 2])
+        );
+    }
+
     #[test]
     fn test_read() {
         let dir = tempdir().unwrap();
@@ -2290,6 +2383,7 @@ mod test {
         let address_gen = EstablishedAddressGen::new("whatever");
         let tx_queue = TxQueue::default();
         let results = BlockResults::default();
+        let gas = BlockGasMeter::default();
         let eth_events_queue = EthEventsQueue::default();
         let block = BlockStateWrite {
             merkle_tree_stores,
@@ -2299,6 +2393,7 @@ mod test {
             time,
             epoch,
             results: &results,
+            gas: &gas,
             conversion_state,
             pred_epochs: &pred_epochs,
             next_epoch_min_start_height,
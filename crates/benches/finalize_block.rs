@@ -0,0 +1,84 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use namada::core::types::address::gen_deterministic_established_address;
+use namada::governance::storage::keys::get_vote_proposal_key;
+use namada::governance::storage::vote::ProposalVote;
+use namada::proof_of_stake::queries::compute_proposal_votes;
+use namada::proof_of_stake::storage::read_pos_params;
+use namada::state::{StorageRead, StorageWrite};
+use namada_apps::bench_utils::BenchShell;
+use namada_apps::node::ledger::shell::phase_timing::{self, Phase};
+use namada_apps::wallet::defaults;
+use tracing::Level;
+
+/// Some work standing in for a phase of `FinalizeBlock`.
+fn phase_work(id: u64) -> u64 {
+    (0..100).fold(id, |acc, i| black_box(acc.wrapping_mul(31) ^ i))
+}
+
+/// Measure the overhead of the spans and timings wrapping the phases of
+/// `FinalizeBlock`, with the spans filtered out by the subscriber, as
+/// with the default log level, and with the spans enabled.
+fn finalize_block_phase_instrumentation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("finalize_block_phase_instrumentation");
+    group.bench_function("uninstrumented", |b| {
+        b.iter(|| phase_work(black_box(1)))
+    });
+    for (bench_name, level) in [
+        ("spans_filtered_out", Level::INFO),
+        ("spans_enabled", Level::DEBUG),
+    ] {
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(level)
+            .with_writer(std::io::sink)
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            group.bench_function(bench_name, |b| {
+                b.iter(|| {
+                    let id = black_box(1);
+                    let _span = tracing::debug_span!("governance_proposal", id)
+                        .entered();
+                    phase_timing::record(Phase::ProposalTally, || {
+                        phase_work(id)
+                    })
+                })
+            });
+        });
+        // drain the timings, as the shell does once per block
+        phase_timing::take_block_timings();
+    }
+    group.finish();
+}
+
+/// Measure the computation of the votes cast on a proposal voted by 10k
+/// delegators. The role of each voter is read from its vote key, so no
+/// PoS read is spent on telling validators and delegators apart.
+fn proposal_votes_tally(c: &mut Criterion) {
+    let mut shell = BenchShell::default();
+    let validator = defaults::validator_address();
+    for i in 0..10_000 {
+        let delegator =
+            gen_deterministic_established_address(format!("voter-{i}"));
+        let vote_key = get_vote_proposal_key(0, delegator, validator.clone());
+        shell
+            .wl_storage
+            .write(&vote_key, ProposalVote::Yay)
+            .unwrap();
+    }
+    shell.commit_block();
+    let params = read_pos_params(&shell.wl_storage).unwrap();
+    let epoch = shell.wl_storage.get_block_epoch().unwrap();
+
+    c.bench_function("proposal_votes_tally_10k_votes", |b| {
+        b.iter(|| {
+            compute_proposal_votes(&shell.wl_storage, &params, 0, epoch)
+                .unwrap()
+        })
+    });
+}
+
+criterion_group!(
+    finalize_block,
+    finalize_block_phase_instrumentation,
+    proposal_votes_tally
+);
+criterion_main!(finalize_block);
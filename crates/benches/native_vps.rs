@@ -126,6 +126,9 @@ fn governance(c: &mut Criterion) {
                         voting_start_epoch,
                         voting_end_epoch: voting_start_epoch + 3_u64,
                         grace_epoch: voting_start_epoch + 9_u64,
+                        voting_heights: None,
+                        priority: None,
+                        code_chunks: vec![],
                     },
                     None,
                     Some(vec![content_section]),
@@ -180,6 +183,9 @@ fn governance(c: &mut Criterion) {
                         voting_start_epoch,
                         voting_end_epoch: voting_start_epoch + 3_u64,
                         grace_epoch: voting_start_epoch + 9_u64,
+                        voting_heights: None,
+                        priority: None,
+                        code_chunks: vec![],
                     },
                     None,
                     Some(vec![content_section, wasm_code_section]),
@@ -960,8 +966,11 @@ fn parameters(c: &mut Criterion) {
                     .write(&min_proposal_fund_key, 1_000)
                     .unwrap();
 
-                let proposal_key = namada::governance::storage::keys::get_proposal_execution_key(0);
-                shell.wl_storage.write(&proposal_key, 0).unwrap();
+                namada::governance::storage::write_pending_execution(
+                    &mut shell.wl_storage,
+                    0,
+                )
+                .unwrap();
 
                 // Return a dummy tx for validation
                 let mut tx =
@@ -1036,8 +1045,11 @@ fn pos(c: &mut Criterion) {
                     .write(&min_proposal_fund_key, 1_000)
                     .unwrap();
 
-                let proposal_key = namada::governance::storage::keys::get_proposal_execution_key(0);
-                shell.wl_storage.write(&proposal_key, 0).unwrap();
+                namada::governance::storage::write_pending_execution(
+                    &mut shell.wl_storage,
+                    0,
+                )
+                .unwrap();
 
                 // Return a dummy tx for validation
                 let mut tx =
@@ -7,8 +7,12 @@ use namada::core::types::key::{
     common, SecretKey as SecretKeyInterface, SigScheme,
 };
 use namada::governance::pgf::storage::steward::StewardDetail;
+use namada::governance::storage::keys::{
+    get_counter_key, get_legacy_vote_proposal_key,
+};
 use namada::governance::storage::proposal::ProposalType;
 use namada::governance::storage::vote::ProposalVote;
+use namada::governance::storage::VOTE_KEY_MIGRATION_BATCH_SIZE;
 use namada::governance::{InitProposalData, VoteProposalData};
 use namada::ibc::core::channel::types::channel::Order;
 use namada::ibc::core::channel::types::msgs::MsgChannelOpenInit;
@@ -43,11 +47,11 @@ use namada_apps::bench_utils::{
     TX_BRIDGE_POOL_WASM, TX_CHANGE_CONSENSUS_KEY_WASM,
     TX_CHANGE_VALIDATOR_COMMISSION_WASM, TX_CHANGE_VALIDATOR_METADATA_WASM,
     TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM,
-    TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL_WASM, TX_REACTIVATE_VALIDATOR_WASM,
-    TX_REDELEGATE_WASM, TX_RESIGN_STEWARD, TX_REVEAL_PK_WASM, TX_UNBOND_WASM,
-    TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
-    TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL_WASM, TX_WITHDRAW_WASM,
-    VP_USER_WASM,
+    TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL_WASM, TX_MIGRATE_VOTE_KEYS_WASM,
+    TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM, TX_RESIGN_STEWARD,
+    TX_REVEAL_PK_WASM, TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM,
+    TX_UPDATE_ACCOUNT_WASM, TX_UPDATE_STEWARD_COMMISSION,
+    TX_VOTE_PROPOSAL_WASM, TX_WITHDRAW_WASM, VP_USER_WASM,
 };
 use namada_apps::wallet::defaults;
 use sha2::Digest;
@@ -467,6 +471,9 @@ fn init_proposal(c: &mut Criterion) {
                                     voting_start_epoch: 12.into(),
                                     voting_end_epoch: 15.into(),
                                     grace_epoch: 18.into(),
+                                    voting_heights: None,
+                                    priority: None,
+                                    code_chunks: vec![],
                                 },
                                 None,
                                 Some(vec![content_section]),
@@ -519,6 +526,9 @@ fn init_proposal(c: &mut Criterion) {
                                     voting_start_epoch: 12.into(),
                                     voting_end_epoch: 15.into(),
                                     grace_epoch: 18.into(),
+                                    voting_heights: None,
+                                    priority: None,
+                                    code_chunks: vec![],
                                 },
                                 None,
                                 Some(vec![content_section, wasm_code_section]),
@@ -584,6 +594,53 @@ fn vote_proposal(c: &mut Criterion) {
     group.finish();
 }
 
+fn migrate_vote_keys(c: &mut Criterion) {
+    c.bench_function("migrate_vote_keys", |b| {
+        b.iter_batched_ref(
+            || {
+                let mut shell = BenchShell::default();
+                // A full batch of proposals, each with the legacy votes of a
+                // validator and of one of its delegators
+                shell
+                    .wl_storage
+                    .write(&get_counter_key(), VOTE_KEY_MIGRATION_BATCH_SIZE)
+                    .unwrap();
+                for id in 0..VOTE_KEY_MIGRATION_BATCH_SIZE {
+                    for voter in [
+                        defaults::validator_address(),
+                        defaults::albert_address(),
+                    ] {
+                        shell
+                            .wl_storage
+                            .write(
+                                &get_legacy_vote_proposal_key(
+                                    id,
+                                    voter,
+                                    defaults::validator_address(),
+                                ),
+                                ProposalVote::Yay,
+                            )
+                            .unwrap();
+                    }
+                }
+
+                // The code of a proposal gets the id of the proposal as data
+                let tx = shell.generate_tx(
+                    TX_MIGRATE_VOTE_KEYS_WASM,
+                    0_u64,
+                    None,
+                    None,
+                    vec![],
+                );
+
+                (shell, tx)
+            },
+            |(shell, tx)| shell.execute_tx(tx),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+}
+
 fn become_validator(c: &mut Criterion) {
     let mut csprng = rand::rngs::OsRng {};
     let address = address::testing::established_address_1();
@@ -1088,6 +1145,7 @@ criterion_group!(
     init_account,
     init_proposal,
     vote_proposal,
+    migrate_vote_keys,
     become_validator,
     change_validator_commission,
     ibc,
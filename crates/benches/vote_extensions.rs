@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use namada::core::types::address::testing::{
+    established_address_1, gen_established_address,
+};
+use namada::core::types::token;
+use namada::ethereum_bridge::protocol::validation::ethereum_events::{
+    validate_eth_events_vext, validate_eth_events_vext_batch,
+};
+use namada::ethereum_bridge::protocol::validation::timing as vext_timing;
+use namada::ethereum_bridge::protocol::validation::validator_set_update::{
+    validate_valset_upd_vext, validate_valset_upd_vext_batch,
+};
+use namada::ethereum_bridge::storage::eth_bridge_queries::EthBridgeQueries;
+use namada::ethereum_bridge::test_utils;
+use namada::vote_ext::{ethereum_events, validator_set_update};
+
+/// Measure the overhead of recording validation timings, by validating
+/// the same validator set update vote extension with the recorder
+/// disabled and enabled.
+fn valset_upd_vext_timing_overhead(c: &mut Criterion) {
+    let (wl_storage, keys) = test_utils::setup_default_storage();
+    let validator = established_address_1();
+    let voting_powers = wl_storage
+        .ethbridge_queries()
+        .get_consensus_eth_addresses(Some(1.into()))
+        .iter()
+        .map(|(addr_book, _, power)| (addr_book, power))
+        .collect();
+    let vext = validator_set_update::Vext {
+        voting_powers,
+        validator_addr: validator.clone(),
+        signing_epoch: 0.into(),
+    }
+    .sign(&keys.get(&validator).unwrap().eth_bridge);
+
+    let mut group = c.benchmark_group("valset_upd_vext_validation");
+    for (bench_name, enabled) in
+        [("timing_disabled", false), ("timing_enabled", true)]
+    {
+        vext_timing::set_enabled(enabled);
+        group.bench_function(bench_name, |b| {
+            b.iter(|| {
+                assert!(
+                    validate_valset_upd_vext(&wl_storage, &vext, 0.into())
+                        .is_ok()
+                );
+                // drain the timings, as the shell does once per block
+                vext_timing::take_block_timings()
+            })
+        });
+    }
+    vext_timing::set_enabled(false);
+    group.finish();
+}
+
+/// Compare the serial validation of the validator set update vote
+/// extensions of 150 validators with their batch validation, which
+/// verifies signatures in parallel.
+fn valset_upd_vext_batch_validation(c: &mut Criterion) {
+    let validators: HashMap<_, _> = (0..150)
+        .map(|_| (gen_established_address(), token::Amount::native_whole(100)))
+        .collect();
+    let (wl_storage, keys) =
+        test_utils::setup_storage_with_validators(validators);
+    let voting_powers: validator_set_update::VotingPowersMap = wl_storage
+        .ethbridge_queries()
+        .get_consensus_eth_addresses(Some(1.into()))
+        .iter()
+        .map(|(addr_book, _, power)| (addr_book, power))
+        .collect();
+    let vexts: Vec<_> = keys
+        .iter()
+        .map(|(validator, validator_keys)| {
+            validator_set_update::Vext {
+                voting_powers: voting_powers.clone(),
+                validator_addr: validator.clone(),
+                signing_epoch: 0.into(),
+            }
+            .sign(&validator_keys.eth_bridge)
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("valset_upd_vext_batch_validation");
+    group.bench_function("serial", |b| {
+        b.iter(|| {
+            for vext in &vexts {
+                assert!(
+                    validate_valset_upd_vext(&wl_storage, vext, 0.into())
+                        .is_ok()
+                );
+            }
+        })
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| {
+            let results =
+                validate_valset_upd_vext_batch(&wl_storage, &vexts, 0.into());
+            assert!(results.iter().all(Result::is_ok));
+        })
+    });
+    group.finish();
+}
+
+/// Compare the serial validation of the Ethereum events vote extensions
+/// of 150 validators with their batch validation, which looks up the
+/// consensus validator set once for the whole batch.
+fn eth_events_vext_batch_validation(c: &mut Criterion) {
+    let validators: HashMap<_, _> = (0..150)
+        .map(|_| (gen_established_address(), token::Amount::native_whole(100)))
+        .collect();
+    let (wl_storage, keys) =
+        test_utils::setup_storage_with_validators(validators);
+    let bridge_contract = wl_storage
+        .ethbridge_queries()
+        .get_contracts_at(wl_storage.storage.last_epoch)
+        .unwrap()
+        .bridge
+        .address;
+    let vexts: Vec<_> = keys
+        .iter()
+        .map(|(validator, validator_keys)| {
+            ethereum_events::Vext::empty(
+                1.into(),
+                validator.clone(),
+                bridge_contract,
+            )
+            .sign(&validator_keys.protocol)
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("eth_events_vext_batch_validation");
+    group.bench_function("serial", |b| {
+        b.iter(|| {
+            for vext in &vexts {
+                assert!(
+                    validate_eth_events_vext(&wl_storage, vext, 1.into())
+                        .is_ok()
+                );
+            }
+        })
+    });
+    group.bench_function("batch", |b| {
+        b.iter(|| {
+            let results =
+                validate_eth_events_vext_batch(&wl_storage, &vexts, 1.into());
+            assert!(results.iter().all(Result::is_ok));
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    vote_extensions,
+    valset_upd_vext_timing_overhead,
+    valset_upd_vext_batch_validation,
+    eth_events_vext_batch_validation
+);
+criterion_main!(vote_extensions);
@@ -6,6 +6,8 @@ use std::marker::PhantomData;
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 #[doc(inline)]
 pub use ethabi::token::Token;
+#[doc(inline)]
+pub use ethabi::{decode, Error as DecodeError, ParamType};
 
 use crate::types::keccak::{keccak_hash, KeccakHash};
 use crate::types::key::{Signable, SignableEthMessage};
@@ -33,6 +33,12 @@ pub struct Segments {
     pub signed_root: &'static str,
     /// Bridge pool nonce storage key
     pub bridge_pool_nonce: &'static str,
+    /// Storage key of the height at which the Bridge pool nonce was last
+    /// realigned by governance
+    pub nonce_realignment_height: &'static str,
+    /// Prefix of the storage keys of the number of transfers each sender
+    /// has pending in the Bridge pool
+    pub pending_per_sender: &'static str,
 }
 
 /// Check if a key is for a pending transfer
@@ -63,6 +69,18 @@ pub fn get_key_from_hash(hash: &KeccakHash) -> Key {
     }
 }
 
+/// Get the storage key for the number of transfers the given sender has
+/// pending in the Bridge pool
+pub fn get_pending_per_sender_key(sender: &Address) -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(BRIDGE_POOL_ADDRESS),
+            DbKeySeg::StringSeg(Segments::VALUES.pending_per_sender.into()),
+            DbKeySeg::AddressSeg(sender.clone()),
+        ],
+    }
+}
+
 /// A version used in our Ethereuem smart contracts
 const VERSION: u8 = 1;
 
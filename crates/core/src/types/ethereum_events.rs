@@ -265,6 +265,8 @@ impl From<TransfersToNamada> for EthereumEvent {
     BorshSerialize,
     BorshDeserialize,
     BorshSchema,
+    Serialize,
+    Deserialize,
 )]
 pub enum EthereumEvent {
     /// Event transferring batches of ether or Ethereum based ERC20 tokens
@@ -326,6 +328,8 @@ impl EthereumEvent {
     BorshSerialize,
     BorshDeserialize,
     BorshSchema,
+    Serialize,
+    Deserialize,
 )]
 pub struct TransferToNamada {
     /// Quantity of the ERC20 token in the transfer
@@ -9,7 +9,11 @@ pub use ethbridge_structs::*;
 use num256::Uint256;
 use serde::{Deserialize, Serialize};
 
+use crate::types::address::Address;
+use crate::types::ethereum_events::EthAddress;
 use crate::types::keccak::KeccakHash;
+use crate::types::storage::Epoch;
+use crate::types::voting_power::FractionalVotingPower;
 
 /// Status of some Bridge pool transfer.
 #[derive(
@@ -54,6 +58,31 @@ pub enum EthBridgeEvent {
         /// Status of the Bridge pool transfer.
         status: BpTransferStatus,
     },
+    /// The Ethereum bridge contracts scheduled by governance were
+    /// activated, superseding the previous ones.
+    ContractsActivated {
+        /// Epoch from which the new contracts are in effect.
+        activation_epoch: Epoch,
+        /// Address of the superseded native ERC20 contract.
+        old_native_erc20: EthAddress,
+        /// Address of the new native ERC20 contract.
+        new_native_erc20: EthAddress,
+        /// Address of the superseded bridge contract.
+        old_bridge: EthAddress,
+        /// Address of the new bridge contract.
+        new_bridge: EthAddress,
+    },
+    /// The signature of a validator over a validator set update was
+    /// included in the proof of the update.
+    ValsetSigIncluded {
+        /// Address of the validator whose signature was included.
+        validator: Address,
+        /// Epoch in which the validator set update was signed.
+        signing_epoch: Epoch,
+        /// Fraction of the voting power behind the proof, once the
+        /// signature of the validator was included.
+        voting_power: FractionalVotingPower,
+    },
 }
 
 impl EthBridgeEvent {
@@ -72,6 +101,38 @@ impl EthBridgeEvent {
             status: BpTransferStatus::Relayed,
         }
     }
+
+    /// Return a new event of the activation of the Ethereum bridge
+    /// contracts scheduled by governance.
+    pub const fn new_contracts_activated(
+        activation_epoch: Epoch,
+        old_native_erc20: EthAddress,
+        new_native_erc20: EthAddress,
+        old_bridge: EthAddress,
+        new_bridge: EthAddress,
+    ) -> Self {
+        Self::ContractsActivated {
+            activation_epoch,
+            old_native_erc20,
+            new_native_erc20,
+            old_bridge,
+            new_bridge,
+        }
+    }
+
+    /// Return a new event of the inclusion of the signature of a validator
+    /// in the proof of a validator set update.
+    pub const fn new_valset_sig_included(
+        validator: Address,
+        signing_epoch: Epoch,
+        voting_power: FractionalVotingPower,
+    ) -> Self {
+        Self::ValsetSigIncluded {
+            validator,
+            signing_epoch,
+            voting_power,
+        }
+    }
 }
 
 /// This type must be able to represent any valid Ethereum block height. It must
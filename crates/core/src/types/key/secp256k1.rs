@@ -469,6 +469,27 @@ impl Signature {
         let r = self.0.r();
         (r.to_bytes().into(), s.to_bytes().into(), v + Self::V_FIX)
     }
+
+    /// Recover the [`EthAddress`] of the key that produced this
+    /// [`Signature`], over the given pre-hashed message.
+    ///
+    /// A signature is valid for some Ethereum address if and only if
+    /// the address recovered from it matches.
+    pub fn recover_eth_address(
+        &self,
+        prehash: &[u8; 32],
+    ) -> Result<EthAddress, VerifySigError> {
+        let vrf_key = k256::ecdsa::VerifyingKey::recover_from_prehash(
+            prehash, &self.0, self.1,
+        )
+        .map_err(|e| {
+            VerifySigError::SigVerifyError(format!(
+                "Error recovering the key of a secp256k1 signature: {}",
+                e
+            ))
+        })?;
+        Ok((&PublicKey(k256::PublicKey::from(&vrf_key))).into())
+    }
 }
 
 impl Encode<1> for Signature {
@@ -658,4 +679,25 @@ mod test {
             .expect("Test failed");
         assert_eq!(sig, signature);
     }
+
+    /// Test that the Ethereum address of the signer can be recovered
+    /// from a signature over a pre-hashed message.
+    #[test]
+    fn test_recover_eth_address() {
+        use crate::types::hash::KeccakHasher;
+        use crate::types::keccak::KeccakHash;
+
+        let sk_bytes = HEXLOWER.decode(SECRET_KEY_HEX.as_bytes()).unwrap();
+        let sk = SecretKey::try_from_slice(&sk_bytes[..]).unwrap();
+        let eth_addr: EthAddress = (&sk.ref_to()).into();
+        let digest = KeccakHash([0xab; 32]);
+        let signature =
+            SigScheme::sign_with_hasher::<KeccakHasher>(&sk, &digest);
+
+        let recovered = signature.recover_eth_address(&digest.0).ok();
+        assert_eq!(recovered, Some(eth_addr));
+        let other = KeccakHash([0xcd; 32]);
+        let recovered = signature.recover_eth_address(&other.0).ok();
+        assert_ne!(recovered, Some(eth_addr));
+    }
 }
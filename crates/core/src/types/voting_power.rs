@@ -5,6 +5,7 @@ use std::fmt::{Display, Formatter};
 use std::io::Read;
 use std::iter::Sum;
 use std::ops::{Add, AddAssign, Mul};
+use std::str::FromStr;
 
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use ethabi::ethereum_types as ethereum;
@@ -238,6 +239,23 @@ impl Display for FractionalVotingPower {
     }
 }
 
+impl FromStr for FractionalVotingPower {
+    type Err = eyre::Report;
+
+    /// Parse a [`FractionalVotingPower`] from its [`Display`] form, i.e. a
+    /// `/` separated pair of numbers.
+    fn from_str(s: &str) -> Result<Self> {
+        let [numer, denom]: [&str; 2] = s
+            .split('/')
+            .collect::<Vec<&str>>()
+            .try_into()
+            .map_err(|_| eyre!("Expected a '/' separated pair of numbers"))?;
+        let numer = Uint::from_str_radix(numer.trim(), 10)?;
+        let denom = Uint::from_str_radix(denom.trim(), 10)?;
+        FractionalVotingPower::new(numer, denom)
+    }
+}
+
 impl BorshSerialize for FractionalVotingPower {
     fn serialize<W: std::io::Write>(
         &self,
@@ -307,16 +325,9 @@ impl<'de> Visitor<'de> for VPVisitor {
     where
         E: de::Error,
     {
-        let [numer, denom]: [&str; 2] =
-            value.split('/').collect::<Vec<&str>>().try_into().or(Err(
-                de::Error::custom("Expected a '/' separated pair of numbers"),
-            ))?;
-        let numer = Uint::from_str_radix(numer.trim(), 10)
-            .map_err(|e| de::Error::custom(e.to_string()))?;
-        let denom = Uint::from_str_radix(denom.trim(), 10)
-            .map_err(|e| de::Error::custom(e.to_string()))?;
-        FractionalVotingPower::new(numer, denom)
-            .map_err(|e| de::Error::custom(e.to_string()))
+        value
+            .parse()
+            .map_err(|e: eyre::Report| de::Error::custom(e.to_string()))
     }
 
     fn visit_string<E>(self, value: String) -> Result<Self::Value, E>
@@ -2,7 +2,6 @@ use std::collections::{HashMap, HashSet};
 
 use eyre::Result;
 use namada_core::types::address::Address;
-use namada_core::types::keccak::keccak_hash;
 use namada_core::types::key::{common, SignableEthMessage};
 use namada_core::types::storage::BlockHeight;
 use namada_core::types::token::Amount;
@@ -11,7 +10,9 @@ use namada_state::{DBIter, StorageHasher, WlStorage, DB};
 use namada_storage::{StorageRead, StorageWrite};
 use namada_tx::data::TxResult;
 use namada_tx::Signed;
-use namada_vote_ext::bridge_pool_roots::{self, MultiSignedVext, SignedVext};
+use namada_vote_ext::bridge_pool_roots::{
+    self, bp_root_digest, MultiSignedVext, SignedVext,
+};
 
 use crate::protocol::transactions::utils::GetVoters;
 use crate::protocol::transactions::votes::update::NewVotes;
@@ -37,15 +38,20 @@ where
     if !wl_storage.ethbridge_queries().is_bridge_active() {
         return None;
     }
-    let bp_root = wl_storage.ethbridge_queries().get_bridge_pool_root().0;
-    let nonce = wl_storage
+    let bp_root = wl_storage.ethbridge_queries().get_bridge_pool_root();
+    let nonce = wl_storage.ethbridge_queries().get_bridge_pool_nonce();
+    let block_height = wl_storage.storage.get_last_block_height();
+    // the root is bound to its height as soon as the bridge contract
+    // verifies roots signed in this format, as set by governance
+    let root_height = wl_storage
         .ethbridge_queries()
-        .get_bridge_pool_nonce()
-        .to_bytes();
-    let to_sign = keccak_hash([bp_root.as_slice(), nonce.as_slice()].concat());
+        .is_bp_root_height_bound_at(block_height)
+        .then_some(block_height);
+    let to_sign = bp_root_digest(&bp_root, nonce, root_height);
     let signed = Signed::<_, SignableEthMessage>::new(eth_hot_key, to_sign);
     let ext = bridge_pool_roots::Vext {
-        block_height: wl_storage.storage.get_last_block_height(),
+        block_height,
+        root_height,
         validator_addr: validator_addr.clone(),
         sig: signed.sig,
     };
@@ -62,7 +68,7 @@ where
 /// pool proofs.
 pub fn apply_derived_tx<D, H>(
     wl_storage: &mut WlStorage<D, H>,
-    vext: MultiSignedVext,
+    mut vext: MultiSignedVext,
 ) -> Result<TxResult>
 where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
@@ -76,8 +82,23 @@ where
         "Applying state updates derived from signatures of the Ethereum \
          bridge pool root and nonce."
     );
-    let voting_powers = utils::get_voting_powers(wl_storage, &vext)?;
     let root_height = vext.iter().next().unwrap().data.block_height;
+    // while roots signed in either format are accepted, only the signatures
+    // in the format in effect at the height of the root make up its proof,
+    // as the bridge contract verifies a single format
+    let height_bound = wl_storage
+        .ethbridge_queries()
+        .is_bp_root_height_bound_at(root_height);
+    vext.retain(|signed| signed.data.root_height.is_some() == height_bound);
+    if vext.is_empty() {
+        tracing::debug!(
+            ?root_height,
+            height_bound,
+            "Discarding Bridge pool root signatures in a format not in effect"
+        );
+        return Ok(TxResult::default());
+    }
+    let voting_powers = utils::get_voting_powers(wl_storage, &vext)?;
     let (partial_proof, seen_by) = parse_vexts(wl_storage, vext);
 
     // return immediately if a complete proof has already been acquired
@@ -262,7 +283,7 @@ mod test_apply_bp_roots_to_storage {
     use namada_core::types::address;
     use namada_core::types::ethereum_events::Uint;
     use namada_core::types::keccak::{keccak_hash, KeccakHash};
-    use namada_core::types::storage::Key;
+    use namada_core::types::storage::{Epoch, Key};
     use namada_core::types::voting_power::FractionalVotingPower;
     use namada_proof_of_stake::parameters::OwnedPosParams;
     use namada_proof_of_stake::storage::write_pos_params;
@@ -275,6 +296,7 @@ mod test_apply_bp_roots_to_storage {
         EpochedVotingPower, EpochedVotingPowerExt,
     };
     use crate::storage::bridge_pool::{get_key_from_hash, get_nonce_key};
+    use crate::storage::parameters::set_bp_root_height_binding_epoch;
     use crate::storage::vp;
     use crate::test_utils;
 
@@ -351,6 +373,7 @@ mod test_apply_bp_roots_to_storage {
         let vext = bridge_pool_roots::Vext {
             validator_addr: validators[0].clone(),
             block_height: 100.into(),
+            root_height: None,
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign.clone())
                 .sig,
         }
@@ -369,6 +392,7 @@ mod test_apply_bp_roots_to_storage {
         let vext = bridge_pool_roots::Vext {
             validator_addr: validators[2].clone(),
             block_height: 100.into(),
+            root_height: None,
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign).sig,
         }
         .sign(&keys[&validators[2]].protocol);
@@ -401,6 +425,7 @@ mod test_apply_bp_roots_to_storage {
         let mut vexts: MultiSignedVext = bridge_pool_roots::Vext {
             validator_addr: validators[0].clone(),
             block_height: 100.into(),
+            root_height: None,
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign.clone())
                 .sig,
         }
@@ -410,6 +435,7 @@ mod test_apply_bp_roots_to_storage {
         let vext = bridge_pool_roots::Vext {
             validator_addr: validators[1].clone(),
             block_height: 100.into(),
+            root_height: None,
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign).sig,
         }
         .sign(&keys[&validators[1]].protocol);
@@ -443,6 +469,7 @@ mod test_apply_bp_roots_to_storage {
         let vext = bridge_pool_roots::Vext {
             validator_addr: validators[0].clone(),
             block_height: 100.into(),
+            root_height: None,
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign.clone())
                 .sig,
         }
@@ -454,6 +481,7 @@ mod test_apply_bp_roots_to_storage {
         let vext = bridge_pool_roots::Vext {
             validator_addr: validators[1].clone(),
             block_height: 100.into(),
+            root_height: None,
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign).sig,
         }
         .sign(&keys[&validators[1]].protocol);
@@ -495,6 +523,7 @@ mod test_apply_bp_roots_to_storage {
         let vext = bridge_pool_roots::Vext {
             validator_addr: validators[0].clone(),
             block_height: 100.into(),
+            root_height: None,
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign.clone())
                 .sig,
         }
@@ -515,6 +544,7 @@ mod test_apply_bp_roots_to_storage {
         let vext = bridge_pool_roots::Vext {
             validator_addr: validators[1].clone(),
             block_height: 100.into(),
+            root_height: None,
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign).sig,
         }
         .sign(&keys[&validators[1]].protocol);
@@ -549,6 +579,7 @@ mod test_apply_bp_roots_to_storage {
         let vext = bridge_pool_roots::Vext {
             validator_addr: validators[0].clone(),
             block_height: 100.into(),
+            root_height: None,
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign.clone())
                 .sig,
         }
@@ -570,6 +601,7 @@ mod test_apply_bp_roots_to_storage {
         let vext = bridge_pool_roots::Vext {
             validator_addr: validators[1].clone(),
             block_height: 100.into(),
+            root_height: None,
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign).sig,
         }
         .sign(&keys[&validators[1]].protocol);
@@ -608,6 +640,7 @@ mod test_apply_bp_roots_to_storage {
         let vext = bridge_pool_roots::Vext {
             validator_addr: validators[0].clone(),
             block_height: 100.into(),
+            root_height: None,
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign.clone())
                 .sig,
         }
@@ -630,6 +663,7 @@ mod test_apply_bp_roots_to_storage {
         let vext = bridge_pool_roots::Vext {
             validator_addr: validators[1].clone(),
             block_height: 100.into(),
+            root_height: None,
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign).sig,
         }
         .sign(&keys[&validators[1]].protocol);
@@ -670,6 +704,7 @@ mod test_apply_bp_roots_to_storage {
         let vext = bridge_pool_roots::Vext {
             validator_addr: validators[0].clone(),
             block_height: 100.into(),
+            root_height: None,
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign).sig,
         };
         expected.0.attach_signature(
@@ -722,6 +757,7 @@ mod test_apply_bp_roots_to_storage {
         let mut vexts: MultiSignedVext = bridge_pool_roots::Vext {
             validator_addr: validators[0].clone(),
             block_height: 100.into(),
+            root_height: None,
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign.clone())
                 .sig,
         }
@@ -732,6 +768,7 @@ mod test_apply_bp_roots_to_storage {
         let vext = bridge_pool_roots::Vext {
             validator_addr: validators[1].clone(),
             block_height: 100.into(),
+            root_height: None,
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign).sig,
         }
         .sign(&keys[&validators[1]].protocol);
@@ -863,6 +900,7 @@ mod test_apply_bp_roots_to_storage {
         let vext = bridge_pool_roots::Vext {
             validator_addr: validator_1.clone(),
             block_height: 3.into(),
+            root_height: None,
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign).sig,
         }
         .sign(&keys[&validator_1].protocol);
@@ -907,6 +945,7 @@ mod test_apply_bp_roots_to_storage {
                 let vext = bridge_pool_roots::Vext {
                     validator_addr: validators[0].clone(),
                     block_height: $block_height.into(),
+                    root_height: None,
                     sig: Signed::<_, SignableEthMessage>::new(
                         hot_key,
                         to_sign.clone(),
@@ -920,6 +959,7 @@ mod test_apply_bp_roots_to_storage {
                 let vext = bridge_pool_roots::Vext {
                     validator_addr: validators[1].clone(),
                     block_height: $block_height.into(),
+                    root_height: None,
                     sig: Signed::<_, SignableEthMessage>::new(
                         hot_key,
                         to_sign.clone(),
@@ -960,4 +1000,62 @@ mod test_apply_bp_roots_to_storage {
                 if r.data.0 == root && r.data.1 == nonce
         );
     }
+
+    /// Test that only the signatures of a Bridge pool root in the format in
+    /// effect at its height make up its proof, such that roots signed along
+    /// with their height are discarded until the height binding epoch.
+    #[test]
+    fn test_bp_root_sigs_in_format_in_effect() {
+        let TestPackage {
+            validators,
+            keys,
+            mut wl_storage,
+        } = setup();
+        let root = wl_storage.ethbridge_queries().get_bridge_pool_root();
+        let nonce = wl_storage.ethbridge_queries().get_bridge_pool_nonce();
+        let root_height = Some(BlockHeight(100));
+        let to_sign = bp_root_digest(&root, nonce, root_height);
+        let vexts = MultiSignedVext(
+            validators[..2]
+                .iter()
+                .map(|validator| {
+                    bridge_pool_roots::Vext {
+                        validator_addr: validator.clone(),
+                        block_height: 100.into(),
+                        root_height,
+                        sig: Signed::<_, SignableEthMessage>::new(
+                            &keys[validator].eth_bridge,
+                            to_sign.clone(),
+                        )
+                        .sig,
+                    }
+                    .sign(&keys[validator].protocol)
+                })
+                .collect(),
+        );
+
+        // roots are not bound to their height yet
+        let TxResult { changed_keys, .. } =
+            apply_derived_tx(&mut wl_storage, vexts.clone())
+                .expect("Test failed");
+        assert!(changed_keys.is_empty());
+        assert!(
+            !wl_storage
+                .has_key(&get_signed_root_key())
+                .expect("Test failed")
+        );
+
+        // from the height binding epoch, the root is signed by a quorum
+        set_bp_root_height_binding_epoch(&mut wl_storage, Epoch(0))
+            .expect("Test failed");
+        let TxResult { changed_keys, .. } =
+            apply_derived_tx(&mut wl_storage, vexts).expect("Test failed");
+        assert!(changed_keys.contains(&get_signed_root_key()));
+        let (BridgePoolRoot(proof), height) = wl_storage
+            .read::<(BridgePoolRoot, BlockHeight)>(&get_signed_root_key())
+            .expect("Test failed")
+            .expect("Test failed");
+        assert_eq!(height, BlockHeight(100));
+        assert_eq!(proof.signatures.len(), 2);
+    }
 }
@@ -0,0 +1,268 @@
+//! Code for handling contract addresses protocol txs.
+
+use std::collections::HashSet;
+
+use eyre::Result;
+use namada_core::types::address::Address;
+use namada_core::types::storage::{BlockHeight, Epoch};
+use namada_state::{DBIter, StorageHasher, WlStorage, DB};
+use namada_tx::data::TxResult;
+use namada_vote_ext::contract_addresses::{self, ContractAddresses};
+
+use crate::protocol::transactions::utils;
+use crate::protocol::transactions::votes::update::NewVotes;
+use crate::protocol::transactions::votes::{self, Votes};
+use crate::storage::eth_bridge_queries::EthBridgeQueries;
+use crate::storage::parameters::read_scheduled_contracts_update;
+use crate::storage::proof::ContractAddressesProof;
+use crate::storage::vote_tallies;
+
+impl utils::GetVoters for (&contract_addresses::SignedVext, BlockHeight) {
+    #[inline]
+    fn get_voters(self) -> HashSet<(Address, BlockHeight)> {
+        // votes were cast at the 2nd block height of the ext's signing epoch
+        let (ext, epoch_2nd_height) = self;
+        HashSet::from([(ext.data.validator_addr.clone(), epoch_2nd_height)])
+    }
+}
+
+/// Build the unsigned contract addresses vote extension of the given
+/// validator, over the Ethereum bridge contracts scheduled by governance,
+/// if one must be sent.
+///
+/// The extension is signed over the current epoch, until a complete proof
+/// of the contract addresses is acquired at that epoch. It is returned
+/// unsigned, such that it can be signed with both the Ethereum hot key
+/// and the Ethereum cold key of the validator, possibly outside of the
+/// ledger.
+pub fn contract_addresses_to_sign<D, H>(
+    wl_storage: &WlStorage<D, H>,
+    validator_addr: &Address,
+) -> Result<Option<contract_addresses::Vext>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let Some(scheduled) = read_scheduled_contracts_update(wl_storage)? else {
+        return Ok(None);
+    };
+    let signing_epoch = wl_storage.storage.get_current_epoch().0;
+    let seen = wl_storage
+        .ethbridge_queries()
+        .contract_addresses_seen(signing_epoch);
+    Ok((!seen).then(|| contract_addresses::Vext {
+        contracts: ContractAddresses {
+            native_erc20: scheduled.contracts.native_erc20,
+            bridge: scheduled.contracts.bridge.address,
+        },
+        validator_addr: validator_addr.clone(),
+        signing_epoch,
+    }))
+}
+
+/// Aggregate the vote of some validator over the addresses of the Ethereum
+/// bridge contracts scheduled by governance into their proof, signed at
+/// the signing epoch of the vote extension.
+///
+/// Once the proof is backed by a quorum of validators, it is complete, and
+/// further votes at the same epoch are ignored.
+pub fn aggregate_votes<D, H>(
+    wl_storage: &mut WlStorage<D, H>,
+    ext: contract_addresses::SignedVext,
+) -> Result<TxResult>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let signing_epoch = ext.data.signing_epoch;
+    let validator = ext.data.validator_addr.clone();
+    tracing::info!(
+        %validator,
+        ?signing_epoch,
+        "Aggregating a new vote for the addresses of the Ethereum bridge \
+         contracts"
+    );
+
+    let epoch_2nd_height = epoch_2nd_height(wl_storage, signing_epoch);
+    let voting_powers =
+        utils::get_voting_powers(wl_storage, (&ext, epoch_2nd_height))?;
+    let keys = vote_tallies::contract_addresses_keys(&signing_epoch);
+    let addr_book = wl_storage
+        .ethbridge_queries()
+        .get_eth_addr_book(&validator, Some(signing_epoch))
+        .expect("All validators should have eth keys");
+    let seen_by = Votes::from([(validator, epoch_2nd_height)]);
+
+    let (tally, proof, changed, already_present) =
+        match votes::storage::maybe_read_seen(wl_storage, &keys)? {
+            Some(true) => {
+                tracing::debug!(
+                    ?signing_epoch,
+                    "Contract addresses tally is already seen"
+                );
+                return Ok(Default::default());
+            }
+            Some(false) => {
+                let mut proof: ContractAddressesProof =
+                    votes::storage::read_body(wl_storage, &keys)?;
+                let new_votes = NewVotes::new(seen_by, &voting_powers)?;
+                let (tally, changed) =
+                    votes::update::calculate(wl_storage, &keys, new_votes)?;
+                if changed.is_empty() {
+                    return Ok(Default::default());
+                }
+                proof.attach_signatures(addr_book, ext.hot.sig, ext.cold_sig);
+                (tally, proof, changed, true)
+            }
+            None => {
+                let tally =
+                    votes::calculate_new(wl_storage, seen_by, &voting_powers)?;
+                let mut proof = ContractAddressesProof::new((
+                    signing_epoch,
+                    ext.hot.data.contracts,
+                ));
+                proof.attach_signatures(addr_book, ext.hot.sig, ext.cold_sig);
+                (tally, proof, keys.into_iter().collect(), false)
+            }
+        };
+
+    votes::storage::write(wl_storage, &keys, &proof, &tally, already_present)?;
+    if tally.seen {
+        tracing::info!(
+            ?signing_epoch,
+            "Acquired complete proof on the addresses of the Ethereum bridge \
+             contracts"
+        );
+    }
+
+    Ok(TxResult {
+        changed_keys: changed,
+        ..Default::default()
+    })
+}
+
+/// Get the 2nd block height of the given epoch, at which the votes signed
+/// over that epoch are cast.
+fn epoch_2nd_height<D, H>(
+    wl_storage: &WlStorage<D, H>,
+    signing_epoch: Epoch,
+) -> BlockHeight
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    wl_storage
+        .storage
+        .block
+        .pred_epochs
+        .get_start_height_of_epoch(signing_epoch)
+        .expect("The first block height of the signing epoch should be known")
+        + 1
+}
+
+#[cfg(test)]
+mod test_contract_addresses_state_changes {
+    use std::collections::HashMap;
+
+    use namada_core::types::ethereum_events::EthAddress;
+    use namada_core::types::key::common;
+    use namada_proof_of_stake::pos_queries::PosQueries;
+
+    use super::*;
+    use crate::storage::parameters::{
+        schedule_contracts_update, ContractVersion, Contracts,
+        ScheduledContractsUpdate, UpgradeableContract,
+    };
+    use crate::test_utils;
+
+    /// The addresses of the contracts scheduled in the tests.
+    const NEW_CONTRACTS: ContractAddresses = ContractAddresses {
+        native_erc20: EthAddress([0xaa; 20]),
+        bridge: EthAddress([0xbb; 20]),
+    };
+
+    /// Test that the vote of the only validator over the scheduled
+    /// contracts completes their proof, holding both its hot and cold key
+    /// signatures, after which no further vote extension must be sent.
+    #[test]
+    fn test_seen_has_complete_proof() {
+        let (mut wl_storage, keys) = test_utils::setup_default_storage();
+        let (validator, _) = test_utils::default_validator();
+        assert!(
+            contract_addresses_to_sign(&wl_storage, &validator)
+                .expect("Test failed")
+                .is_none()
+        );
+
+        schedule_contracts_update(
+            &mut wl_storage,
+            ScheduledContractsUpdate {
+                activation_epoch: 5.into(),
+                contracts: Contracts {
+                    native_erc20: NEW_CONTRACTS.native_erc20,
+                    bridge: UpgradeableContract {
+                        address: NEW_CONTRACTS.bridge,
+                        version: ContractVersion::default(),
+                    },
+                },
+                bridge_pool_nonce: None,
+            },
+        )
+        .expect("Test failed");
+        let last_height = wl_storage.storage.get_last_block_height();
+        let signing_epoch = wl_storage
+            .pos_queries()
+            .get_epoch(last_height)
+            .expect("The epoch of the last block height should be known");
+        let ext = contract_addresses_to_sign(&wl_storage, &validator)
+            .expect("Test failed")
+            .expect("Test failed");
+        assert_eq!(ext.contracts, NEW_CONTRACTS);
+        assert_eq!(ext.signing_epoch, signing_epoch);
+
+        let validator_keys = keys.get(&validator).expect("Test failed");
+        let signed = ext.sign_with_cold_key(
+            &validator_keys.eth_bridge,
+            &validator_keys.eth_gov,
+        );
+        let tx_result = aggregate_votes(&mut wl_storage, signed.clone())
+            .expect("Test failed");
+
+        let proof_keys = vote_tallies::contract_addresses_keys(&signing_epoch);
+        assert!(tx_result.changed_keys.contains(&proof_keys.body()));
+        assert!(tx_result.changed_keys.contains(&proof_keys.seen()));
+        let tally = votes::storage::read(&wl_storage, &proof_keys)
+            .expect("Test failed");
+        assert!(tally.seen);
+        let proof: ContractAddressesProof =
+            votes::storage::read_body(&wl_storage, &proof_keys)
+                .expect("Test failed");
+        assert_eq!(proof.hot.data, (signing_epoch, NEW_CONTRACTS));
+        let addr_book = wl_storage
+            .ethbridge_queries()
+            .get_eth_addr_book(&validator, Some(signing_epoch))
+            .expect("Test failed");
+        let common::Signature::Secp256k1(hot_sig) = signed.hot.sig else {
+            panic!("Test failed");
+        };
+        let Some(common::Signature::Secp256k1(cold_sig)) = signed.cold_sig
+        else {
+            panic!("Test failed");
+        };
+        assert_eq!(
+            proof.hot.signatures,
+            HashMap::from([(addr_book.clone(), hot_sig)])
+        );
+        assert_eq!(
+            proof.cold_signatures,
+            HashMap::from([(addr_book, cold_sig)])
+        );
+
+        // the proof is complete, so no more votes are needed
+        assert!(
+            contract_addresses_to_sign(&wl_storage, &validator)
+                .expect("Test failed")
+                .is_none()
+        );
+    }
+}
@@ -26,7 +26,8 @@ use namada_trans_token::storage_key::{balance_key, minted_balance_key};
 
 use crate::protocol::transactions::update;
 use crate::storage::bridge_pool::{
-    get_nonce_key, is_pending_transfer_key, BRIDGE_POOL_ADDRESS,
+    get_nonce_key, get_pending_per_sender_key, is_pending_transfer_key,
+    BRIDGE_POOL_ADDRESS,
 };
 use crate::storage::eth_bridge_queries::{EthAssetMint, EthBridgeQueries};
 use crate::storage::parameters::read_native_erc20_address;
@@ -267,40 +268,17 @@ where
     // minted from the iterator
     .flatten();
 
-    for (token, ref amount) in assets_to_mint {
-        let balance_key = balance_key(&token, receiver);
-        update::amount(wl_storage, &balance_key, |balance| {
-            tracing::debug!(
-                %balance_key,
-                ?balance,
-                "Existing value found",
-            );
-            balance.receive(amount)?;
-            tracing::debug!(
-                %balance_key,
-                ?balance,
-                "New value calculated",
-            );
-            Ok(())
-        })?;
-        _ = changed_keys.insert(balance_key);
-
-        let supply_key = minted_balance_key(&token);
-        update::amount(wl_storage, &supply_key, |supply| {
-            tracing::debug!(
-                %supply_key,
-                ?supply,
-                "Existing value found",
-            );
-            supply.receive(amount)?;
-            tracing::debug!(
-                %supply_key,
-                ?supply,
-                "New value calculated",
-            );
-            Ok(())
-        })?;
-        _ = changed_keys.insert(supply_key);
+    for (token, amount) in assets_to_mint {
+        // the minted supply is updated along with the balance
+        token::credit_tokens(wl_storage, &token, receiver, amount)?;
+        tracing::debug!(
+            %token,
+            %receiver,
+            amount = %amount.to_string_native(),
+            "Minted wrapped Ethereum asset",
+        );
+        _ = changed_keys.insert(balance_key(&token, receiver));
+        _ = changed_keys.insert(minted_balance_key(&token));
     }
 
     Ok((asset_count, changed_keys))
@@ -369,6 +347,10 @@ where
             balance.spend(&pending_transfer.gas_fee.amount)
         })?;
         wl_storage.delete(&key)?;
+        changed_keys.extend(decrement_pending_per_sender(
+            wl_storage,
+            &pending_transfer.transfer.sender,
+        )?);
         _ = pending_keys.remove(&key);
         _ = changed_keys.insert(key);
         _ = changed_keys.insert(pool_balance_key);
@@ -444,6 +426,10 @@ where
 
     // Delete the key from the bridge pool
     wl_storage.delete(&key)?;
+    changed_keys.extend(decrement_pending_per_sender(
+        wl_storage,
+        &transfer.transfer.sender,
+    )?);
     _ = changed_keys.insert(key);
 
     // Emit expiration event
@@ -454,6 +440,29 @@ where
     Ok((changed_keys, tx_events))
 }
 
+/// Remove a transfer that left the Bridge pool from the count of pending
+/// transfers of its sender, returning the changed key, if any.
+fn decrement_pending_per_sender<D, H>(
+    wl_storage: &mut WlStorage<D, H>,
+    sender: &Address,
+) -> Result<Option<Key>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let key = get_pending_per_sender_key(sender);
+    // transfers added before the pending transfers of each sender were
+    // counted are not accounted for
+    let Some(pending) = wl_storage.read::<u64>(&key)? else {
+        return Ok(None);
+    };
+    match pending.checked_sub(1) {
+        Some(0) | None => wl_storage.delete(&key)?,
+        Some(pending) => wl_storage.write(&key, pending)?,
+    }
+    Ok(Some(key))
+}
+
 fn refund_transfer_fees<D, H>(
     wl_storage: &mut WlStorage<D, H>,
     transfer: &PendingTransfer,
@@ -562,19 +571,17 @@ where
         return Ok(changed_keys);
     }
 
-    // other asset kinds must be burned
-
-    let escrow_balance_key = balance_key(&token, &BRIDGE_POOL_ADDRESS);
-    update::amount(wl_storage, &escrow_balance_key, |balance| {
-        balance.spend(&transfer.transfer.amount)
-    })?;
-    _ = changed_keys.insert(escrow_balance_key);
+    // other asset kinds must be burned from escrow, along with their
+    // minted supply
 
-    let supply_key = minted_balance_key(&token);
-    update::amount(wl_storage, &supply_key, |supply| {
-        supply.spend(&transfer.transfer.amount)
-    })?;
-    _ = changed_keys.insert(supply_key);
+    token::burn_exact_tokens(
+        wl_storage,
+        &token,
+        &BRIDGE_POOL_ADDRESS,
+        transfer.transfer.amount,
+    )?;
+    _ = changed_keys.insert(balance_key(&token, &BRIDGE_POOL_ADDRESS));
+    _ = changed_keys.insert(minted_balance_key(&token));
 
     tracing::debug!(?transfer, "Burned wrapped ERC20 tokens");
     Ok(changed_keys)
@@ -932,6 +939,8 @@ mod tests {
                         assert_matches!(value, Some(amount) if amount == expected_amount);
                     }
                 }
+                wrapped_erc20s::check_supply_invariant(&wl_storage, &wdai)
+                    .expect("Test failed");
             }
         }
     }
@@ -1246,6 +1255,58 @@ mod tests {
         }
     }
 
+    #[test]
+    /// Test that transfers leaving the Bridge pool, either relayed or
+    /// expired, are no longer counted as pending transfers of their sender
+    fn test_act_on_decrements_pending_per_sender() {
+        let mut wl_storage = TestWlStorage::default();
+        test_utils::bootstrap_ethereum_bridge(&mut wl_storage);
+        wl_storage.commit_block().expect("Test failed");
+        init_storage(&mut wl_storage);
+        // Height 0: the sender fills its cap of three pending transfers
+        let sender = address::testing::established_address_1();
+        let per_sender_key = get_pending_per_sender_key(&sender);
+        let mut pending_transfers = init_bridge_pool(&mut wl_storage);
+        pending_transfers.extend(init_bridge_pool_transfers(
+            &mut wl_storage,
+            [(EthAddress([2; 20]), TransferData::default())],
+        ));
+        init_balance(&mut wl_storage, &pending_transfers);
+        wl_storage
+            .storage
+            .write(&per_sender_key, 3u64.serialize_to_vec())
+            .expect("Test failed");
+        wl_storage
+            .storage
+            .commit_block(MockDBWriteBatch)
+            .expect("Test failed");
+        wl_storage.storage.block.height += 1;
+
+        // one transfer is relayed, before the others time out
+        let relayed = pending_transfers.pop().expect("Test failed");
+        let event = EthereumEvent::TransfersToEthereum {
+            nonce: arbitrary_nonce(),
+            transfers: vec![TransferToEthereum::from(&relayed)],
+            relayer: gen_implicit_address(),
+        };
+        let (changed_keys, _) = act_on(&mut wl_storage, event).unwrap();
+        assert!(changed_keys.contains(&per_sender_key));
+        let pending: Option<u64> =
+            wl_storage.read(&per_sender_key).expect("Test failed");
+        assert_eq!(pending, Some(2));
+
+        // the other transfers time out
+        wl_storage.storage.block.height += 10 + 1;
+        let event = EthereumEvent::TransfersToEthereum {
+            nonce: arbitrary_nonce(),
+            transfers: vec![],
+            relayer: gen_implicit_address(),
+        };
+        let (changed_keys, _) = act_on(&mut wl_storage, event).unwrap();
+        assert!(changed_keys.contains(&per_sender_key));
+        assert!(!wl_storage.has_key(&per_sender_key).expect("Test failed"));
+    }
+
     #[test]
     fn test_redeem_native_token() -> Result<()> {
         let mut wl_storage = TestWlStorage::default();
@@ -1490,6 +1551,11 @@ mod tests {
 
                 assert_eq!(balance, burn_balance);
                 assert_eq!(supply, burn_supply);
+                wrapped_erc20s::check_supply_invariant(
+                    wl_storage,
+                    &erc20_token,
+                )
+                .expect("Test failed");
             }
         })
     }
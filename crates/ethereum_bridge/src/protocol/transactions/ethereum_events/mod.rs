@@ -56,10 +56,25 @@ where
         return None;
     }
 
+    let bridge_contract = match wl_storage
+        .ethbridge_queries()
+        .get_contracts_at(wl_storage.storage.last_epoch)
+    {
+        Ok(contracts) => contracts.bridge.address,
+        Err(err) => {
+            tracing::error!(
+                %err,
+                "Failed to read the Ethereum bridge contracts, skipping the \
+                 Ethereum events vote extension"
+            );
+            return None;
+        }
+    };
     let ext = Vext {
         block_height: wl_storage.storage.get_last_block_height(),
         validator_addr: validator_addr.clone(),
         ethereum_events,
+        bridge_contract,
     };
     if !ext.ethereum_events.is_empty() {
         tracing::info!(
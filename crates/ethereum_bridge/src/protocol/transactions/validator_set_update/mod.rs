@@ -1,20 +1,26 @@
 //! Code for handling validator set update protocol txs.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 use eyre::Result;
 use namada_core::types::address::Address;
+use namada_core::types::ethereum_structs::EthBridgeEvent;
 use namada_core::types::key::common;
 use namada_core::types::storage::{BlockHeight, Epoch};
 use namada_core::types::token::Amount;
-use namada_state::{DBIter, StorageHasher, WlStorage, DB};
+use namada_core::types::voting_power::FractionalVotingPower;
+use namada_governance::storage as gov_storage;
+use namada_governance::storage::keys as gov_keys;
+use namada_state::{DBIter, StorageHasher, StorageRead, WlStorage, DB};
 use namada_tx::data::TxResult;
 use namada_vote_ext::validator_set_update;
 
 use super::ChangedKeys;
 use crate::protocol::transactions::utils;
 use crate::protocol::transactions::votes::update::NewVotes;
-use crate::protocol::transactions::votes::{self, Votes};
+use crate::protocol::transactions::votes::{
+    self, EpochedVotingPowerExt, Tally, Votes,
+};
 use crate::storage::eth_bridge_queries::{EthBridgeQueries, SendValsetUpd};
 use crate::storage::proof::EthereumProof;
 use crate::storage::vote_tallies;
@@ -34,11 +40,35 @@ impl utils::GetVoters for (&validator_set_update::VextDigest, BlockHeight) {
 
 /// Sign the next set of validators, and return the associated
 /// vote extension protocol transaction.
+///
+/// The extension is signed over a message separated to the domain of
+/// the chain, once domain separation is in effect at its signing epoch.
 pub fn sign_validator_set_update<D, H>(
     wl_storage: &WlStorage<D, H>,
     validator_addr: &Address,
     eth_hot_key: &common::SecretKey,
 ) -> Option<validator_set_update::SignedVext>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    valset_upd_to_sign(wl_storage, validator_addr).map(|ext| {
+        let domain = wl_storage
+            .ethbridge_queries()
+            .valset_upd_signing_domain(ext.signing_epoch);
+        ext.sign_in_domain(eth_hot_key, domain.as_ref())
+    })
+}
+
+/// Build the unsigned validator set update vote extension of the given
+/// validator, over the next set of validators, if one must be sent.
+///
+/// This allows the extension to be signed outside of the ledger, e.g. by
+/// a remote signer holding the Ethereum hot key of the validator.
+pub fn valset_upd_to_sign<D, H>(
+    wl_storage: &WlStorage<D, H>,
+    validator_addr: &Address,
+) -> Option<validator_set_update::Vext>
 where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
     H: 'static + StorageHasher + Sync,
@@ -58,13 +88,11 @@ where
                 })
                 .collect();
 
-            let ext = validator_set_update::Vext {
+            validator_set_update::Vext {
                 voting_powers,
                 validator_addr: validator_addr.clone(),
                 signing_epoch: wl_storage.storage.get_current_epoch().0,
-            };
-
-            ext.sign(eth_hot_key)
+            }
         })
 }
 
@@ -101,7 +129,7 @@ where
         + 1;
     let voting_powers =
         utils::get_voting_powers(wl_storage, (&ext, epoch_2nd_height))?;
-    let changed_keys = apply_update(
+    let (changed_keys, eth_bridge_events) = apply_update(
         wl_storage,
         ext,
         signing_epoch,
@@ -111,6 +139,7 @@ where
 
     Ok(TxResult {
         changed_keys,
+        eth_bridge_events,
         ..Default::default()
     })
 }
@@ -121,7 +150,7 @@ fn apply_update<D, H>(
     signing_epoch: Epoch,
     epoch_2nd_height: BlockHeight,
     voting_powers: HashMap<(Address, BlockHeight), Amount>,
-) -> Result<ChangedKeys>
+) -> Result<(ChangedKeys, BTreeSet<EthBridgeEvent>)>
 where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
     H: 'static + StorageHasher + Sync,
@@ -139,7 +168,7 @@ where
         };
         if seen {
             tracing::debug!("Validator set update tally is already seen");
-            return Ok(ChangedKeys::default());
+            return Ok(Default::default());
         }
         let proof = votes::storage::read_body(wl_storage, &valset_upd_keys)?;
         Some(proof)
@@ -154,12 +183,15 @@ where
         }
     }
 
-    let (tally, proof, changed, confirmed, already_present) =
+    let (tally, proof, mut changed, confirmed, already_present, seen_by_pre) =
         if let Some(mut proof) = maybe_proof {
             tracing::debug!(
                 %valset_upd_keys.prefix,
                 "Validator set update votes already in storage",
             );
+            let seen_by_pre: Votes = wl_storage
+                .read(&valset_upd_keys.seen_by())?
+                .unwrap_or_default();
             let new_votes = NewVotes::new(seen_by, &voting_powers)?;
             let (tally, changed) = votes::update::calculate(
                 wl_storage,
@@ -167,7 +199,7 @@ where
                 new_votes,
             )?;
             if changed.is_empty() {
-                return Ok(changed);
+                return Ok(Default::default());
             }
             let confirmed =
                 tally.seen && changed.contains(&valset_upd_keys.seen());
@@ -182,7 +214,7 @@ where
                     )
                 },
             ));
-            (tally, proof, changed, confirmed, true)
+            (tally, proof, changed, confirmed, true, seen_by_pre)
         } else {
             tracing::debug!(
                 %valset_upd_keys.prefix,
@@ -205,7 +237,7 @@ where
             ));
             let changed = valset_upd_keys.into_iter().collect();
             let confirmed = tally.seen;
-            (tally, proof, changed, confirmed, false)
+            (tally, proof, changed, confirmed, false, Votes::default())
         };
 
     tracing::debug!(
@@ -228,7 +260,82 @@ where
         );
     }
 
-    Ok(changed)
+    let events = sig_included_events(
+        wl_storage,
+        &mut changed,
+        &tally,
+        &seen_by_pre,
+        signing_epoch,
+        &voting_powers,
+    )?;
+    Ok((changed, events))
+}
+
+/// Build the events of the validators whose signatures were included in
+/// the proof of a validator set update, i.e. the voters counted by the
+/// given tally which weren't counted before, and record their signatures
+/// in the governance participation statistics. Re-submitted extensions of
+/// validators who were already counted don't emit any event.
+///
+/// Each event carries the fraction of the voting power behind the proof
+/// once the signature of its validator was included, accumulating the new
+/// voters in the order of their addresses.
+fn sig_included_events<D, H>(
+    wl_storage: &mut WlStorage<D, H>,
+    changed: &mut ChangedKeys,
+    tally: &Tally,
+    seen_by_pre: &Votes,
+    signing_epoch: Epoch,
+    voting_powers: &HashMap<(Address, BlockHeight), Amount>,
+) -> Result<BTreeSet<EthBridgeEvent>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let new_voters: Vec<_> = tally
+        .seen_by
+        .iter()
+        .filter(|(validator, _)| !seen_by_pre.contains_key(validator))
+        .map(|(validator, height)| {
+            let power = voting_powers
+                .get(&(validator.clone(), *height))
+                .copied()
+                .unwrap_or_default();
+            (validator, power)
+        })
+        .collect();
+    let new_stake: Amount = new_voters.iter().map(|(_, power)| *power).sum();
+    let max_voting_power =
+        tally.voting_power.epoch_max_voting_power(wl_storage);
+    let mut running_stake = tally
+        .voting_power
+        .tallied_stake()
+        .checked_sub(new_stake)
+        .unwrap_or_default();
+    let participation_key =
+        gov_keys::get_valset_sig_participation_key(signing_epoch);
+    let mut events = BTreeSet::new();
+    for (validator, power) in new_voters {
+        running_stake += power;
+        let voting_power = max_voting_power
+            .and_then(|max| {
+                FractionalVotingPower::new(running_stake.into(), max.into())
+                    .ok()
+            })
+            .unwrap_or(FractionalVotingPower::NULL);
+        gov_storage::write_valset_sig_participation(
+            wl_storage,
+            signing_epoch,
+            validator.clone(),
+        )?;
+        changed.insert(participation_key.clone());
+        events.insert(EthBridgeEvent::new_valset_sig_included(
+            validator.clone(),
+            signing_epoch,
+            voting_power,
+        ));
+    }
+    Ok(events)
 }
 
 #[cfg(test)]
@@ -436,4 +543,135 @@ mod test_valset_upd_state_changes {
 
         assert!(voting_power <= FractionalVotingPower::TWO_THIRDS);
     }
+
+    /// Test that the inclusion of the signature of each validator in the
+    /// proof of a validator set update emits an event, once
+    #[test]
+    fn test_valset_sig_included_events() {
+        let validator_1 = address::testing::established_address_1();
+        let validator_2 = address::testing::established_address_2();
+        let (mut wl_storage, keys) =
+            test_utils::setup_storage_with_validators(HashMap::from_iter([
+                (validator_1.clone(), Amount::native_whole(50_000)),
+                (validator_2.clone(), Amount::native_whole(25_000)),
+            ]));
+        let last_height = wl_storage.storage.get_last_block_height();
+        let signing_epoch = wl_storage
+            .pos_queries()
+            .get_epoch(last_height)
+            .expect("The epoch of the last block height should be known");
+        let ext = |validator: &Address| {
+            validator_set_update::VextDigest::singleton(
+                validator_set_update::Vext {
+                    voting_powers: VotingPowersMap::new(),
+                    validator_addr: validator.clone(),
+                    signing_epoch,
+                }
+                .sign(&keys.get(validator).expect("Test failed").eth_bridge),
+            )
+        };
+
+        let tx_result =
+            aggregate_votes(&mut wl_storage, ext(&validator_1), signing_epoch)
+                .expect("Test failed");
+        assert_eq!(
+            tx_result.eth_bridge_events,
+            BTreeSet::from([EthBridgeEvent::new_valset_sig_included(
+                validator_1.clone(),
+                signing_epoch,
+                FractionalVotingPower::TWO_THIRDS,
+            )])
+        );
+
+        // re-submitting the same extension doesn't emit another event
+        let tx_result =
+            aggregate_votes(&mut wl_storage, ext(&validator_1), signing_epoch)
+                .expect("Test failed");
+        assert!(tx_result.changed_keys.is_empty());
+        assert!(tx_result.eth_bridge_events.is_empty());
+
+        // the running voting power accounts for the earlier signatures
+        let tx_result =
+            aggregate_votes(&mut wl_storage, ext(&validator_2), signing_epoch)
+                .expect("Test failed");
+        assert_eq!(
+            tx_result.eth_bridge_events,
+            BTreeSet::from([EthBridgeEvent::new_valset_sig_included(
+                validator_2,
+                signing_epoch,
+                FractionalVotingPower::WHOLE,
+            )])
+        );
+
+        // no signatures are included once the proof is complete
+        let tx_result =
+            aggregate_votes(&mut wl_storage, ext(&validator_1), signing_epoch)
+                .expect("Test failed");
+        assert!(tx_result.eth_bridge_events.is_empty());
+    }
+
+    /// Test that the signatures included in the proof of a validator set
+    /// update are recorded in the governance participation statistics.
+    #[test]
+    fn test_valset_sig_included_participation() {
+        let validator_1 = address::testing::established_address_1();
+        let validator_2 = address::testing::established_address_2();
+        let (mut wl_storage, keys) =
+            test_utils::setup_storage_with_validators(HashMap::from_iter([
+                (validator_1.clone(), Amount::native_whole(50_000)),
+                (validator_2.clone(), Amount::native_whole(25_000)),
+            ]));
+        let last_height = wl_storage.storage.get_last_block_height();
+        let signing_epoch = wl_storage
+            .pos_queries()
+            .get_epoch(last_height)
+            .expect("The epoch of the last block height should be known");
+        let ext = |validator: &Address| {
+            validator_set_update::VextDigest::singleton(
+                validator_set_update::Vext {
+                    voting_powers: VotingPowersMap::new(),
+                    validator_addr: validator.clone(),
+                    signing_epoch,
+                }
+                .sign(&keys.get(validator).expect("Test failed").eth_bridge),
+            )
+        };
+        let participation_key =
+            gov_keys::get_valset_sig_participation_key(signing_epoch);
+
+        assert_eq!(
+            gov_storage::get_valset_sig_participation_at(
+                &wl_storage,
+                signing_epoch
+            )
+            .expect("Test failed"),
+            None
+        );
+
+        let tx_result =
+            aggregate_votes(&mut wl_storage, ext(&validator_1), signing_epoch)
+                .expect("Test failed");
+        assert!(tx_result.changed_keys.contains(&participation_key));
+        assert_eq!(
+            gov_storage::get_valset_sig_participation_at(
+                &wl_storage,
+                signing_epoch
+            )
+            .expect("Test failed"),
+            Some(BTreeSet::from([validator_1.clone()]))
+        );
+
+        let tx_result =
+            aggregate_votes(&mut wl_storage, ext(&validator_2), signing_epoch)
+                .expect("Test failed");
+        assert!(tx_result.changed_keys.contains(&participation_key));
+        assert_eq!(
+            gov_storage::get_valset_sig_participation_at(
+                &wl_storage,
+                signing_epoch
+            )
+            .expect("Test failed"),
+            Some(BTreeSet::from([validator_1, validator_2]))
+        );
+    }
 }
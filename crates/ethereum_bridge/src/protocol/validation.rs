@@ -1,14 +1,61 @@
 //! Validation logic for Ethereum bridge protocol actions.
 
+pub mod batch;
 pub mod bridge_pool_roots;
+pub mod contract_addresses;
 pub mod ethereum_events;
+pub mod timing;
 pub mod validator_set_update;
 
+use borsh::{BorshDeserialize, BorshSerialize};
 use thiserror::Error;
 
-/// The error yielded from validating faulty vote extensions.
-#[derive(Error, Debug)]
-pub enum VoteExtensionError {
+/// The Ethereum keys of a validator that must sign a kind of vote
+/// extension, for it to be valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequiredEthKeys {
+    /// Only the Ethereum hot key of the validator.
+    Hot,
+    /// Both the Ethereum hot key and the Ethereum cold key of the
+    /// validator, for high-value operations.
+    HotAndCold,
+}
+
+/// The kinds of vote extensions validated by the Ethereum bridge.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize,
+)]
+pub enum ExtensionKind {
+    /// Ethereum events vote extensions.
+    EthereumEvents,
+    /// Bridge pool root vote extensions.
+    BridgePoolRoot,
+    /// Validator set update vote extensions.
+    ValidatorSetUpdate,
+    /// Contract addresses vote extensions.
+    ContractAddresses,
+}
+
+/// The error yielded from validating faulty vote extensions, tagged with
+/// the kind of vote extension that produced it.
+///
+/// It is displayed as the [`InvalidVextReason`] it carries.
+#[derive(
+    Error, Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize,
+)]
+#[error("{reason}")]
+pub struct VoteExtensionError {
+    /// The kind of the faulty vote extension.
+    pub kind: ExtensionKind,
+    /// The reason why the vote extension is faulty.
+    pub reason: InvalidVextReason,
+}
+
+/// The reasons why a vote extension may be faulty.
+#[derive(
+    Error, Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize,
+)]
+pub enum InvalidVextReason {
     #[error(
         "A validator set update proof is already available in storage for the \
          given epoch"
@@ -52,4 +99,62 @@ pub enum VoteExtensionError {
          not active"
     )]
     EthereumBridgeInactive,
+    #[error(
+        "The Ethereum events were observed from another bridge contract than \
+         the one in effect at the epoch of the vote extension"
+    )]
+    UnexpectedBridgeContract,
+    #[error(
+        "Received a vote extension for the Ethereum bridge which is currently \
+         paused by governance"
+    )]
+    BridgePaused,
+    #[error(
+        "The consensus validator set the vote extension should sign over is \
+         not available in storage"
+    )]
+    ConsensusSetUnavailable,
+    #[error(
+        "The Bridge pool root was signed under a nonce that was realigned by \
+         governance"
+    )]
+    StaleBridgePoolNonce,
+    #[error(
+        "Received a contract addresses vote extension, but no update of the \
+         Ethereum bridge contracts was scheduled by governance"
+    )]
+    NoContractsUpdateScheduled,
+    #[error(
+        "The vote extension is missing a signature with the Ethereum cold key \
+         of its associated validator"
+    )]
+    ColdKeySigMissing,
+    #[error(
+        "The normalized voting powers in the validator set update vote \
+         extension sum past the total voting power of the Ethereum bridge"
+    )]
+    NormalizedSumOverflow,
+    #[error(
+        "The Bridge pool root was signed without its height once required, or \
+         along with another height than the one it was computed at"
+    )]
+    InvalidBPRootHeight,
+    #[error(
+        "The validator set update vote extension has more voting powers than \
+         the maximum number of consensus validators"
+    )]
+    ExtensionTooLarge,
+    #[error(
+        "The validator set update vote extension was signed over the last \
+         representable epoch, past which there is no epoch to sign over"
+    )]
+    EpochOverflow,
+}
+
+impl InvalidVextReason {
+    /// Tag this reason with the kind of the faulty vote extension.
+    #[inline]
+    pub const fn of(self, kind: ExtensionKind) -> VoteExtensionError {
+        VoteExtensionError { kind, reason: self }
+    }
 }
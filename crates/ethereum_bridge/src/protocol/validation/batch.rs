@@ -0,0 +1,197 @@
+//! Batch validation of vote extensions.
+//!
+//! Validating a vote extension consists of a number of checks against
+//! storage, followed by the verification of one or more signatures.
+//! Storage access isn't `Sync`, hence the checks of a batch of vote
+//! extensions against storage are performed serially, on the calling
+//! thread. The signatures of the extensions that passed these checks are
+//! then verified in parallel, over the global [`rayon`] thread pool.
+//!
+//! The results of a batch are returned in the same order as its vote
+//! extensions, and are identical to the results of validating each of
+//! the vote extensions on its own.
+//!
+//! The metadata of the consensus validators looked up by the checks is
+//! kept in a [`ValidatorDetailsCache`], such that the consensus set of
+//! each epoch is only read once per batch.
+
+use std::collections::BTreeMap;
+
+use namada_core::types::address::Address;
+use namada_core::types::storage::Epoch;
+use namada_proof_of_stake::pos_queries::{PosQueries, ValidatorDetails};
+use namada_state::{DBIter, StorageHasher, WlStorage, DB};
+use rayon::prelude::*;
+
+use super::VoteExtensionError;
+
+/// Cache of the [`ValidatorDetails`] of the consensus validators of
+/// each epoch, looked up while validating vote extensions.
+#[derive(Debug, Default)]
+pub struct ValidatorDetailsCache {
+    epochs: BTreeMap<Epoch, Vec<ValidatorDetails>>,
+}
+
+impl ValidatorDetailsCache {
+    /// Get the consensus validators of the given epoch, reading them from
+    /// storage if they haven't been cached yet.
+    pub fn get<D, H>(
+        &mut self,
+        wl_storage: &WlStorage<D, H>,
+        epoch: Epoch,
+    ) -> &[ValidatorDetails]
+    where
+        D: 'static + DB + for<'iter> DBIter<'iter>,
+        H: 'static + StorageHasher,
+    {
+        self.epochs.entry(epoch).or_insert_with(|| {
+            wl_storage
+                .pos_queries()
+                .consensus_validator_details(Some(epoch))
+        })
+    }
+
+    /// Look up a consensus validator of the given epoch.
+    pub fn find<D, H>(
+        &mut self,
+        wl_storage: &WlStorage<D, H>,
+        epoch: Epoch,
+        validator: &Address,
+    ) -> Option<&ValidatorDetails>
+    where
+        D: 'static + DB + for<'iter> DBIter<'iter>,
+        H: 'static + StorageHasher,
+    {
+        self.get(wl_storage, epoch)
+            .iter()
+            .find(|details| &details.address == validator)
+    }
+}
+
+/// Verify the signatures of a batch of vote extensions in parallel.
+///
+/// Each element of `checked` holds the outcome of the checks against
+/// storage of some vote extension, which carries the data needed to
+/// verify its signatures if these checks passed. Signatures are only
+/// verified for these extensions. The returned results are in the same
+/// order as `checked`.
+pub fn verify_signatures_par<T, F>(
+    checked: Vec<Result<T, VoteExtensionError>>,
+    verify: F,
+) -> Vec<Result<(), VoteExtensionError>>
+where
+    T: Send,
+    F: Fn(T) -> Result<(), VoteExtensionError> + Sync,
+{
+    checked
+        .into_par_iter()
+        .map(|checked| checked.and_then(&verify))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use namada_core::types::address::testing::gen_established_address;
+    use namada_core::types::key::RefTo;
+    use namada_core::types::token;
+    use namada_proof_of_stake::types::ValidatorState;
+
+    use super::*;
+    use crate::protocol::validation::{ExtensionKind, InvalidVextReason};
+    use crate::test_utils;
+
+    /// Test that the results of a batch are returned in input order, and
+    /// that signatures are only verified for the extensions that passed
+    /// the checks against storage.
+    #[test]
+    fn test_verify_signatures_par_order() {
+        const KIND: ExtensionKind = ExtensionKind::EthereumEvents;
+        let checked: Vec<Result<u64, _>> = (0..1_000)
+            .map(|i| {
+                if i % 3 == 0 {
+                    Err(InvalidVextReason::PubKeyNotInStorage.of(KIND))
+                } else {
+                    Ok(i)
+                }
+            })
+            .collect();
+        let results = verify_signatures_par(checked, |i| {
+            assert_ne!(i % 3, 0);
+            if i % 3 == 1 {
+                Ok(())
+            } else {
+                Err(InvalidVextReason::VerifySigFailed.of(KIND))
+            }
+        });
+        assert_eq!(results.len(), 1_000);
+        for (i, result) in results.into_iter().enumerate() {
+            let expected = match i % 3 {
+                0 => Err(InvalidVextReason::PubKeyNotInStorage.of(KIND)),
+                1 => Ok(()),
+                _ => Err(InvalidVextReason::VerifySigFailed.of(KIND)),
+            };
+            assert_eq!(result, expected);
+        }
+    }
+
+    /// Test that the cached consensus validators are sorted by descending
+    /// stake and then by address, and carry the keys of each validator.
+    #[test]
+    fn test_validator_details_cache_order() {
+        let validators: HashMap<_, _> = [100, 50, 100, 200, 50]
+            .into_iter()
+            .map(|stake| {
+                (
+                    gen_established_address(),
+                    token::Amount::native_whole(stake),
+                )
+            })
+            .collect();
+        let (wl_storage, keys) =
+            test_utils::setup_storage_with_validators(validators.clone());
+        let mut cache = ValidatorDetailsCache::default();
+        let details = cache.get(&wl_storage, 0.into()).to_vec();
+
+        let mut expected: Vec<_> = validators.into_iter().collect();
+        expected.sort_by(|(addr_a, stake_a), (addr_b, stake_b)| {
+            stake_b.cmp(stake_a).then_with(|| addr_a.cmp(addr_b))
+        });
+        let got: Vec<_> = details
+            .iter()
+            .map(|validator| (validator.address.clone(), validator.stake))
+            .collect();
+        assert_eq!(got, expected);
+        for validator in &details {
+            let validator_keys = &keys[&validator.address];
+            assert_eq!(
+                validator.protocol_key,
+                Some(validator_keys.protocol.ref_to())
+            );
+            assert_eq!(
+                validator.eth_hot_key,
+                Some(validator_keys.eth_bridge.ref_to())
+            );
+            assert_eq!(
+                validator.eth_cold_key,
+                Some(validator_keys.eth_gov.ref_to())
+            );
+            assert_eq!(validator.state, Some(ValidatorState::Consensus));
+        }
+
+        // the cached validators are those read from storage, and
+        // unknown validators are not found
+        assert_eq!(
+            details,
+            wl_storage
+                .pos_queries()
+                .consensus_validator_details(Some(0.into()))
+        );
+        assert!(
+            cache
+                .find(&wl_storage, 0.into(), &gen_established_address())
+                .is_none()
+        );
+    }
+}
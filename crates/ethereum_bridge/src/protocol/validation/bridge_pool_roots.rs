@@ -1,15 +1,20 @@
 //! Bridge pool roots validation.
 
-use namada_core::types::keccak::keccak_hash;
+use namada_core::types::keccak::KeccakHash;
+use namada_core::types::key::common;
 use namada_core::types::storage::BlockHeight;
 use namada_proof_of_stake::pos_queries::PosQueries;
 use namada_state::{DBIter, StorageHasher, WlStorage, DB};
 use namada_tx::{SignableEthMessage, Signed};
-use namada_vote_ext::bridge_pool_roots;
+use namada_vote_ext::bridge_pool_roots::{self, bp_root_digest};
 
-use super::VoteExtensionError;
+use super::timing::{self, ExtensionGuard, Phase};
+use super::{batch, ExtensionKind, InvalidVextReason, VoteExtensionError};
 use crate::storage::eth_bridge_queries::EthBridgeQueries;
 
+/// The kind of the vote extensions validated in this module.
+const KIND: ExtensionKind = ExtensionKind::BridgePoolRoot;
+
 /// Validates a vote extension issued at the provided
 /// block height signing over the latest Ethereum bridge
 /// pool root and nonce.
@@ -17,6 +22,10 @@ use crate::storage::eth_bridge_queries::EthBridgeQueries;
 /// Checks that at epoch of the provided height:
 ///  * The inner Namada address corresponds to a consensus validator.
 ///  * Check that the root and nonce are correct.
+///  * The extension was issued after the Bridge pool nonce was last realigned
+///    by governance, if it ever was.
+///  * The root is signed along with the height it was computed at, if any, and
+///    that it is from the height binding epoch set by governance.
 ///  * The validator correctly signed the extension.
 ///  * The validator signed over the correct height inside of the extension.
 ///  * Check that the inner signature is valid.
@@ -25,34 +34,102 @@ pub fn validate_bp_roots_vext<D, H>(
     ext: &Signed<bridge_pool_roots::Vext>,
     last_height: BlockHeight,
 ) -> Result<(), VoteExtensionError>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    let _guard = ExtensionGuard::new("bp_roots", &ext.data.validator_addr);
+    let sig_data = validate_bp_roots_vext_data(wl_storage, ext, last_height)?;
+    verify_bp_roots_vext_sigs(ext, sig_data)
+}
+
+/// Validates a batch of Bridge pool root vote extensions issued at the
+/// provided block height, returning the result of each extension in the
+/// order of `exts`.
+///
+/// The results are identical to those of [`validate_bp_roots_vext`],
+/// but the signatures of the extensions are verified in parallel. Refer
+/// to [`batch`] for more details.
+pub fn validate_bp_roots_vext_batch<D, H>(
+    wl_storage: &WlStorage<D, H>,
+    exts: &[Signed<bridge_pool_roots::Vext>],
+    last_height: BlockHeight,
+) -> Vec<Result<(), VoteExtensionError>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    let checked = exts
+        .iter()
+        .map(|ext| {
+            let _guard =
+                ExtensionGuard::new("bp_roots", &ext.data.validator_addr);
+            validate_bp_roots_vext_data(wl_storage, ext, last_height)
+                .map(|sig_data| (ext, sig_data))
+        })
+        .collect();
+    batch::verify_signatures_par(checked, |(ext, sig_data)| {
+        verify_bp_roots_vext_sigs(ext, sig_data)
+    })
+}
+
+/// The data read from storage to verify the signatures of a Bridge pool
+/// root vote extension.
+struct BpRootSigData {
+    /// The protocol key of the signing validator.
+    protocol_pk: common::PublicKey,
+    /// The Bridge pool root and nonce, signed by the validator.
+    signed_root: Signed<KeccakHash, SignableEthMessage>,
+    /// The Ethereum hot key of the signing validator.
+    eth_hot_pk: common::PublicKey,
+}
+
+/// Validates the data of a Bridge pool root vote extension, returning
+/// the data needed to verify its signatures.
+fn validate_bp_roots_vext_data<D, H>(
+    wl_storage: &WlStorage<D, H>,
+    ext: &Signed<bridge_pool_roots::Vext>,
+    last_height: BlockHeight,
+) -> Result<BpRootSigData, VoteExtensionError>
 where
     D: 'static + DB + for<'iter> DBIter<'iter>,
     H: 'static + StorageHasher,
 {
     // NOTE: for ABCI++, we should pass
     // `last_height` here, instead of `ext.data.block_height`
-    let ext_height_epoch =
-        match wl_storage.pos_queries().get_epoch(ext.data.block_height) {
-            Some(epoch) => epoch,
-            _ => {
-                tracing::debug!(
-                    block_height = ?ext.data.block_height,
-                    "The epoch of the Bridge pool root's vote extension's \
-                     block height should always be known",
-                );
-                return Err(VoteExtensionError::UnexpectedEpoch);
-            }
-        };
-    if !wl_storage
-        .ethbridge_queries()
-        .is_bridge_active_at(ext_height_epoch)
-    {
+    let ext_height_epoch = match timing::record(Phase::StorageRead, || {
+        wl_storage.pos_queries().get_epoch(ext.data.block_height)
+    }) {
+        Some(epoch) => epoch,
+        _ => {
+            tracing::debug!(
+                block_height = ?ext.data.block_height,
+                "The epoch of the Bridge pool root's vote extension's \
+                 block height should always be known",
+            );
+            return Err(InvalidVextReason::UnexpectedEpoch.of(KIND));
+        }
+    };
+    if !timing::record(Phase::StorageRead, || {
+        wl_storage
+            .ethbridge_queries()
+            .is_bridge_active_at(ext_height_epoch)
+    }) {
         tracing::debug!(
             vext_epoch = ?ext_height_epoch,
             "The Ethereum bridge was not enabled when the pool
              root's vote extension was cast",
         );
-        return Err(VoteExtensionError::EthereumBridgeInactive);
+        return Err(InvalidVextReason::EthereumBridgeInactive.of(KIND));
+    }
+    if timing::record(Phase::StorageRead, || {
+        wl_storage.ethbridge_queries().is_bridge_paused()
+    }) {
+        tracing::debug!(
+            "Dropping Bridge pool root vote extension, since the Ethereum \
+             bridge is paused"
+        );
+        return Err(InvalidVextReason::BridgePaused.of(KIND));
     }
 
     if ext.data.block_height > last_height {
@@ -62,67 +139,141 @@ where
             "Bridge pool root's vote extension issued for a block height \
              higher than the chain's last height."
         );
-        return Err(VoteExtensionError::UnexpectedBlockHeight);
+        return Err(InvalidVextReason::UnexpectedBlockHeight.of(KIND));
     }
     if ext.data.block_height.0 == 0 {
         tracing::debug!("Dropping vote extension issued at genesis");
-        return Err(VoteExtensionError::UnexpectedBlockHeight);
+        return Err(InvalidVextReason::UnexpectedBlockHeight.of(KIND));
+    }
+    // roots signed before a realignment of the nonce can't be relayed
+    // to the redeployed bridge contract
+    if let Some(realignment_height) = timing::record(Phase::StorageRead, || {
+        wl_storage
+            .ethbridge_queries()
+            .get_bridge_pool_nonce_realignment_height()
+    }) {
+        if ext.data.block_height < realignment_height {
+            tracing::debug!(
+                ext_height = ?ext.data.block_height,
+                ?realignment_height,
+                "Dropping Bridge pool root vote extension signed under a \
+                 nonce realigned by governance"
+            );
+            return Err(InvalidVextReason::StaleBridgePoolNonce.of(KIND));
+        }
     }
 
-    // get the public key associated with this validator
-    let validator = &ext.data.validator_addr;
-    let (_, pk) = wl_storage
-        .pos_queries()
-        .get_validator_from_address(validator, Some(ext_height_epoch))
-        .map_err(|err| {
+    // roots signed without their height could be relayed after newer
+    // transfers were added to the pool, hence these are rejected once the
+    // bridge contract verifies roots signed along with their height
+    match ext.data.root_height {
+        Some(root_height) if root_height != ext.data.block_height => {
+            tracing::debug!(
+                ext_height = ?ext.data.block_height,
+                ?root_height,
+                "Dropping Bridge pool root vote extension signed along with \
+                 another height than the one the root was computed at"
+            );
+            return Err(InvalidVextReason::InvalidBPRootHeight.of(KIND));
+        }
+        None if timing::record(Phase::StorageRead, || {
+            wl_storage
+                .ethbridge_queries()
+                .is_bp_root_height_bound_at(ext.data.block_height)
+        }) =>
+        {
             tracing::debug!(
-                ?err,
-                %validator,
-                "Could not get public key from Storage for some validator, \
-                 while validating Bridge pool root's vote extension"
+                ext_height = ?ext.data.block_height,
+                "Dropping Bridge pool root vote extension signed without \
+                 its height, past the height binding epoch"
             );
-            VoteExtensionError::PubKeyNotInStorage
-        })?;
+            return Err(InvalidVextReason::InvalidBPRootHeight.of(KIND));
+        }
+        _ => {}
+    }
+
+    // get the public key associated with this validator
+    let validator = &ext.data.validator_addr;
+    let (_, pk) = timing::record(Phase::StorageRead, || {
+        wl_storage
+            .pos_queries()
+            .get_validator_from_address(validator, Some(ext_height_epoch))
+    })
+    .map_err(|err| {
+        tracing::debug!(
+            ?err,
+            %validator,
+            "Could not get public key from Storage for some validator, \
+             while validating Bridge pool root's vote extension"
+        );
+        InvalidVextReason::PubKeyNotInStorage.of(KIND)
+    })?;
+    let (bp_root, nonce, eth_hot_pk) =
+        timing::record(Phase::StorageRead, || {
+            let bp_root = wl_storage
+                .ethbridge_queries()
+                .get_bridge_pool_root_at_height(ext.data.block_height)
+                .expect("We asserted that the queried height is correct");
+            let nonce = wl_storage
+                .ethbridge_queries()
+                .get_bridge_pool_nonce_at_height(ext.data.block_height);
+            let eth_hot_pk = wl_storage
+                .pos_queries()
+                .read_validator_eth_hot_key(validator, Some(ext_height_epoch))
+                .expect(
+                    "A validator should have an Ethereum hot key in storage.",
+                );
+            (bp_root, nonce, eth_hot_pk)
+        });
+    let signed_root = Signed::<_, SignableEthMessage>::new_from(
+        timing::record(Phase::Encoding, || {
+            bp_root_digest(&bp_root, nonce, ext.data.root_height)
+        }),
+        ext.data.sig.clone(),
+    );
+    Ok(BpRootSigData {
+        protocol_pk: pk,
+        signed_root,
+        eth_hot_pk,
+    })
+}
+
+/// Verifies the signature of a Bridge pool root vote extension, against
+/// the protocol key of the signing validator, and the signature of the
+/// Bridge pool root and nonce inside of it, against the Ethereum hot key
+/// of the signing validator.
+fn verify_bp_roots_vext_sigs(
+    ext: &Signed<bridge_pool_roots::Vext>,
+    sig_data: BpRootSigData,
+) -> Result<(), VoteExtensionError> {
+    let BpRootSigData {
+        protocol_pk,
+        signed_root,
+        eth_hot_pk,
+    } = sig_data;
+    let validator = &ext.data.validator_addr;
     // verify the signature of the vote extension
-    ext.verify(&pk).map_err(|err| {
+    timing::verify_signed(ext, &protocol_pk).map_err(|err| {
         tracing::debug!(
             ?err,
             ?ext.sig,
-            ?pk,
+            ?protocol_pk,
             %validator,
             "Failed to verify the signature of an Bridge pool root's vote \
              extension issued by some validator"
         );
-        VoteExtensionError::VerifySigFailed
+        InvalidVextReason::VerifySigFailed.of(KIND)
     })?;
-
-    let bp_root = wl_storage
-        .ethbridge_queries()
-        .get_bridge_pool_root_at_height(ext.data.block_height)
-        .expect("We asserted that the queried height is correct")
-        .0;
-    let nonce = wl_storage
-        .ethbridge_queries()
-        .get_bridge_pool_nonce_at_height(ext.data.block_height)
-        .to_bytes();
-    let signed = Signed::<_, SignableEthMessage>::new_from(
-        keccak_hash([bp_root, nonce].concat()),
-        ext.data.sig.clone(),
-    );
-    let pk = wl_storage
-        .pos_queries()
-        .read_validator_eth_hot_key(validator, Some(ext_height_epoch))
-        .expect("A validator should have an Ethereum hot key in storage.");
-    signed.verify(&pk).map_err(|err| {
+    timing::verify_signed(&signed_root, &eth_hot_pk).map_err(|err| {
         tracing::debug!(
             ?err,
-            ?signed.sig,
-            ?pk,
+            ?signed_root.sig,
+            ?eth_hot_pk,
             %validator,
             "Failed to verify the signature of an Bridge pool root \
             issued by some validator."
         );
-        VoteExtensionError::InvalidBPRootSig
+        InvalidVextReason::InvalidBPRootSig.of(KIND)
     })?;
     Ok(())
 }
@@ -0,0 +1,411 @@
+//! Contract addresses validation.
+
+use namada_core::types::key::common;
+use namada_core::types::storage::Epoch;
+use namada_state::{DBIter, StorageHasher, WlStorage, DB};
+use namada_tx::verify_standalone_sig;
+use namada_vote_ext::contract_addresses::{
+    self, ContractAddresses, SerializeWithAbiEncode,
+};
+
+use super::batch::ValidatorDetailsCache;
+use super::timing::{self, ExtensionGuard, Phase};
+use super::{
+    ExtensionKind, InvalidVextReason, RequiredEthKeys, VoteExtensionError,
+};
+use crate::storage::parameters::read_scheduled_contracts_update;
+
+/// The kind of the vote extensions validated in this module.
+const KIND: ExtensionKind = ExtensionKind::ContractAddresses;
+
+/// The Ethereum keys that must sign contract addresses vote extensions.
+/// Migrating the bridge contracts is a high-value operation, hence the
+/// cold key of each validator must sign off on it too.
+pub const REQUIRED_ETH_KEYS: RequiredEthKeys = RequiredEthKeys::HotAndCold;
+
+/// Validates a contract addresses vote extension issued at the epoch
+/// provided as an argument.
+///
+/// # Validation checks
+///
+/// To validate a [`contract_addresses::SignedVext`], Namada nodes
+/// check if:
+///
+///  * The vote extension was not issued at genesis.
+///  * The signing validator is a consensus validator during the epoch
+///    `signing_epoch` inside the extension.
+///  * The validator signed over the epoch inside of the extension, whose value
+///    should not be greater than `last_epoch`.
+///  * A contracts update has been scheduled by governance, and the contract
+///    addresses in the vote extension match the addresses of the scheduled
+///    contracts.
+///  * The validator correctly signed the extension, with its Ethereum hot key.
+///  * The validator correctly signed the extension with its Ethereum cold key
+///    too, as required by [`REQUIRED_ETH_KEYS`].
+pub fn validate_contract_addresses_vext<D, H>(
+    wl_storage: &WlStorage<D, H>,
+    ext: &contract_addresses::SignedVext,
+    last_epoch: Epoch,
+) -> Result<(), VoteExtensionError>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    validate_contract_addresses_vext_with_cache(
+        wl_storage,
+        ext,
+        last_epoch,
+        &mut ValidatorDetailsCache::default(),
+    )
+}
+
+/// Validates a contract addresses vote extension issued at the epoch
+/// provided as an argument, like [`validate_contract_addresses_vext`],
+/// looking up the signing validator in `validators`.
+///
+/// This allows the same cache to be shared by all the vote extensions of
+/// a block.
+pub fn validate_contract_addresses_vext_with_cache<D, H>(
+    wl_storage: &WlStorage<D, H>,
+    ext: &contract_addresses::SignedVext,
+    last_epoch: Epoch,
+    validators: &mut ValidatorDetailsCache,
+) -> Result<(), VoteExtensionError>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    let _guard =
+        ExtensionGuard::new("contract_addresses", &ext.data.validator_addr);
+    let (pk, cold_pk) = validate_contract_addresses_vext_data(
+        wl_storage, &ext.data, last_epoch, validators,
+    )?;
+    timing::verify_signed(ext, &pk).map_err(|err| {
+        tracing::debug!(
+            ?err,
+            ?ext.sig,
+            ?pk,
+            validator = %ext.data.validator_addr,
+            "Failed to verify the signature of a contract addresses vote \
+             extension issued by some validator"
+        );
+        InvalidVextReason::VerifySigFailed.of(KIND)
+    })?;
+    if REQUIRED_ETH_KEYS == RequiredEthKeys::HotAndCold {
+        verify_cold_key_sig(ext, cold_pk)?;
+    }
+    Ok(())
+}
+
+/// Verify the signature of a contract addresses vote extension with the
+/// Ethereum cold key of its validator, if it's found in storage.
+fn verify_cold_key_sig(
+    ext: &contract_addresses::SignedVext,
+    cold_pk: Option<common::PublicKey>,
+) -> Result<(), VoteExtensionError> {
+    let validator = &ext.data.validator_addr;
+    let cold_sig = ext.cold_sig.as_ref().ok_or_else(|| {
+        tracing::debug!(
+            %validator,
+            "Contract addresses vote extension is missing a signature with \
+             the Ethereum cold key of its validator"
+        );
+        InvalidVextReason::ColdKeySigMissing.of(KIND)
+    })?;
+    let cold_pk = cold_pk.ok_or_else(|| {
+        tracing::debug!(
+            %validator,
+            "Could not get Ethereum cold key from Storage for some consensus \
+             validator, while validating contract addresses vote extension"
+        );
+        InvalidVextReason::PubKeyNotInStorage.of(KIND)
+    })?;
+    timing::record(Phase::SigVerification, || {
+        verify_standalone_sig::<_, SerializeWithAbiEncode>(
+            &ext.data, &cold_pk, cold_sig,
+        )
+    })
+    .map_err(|err| {
+        tracing::debug!(
+            ?err,
+            ?cold_sig,
+            ?cold_pk,
+            %validator,
+            "Failed to verify the cold key signature of a contract addresses \
+             vote extension issued by some validator"
+        );
+        InvalidVextReason::VerifySigFailed.of(KIND)
+    })
+}
+
+/// Validates the data of a contract addresses vote extension,
+/// returning the Ethereum hot key of the signing validator, along with
+/// its Ethereum cold key, if any.
+///
+/// The signing validator is looked up in `validators`.
+fn validate_contract_addresses_vext_data<D, H>(
+    wl_storage: &WlStorage<D, H>,
+    ext: &contract_addresses::Vext,
+    last_epoch: Epoch,
+    validators: &mut ValidatorDetailsCache,
+) -> Result<(common::PublicKey, Option<common::PublicKey>), VoteExtensionError>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    if wl_storage.storage.last_block.is_none() {
+        tracing::debug!(
+            "Dropping contract addresses vote extension issued at genesis"
+        );
+        return Err(InvalidVextReason::UnexpectedBlockHeight.of(KIND));
+    }
+    let signing_epoch = ext.signing_epoch;
+    if signing_epoch > last_epoch {
+        tracing::debug!(
+            vext_epoch = ?signing_epoch,
+            ?last_epoch,
+            "Contract addresses vote extension issued for an epoch greater \
+             than the last one.",
+        );
+        return Err(InvalidVextReason::UnexpectedEpoch.of(KIND));
+    }
+    let scheduled = timing::record(Phase::StorageRead, || {
+        read_scheduled_contracts_update(wl_storage)
+    })
+    .map_err(|err| {
+        tracing::debug!(
+            ?err,
+            "Could not read the scheduled Ethereum bridge contracts update \
+             from storage, while validating contract addresses vote extension"
+        );
+        InvalidVextReason::NoContractsUpdateScheduled.of(KIND)
+    })?
+    .ok_or_else(|| {
+        let err = InvalidVextReason::NoContractsUpdateScheduled.of(KIND);
+        tracing::debug!("{err}");
+        err
+    })?;
+    let expected = ContractAddresses {
+        native_erc20: scheduled.contracts.native_erc20,
+        bridge: scheduled.contracts.bridge.address,
+    };
+    if ext.contracts != expected {
+        tracing::debug!(
+            ?expected,
+            got = ?ext.contracts,
+            "Found unexpected contract addresses in contract addresses vote \
+             extension",
+        );
+        return Err(InvalidVextReason::DivergesFromStorage.of(KIND));
+    }
+    // get the public key associated with this validator
+    let validator = &ext.validator_addr;
+    timing::record(Phase::StorageRead, || {
+        validators
+            .find(wl_storage, signing_epoch, validator)
+            .and_then(|details| {
+                let hot_key = details.eth_hot_key.clone()?;
+                Some((hot_key, details.eth_cold_key.clone()))
+            })
+    })
+    .ok_or_else(|| {
+        tracing::debug!(
+            %validator,
+            "Could not get Ethereum hot key from Storage for some consensus \
+             validator, while validating contract addresses vote extension"
+        );
+        InvalidVextReason::PubKeyNotInStorage.of(KIND)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use assert_matches::assert_matches;
+    use namada_core::types::address::{self, Address};
+    use namada_core::types::ethereum_events::EthAddress;
+    use namada_state::testing::TestWlStorage;
+
+    use super::*;
+    use crate::storage::parameters::{
+        schedule_contracts_update, ContractVersion, Contracts,
+        ScheduledContractsUpdate, UpgradeableContract,
+    };
+    use crate::test_utils::{self, TestValidatorKeys};
+
+    /// The addresses of the contracts scheduled by [`setup_storage`].
+    const NEW_CONTRACTS: ContractAddresses = ContractAddresses {
+        native_erc20: EthAddress([0xaa; 20]),
+        bridge: EthAddress([0xbb; 20]),
+    };
+
+    /// Set up storage with the default validator, and schedule an update
+    /// of the bridge contracts to [`NEW_CONTRACTS`].
+    fn setup_storage() -> (TestWlStorage, HashMap<Address, TestValidatorKeys>) {
+        let (mut wl_storage, keys) = test_utils::setup_default_storage();
+        schedule_contracts_update(
+            &mut wl_storage,
+            ScheduledContractsUpdate {
+                activation_epoch: 5.into(),
+                contracts: Contracts {
+                    native_erc20: NEW_CONTRACTS.native_erc20,
+                    bridge: UpgradeableContract {
+                        address: NEW_CONTRACTS.bridge,
+                        version: ContractVersion::default(),
+                    },
+                },
+                bridge_pool_nonce: None,
+            },
+        )
+        .expect("Test failed");
+        (wl_storage, keys)
+    }
+
+    /// Build a contract addresses vote extension, signed by the given
+    /// validator with its Ethereum hot and cold keys.
+    fn signed_vext(
+        keys: &HashMap<Address, TestValidatorKeys>,
+        validator: &Address,
+        contracts: ContractAddresses,
+        signing_epoch: Epoch,
+    ) -> contract_addresses::SignedVext {
+        let validator_keys = keys.get(validator).expect("Test failed");
+        contract_addresses::Vext {
+            contracts,
+            validator_addr: validator.clone(),
+            signing_epoch,
+        }
+        .sign_with_cold_key(&validator_keys.eth_bridge, &validator_keys.eth_gov)
+    }
+
+    /// Test that a vote extension over the scheduled contracts, signed by
+    /// a consensus validator, is accepted.
+    #[test]
+    fn test_contract_addresses_vext_accepted() {
+        let (wl_storage, keys) = setup_storage();
+        let (validator, _) = test_utils::default_validator();
+        let ext = signed_vext(&keys, &validator, NEW_CONTRACTS, 0.into());
+        let result =
+            validate_contract_addresses_vext(&wl_storage, &ext, 0.into());
+        assert_matches!(result, Ok(()));
+    }
+
+    /// Test that we reject vote extensions if governance did not schedule
+    /// any contracts update.
+    #[test]
+    fn test_contract_addresses_vext_rejected_without_update() {
+        let (wl_storage, keys) = test_utils::setup_default_storage();
+        let (validator, _) = test_utils::default_validator();
+        let ext = signed_vext(&keys, &validator, NEW_CONTRACTS, 0.into());
+        let result =
+            validate_contract_addresses_vext(&wl_storage, &ext, 0.into());
+        assert_eq!(
+            result,
+            Err(InvalidVextReason::NoContractsUpdateScheduled.of(KIND))
+        );
+    }
+
+    /// Test that we reject vote extensions signed over an epoch greater
+    /// than the last one, or with the wrong key.
+    #[test]
+    fn test_contract_addresses_vext_epoch_and_sig_checks() {
+        let (wl_storage, keys) = setup_storage();
+        let (validator, _) = test_utils::default_validator();
+
+        let ext = signed_vext(&keys, &validator, NEW_CONTRACTS, 1.into());
+        let result =
+            validate_contract_addresses_vext(&wl_storage, &ext, 0.into());
+        assert_eq!(result, Err(InvalidVextReason::UnexpectedEpoch.of(KIND)));
+
+        let ext = contract_addresses::Vext {
+            contracts: NEW_CONTRACTS,
+            validator_addr: validator.clone(),
+            signing_epoch: 0.into(),
+        }
+        .sign(&keys.get(&validator).expect("Test failed").eth_gov);
+        let result =
+            validate_contract_addresses_vext(&wl_storage, &ext, 0.into());
+        assert_eq!(result, Err(InvalidVextReason::VerifySigFailed.of(KIND)));
+    }
+
+    /// Test that we reject vote extensions issued by an address that is
+    /// not a consensus validator.
+    #[test]
+    fn test_contract_addresses_vext_rejected_from_non_validator() {
+        let (wl_storage, keys) = setup_storage();
+        let (validator, _) = test_utils::default_validator();
+        let ext = contract_addresses::Vext {
+            contracts: NEW_CONTRACTS,
+            validator_addr: address::testing::established_address_4(),
+            signing_epoch: 0.into(),
+        }
+        .sign(&keys.get(&validator).expect("Test failed").eth_bridge);
+        let result =
+            validate_contract_addresses_vext(&wl_storage, &ext, 0.into());
+        assert_eq!(result, Err(InvalidVextReason::PubKeyNotInStorage.of(KIND)));
+    }
+
+    /// Test that we reject vote extensions containing contract addresses
+    /// other than the ones scheduled in storage.
+    #[test]
+    fn test_diverging_contract_addresses_rejected() {
+        let (wl_storage, keys) = setup_storage();
+        let (validator, _) = test_utils::default_validator();
+
+        for contracts in [
+            ContractAddresses {
+                bridge: EthAddress([0; 20]),
+                ..NEW_CONTRACTS
+            },
+            ContractAddresses {
+                native_erc20: EthAddress([0xff; 20]),
+                ..NEW_CONTRACTS
+            },
+        ] {
+            let ext = signed_vext(&keys, &validator, contracts, 0.into());
+            let result =
+                validate_contract_addresses_vext(&wl_storage, &ext, 0.into());
+            assert_eq!(
+                result,
+                Err(InvalidVextReason::DivergesFromStorage.of(KIND))
+            );
+        }
+    }
+
+    /// Test that we reject vote extensions only signed with the Ethereum
+    /// hot key of their validator, or whose cold key signature is invalid.
+    #[test]
+    fn test_contract_addresses_vext_cold_key_sig() {
+        let (wl_storage, keys) = setup_storage();
+        let (validator, _) = test_utils::default_validator();
+        let validator_keys = keys.get(&validator).expect("Test failed");
+        let vext = contract_addresses::Vext {
+            contracts: NEW_CONTRACTS,
+            validator_addr: validator.clone(),
+            signing_epoch: 0.into(),
+        };
+
+        let ext = vext.sign(&validator_keys.eth_bridge);
+        let result =
+            validate_contract_addresses_vext(&wl_storage, &ext, 0.into());
+        assert_eq!(result, Err(InvalidVextReason::ColdKeySigMissing.of(KIND)));
+
+        // a cold key signature made with the hot key
+        let ext = vext.sign_with_cold_key(
+            &validator_keys.eth_bridge,
+            &validator_keys.eth_bridge,
+        );
+        let result =
+            validate_contract_addresses_vext(&wl_storage, &ext, 0.into());
+        assert_eq!(result, Err(InvalidVextReason::VerifySigFailed.of(KIND)));
+
+        let ext = vext.sign_with_cold_key(
+            &validator_keys.eth_bridge,
+            &validator_keys.eth_gov,
+        );
+        let result =
+            validate_contract_addresses_vext(&wl_storage, &ext, 0.into());
+        assert_matches!(result, Ok(()));
+    }
+}
@@ -1,14 +1,20 @@
 //! Ethereum events validation.
 
+use namada_core::types::key::common;
 use namada_core::types::storage::BlockHeight;
 use namada_proof_of_stake::pos_queries::PosQueries;
 use namada_state::{DBIter, StorageHasher, WlStorage, DB};
 use namada_tx::Signed;
 use namada_vote_ext::ethereum_events;
 
-use super::VoteExtensionError;
+use super::batch::{self, ValidatorDetailsCache};
+use super::timing::{self, ExtensionGuard, Phase};
+use super::{ExtensionKind, InvalidVextReason, VoteExtensionError};
 use crate::storage::eth_bridge_queries::EthBridgeQueries;
 
+/// The kind of the vote extensions validated in this module.
+const KIND: ExtensionKind = ExtensionKind::EthereumEvents;
+
 /// Validates an Ethereum events vote extension issued at the provided
 /// block height.
 ///
@@ -18,39 +24,162 @@ use crate::storage::eth_bridge_queries::EthBridgeQueries;
 ///  * The validator signed over the correct height inside of the extension.
 ///  * There are no duplicate Ethereum events in this vote extension, and the
 ///    events are sorted in ascending order.
+///  * The events were observed from the bridge contract in effect at that
+///    epoch, taking into account the contracts updates of governance.
 pub fn validate_eth_events_vext<D, H>(
     wl_storage: &WlStorage<D, H>,
     ext: &Signed<ethereum_events::Vext>,
     last_height: BlockHeight,
 ) -> Result<(), VoteExtensionError>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    validate_eth_events_vext_with_cache(
+        wl_storage,
+        ext,
+        last_height,
+        &mut ValidatorDetailsCache::default(),
+    )
+}
+
+/// Validates an Ethereum events vote extension issued at the provided
+/// block height, like [`validate_eth_events_vext`], looking up the signing
+/// validator in `validators`.
+///
+/// This allows the same cache to be shared by all the vote extensions of
+/// a block.
+pub fn validate_eth_events_vext_with_cache<D, H>(
+    wl_storage: &WlStorage<D, H>,
+    ext: &Signed<ethereum_events::Vext>,
+    last_height: BlockHeight,
+    validators: &mut ValidatorDetailsCache,
+) -> Result<(), VoteExtensionError>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    let _guard = ExtensionGuard::new("eth_events", &ext.data.validator_addr);
+    let pk = validate_eth_events_vext_data(
+        wl_storage,
+        ext,
+        last_height,
+        validators,
+    )?;
+    verify_eth_events_vext_sig(ext, &pk)
+}
+
+/// Validates a batch of Ethereum events vote extensions issued at the
+/// provided block height, returning the result of each extension in the
+/// order of `exts`.
+///
+/// The results are identical to those of [`validate_eth_events_vext`],
+/// but the signatures of the extensions are verified in parallel. Refer
+/// to [`batch`] for more details.
+pub fn validate_eth_events_vext_batch<D, H>(
+    wl_storage: &WlStorage<D, H>,
+    exts: &[Signed<ethereum_events::Vext>],
+    last_height: BlockHeight,
+) -> Vec<Result<(), VoteExtensionError>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    let mut validators = ValidatorDetailsCache::default();
+    let checked = exts
+        .iter()
+        .map(|ext| {
+            let _guard =
+                ExtensionGuard::new("eth_events", &ext.data.validator_addr);
+            validate_eth_events_vext_data(
+                wl_storage,
+                ext,
+                last_height,
+                &mut validators,
+            )
+            .map(|pk| (ext, pk))
+        })
+        .collect();
+    batch::verify_signatures_par(checked, |(ext, pk)| {
+        verify_eth_events_vext_sig(ext, &pk)
+    })
+}
+
+/// Validates the data of an Ethereum events vote extension, returning
+/// the protocol key of the signing validator.
+///
+/// The signing validator is looked up in `validators`.
+fn validate_eth_events_vext_data<D, H>(
+    wl_storage: &WlStorage<D, H>,
+    ext: &Signed<ethereum_events::Vext>,
+    last_height: BlockHeight,
+    validators: &mut ValidatorDetailsCache,
+) -> Result<common::PublicKey, VoteExtensionError>
 where
     D: 'static + DB + for<'iter> DBIter<'iter>,
     H: 'static + StorageHasher,
 {
     // NOTE: for ABCI++, we should pass
     // `last_height` here, instead of `ext.data.block_height`
-    let ext_height_epoch =
-        match wl_storage.pos_queries().get_epoch(ext.data.block_height) {
-            Some(epoch) => epoch,
-            _ => {
-                tracing::debug!(
-                    block_height = ?ext.data.block_height,
-                    "The epoch of the Ethereum events vote extension's \
-                     block height should always be known",
-                );
-                return Err(VoteExtensionError::UnexpectedEpoch);
-            }
-        };
-    if !wl_storage
-        .ethbridge_queries()
-        .is_bridge_active_at(ext_height_epoch)
-    {
+    let ext_height_epoch = match timing::record(Phase::StorageRead, || {
+        wl_storage.pos_queries().get_epoch(ext.data.block_height)
+    }) {
+        Some(epoch) => epoch,
+        _ => {
+            tracing::debug!(
+                block_height = ?ext.data.block_height,
+                "The epoch of the Ethereum events vote extension's \
+                 block height should always be known",
+            );
+            return Err(InvalidVextReason::UnexpectedEpoch.of(KIND));
+        }
+    };
+    if !timing::record(Phase::StorageRead, || {
+        wl_storage
+            .ethbridge_queries()
+            .is_bridge_active_at(ext_height_epoch)
+    }) {
         tracing::debug!(
             vext_epoch = ?ext_height_epoch,
             "The Ethereum bridge was not enabled when the Ethereum
              events' vote extension was cast",
         );
-        return Err(VoteExtensionError::EthereumBridgeInactive);
+        return Err(InvalidVextReason::EthereumBridgeInactive.of(KIND));
+    }
+    if timing::record(Phase::StorageRead, || {
+        wl_storage.ethbridge_queries().is_bridge_paused()
+    }) {
+        tracing::debug!(
+            "Dropping Ethereum events vote extension, since the Ethereum \
+             bridge is paused"
+        );
+        return Err(InvalidVextReason::BridgePaused.of(KIND));
+    }
+    let bridge_contract = timing::record(Phase::StorageRead, || {
+        wl_storage
+            .ethbridge_queries()
+            .get_contracts_at(ext_height_epoch)
+    })
+    .map_err(|err| {
+        tracing::debug!(
+            ?err,
+            vext_epoch = ?ext_height_epoch,
+            "Could not read the Ethereum bridge contracts in effect at the \
+             epoch the Ethereum events vote extension was issued at"
+        );
+        InvalidVextReason::UnexpectedBridgeContract.of(KIND)
+    })?
+    .bridge
+    .address;
+    if ext.data.bridge_contract != bridge_contract {
+        tracing::debug!(
+            vext_epoch = ?ext_height_epoch,
+            expected = ?bridge_contract,
+            got = ?ext.data.bridge_contract,
+            "Ethereum events vote extension observed from a bridge contract \
+             not in effect at the epoch it was issued at"
+        );
+        return Err(InvalidVextReason::UnexpectedBridgeContract.of(KIND));
     }
     if ext.data.block_height > last_height {
         tracing::debug!(
@@ -59,40 +188,48 @@ where
             "Ethereum events vote extension issued for a block height \
              higher than the chain's last height."
         );
-        return Err(VoteExtensionError::UnexpectedBlockHeight);
+        return Err(InvalidVextReason::UnexpectedBlockHeight.of(KIND));
     }
     if ext.data.block_height.0 == 0 {
         tracing::debug!("Dropping vote extension issued at genesis");
-        return Err(VoteExtensionError::UnexpectedBlockHeight);
+        return Err(InvalidVextReason::UnexpectedBlockHeight.of(KIND));
     }
     validate_eth_events(wl_storage, &ext.data)?;
     // get the public key associated with this validator
     let validator = &ext.data.validator_addr;
-    let (_, pk) = wl_storage
-        .pos_queries()
-        .get_validator_from_address(validator, Some(ext_height_epoch))
-        .map_err(|err| {
-            tracing::debug!(
-                ?err,
-                %validator,
-                "Could not get public key from Storage for some validator, \
-                 while validating Ethereum events vote extension"
-            );
-            VoteExtensionError::PubKeyNotInStorage
-        })?;
-    // verify the signature of the vote extension
-    ext.verify(&pk).map_err(|err| {
+    timing::record(Phase::StorageRead, || {
+        validators
+            .find(wl_storage, ext_height_epoch, validator)
+            .and_then(|details| details.protocol_key.clone())
+    })
+    .ok_or_else(|| {
+        tracing::debug!(
+            %validator,
+            epoch = ?ext_height_epoch,
+            "Could not get public key from Storage for some validator, \
+             while validating Ethereum events vote extension"
+        );
+        InvalidVextReason::PubKeyNotInStorage.of(KIND)
+    })
+}
+
+/// Verifies the signature of an Ethereum events vote extension, against
+/// the protocol key of the signing validator.
+fn verify_eth_events_vext_sig(
+    ext: &Signed<ethereum_events::Vext>,
+    pk: &common::PublicKey,
+) -> Result<(), VoteExtensionError> {
+    timing::verify_signed(ext, pk).map_err(|err| {
         tracing::debug!(
             ?err,
             ?ext.sig,
             ?pk,
-            %validator,
+            validator = %ext.data.validator_addr,
             "Failed to verify the signature of an Ethereum events vote \
              extension issued by some validator"
         );
-        VoteExtensionError::VerifySigFailed
-    })?;
-    Ok(())
+        InvalidVextReason::VerifySigFailed.of(KIND)
+    })
 }
 
 /// Validate a batch of Ethereum events contained in
@@ -124,17 +261,20 @@ where
             "Found duplicate or non-sorted Ethereum events in a vote extension from \
              some validator"
         );
-        return Err(VoteExtensionError::HaveDupesOrNonSorted);
+        return Err(InvalidVextReason::HaveDupesOrNonSorted.of(KIND));
     }
     // for the proposal to be valid, at least one of the
     // event's nonces must be valid
-    if ext.ethereum_events.iter().any(|event| {
-        wl_storage
-            .ethbridge_queries()
-            .validate_eth_event_nonce(event)
-    }) {
+    let has_valid_nonce = timing::record(Phase::StorageRead, || {
+        ext.ethereum_events.iter().any(|event| {
+            wl_storage
+                .ethbridge_queries()
+                .validate_eth_event_nonce(event)
+        })
+    });
+    if has_valid_nonce {
         Ok(())
     } else {
-        Err(VoteExtensionError::InvalidEthEventNonce)
+        Err(InvalidVextReason::InvalidEthEventNonce.of(KIND))
     }
 }
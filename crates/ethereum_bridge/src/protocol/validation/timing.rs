@@ -0,0 +1,345 @@
+//! Timing instrumentation of vote extension validation.
+//!
+//! Vote extension validators record the time spent in each [`Phase`] of
+//! the validation of an extension. These timings are aggregated into
+//! histograms, until they are drained with [`take_block_timings`],
+//! typically once per block.
+//!
+//! Recording is disabled by default, in which case the instrumentation
+//! amounts to a relaxed atomic load per measured phase.
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use namada_core::types::address::Address;
+use namada_core::types::key::{common, SigScheme, Signable, VerifySigError};
+use namada_tx::Signed;
+
+/// Whether timings are being recorded.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Timings recorded since they were last drained.
+static TIMINGS: Mutex<BlockTimings> = Mutex::new(BlockTimings::new());
+
+/// Upper bounds of the buckets of a [`Histogram`], in microseconds.
+///
+/// Measurements greater than the last bound are stored in an
+/// additional overflow bucket.
+pub const BUCKET_BOUNDS_MICROS: [u64; 8] =
+    [10, 50, 100, 500, 1_000, 5_000, 10_000, 50_000];
+
+/// Validation time of a single vote extension above which a
+/// warning is logged.
+pub const SLOW_EXTENSION_THRESHOLD: Duration = Duration::from_millis(50);
+
+/// A phase of the validation of a vote extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Reads from storage, such as PoS queries or the construction
+    /// of the Ethereum address book of the consensus validators.
+    StorageRead,
+    /// Encoding of the data signed over by a validator.
+    Encoding,
+    /// Verification of a validator's signature.
+    SigVerification,
+}
+
+impl Phase {
+    /// All the phases of vote extension validation.
+    pub const ALL: [Phase; 3] =
+        [Phase::StorageRead, Phase::Encoding, Phase::SigVerification];
+
+    /// Return a human readable name of this phase.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Phase::StorageRead => "storage_read",
+            Phase::Encoding => "encoding",
+            Phase::SigVerification => "sig_verification",
+        }
+    }
+}
+
+/// Histogram of the durations of some [`Phase`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Histogram {
+    /// Number of measurements in each bucket, including the
+    /// overflow bucket.
+    buckets: [u64; BUCKET_BOUNDS_MICROS.len() + 1],
+    /// Total number of measurements.
+    count: u64,
+    /// Sum of all measurements, in microseconds.
+    sum_micros: u64,
+    /// Greatest measurement, in microseconds.
+    max_micros: u64,
+}
+
+impl Histogram {
+    /// Create a new empty histogram.
+    pub const fn new() -> Self {
+        Self {
+            buckets: [0; BUCKET_BOUNDS_MICROS.len() + 1],
+            count: 0,
+            sum_micros: 0,
+            max_micros: 0,
+        }
+    }
+
+    /// Add a measurement to this histogram.
+    pub fn record(&mut self, elapsed: Duration) {
+        let micros = u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX);
+        let bucket = BUCKET_BOUNDS_MICROS
+            .iter()
+            .position(|&bound| micros <= bound)
+            .unwrap_or(BUCKET_BOUNDS_MICROS.len());
+        self.buckets[bucket] += 1;
+        self.count += 1;
+        self.sum_micros = self.sum_micros.saturating_add(micros);
+        self.max_micros = self.max_micros.max(micros);
+    }
+
+    /// Return the number of measurements in this histogram.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Return the sum of all measurements in this histogram.
+    pub fn sum(&self) -> Duration {
+        Duration::from_micros(self.sum_micros)
+    }
+
+    /// Return the greatest measurement in this histogram.
+    pub fn max(&self) -> Duration {
+        Duration::from_micros(self.max_micros)
+    }
+
+    /// Iterate over the upper bound of each bucket, in microseconds,
+    /// and its number of measurements. The overflow bucket has no
+    /// upper bound.
+    pub fn buckets(&self) -> impl Iterator<Item = (Option<u64>, u64)> + '_ {
+        BUCKET_BOUNDS_MICROS
+            .iter()
+            .copied()
+            .map(Some)
+            .chain(std::iter::once(None))
+            .zip(self.buckets.iter().copied())
+    }
+}
+
+/// Histograms of the durations of each validation [`Phase`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockTimings {
+    storage_read: Histogram,
+    encoding: Histogram,
+    sig_verification: Histogram,
+}
+
+impl BlockTimings {
+    /// Create a new set of empty histograms.
+    pub const fn new() -> Self {
+        Self {
+            storage_read: Histogram::new(),
+            encoding: Histogram::new(),
+            sig_verification: Histogram::new(),
+        }
+    }
+
+    /// Get the histogram of the given phase.
+    pub fn get(&self, phase: Phase) -> &Histogram {
+        match phase {
+            Phase::StorageRead => &self.storage_read,
+            Phase::Encoding => &self.encoding,
+            Phase::SigVerification => &self.sig_verification,
+        }
+    }
+
+    /// Get a mutable reference to the histogram of the given phase.
+    fn get_mut(&mut self, phase: Phase) -> &mut Histogram {
+        match phase {
+            Phase::StorageRead => &mut self.storage_read,
+            Phase::Encoding => &mut self.encoding,
+            Phase::SigVerification => &mut self.sig_verification,
+        }
+    }
+
+    /// Check if no measurements have been recorded.
+    pub fn is_empty(&self) -> bool {
+        Phase::ALL.iter().all(|&phase| self.get(phase).count() == 0)
+    }
+}
+
+impl fmt::Display for BlockTimings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, phase) in Phase::ALL.iter().enumerate() {
+            let histogram = self.get(*phase);
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(
+                f,
+                "{}: count={} sum={:?} max={:?} buckets=[",
+                phase.as_str(),
+                histogram.count(),
+                histogram.sum(),
+                histogram.max(),
+            )?;
+            for (j, (bound, count)) in histogram.buckets().enumerate() {
+                if j > 0 {
+                    write!(f, " ")?;
+                }
+                match bound {
+                    Some(bound) => write!(f, "le{bound}us:{count}")?,
+                    None => write!(f, "inf:{count}")?,
+                }
+            }
+            write!(f, "]")?;
+        }
+        Ok(())
+    }
+}
+
+/// Enable or disable the recording of timings.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Check if timings are being recorded.
+#[inline]
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Drain the timings recorded since the last call to this function.
+pub fn take_block_timings() -> BlockTimings {
+    std::mem::take(&mut *TIMINGS.lock().unwrap_or_else(PoisonError::into_inner))
+}
+
+/// Run `f`, recording its duration under the given [`Phase`].
+#[inline]
+pub fn record<T>(phase: Phase, f: impl FnOnce() -> T) -> T {
+    if !is_enabled() {
+        return f();
+    }
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+    TIMINGS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get_mut(phase)
+        .record(elapsed);
+    result
+}
+
+/// Verify the signature of some [`Signed`] data, recording the
+/// time spent encoding the data and verifying the signature.
+pub fn verify_signed<T, S>(
+    signed: &Signed<T, S>,
+    pk: &common::PublicKey,
+) -> Result<(), VerifySigError>
+where
+    S: Signable<T>,
+{
+    let signed_bytes = record(Phase::Encoding, || S::as_signable(&signed.data));
+    record(Phase::SigVerification, || {
+        common::SigScheme::verify_signature_with_hasher::<S::Hasher>(
+            pk,
+            &signed_bytes,
+            &signed.sig,
+        )
+    })
+}
+
+/// Guard tracking the validation of a single vote extension.
+///
+/// While alive, the guard keeps a `tracing` span entered, carrying the
+/// address of the validator that issued the extension. If timings are
+/// being recorded, a warning is logged on drop when the validation took
+/// longer than [`SLOW_EXTENSION_THRESHOLD`].
+#[must_use]
+pub struct ExtensionGuard {
+    _span: tracing::span::EnteredSpan,
+    start: Option<Instant>,
+}
+
+impl ExtensionGuard {
+    /// Start tracking the validation of a vote extension of the
+    /// given kind, issued by `validator`.
+    pub fn new(kind: &'static str, validator: &Address) -> Self {
+        let span = tracing::debug_span!(
+            "validate_vext",
+            kind,
+            validator = %validator,
+        )
+        .entered();
+        Self {
+            _span: span,
+            start: is_enabled().then(Instant::now),
+        }
+    }
+}
+
+impl Drop for ExtensionGuard {
+    fn drop(&mut self) {
+        let Some(start) = self.start else {
+            return;
+        };
+        let elapsed = start.elapsed();
+        if elapsed > SLOW_EXTENSION_THRESHOLD {
+            tracing::warn!(
+                ?elapsed,
+                "Slow vote extension validation; the validator that issued \
+                 the extension is recorded in the current span"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that measurements are placed in the correct buckets.
+    #[test]
+    fn test_histogram_buckets() {
+        let mut histogram = Histogram::new();
+        histogram.record(Duration::from_micros(10));
+        histogram.record(Duration::from_micros(11));
+        histogram.record(Duration::from_millis(1));
+        histogram.record(Duration::from_secs(1));
+
+        let buckets: Vec<_> = histogram.buckets().collect();
+        assert_eq!(buckets[0], (Some(10), 1));
+        assert_eq!(buckets[1], (Some(50), 1));
+        assert_eq!(buckets[4], (Some(1_000), 1));
+        assert_eq!(buckets[8], (None, 1));
+        assert_eq!(histogram.count(), 4);
+        assert_eq!(histogram.max(), Duration::from_secs(1));
+        assert_eq!(
+            histogram.sum(),
+            Duration::from_micros(10 + 11 + 1_000 + 1_000_000)
+        );
+    }
+
+    /// Test that timings are only recorded while enabled.
+    #[test]
+    fn test_record_and_take_timings() {
+        set_enabled(false);
+        _ = take_block_timings();
+        assert_eq!(record(Phase::StorageRead, || 1), 1);
+        assert!(take_block_timings().is_empty());
+
+        set_enabled(true);
+        record(Phase::StorageRead, || ());
+        record(Phase::SigVerification, || ());
+        record(Phase::SigVerification, || ());
+        set_enabled(false);
+
+        // other tests may validate vote extensions concurrently,
+        // hence the lower bounds
+        let timings = take_block_timings();
+        assert!(timings.get(Phase::StorageRead).count() >= 1);
+        assert!(timings.get(Phase::SigVerification).count() >= 2);
+    }
+}
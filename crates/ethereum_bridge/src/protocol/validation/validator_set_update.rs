@@ -1,12 +1,81 @@
 //! Validator set update validation.
 
+use namada_core::types::chain::ChainId;
+use namada_core::types::hash::KeccakHasher;
+use namada_core::types::key::{common, SigScheme};
 use namada_core::types::storage::Epoch;
+use namada_core::types::voting_power::EthBridgeVotingPower;
 use namada_proof_of_stake::pos_queries::PosQueries;
 use namada_state::{DBIter, StorageHasher, WlStorage, DB};
-use namada_vote_ext::validator_set_update;
+use namada_vote_ext::validator_set_update::{
+    self, VotingPowersMap, VotingPowersMapExt,
+};
 
-use super::VoteExtensionError;
-use crate::storage::eth_bridge_queries::EthBridgeQueries;
+use super::batch::{self, ValidatorDetailsCache};
+use super::timing::{self, ExtensionGuard, Phase};
+use super::{ExtensionKind, InvalidVextReason, VoteExtensionError};
+use crate::storage::eth_bridge_queries::{
+    consensus_eth_address, EthBridgeQueries,
+};
+
+/// The kind of the vote extensions validated in this module.
+const KIND: ExtensionKind = ExtensionKind::ValidatorSetUpdate;
+
+/// Check if a validator set update vote extension was signed over an
+/// epoch greater than `last_epoch`, the last epoch known to this node.
+#[inline]
+pub fn is_future_epoch(signing_epoch: Epoch, last_epoch: Epoch) -> bool {
+    signing_epoch > last_epoch
+}
+
+/// Check if a validator set update vote extension issued at genesis
+/// is the bootstrap update of the bridge, signed over the first epoch.
+#[inline]
+pub fn is_bootstrap_epoch(signing_epoch: Epoch, last_epoch: Epoch) -> bool {
+    signing_epoch == Epoch(0) && last_epoch == Epoch(0)
+}
+
+/// Return the epoch of the validator set signed over by a validator
+/// set update vote extension, i.e. `signing_epoch + 1`.
+///
+/// Returns [`None`] if the next epoch cannot be represented, rather
+/// than wrapping around to the first epoch.
+#[inline]
+pub fn target_valset_epoch(signing_epoch: Epoch) -> Option<Epoch> {
+    signing_epoch.0.checked_add(1).map(Epoch)
+}
+
+/// Check if the sum of the normalized voting powers of a validator set
+/// update stays within the total voting power expected by the Ethereum
+/// bridge contracts, [`EthBridgeVotingPower::MAX`].
+///
+/// Each voting power is normalized by rounding it to the nearest integer,
+/// such that the sum of the voting powers of `n` validators may exceed the
+/// total by up to `n / 2`. Greater sums can't be the result of normalizing
+/// the voting powers of a validator set.
+pub fn is_normalized_sum_within_ceiling(
+    normalized_powers: &[EthBridgeVotingPower],
+) -> bool {
+    let rounding_slack = normalized_powers.len() as u128 / 2;
+    let ceiling = u128::from(EthBridgeVotingPower::MAX) + rounding_slack;
+    normalized_powers
+        .iter()
+        .try_fold(0u128, |sum, &power| sum.checked_add(power.into()))
+        .map_or(false, |sum| sum <= ceiling)
+}
+
+/// Check if the number of voting powers in a validator set update vote
+/// extension is within `max_validator_slots`, the maximum number of
+/// consensus validators set in the PoS parameters. A validator set with
+/// more entries than this can't have been signed over.
+#[inline]
+pub fn is_within_max_entries(
+    no_voting_powers: usize,
+    max_validator_slots: u64,
+) -> bool {
+    u64::try_from(no_voting_powers)
+        .map_or(false, |no_entries| no_entries <= max_validator_slots)
+}
 
 /// Validates a validator set update vote extension issued at the
 /// epoch provided as an argument.
@@ -16,16 +85,43 @@ use crate::storage::eth_bridge_queries::EthBridgeQueries;
 /// To validate a [`validator_set_update::SignedVext`], Namada nodes
 /// check if:
 ///
+///  * The number of voting powers in the extension doesn't exceed the maximum
+///    number of consensus validators in the PoS parameters. This is checked
+///    before any other, such that oversized extensions are rejected without
+///    iterating over their voting powers.
+///  * The Ethereum bridge was not paused by governance.
 ///  * The signing validator is a consensus validator during the epoch
 ///    `signing_epoch` inside the extension.
 ///  * A validator set update proof is not available yet for `signing_epoch`.
 ///  * The validator correctly signed the extension, with its Ethereum hot key.
+///    Past the domain separation epoch set by governance, the signature must be
+///    over a message separated to the domain of this chain, such that
+///    signatures issued for other chains are rejected.
 ///  * The validator signed over the epoch inside of the extension, whose value
 ///    should not be greater than `last_epoch`.
+///  * The epoch after the one signed over can be represented. Extensions signed
+///    over the last representable epoch are rejected with
+///    [`InvalidVextReason::EpochOverflow`], rather than wrapping around.
+///  * The consensus validator set of `signing_epoch + 1` is available in
+///    storage. Past genesis, an empty set means PoS storage is not readable for
+///    that epoch, rather than there being no validators to sign over.
 ///  * The voting powers in the vote extension correspond to the voting powers
 ///    of the validators of `signing_epoch + 1`.
 ///  * The voting powers signed over were Ethereum ABI encoded, normalized to
 ///    `2^32`, and sorted in descending order.
+///  * The sum of the normalized voting powers doesn't exceed the total voting
+///    power of the Ethereum bridge contracts, beyond the rounding error of the
+///    normalization.
+///
+/// # Genesis bootstrap
+///
+/// Vote extensions issued before any block has been committed are
+/// rejected, unless the bootstrap flag at
+/// [`crate::storage::valset_upd_bootstrap_key`] was set at genesis. In
+/// that case, a single validator set update signed over the first epoch
+/// is validated with the checks above, such that the bridge can be
+/// initialized. Once the proof for the next epoch is available, or a
+/// block has been committed, the flag no longer has any effect.
 pub fn validate_valset_upd_vext<D, H>(
     wl_storage: &WlStorage<D, H>,
     ext: &validator_set_update::SignedVext,
@@ -35,29 +131,268 @@ where
     D: 'static + DB + for<'iter> DBIter<'iter>,
     H: 'static + StorageHasher,
 {
+    validate_valset_upd_vext_with_cache(
+        wl_storage,
+        ext,
+        last_epoch,
+        &mut ValidatorDetailsCache::default(),
+    )
+}
+
+/// Validates a validator set update vote extension issued at the epoch
+/// provided as an argument, like [`validate_valset_upd_vext`], looking up
+/// the consensus validators signed over in `validators`.
+///
+/// This allows the same cache to be shared by all the vote extensions of
+/// a block.
+pub fn validate_valset_upd_vext_with_cache<D, H>(
+    wl_storage: &WlStorage<D, H>,
+    ext: &validator_set_update::SignedVext,
+    last_epoch: Epoch,
+    validators: &mut ValidatorDetailsCache,
+) -> Result<(), VoteExtensionError>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    let _guard = ExtensionGuard::new("valset_upd", &ext.data.validator_addr);
+    let pk = validate_valset_upd_vext_data(
+        wl_storage, &ext.data, last_epoch, validators,
+    )?;
+    let domain = wl_storage
+        .ethbridge_queries()
+        .valset_upd_signing_domain(ext.data.signing_epoch);
+    verify_valset_upd_vext_sig(ext, &pk, domain.as_ref())
+}
+
+/// Return the exact bytes whose signature is verified by
+/// [`validate_valset_upd_vext`], for the given validator set update vote
+/// extension.
+///
+/// These are the voting powers of the extension, normalized, sorted and
+/// ABI-encoded, as per
+/// [`validator_set_update::encode_signable_message`]. Past the domain
+/// separation epoch set by governance, they are separated to the domain
+/// of this chain, exactly as during validation. The signature of the
+/// extension is over the keccak hash of these bytes, prefixed with the
+/// Ethereum signed message header, such that operators may independently
+/// check why an extension failed with
+/// [`InvalidVextReason::VerifySigFailed`], e.g. with Ethereum tooling.
+pub fn valset_upd_signing_bytes<D, H>(
+    wl_storage: &WlStorage<D, H>,
+    ext: &validator_set_update::SignedVext,
+) -> Vec<u8>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    let domain = wl_storage
+        .ethbridge_queries()
+        .valset_upd_signing_domain(ext.data.signing_epoch);
+    ext.data.signable_bytes(domain.as_ref())
+}
+
+/// Validates a batch of validator set update vote extensions issued at
+/// the epoch provided as an argument, returning the result of each
+/// extension in the order of `exts`.
+///
+/// The results are identical to those of [`validate_valset_upd_vext`],
+/// but the signatures of the extensions are verified in parallel. Refer
+/// to [`batch`] for more details.
+pub fn validate_valset_upd_vext_batch<D, H>(
+    wl_storage: &WlStorage<D, H>,
+    exts: &[validator_set_update::SignedVext],
+    last_epoch: Epoch,
+) -> Vec<Result<(), VoteExtensionError>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    let mut validators = ValidatorDetailsCache::default();
+    let checked = exts
+        .iter()
+        .map(|ext| {
+            let _guard =
+                ExtensionGuard::new("valset_upd", &ext.data.validator_addr);
+            validate_valset_upd_vext_data(
+                wl_storage,
+                &ext.data,
+                last_epoch,
+                &mut validators,
+            )
+            .map(|pk| {
+                let domain = wl_storage
+                    .ethbridge_queries()
+                    .valset_upd_signing_domain(ext.data.signing_epoch);
+                (ext, pk, domain)
+            })
+        })
+        .collect();
+    batch::verify_signatures_par(checked, |(ext, pk, domain)| {
+        verify_valset_upd_vext_sig(ext, &pk, domain.as_ref())
+    })
+}
+
+/// Verifies the signature of a validator set update vote extension,
+/// against the Ethereum hot key of the signing validator.
+///
+/// If a `domain` is given, the signature must be over the message
+/// separated to the domain of that chain.
+fn verify_valset_upd_vext_sig(
+    ext: &validator_set_update::SignedVext,
+    pk: &common::PublicKey,
+    domain: Option<&ChainId>,
+) -> Result<(), VoteExtensionError> {
+    // the signed message is over the epoch after the signing epoch, which
+    // must be representable. this is checked along with the data of the
+    // extension, but we don't rely on it here
+    if target_valset_epoch(ext.data.signing_epoch).is_none() {
+        return Err(InvalidVextReason::EpochOverflow.of(KIND));
+    }
+    let digest =
+        timing::record(Phase::Encoding, || ext.data.signable_digest(domain));
+    timing::record(Phase::SigVerification, || {
+        common::SigScheme::verify_signature_with_hasher::<KeccakHasher>(
+            pk, &digest, &ext.sig,
+        )
+    })
+    .map_err(|err| {
+        tracing::debug!(
+            ?err,
+            ?ext.sig,
+            ?pk,
+            ?domain,
+            validator = %ext.data.validator_addr,
+            "Failed to verify the signature of a valset upd vote \
+             extension issued by some validator"
+        );
+        InvalidVextReason::VerifySigFailed.of(KIND)
+    })
+}
+
+/// Validates an unsigned validator set update vote extension, issued at
+/// the epoch provided as an argument.
+///
+/// All the checks of [`validate_valset_upd_vext`] are performed, except
+/// for the verification of the signature. This allows validators to
+/// check if a vote extension would be accepted, before signing it.
+pub fn validate_unsigned_valset_upd_vext<D, H>(
+    wl_storage: &WlStorage<D, H>,
+    ext: &validator_set_update::Vext,
+    last_epoch: Epoch,
+) -> Result<(), VoteExtensionError>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    let _guard = ExtensionGuard::new("valset_upd", &ext.validator_addr);
+    validate_valset_upd_vext_data(
+        wl_storage,
+        ext,
+        last_epoch,
+        &mut ValidatorDetailsCache::default(),
+    )
+    .map(|_| ())
+}
+
+/// Normalize the voting powers of a validator set update vote extension,
+/// as they are signed over and relayed to the Ethereum bridge contracts.
+fn normalized_voting_powers(
+    voting_powers: &VotingPowersMap,
+) -> Vec<EthBridgeVotingPower> {
+    #[cfg(test)]
+    if let Some(normalized_powers) = tests::FAULTY_NORMALIZED_POWERS
+        .with(|powers| powers.borrow_mut().take())
+    {
+        return normalized_powers;
+    }
+    voting_powers
+        .get_normalized()
+        .into_iter()
+        .map(|(_, power)| power)
+        .collect()
+}
+
+/// Validates the data of a validator set update vote extension,
+/// returning the Ethereum hot key of the signing validator.
+///
+/// The consensus validators signed over are looked up in `validators`.
+fn validate_valset_upd_vext_data<D, H>(
+    wl_storage: &WlStorage<D, H>,
+    ext: &validator_set_update::Vext,
+    last_epoch: Epoch,
+    validators: &mut ValidatorDetailsCache,
+) -> Result<common::PublicKey, VoteExtensionError>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    let max_validator_slots = timing::record(Phase::StorageRead, || {
+        wl_storage
+            .pos_queries()
+            .get_pos_params()
+            .max_validator_slots
+    });
+    if !is_within_max_entries(ext.voting_powers.len(), max_validator_slots) {
+        let err = InvalidVextReason::ExtensionTooLarge.of(KIND);
+        tracing::debug!(
+            no_voting_powers = ext.voting_powers.len(),
+            max_validator_slots,
+            "{err}"
+        );
+        return Err(err);
+    }
+    if timing::record(Phase::StorageRead, || {
+        wl_storage.ethbridge_queries().is_bridge_paused()
+    }) {
+        tracing::debug!(
+            "Dropping validator set update vote extension, since the Ethereum \
+             bridge is paused"
+        );
+        return Err(InvalidVextReason::BridgePaused.of(KIND));
+    }
+    let signing_epoch = ext.signing_epoch;
     if wl_storage.storage.last_block.is_none() {
+        // the only validator set update we accept at genesis is the
+        // bootstrap update of the bridge, signed over the first epoch
+        let is_bootstrap_upd =
+            timing::record(Phase::StorageRead, || {
+                wl_storage
+                    .ethbridge_queries()
+                    .valset_upd_bootstrap_enabled()
+            }) && is_bootstrap_epoch(signing_epoch, last_epoch);
+        if !is_bootstrap_upd {
+            tracing::debug!(
+                "Dropping validator set update vote extension issued at \
+                 genesis"
+            );
+            return Err(InvalidVextReason::UnexpectedBlockHeight.of(KIND));
+        }
         tracing::debug!(
-            "Dropping validator set update vote extension issued at genesis"
+            "Validating bootstrap validator set update vote extension issued \
+             at genesis"
         );
-        return Err(VoteExtensionError::UnexpectedBlockHeight);
     }
-    let signing_epoch = ext.data.signing_epoch;
-    if signing_epoch > last_epoch {
+    if is_future_epoch(signing_epoch, last_epoch) {
         tracing::debug!(
             vext_epoch = ?signing_epoch,
             ?last_epoch,
             "Validator set update vote extension issued for an epoch \
              greater than the last one.",
         );
-        return Err(VoteExtensionError::UnexpectedEpoch);
+        return Err(InvalidVextReason::UnexpectedEpoch.of(KIND));
     }
-    if wl_storage
-        .ethbridge_queries()
-        .valset_upd_seen(signing_epoch.next())
-    {
-        let err = VoteExtensionError::ValsetUpdProofAvailable;
+    let Some(valset_epoch) = target_valset_epoch(signing_epoch) else {
+        let err = InvalidVextReason::EpochOverflow.of(KIND);
+        tracing::debug!(vext_epoch = ?signing_epoch, "{err}");
+        return Err(err);
+    };
+    if timing::record(Phase::StorageRead, || {
+        wl_storage.ethbridge_queries().valset_upd_seen(valset_epoch)
+    }) {
+        let err = InvalidVextReason::ValsetUpdProofAvailable.of(KIND);
         tracing::debug!(
-            proof_epoch = ?signing_epoch.next(),
+            proof_epoch = ?valset_epoch,
             "{err}"
         );
         return Err(err);
@@ -65,12 +400,22 @@ where
     // verify if the new epoch validators' voting powers in storage match
     // the voting powers in the vote extension
     let mut no_local_consensus_eth_addresses = 0;
-    for (eth_addr_book, namada_addr, namada_power) in wl_storage
-        .ethbridge_queries()
-        .get_consensus_eth_addresses(Some(signing_epoch.next()))
-        .iter()
+    let consensus_eth_addresses = timing::record(Phase::StorageRead, || {
+        validators
+            .get(wl_storage, valset_epoch)
+            .iter()
+            .map(consensus_eth_address)
+            .collect::<Vec<_>>()
+    });
+    if consensus_eth_addresses.is_empty()
+        && wl_storage.storage.last_block.is_some()
     {
-        let &ext_power = match ext.data.voting_powers.get(&eth_addr_book) {
+        let err = InvalidVextReason::ConsensusSetUnavailable.of(KIND);
+        tracing::debug!(?valset_epoch, "{err}");
+        return Err(err);
+    }
+    for (eth_addr_book, namada_addr, namada_power) in consensus_eth_addresses {
+        let &ext_power = match ext.voting_powers.get(&eth_addr_book) {
             Some(voting_power) => voting_power,
             _ => {
                 tracing::debug!(
@@ -78,7 +423,9 @@ where
                     "Could not find expected Ethereum addresses in valset upd \
                      vote extension",
                 );
-                return Err(VoteExtensionError::ValidatorMissingFromExtension);
+                return Err(
+                    InvalidVextReason::ValidatorMissingFromExtension.of(KIND)
+                );
             }
         };
         if namada_power != ext_power {
@@ -88,56 +435,400 @@ where
                 got = ?ext_power,
                 "Found unexpected voting power value in valset upd vote extension",
             );
-            return Err(VoteExtensionError::DivergesFromStorage);
+            return Err(InvalidVextReason::DivergesFromStorage.of(KIND));
         }
         no_local_consensus_eth_addresses += 1;
     }
-    if no_local_consensus_eth_addresses != ext.data.voting_powers.len() {
+    if no_local_consensus_eth_addresses != ext.voting_powers.len() {
         tracing::debug!(
-            no_ext_consensus_eth_addresses = ext.data.voting_powers.len(),
+            no_ext_consensus_eth_addresses = ext.voting_powers.len(),
             no_local_consensus_eth_addresses,
             "Superset of the next validator set was included in the validator \
              set update vote extension",
         );
-        return Err(VoteExtensionError::ExtraValidatorsInExtension);
+        return Err(InvalidVextReason::ExtraValidatorsInExtension.of(KIND));
+    }
+    let normalized_powers = normalized_voting_powers(&ext.voting_powers);
+    if !is_normalized_sum_within_ceiling(&normalized_powers) {
+        let err = InvalidVextReason::NormalizedSumOverflow.of(KIND);
+        tracing::debug!(?normalized_powers, "{err}");
+        return Err(err);
     }
     // get the public key associated with this validator
-    let validator = &ext.data.validator_addr;
-    let pk = wl_storage
-        .pos_queries()
-        .read_validator_eth_hot_key(validator, Some(signing_epoch))
-        .ok_or_else(|| {
-            tracing::debug!(
-                %validator,
-                "Could not get Ethereum hot key from Storage for some validator, \
-                 while validating valset upd vote extension"
-            );
-            VoteExtensionError::PubKeyNotInStorage
-        })?;
-    // verify the signature of the vote extension
-    ext.verify(&pk).map_err(|err| {
+    let validator = &ext.validator_addr;
+    timing::record(Phase::StorageRead, || {
+        wl_storage
+            .pos_queries()
+            .read_validator_eth_hot_key(validator, Some(signing_epoch))
+    })
+    .ok_or_else(|| {
         tracing::debug!(
-            ?err,
-            ?ext.sig,
-            ?pk,
             %validator,
-            "Failed to verify the signature of a valset upd vote \
-             extension issued by some validator"
+            "Could not get Ethereum hot key from Storage for some validator, \
+             while validating valset upd vote extension"
         );
-        VoteExtensionError::VerifySigFailed
-    })?;
-    Ok(())
+        InvalidVextReason::PubKeyNotInStorage.of(KIND)
+    })
 }
 
 #[cfg(test)]
 mod tests {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
     use assert_matches::assert_matches;
+    use namada_core::types::address::{self, Address};
     use namada_core::types::ethereum_events::EthAddress;
-    use namada_core::types::key::{common, RefTo};
-    use namada_vote_ext::validator_set_update::{EthAddrBook, VotingPowersMap};
+    use namada_core::types::keccak::keccak_hash;
+    use namada_core::types::key::{RefTo, Signable, SignableEthMessage};
+    use namada_core::types::token;
+    use namada_state::testing::TestWlStorage;
+    use namada_state::StorageWrite;
+    use namada_tx::Signed;
+    use namada_vote_ext::validator_set_update::EthAddrBook;
 
     use super::*;
-    use crate::test_utils;
+    use crate::storage::parameters::{
+        set_bridge_paused, set_valset_upd_domain_epoch,
+    };
+    use crate::storage::{valset_upd_bootstrap_key, vote_tallies};
+    use crate::test_utils::{self, TestValidatorKeys};
+
+    std::thread_local! {
+        /// Normalized voting powers returned in place of those of the next
+        /// extension validated by this thread, simulating a faulty
+        /// normalization of its voting powers.
+        pub(super) static FAULTY_NORMALIZED_POWERS:
+            RefCell<Option<Vec<EthBridgeVotingPower>>> = RefCell::new(None);
+    }
+
+    /// Build a valid validator set update vote extension, signed by the
+    /// default validator over the given epoch.
+    fn signed_valset_upd(
+        keys: &HashMap<Address, TestValidatorKeys>,
+        signing_epoch: Epoch,
+    ) -> validator_set_update::SignedVext {
+        let (validator, validator_stake) = test_utils::default_validator();
+        let validator_keys = keys.get(&validator).expect("Test failed");
+        let voting_powers = {
+            let mut map = VotingPowersMap::new();
+            map.insert(
+                EthAddrBook {
+                    hot_key_addr: eth_addr(&validator_keys.eth_bridge),
+                    cold_key_addr: eth_addr(&validator_keys.eth_gov),
+                },
+                validator_stake,
+            );
+            map
+        };
+        validator_set_update::Vext {
+            voting_powers,
+            signing_epoch,
+            validator_addr: validator,
+        }
+        .sign(&validator_keys.eth_bridge)
+    }
+
+    /// Get the Ethereum address of a secp256k1 key.
+    fn eth_addr(key: &common::SecretKey) -> EthAddress {
+        match key.ref_to() {
+            common::PublicKey::Secp256k1(ref k) => k.into(),
+            _ => panic!("Test failed"),
+        }
+    }
+
+    /// Set up storage as it looks at genesis, before any block
+    /// has been committed.
+    fn setup_genesis_storage()
+    -> (TestWlStorage, HashMap<Address, TestValidatorKeys>) {
+        let (mut wl_storage, keys) = test_utils::setup_default_storage();
+        wl_storage.storage.last_block = None;
+        (wl_storage, keys)
+    }
+
+    /// Test that validator set updates issued at genesis are rejected,
+    /// if the bootstrap flag has not been set.
+    #[test]
+    fn test_genesis_valset_upd_rejected_without_bootstrap() {
+        let (wl_storage, keys) = setup_genesis_storage();
+        let ext = signed_valset_upd(&keys, 0.into());
+
+        let result = validate_valset_upd_vext(&wl_storage, &ext, 0.into());
+        assert_eq!(
+            result,
+            Err(InvalidVextReason::UnexpectedBlockHeight.of(KIND))
+        );
+    }
+
+    /// Test that the bootstrap validator set update is validated at
+    /// genesis, if the bootstrap flag has been set.
+    #[test]
+    fn test_genesis_bootstrap_valset_upd_accepted() {
+        let (mut wl_storage, keys) = setup_genesis_storage();
+        wl_storage
+            .write(&valset_upd_bootstrap_key(), true)
+            .expect("Test failed");
+        let ext = signed_valset_upd(&keys, 0.into());
+
+        let result = validate_valset_upd_vext(&wl_storage, &ext, 0.into());
+        assert_matches!(result, Ok(()));
+
+        // only a single update may be bootstrapped, after which
+        // the proof for the next epoch is available
+        wl_storage
+            .write(&vote_tallies::Keys::from(&Epoch(1)).seen(), true)
+            .expect("Test failed");
+        let result = validate_valset_upd_vext(&wl_storage, &ext, 0.into());
+        assert_eq!(
+            result,
+            Err(InvalidVextReason::ValsetUpdProofAvailable.of(KIND))
+        );
+    }
+
+    /// Test that the bootstrap path is only taken for updates signed
+    /// over the first epoch, and not at all once a block exists.
+    #[test]
+    fn test_bootstrap_disabled_after_first_block() {
+        let (mut wl_storage, keys) = setup_genesis_storage();
+        wl_storage
+            .write(&valset_upd_bootstrap_key(), true)
+            .expect("Test failed");
+        let ext = signed_valset_upd(&keys, 1.into());
+
+        let result = validate_valset_upd_vext(&wl_storage, &ext, 1.into());
+        assert_eq!(
+            result,
+            Err(InvalidVextReason::UnexpectedBlockHeight.of(KIND))
+        );
+
+        // once a block has been committed, the extension is subject to
+        // the regular checks, regardless of the bootstrap flag
+        wl_storage.commit_block().expect("Test failed");
+        assert!(wl_storage.storage.last_block.is_some());
+        let result = validate_valset_upd_vext(&wl_storage, &ext, 1.into());
+        assert_matches!(result, Ok(()));
+    }
+
+    /// Test that validator set updates are rejected while the bridge is
+    /// paused by governance, and accepted again once it is resumed.
+    #[test]
+    fn test_valset_upd_rejected_while_paused() {
+        let (mut wl_storage, keys) = test_utils::setup_default_storage();
+        let ext = signed_valset_upd(&keys, 0.into());
+        let result = validate_valset_upd_vext(&wl_storage, &ext, 0.into());
+        assert_matches!(result, Ok(()));
+
+        set_bridge_paused(&mut wl_storage, true).expect("Test failed");
+        let result = validate_valset_upd_vext(&wl_storage, &ext, 0.into());
+        assert_eq!(result, Err(InvalidVextReason::BridgePaused.of(KIND)));
+        let result =
+            validate_unsigned_valset_upd_vext(&wl_storage, &ext.data, 0.into());
+        assert_eq!(result, Err(InvalidVextReason::BridgePaused.of(KIND)));
+
+        set_bridge_paused(&mut wl_storage, false).expect("Test failed");
+        let result = validate_valset_upd_vext(&wl_storage, &ext, 0.into());
+        assert_matches!(result, Ok(()));
+    }
+
+    /// Test that, once domain separation is in effect, validator set
+    /// updates must be signed over the domain of this chain, and that
+    /// updates signed for another chain id are rejected.
+    #[test]
+    fn test_valset_upd_domain_separation() {
+        let (mut wl_storage, keys) = test_utils::setup_default_storage();
+        let (validator, _) = test_utils::default_validator();
+        let eth_bridge_key =
+            &keys.get(&validator).expect("Test failed").eth_bridge;
+        let legacy = signed_valset_upd(&keys, 0.into());
+        let this_chain = wl_storage.storage.chain_id.clone();
+        let other_chain = ChainId("other-chain".into());
+        let in_domain = |chain_id: &ChainId| {
+            legacy.data.sign_in_domain(eth_bridge_key, Some(chain_id))
+        };
+
+        // before domain separation is in effect, only legacy
+        // signatures are accepted
+        let result = validate_valset_upd_vext(&wl_storage, &legacy, 0.into());
+        assert_matches!(result, Ok(()));
+        let result = validate_valset_upd_vext(
+            &wl_storage,
+            &in_domain(&this_chain),
+            0.into(),
+        );
+        assert_eq!(result, Err(InvalidVextReason::VerifySigFailed.of(KIND)));
+
+        set_valset_upd_domain_epoch(&mut wl_storage, 0.into())
+            .expect("Test failed");
+        let result = validate_valset_upd_vext(
+            &wl_storage,
+            &in_domain(&this_chain),
+            0.into(),
+        );
+        assert_matches!(result, Ok(()));
+        let result = validate_valset_upd_vext(
+            &wl_storage,
+            &in_domain(&other_chain),
+            0.into(),
+        );
+        assert_eq!(result, Err(InvalidVextReason::VerifySigFailed.of(KIND)));
+        let result = validate_valset_upd_vext(&wl_storage, &legacy, 0.into());
+        assert_eq!(result, Err(InvalidVextReason::VerifySigFailed.of(KIND)));
+
+        // the same holds when validating a batch of extensions
+        let batch = validate_valset_upd_vext_batch(
+            &wl_storage,
+            &[in_domain(&this_chain), in_domain(&other_chain), legacy],
+            0.into(),
+        );
+        assert_eq!(
+            batch,
+            vec![
+                Ok(()),
+                Err(InvalidVextReason::VerifySigFailed.of(KIND)),
+                Err(InvalidVextReason::VerifySigFailed.of(KIND)),
+            ]
+        );
+    }
+
+    /// Test that the bytes returned by [`valset_upd_signing_bytes`] are
+    /// the ones signed over by the extensions accepted by validation,
+    /// before and after domain separation is in effect.
+    #[test]
+    fn test_valset_upd_signing_bytes() {
+        let (mut wl_storage, keys) = test_utils::setup_default_storage();
+        let (validator, _) = test_utils::default_validator();
+        let eth_bridge_key =
+            &keys.get(&validator).expect("Test failed").eth_bridge;
+        let legacy = signed_valset_upd(&keys, 0.into());
+        let this_chain = wl_storage.storage.chain_id.clone();
+        let in_domain = legacy
+            .data
+            .sign_in_domain(eth_bridge_key, Some(&this_chain));
+        let verifies = |ext: &validator_set_update::SignedVext,
+                        bytes: &[u8]| {
+            let digest = SignableEthMessage::as_signable(&keccak_hash(bytes));
+            common::SigScheme::verify_signature_with_hasher::<KeccakHasher>(
+                &eth_bridge_key.ref_to(),
+                &digest,
+                &ext.sig,
+            )
+            .is_ok()
+        };
+
+        let bytes = valset_upd_signing_bytes(&wl_storage, &legacy);
+        assert_eq!(bytes, legacy.data.signable_bytes(None));
+        assert!(verifies(&legacy, &bytes));
+        assert!(!verifies(&in_domain, &bytes));
+
+        set_valset_upd_domain_epoch(&mut wl_storage, 0.into())
+            .expect("Test failed");
+        let bytes = valset_upd_signing_bytes(&wl_storage, &legacy);
+        assert_eq!(bytes, legacy.data.signable_bytes(Some(&this_chain)));
+        assert_eq!(bytes, valset_upd_signing_bytes(&wl_storage, &in_domain));
+        assert!(verifies(&in_domain, &bytes));
+        assert!(!verifies(&legacy, &bytes));
+        let result = validate_valset_upd_vext(&wl_storage, &legacy, 0.into());
+        assert_eq!(result, Err(InvalidVextReason::VerifySigFailed.of(KIND)));
+    }
+
+    /// Test that validating a batch of vote extensions, whose signatures
+    /// are verified in parallel, yields the same results as validating
+    /// each extension serially.
+    #[test]
+    fn test_valset_upd_batch_matches_serial() {
+        let validators: HashMap<_, _> = (0..10)
+            .map(|_| {
+                (
+                    address::testing::gen_established_address(),
+                    token::Amount::native_whole(100),
+                )
+            })
+            .collect();
+        let (wl_storage, keys) =
+            test_utils::setup_storage_with_validators(validators);
+        let voting_powers: VotingPowersMap = wl_storage
+            .ethbridge_queries()
+            .get_consensus_eth_addresses(Some(1.into()))
+            .iter()
+            .map(|(addr_book, _, power)| (addr_book, power))
+            .collect();
+        let vext = |validator_addr: &Address, signing_epoch: Epoch| {
+            validator_set_update::Vext {
+                voting_powers: voting_powers.clone(),
+                validator_addr: validator_addr.clone(),
+                signing_epoch,
+            }
+        };
+
+        let mut exts: Vec<_> = keys
+            .iter()
+            .map(|(validator, validator_keys)| {
+                vext(validator, 0.into()).sign(&validator_keys.eth_bridge)
+            })
+            .collect();
+        let (validator, validator_keys) = keys.iter().next().unwrap();
+        // signed with the wrong key
+        exts.insert(3, vext(validator, 0.into()).sign(&validator_keys.eth_gov));
+        // signed over a future epoch
+        exts.insert(
+            7,
+            vext(validator, 1.into()).sign(&validator_keys.eth_bridge),
+        );
+        // issued by an address that isn't a validator
+        exts.push(
+            vext(&address::testing::established_address_4(), 0.into())
+                .sign(&validator_keys.eth_bridge),
+        );
+
+        let serial: Vec<_> = exts
+            .iter()
+            .map(|ext| validate_valset_upd_vext(&wl_storage, ext, 0.into()))
+            .collect();
+        let batch =
+            validate_valset_upd_vext_batch(&wl_storage, &exts, 0.into());
+        assert_eq!(batch, serial);
+        assert_eq!(batch.iter().filter(|result| result.is_ok()).count(), 10);
+        assert_eq!(batch[3], Err(InvalidVextReason::VerifySigFailed.of(KIND)));
+        assert_eq!(batch[7], Err(InvalidVextReason::UnexpectedEpoch.of(KIND)));
+        assert_eq!(
+            batch[12],
+            Err(InvalidVextReason::PubKeyNotInStorage.of(KIND))
+        );
+    }
+
+    /// Test that vote extensions are rejected past genesis if the consensus
+    /// validator set they sign over is empty, instead of an empty extension
+    /// trivially matching it.
+    #[test]
+    fn test_valset_upd_rejected_with_empty_consensus_set() {
+        let (wl_storage, _keys) = test_utils::setup_default_storage();
+        assert!(wl_storage.storage.last_block.is_some());
+        let (validator, _) = test_utils::default_validator();
+
+        // PoS storage holds no consensus validator set past the pipeline
+        // offset of the genesis epoch
+        let signing_epoch = Epoch(10);
+        assert_eq!(
+            wl_storage
+                .ethbridge_queries()
+                .get_consensus_eth_addresses(Some(signing_epoch.next()))
+                .iter()
+                .count(),
+            0
+        );
+        let ext = validator_set_update::Vext {
+            voting_powers: VotingPowersMap::new(),
+            signing_epoch,
+            validator_addr: validator,
+        };
+
+        let result =
+            validate_unsigned_valset_upd_vext(&wl_storage, &ext, signing_epoch);
+        assert_eq!(
+            result,
+            Err(InvalidVextReason::ConsensusSetUnavailable.of(KIND))
+        );
+    }
 
     /// Test that we reject vote extensions containing a superset of the
     /// next validator set in storage.
@@ -195,9 +886,244 @@ mod tests {
         .sign(&keys.get(&validator).expect("Test failed").eth_bridge);
 
         let result = validate_valset_upd_vext(&wl_storage, &ext, 0.into());
-        assert_matches!(
+        assert_eq!(
             result,
-            Err(VoteExtensionError::ExtraValidatorsInExtension)
+            Err(InvalidVextReason::ExtraValidatorsInExtension.of(KIND))
+        );
+    }
+
+    /// Test that vote extensions with more voting powers than the maximum
+    /// number of consensus validators are rejected, before looking up the
+    /// validator set or verifying their signature.
+    #[test]
+    fn test_oversized_valset_upd_rejected() {
+        let (wl_storage, keys) = test_utils::setup_default_storage();
+        let (validator, validator_stake) = test_utils::default_validator();
+        let max_validator_slots = wl_storage
+            .pos_queries()
+            .get_pos_params()
+            .max_validator_slots;
+
+        let voting_powers = (0..=max_validator_slots)
+            .map(|i| {
+                let mut addr = [0; 20];
+                addr[12..].copy_from_slice(&i.to_be_bytes());
+                let book = EthAddrBook {
+                    hot_key_addr: EthAddress(addr),
+                    cold_key_addr: EthAddress(addr),
+                };
+                (book, validator_stake)
+            })
+            .collect::<VotingPowersMap>();
+        assert_eq!(voting_powers.len() as u64, max_validator_slots + 1);
+
+        // sign with a key other than the validator's Ethereum hot key,
+        // which would fail signature verification
+        let ext = validator_set_update::Vext {
+            voting_powers,
+            signing_epoch: 0.into(),
+            validator_addr: validator.clone(),
+        }
+        .sign(&keys.get(&validator).expect("Test failed").eth_gov);
+
+        let result = validate_valset_upd_vext(&wl_storage, &ext, 0.into());
+        assert_eq!(result, Err(InvalidVextReason::ExtensionTooLarge.of(KIND)));
+        // the error is tagged with the kind of the extension, and displayed
+        // as the reason why it is faulty
+        let err = result.expect_err("Test failed");
+        assert_eq!(err.kind, ExtensionKind::ValidatorSetUpdate);
+        assert_eq!(
+            err.to_string(),
+            InvalidVextReason::ExtensionTooLarge.to_string()
+        );
+        let result =
+            validate_valset_upd_vext_batch(&wl_storage, &[ext], 0.into());
+        assert_eq!(
+            result,
+            [Err(InvalidVextReason::ExtensionTooLarge.of(KIND))]
+        );
+    }
+
+    /// Test that validator set updates signed over the last representable
+    /// epoch are rejected with a clean error, rather than overflowing when
+    /// computing the epoch signed over.
+    #[test]
+    fn test_valset_upd_epoch_overflow_rejected() {
+        let (wl_storage, keys) = test_utils::setup_default_storage();
+        assert!(wl_storage.storage.last_block.is_some());
+        let (validator, validator_stake) = test_utils::default_validator();
+        let validator_keys = keys.get(&validator).expect("Test failed");
+        let voting_powers = VotingPowersMap::from([(
+            EthAddrBook {
+                hot_key_addr: eth_addr(&validator_keys.eth_bridge),
+                cold_key_addr: eth_addr(&validator_keys.eth_gov),
+            },
+            validator_stake,
+        )]);
+        // the extension can't be signed over its actual message, which
+        // would overflow, so any signature does
+        let sig = common::SigScheme::sign_with_hasher::<KeccakHasher>(
+            &validator_keys.eth_bridge,
+            keccak_hash([0; 32]),
+        );
+        let ext = validator_set_update::SignedVext(Signed::new_from(
+            validator_set_update::Vext {
+                voting_powers,
+                signing_epoch: Epoch(u64::MAX),
+                validator_addr: validator,
+            },
+            sig,
+        ));
+
+        let last_epoch = Epoch(u64::MAX);
+        let result = validate_valset_upd_vext(&wl_storage, &ext, last_epoch);
+        assert_eq!(result, Err(InvalidVextReason::EpochOverflow.of(KIND)));
+        let result = validate_valset_upd_vext_batch(
+            &wl_storage,
+            &[ext.clone()],
+            last_epoch,
+        );
+        assert_eq!(result, [Err(InvalidVextReason::EpochOverflow.of(KIND))]);
+
+        // the signature isn't verified over an overflowing epoch either
+        let pk = validator_keys.eth_bridge.ref_to();
+        let result = verify_valset_upd_vext_sig(&ext, &pk, None);
+        assert_eq!(result, Err(InvalidVextReason::EpochOverflow.of(KIND)));
+    }
+
+    /// Test the boundaries of the check on the number of voting powers
+    /// in a validator set update vote extension.
+    #[test]
+    fn test_is_within_max_entries() {
+        assert!(is_within_max_entries(0, 0));
+        assert!(is_within_max_entries(99, 100));
+        assert!(is_within_max_entries(100, 100));
+        assert!(!is_within_max_entries(101, 100));
+        assert!(!is_within_max_entries(1, 0));
+    }
+
+    /// Test the boundaries of the check against extensions signed over
+    /// epochs past the last one.
+    #[test]
+    fn test_is_future_epoch() {
+        assert!(!is_future_epoch(Epoch(0), Epoch(0)));
+        assert!(!is_future_epoch(Epoch(4), Epoch(5)));
+        assert!(!is_future_epoch(Epoch(5), Epoch(5)));
+        assert!(is_future_epoch(Epoch(6), Epoch(5)));
+        assert!(is_future_epoch(Epoch(1), Epoch(0)));
+        assert!(!is_future_epoch(Epoch(u64::MAX), Epoch(u64::MAX)));
+        assert!(is_future_epoch(Epoch(u64::MAX), Epoch(u64::MAX - 1)));
+    }
+
+    /// Test that only updates signed over the first epoch, before any
+    /// other epoch is known, are bootstrap updates.
+    #[test]
+    fn test_is_bootstrap_epoch() {
+        assert!(is_bootstrap_epoch(Epoch(0), Epoch(0)));
+        assert!(!is_bootstrap_epoch(Epoch(0), Epoch(1)));
+        assert!(!is_bootstrap_epoch(Epoch(1), Epoch(0)));
+        assert!(!is_bootstrap_epoch(Epoch(1), Epoch(1)));
+    }
+
+    /// Test that the validator set signed over is that of the next epoch,
+    /// without wrapping around past the last representable epoch.
+    #[test]
+    fn test_target_valset_epoch() {
+        assert_eq!(target_valset_epoch(Epoch(0)), Some(Epoch(1)));
+        assert_eq!(target_valset_epoch(Epoch(41)), Some(Epoch(42)));
+        assert_eq!(
+            target_valset_epoch(Epoch(u64::MAX - 1)),
+            Some(Epoch(u64::MAX))
+        );
+        assert_eq!(target_valset_epoch(Epoch(u64::MAX)), None);
+    }
+
+    /// Test that normalized voting powers summing past the total voting
+    /// power of the bridge contracts are detected, beyond the rounding
+    /// error of their normalization.
+    #[test]
+    fn test_is_normalized_sum_within_ceiling() {
+        let max = u128::from(EthBridgeVotingPower::MAX);
+        let power =
+            |power: u128| EthBridgeVotingPower::try_from(power).unwrap();
+
+        assert!(is_normalized_sum_within_ceiling(&[]));
+        assert!(is_normalized_sum_within_ceiling(&[
+            EthBridgeVotingPower::MAX
+        ]));
+        assert!(is_normalized_sum_within_ceiling(&[
+            power(max / 3),
+            power(max / 3),
+            power(max - 2 * (max / 3)),
+        ]));
+
+        // two validators with the same voting power are each normalized to
+        // half of the total, rounded up
+        let half = max / 2 + 1;
+        assert!(is_normalized_sum_within_ceiling(&[
+            power(half),
+            power(half)
+        ]));
+        assert!(!is_normalized_sum_within_ceiling(&[
+            power(half + 1),
+            power(half)
+        ]));
+
+        // the powers are individually valid, but their sum overflows
+        assert!(!is_normalized_sum_within_ceiling(&[
+            EthBridgeVotingPower::MAX,
+            power(2),
+        ]));
+        assert!(!is_normalized_sum_within_ceiling(&vec![
+            EthBridgeVotingPower::MAX;
+            64
+        ]));
+    }
+
+    /// Test that validator set updates whose normalized voting powers sum
+    /// within the ceiling are accepted.
+    #[test]
+    fn test_normalized_sum_of_valid_valset_upd() {
+        let (wl_storage, keys) = test_utils::setup_default_storage();
+        let ext = signed_valset_upd(&keys, 0.into());
+        let normalized_powers: Vec<_> = ext
+            .data
+            .voting_powers
+            .get_normalized()
+            .into_iter()
+            .map(|(_, power)| power)
+            .collect();
+        assert_eq!(normalized_powers, vec![EthBridgeVotingPower::MAX]);
+        assert!(is_normalized_sum_within_ceiling(&normalized_powers));
+        assert_matches!(
+            validate_valset_upd_vext(&wl_storage, &ext, 0.into()),
+            Ok(())
+        );
+    }
+
+    /// Test that validator set updates whose normalized voting powers sum
+    /// past the ceiling are rejected by [`validate_valset_upd_vext`], even
+    /// though their voting powers match those in storage.
+    #[test]
+    fn test_normalized_sum_overflow_valset_upd_rejected() {
+        let (wl_storage, keys) = test_utils::setup_default_storage();
+        let ext = signed_valset_upd(&keys, 0.into());
+
+        FAULTY_NORMALIZED_POWERS.with(|powers| {
+            *powers.borrow_mut() = Some(vec![
+                EthBridgeVotingPower::MAX,
+                EthBridgeVotingPower::from(2_u64),
+            ]);
+        });
+        assert_eq!(
+            validate_valset_upd_vext(&wl_storage, &ext, 0.into()),
+            Err(InvalidVextReason::NormalizedSumOverflow.of(KIND))
+        );
+
+        // the same extension is accepted once normalized correctly
+        assert_matches!(
+            validate_valset_upd_vext(&wl_storage, &ext, 0.into()),
+            Ok(())
         );
     }
 }
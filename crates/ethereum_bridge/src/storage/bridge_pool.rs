@@ -3,8 +3,8 @@
 
 use namada_core::types::eth_bridge_pool::Segments;
 pub use namada_core::types::eth_bridge_pool::{
-    get_key_from_hash, get_pending_key, is_pending_transfer_key,
-    BRIDGE_POOL_ADDRESS,
+    get_key_from_hash, get_pending_key, get_pending_per_sender_key,
+    is_pending_transfer_key, BRIDGE_POOL_ADDRESS,
 };
 use namada_core::types::storage::{DbKeySeg, Key};
 pub use namada_state::merkle_tree::eth_bridge_pool::BridgePoolTree;
@@ -31,6 +31,19 @@ pub fn get_nonce_key() -> Key {
     }
 }
 
+/// Get the storage key for the height of the block in which the
+/// batch nonce of the bridge pool was last realigned by governance.
+pub fn get_nonce_realignment_height_key() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(BRIDGE_POOL_ADDRESS),
+            DbKeySeg::StringSeg(
+                Segments::VALUES.nonce_realignment_height.into(),
+            ),
+        ],
+    }
+}
+
 /// Check if a key belongs to the bridge pools sub-storage
 pub fn is_bridge_pool_key(key: &Key) -> bool {
     matches!(&key.segments[0], DbKeySeg::AddressSeg(addr) if addr == &BRIDGE_POOL_ADDRESS)
@@ -1,18 +1,20 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use namada_core::hints;
 use namada_core::types::address::Address;
+use namada_core::types::chain::ChainId;
 use namada_core::types::eth_abi::Encode;
 use namada_core::types::eth_bridge_pool::PendingTransfer;
 use namada_core::types::ethereum_events::{
     EthAddress, EthereumEvent, GetEventNonce, TransferToEthereum, Uint,
 };
 use namada_core::types::keccak::KeccakHash;
+use namada_core::types::key::common;
 use namada_core::types::storage::{BlockHeight, Epoch, Key as StorageKey};
 use namada_core::types::token;
 use namada_core::types::voting_power::{
     EthBridgeVotingPower, FractionalVotingPower,
 };
-use namada_proof_of_stake::pos_queries::{ConsensusValidators, PosQueries};
+use namada_proof_of_stake::pos_queries::{PosQueries, ValidatorDetails};
 use namada_proof_of_stake::storage::{
     validator_eth_cold_key_handle, validator_eth_hot_key_handle,
 };
@@ -22,8 +24,18 @@ use namada_vote_ext::validator_set_update::{
     EthAddrBook, ValidatorSetArgs, VotingPowersMap, VotingPowersMapExt,
 };
 
+use crate::protocol::transactions::votes::Votes;
+use crate::storage::parameters::{
+    read_bp_root_height_binding_epoch, read_bridge_paused, read_contracts,
+    read_max_vext_digest_bytes, read_nonce_realignment_height,
+    read_relay_gas_model, read_scheduled_contracts_update,
+    read_superseded_contracts, read_valset_upd_domain_epoch, Contracts,
+    RelayGasModel,
+};
 use crate::storage::proof::BridgePoolRootProof;
-use crate::storage::{active_key, bridge_pool, vote_tallies, whitelist};
+use crate::storage::{
+    active_key, bridge_pool, valset_upd_bootstrap_key, vote_tallies, whitelist,
+};
 
 /// This enum is used as a parameter to
 /// [`EthBridgeQueriesHook::must_send_valset_upd`].
@@ -144,6 +156,64 @@ where
             .unwrap_or(false)
     }
 
+    /// Check which of the epochs between `from` and `to`, both inclusive,
+    /// have a validator set update proof available, in ascending order.
+    /// The first epoch, which has no validator set update proof, is
+    /// skipped.
+    pub fn valset_upd_seen_range(
+        self,
+        from: Epoch,
+        to: Epoch,
+    ) -> Vec<(Epoch, bool)> {
+        (from.0.max(1)..=to.0)
+            .map(Epoch)
+            .map(|epoch| (epoch, self.valset_upd_seen(epoch)))
+            .collect()
+    }
+
+    /// Check if a proof of the addresses of the Ethereum bridge contracts
+    /// scheduled by governance is available for the given signing
+    /// [`Epoch`].
+    pub fn contract_addresses_seen(self, epoch: Epoch) -> bool {
+        let keys = vote_tallies::contract_addresses_keys(&epoch);
+        self.wl_storage
+            .read(&keys.seen())
+            .expect("Reading a value from storage should not fail")
+            .unwrap_or(false)
+    }
+
+    /// Get the consensus validators who sign the validator set update to
+    /// the given [`Epoch`], i.e. those of the epoch before it, and who have
+    /// not yet voted for it, sorted by descending stake.
+    ///
+    /// The list is empty once the proof of the validator set update is
+    /// complete, and for the first epoch, which has no validator set
+    /// update proof.
+    pub fn missing_valset_upd_voters(self, epoch: Epoch) -> Vec<Address> {
+        if epoch.0 == 0 || self.valset_upd_seen(epoch) {
+            return vec![];
+        }
+        let seen_by: Votes = self
+            .wl_storage
+            .read(&vote_tallies::Keys::from(&epoch).seen_by())
+            .expect("Reading a value from storage should not fail")
+            .unwrap_or_default();
+        self.get_consensus_eth_addresses(Some(epoch.prev()))
+            .iter()
+            .map(|(_, address, _)| address)
+            .filter(|address| !seen_by.contains_key(address))
+            .collect()
+    }
+
+    /// Check if the validation of a bootstrap validator set update
+    /// vote extension was enabled at genesis.
+    pub fn valset_upd_bootstrap_enabled(self) -> bool {
+        self.wl_storage
+            .read(&valset_upd_bootstrap_key())
+            .expect("Reading a value from storage should not fail")
+            .unwrap_or(false)
+    }
+
     /// Check if the bridge is disabled, enabled, or
     /// scheduled to be enabled at a specified epoch.
     pub fn check_bridge_status(self) -> EthBridgeStatus {
@@ -178,6 +248,90 @@ where
         }
     }
 
+    /// Check if the Ethereum bridge was paused by governance.
+    pub fn is_bridge_paused(self) -> bool {
+        read_bridge_paused(self.wl_storage)
+            .expect("Reading the Ethereum bridge paused flag shouldn't fail")
+    }
+
+    /// Check if the Bridge pool root computed at the given height must be
+    /// signed along with its height, i.e. if the epoch of the height is
+    /// past the height binding epoch set by governance.
+    pub fn is_bp_root_height_bound_at(self, height: BlockHeight) -> bool {
+        let Some(binding_epoch) =
+            read_bp_root_height_binding_epoch(self.wl_storage).expect(
+                "Reading the Bridge pool root height binding epoch shouldn't \
+                 fail",
+            )
+        else {
+            return false;
+        };
+        self.wl_storage
+            .pos_queries()
+            .get_epoch(height)
+            .map(|epoch| epoch >= binding_epoch)
+            .unwrap_or(false)
+    }
+
+    /// Get the domain of the signatures of the validator set updates
+    /// issued at `signing_epoch`, i.e. the chain id, if the epoch is past
+    /// the domain separation epoch set by governance. Otherwise, the
+    /// signatures are not separated to any domain.
+    pub fn valset_upd_signing_domain(
+        self,
+        signing_epoch: Epoch,
+    ) -> Option<ChainId> {
+        let domain_epoch = read_valset_upd_domain_epoch(self.wl_storage)
+            .expect(
+                "Reading the validator set update domain separation epoch \
+                 shouldn't fail",
+            )?;
+        (signing_epoch >= domain_epoch)
+            .then(|| self.wl_storage.storage.chain_id.clone())
+    }
+
+    /// Get the maximum number of bytes of the vote extension protocol txs
+    /// included in a block, if set by governance.
+    pub fn get_max_vext_digest_bytes(self) -> Option<u64> {
+        read_max_vext_digest_bytes(self.wl_storage).expect(
+            "Reading the maximum size of the vote extensions in a block \
+             shouldn't fail",
+        )
+    }
+
+    /// Get the model of the Ethereum gas cost of relaying proofs to the
+    /// bridge contracts, as calibrated by governance, or else the default
+    /// one.
+    pub fn get_relay_gas_model(self) -> RelayGasModel {
+        read_relay_gas_model(self.wl_storage)
+            .expect("Reading the relay gas model shouldn't fail")
+            .unwrap_or_default()
+    }
+
+    /// Get the Ethereum bridge [`Contracts`] in effect at the given
+    /// [`Epoch`], taking into account any update scheduled by governance,
+    /// as well as the contracts superseded by the last activated update.
+    ///
+    /// Fails if the contracts can't be read from storage, e.g. on chains
+    /// whose Ethereum bridge was never configured.
+    pub fn get_contracts_at(
+        self,
+        epoch: Epoch,
+    ) -> namada_storage::Result<Contracts> {
+        if let Some(update) = read_scheduled_contracts_update(self.wl_storage)?
+        {
+            if epoch >= update.activation_epoch {
+                return Ok(update.contracts);
+            }
+        }
+        match read_superseded_contracts(self.wl_storage)? {
+            Some(superseded) if epoch < superseded.activation_epoch => {
+                Ok(superseded.contracts)
+            }
+            _ => read_contracts(self.wl_storage),
+        }
+    }
+
     /// Get the nonce of the next transfers to Namada event to be processed.
     pub fn get_next_nam_transfers_nonce(self) -> Uint {
         self.wl_storage
@@ -220,6 +374,16 @@ where
         .expect("Deserializing the signed nonce from storage should not fail.")
     }
 
+    /// Get the height of the block in which the Bridge pool nonce was
+    /// last realigned by governance, if it ever was.
+    pub fn get_bridge_pool_nonce_realignment_height(
+        self,
+    ) -> Option<BlockHeight> {
+        read_nonce_realignment_height(self.wl_storage).expect(
+            "Reading the Bridge pool nonce realignment height shouldn't fail.",
+        )
+    }
+
     /// Get the latest root of the Ethereum bridge
     /// pool Merkle tree.
     pub fn get_bridge_pool_root(self) -> KeccakHash {
@@ -336,22 +500,21 @@ where
     /// Extension of
     /// [`get_consensus_validators`](namada_proof_of_stake::pos_queries::PosQueriesHook::get_consensus_validators),
     /// which additionally returns all Ethereum addresses of some validator.
+    ///
+    /// Built from
+    /// [`consensus_validator_details`](namada_proof_of_stake::pos_queries::PosQueriesHook::consensus_validator_details),
+    /// hence the validators are sorted by descending stake.
     #[inline]
     pub fn get_consensus_eth_addresses(
         self,
         epoch: Option<Epoch>,
-    ) -> ConsensusEthAddresses<'db, D, H> {
+    ) -> ConsensusEthAddresses {
         let epoch = epoch
             .unwrap_or_else(|| self.wl_storage.storage.get_current_epoch().0);
-        let consensus_validators = self
-            .wl_storage
+        self.wl_storage
             .pos_queries()
-            .get_consensus_validators(Some(epoch));
-        ConsensusEthAddresses {
-            wl_storage: self.wl_storage,
-            consensus_validators,
-            epoch,
-        }
+            .consensus_validator_details(Some(epoch))
+            .into()
     }
 
     /// Query a chosen [`ValidatorSetArgs`] at the given [`Epoch`].
@@ -481,8 +644,8 @@ where
     /// NUTs are minted when:
     ///
     /// 1. `token` is not whitelisted.
-    /// 2. `token` has exceeded the configured token caps,
-    ///    after minting `amount_to_mint`.
+    /// 2. `token` has exceeded the configured token caps, after minting
+    ///    `amount_to_mint`.
     pub fn get_eth_assets_to_mint(
         self,
         token: &EthAddress,
@@ -609,33 +772,141 @@ impl EthAssetMint {
 
 /// A handle to the Ethereum addresses of the set of consensus
 /// validators in Namada, at some given epoch.
-pub struct ConsensusEthAddresses<'db, D, H>
-where
-    D: 'static + DB + for<'iter> DBIter<'iter>,
-    H: 'static + StorageHasher,
-{
-    epoch: Epoch,
-    wl_storage: &'db WlStorage<D, H>,
-    consensus_validators: ConsensusValidators<'db, WlStorage<D, H>>,
+pub struct ConsensusEthAddresses {
+    validators: Vec<ValidatorDetails>,
 }
 
-impl<'db, D, H> ConsensusEthAddresses<'db, D, H>
-where
-    D: 'static + DB + for<'iter> DBIter<'iter>,
-    H: 'static + StorageHasher,
-{
+impl From<Vec<ValidatorDetails>> for ConsensusEthAddresses {
+    #[inline]
+    fn from(validators: Vec<ValidatorDetails>) -> Self {
+        Self { validators }
+    }
+}
+
+impl ConsensusEthAddresses {
     /// Iterate over the Ethereum addresses of the set of consensus validators
     /// in Namada, at some given epoch.
-    pub fn iter<'this: 'db>(
-        &'this self,
-    ) -> impl Iterator<Item = (EthAddrBook, Address, token::Amount)> + 'db {
-        self.consensus_validators.iter().map(move |validator| {
-            let eth_addr_book = self
-                .wl_storage
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = (EthAddrBook, Address, token::Amount)> + '_ {
+        self.validators.iter().map(consensus_eth_address)
+    }
+}
+
+/// Return the Ethereum addresses of a consensus validator, along with
+/// its Namada address and stake.
+pub(crate) fn consensus_eth_address(
+    validator: &ValidatorDetails,
+) -> (EthAddrBook, Address, token::Amount) {
+    let to_eth_addr = |key: &Option<common::PublicKey>| -> EthAddress {
+        key.as_ref()
+            .and_then(|pk| pk.try_into().ok())
+            .expect("All Namada validators should have Ethereum keys")
+    };
+    let eth_addr_book = EthAddrBook {
+        hot_key_addr: to_eth_addr(&validator.eth_hot_key),
+        cold_key_addr: to_eth_addr(&validator.eth_cold_key),
+    };
+    (eth_addr_book, validator.address.clone(), validator.stake)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use namada_core::types::address::testing::{
+        established_address_1, established_address_2, established_address_3,
+    };
+    use namada_state::testing::TestWlStorage;
+    use namada_storage::StorageWrite;
+
+    use super::*;
+    use crate::test_utils;
+
+    /// Test that the availability of validator set update proofs is
+    /// reported for every epoch of a range, skipping the first epoch
+    #[test]
+    fn test_valset_upd_seen_range() {
+        let mut wl_storage = TestWlStorage::default();
+        for epoch in [1, 2, 4] {
+            wl_storage
+                .write(&vote_tallies::Keys::from(&Epoch(epoch)).seen(), true)
+                .expect("Test failed");
+        }
+        // a proof still collecting signatures isn't seen
+        wl_storage
+            .write(&vote_tallies::Keys::from(&Epoch(5)).seen(), false)
+            .expect("Test failed");
+
+        let queries = wl_storage.ethbridge_queries();
+        assert_eq!(
+            queries.valset_upd_seen_range(Epoch(0), Epoch(6)),
+            vec![
+                (Epoch(1), true),
+                (Epoch(2), true),
+                (Epoch(3), false),
+                (Epoch(4), true),
+                (Epoch(5), false),
+                (Epoch(6), false),
+            ]
+        );
+        assert_eq!(
+            queries.valset_upd_seen_range(Epoch(3), Epoch(4)),
+            vec![(Epoch(3), false), (Epoch(4), true)]
+        );
+        assert!(queries.valset_upd_seen_range(Epoch(4), Epoch(3)).is_empty());
+    }
+    /// Test that the validators missing from a partially attested validator
+    /// set update are those of the signing epoch who haven't voted yet, and
+    /// that none are missing once the proof is complete
+    #[test]
+    fn test_missing_valset_upd_voters() {
+        let (mut wl_storage, _) =
+            test_utils::setup_storage_with_validators(HashMap::from_iter([
+                (established_address_1(), token::Amount::native_whole(100)),
+                (established_address_2(), token::Amount::native_whole(200)),
+                (established_address_3(), token::Amount::native_whole(300)),
+            ]));
+        let keys = vote_tallies::Keys::from(&Epoch(1));
+
+        // nobody voted yet
+        assert_eq!(
+            wl_storage
                 .ethbridge_queries()
-                .get_eth_addr_book(&validator.address, Some(self.epoch))
-                .expect("All Namada validators should have Ethereum keys");
-            (eth_addr_book, validator.address, validator.bonded_stake)
-        })
+                .missing_valset_upd_voters(Epoch(1)),
+            vec![
+                established_address_3(),
+                established_address_2(),
+                established_address_1(),
+            ]
+        );
+
+        // the validator with the largest stake voted
+        let seen_by = Votes::from([(established_address_3(), BlockHeight(1))]);
+        wl_storage
+            .write(&keys.seen_by(), seen_by)
+            .expect("Test failed");
+        wl_storage.write(&keys.seen(), false).expect("Test failed");
+        assert_eq!(
+            wl_storage
+                .ethbridge_queries()
+                .missing_valset_upd_voters(Epoch(1)),
+            vec![established_address_2(), established_address_1()]
+        );
+
+        // the proof is complete
+        wl_storage.write(&keys.seen(), true).expect("Test failed");
+        assert!(
+            wl_storage
+                .ethbridge_queries()
+                .missing_valset_upd_voters(Epoch(1))
+                .is_empty()
+        );
+        assert!(
+            wl_storage
+                .ethbridge_queries()
+                .missing_valset_upd_voters(Epoch(0))
+                .is_empty()
+        );
     }
 }
@@ -65,6 +65,71 @@ pub fn bridge_contract_key() -> Key {
     get_bridge_contract_address_key_at_addr(PARAM_ADDRESS)
 }
 
+/// Storage key for an update of the Ethereum bridge contracts scheduled
+/// by governance.
+pub fn scheduled_contracts_update_key() -> Key {
+    get_scheduled_contracts_update_key_at_addr(PARAM_ADDRESS)
+}
+
+/// Storage key for the Ethereum bridge contracts superseded by the last
+/// update scheduled by governance.
+pub fn superseded_contracts_key() -> Key {
+    get_superseded_contracts_key_at_addr(PARAM_ADDRESS)
+}
+
+/// Storage key for the flag pausing the Ethereum bridge, set by
+/// governance.
+pub fn bridge_paused_key() -> Key {
+    get_bridge_paused_key_at_addr(PARAM_ADDRESS)
+}
+
+/// Storage key for the epoch from which the Bridge pool roots are signed
+/// along with their height, set by governance.
+pub fn bp_root_height_binding_epoch_key() -> Key {
+    get_bp_root_height_binding_epoch_key_at_addr(PARAM_ADDRESS)
+}
+
+/// Storage key for the epoch from which the validator set updates are
+/// signed over a message separated to the domain of the chain, set by
+/// governance.
+pub fn valset_upd_domain_epoch_key() -> Key {
+    get_valset_upd_domain_epoch_key_at_addr(PARAM_ADDRESS)
+}
+
+/// Storage key for the maximum number of bytes of the vote extension
+/// protocol txs included in a block, set by governance.
+pub fn max_vext_digest_bytes_key() -> Key {
+    get_max_vext_digest_bytes_key_at_addr(PARAM_ADDRESS)
+}
+
+/// Storage key for the model of the Ethereum gas cost of relaying proofs
+/// to the bridge contracts, set by governance.
+pub fn relay_gas_model_key() -> Key {
+    get_relay_gas_model_key_at_addr(PARAM_ADDRESS)
+}
+
+/// Storage key for the maximum number of transfers a single sender may
+/// have pending in the Bridge pool, set by governance.
+pub fn max_pending_transfers_per_sender_key() -> Key {
+    get_max_pending_transfers_per_sender_key_at_addr(PARAM_ADDRESS)
+}
+
+/// Storage key for the flag allowing a bootstrap validator set update
+/// vote extension to be validated at genesis, before any block has been
+/// committed.
+pub fn valset_upd_bootstrap_key() -> Key {
+    prefix()
+        .push(&"valset_upd_bootstrap".to_owned())
+        .expect("should always be able to construct this key")
+}
+
+/// Storage key for the block height at which the minted supply of the
+/// wrapped Ethereum assets is backfilled from their balances, set by
+/// governance.
+pub fn wrapped_supply_backfill_height_key() -> Key {
+    get_wrapped_supply_backfill_height_key_at_addr(PARAM_ADDRESS)
+}
+
 #[cfg(test)]
 mod test {
     use namada_core::types::address;
@@ -5,8 +5,9 @@ use eyre::{eyre, Result};
 use namada_core::borsh::{BorshDeserialize, BorshSerialize};
 use namada_core::types::ethereum_events::EthAddress;
 use namada_core::types::ethereum_structs;
-use namada_core::types::storage::Key;
+use namada_core::types::storage::{BlockHeight, Epoch, Key};
 use namada_core::types::token::{DenominatedAmount, NATIVE_MAX_DECIMAL_PLACES};
+use namada_core::types::uint::Uint;
 use namada_state::{DBIter, StorageHasher, WlStorage, DB};
 use namada_storage::{StorageRead, StorageWrite};
 use serde::{Deserialize, Serialize};
@@ -73,6 +74,51 @@ impl From<MinimumConfirmations> for NonZeroU64 {
     }
 }
 
+/// A model of the Ethereum gas cost of relaying a proof to the bridge
+/// contracts, which relayers may use to decide whether relaying is
+/// profitable. Its constants are calibrated by governance, as the costs of
+/// the contracts change.
+#[derive(
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Debug,
+    Deserialize,
+    Serialize,
+    BorshSerialize,
+    BorshDeserialize,
+)]
+pub struct RelayGasModel {
+    /// The gas cost of any relay call, irrespective of the relayed proof.
+    pub base_gas: u64,
+    /// The gas cost of verifying each signature of the relayed proof.
+    pub gas_per_signature: u64,
+    /// The gas cost of executing each transfer of a relayed Bridge pool
+    /// batch.
+    pub gas_per_transfer: u64,
+}
+
+impl Default for RelayGasModel {
+    fn default() -> Self {
+        Self {
+            base_gas: 80_000,
+            gas_per_signature: 7_000,
+            gas_per_transfer: 35_000,
+        }
+    }
+}
+
+impl RelayGasModel {
+    /// Estimate the gas cost of relaying a proof with the given number of
+    /// signatures and of transfers, saturating at [`u64::MAX`].
+    pub fn estimate(&self, signatures: u64, transfers: u64) -> u64 {
+        self.base_gas
+            .saturating_add(self.gas_per_signature.saturating_mul(signatures))
+            .saturating_add(self.gas_per_transfer.saturating_mul(transfers))
+    }
+}
+
 /// Represents a configuration value for the version of a contract that can be
 /// upgraded. Starts from 1.
 #[derive(
@@ -98,6 +144,13 @@ impl Default for ContractVersion {
     }
 }
 
+impl ContractVersion {
+    /// Return the version that follows this one.
+    pub fn next(self) -> Self {
+        Self(self.0.saturating_add(1))
+    }
+}
+
 /// Represents an Ethereum contract that may be upgraded.
 #[derive(
     Copy,
@@ -138,6 +191,34 @@ pub struct Contracts {
     pub bridge: UpgradeableContract,
 }
 
+/// An update of the Ethereum bridge [`Contracts`], scheduled by governance
+/// to take effect from some activation epoch onwards.
+#[derive(
+    Copy, Clone, Debug, Eq, PartialEq, BorshSerialize, BorshDeserialize,
+)]
+pub struct ScheduledContractsUpdate {
+    /// The first epoch in which the new contracts are in effect.
+    pub activation_epoch: Epoch,
+    /// The contracts in effect from the activation epoch onwards.
+    pub contracts: Contracts,
+    /// The transfer nonce of the redeployed bridge contract, if the Bridge
+    /// pool nonce must be realigned with it at the activation epoch.
+    pub bridge_pool_nonce: Option<Uint>,
+}
+
+/// The Ethereum bridge [`Contracts`] superseded by the last update
+/// scheduled by governance.
+#[derive(
+    Copy, Clone, Debug, Eq, PartialEq, BorshSerialize, BorshDeserialize,
+)]
+pub struct SupersededContracts {
+    /// The activation epoch of the update, i.e. the first epoch in which
+    /// the superseded contracts are no longer in effect.
+    pub activation_epoch: Epoch,
+    /// The contracts in effect up to the activation epoch of the update.
+    pub contracts: Contracts,
+}
+
 /// Represents chain parameters for the Ethereum bridge.
 #[derive(
     Clone,
@@ -338,6 +419,333 @@ where
     }
 }
 
+/// Read the Ethereum bridge [`Contracts`] currently in effect.
+pub fn read_contracts<S>(storage: &S) -> namada_storage::Result<Contracts>
+where
+    S: StorageRead,
+{
+    let native_erc20 = storage
+        .read(&bridge_storage::native_erc20_key())?
+        .ok_or_else(|| {
+            namada_storage::Error::new_const(
+                "The native ERC20 address was not found in storage",
+            )
+        })?;
+    let bridge = storage
+        .read(&bridge_storage::bridge_contract_key())?
+        .ok_or_else(|| {
+            namada_storage::Error::new_const(
+                "The bridge contract was not found in storage",
+            )
+        })?;
+    Ok(Contracts {
+        native_erc20,
+        bridge,
+    })
+}
+
+/// Read the update of the Ethereum bridge [`Contracts`] scheduled by
+/// governance, if any.
+pub fn read_scheduled_contracts_update<S>(
+    storage: &S,
+) -> namada_storage::Result<Option<ScheduledContractsUpdate>>
+where
+    S: StorageRead,
+{
+    storage.read(&bridge_storage::scheduled_contracts_update_key())
+}
+
+/// Schedule an update of the Ethereum bridge [`Contracts`], replacing any
+/// previously scheduled update.
+pub fn schedule_contracts_update<S>(
+    storage: &mut S,
+    update: ScheduledContractsUpdate,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    storage.write(&bridge_storage::scheduled_contracts_update_key(), update)
+}
+
+/// Read the Ethereum bridge [`Contracts`] superseded by the last update
+/// scheduled by governance, if any was ever activated.
+pub fn read_superseded_contracts<S>(
+    storage: &S,
+) -> namada_storage::Result<Option<SupersededContracts>>
+where
+    S: StorageRead,
+{
+    storage.read(&bridge_storage::superseded_contracts_key())
+}
+
+/// Switch to the scheduled Ethereum bridge [`Contracts`], if their
+/// activation epoch has been reached by `current_epoch`. On success, the
+/// previous and the new contracts are returned.
+///
+/// The contract addresses stored under the bridge configuration keys are
+/// swapped, and the previous contracts are kept as
+/// [`SupersededContracts`], such that the Ethereum events vote extensions
+/// issued before the activation epoch are still validated against them.
+/// Events that were already observed from the old contracts are still
+/// confirmed, as their vote tallies are independent of the addresses of
+/// the contracts that emitted them. If the update carries the transfer
+/// nonce of a redeployed bridge contract, the Bridge pool nonce is
+/// realigned with it, as per [`realign_bridge_pool_nonce`].
+pub fn apply_scheduled_contracts_update<S>(
+    storage: &mut S,
+    current_epoch: Epoch,
+) -> namada_storage::Result<Option<(Contracts, Contracts)>>
+where
+    S: StorageRead + StorageWrite,
+{
+    let Some(update) = read_scheduled_contracts_update(storage)? else {
+        return Ok(None);
+    };
+    if update.activation_epoch > current_epoch {
+        return Ok(None);
+    }
+    let old_contracts = read_contracts(storage)?;
+    storage.write(
+        &bridge_storage::superseded_contracts_key(),
+        SupersededContracts {
+            activation_epoch: update.activation_epoch,
+            contracts: old_contracts,
+        },
+    )?;
+    let Contracts {
+        native_erc20,
+        bridge,
+    } = update.contracts;
+    storage.write(&bridge_storage::native_erc20_key(), native_erc20)?;
+    storage.write(&bridge_storage::bridge_contract_key(), bridge)?;
+    if let Some(nonce) = update.bridge_pool_nonce {
+        realign_bridge_pool_nonce(storage, nonce)?;
+    }
+    storage.delete(&bridge_storage::scheduled_contracts_update_key())?;
+    Ok(Some((old_contracts, update.contracts)))
+}
+
+/// Realign the Bridge pool nonce with the transfer nonce of a redeployed
+/// bridge contract.
+///
+/// The signed Bridge pool root was computed under the previous nonce, and
+/// can't be relayed to the new contract, hence its proof is pruned. The
+/// transfers it covered remain pending in the Bridge pool, until they are
+/// covered by a root signed under the new nonce. Bridge pool root vote
+/// extensions issued before the current block height, i.e. signed under
+/// the previous nonce, are rejected from then on.
+pub fn realign_bridge_pool_nonce<S>(
+    storage: &mut S,
+    nonce: Uint,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let height = storage.get_block_height()?;
+    let old_nonce: Option<Uint> =
+        storage.read(&bridge_storage::bridge_pool::get_nonce_key())?;
+    tracing::info!(
+        ?old_nonce,
+        new_nonce = ?nonce,
+        %height,
+        "Realigning the Bridge pool nonce with the bridge contract"
+    );
+    storage.write(&bridge_storage::bridge_pool::get_nonce_key(), nonce)?;
+    storage.write(
+        &bridge_storage::bridge_pool::get_nonce_realignment_height_key(),
+        height,
+    )?;
+    storage.delete(&bridge_storage::bridge_pool::get_signed_root_key())
+}
+
+/// Read the height of the block in which the Bridge pool nonce was last
+/// realigned by governance, if it ever was.
+pub fn read_nonce_realignment_height<S>(
+    storage: &S,
+) -> namada_storage::Result<Option<BlockHeight>>
+where
+    S: StorageRead,
+{
+    storage
+        .read(&bridge_storage::bridge_pool::get_nonce_realignment_height_key())
+}
+
+/// Check if the Ethereum bridge was paused by governance.
+pub fn read_bridge_paused<S>(storage: &S) -> namada_storage::Result<bool>
+where
+    S: StorageRead,
+{
+    Ok(storage
+        .read(&bridge_storage::bridge_paused_key())?
+        .unwrap_or(false))
+}
+
+/// Pause or resume the Ethereum bridge. While paused, vote extensions
+/// of the bridge are rejected.
+pub fn set_bridge_paused<S>(
+    storage: &mut S,
+    paused: bool,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    storage.write(&bridge_storage::bridge_paused_key(), paused)
+}
+
+/// Read the epoch from which the Bridge pool roots must be signed along
+/// with the height at which they were computed, if set by governance.
+/// Until then, roots signed in either format are accepted.
+pub fn read_bp_root_height_binding_epoch<S>(
+    storage: &S,
+) -> namada_storage::Result<Option<Epoch>>
+where
+    S: StorageRead,
+{
+    storage.read(&bridge_storage::bp_root_height_binding_epoch_key())
+}
+
+/// Set the epoch from which the Bridge pool roots must be signed along
+/// with the height at which they were computed. This should only be set
+/// once the bridge contract verifies roots signed in this format.
+pub fn set_bp_root_height_binding_epoch<S>(
+    storage: &mut S,
+    epoch: Epoch,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    storage.write(&bridge_storage::bp_root_height_binding_epoch_key(), epoch)
+}
+
+/// Read the epoch from which the validator set updates must be signed
+/// over a message separated to the domain of the chain, if set by
+/// governance.
+pub fn read_valset_upd_domain_epoch<S>(
+    storage: &S,
+) -> namada_storage::Result<Option<Epoch>>
+where
+    S: StorageRead,
+{
+    storage.read(&bridge_storage::valset_upd_domain_epoch_key())
+}
+
+/// Set the epoch from which the validator set updates must be signed over
+/// a message separated to the domain of the chain. This should only be set
+/// once the governance contract verifies signatures in this format.
+pub fn set_valset_upd_domain_epoch<S>(
+    storage: &mut S,
+    epoch: Epoch,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    storage.write(&bridge_storage::valset_upd_domain_epoch_key(), epoch)
+}
+
+/// Read the maximum number of bytes of the vote extension protocol txs
+/// included in a block, if set by governance. Otherwise, vote extensions
+/// are only bounded by the size of the block.
+pub fn read_max_vext_digest_bytes<S>(
+    storage: &S,
+) -> namada_storage::Result<Option<u64>>
+where
+    S: StorageRead,
+{
+    storage.read(&bridge_storage::max_vext_digest_bytes_key())
+}
+
+/// Set the maximum number of bytes of the vote extension protocol txs
+/// included in a block.
+pub fn set_max_vext_digest_bytes<S>(
+    storage: &mut S,
+    max_bytes: u64,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    storage.write(&bridge_storage::max_vext_digest_bytes_key(), max_bytes)
+}
+
+/// Read the block height at which the minted supply of the wrapped
+/// Ethereum assets is backfilled from their balances, if set by
+/// governance.
+pub fn read_wrapped_supply_backfill_height<S>(
+    storage: &S,
+) -> namada_storage::Result<Option<BlockHeight>>
+where
+    S: StorageRead,
+{
+    storage.read(&bridge_storage::wrapped_supply_backfill_height_key())
+}
+
+/// Set the block height at which the minted supply of the wrapped Ethereum
+/// assets is backfilled from their balances. This should be the upgrade
+/// height from which the wrapped assets are minted and burned through the
+/// token module.
+pub fn set_wrapped_supply_backfill_height<S>(
+    storage: &mut S,
+    height: BlockHeight,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    storage.write(
+        &bridge_storage::wrapped_supply_backfill_height_key(),
+        height,
+    )
+}
+
+/// Read the model of the Ethereum gas cost of relaying proofs to the
+/// bridge contracts, if calibrated by governance. Otherwise, the default
+/// model applies.
+pub fn read_relay_gas_model<S>(
+    storage: &S,
+) -> namada_storage::Result<Option<RelayGasModel>>
+where
+    S: StorageRead,
+{
+    storage.read(&bridge_storage::relay_gas_model_key())
+}
+
+/// Set the model of the Ethereum gas cost of relaying proofs to the bridge
+/// contracts.
+pub fn set_relay_gas_model<S>(
+    storage: &mut S,
+    model: &RelayGasModel,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    storage.write(&bridge_storage::relay_gas_model_key(), model)
+}
+
+/// Read the maximum number of transfers a single sender may have pending
+/// in the Bridge pool, if set by governance. Otherwise, the number of
+/// pending transfers of a sender is unbounded.
+pub fn read_max_pending_transfers_per_sender<S>(
+    storage: &S,
+) -> namada_storage::Result<Option<u64>>
+where
+    S: StorageRead,
+{
+    storage.read(&bridge_storage::max_pending_transfers_per_sender_key())
+}
+
+/// Set the maximum number of transfers a single sender may have pending in
+/// the Bridge pool.
+pub fn set_max_pending_transfers_per_sender<S>(
+    storage: &mut S,
+    max_pending: u64,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    storage.write(
+        &bridge_storage::max_pending_transfers_per_sender_key(),
+        max_pending,
+    )
+}
+
 /// Reads the value of `key` from `storage` and deserializes it, or panics
 /// otherwise.
 fn must_read_key<D, H, T: BorshDeserialize>(
@@ -365,6 +773,7 @@ where
 mod tests {
     use eyre::Result;
     use namada_core::types::ethereum_events::EthAddress;
+    use namada_core::types::keccak::KeccakHash;
     use namada_state::testing::TestWlStorage;
 
     use super::*;
@@ -473,4 +882,165 @@ mod tests {
         // This should panic as the other config values are not written
         EthereumOracleConfig::read(&wl_storage);
     }
+
+    /// Test that a scheduled update of the bridge contracts only takes
+    /// effect from its activation epoch onwards.
+    #[test]
+    fn test_scheduled_contracts_update_crossover_epoch() {
+        let mut wl_storage = TestWlStorage::default();
+        let old_contracts = Contracts {
+            native_erc20: EthAddress([42; 20]),
+            bridge: UpgradeableContract {
+                address: EthAddress([23; 20]),
+                version: ContractVersion::default(),
+            },
+        };
+        let config = EthereumBridgeParams {
+            erc20_whitelist: vec![],
+            eth_start_height: Default::default(),
+            min_confirmations: MinimumConfirmations::default(),
+            contracts: old_contracts,
+        };
+        config.init_storage(&mut wl_storage);
+
+        let new_contracts = Contracts {
+            native_erc20: EthAddress([43; 20]),
+            bridge: UpgradeableContract {
+                address: EthAddress([24; 20]),
+                version: ContractVersion::default().next(),
+            },
+        };
+        let activation_epoch = Epoch(5);
+        schedule_contracts_update(
+            &mut wl_storage,
+            ScheduledContractsUpdate {
+                activation_epoch,
+                contracts: new_contracts,
+                bridge_pool_nonce: None,
+            },
+        )
+        .unwrap();
+
+        // before the crossover epoch, the old contracts are in effect
+        let queries = wl_storage.ethbridge_queries();
+        assert_eq!(queries.get_contracts_at(Epoch(4)).unwrap(), old_contracts);
+        assert_eq!(
+            queries.get_contracts_at(activation_epoch).unwrap(),
+            new_contracts
+        );
+        assert_eq!(
+            apply_scheduled_contracts_update(&mut wl_storage, Epoch(4))
+                .unwrap(),
+            None
+        );
+        assert_eq!(read_contracts(&wl_storage).unwrap(), old_contracts);
+
+        // at the crossover epoch, the new contracts are switched in
+        assert_eq!(
+            apply_scheduled_contracts_update(&mut wl_storage, activation_epoch)
+                .unwrap(),
+            Some((old_contracts, new_contracts))
+        );
+        assert_eq!(read_contracts(&wl_storage).unwrap(), new_contracts);
+        assert_eq!(
+            EthereumOracleConfig::read(&wl_storage).unwrap().contracts,
+            new_contracts
+        );
+        assert!(
+            read_scheduled_contracts_update(&wl_storage)
+                .unwrap()
+                .is_none()
+        );
+        // the vote extensions issued before the crossover epoch are still
+        // validated against the superseded contracts
+        assert_eq!(
+            read_superseded_contracts(&wl_storage).unwrap(),
+            Some(SupersededContracts {
+                activation_epoch,
+                contracts: old_contracts,
+            })
+        );
+        let queries = wl_storage.ethbridge_queries();
+        assert_eq!(queries.get_contracts_at(Epoch(4)).unwrap(), old_contracts);
+        assert_eq!(
+            queries.get_contracts_at(activation_epoch).unwrap(),
+            new_contracts
+        );
+        assert_eq!(queries.get_contracts_at(Epoch(6)).unwrap(), new_contracts);
+    }
+
+    /// Test that a contracts update carrying the transfer nonce of a
+    /// redeployed bridge contract realigns the Bridge pool nonce at its
+    /// activation epoch, pruning the signed root while leaving the pending
+    /// transfers in the pool.
+    #[test]
+    fn test_scheduled_contracts_update_realigns_bp_nonce() {
+        let mut wl_storage = TestWlStorage::default();
+        let old_contracts = Contracts {
+            native_erc20: EthAddress([42; 20]),
+            bridge: UpgradeableContract {
+                address: EthAddress([23; 20]),
+                version: ContractVersion::default(),
+            },
+        };
+        let config = EthereumBridgeParams {
+            erc20_whitelist: vec![],
+            eth_start_height: Default::default(),
+            min_confirmations: MinimumConfirmations::default(),
+            contracts: old_contracts,
+        };
+        config.init_storage(&mut wl_storage);
+
+        // a transfer was signed over under the old nonce, but not relayed
+        let nonce_key = bridge_storage::bridge_pool::get_nonce_key();
+        let signed_root_key =
+            bridge_storage::bridge_pool::get_signed_root_key();
+        let pending_key = bridge_storage::bridge_pool::get_key_from_hash(
+            &KeccakHash([1; 32]),
+        );
+        wl_storage.write(&nonce_key, Uint::from(7)).unwrap();
+        wl_storage.write_bytes(&signed_root_key, [1, 2, 3]).unwrap();
+        wl_storage.write_bytes(&pending_key, [4, 5, 6]).unwrap();
+
+        let activation_epoch = Epoch(5);
+        schedule_contracts_update(
+            &mut wl_storage,
+            ScheduledContractsUpdate {
+                activation_epoch,
+                contracts: Contracts {
+                    native_erc20: old_contracts.native_erc20,
+                    bridge: UpgradeableContract {
+                        address: EthAddress([24; 20]),
+                        version: ContractVersion::default().next(),
+                    },
+                },
+                bridge_pool_nonce: Some(Uint::zero()),
+            },
+        )
+        .unwrap();
+
+        // before the activation epoch, the old nonce is in effect
+        apply_scheduled_contracts_update(&mut wl_storage, Epoch(4)).unwrap();
+        assert_eq!(
+            wl_storage.read::<Uint>(&nonce_key).unwrap(),
+            Some(Uint::from(7))
+        );
+        assert!(wl_storage.has_key(&signed_root_key).unwrap());
+        assert_eq!(read_nonce_realignment_height(&wl_storage).unwrap(), None);
+
+        // at the activation epoch, the nonce is realigned
+        wl_storage.storage.block.height = BlockHeight(10);
+        apply_scheduled_contracts_update(&mut wl_storage, activation_epoch)
+            .unwrap();
+        assert_eq!(
+            wl_storage.read::<Uint>(&nonce_key).unwrap(),
+            Some(Uint::zero())
+        );
+        assert!(!wl_storage.has_key(&signed_root_key).unwrap());
+        assert!(wl_storage.has_key(&pending_key).unwrap());
+        assert_eq!(
+            read_nonce_realignment_height(&wl_storage).unwrap(),
+            Some(BlockHeight(10))
+        );
+    }
 }
@@ -10,6 +10,7 @@ use namada_core::types::keccak::KeccakHash;
 use namada_core::types::key::{common, secp256k1};
 use namada_core::types::storage::Epoch;
 use namada_core::types::{eth_abi, ethereum_structs};
+use namada_vote_ext::contract_addresses::ContractAddresses;
 use namada_vote_ext::validator_set_update::{
     valset_upd_toks_to_hashes, EthAddrBook, VotingPowersMap, VotingPowersMapExt,
 };
@@ -30,6 +31,44 @@ pub struct EthereumProof<T> {
 
 pub type BridgePoolRootProof = EthereumProof<(KeccakHash, Uint)>;
 
+/// The proof of an update of the Ethereum bridge contracts, signed over
+/// the signing epoch and the addresses of the new contracts.
+///
+/// Besides the signatures made with the Ethereum hot keys of validators,
+/// the proof holds the signatures of the same data made with their
+/// Ethereum cold keys.
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct ContractAddressesProof {
+    /// The proof signed with the Ethereum hot keys of validators.
+    pub hot: EthereumProof<(Epoch, ContractAddresses)>,
+    /// The signatures made with the Ethereum cold keys of validators.
+    pub cold_signatures: HashMap<EthAddrBook, secp256k1::Signature>,
+}
+
+impl ContractAddressesProof {
+    /// Return an incomplete [`ContractAddressesProof`].
+    pub fn new(data: (Epoch, ContractAddresses)) -> Self {
+        Self {
+            hot: EthereumProof::new(data),
+            cold_signatures: HashMap::new(),
+        }
+    }
+
+    /// Add the hot and cold key signatures of some validator to this
+    /// [`ContractAddressesProof`].
+    pub fn attach_signatures(
+        &mut self,
+        addr_book: EthAddrBook,
+        hot_sig: common::Signature,
+        cold_sig: Option<common::Signature>,
+    ) {
+        if let Some(common::Signature::Secp256k1(sig)) = cold_sig {
+            self.cold_signatures.insert(addr_book.clone(), sig);
+        }
+        self.hot.attach_signature(addr_book, hot_sig);
+    }
+}
+
 impl<T> EthereumProof<T> {
     /// Return an incomplete [`EthereumProof`].
     pub fn new(data: T) -> Self {
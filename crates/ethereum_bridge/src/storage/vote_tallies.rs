@@ -13,7 +13,9 @@ use namada_core::types::storage::{BlockHeight, DbKeySeg, Epoch, Key};
 use namada_macros::StorageKeys;
 use namada_vote_ext::validator_set_update::VotingPowersMap;
 
-use crate::storage::proof::{BridgePoolRootProof, EthereumProof};
+use crate::storage::proof::{
+    BridgePoolRootProof, ContractAddressesProof, EthereumProof,
+};
 
 /// Storage sub-key space reserved to keeping track of the
 /// voting power assigned to Ethereum events.
@@ -28,6 +30,11 @@ pub const BRIDGE_POOL_ROOT_PREFIX_KEY_SEGMENT: &str = "bp_root_and_nonce";
 /// voting power assigned to validator set updates.
 pub const VALSET_UPDS_PREFIX_KEY_SEGMENT: &str = "validator_set_updates";
 
+/// Storage sub-key space reserved to keeping track of the
+/// voting power assigned to the addresses of new Ethereum bridge
+/// contracts.
+pub const CONTRACT_ADDRESSES_PREFIX_KEY_SEGMENT: &str = "contract_addresses";
+
 /// Storage segments of [`Keys`].
 #[derive(StorageKeys)]
 pub struct KeysSegments {
@@ -251,6 +258,27 @@ impl From<&Epoch> for Keys<EthereumProof<VotingPowersMap>> {
     }
 }
 
+/// Get the key prefix corresponding to the storage location of the
+/// addresses of new Ethereum bridge contracts whose "seen" state is being
+/// tracked.
+pub fn contract_addresses_prefix() -> Key {
+    super::prefix()
+        .push(&CONTRACT_ADDRESSES_PREFIX_KEY_SEGMENT.to_owned())
+        .expect("should always be able to construct this key")
+}
+
+/// Get the [`Keys`] of the proof of the addresses of new Ethereum bridge
+/// contracts, signed at the given epoch.
+pub fn contract_addresses_keys(epoch: &Epoch) -> Keys<ContractAddressesProof> {
+    let prefix = contract_addresses_prefix()
+        .push(epoch)
+        .expect("should always be able to construct this key");
+    Keys {
+        prefix,
+        _phantom: std::marker::PhantomData,
+    }
+}
+
 #[cfg(test)]
 mod test {
     use assert_matches::assert_matches;
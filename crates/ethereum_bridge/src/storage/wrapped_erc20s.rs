@@ -1,15 +1,24 @@
 //! Functionality for accessing the multitoken subspace
 
+use std::collections::{BTreeMap, BTreeSet};
+
 use eyre::eyre;
+use namada_core::borsh::BorshDeserialize;
 use namada_core::types::address::{Address, InternalAddress};
 pub use namada_core::types::eth_bridge_pool::{
     erc20_nut_address as nut, erc20_token_address as token,
 };
 use namada_core::types::ethereum_events::EthAddress;
-use namada_core::types::storage::{self, DbKeySeg};
+use namada_core::types::storage::{self, DbKeySeg, KeySeg};
+use namada_core::types::token::Amount;
+use namada_storage::{ResultExt, StorageRead, StorageWrite};
 use namada_trans_token::storage_key::{
-    balance_key, minted_balance_key, MINTED_STORAGE_KEY,
+    balance_key, is_any_minted_balance_key, is_any_token_balance_key,
+    minted_balance_key, MINTED_STORAGE_KEY,
 };
+use namada_trans_token::{read_balances_sum, read_total_supply};
+
+use super::native_erc20_key;
 
 /// Represents the type of a key relating to a wrapped ERC20
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
@@ -104,15 +113,102 @@ impl TryFrom<(&Address, &storage::Key)> for Key {
     }
 }
 
+/// Check that the minted supply of some wrapped Ethereum asset, i.e. a
+/// wrapped ERC20 or a NUT, equals the sum of its balances on Namada.
+pub fn check_supply_invariant<S>(
+    storage: &S,
+    token: &Address,
+) -> eyre::Result<()>
+where
+    S: StorageRead,
+{
+    let supply = read_total_supply(storage, token)?;
+    let balances = read_balances_sum(storage, token)?;
+    if supply != balances {
+        return Err(eyre!(
+            "The minted supply of {token} ({}) differs from the sum of its \
+             balances ({})",
+            supply.to_string_native(),
+            balances.to_string_native(),
+        ));
+    }
+    Ok(())
+}
+
+/// Backfill the minted supply of the wrapped Ethereum assets from the sum
+/// of their balances on Namada. Wrapped NAM is left out, as its supply
+/// tracks the NAM transferred to Ethereum rather than any balances. Return
+/// the written supply keys.
+pub fn backfill_supply<S>(
+    storage: &mut S,
+) -> namada_storage::Result<BTreeSet<storage::Key>>
+where
+    S: StorageRead + StorageWrite,
+{
+    let native_erc20: Option<EthAddress> = storage.read(&native_erc20_key())?;
+    let is_wrapped_asset = |token: &Address| match token {
+        Address::Internal(InternalAddress::Erc20(asset)) => {
+            Some(asset) != native_erc20.as_ref()
+        }
+        Address::Internal(InternalAddress::Nut(_)) => true,
+        _ => false,
+    };
+
+    let prefix = storage::Key::from(
+        Address::Internal(InternalAddress::Multitoken).to_db_key(),
+    );
+    let mut supplies: BTreeMap<Address, Amount> = BTreeMap::new();
+    for item in namada_storage::iter_prefix_bytes(storage, &prefix)? {
+        let (key, value) = item?;
+        // reset the supply of assets which are no longer held by anyone
+        if let Some(token) = is_any_minted_balance_key(&key) {
+            if is_wrapped_asset(token) {
+                supplies.entry(token.clone()).or_default();
+            }
+            continue;
+        }
+        let Some([token, _]) = is_any_token_balance_key(&key) else {
+            continue;
+        };
+        if !is_wrapped_asset(token) {
+            continue;
+        }
+        let balance = Amount::try_from_slice(&value).into_storage_result()?;
+        let supply = supplies.entry(token.clone()).or_default();
+        *supply = supply.checked_add(balance).ok_or_else(|| {
+            namada_storage::Error::new_const("Wrapped asset supply overflow")
+        })?;
+    }
+
+    let mut changed_keys = BTreeSet::new();
+    for (token, supply) in supplies {
+        let supply_key = minted_balance_key(&token);
+        tracing::info!(
+            %token,
+            supply = %supply.to_string_native(),
+            "Backfilled the minted supply of a wrapped Ethereum asset"
+        );
+        storage.write(&supply_key, supply)?;
+        changed_keys.insert(supply_key);
+    }
+    Ok(changed_keys)
+}
+
 #[cfg(test)]
 mod test {
     use std::result::Result;
     use std::str::FromStr;
 
     use assert_matches::assert_matches;
-    use namada_core::types::address::{nam, Address};
-    use namada_core::types::ethereum_events::testing::DAI_ERC20_ETH_ADDRESS;
+    use namada_core::types::address::testing::{
+        established_address_1, established_address_2,
+    };
+    use namada_core::types::address::{nam, wnam, Address};
+    use namada_core::types::ethereum_events::testing::{
+        DAI_ERC20_ETH_ADDRESS, USDC_ERC20_ETH_ADDRESS,
+    };
     use namada_core::types::storage::DbKeySeg;
+    use namada_state::testing::TestWlStorage;
 
     use super::*;
     use crate::token::storage_key::BALANCE_STORAGE_KEY;
@@ -325,4 +421,58 @@ mod test {
 
         assert!(has_erc20_segment(&key));
     }
+    /// Test that the minted supply of the wrapped Ethereum assets is
+    /// backfilled from their balances, leaving wrapped NAM out.
+    #[test]
+    fn test_backfill_supply() {
+        let mut wl_storage = TestWlStorage::default();
+        wl_storage
+            .write(&native_erc20_key(), wnam())
+            .expect("Test failed");
+        let dai = dai_erc20_token();
+        let dai_nut = nut(&DAI_ERC20_ETH_ADDRESS);
+        let usdc = token(&USDC_ERC20_ETH_ADDRESS);
+        let wnam = token(&wnam());
+
+        // balances written without tracking the supply
+        for (token, owner, amount) in [
+            (&dai, established_address_1(), 10),
+            (&dai, established_address_2(), 5),
+            (&dai_nut, established_address_1(), 3),
+        ] {
+            wl_storage
+                .write(&balance_key(token, &owner), Amount::from(amount))
+                .expect("Test failed");
+        }
+        // a stale supply of an asset no longer held by anyone
+        wl_storage
+            .write(&minted_balance_key(&usdc), Amount::from(7))
+            .expect("Test failed");
+        wl_storage
+            .write(&minted_balance_key(&wnam), Amount::from(100))
+            .expect("Test failed");
+        assert!(check_supply_invariant(&wl_storage, &dai).is_err());
+
+        let changed_keys =
+            backfill_supply(&mut wl_storage).expect("Test failed");
+        assert_eq!(
+            changed_keys,
+            BTreeSet::from([
+                minted_balance_key(&dai),
+                minted_balance_key(&dai_nut),
+                minted_balance_key(&usdc),
+            ])
+        );
+        for token in [&dai, &dai_nut, &usdc] {
+            check_supply_invariant(&wl_storage, token).expect("Test failed");
+        }
+        assert_eq!(
+            read_total_supply(&wl_storage, &dai).expect("Test failed"),
+            Amount::from(15)
+        );
+        assert_eq!(
+            read_total_supply(&wl_storage, &wnam).expect("Test failed"),
+            Amount::from(100)
+        );
+    }
 }
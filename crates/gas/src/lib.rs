@@ -1,6 +1,7 @@
 //! Gas accounting module to track the gas usage in a block for transactions and
 //! validity predicates triggered by transactions.
 
+use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::ops::Div;
 
@@ -179,6 +180,29 @@ pub trait GasMetering {
     fn get_gas_limit(&self) -> Gas;
 }
 
+/// Gas accounting of a whole block. It records the gas consumed by every tx
+/// and by every governance proposal executed in the block, next to their sum.
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct BlockGasMeter {
+    /// The gas consumed by each tx, indexed by its position in the block
+    txs: BTreeMap<u32, Gas>,
+    /// The gas consumed by the code of each executed governance proposal,
+    /// indexed by the proposal id
+    proposals: BTreeMap<u64, Gas>,
+    /// The gas consumed by the whole block
+    block_gas: Gas,
+}
+
 /// Gas metering in a transaction
 #[derive(Debug)]
 pub struct TxGasMeter {
@@ -382,6 +406,64 @@ impl VpsGas {
     }
 }
 
+impl BlockGasMeter {
+    /// Record the gas consumed by the tx at the given position in the block
+    pub fn add_tx_gas(&mut self, tx_index: u32, gas: Gas) -> Result<()> {
+        self.add_block_gas(gas)?;
+        let tx_gas = self.txs.entry(tx_index).or_default();
+        *tx_gas = tx_gas.checked_add(gas).ok_or(Error::GasOverflow)?;
+        Ok(())
+    }
+
+    /// Record the gas consumed by the execution of the governance proposal
+    /// with the given id
+    pub fn add_proposal_gas(
+        &mut self,
+        proposal_id: u64,
+        gas: Gas,
+    ) -> Result<()> {
+        self.add_block_gas(gas)?;
+        let proposal_gas = self.proposals.entry(proposal_id).or_default();
+        *proposal_gas =
+            proposal_gas.checked_add(gas).ok_or(Error::GasOverflow)?;
+        Ok(())
+    }
+
+    fn add_block_gas(&mut self, gas: Gas) -> Result<()> {
+        self.block_gas =
+            self.block_gas.checked_add(gas).ok_or(Error::GasOverflow)?;
+        Ok(())
+    }
+
+    /// Get the gas consumed by the tx at the given position in the block, if
+    /// any was recorded
+    pub fn get_tx_gas(&self, tx_index: u32) -> Option<Gas> {
+        self.txs.get(&tx_index).copied()
+    }
+
+    /// Get the gas consumed by the execution of the governance proposal with
+    /// the given id, if any was recorded
+    pub fn get_proposal_gas(&self, proposal_id: u64) -> Option<Gas> {
+        self.proposals.get(&proposal_id).copied()
+    }
+
+    /// Iterate over the gas consumed by the txs of the block, in order
+    pub fn iter_txs_gas(&self) -> impl Iterator<Item = (u32, Gas)> + '_ {
+        self.txs.iter().map(|(index, gas)| (*index, *gas))
+    }
+
+    /// Iterate over the gas consumed by the governance proposals executed in
+    /// the block
+    pub fn iter_proposals_gas(&self) -> impl Iterator<Item = (u64, Gas)> + '_ {
+        self.proposals.iter().map(|(id, gas)| (*id, *gas))
+    }
+
+    /// Get the gas consumed by the whole block
+    pub fn get_block_consumed_gas(&self) -> Gas {
+        self.block_gas
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use assert_matches::assert_matches;
@@ -452,4 +534,37 @@ mod tests {
             Error::TransactionGasExceededError
         );
     }
+
+    #[test]
+    fn test_block_gas_meter_sum() {
+        let mut meter = BlockGasMeter::default();
+        meter.add_proposal_gas(0, 7.into()).unwrap();
+        meter.add_tx_gas(0, 10.into()).unwrap();
+        meter.add_tx_gas(2, 25.into()).unwrap();
+        meter.add_tx_gas(2, 5.into()).unwrap();
+
+        assert_eq!(meter.get_tx_gas(0), Some(10.into()));
+        assert_eq!(meter.get_tx_gas(1), None);
+        assert_eq!(meter.get_tx_gas(2), Some(30.into()));
+        assert_eq!(meter.get_proposal_gas(0), Some(7.into()));
+
+        let sum = meter
+            .iter_txs_gas()
+            .map(|(_, gas)| gas)
+            .chain(meter.iter_proposals_gas().map(|(_, gas)| gas))
+            .fold(Gas::default(), |acc, gas| acc.checked_add(gas).unwrap());
+        assert_eq!(sum, meter.get_block_consumed_gas());
+    }
+
+    #[test]
+    fn test_block_gas_meter_overflow() {
+        let mut meter = BlockGasMeter::default();
+        meter.add_tx_gas(0, u64::MAX.into()).unwrap();
+        assert_matches!(
+            meter
+                .add_tx_gas(1, 1.into())
+                .expect_err("unexpectedly succeeded"),
+            Error::GasOverflow
+        );
+    }
 }
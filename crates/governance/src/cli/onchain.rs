@@ -2,19 +2,21 @@ use std::collections::BTreeMap;
 use std::fmt::Display;
 
 use borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::borsh::BorshSerializeExt;
 use namada_core::types::address::Address;
 use namada_core::types::storage::Epoch;
 use namada_core::types::token;
 use serde::{Deserialize, Serialize};
 
 use super::validation::{
-    is_valid_author_balance, is_valid_content, is_valid_default_proposal_data,
+    is_valid_author_balance, is_valid_content,
+    is_valid_default_proposal_code_chunks, is_valid_default_proposal_data,
     is_valid_end_epoch, is_valid_grace_epoch, is_valid_pgf_funding_data,
     is_valid_pgf_stewards_data, is_valid_proposal_period, is_valid_start_epoch,
-    ProposalValidation,
+    is_valid_voting_heights, ProposalValidation,
 };
 use crate::parameters::GovernanceParameters;
-use crate::storage::proposal::PGFTarget;
+use crate::storage::proposal::{PGFTarget, VotingHeights};
 
 #[derive(
     Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
@@ -33,6 +35,70 @@ pub struct OnChainProposal {
     pub voting_end_epoch: Epoch,
     /// The epoch from which this changes are executed
     pub grace_epoch: Epoch,
+    /// The block heights bounding the voting period, to be used instead of
+    /// the epochs above for proposals that must be decided within less than
+    /// an epoch
+    #[serde(default)]
+    pub voting_heights: Option<VotingHeights>,
+    /// The execution priority of the proposal, the proposals concluded in
+    /// the same block being executed by decreasing priority
+    #[serde(default)]
+    pub priority: Option<u8>,
+}
+
+impl OnChainProposal {
+    /// Compute the deposit to be locked by this proposal, given the size of
+    /// its code, if any. The deposit saturates to the maximum amount on
+    /// overflow, such that no balance can cover it.
+    pub fn required_deposit(
+        &self,
+        governance_parameters: &GovernanceParameters,
+        code_len: usize,
+    ) -> token::Amount {
+        let content_len = self.content.serialize_to_vec().len();
+        governance_parameters
+            .required_deposit((content_len + code_len) as u64)
+            .unwrap_or_else(token::Amount::max)
+    }
+
+    /// Validate the voting period of the proposal, which is either expressed
+    /// in block heights or in epochs
+    pub fn validate_voting_period(
+        &self,
+        governance_parameters: &GovernanceParameters,
+        current_epoch: Epoch,
+    ) -> Result<(), ProposalValidation> {
+        if let Some(voting_heights) = self.voting_heights {
+            return is_valid_voting_heights(
+                voting_heights,
+                governance_parameters.min_proposal_voting_period_blocks,
+                governance_parameters.max_proposal_voting_period_blocks,
+            );
+        }
+        is_valid_start_epoch(
+            self.voting_start_epoch,
+            current_epoch,
+            governance_parameters.min_proposal_voting_period,
+        )?;
+        is_valid_end_epoch(
+            self.voting_start_epoch,
+            self.voting_end_epoch,
+            current_epoch,
+            governance_parameters.min_proposal_voting_period,
+            governance_parameters.min_proposal_voting_period,
+            governance_parameters.max_proposal_period,
+        )?;
+        is_valid_grace_epoch(
+            self.grace_epoch,
+            self.voting_end_epoch,
+            governance_parameters.min_proposal_grace_epochs,
+        )?;
+        is_valid_proposal_period(
+            self.voting_start_epoch,
+            self.grace_epoch,
+            governance_parameters.max_proposal_period,
+        )
+    }
 }
 
 /// Pgf default proposal
@@ -44,6 +110,13 @@ pub struct DefaultProposal {
     pub proposal: OnChainProposal,
     /// The default proposal extra data
     pub data: Option<Vec<u8>>,
+    /// The code chunks executed after the extra data, in order and
+    /// atomically with it
+    #[serde(default)]
+    pub code_chunks: Vec<Vec<u8>>,
+    /// Whether the code and its chunks are stored compressed on chain
+    #[serde(default)]
+    pub compress_code: bool,
 }
 
 impl DefaultProposal {
@@ -58,32 +131,11 @@ impl DefaultProposal {
         if force {
             return Ok(self);
         }
-        is_valid_start_epoch(
-            self.proposal.voting_start_epoch,
-            current_epoch,
-            governance_parameters.min_proposal_voting_period,
-        )?;
-        is_valid_end_epoch(
-            self.proposal.voting_start_epoch,
-            self.proposal.voting_end_epoch,
-            current_epoch,
-            governance_parameters.min_proposal_voting_period,
-            governance_parameters.min_proposal_voting_period,
-            governance_parameters.max_proposal_period,
-        )?;
-        is_valid_grace_epoch(
-            self.proposal.grace_epoch,
-            self.proposal.voting_end_epoch,
-            governance_parameters.min_proposal_grace_epochs,
-        )?;
-        is_valid_proposal_period(
-            self.proposal.voting_start_epoch,
-            self.proposal.grace_epoch,
-            governance_parameters.max_proposal_period,
-        )?;
+        self.proposal
+            .validate_voting_period(governance_parameters, current_epoch)?;
         is_valid_author_balance(
             balance,
-            governance_parameters.min_proposal_fund,
+            self.required_deposit(governance_parameters),
         )?;
         is_valid_content(
             &self.proposal.content,
@@ -93,11 +145,29 @@ impl DefaultProposal {
             &self.data,
             governance_parameters.max_proposal_code_size,
         )?;
+        is_valid_default_proposal_code_chunks(
+            &self.data,
+            &self.code_chunks,
+            governance_parameters.max_proposal_code_size,
+        )?;
 
         Ok(self)
     }
 }
 
+impl DefaultProposal {
+    /// Compute the deposit to be locked by this proposal
+    pub fn required_deposit(
+        &self,
+        governance_parameters: &GovernanceParameters,
+    ) -> token::Amount {
+        let code_len = self.data.as_ref().map(Vec::len).unwrap_or_default()
+            + self.code_chunks.iter().map(Vec::len).sum::<usize>();
+        self.proposal
+            .required_deposit(governance_parameters, code_len)
+    }
+}
+
 impl TryFrom<&[u8]> for DefaultProposal {
     type Error = serde_json::Error;
 
@@ -136,32 +206,11 @@ impl PgfStewardProposal {
         if force {
             return Ok(self);
         }
-        is_valid_start_epoch(
-            self.proposal.voting_start_epoch,
-            current_epoch,
-            governance_parameters.min_proposal_voting_period,
-        )?;
-        is_valid_end_epoch(
-            self.proposal.voting_start_epoch,
-            self.proposal.voting_end_epoch,
-            current_epoch,
-            governance_parameters.min_proposal_voting_period,
-            governance_parameters.min_proposal_voting_period,
-            governance_parameters.max_proposal_period,
-        )?;
-        is_valid_grace_epoch(
-            self.proposal.grace_epoch,
-            self.proposal.voting_end_epoch,
-            governance_parameters.min_proposal_grace_epochs,
-        )?;
-        is_valid_proposal_period(
-            self.proposal.voting_start_epoch,
-            self.proposal.grace_epoch,
-            governance_parameters.max_proposal_period,
-        )?;
+        self.proposal
+            .validate_voting_period(governance_parameters, current_epoch)?;
         is_valid_author_balance(
             balance,
-            governance_parameters.min_proposal_fund,
+            self.proposal.required_deposit(governance_parameters, 0),
         )?;
         is_valid_content(
             &self.proposal.content,
@@ -203,29 +252,8 @@ impl PgfFundingProposal {
         if force {
             return Ok(self);
         }
-        is_valid_start_epoch(
-            self.proposal.voting_start_epoch,
-            current_epoch,
-            governance_parameters.min_proposal_voting_period,
-        )?;
-        is_valid_end_epoch(
-            self.proposal.voting_start_epoch,
-            self.proposal.voting_end_epoch,
-            current_epoch,
-            governance_parameters.min_proposal_voting_period,
-            governance_parameters.min_proposal_voting_period,
-            governance_parameters.max_proposal_period,
-        )?;
-        is_valid_grace_epoch(
-            self.proposal.grace_epoch,
-            self.proposal.voting_end_epoch,
-            governance_parameters.min_proposal_grace_epochs,
-        )?;
-        is_valid_proposal_period(
-            self.proposal.voting_start_epoch,
-            self.proposal.grace_epoch,
-            governance_parameters.max_proposal_period,
-        )?;
+        self.proposal
+            .validate_voting_period(governance_parameters, current_epoch)?;
         is_valid_content(
             &self.proposal.content,
             governance_parameters.max_proposal_content_size,
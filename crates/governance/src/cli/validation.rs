@@ -6,6 +6,7 @@ use namada_core::types::token;
 use thiserror::Error;
 
 use super::onchain::{PgfFunding, StewardsUpdate};
+use crate::storage::proposal::VotingHeights;
 
 /// This enum raprresent a proposal data
 #[derive(Clone, Debug, PartialEq, Error)]
@@ -38,6 +39,12 @@ pub enum ProposalValidation {
          epoch must be at most {1}, but found {0}"
     )]
     InvalidProposalPeriod(u64, u64),
+    /// The proposal voting period in blocks is invalid
+    #[error(
+        "Invalid proposal voting heights: the voting period must be at least \
+         {0} blocks and at most {1} blocks, but found {2}"
+    )]
+    InvalidVotingHeights(u64, u64, u64),
     /// The proposal author does not have enough balance to pay for proposal
     /// fees
     #[error(
@@ -63,6 +70,12 @@ pub enum ProposalValidation {
          ({0}) is to big (max {1})"
     )]
     InvalidDefaultProposalExtraData(u64, u64),
+    /// The proposal has code chunks without any code
+    #[error(
+        "Invalid proposal extra data: code chunks can only follow the code of \
+         the proposal"
+    )]
+    InvalidDefaultProposalCodeChunks,
     /// The pgf stewards data is not valid
     #[error("Invalid proposal extra data: cannot be empty.")]
     InvalidPgfStewardsExtraData,
@@ -164,6 +177,27 @@ pub fn is_valid_proposal_period(
     }
 }
 
+pub fn is_valid_voting_heights(
+    voting_heights: VotingHeights,
+    min_proposal_voting_period_blocks: u64,
+    max_proposal_voting_period_blocks: u64,
+) -> Result<(), ProposalValidation> {
+    let voting_period = voting_heights.duration();
+
+    if voting_heights.start < voting_heights.end
+        && voting_period >= min_proposal_voting_period_blocks
+        && voting_period <= max_proposal_voting_period_blocks
+    {
+        Ok(())
+    } else {
+        Err(ProposalValidation::InvalidVotingHeights(
+            min_proposal_voting_period_blocks,
+            max_proposal_voting_period_blocks,
+            voting_period,
+        ))
+    }
+}
+
 pub fn is_valid_content(
     proposal_content: &BTreeMap<String, String>,
     max_content_length: u64,
@@ -221,6 +255,22 @@ pub fn is_valid_default_proposal_data(
     }
 }
 
+pub fn is_valid_default_proposal_code_chunks(
+    data: &Option<Vec<u8>>,
+    code_chunks: &[Vec<u8>],
+    max_extra_data_size: u64,
+) -> Result<(), ProposalValidation> {
+    if data.is_none() && !code_chunks.is_empty() {
+        return Err(ProposalValidation::InvalidDefaultProposalCodeChunks);
+    }
+    code_chunks.iter().try_for_each(|chunk| {
+        is_valid_default_proposal_data(
+            &Some(chunk.clone()),
+            max_extra_data_size,
+        )
+    })
+}
+
 pub fn is_valid_pgf_stewards_data(
     data: &StewardsUpdate,
     author: &Address,
@@ -13,8 +13,11 @@ pub mod storage;
 pub mod utils;
 
 pub use storage::proposal::{InitProposalData, ProposalType, VoteProposalData};
-pub use storage::vote::ProposalVote;
-pub use storage::{init_proposal, is_proposal_accepted, vote_proposal};
+pub use storage::vote::{ProposalVote, VoteDelegationData};
+pub use storage::{
+    delegate_votes, init_proposal, is_proposal_accepted, migrate_vote_keys,
+    vote_proposal,
+};
 
 /// The governance internal address
 pub const ADDRESS: Address = address::GOV;
@@ -1,8 +1,223 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
+
 use namada_core::borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::types::address::Address;
+use namada_core::types::dec::Dec;
 use namada_core::types::token;
 use namada_state::{StorageRead, StorageResult, StorageWrite};
+use serde::{Deserialize, Serialize};
 
 use super::storage::keys as goverance_storage;
+use super::storage::proposal::ProposalType;
+use super::utils::{TallyType, TieBreakSeed, TiePolicy, WithTiePolicy};
+
+/// How to handle a passed proposal setting a protocol parameter that was
+/// already set by another proposal concluded in the same epoch
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ParameterConflictPolicy {
+    /// Apply both proposals in execution order, such that the later one
+    /// prevails, emitting a warning event
+    #[default]
+    ApplyInOrder,
+    /// Reject the later proposal, i.e. the one with the lower priority or
+    /// the higher id, emitting a conflict event
+    RejectLater,
+}
+
+impl Display for ParameterConflictPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParameterConflictPolicy::ApplyInOrder => {
+                write!(f, "apply_in_order")
+            }
+            ParameterConflictPolicy::RejectLater => write!(f, "reject_later"),
+        }
+    }
+}
+
+/// How to break a tie of the tally of a proposal, i.e. when yay and nay
+/// votes carry the same voting power, or when a validator quorum is met
+/// exactly
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum TieBreak {
+    /// Pass the proposal
+    Pass,
+    /// Reject the proposal
+    #[default]
+    Reject,
+    /// Pass or reject the proposal at random, as drawn from the hash of the
+    /// block concluding it and its id
+    Random,
+}
+
+impl TieBreak {
+    /// The policy resolving ties with this tie-break, drawing from the given
+    /// seed if ties are broken at random
+    pub fn policy(&self, seed: TieBreakSeed) -> TiePolicy {
+        match self {
+            TieBreak::Pass => TiePolicy::TieBreaksToPass,
+            TieBreak::Reject => TiePolicy::TieBreaksToReject,
+            TieBreak::Random => TiePolicy::TieBreaksAtRandom(seed),
+        }
+    }
+}
+
+impl Display for TieBreak {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TieBreak::Pass => write!(f, "pass"),
+            TieBreak::Reject => write!(f, "reject"),
+            TieBreak::Random => write!(f, "random"),
+        }
+    }
+}
+
+/// The tie-breaks of the tally types whose tallies may land on a tie. The
+/// other tally types never tie.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(default)]
+pub struct TieBreaks {
+    /// The tie-break of the tallies requiring half of the yay and nay votes
+    /// over a third of the voting power to be yay
+    pub one_half_over_one_third: TieBreak,
+    /// The tie-break of the tallies requiring less than half of the yay and
+    /// nay votes over a third of the voting power to be nay
+    pub less_one_half_over_one_third_nay: TieBreak,
+}
+
+impl TieBreaks {
+    /// The tie-break of the given tally type
+    pub fn get(&self, tally_type: &TallyType) -> TieBreak {
+        match tally_type {
+            TallyType::OneHalfOverOneThird => self.one_half_over_one_third,
+            TallyType::LessOneHalfOverOneThirdNay => {
+                self.less_one_half_over_one_third_nay
+            }
+            TallyType::TwoThirds
+            | TallyType::ValidatorCount(_)
+            | TallyType::Council(_) => TieBreak::Reject,
+        }
+    }
+
+    /// The strategy tallying a proposal with the given tally type, whose
+    /// ties are broken with the tie-break of the type, drawing from the
+    /// given seed if they're broken at random
+    pub fn strategy(
+        &self,
+        tally_type: TallyType,
+        seed: TieBreakSeed,
+    ) -> WithTiePolicy<TallyType> {
+        WithTiePolicy {
+            strategy: tally_type,
+            tie_policy: self.get(&tally_type).policy(seed),
+        }
+    }
+}
+
+/// The council whose approval is required by council proposals, regardless
+/// of the stake of its members
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct Council {
+    /// The addresses of the members of the council
+    pub members: BTreeSet<Address>,
+    /// The total weight of the members that must vote yay for a council
+    /// proposal to pass
+    pub threshold: u64,
+    /// The weights of the members of the council that don't weigh 1, e.g.
+    /// founder seats counting double
+    #[serde(default)]
+    pub weights: BTreeMap<Address, u64>,
+}
+
+impl Council {
+    /// The weight of the given member of the council, which is 1 unless
+    /// set otherwise. Non-members weigh nothing.
+    pub fn weight(&self, member: &Address) -> u64 {
+        if !self.members.contains(member) {
+            return 0;
+        }
+        self.weights.get(member).copied().unwrap_or(1)
+    }
+
+    /// The total weight of the members of the council, or `None` if it
+    /// overflows
+    pub fn total_weight(&self) -> Option<u64> {
+        self.members.iter().try_fold(0u64, |total, member| {
+            total.checked_add(self.weight(member))
+        })
+    }
+
+    /// Check that the threshold of the council can be met by the total
+    /// weight of its members, and requires at least one of them. The
+    /// weights must belong to members and be positive.
+    pub fn is_valid(&self) -> bool {
+        let weights_are_valid = self.weights.iter().all(|(member, weight)| {
+            *weight > 0 && self.members.contains(member)
+        });
+        weights_are_valid
+            && self.threshold > 0
+            && self
+                .total_weight()
+                .map_or(false, |total_weight| self.threshold <= total_weight)
+    }
+}
 
 #[derive(
     Clone,
@@ -29,6 +244,62 @@ pub struct GovernanceParameters {
     pub max_proposal_content_size: u64,
     /// Minimum epochs between end and grace epochs
     pub min_proposal_grace_epochs: u64,
+    /// Maximum number of epochs a proposal can stay undecided, counted from
+    /// its voting start epoch, before it gets expired
+    pub max_proposal_lifetime: u64,
+    /// Deposit locked by every proposal, regardless of its size
+    pub base_deposit: token::Amount,
+    /// Deposit locked for each byte of the content and code of a proposal
+    pub deposit_per_byte: token::Amount,
+    /// Maximum fraction of the total voting power that may be counted for
+    /// any single voter when tallying a proposal, if any
+    pub max_voting_power_fraction: Option<Dec>,
+    /// Treasury balance above which a warning event is emitted when the
+    /// funds of proposals are moved to the treasury, if any
+    pub treasury_high_water_mark: Option<token::Amount>,
+    /// Minimum amount of tokens that the author of a proposal must have
+    /// bonded, to any validator, to be allowed to submit it
+    pub min_proposal_author_stake: token::Amount,
+    /// Number of epochs after which the snapshots of the treasury balance
+    /// taken at each epoch are pruned from storage
+    pub treasury_history_retention: u64,
+    /// Fraction of the total native token supply minted to the treasury at
+    /// each epoch
+    pub treasury_inflation_rate: Dec,
+    /// Minimum proposal voting period in blocks, for proposals whose voting
+    /// period is expressed in block heights
+    pub min_proposal_voting_period_blocks: u64,
+    /// Maximum proposal voting period in blocks, for proposals whose voting
+    /// period is expressed in block heights
+    pub max_proposal_voting_period_blocks: u64,
+    /// Minimum number of distinct validators that must vote yay for an
+    /// emergency proposal to pass, instead of a fraction of the voting
+    /// power, if any
+    pub emergency_validator_quorum: Option<u64>,
+    /// Maximum number of governance proposals concluded in a single block,
+    /// the proposals due in excess being deferred to the following blocks,
+    /// if any
+    pub max_proposals_per_block: Option<u64>,
+    /// How to handle proposals setting a protocol parameter already set by
+    /// another proposal concluded in the same epoch
+    pub parameter_conflict_policy: ParameterConflictPolicy,
+    /// Whether the code of a proposal must be a well-formed wasm module at
+    /// submission, rather than failing once executed
+    pub strict_proposal_code_validation: bool,
+    /// Fraction of the funds of a rejected proposal returned to its author,
+    /// the rest being burned, unless set for the type of the proposal
+    pub rejected_refund_ratio: Dec,
+    /// Fractions of the funds of rejected proposals returned to their
+    /// authors, by proposal type, overriding `rejected_refund_ratio`
+    pub rejected_refund_ratios: BTreeMap<String, Dec>,
+    /// The council approving council proposals, if any. Council proposals
+    /// can't be submitted without it.
+    pub council: Option<Council>,
+    /// Treasury balance above which the funds of proposals moved to the
+    /// treasury are burned instead, if any
+    pub max_treasury_balance: Option<token::Amount>,
+    /// How to break the ties of the tallies of proposals, by tally type
+    pub tie_breaks: TieBreaks,
 }
 
 impl Default for GovernanceParameters {
@@ -40,6 +311,25 @@ impl Default for GovernanceParameters {
             max_proposal_period: 27,
             max_proposal_content_size: 10_000,
             min_proposal_grace_epochs: 6,
+            max_proposal_lifetime: 100,
+            base_deposit: token::Amount::zero(),
+            deposit_per_byte: token::Amount::zero(),
+            max_voting_power_fraction: None,
+            treasury_high_water_mark: None,
+            min_proposal_author_stake: token::Amount::zero(),
+            treasury_history_retention: 100,
+            treasury_inflation_rate: Dec::zero(),
+            min_proposal_voting_period_blocks: 600,
+            max_proposal_voting_period_blocks: 14_400,
+            emergency_validator_quorum: None,
+            max_proposals_per_block: None,
+            parameter_conflict_policy: ParameterConflictPolicy::default(),
+            strict_proposal_code_validation: false,
+            rejected_refund_ratio: Dec::zero(),
+            rejected_refund_ratios: BTreeMap::new(),
+            council: None,
+            max_treasury_balance: None,
+            tie_breaks: TieBreaks::default(),
         }
     }
 }
@@ -57,6 +347,25 @@ impl GovernanceParameters {
             max_proposal_period,
             max_proposal_content_size,
             min_proposal_grace_epochs,
+            max_proposal_lifetime,
+            base_deposit,
+            deposit_per_byte,
+            max_voting_power_fraction,
+            treasury_high_water_mark,
+            min_proposal_author_stake,
+            treasury_history_retention,
+            treasury_inflation_rate,
+            min_proposal_voting_period_blocks,
+            max_proposal_voting_period_blocks,
+            emergency_validator_quorum,
+            max_proposals_per_block,
+            parameter_conflict_policy,
+            strict_proposal_code_validation,
+            rejected_refund_ratio,
+            rejected_refund_ratios,
+            council,
+            max_treasury_balance,
+            tie_breaks,
         } = self;
 
         let min_proposal_fund_key =
@@ -88,7 +397,223 @@ impl GovernanceParameters {
         storage
             .write(&min_proposal_grace_epoch_key, min_proposal_grace_epochs)?;
 
+        let max_proposal_lifetime_key =
+            goverance_storage::get_max_proposal_lifetime_key();
+        storage.write(&max_proposal_lifetime_key, max_proposal_lifetime)?;
+
+        let base_deposit_key = goverance_storage::get_base_deposit_key();
+        storage.write(&base_deposit_key, base_deposit)?;
+
+        let deposit_per_byte_key =
+            goverance_storage::get_deposit_per_byte_key();
+        storage.write(&deposit_per_byte_key, deposit_per_byte)?;
+
+        let max_voting_power_fraction_key =
+            goverance_storage::get_max_voting_power_fraction_key();
+        storage
+            .write(&max_voting_power_fraction_key, max_voting_power_fraction)?;
+
+        let treasury_high_water_mark_key =
+            goverance_storage::get_treasury_high_water_mark_key();
+        storage
+            .write(&treasury_high_water_mark_key, treasury_high_water_mark)?;
+
+        let min_proposal_author_stake_key =
+            goverance_storage::get_min_proposal_author_stake_key();
+        storage
+            .write(&min_proposal_author_stake_key, min_proposal_author_stake)?;
+
+        let treasury_history_retention_key =
+            goverance_storage::get_treasury_history_retention_key();
+        storage.write(
+            &treasury_history_retention_key,
+            treasury_history_retention,
+        )?;
+
+        let treasury_inflation_rate_key =
+            goverance_storage::get_treasury_inflation_rate_key();
+        storage.write(&treasury_inflation_rate_key, treasury_inflation_rate)?;
+
+        let min_proposal_voting_period_blocks_key =
+            goverance_storage::get_min_proposal_voting_period_blocks_key();
+        storage.write(
+            &min_proposal_voting_period_blocks_key,
+            min_proposal_voting_period_blocks,
+        )?;
+
+        let max_proposal_voting_period_blocks_key =
+            goverance_storage::get_max_proposal_voting_period_blocks_key();
+        storage.write(
+            &max_proposal_voting_period_blocks_key,
+            max_proposal_voting_period_blocks,
+        )?;
+
+        let emergency_validator_quorum_key =
+            goverance_storage::get_emergency_validator_quorum_key();
+        storage.write(
+            &emergency_validator_quorum_key,
+            emergency_validator_quorum,
+        )?;
+
+        let max_proposals_per_block_key =
+            goverance_storage::get_max_proposals_per_block_key();
+        storage.write(&max_proposals_per_block_key, max_proposals_per_block)?;
+
+        let parameter_conflict_policy_key =
+            goverance_storage::get_parameter_conflict_policy_key();
+        storage
+            .write(&parameter_conflict_policy_key, parameter_conflict_policy)?;
+
+        let strict_proposal_code_validation_key =
+            goverance_storage::get_strict_proposal_code_validation_key();
+        storage.write(
+            &strict_proposal_code_validation_key,
+            strict_proposal_code_validation,
+        )?;
+
+        let rejected_refund_ratio_key =
+            goverance_storage::get_rejected_refund_ratio_key();
+        storage.write(&rejected_refund_ratio_key, rejected_refund_ratio)?;
+
+        let rejected_refund_ratios_key =
+            goverance_storage::get_rejected_refund_ratios_key();
+        storage.write(&rejected_refund_ratios_key, rejected_refund_ratios)?;
+
+        let council_key = goverance_storage::get_council_key();
+        storage.write(&council_key, council)?;
+
+        let max_treasury_balance_key =
+            goverance_storage::get_max_treasury_balance_key();
+        storage.write(&max_treasury_balance_key, max_treasury_balance)?;
+
+        let tie_breaks_key = goverance_storage::get_tie_breaks_key();
+        storage.write(&tie_breaks_key, tie_breaks)?;
+
         let counter_key = goverance_storage::get_counter_key();
         storage.write(&counter_key, u64::MIN)
     }
+
+    /// Compute the deposit to be locked by a proposal whose content and code
+    /// add up to `proposal_size` bytes. The deposit is never lower than the
+    /// `min_proposal_fund`. Returns `None` on overflow.
+    pub fn required_deposit(
+        &self,
+        proposal_size: u64,
+    ) -> Option<token::Amount> {
+        let deposit = self
+            .deposit_per_byte
+            .checked_mul(token::Amount::from_u64(proposal_size))?
+            .checked_add(self.base_deposit)?;
+        Some(std::cmp::max(deposit, self.min_proposal_fund))
+    }
+
+    /// Get the fraction of the funds of a rejected proposal of the given
+    /// type returned to its author, as [`get_refund_ratio_by_type`] reads it
+    /// from storage.
+    ///
+    /// [`get_refund_ratio_by_type`]: crate::storage::get_refund_ratio_by_type
+    pub fn refund_ratio(&self, proposal_type: &ProposalType) -> Dec {
+        self.rejected_refund_ratios
+            .get(proposal_type.kind())
+            .copied()
+            .unwrap_or(self.rejected_refund_ratio)
+            .max(Dec::zero())
+            .min(Dec::one())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_required_deposit() {
+        let params = GovernanceParameters {
+            min_proposal_fund: token::Amount::native_whole(500),
+            base_deposit: token::Amount::native_whole(100),
+            deposit_per_byte: token::Amount::native_whole(1),
+            ..Default::default()
+        };
+
+        // Below the threshold size, the min proposal fund is required
+        assert_eq!(
+            params.required_deposit(0),
+            Some(token::Amount::native_whole(500))
+        );
+        assert_eq!(
+            params.required_deposit(399),
+            Some(token::Amount::native_whole(500))
+        );
+        // At the threshold size, the scaled deposit meets the min fund
+        assert_eq!(
+            params.required_deposit(400),
+            Some(token::Amount::native_whole(500))
+        );
+        // Above it, every byte adds to the deposit
+        assert_eq!(
+            params.required_deposit(401),
+            Some(token::Amount::native_whole(501))
+        );
+        assert_eq!(
+            params.required_deposit(1_000_000),
+            Some(token::Amount::native_whole(1_000_100))
+        );
+
+        // With the default parameters, only the min proposal fund applies
+        let params = GovernanceParameters::default();
+        assert_eq!(
+            params.required_deposit(u64::MAX),
+            Some(params.min_proposal_fund)
+        );
+
+        // Overflows are reported
+        let params = GovernanceParameters {
+            deposit_per_byte: token::Amount::max(),
+            ..Default::default()
+        };
+        assert_eq!(params.required_deposit(2), None);
+    }
+
+    #[test]
+    fn test_tie_breaks() {
+        // Unset tie-breaks reject ties
+        let tie_breaks: TieBreaks =
+            serde_json::from_str(r#"{"one_half_over_one_third": "random"}"#)
+                .unwrap();
+        assert_eq!(
+            tie_breaks,
+            TieBreaks {
+                one_half_over_one_third: TieBreak::Random,
+                ..Default::default()
+            }
+        );
+
+        let tie_breaks = TieBreaks {
+            one_half_over_one_third: TieBreak::Pass,
+            less_one_half_over_one_third_nay: TieBreak::Random,
+        };
+        let seed = TieBreakSeed {
+            proposal_id: 7,
+            ..Default::default()
+        };
+        for (tally_type, tie_policy) in [
+            (TallyType::OneHalfOverOneThird, TiePolicy::TieBreaksToPass),
+            (
+                TallyType::LessOneHalfOverOneThirdNay,
+                TiePolicy::TieBreaksAtRandom(seed),
+            ),
+            // Tally types that never tie keep rejecting
+            (TallyType::TwoThirds, TiePolicy::TieBreaksToReject),
+            (TallyType::ValidatorCount(3), TiePolicy::TieBreaksToReject),
+            (TallyType::Council(2), TiePolicy::TieBreaksToReject),
+        ] {
+            assert_eq!(
+                tie_breaks.strategy(tally_type, seed),
+                WithTiePolicy {
+                    strategy: tally_type,
+                    tie_policy,
+                }
+            );
+        }
+    }
 }
@@ -0,0 +1,126 @@
+//! Optional compression of the code of proposals in storage.
+//!
+//! The code of a proposal, and each of its chunks, is stored either as is or
+//! compressed with DEFLATE, in which case it's prefixed with
+//! [`COMPRESSED_CODE_MARKER`]. Wasm modules start with the `\0asm` magic
+//! bytes, so code stored uncompressed is never mistaken for compressed code,
+//! and the code of the proposals submitted before compression was supported
+//! is still read as is.
+//!
+//! The size limit of the code is applied to its decompressed size, such that
+//! a small compressed blob can't expand to an arbitrarily large module.
+
+use std::io::{Read, Write};
+
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use thiserror::Error;
+
+/// The byte prefixing the compressed code of a proposal
+pub const COMPRESSED_CODE_MARKER: u8 = 0xc0;
+
+/// Errors decoding the stored code of a proposal
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum CodeDecodingError {
+    /// The code exceeds the maximum size once decompressed
+    #[error("The proposal code exceeds the maximum size of {0} bytes")]
+    TooLarge(u64),
+    /// The compressed code can't be decompressed
+    #[error("Invalid compressed proposal code: {0}")]
+    Invalid(String),
+}
+
+/// Compress the code of a proposal, to be stored in place of the code
+pub fn compress_code(code: &[u8]) -> Vec<u8> {
+    let mut encoder =
+        DeflateEncoder::new(vec![COMPRESSED_CODE_MARKER], Compression::best());
+    encoder
+        .write_all(code)
+        .expect("Writing to a vector shouldn't fail");
+    encoder
+        .finish()
+        .expect("Writing to a vector shouldn't fail")
+}
+
+/// Check if the stored code of a proposal is compressed
+pub fn is_compressed(stored: &[u8]) -> bool {
+    stored.first() == Some(&COMPRESSED_CODE_MARKER)
+}
+
+/// Decode the stored code of a proposal, decompressing it if it's
+/// compressed. Fails if the decoded code exceeds `max_size` bytes, without
+/// decompressing more than `max_size + 1` bytes.
+pub fn decode_code(
+    stored: Vec<u8>,
+    max_size: u64,
+) -> Result<Vec<u8>, CodeDecodingError> {
+    if !is_compressed(&stored) {
+        return if stored.len() as u64 > max_size {
+            Err(CodeDecodingError::TooLarge(max_size))
+        } else {
+            Ok(stored)
+        };
+    }
+    let mut code = vec![];
+    DeflateDecoder::new(&stored[1..])
+        .take(max_size.saturating_add(1))
+        .read_to_end(&mut code)
+        .map_err(|err| CodeDecodingError::Invalid(err.to_string()))?;
+    if code.len() as u64 > max_size {
+        return Err(CodeDecodingError::TooLarge(max_size));
+    }
+    Ok(code)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// The magic bytes and version of a wasm module
+    const WASM_HEADER: [u8; 8] = [0x00, 0x61, 0x73, 0x6d, 0x01, 0, 0, 0];
+
+    #[test]
+    fn test_compressed_code_round_trip() {
+        let code: Vec<u8> = WASM_HEADER
+            .into_iter()
+            .chain(std::iter::repeat(7).take(10_000))
+            .collect();
+        let compressed = compress_code(&code);
+        assert!(is_compressed(&compressed));
+        assert!(compressed.len() < code.len());
+        assert_eq!(
+            decode_code(compressed, code.len() as u64),
+            Ok(code.clone())
+        );
+
+        // uncompressed code is read as is
+        assert!(!is_compressed(&code));
+        assert_eq!(decode_code(code.clone(), code.len() as u64), Ok(code));
+    }
+
+    #[test]
+    fn test_decompression_bomb_rejected() {
+        // a few kilobytes decompressing to 100 MiB
+        let bomb = compress_code(&vec![0; 100 << 20]);
+        assert!(bomb.len() < 1 << 20);
+        let max_size = 1 << 20;
+        assert_eq!(
+            decode_code(bomb, max_size),
+            Err(CodeDecodingError::TooLarge(max_size))
+        );
+
+        // the limit applies to uncompressed code too
+        let code = WASM_HEADER.to_vec();
+        assert_eq!(decode_code(code, 4), Err(CodeDecodingError::TooLarge(4)));
+    }
+
+    #[test]
+    fn test_invalid_compressed_code_rejected() {
+        let stored = vec![COMPRESSED_CODE_MARKER, 0xff, 0xff, 0xff];
+        assert!(matches!(
+            decode_code(stored, 1024),
+            Err(CodeDecodingError::Invalid(_))
+        ));
+    }
+}
@@ -0,0 +1,353 @@
+//! Consistency checks of the governance storage.
+//!
+//! [`verify_governance_storage`] walks the keys of all the proposals and
+//! reports the entries that violate the invariants maintained by the
+//! governance transactions and the protocol. It only reads from storage.
+
+use std::fmt::{self, Display};
+
+use namada_core::types::storage::{BlockHeight, DbKeySeg, Epoch, Key};
+use namada_state::{iter_prefix_bytes, StorageRead, StorageResult};
+
+use crate::storage::facade::ProposalStorageReader;
+use crate::storage::keys as governance_keys;
+use crate::storage::proposal::VotingHeights;
+
+/// A violation of the invariants of the governance storage
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Inconsistency {
+    /// A proposal below the proposal counter is missing a required field
+    MissingField {
+        /// The id of the proposal
+        id: u64,
+        /// The name of the missing field
+        field: &'static str,
+    },
+    /// A proposal key was found for an id that wasn't yet allocated by the
+    /// proposal counter
+    OrphanedKey {
+        /// The id of the proposal
+        id: u64,
+        /// The orphaned key
+        key: Key,
+    },
+    /// An undecided proposal isn't queued for execution at its grace epoch
+    NotQueued {
+        /// The id of the proposal
+        id: u64,
+        /// The grace epoch of the proposal
+        grace_epoch: Epoch,
+    },
+    /// An undecided proposal, voted on over block heights, isn't queued to
+    /// be concluded at its voting end height
+    NotQueuedAtHeight {
+        /// The id of the proposal
+        id: u64,
+        /// The voting end height of the proposal
+        end_height: BlockHeight,
+    },
+    /// A proposal is flagged as being executed, but it wasn't decided
+    UndecidedExecution {
+        /// The id of the proposal
+        id: u64,
+    },
+}
+
+impl Display for Inconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Inconsistency::MissingField { id, field } => {
+                write!(f, "Proposal {id} is missing its {field}")
+            }
+            Inconsistency::OrphanedKey { id, key } => write!(
+                f,
+                "Key {key} belongs to proposal {id}, which is beyond the \
+                 proposal counter"
+            ),
+            Inconsistency::NotQueued { id, grace_epoch } => write!(
+                f,
+                "Undecided proposal {id} isn't queued for execution at its \
+                 grace epoch {grace_epoch}"
+            ),
+            Inconsistency::NotQueuedAtHeight { id, end_height } => write!(
+                f,
+                "Undecided proposal {id} isn't queued to be concluded at its \
+                 voting end height {end_height}"
+            ),
+            Inconsistency::UndecidedExecution { id } => write!(
+                f,
+                "Proposal {id} is flagged as being executed, but it has no \
+                 tally result"
+            ),
+        }
+    }
+}
+
+/// Report of the consistency checks of the governance storage
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConsistencyReport {
+    /// The number of proposals below the proposal counter
+    pub proposals_checked: u64,
+    /// The inconsistencies found
+    pub inconsistencies: Vec<Inconsistency>,
+}
+
+impl ConsistencyReport {
+    /// Check if no inconsistencies were found
+    pub fn is_consistent(&self) -> bool {
+        self.inconsistencies.is_empty()
+    }
+}
+
+/// Check that the governance storage is internally consistent:
+///
+/// - every proposal below the proposal counter has all its required fields,
+/// - no proposal key exists for an id at or above the proposal counter,
+/// - every undecided proposal is queued for execution at its grace epoch, or at
+///   its voting end height if it's voted on over block heights,
+/// - only decided proposals are flagged as being executed.
+pub fn verify_governance_storage<S>(
+    storage: &S,
+) -> StorageResult<ConsistencyReport>
+where
+    S: StorageRead,
+{
+    let counter: u64 = storage
+        .read(&governance_keys::get_counter_key())?
+        .unwrap_or_default();
+    let mut report = ConsistencyReport {
+        proposals_checked: counter,
+        inconsistencies: vec![],
+    };
+
+    let proposal_store = ProposalStorageReader::new(storage);
+    for id in 0..counter {
+        let is_height_based = storage
+            .has_key(&governance_keys::get_voting_start_height_key(id))?
+            || storage
+                .has_key(&governance_keys::get_voting_end_height_key(id))?;
+        let mut required_keys = vec![
+            (governance_keys::get_author_key(id), "author"),
+            (governance_keys::get_content_key(id), "content"),
+            (governance_keys::get_proposal_type_key(id), "type"),
+            (governance_keys::get_funds_key(id), "funds"),
+        ];
+        if is_height_based {
+            required_keys.extend([
+                (
+                    governance_keys::get_voting_start_height_key(id),
+                    "voting start height",
+                ),
+                (
+                    governance_keys::get_voting_end_height_key(id),
+                    "voting end height",
+                ),
+            ]);
+        } else {
+            required_keys.extend([
+                (
+                    governance_keys::get_voting_start_epoch_key(id),
+                    "voting start epoch",
+                ),
+                (
+                    governance_keys::get_voting_end_epoch_key(id),
+                    "voting end epoch",
+                ),
+                (governance_keys::get_grace_epoch_key(id), "grace epoch"),
+            ]);
+        }
+        for (key, field) in required_keys {
+            if !storage.has_key(&key)? {
+                report
+                    .inconsistencies
+                    .push(Inconsistency::MissingField { id, field });
+            }
+        }
+
+        let is_decided =
+            storage.has_key(&governance_keys::get_proposal_result_key(id))?;
+        if let (false, Some(grace_epoch)) =
+            (is_decided, proposal_store.grace_epoch(id)?)
+        {
+            let committing_key = governance_keys::get_committing_proposals_key(
+                id,
+                grace_epoch.0,
+            );
+            if !storage.has_key(&committing_key)? {
+                report
+                    .inconsistencies
+                    .push(Inconsistency::NotQueued { id, grace_epoch });
+            }
+        }
+        if let (false, Some(VotingHeights { end, .. })) =
+            (is_decided, proposal_store.voting_heights(id)?)
+        {
+            let committing_key =
+                governance_keys::get_committing_proposals_at_height_key(
+                    id, end.0,
+                );
+            if !storage.has_key(&committing_key)? {
+                report
+                    .inconsistencies
+                    .push(Inconsistency::NotQueuedAtHeight {
+                        id,
+                        end_height: end,
+                    });
+            }
+        }
+    }
+
+    for entry in
+        iter_prefix_bytes(storage, &governance_keys::proposal_prefix())?
+    {
+        let (key, _) = entry?;
+        let id = if governance_keys::is_commit_proposal_key(&key)
+            || governance_keys::is_commit_proposal_at_height_key(&key)
+        {
+            governance_keys::get_commit_proposal_id(&key)
+        } else {
+            governance_keys::get_proposal_id(&key)
+        };
+        match id {
+            Some(id) if id >= counter => report
+                .inconsistencies
+                .push(Inconsistency::OrphanedKey { id, key }),
+            _ => {}
+        }
+    }
+
+    for entry in iter_prefix_bytes(
+        storage,
+        &governance_keys::get_proposal_execution_prefix(),
+    )? {
+        let (key, _) = entry?;
+        let id = match key.last() {
+            Some(DbKeySeg::StringSeg(id)) => id.parse::<u64>().ok(),
+            _ => None,
+        };
+        if let Some(id) = id {
+            if !storage
+                .has_key(&governance_keys::get_proposal_result_key(id))?
+            {
+                report
+                    .inconsistencies
+                    .push(Inconsistency::UndecidedExecution { id });
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod test {
+    use namada_core::types::address::testing::established_address_1;
+    use namada_state::testing::TestWlStorage;
+    use namada_state::StorageWrite;
+    use namada_trans_token::Amount;
+
+    use super::*;
+    use crate::storage::facade::ProposalStorageWriter;
+    use crate::storage::proposal::ProposalType;
+    use crate::utils::{ProposalResult, TallyResult, TallyType};
+
+    /// Write all the keys of a proposal, queued for execution at its grace
+    /// epoch
+    fn write_proposal(storage: &mut TestWlStorage, id: u64) {
+        let mut writer = ProposalStorageWriter::new(storage);
+        writer.set_author(id, &established_address_1()).unwrap();
+        writer.set_content_bytes(id, vec![]).unwrap();
+        writer
+            .set_proposal_type(id, &ProposalType::Default(None))
+            .unwrap();
+        writer.set_voting_start(id, Epoch(1)).unwrap();
+        writer.set_voting_end(id, Epoch(4)).unwrap();
+        writer.set_grace_epoch(id, Epoch(6)).unwrap();
+        writer.set_funds(id, Amount::native_whole(500)).unwrap();
+        storage
+            .write(&governance_keys::get_committing_proposals_key(id, 6), ())
+            .unwrap();
+    }
+
+    fn rejected() -> ProposalResult {
+        ProposalResult {
+            result: TallyResult::Rejected,
+            tally_type: TallyType::TwoThirds,
+            total_voting_power: Amount::native_whole(10),
+            total_yay_power: Amount::zero(),
+            total_nay_power: Amount::native_whole(10),
+            total_abstain_power: Amount::zero(),
+        }
+    }
+
+    /// Seed storage with two proposals, the first of which was decided
+    fn consistent_storage() -> TestWlStorage {
+        let mut storage = TestWlStorage::default();
+        write_proposal(&mut storage, 0);
+        write_proposal(&mut storage, 1);
+        ProposalStorageWriter::new(&mut storage)
+            .set_result(0, rejected())
+            .unwrap();
+        storage
+            .write(&governance_keys::get_counter_key(), 2_u64)
+            .unwrap();
+        storage
+    }
+
+    #[test]
+    fn test_consistent_governance_storage() {
+        let storage = consistent_storage();
+        let report = verify_governance_storage(&storage).unwrap();
+        assert_eq!(report.proposals_checked, 2);
+        assert!(report.is_consistent(), "{:?}", report.inconsistencies);
+
+        // an empty governance storage is trivially consistent
+        let report =
+            verify_governance_storage(&TestWlStorage::default()).unwrap();
+        assert_eq!(report, ConsistencyReport::default());
+    }
+
+    #[test]
+    fn test_corrupted_governance_storage() {
+        let mut storage = consistent_storage();
+
+        // proposal 1 lost its funds and its queue entry
+        storage.delete(&governance_keys::get_funds_key(1)).unwrap();
+        storage
+            .delete(&governance_keys::get_committing_proposals_key(1, 6))
+            .unwrap();
+        // funds of a proposal that was never allocated
+        ProposalStorageWriter::new(&mut storage)
+            .set_funds(2, Amount::native_whole(500))
+            .unwrap();
+        // execution flag of the undecided proposal 1
+        storage
+            .write(&governance_keys::get_proposal_execution_key(1), ())
+            .unwrap();
+        // the execution flag of the decided proposal 0 is fine
+        storage
+            .write(&governance_keys::get_proposal_execution_key(0), ())
+            .unwrap();
+
+        let report = verify_governance_storage(&storage).unwrap();
+        assert!(!report.is_consistent());
+        assert_eq!(
+            report.inconsistencies,
+            vec![
+                Inconsistency::MissingField {
+                    id: 1,
+                    field: "funds",
+                },
+                Inconsistency::NotQueued {
+                    id: 1,
+                    grace_epoch: Epoch(6),
+                },
+                Inconsistency::OrphanedKey {
+                    id: 2,
+                    key: governance_keys::get_funds_key(2),
+                },
+                Inconsistency::UndecidedExecution { id: 1 },
+            ]
+        );
+    }
+}
@@ -0,0 +1,604 @@
+//! Typed access to the storage of governance proposals.
+//!
+//! Each method pins the type of the value stored under the corresponding
+//! proposal key, such that callers don't have to pair raw keys with the
+//! right type by hand.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use namada_core::borsh::BorshDeserialize;
+use namada_core::types::address::Address;
+use namada_core::types::dec::Dec;
+use namada_core::types::hash::Hash;
+use namada_core::types::storage::{BlockHeight, Epoch, Key};
+use namada_state::{StorageError, StorageRead, StorageResult, StorageWrite};
+use namada_trans_token::Amount;
+use serde::Serialize;
+
+use crate::parameters::{Council, ParameterConflictPolicy, TieBreaks};
+use crate::storage::keys as governance_keys;
+use crate::storage::proposal::{ProposalType, VotingHeights};
+use crate::storage::vote::ProposalVote;
+use crate::utils::{
+    ProposalExecutionResult, ProposalResult, ReleasedFunds, TieBreakSeed,
+};
+
+/// Typed reader of the fields of governance proposals
+#[derive(Debug)]
+pub struct ProposalStorageReader<'a, S> {
+    storage: &'a S,
+}
+
+impl<'a, S> ProposalStorageReader<'a, S>
+where
+    S: StorageRead,
+{
+    /// Create a new reader over the given storage
+    pub fn new(storage: &'a S) -> Self {
+        Self { storage }
+    }
+
+    /// Read the author of a proposal
+    pub fn author(&self, id: u64) -> StorageResult<Option<Address>> {
+        self.storage.read(&governance_keys::get_author_key(id))
+    }
+
+    /// Read the content of a proposal
+    pub fn content(
+        &self,
+        id: u64,
+    ) -> StorageResult<Option<BTreeMap<String, String>>> {
+        self.storage.read(&governance_keys::get_content_key(id))
+    }
+
+    /// Read the type of a proposal
+    pub fn proposal_type(
+        &self,
+        id: u64,
+    ) -> StorageResult<Option<ProposalType>> {
+        self.storage
+            .read(&governance_keys::get_proposal_type_key(id))
+    }
+
+    /// Read the epoch from which voting on a proposal is allowed
+    pub fn voting_start(&self, id: u64) -> StorageResult<Option<Epoch>> {
+        self.storage
+            .read(&governance_keys::get_voting_start_epoch_key(id))
+    }
+
+    /// Read the epoch from which voting on a proposal is stopped
+    pub fn voting_end(&self, id: u64) -> StorageResult<Option<Epoch>> {
+        self.storage
+            .read(&governance_keys::get_voting_end_epoch_key(id))
+    }
+
+    /// Read the block heights bounding the voting period of a proposal, if
+    /// it's expressed in blocks
+    pub fn voting_heights(
+        &self,
+        id: u64,
+    ) -> StorageResult<Option<VotingHeights>> {
+        let start = self
+            .storage
+            .read(&governance_keys::get_voting_start_height_key(id))?;
+        let end = self
+            .storage
+            .read(&governance_keys::get_voting_end_height_key(id))?;
+        Ok(start
+            .zip(end)
+            .map(|(start, end)| VotingHeights { start, end }))
+    }
+
+    /// Read the epoch at which a proposal is executed
+    pub fn grace_epoch(&self, id: u64) -> StorageResult<Option<Epoch>> {
+        self.storage.read(&governance_keys::get_grace_epoch_key(id))
+    }
+
+    /// Read the execution priority of a proposal, if any
+    pub fn priority(&self, id: u64) -> StorageResult<Option<u8>> {
+        self.storage.read(&governance_keys::get_priority_key(id))
+    }
+
+    /// Read the funds locked by a proposal
+    pub fn funds(&self, id: u64) -> StorageResult<Option<Amount>> {
+        self.storage.read(&governance_keys::get_funds_key(id))
+    }
+
+    /// Read where the funds of a proposal went, if they were already
+    /// released
+    pub fn funds_released(
+        &self,
+        id: u64,
+    ) -> StorageResult<Option<ReleasedFunds>> {
+        self.storage
+            .read(&governance_keys::get_funds_released_key(id))
+    }
+
+    /// Read the wasm code of a proposal, as stored, i.e. possibly
+    /// compressed, see [`crate::storage::code`]
+    pub fn code(&self, id: u64) -> StorageResult<Option<Vec<u8>>> {
+        self.storage
+            .read_bytes(&governance_keys::get_proposal_code_key(id))
+    }
+
+    /// Read the hash of the wasm code of a proposal
+    pub fn code_hash(&self, id: u64) -> StorageResult<Option<Hash>> {
+        self.storage
+            .read(&governance_keys::get_proposal_code_hash_key(id))
+    }
+
+    /// Read the number of chunks of the code of a proposal, executed after
+    /// its code
+    pub fn code_chunk_count(&self, id: u64) -> StorageResult<u64> {
+        self.storage
+            .read(&governance_keys::get_proposal_code_chunk_count_key(id))
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Read the hash of a chunk of the code of a proposal
+    pub fn code_chunk_hash(
+        &self,
+        id: u64,
+        idx: u64,
+    ) -> StorageResult<Option<Hash>> {
+        self.storage
+            .read(&governance_keys::get_proposal_code_chunk_hash_key(id, idx))
+    }
+
+    /// Read the chunks of the code of a proposal, in order of execution, as
+    /// stored
+    pub fn code_chunks(&self, id: u64) -> StorageResult<Vec<Vec<u8>>> {
+        (0..self.code_chunk_count(id)?)
+            .map(|idx| {
+                self.storage
+                    .read_bytes(&governance_keys::get_proposal_code_chunk_key(
+                        id, idx,
+                    ))?
+                    .ok_or(StorageError::new_const(
+                        "Missing proposal code chunk",
+                    ))
+            })
+            .collect()
+    }
+
+    /// Read the tally result of a proposal
+    pub fn result(&self, id: u64) -> StorageResult<Option<ProposalResult>> {
+        self.storage
+            .read(&governance_keys::get_proposal_result_key(id))
+    }
+
+    /// Read the id of the proposal that a proposal was rejected for
+    /// conflicting with, if any
+    pub fn conflict(&self, id: u64) -> StorageResult<Option<u64>> {
+        self.storage
+            .read(&governance_keys::get_proposal_conflict_key(id))
+    }
+
+    /// Read the input of the random draw which broke a tie of the tally of
+    /// a proposal, if its tally landed on a tie broken at random
+    pub fn tie_break(&self, id: u64) -> StorageResult<Option<TieBreakSeed>> {
+        self.storage
+            .read(&governance_keys::get_proposal_tie_break_key(id))
+    }
+}
+
+/// Typed writer of the fields of governance proposals
+#[derive(Debug)]
+pub struct ProposalStorageWriter<'a, S> {
+    storage: &'a mut S,
+}
+
+impl<'a, S> ProposalStorageWriter<'a, S>
+where
+    S: StorageRead + StorageWrite,
+{
+    /// Create a new writer over the given storage
+    pub fn new(storage: &'a mut S) -> Self {
+        Self { storage }
+    }
+
+    /// Get a reader over the same storage
+    pub fn reader(&self) -> ProposalStorageReader<'_, S> {
+        ProposalStorageReader::new(self.storage)
+    }
+
+    /// Write the author of a proposal
+    pub fn set_author(
+        &mut self,
+        id: u64,
+        author: &Address,
+    ) -> StorageResult<()> {
+        self.storage
+            .write(&governance_keys::get_author_key(id), author)
+    }
+
+    /// Write the already encoded content of a proposal
+    pub fn set_content_bytes(
+        &mut self,
+        id: u64,
+        content: Vec<u8>,
+    ) -> StorageResult<()> {
+        self.storage
+            .write_bytes(&governance_keys::get_content_key(id), content)
+    }
+
+    /// Write the type of a proposal
+    pub fn set_proposal_type(
+        &mut self,
+        id: u64,
+        proposal_type: &ProposalType,
+    ) -> StorageResult<()> {
+        self.storage
+            .write(&governance_keys::get_proposal_type_key(id), proposal_type)
+    }
+
+    /// Write the epoch from which voting on a proposal is allowed
+    pub fn set_voting_start(
+        &mut self,
+        id: u64,
+        epoch: Epoch,
+    ) -> StorageResult<()> {
+        self.storage
+            .write(&governance_keys::get_voting_start_epoch_key(id), epoch)
+    }
+
+    /// Write the epoch from which voting on a proposal is stopped
+    pub fn set_voting_end(
+        &mut self,
+        id: u64,
+        epoch: Epoch,
+    ) -> StorageResult<()> {
+        self.storage
+            .write(&governance_keys::get_voting_end_epoch_key(id), epoch)
+    }
+
+    /// Write the block heights bounding the voting period of a proposal
+    pub fn set_voting_heights(
+        &mut self,
+        id: u64,
+        VotingHeights { start, end }: VotingHeights,
+    ) -> StorageResult<()> {
+        self.storage
+            .write(&governance_keys::get_voting_start_height_key(id), start)?;
+        self.storage
+            .write(&governance_keys::get_voting_end_height_key(id), end)
+    }
+
+    /// Write the epoch at which a proposal is executed
+    pub fn set_grace_epoch(
+        &mut self,
+        id: u64,
+        epoch: Epoch,
+    ) -> StorageResult<()> {
+        self.storage
+            .write(&governance_keys::get_grace_epoch_key(id), epoch)
+    }
+
+    /// Write the execution priority of a proposal
+    pub fn set_priority(&mut self, id: u64, priority: u8) -> StorageResult<()> {
+        self.storage
+            .write(&governance_keys::get_priority_key(id), priority)
+    }
+
+    /// Write the funds locked by a proposal
+    pub fn set_funds(&mut self, id: u64, funds: Amount) -> StorageResult<()> {
+        self.storage
+            .write(&governance_keys::get_funds_key(id), funds)
+    }
+
+    /// Write where the funds of a proposal went once released
+    pub fn set_funds_released(
+        &mut self,
+        id: u64,
+        released: ReleasedFunds,
+    ) -> StorageResult<()> {
+        self.storage
+            .write(&governance_keys::get_funds_released_key(id), released)
+    }
+
+    /// Write the wasm code of a proposal
+    pub fn set_code(&mut self, id: u64, code: Vec<u8>) -> StorageResult<()> {
+        self.storage
+            .write_bytes(&governance_keys::get_proposal_code_key(id), code)
+    }
+
+    /// Write the hash of the wasm code of a proposal
+    pub fn set_code_hash(&mut self, id: u64, hash: Hash) -> StorageResult<()> {
+        self.storage
+            .write(&governance_keys::get_proposal_code_hash_key(id), hash)
+    }
+
+    /// Write the chunks of the code of a proposal, in order of execution,
+    /// along with their hashes and their number
+    pub fn set_code_chunks(
+        &mut self,
+        id: u64,
+        chunks: Vec<Vec<u8>>,
+    ) -> StorageResult<()> {
+        self.storage.write(
+            &governance_keys::get_proposal_code_chunk_count_key(id),
+            chunks.len() as u64,
+        )?;
+        for (idx, chunk) in chunks.into_iter().enumerate() {
+            // Allow clients and the ledger to verify each chunk on its own
+            self.storage.write(
+                &governance_keys::get_proposal_code_chunk_hash_key(
+                    id, idx as u64,
+                ),
+                Hash::sha256(&chunk),
+            )?;
+            self.storage.write_bytes(
+                &governance_keys::get_proposal_code_chunk_key(id, idx as u64),
+                chunk,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Write the tally result of a proposal
+    pub fn set_result(
+        &mut self,
+        id: u64,
+        result: ProposalResult,
+    ) -> StorageResult<()> {
+        self.storage
+            .write(&governance_keys::get_proposal_result_key(id), result)
+    }
+
+    /// Write the id of the proposal that a proposal was rejected for
+    /// conflicting with
+    pub fn set_conflict(
+        &mut self,
+        id: u64,
+        conflicting_id: u64,
+    ) -> StorageResult<()> {
+        self.storage.write(
+            &governance_keys::get_proposal_conflict_key(id),
+            conflicting_id,
+        )
+    }
+
+    /// Write the input of the random draw which broke a tie of the tally of
+    /// a proposal
+    pub fn set_tie_break(
+        &mut self,
+        id: u64,
+        seed: TieBreakSeed,
+    ) -> StorageResult<()> {
+        self.storage
+            .write(&governance_keys::get_proposal_tie_break_key(id), seed)
+    }
+}
+
+/// Decode the value stored under a governance key into JSON, using the type
+/// pinned for the key by the typed reader, or by the governance parameters.
+/// Returns `None` for keys of unknown type, such as the proposal code, and
+/// for values that can't be decoded as their expected type.
+pub fn decode_value_as_json(
+    key: &Key,
+    bytes: &[u8],
+) -> Option<serde_json::Value> {
+    fn to_json<T>(bytes: &[u8]) -> Option<serde_json::Value>
+    where
+        T: BorshDeserialize + Serialize,
+    {
+        let value = T::try_from_slice(bytes).ok()?;
+        serde_json::to_value(value).ok()
+    }
+
+    use governance_keys as keys;
+    let is_result_key = || {
+        keys::get_proposal_id(key)
+            .map(|id| &keys::get_proposal_result_key(id) == key)
+            .unwrap_or_default()
+    };
+    let is_conflict_key = || {
+        keys::get_proposal_id(key)
+            .map(|id| &keys::get_proposal_conflict_key(id) == key)
+            .unwrap_or_default()
+    };
+    let is_funds_released_key = || {
+        keys::get_proposal_id(key)
+            .map(|id| &keys::get_funds_released_key(id) == key)
+            .unwrap_or_default()
+    };
+    let is_tie_break_key = || {
+        keys::get_proposal_id(key)
+            .map(|id| &keys::get_proposal_tie_break_key(id) == key)
+            .unwrap_or_default()
+    };
+    let is_execution_result_key = || {
+        keys::get_proposal_id(key)
+            .map(|id| &keys::get_proposal_execution_result_key(id) == key)
+            .unwrap_or_default()
+    };
+    if keys::is_author_key(key) {
+        to_json::<Address>(bytes)
+    } else if keys::is_content_key(key) {
+        to_json::<BTreeMap<String, String>>(bytes)
+    } else if keys::is_proposal_type_key(key) {
+        to_json::<ProposalType>(bytes)
+    } else if keys::is_start_epoch_key(key)
+        || keys::is_end_epoch_key(key)
+        || keys::is_grace_epoch_key(key)
+    {
+        to_json::<Epoch>(bytes)
+    } else if keys::is_start_height_key(key) || keys::is_end_height_key(key) {
+        to_json::<BlockHeight>(bytes)
+    } else if keys::is_balance_key(key)
+        || keys::is_min_proposal_fund_key(key)
+        || keys::is_base_deposit_key(key)
+        || keys::is_deposit_per_byte_key(key)
+        || keys::is_min_proposal_author_stake_key(key)
+    {
+        to_json::<Amount>(bytes)
+    } else if keys::is_proposal_code_hash_key(key)
+        || keys::is_proposal_code_chunk_hash_key(key).is_some()
+    {
+        to_json::<Hash>(bytes)
+    } else if keys::is_priority_key(key) {
+        to_json::<u8>(bytes)
+    } else if keys::is_vote_key(key) {
+        to_json::<ProposalVote>(bytes)
+    } else if is_result_key() {
+        to_json::<ProposalResult>(bytes)
+    } else if is_execution_result_key() {
+        to_json::<ProposalExecutionResult>(bytes)
+    } else if is_funds_released_key() {
+        to_json::<ReleasedFunds>(bytes)
+    } else if is_tie_break_key() {
+        to_json::<TieBreakSeed>(bytes)
+    } else if keys::is_counter_key(key)
+        || is_conflict_key()
+        || keys::is_max_content_size_key(key)
+        || keys::is_max_proposal_code_size_key(key)
+        || keys::is_min_proposal_voting_period_key(key)
+        || keys::is_max_proposal_period_key(key)
+        || keys::is_min_proposal_voting_period_blocks_key(key)
+        || keys::is_max_proposal_voting_period_blocks_key(key)
+        || keys::is_min_grace_epoch_key(key)
+        || keys::is_max_proposal_lifetime_key(key)
+        || keys::is_treasury_history_retention_key(key)
+        || keys::is_proposal_code_chunk_count_key(key)
+        || keys::is_vote_key_migration_cursor_key(key)
+    {
+        to_json::<u64>(bytes)
+    } else if keys::is_emergency_validator_quorum_key(key)
+        || keys::is_max_proposals_per_block_key(key)
+    {
+        to_json::<Option<u64>>(bytes)
+    } else if keys::is_deferred_proposals_key(key) {
+        to_json::<BTreeSet<u64>>(bytes)
+    } else if keys::is_concluded_epoch_key(key) {
+        to_json::<Epoch>(bytes)
+    } else if keys::is_parameter_conflict_policy_key(key) {
+        to_json::<ParameterConflictPolicy>(bytes)
+    } else if keys::is_strict_proposal_code_validation_key(key) {
+        to_json::<bool>(bytes)
+    } else if keys::is_parameter_changes_key(key) {
+        to_json::<(Epoch, BTreeMap<String, u64>)>(bytes)
+    } else if keys::is_treasury_high_water_mark_key(key)
+        || keys::is_max_treasury_balance_key(key)
+    {
+        to_json::<Option<Amount>>(bytes)
+    } else if keys::is_max_voting_power_fraction_key(key) {
+        to_json::<Option<Dec>>(bytes)
+    } else if keys::is_treasury_inflation_rate_key(key)
+        || keys::is_rejected_refund_ratio_key(key)
+    {
+        to_json::<Dec>(bytes)
+    } else if keys::is_rejected_refund_ratios_key(key) {
+        to_json::<BTreeMap<String, Dec>>(bytes)
+    } else if keys::is_council_key(key) {
+        to_json::<Option<Council>>(bytes)
+    } else if keys::is_tie_breaks_key(key) {
+        to_json::<TieBreaks>(bytes)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use namada_core::borsh::BorshSerializeExt;
+    use namada_core::types::address::testing::established_address_1;
+    use namada_core::types::storage::BlockHeight;
+    use namada_state::testing::TestWlStorage;
+
+    use super::*;
+    use crate::utils::{TallyResult, TallyType};
+
+    #[test]
+    fn test_proposal_storage_facade_round_trip() {
+        let mut storage = TestWlStorage::default();
+        let id = 7;
+        let author = established_address_1();
+        let content =
+            BTreeMap::from([("title".to_string(), "A proposal".to_string())]);
+        let proposal_type = ProposalType::Default(Some(Hash::zero()));
+        let result = ProposalResult {
+            result: TallyResult::Rejected,
+            tally_type: TallyType::TwoThirds,
+            total_voting_power: Amount::native_whole(10),
+            total_yay_power: Amount::native_whole(1),
+            total_nay_power: Amount::native_whole(5),
+            total_abstain_power: Amount::zero(),
+        };
+
+        {
+            let reader = ProposalStorageReader::new(&storage);
+            assert!(reader.author(id).unwrap().is_none());
+            assert!(reader.funds(id).unwrap().is_none());
+            assert!(reader.code(id).unwrap().is_none());
+        }
+
+        let mut writer = ProposalStorageWriter::new(&mut storage);
+        writer.set_author(id, &author).unwrap();
+        writer
+            .set_content_bytes(id, content.serialize_to_vec())
+            .unwrap();
+        writer.set_proposal_type(id, &proposal_type).unwrap();
+        writer.set_voting_start(id, Epoch(1)).unwrap();
+        writer.set_voting_end(id, Epoch(4)).unwrap();
+        writer.set_grace_epoch(id, Epoch(6)).unwrap();
+        writer.set_funds(id, Amount::native_whole(500)).unwrap();
+        writer.set_code(id, vec![1, 2, 3]).unwrap();
+        writer.set_code_hash(id, Hash::sha256([1, 2, 3])).unwrap();
+        writer.set_result(id, result).unwrap();
+
+        let reader = ProposalStorageReader::new(&storage);
+        assert_eq!(reader.author(id).unwrap(), Some(author));
+        assert_eq!(reader.content(id).unwrap(), Some(content));
+        assert_eq!(reader.proposal_type(id).unwrap(), Some(proposal_type));
+        assert_eq!(reader.voting_start(id).unwrap(), Some(Epoch(1)));
+        assert_eq!(reader.voting_end(id).unwrap(), Some(Epoch(4)));
+        assert_eq!(reader.grace_epoch(id).unwrap(), Some(Epoch(6)));
+        assert_eq!(reader.funds(id).unwrap(), Some(Amount::native_whole(500)));
+        assert_eq!(reader.code(id).unwrap(), Some(vec![1, 2, 3]));
+        assert_eq!(
+            reader.code_hash(id).unwrap(),
+            Some(Hash::sha256([1, 2, 3]))
+        );
+        let read_result = reader.result(id).unwrap().unwrap();
+        assert!(matches!(read_result.result, TallyResult::Rejected));
+        assert_eq!(read_result.total_nay_power, Amount::native_whole(5));
+        assert!(reader.voting_heights(id).unwrap().is_none());
+        assert!(reader.priority(id).unwrap().is_none());
+        assert!(reader.funds_released(id).unwrap().is_none());
+        assert!(reader.conflict(id).unwrap().is_none());
+        assert!(reader.code_chunks(id).unwrap().is_empty());
+
+        let released = ReleasedFunds {
+            returned: Amount::native_whole(400),
+            burned: Amount::native_whole(100),
+            to_treasury: Amount::zero(),
+        };
+        let mut writer = ProposalStorageWriter::new(&mut storage);
+        writer.set_priority(id, 2).unwrap();
+        writer.set_funds_released(id, released).unwrap();
+        writer.set_conflict(id, 3).unwrap();
+        writer
+            .set_code_chunks(id, vec![vec![4, 5], vec![6]])
+            .unwrap();
+        let reader = ProposalStorageReader::new(&storage);
+        assert_eq!(reader.priority(id).unwrap(), Some(2));
+        assert_eq!(reader.funds_released(id).unwrap(), Some(released));
+        assert_eq!(reader.conflict(id).unwrap(), Some(3));
+        assert_eq!(reader.code_chunk_count(id).unwrap(), 2);
+        assert_eq!(
+            reader.code_chunk_hash(id, 1).unwrap(),
+            Some(Hash::sha256([6]))
+        );
+        assert_eq!(reader.code_chunks(id).unwrap(), vec![vec![4, 5], vec![6]]);
+
+        let heights = VotingHeights {
+            start: BlockHeight(10),
+            end: BlockHeight(700),
+        };
+        ProposalStorageWriter::new(&mut storage)
+            .set_voting_heights(id, heights)
+            .unwrap();
+        let reader = ProposalStorageReader::new(&storage);
+        assert_eq!(reader.voting_heights(id).unwrap(), Some(heights));
+    }
+}
@@ -1,6 +1,8 @@
+use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use namada_core::types::address::Address;
-use namada_core::types::storage::{DbKeySeg, Key, KeySeg};
+use namada_core::types::storage::{DbKeySeg, Epoch, Key, KeySeg};
 use namada_macros::StorageKeys;
+use serde::{Deserialize, Serialize};
 
 use crate::ADDRESS;
 
@@ -9,24 +11,70 @@ use crate::ADDRESS;
 struct Keys {
     proposal: &'static str,
     vote: &'static str,
+    validator: &'static str,
+    delegator: &'static str,
     author: &'static str,
     proposal_type: &'static str,
     content: &'static str,
     start_epoch: &'static str,
     end_epoch: &'static str,
+    start_height: &'static str,
+    end_height: &'static str,
+    priority: &'static str,
     grace_epoch: &'static str,
     funds: &'static str,
     proposal_code: &'static str,
+    proposal_code_hash: &'static str,
+    proposal_code_chunk: &'static str,
+    proposal_code_chunk_hash: &'static str,
+    proposal_code_chunk_count: &'static str,
     committing_epoch: &'static str,
+    committing_height: &'static str,
     min_fund: &'static str,
     max_code_size: &'static str,
     min_period: &'static str,
     max_period: &'static str,
+    min_period_blocks: &'static str,
+    max_period_blocks: &'static str,
     max_content: &'static str,
     min_grace_epoch: &'static str,
+    max_lifetime: &'static str,
+    base_deposit: &'static str,
+    deposit_per_byte: &'static str,
+    max_voting_power_fraction: &'static str,
+    treasury_high_water_mark: &'static str,
+    min_author_stake: &'static str,
+    treasury_history_retention: &'static str,
+    treasury_balance: &'static str,
+    treasury_inflation_rate: &'static str,
+    treasury_inflation: &'static str,
+    emergency_validator_quorum: &'static str,
+    max_proposals_per_block: &'static str,
+    deferred_proposals: &'static str,
+    concluded_epoch: &'static str,
+    parameter_conflict_policy: &'static str,
+    parameter_changes: &'static str,
+    strict_proposal_code_validation: &'static str,
+    rejected_refund_ratio: &'static str,
+    rejected_refund_ratios: &'static str,
+    council: &'static str,
+    max_treasury_balance: &'static str,
+    tie_breaks: &'static str,
     counter: &'static str,
     pending: &'static str,
     result: &'static str,
+    conflicts_with: &'static str,
+    output: &'static str,
+    tie_break: &'static str,
+    execution_result: &'static str,
+    funds_released: &'static str,
+    archive: &'static str,
+    participation: &'static str,
+    valset_sig_participation: &'static str,
+    voting_key: &'static str,
+    voter: &'static str,
+    delegation: &'static str,
+    vote_key_migration_cursor: &'static str,
 }
 
 /// Check if key is inside governance address space
@@ -34,26 +82,128 @@ pub fn is_governance_key(key: &Key) -> bool {
     matches!(&key.segments[0], DbKeySeg::AddressSeg(addr) if addr == &ADDRESS)
 }
 
-/// Check if a key is a vote key
-pub fn is_vote_key(key: &Key) -> bool {
+/// The role of a voter, recorded in the keys of its votes
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub enum VoterType {
+    /// A validator, voting with its own stake
+    Validator,
+    /// A delegator, voting with its bonds to a validator
+    Delegator,
+}
+
+/// A vote key parsed into its components
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VoteKey<'a> {
+    /// The id of the proposal voted on
+    pub proposal_id: u64,
+    /// The role of the voter
+    pub voter_type: VoterType,
+    /// The address of the voter
+    pub voter: &'a Address,
+    /// The validator whose stake the vote is cast with, which is the voter
+    /// itself for a validator
+    pub validator: &'a Address,
+}
+
+/// Parse a vote key, laid out as
+/// `proposal/{id}/vote/validator/{validator}` for the vote of a validator and
+/// `proposal/{id}/vote/delegator/{delegator}/{validator}` for the vote of a
+/// delegator
+pub fn parse_vote_key(key: &Key) -> Option<VoteKey<'_>> {
+    let (voter_type, voter, validator) = match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(_id),
+            DbKeySeg::StringSeg(vote),
+            DbKeySeg::StringSeg(role),
+            DbKeySeg::AddressSeg(validator),
+        ] if addr == &ADDRESS
+            && prefix == Keys::VALUES.proposal
+            && vote == Keys::VALUES.vote
+            && role == Keys::VALUES.validator =>
+        {
+            (VoterType::Validator, validator, validator)
+        }
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(_id),
+            DbKeySeg::StringSeg(vote),
+            DbKeySeg::StringSeg(role),
+            DbKeySeg::AddressSeg(delegator),
+            DbKeySeg::AddressSeg(validator),
+        ] if addr == &ADDRESS
+            && prefix == Keys::VALUES.proposal
+            && vote == Keys::VALUES.vote
+            && role == Keys::VALUES.delegator
+            && delegator != validator =>
+        {
+            (VoterType::Delegator, delegator, validator)
+        }
+        _ => return None,
+    };
+    Some(VoteKey {
+        proposal_id: get_proposal_id(key)?,
+        voter_type,
+        voter,
+        validator,
+    })
+}
+
+/// Parse a vote key of the legacy layout
+/// `proposal/{id}/vote/{validator}/{voter}`, which doesn't record the role
+/// of the voter. These keys are rewritten to the current layout by
+/// [`super::migrate_vote_keys`].
+pub fn parse_legacy_vote_key(key: &Key) -> Option<VoteKey<'_>> {
     match &key.segments[..] {
         [
             DbKeySeg::AddressSeg(addr),
             DbKeySeg::StringSeg(prefix),
-            DbKeySeg::StringSeg(id),
+            DbKeySeg::StringSeg(_id),
             DbKeySeg::StringSeg(vote),
-            DbKeySeg::AddressSeg(_validator_address),
-            DbKeySeg::AddressSeg(_address),
+            DbKeySeg::AddressSeg(validator),
+            DbKeySeg::AddressSeg(voter),
         ] if addr == &ADDRESS
             && prefix == Keys::VALUES.proposal
             && vote == Keys::VALUES.vote =>
         {
-            id.parse::<u64>().is_ok()
+            Some(VoteKey {
+                proposal_id: get_proposal_id(key)?,
+                voter_type: if voter == validator {
+                    VoterType::Validator
+                } else {
+                    VoterType::Delegator
+                },
+                voter,
+                validator,
+            })
         }
-        _ => false,
+        _ => None,
     }
 }
 
+/// Check if a key is a vote key
+pub fn is_vote_key(key: &Key) -> bool {
+    parse_vote_key(key).is_some()
+}
+
+/// Check if a key is a vote key of the legacy layout
+pub fn is_legacy_vote_key(key: &Key) -> bool {
+    parse_legacy_vote_key(key).is_some()
+}
+
 /// Check if key is author key
 pub fn is_author_key(key: &Key) -> bool {
     match &key.segments[..] {
@@ -90,6 +240,86 @@ pub fn is_proposal_code_key(key: &Key) -> bool {
     }
 }
 
+/// Check if key is a proposal code hash key
+pub fn is_proposal_code_hash_key(key: &Key) -> bool {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(id),
+            DbKeySeg::StringSeg(proposal_code_hash),
+        ] if addr == &ADDRESS
+            && prefix == Keys::VALUES.proposal
+            && proposal_code_hash == Keys::VALUES.proposal_code_hash =>
+        {
+            id.parse::<u64>().is_ok()
+        }
+        _ => false,
+    }
+}
+
+/// Check if key is a proposal code chunk key, returning the index of the
+/// chunk
+pub fn is_proposal_code_chunk_key(key: &Key) -> Option<u64> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(id),
+            DbKeySeg::StringSeg(proposal_code_chunk),
+            DbKeySeg::StringSeg(idx),
+        ] if addr == &ADDRESS
+            && prefix == Keys::VALUES.proposal
+            && proposal_code_chunk == Keys::VALUES.proposal_code_chunk =>
+        {
+            id.parse::<u64>().ok()?;
+            idx.parse::<u64>().ok()
+        }
+        _ => None,
+    }
+}
+
+/// Check if key is a proposal code chunk hash key, returning the index of
+/// the chunk
+pub fn is_proposal_code_chunk_hash_key(key: &Key) -> Option<u64> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(id),
+            DbKeySeg::StringSeg(proposal_code_chunk_hash),
+            DbKeySeg::StringSeg(idx),
+        ] if addr == &ADDRESS
+            && prefix == Keys::VALUES.proposal
+            && proposal_code_chunk_hash
+                == Keys::VALUES.proposal_code_chunk_hash =>
+        {
+            id.parse::<u64>().ok()?;
+            idx.parse::<u64>().ok()
+        }
+        _ => None,
+    }
+}
+
+/// Check if key is a proposal code chunk count key
+pub fn is_proposal_code_chunk_count_key(key: &Key) -> bool {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(id),
+            DbKeySeg::StringSeg(proposal_code_chunk_count),
+        ] if addr == &ADDRESS
+            && prefix == Keys::VALUES.proposal
+            && proposal_code_chunk_count
+                == Keys::VALUES.proposal_code_chunk_count =>
+        {
+            id.parse::<u64>().is_ok()
+        }
+        _ => false,
+    }
+}
+
 /// Check if key is grace epoch key
 pub fn is_grace_epoch_key(key: &Key) -> bool {
     match &key.segments[..] {
@@ -180,6 +410,78 @@ pub fn is_end_epoch_key(key: &Key) -> bool {
     }
 }
 
+/// Check if key is voting start height key
+pub fn is_start_height_key(key: &Key) -> bool {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(id),
+            DbKeySeg::StringSeg(start_height),
+        ] if addr == &ADDRESS
+            && prefix == Keys::VALUES.proposal
+            && start_height == Keys::VALUES.start_height =>
+        {
+            id.parse::<u64>().is_ok()
+        }
+        _ => false,
+    }
+}
+
+/// Check if key is voting end height key
+pub fn is_end_height_key(key: &Key) -> bool {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(id),
+            DbKeySeg::StringSeg(end_height),
+        ] if addr == &ADDRESS
+            && prefix == Keys::VALUES.proposal
+            && end_height == Keys::VALUES.end_height =>
+        {
+            id.parse::<u64>().is_ok()
+        }
+        _ => false,
+    }
+}
+
+/// Check if key is proposal execution priority key
+pub fn is_priority_key(key: &Key) -> bool {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(id),
+            DbKeySeg::StringSeg(priority),
+        ] if addr == &ADDRESS
+            && prefix == Keys::VALUES.proposal
+            && priority == Keys::VALUES.priority =>
+        {
+            id.parse::<u64>().is_ok()
+        }
+        _ => false,
+    }
+}
+
+/// Check if key is the key of the output of the code of a proposal
+pub fn is_proposal_output_key(key: &Key) -> bool {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(id),
+            DbKeySeg::StringSeg(output),
+        ] if addr == &ADDRESS
+            && prefix == Keys::VALUES.proposal
+            && output == Keys::VALUES.output =>
+        {
+            id.parse::<u64>().is_ok()
+        }
+        _ => false,
+    }
+}
+
 /// Check if key is proposal type key
 pub fn is_proposal_type_key(key: &Key) -> bool {
     match &key.segments[..] {
@@ -203,6 +505,15 @@ pub fn is_counter_key(key: &Key) -> bool {
     matches!(&key.segments[..], [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(counter)] if addr == &ADDRESS && counter == Keys::VALUES.counter)
 }
 
+/// Check if key is the key of the cursor of the migration of the vote keys
+pub fn is_vote_key_migration_cursor_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+                    DbKeySeg::AddressSeg(addr),
+                    DbKeySeg::StringSeg(cursor),
+                ] if addr == &ADDRESS
+                    && cursor == Keys::VALUES.vote_key_migration_cursor)
+}
+
 /// Check if key is a proposal fund parameter key
 pub fn is_min_proposal_fund_key(key: &Key) -> bool {
     matches!(&key.segments[..], [
@@ -247,6 +558,141 @@ pub fn is_max_proposal_period_key(key: &Key) -> bool {
              && max_proposal_period_param == Keys::VALUES.max_period)
 }
 
+/// Check if key is a min proposal period in blocks param key
+pub fn is_min_proposal_voting_period_blocks_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+             DbKeySeg::AddressSeg(addr),
+             DbKeySeg::StringSeg(min_period_blocks_param),
+         ] if addr == &ADDRESS
+             && min_period_blocks_param == Keys::VALUES.min_period_blocks)
+}
+
+/// Check if key is a max proposal period in blocks param key
+pub fn is_max_proposal_voting_period_blocks_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+             DbKeySeg::AddressSeg(addr),
+             DbKeySeg::StringSeg(max_period_blocks_param),
+         ] if addr == &ADDRESS
+             && max_period_blocks_param == Keys::VALUES.max_period_blocks)
+}
+
+/// Check if key is an emergency validator quorum parameter key
+pub fn is_emergency_validator_quorum_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+                    DbKeySeg::AddressSeg(addr),
+                    DbKeySeg::StringSeg(emergency_validator_quorum_param),
+                ] if addr == &ADDRESS
+                    && emergency_validator_quorum_param
+                        == Keys::VALUES.emergency_validator_quorum)
+}
+
+/// Check if key is a max proposals per block parameter key
+pub fn is_max_proposals_per_block_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+                    DbKeySeg::AddressSeg(addr),
+                    DbKeySeg::StringSeg(max_proposals_per_block_param),
+                ] if addr == &ADDRESS
+                    && max_proposals_per_block_param
+                        == Keys::VALUES.max_proposals_per_block)
+}
+
+/// Check if key is the key of the proposals deferred to the next block
+pub fn is_deferred_proposals_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+                    DbKeySeg::AddressSeg(addr),
+                    DbKeySeg::StringSeg(deferred_proposals),
+                ] if addr == &ADDRESS
+                    && deferred_proposals == Keys::VALUES.deferred_proposals)
+}
+
+/// Check if key is the key of the last epoch whose committed proposals were
+/// concluded
+pub fn is_concluded_epoch_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+                    DbKeySeg::AddressSeg(addr),
+                    DbKeySeg::StringSeg(concluded_epoch),
+                ] if addr == &ADDRESS
+                    && concluded_epoch == Keys::VALUES.concluded_epoch)
+}
+
+/// Check if key is a parameter conflict policy parameter key
+pub fn is_parameter_conflict_policy_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+                    DbKeySeg::AddressSeg(addr),
+                    DbKeySeg::StringSeg(parameter_conflict_policy_param),
+                ] if addr == &ADDRESS
+                    && parameter_conflict_policy_param
+                        == Keys::VALUES.parameter_conflict_policy)
+}
+
+/// Check if key is a strict proposal code validation parameter key
+pub fn is_strict_proposal_code_validation_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+                    DbKeySeg::AddressSeg(addr),
+                    DbKeySeg::StringSeg(strict_proposal_code_validation_param),
+                ] if addr == &ADDRESS
+                    && strict_proposal_code_validation_param
+                        == Keys::VALUES.strict_proposal_code_validation)
+}
+
+/// Check if key is a rejected refund ratio parameter key
+pub fn is_rejected_refund_ratio_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+                    DbKeySeg::AddressSeg(addr),
+                    DbKeySeg::StringSeg(rejected_refund_ratio_param),
+                ] if addr == &ADDRESS
+                    && rejected_refund_ratio_param
+                        == Keys::VALUES.rejected_refund_ratio)
+}
+
+/// Check if key is the parameter key of the rejected refund ratios per
+/// proposal type
+pub fn is_rejected_refund_ratios_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+                    DbKeySeg::AddressSeg(addr),
+                    DbKeySeg::StringSeg(rejected_refund_ratios_param),
+                ] if addr == &ADDRESS
+                    && rejected_refund_ratios_param
+                        == Keys::VALUES.rejected_refund_ratios)
+}
+
+/// Check if key is the parameter key of the council
+pub fn is_council_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+                    DbKeySeg::AddressSeg(addr),
+                    DbKeySeg::StringSeg(council_param),
+                ] if addr == &ADDRESS && council_param == Keys::VALUES.council)
+}
+
+/// Check if key is the maximum treasury balance parameter key
+pub fn is_max_treasury_balance_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+                    DbKeySeg::AddressSeg(addr),
+                    DbKeySeg::StringSeg(max_treasury_balance_param),
+                ] if addr == &ADDRESS
+                    && max_treasury_balance_param
+                        == Keys::VALUES.max_treasury_balance)
+}
+
+/// Check if key is the tie-breaks parameter key
+pub fn is_tie_breaks_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+                    DbKeySeg::AddressSeg(addr),
+                    DbKeySeg::StringSeg(tie_breaks_param),
+                ] if addr == &ADDRESS
+                    && tie_breaks_param == Keys::VALUES.tie_breaks)
+}
+
+/// Check if key is the key of the protocol parameters set by the proposals
+/// concluded in the current epoch
+pub fn is_parameter_changes_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+                    DbKeySeg::AddressSeg(addr),
+                    DbKeySeg::StringSeg(parameter_changes),
+                ] if addr == &ADDRESS
+                    && parameter_changes == Keys::VALUES.parameter_changes)
+}
+
 /// Check if key is a min grace epoch key
 pub fn is_commit_proposal_key(key: &Key) -> bool {
     matches!(&key.segments[..], [
@@ -261,6 +707,21 @@ pub fn is_commit_proposal_key(key: &Key) -> bool {
     )
 }
 
+/// Check if key is a key committing a proposal to be concluded at some
+/// block height
+pub fn is_commit_proposal_at_height_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+        DbKeySeg::AddressSeg(addr),
+        DbKeySeg::StringSeg(prefix),
+        DbKeySeg::StringSeg(height_prefix),
+        DbKeySeg::StringSeg(_height),
+        DbKeySeg::StringSeg(_id),
+    ] if addr == &ADDRESS
+        && prefix == Keys::VALUES.proposal
+        && height_prefix == Keys::VALUES.committing_height
+    )
+}
+
 /// Check if key is a commit proposal key
 pub fn is_min_grace_epoch_key(key: &Key) -> bool {
     matches!(&key.segments[..], [
@@ -270,33 +731,147 @@ pub fn is_min_grace_epoch_key(key: &Key) -> bool {
                     && min_grace_epoch_param == Keys::VALUES.min_grace_epoch)
 }
 
-/// Check if key is parameter key
-pub fn is_parameter_key(key: &Key) -> bool {
-    is_min_proposal_fund_key(key)
-        || is_max_content_size_key(key)
-        || is_max_proposal_code_size_key(key)
-        || is_min_proposal_voting_period_key(key)
-        || is_max_proposal_period_key(key)
-        || is_min_grace_epoch_key(key)
-}
-
-/// Check if key is start epoch or end epoch key
-pub fn is_start_or_end_epoch_key(key: &Key) -> bool {
-    is_end_epoch_key(key) || is_start_epoch_key(key)
+/// Check if key is a proposal max lifetime parameter key
+pub fn is_max_proposal_lifetime_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+                    DbKeySeg::AddressSeg(addr),
+                    DbKeySeg::StringSeg(max_lifetime_param),
+                ] if addr == &ADDRESS
+                    && max_lifetime_param == Keys::VALUES.max_lifetime)
 }
 
-/// Get governance prefix key
-pub fn proposal_prefix() -> Key {
-    Key::from(ADDRESS.to_db_key())
-        .push(&Keys::VALUES.proposal.to_owned())
-        .expect("Cannot obtain a storage key")
+/// Check if key is a proposal base deposit parameter key
+pub fn is_base_deposit_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+                    DbKeySeg::AddressSeg(addr),
+                    DbKeySeg::StringSeg(base_deposit_param),
+                ] if addr == &ADDRESS
+                    && base_deposit_param == Keys::VALUES.base_deposit)
 }
 
-/// Get key for the minimum proposal fund
-pub fn get_min_proposal_fund_key() -> Key {
-    Key::from(ADDRESS.to_db_key())
-        .push(&Keys::VALUES.min_fund.to_owned())
-        .expect("Cannot obtain a storage key")
+/// Check if key is a proposal deposit per byte parameter key
+pub fn is_deposit_per_byte_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+                    DbKeySeg::AddressSeg(addr),
+                    DbKeySeg::StringSeg(deposit_per_byte_param),
+                ] if addr == &ADDRESS
+                    && deposit_per_byte_param == Keys::VALUES.deposit_per_byte)
+}
+
+/// Check if key is a max voting power fraction parameter key
+pub fn is_max_voting_power_fraction_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+                    DbKeySeg::AddressSeg(addr),
+                    DbKeySeg::StringSeg(max_voting_power_fraction_param),
+                ] if addr == &ADDRESS
+                    && max_voting_power_fraction_param
+                        == Keys::VALUES.max_voting_power_fraction)
+}
+
+/// Check if key is a treasury high-water mark parameter key
+pub fn is_treasury_high_water_mark_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+                    DbKeySeg::AddressSeg(addr),
+                    DbKeySeg::StringSeg(treasury_high_water_mark_param),
+                ] if addr == &ADDRESS
+                    && treasury_high_water_mark_param
+                        == Keys::VALUES.treasury_high_water_mark)
+}
+
+/// Check if key is a min proposal author stake parameter key
+pub fn is_min_proposal_author_stake_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+                    DbKeySeg::AddressSeg(addr),
+                    DbKeySeg::StringSeg(min_author_stake_param),
+                ] if addr == &ADDRESS
+                    && min_author_stake_param == Keys::VALUES.min_author_stake)
+}
+
+/// Check if key is a treasury history retention parameter key
+pub fn is_treasury_history_retention_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+                    DbKeySeg::AddressSeg(addr),
+                    DbKeySeg::StringSeg(treasury_history_retention_param),
+                ] if addr == &ADDRESS
+                    && treasury_history_retention_param
+                        == Keys::VALUES.treasury_history_retention)
+}
+
+/// Check if key is a treasury inflation rate parameter key
+pub fn is_treasury_inflation_rate_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+                    DbKeySeg::AddressSeg(addr),
+                    DbKeySeg::StringSeg(treasury_inflation_rate_param),
+                ] if addr == &ADDRESS
+                    && treasury_inflation_rate_param
+                        == Keys::VALUES.treasury_inflation_rate)
+}
+
+/// Check if key is parameter key
+pub fn is_parameter_key(key: &Key) -> bool {
+    is_min_proposal_fund_key(key)
+        || is_max_content_size_key(key)
+        || is_max_proposal_code_size_key(key)
+        || is_min_proposal_voting_period_key(key)
+        || is_max_proposal_period_key(key)
+        || is_min_grace_epoch_key(key)
+        || is_max_proposal_lifetime_key(key)
+        || is_base_deposit_key(key)
+        || is_deposit_per_byte_key(key)
+        || is_max_voting_power_fraction_key(key)
+        || is_treasury_high_water_mark_key(key)
+        || is_min_proposal_author_stake_key(key)
+        || is_treasury_history_retention_key(key)
+        || is_treasury_inflation_rate_key(key)
+        || is_min_proposal_voting_period_blocks_key(key)
+        || is_max_proposal_voting_period_blocks_key(key)
+        || is_emergency_validator_quorum_key(key)
+        || is_max_proposals_per_block_key(key)
+        || is_parameter_conflict_policy_key(key)
+        || is_strict_proposal_code_validation_key(key)
+        || is_rejected_refund_ratio_key(key)
+        || is_rejected_refund_ratios_key(key)
+        || is_council_key(key)
+        || is_max_treasury_balance_key(key)
+        || is_tie_breaks_key(key)
+}
+
+/// Check if key is start epoch or end epoch key
+pub fn is_start_or_end_epoch_key(key: &Key) -> bool {
+    is_end_epoch_key(key) || is_start_epoch_key(key)
+}
+
+/// Get the prefixes of the storage keys the code of a proposal must not
+/// write, as they hold the state of the proposals themselves and of their
+/// queues
+pub fn protected_prefixes() -> [Key; 4] {
+    [
+        proposal_prefix(),
+        get_counter_key(),
+        get_deferred_proposals_key(),
+        get_concluded_epoch_key(),
+    ]
+}
+
+/// Check if key falls under one of the [`protected_prefixes`]
+pub fn is_protected_key(key: &Key) -> bool {
+    protected_prefixes()
+        .iter()
+        .any(|prefix| key.split_prefix(prefix).is_some())
+}
+
+/// Get governance prefix key
+pub fn proposal_prefix() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.proposal.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get key for the minimum proposal fund
+pub fn get_min_proposal_fund_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.min_fund.to_owned())
+        .expect("Cannot obtain a storage key")
 }
 
 /// Get maximum proposal code size key
@@ -334,6 +909,161 @@ pub fn get_min_proposal_grace_epoch_key() -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Get max proposal lifetime key
+pub fn get_max_proposal_lifetime_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.max_lifetime.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get proposal base deposit key
+pub fn get_base_deposit_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.base_deposit.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get proposal deposit per byte key
+pub fn get_deposit_per_byte_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.deposit_per_byte.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get max voting power fraction key
+pub fn get_max_voting_power_fraction_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.max_voting_power_fraction.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get treasury high-water mark key
+pub fn get_treasury_high_water_mark_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.treasury_high_water_mark.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get min proposal author stake key
+pub fn get_min_proposal_author_stake_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.min_author_stake.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get treasury history retention key
+pub fn get_treasury_history_retention_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.treasury_history_retention.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get treasury inflation rate key
+pub fn get_treasury_inflation_rate_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.treasury_inflation_rate.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get min proposal voting period in blocks key
+pub fn get_min_proposal_voting_period_blocks_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.min_period_blocks.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get max proposal voting period in blocks key
+pub fn get_max_proposal_voting_period_blocks_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.max_period_blocks.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get emergency validator quorum key
+pub fn get_emergency_validator_quorum_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.emergency_validator_quorum.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get max proposals per block key
+pub fn get_max_proposals_per_block_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.max_proposals_per_block.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the ids of the proposals deferred to the next block
+pub fn get_deferred_proposals_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.deferred_proposals.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the last epoch whose committed proposals were concluded
+pub fn get_concluded_epoch_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.concluded_epoch.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get parameter conflict policy key
+pub fn get_parameter_conflict_policy_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.parameter_conflict_policy.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get strict proposal code validation key
+pub fn get_strict_proposal_code_validation_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.strict_proposal_code_validation.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get rejected refund ratio key
+pub fn get_rejected_refund_ratio_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.rejected_refund_ratio.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the rejected refund ratios per proposal type
+pub fn get_rejected_refund_ratios_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.rejected_refund_ratios.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the council
+pub fn get_council_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.council.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the maximum treasury balance
+pub fn get_max_treasury_balance_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.max_treasury_balance.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the tie-breaks of the tallies of proposals
+pub fn get_tie_breaks_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.tie_breaks.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the protocol parameters set by the proposals concluded in
+/// the current epoch
+pub fn get_parameter_changes_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.parameter_changes.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get key of proposal ids counter
 pub fn get_counter_key() -> Key {
     Key::from(ADDRESS.to_db_key())
@@ -341,6 +1071,14 @@ pub fn get_counter_key() -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Get the key of the id of the first proposal whose vote keys are left to
+/// migrate by [`super::migrate_vote_keys`]
+pub fn get_vote_key_migration_cursor_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.vote_key_migration_cursor.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get key of proposal content
 pub fn get_content_key(id: u64) -> Key {
     proposal_prefix()
@@ -386,6 +1124,33 @@ pub fn get_voting_end_epoch_key(id: u64) -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Get key of proposal voting start height
+pub fn get_voting_start_height_key(id: u64) -> Key {
+    proposal_prefix()
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+        .push(&Keys::VALUES.start_height.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get key of proposal voting end height
+pub fn get_voting_end_height_key(id: u64) -> Key {
+    proposal_prefix()
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+        .push(&Keys::VALUES.end_height.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get key of proposal execution priority
+pub fn get_priority_key(id: u64) -> Key {
+    proposal_prefix()
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+        .push(&Keys::VALUES.priority.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get key of proposal funds
 pub fn get_funds_key(id: u64) -> Key {
     proposal_prefix()
@@ -404,15 +1169,30 @@ pub fn get_grace_epoch_key(id: u64) -> Key {
         .expect("Cannot obtain a storage key")
 }
 
-/// Get the proposal committing key prefix
-pub fn get_commiting_proposals_prefix(epoch: u64) -> Key {
+/// Get the prefix of the proposal committing keys of all the epochs
+pub fn get_all_commiting_proposals_prefix() -> Key {
     proposal_prefix()
         .push(&Keys::VALUES.committing_epoch.to_owned())
         .expect("Cannot obtain a storage key")
+}
+
+/// Get the proposal committing key prefix
+pub fn get_commiting_proposals_prefix(epoch: u64) -> Key {
+    get_all_commiting_proposals_prefix()
         .push(&epoch.to_string())
         .expect("Cannot obtain a storage key")
 }
 
+/// Get the prefix of the keys committing proposals to be concluded at the
+/// given block height
+pub fn get_commiting_proposals_at_height_prefix(height: u64) -> Key {
+    proposal_prefix()
+        .push(&Keys::VALUES.committing_height.to_owned())
+        .expect("Cannot obtain a storage key")
+        .push(&height.to_string())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get proposal code key
 pub fn get_proposal_code_key(id: u64) -> Key {
     proposal_prefix()
@@ -422,6 +1202,48 @@ pub fn get_proposal_code_key(id: u64) -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Get proposal code hash key
+pub fn get_proposal_code_hash_key(id: u64) -> Key {
+    proposal_prefix()
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+        .push(&Keys::VALUES.proposal_code_hash.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of a chunk of the code of a proposal, holding the wasm code
+/// executed after the code under [`get_proposal_code_key`] and after the
+/// chunks of lower index
+pub fn get_proposal_code_chunk_key(id: u64, idx: u64) -> Key {
+    proposal_prefix()
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+        .push(&Keys::VALUES.proposal_code_chunk.to_owned())
+        .expect("Cannot obtain a storage key")
+        .push(&idx.to_string())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the hash of a chunk of the code of a proposal
+pub fn get_proposal_code_chunk_hash_key(id: u64, idx: u64) -> Key {
+    proposal_prefix()
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+        .push(&Keys::VALUES.proposal_code_chunk_hash.to_owned())
+        .expect("Cannot obtain a storage key")
+        .push(&idx.to_string())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the number of chunks of the code of a proposal
+pub fn get_proposal_code_chunk_count_key(id: u64) -> Key {
+    proposal_prefix()
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+        .push(&Keys::VALUES.proposal_code_chunk_count.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get the committing proposal key
 pub fn get_committing_proposals_key(id: u64, epoch: u64) -> Key {
     get_commiting_proposals_prefix(epoch)
@@ -429,6 +1251,14 @@ pub fn get_committing_proposals_key(id: u64, epoch: u64) -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Get the key committing a proposal to be concluded at the given block
+/// height
+pub fn get_committing_proposals_at_height_key(id: u64, height: u64) -> Key {
+    get_commiting_proposals_at_height_prefix(height)
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get proposal vote prefix key
 pub fn get_proposal_vote_prefix_key(id: u64) -> Key {
     proposal_prefix()
@@ -438,11 +1268,37 @@ pub fn get_proposal_vote_prefix_key(id: u64) -> Key {
         .expect("Cannot obtain a storage key")
 }
 
-/// Get the vote key for a specific proposal id
+/// Get the vote key for a specific proposal id. The voter is a validator
+/// voting with its own stake if it's the delegation address, a delegator
+/// otherwise.
 pub fn get_vote_proposal_key(
     id: u64,
     voter_address: Address,
     delegation_address: Address,
+) -> Key {
+    let prefix = get_proposal_vote_prefix_key(id);
+    if voter_address == delegation_address {
+        prefix
+            .push(&Keys::VALUES.validator.to_owned())
+            .expect("Cannot obtain a storage key")
+            .push(&voter_address)
+            .expect("Cannot obtain a storage key")
+    } else {
+        prefix
+            .push(&Keys::VALUES.delegator.to_owned())
+            .expect("Cannot obtain a storage key")
+            .push(&voter_address)
+            .expect("Cannot obtain a storage key")
+            .push(&delegation_address)
+            .expect("Cannot obtain a storage key")
+    }
+}
+
+/// Get the vote key of the legacy layout for a specific proposal id
+pub fn get_legacy_vote_proposal_key(
+    id: u64,
+    voter_address: Address,
+    delegation_address: Address,
 ) -> Key {
     get_proposal_vote_prefix_key(id)
         .push(&delegation_address)
@@ -451,11 +1307,16 @@ pub fn get_vote_proposal_key(
         .expect("Cannot obtain a storage key")
 }
 
-/// Get the proposal execution key
-pub fn get_proposal_execution_key(id: u64) -> Key {
+/// Get the prefix of the proposal execution keys
+pub fn get_proposal_execution_prefix() -> Key {
     Key::from(ADDRESS.to_db_key())
         .push(&Keys::VALUES.pending.to_owned())
         .expect("Cannot obtain a storage key")
+}
+
+/// Get the proposal execution key
+pub fn get_proposal_execution_key(id: u64) -> Key {
+    get_proposal_execution_prefix()
         .push(&id.to_string())
         .expect("Cannot obtain a storage key")
 }
@@ -469,6 +1330,297 @@ pub fn get_proposal_result_key(id: u64) -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Get the key of the id of the proposal that a proposal was rejected for
+/// conflicting with
+pub fn get_proposal_conflict_key(id: u64) -> Key {
+    proposal_prefix()
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+        .push(&Keys::VALUES.conflicts_with.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the structured result the code of a proposal may write
+/// while being executed, which is reported in the event of the proposal and
+/// deleted right after
+pub fn get_proposal_output_key(id: u64) -> Key {
+    proposal_prefix()
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+        .push(&Keys::VALUES.output.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the input of the random draw which broke a tie of the
+/// tally of a proposal
+pub fn get_proposal_tie_break_key(id: u64) -> Key {
+    proposal_prefix()
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+        .push(&Keys::VALUES.tie_break.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the outcome of the execution of the code of a proposal
+pub fn get_proposal_execution_result_key(id: u64) -> Key {
+    proposal_prefix()
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+        .push(&Keys::VALUES.execution_result.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key recording where the funds of a proposal went, which marks
+/// them as released
+pub fn get_funds_released_key(id: u64) -> Key {
+    proposal_prefix()
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+        .push(&Keys::VALUES.funds_released.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the prefix of the archived proposal results decided at the given epoch
+pub fn get_proposal_archive_prefix(epoch: Epoch) -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.archive.to_owned())
+        .expect("Cannot obtain a storage key")
+        .push(&epoch)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of an archived proposal result
+pub fn get_proposal_archive_key(epoch: Epoch, id: u64) -> Key {
+    get_proposal_archive_prefix(epoch)
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Check if key is an archived proposal result key
+pub fn is_proposal_archive_key(key: &Key) -> bool {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(_epoch),
+            DbKeySeg::StringSeg(id),
+        ] if addr == &ADDRESS && prefix == Keys::VALUES.archive => {
+            id.parse::<u64>().is_ok()
+        }
+        _ => false,
+    }
+}
+
+/// Get the prefix of the snapshots of the treasury balance
+pub fn get_treasury_balance_prefix() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.treasury_balance.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the snapshot of the treasury balance taken at the given
+/// epoch
+pub fn get_treasury_balance_key(epoch: Epoch) -> Key {
+    get_treasury_balance_prefix()
+        .push(&epoch)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Check if key is a treasury balance snapshot key, returning the epoch at
+/// which the snapshot was taken
+pub fn is_treasury_balance_key(key: &Key) -> Option<Epoch> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(epoch),
+        ] if addr == &ADDRESS && prefix == Keys::VALUES.treasury_balance => {
+            Epoch::parse(epoch.clone()).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Get the prefix of the amounts minted to the treasury by inflation
+pub fn get_treasury_inflation_prefix() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.treasury_inflation.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the amount minted to the treasury by inflation at the
+/// given epoch
+pub fn get_treasury_inflation_key(epoch: Epoch) -> Key {
+    get_treasury_inflation_prefix()
+        .push(&epoch)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Check if key is a treasury inflation key, returning the epoch at which
+/// the inflation was minted
+pub fn is_treasury_inflation_key(key: &Key) -> Option<Epoch> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(epoch),
+        ] if addr == &ADDRESS && prefix == Keys::VALUES.treasury_inflation => {
+            Epoch::parse(epoch.clone()).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Get the prefix of the participation of the validators in the proposals
+/// concluded at each epoch
+pub fn get_participation_prefix() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.participation.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the participation of the validators in the proposals
+/// concluded at the given epoch
+pub fn get_participation_key(epoch: Epoch) -> Key {
+    get_participation_prefix()
+        .push(&epoch)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Check if key is a participation key, returning the epoch at which the
+/// proposals were concluded
+pub fn is_participation_key(key: &Key) -> Option<Epoch> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(epoch),
+        ] if addr == &ADDRESS && prefix == Keys::VALUES.participation => {
+            Epoch::parse(epoch.clone()).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Get the prefix of the validators whose signatures were included in the
+/// proofs of the validator set updates signed at each epoch
+pub fn get_valset_sig_participation_prefix() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.valset_sig_participation.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the validators whose signatures were included in the
+/// proof of the validator set update signed at the given epoch
+pub fn get_valset_sig_participation_key(epoch: Epoch) -> Key {
+    get_valset_sig_participation_prefix()
+        .push(&epoch)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Check if key is a validator set update signature participation key,
+/// returning the epoch at which the validator set update was signed
+pub fn is_valset_sig_participation_key(key: &Key) -> Option<Epoch> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(epoch),
+        ] if addr == &ADDRESS
+            && prefix == Keys::VALUES.valset_sig_participation =>
+        {
+            Epoch::parse(epoch.clone()).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Get the key of the governance voting public key registered by an account
+pub fn get_voting_key_key(owner: &Address) -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.voting_key.to_owned())
+        .expect("Cannot obtain a storage key")
+        .push(owner)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Check if key is a governance voting public key key, returning the
+/// address of the account that registered it
+pub fn is_voting_key_key(key: &Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::AddressSeg(owner),
+        ] if addr == &ADDRESS && prefix == Keys::VALUES.voting_key => {
+            Some(owner)
+        }
+        _ => None,
+    }
+}
+
+/// Get the prefix of the votes index of the given voter
+pub fn get_voter_index_prefix(voter: &Address) -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.voter.to_owned())
+        .expect("Cannot obtain a storage key")
+        .push(voter)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key indexing the vote of the given voter on a proposal
+pub fn get_voter_index_key(voter: &Address, id: u64) -> Key {
+    get_voter_index_prefix(voter)
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Check if key is a voter index key, returning the address of the voter
+/// and the id of the proposal that was voted on
+pub fn is_voter_index_key(key: &Key) -> Option<(&Address, u64)> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::AddressSeg(voter),
+            DbKeySeg::StringSeg(id),
+        ] if addr == &ADDRESS && prefix == Keys::VALUES.voter => {
+            id.parse::<u64>().ok().map(|id| (voter, id))
+        }
+        _ => None,
+    }
+}
+
+/// Get the prefix of the governance vote delegations
+pub fn get_delegation_prefix() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.delegation.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the governor to whom the given delegator has delegated
+/// its governance votes
+pub fn get_delegation_key(delegator: &Address) -> Key {
+    get_delegation_prefix()
+        .push(delegator)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Check if key is a governance vote delegation key, returning the address
+/// of the delegator
+pub fn is_delegation_key(key: &Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::AddressSeg(delegator),
+        ] if addr == &ADDRESS && prefix == Keys::VALUES.delegation => {
+            Some(delegator)
+        }
+        _ => None,
+    }
+}
+
 /// Get proposal id from key
 pub fn get_proposal_id(key: &Key) -> Option<u64> {
     match key.get_at(2) {
@@ -501,24 +1653,86 @@ pub fn get_commit_proposal_id(key: &Key) -> Option<u64> {
         None => None,
     }
 }
-/// Get the delegation address from vote key
+/// Get the delegation address from vote key, of either layout
 pub fn get_vote_delegation_address(key: &Key) -> Option<&Address> {
-    match key.get_at(4) {
-        Some(addr) => match addr {
-            DbKeySeg::AddressSeg(res) => Some(res),
-            DbKeySeg::StringSeg(_) => None,
-        },
-        None => None,
-    }
+    parse_vote_key(key)
+        .or_else(|| parse_legacy_vote_key(key))
+        .map(|vote_key| vote_key.validator)
 }
 
-/// Get voter address from vote key
+/// Get voter address from vote key, of either layout
 pub fn get_voter_address(key: &Key) -> Option<&Address> {
-    match key.get_at(5) {
-        Some(addr) => match addr {
-            DbKeySeg::AddressSeg(res) => Some(res),
-            DbKeySeg::StringSeg(_) => None,
-        },
-        None => None,
+    parse_vote_key(key)
+        .or_else(|| parse_legacy_vote_key(key))
+        .map(|vote_key| vote_key.voter)
+}
+
+#[cfg(test)]
+mod test {
+    use namada_core::types::address::testing::{
+        established_address_1, established_address_2,
+    };
+
+    use super::*;
+
+    /// Test that the state of proposals and of their queues is protected,
+    /// unlike the governance parameters
+    #[test]
+    fn test_protected_keys() {
+        assert!(is_protected_key(&get_funds_key(0)));
+        assert!(is_protected_key(&get_committing_proposals_key(0, 1)));
+        assert!(is_protected_key(&get_counter_key()));
+        assert!(is_protected_key(&get_deferred_proposals_key()));
+        assert!(is_protected_key(&get_concluded_epoch_key()));
+        assert!(!is_protected_key(&get_min_proposal_grace_epoch_key()));
+        assert!(!is_protected_key(&get_max_proposals_per_block_key()));
+    }
+
+    /// Test that vote keys record the role of the voter, and that both the
+    /// current and the legacy layouts are parsed
+    #[test]
+    fn test_vote_key_layouts() {
+        let validator = established_address_1();
+        let delegator = established_address_2();
+
+        let key =
+            get_vote_proposal_key(3, validator.clone(), validator.clone());
+        assert!(is_vote_key(&key));
+        assert!(!is_legacy_vote_key(&key));
+        assert_eq!(
+            parse_vote_key(&key),
+            Some(VoteKey {
+                proposal_id: 3,
+                voter_type: VoterType::Validator,
+                voter: &validator,
+                validator: &validator,
+            })
+        );
+
+        let key =
+            get_vote_proposal_key(3, delegator.clone(), validator.clone());
+        assert!(is_vote_key(&key));
+        assert_eq!(
+            parse_vote_key(&key),
+            Some(VoteKey {
+                proposal_id: 3,
+                voter_type: VoterType::Delegator,
+                voter: &delegator,
+                validator: &validator,
+            })
+        );
+        assert_eq!(get_voter_address(&key), Some(&delegator));
+        assert_eq!(get_vote_delegation_address(&key), Some(&validator));
+
+        let legacy = get_legacy_vote_proposal_key(
+            3,
+            delegator.clone(),
+            validator.clone(),
+        );
+        assert!(!is_vote_key(&legacy));
+        assert!(is_legacy_vote_key(&legacy));
+        assert_eq!(parse_legacy_vote_key(&legacy), parse_vote_key(&key));
+        assert_eq!(get_voter_address(&legacy), Some(&delegator));
+        assert_eq!(get_vote_delegation_address(&legacy), Some(&validator));
     }
 }
@@ -1,5 +1,11 @@
 //! Governance storage
 
+/// Optional compression of the code of proposals
+pub mod code;
+/// Consistency checks of the governance storage
+pub mod consistency;
+/// Typed access to the fields of proposals
+pub mod facade;
 /// Governance proposal keys
 pub mod keys;
 /// Proposal structures
@@ -7,31 +13,58 @@ pub mod proposal;
 /// Vote structures
 pub mod vote;
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
-use namada_core::borsh::BorshDeserialize;
+use namada_core::borsh::{BorshDeserialize, BorshSerialize};
 use namada_core::types::address::Address;
-use namada_core::types::storage::Epoch;
+use namada_core::types::dec::Dec;
+use namada_core::types::hash::Hash;
+use namada_core::types::key::common;
+use namada_core::types::storage::{BlockHeight, Epoch, Key};
 use namada_state::{
-    iter_prefix, StorageError, StorageRead, StorageResult, StorageWrite,
+    iter_prefix, iter_prefix_bytes, StorageError, StorageRead, StorageResult,
+    StorageWrite,
 };
 use namada_trans_token as token;
 
-use crate::parameters::GovernanceParameters;
+use crate::parameters::{
+    Council, GovernanceParameters, ParameterConflictPolicy, TieBreaks,
+};
+use crate::pgf::ADDRESS as pgf_address;
+use crate::storage::facade::{ProposalStorageReader, ProposalStorageWriter};
 use crate::storage::keys as governance_keys;
 use crate::storage::proposal::{
-    InitProposalData, ProposalType, StorageProposal, VoteProposalData,
+    InitProposalData, PendingExecution, ProposalType, StorageProposal,
+    VoteProposalData,
+};
+use crate::storage::vote::{ProposalVote, VoteDelegationData, VoteReceipt};
+use crate::utils::{
+    ArchivedProposalResult, EpochParticipation, ProposalExecutionResult,
+    ProposalOutput, ProposalResult, ReleasedFunds, TallyResult, TieBreakSeed,
+    ValidatorParticipation, ValsetSigParticipation, Vote,
 };
-use crate::storage::vote::ProposalVote;
-use crate::utils::{ProposalResult, Vote};
 use crate::ADDRESS as governance_address;
 
+/// The maximum length of a chain of governance vote delegations. Longer
+/// chains are rejected when written, and aren't followed further when
+/// tallying the votes.
+pub const MAX_VOTE_DELEGATION_DEPTH: usize = 8;
+
+/// The number of proposals whose votes are migrated to the current vote key
+/// layout by a single run of the `tx_migrate_vote_keys` proposal code, see
+/// [`migrate_vote_keys`]
+pub const VOTE_KEY_MIGRATION_BATCH_SIZE: u64 = 100;
+
 /// A proposal creation transaction.
+///
+/// The `code_chunks` of a proposal with some `code` are executed in order
+/// after it, atomically with it.
 pub fn init_proposal<S>(
     storage: &mut S,
     data: InitProposalData,
     content: Vec<u8>,
     code: Option<Vec<u8>>,
+    code_chunks: Vec<Vec<u8>>,
 ) -> StorageResult<()>
 where
     S: StorageRead + StorageWrite,
@@ -42,61 +75,69 @@ where
          proposal id",
     );
 
-    let content_key = governance_keys::get_content_key(proposal_id);
-    storage.write_bytes(&content_key, content)?;
+    let proposal_size = content.len()
+        + code.as_ref().map(Vec::len).unwrap_or_default()
+        + code_chunks.iter().map(Vec::len).sum::<usize>();
+    let deposit = get_required_deposit(storage, proposal_size as u64)?;
 
-    let author_key = governance_keys::get_author_key(proposal_id);
-    storage.write(&author_key, data.author.clone())?;
+    let mut proposal_store = ProposalStorageWriter::new(storage);
+    proposal_store.set_content_bytes(proposal_id, content)?;
+    proposal_store.set_author(proposal_id, &data.author)?;
 
-    let proposal_type_key = governance_keys::get_proposal_type_key(proposal_id);
     match data.r#type {
-        ProposalType::Default(Some(_)) => {
+        ProposalType::Default(Some(_)) | ProposalType::Council(Some(_)) => {
             // Remove wasm code and write it under a different subkey
-            storage.write(&proposal_type_key, ProposalType::Default(None))?;
-            let proposal_code_key =
-                governance_keys::get_proposal_code_key(proposal_id);
-            let proposal_code = code
-                .clone()
-                .ok_or(StorageError::new_const("Missing proposal code"))?;
-            storage.write_bytes(&proposal_code_key, proposal_code)?
+            proposal_store
+                .set_proposal_type(proposal_id, &data.r#type.without_code())?;
+            let proposal_code =
+                code.ok_or(StorageError::new_const("Missing proposal code"))?;
+            // Allow clients to verify the code they download
+            proposal_store
+                .set_code_hash(proposal_id, Hash::sha256(&proposal_code))?;
+            proposal_store.set_code(proposal_id, proposal_code)?;
+            if !code_chunks.is_empty() {
+                proposal_store.set_code_chunks(proposal_id, code_chunks)?;
+            }
+        }
+        _ if !code_chunks.is_empty() => {
+            return Err(StorageError::new_const(
+                "Only proposals with code may have code chunks",
+            ));
         }
-        _ => storage.write(&proposal_type_key, data.r#type.clone())?,
+        _ => proposal_store.set_proposal_type(proposal_id, &data.r#type)?,
     }
 
-    let voting_start_epoch_key =
-        governance_keys::get_voting_start_epoch_key(proposal_id);
-    storage.write(&voting_start_epoch_key, data.voting_start_epoch)?;
-
-    let voting_end_epoch_key =
-        governance_keys::get_voting_end_epoch_key(proposal_id);
-    storage.write(&voting_end_epoch_key, data.voting_end_epoch)?;
-
-    let grace_epoch_key = governance_keys::get_grace_epoch_key(proposal_id);
-    storage.write(&grace_epoch_key, data.grace_epoch)?;
-
-    if let ProposalType::Default(Some(_)) = data.r#type {
-        let proposal_code_key =
-            governance_keys::get_proposal_code_key(proposal_id);
-        let proposal_code =
-            code.ok_or(StorageError::new_const("Missing proposal code"))?;
-        storage.write_bytes(&proposal_code_key, proposal_code)?;
+    // the voting period is either expressed in block heights or in epochs,
+    // and the proposal is committed to be concluded accordingly
+    let committing_proposals_key = match data.voting_heights {
+        Some(voting_heights) => {
+            proposal_store.set_voting_heights(proposal_id, voting_heights)?;
+            governance_keys::get_committing_proposals_at_height_key(
+                proposal_id,
+                voting_heights.end.0,
+            )
+        }
+        None => {
+            proposal_store
+                .set_voting_start(proposal_id, data.voting_start_epoch)?;
+            proposal_store
+                .set_voting_end(proposal_id, data.voting_end_epoch)?;
+            proposal_store.set_grace_epoch(proposal_id, data.grace_epoch)?;
+            governance_keys::get_committing_proposals_key(
+                proposal_id,
+                data.grace_epoch.0,
+            )
+        }
+    };
+    if let Some(priority) = data.priority {
+        proposal_store.set_priority(proposal_id, priority)?;
     }
 
     storage.write(&counter_key, proposal_id + 1)?;
 
-    let min_proposal_funds_key = governance_keys::get_min_proposal_fund_key();
-    let min_proposal_funds: token::Amount =
-        storage.read(&min_proposal_funds_key)?.unwrap();
-
-    let funds_key = governance_keys::get_funds_key(proposal_id);
-    storage.write(&funds_key, min_proposal_funds)?;
+    ProposalStorageWriter::new(storage).set_funds(proposal_id, deposit)?;
 
     // this key must always be written for each proposal
-    let committing_proposals_key =
-        governance_keys::get_committing_proposals_key(
-            proposal_id,
-            data.grace_epoch.0,
-        );
     storage.write(&committing_proposals_key, ())?;
 
     token::transfer(
@@ -104,7 +145,7 @@ where
         &storage.get_native_token()?,
         &data.author,
         &governance_address,
-        min_proposal_funds,
+        deposit,
     )
 }
 
@@ -116,7 +157,19 @@ pub fn vote_proposal<S>(
 where
     S: StorageRead + StorageWrite,
 {
+    if data.delegations.is_empty() {
+        return Ok(());
+    }
     for delegation in data.delegations {
+        // A vote cast before the vote keys were migrated is replaced
+        let legacy_key = governance_keys::get_legacy_vote_proposal_key(
+            data.id,
+            data.voter.clone(),
+            delegation.clone(),
+        );
+        if storage.has_key(&legacy_key)? {
+            storage.delete(&legacy_key)?;
+        }
         let vote_key = governance_keys::get_vote_proposal_key(
             data.id,
             data.voter.clone(),
@@ -124,7 +177,127 @@ where
         );
         storage.write(&vote_key, data.vote.clone())?;
     }
-    Ok(())
+    // Keep the votes index of the voter pointing at the latest vote
+    let receipt = VoteReceipt {
+        proposal_id: data.id,
+        vote: data.vote,
+        epoch: storage.get_block_epoch()?,
+    };
+    let index_key = governance_keys::get_voter_index_key(&data.voter, data.id);
+    storage.write(&index_key, receipt)
+}
+
+/// Register a dedicated public key to authenticate the governance votes of
+/// the given account, in addition to the keys of the account itself.
+pub fn register_voting_key<S>(
+    storage: &mut S,
+    owner: &Address,
+    voting_key: &common::PublicKey,
+) -> StorageResult<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    if !matches!(voting_key, common::PublicKey::Ed25519(_)) {
+        return Err(StorageError::new_const(
+            "Governance voting keys must be Ed25519 keys",
+        ));
+    }
+    let key = governance_keys::get_voting_key_key(owner);
+    storage.write(&key, voting_key)
+}
+
+/// Remove the governance voting key registered by the given account, if
+/// any. Votes of the account are then only authenticated with its own keys.
+pub fn unregister_voting_key<S>(
+    storage: &mut S,
+    owner: &Address,
+) -> StorageResult<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = governance_keys::get_voting_key_key(owner);
+    storage.delete(&key)
+}
+
+/// A governance vote delegation transaction. The delegation is rejected if
+/// it would form a cycle of delegations, or a chain of delegations longer
+/// than [`MAX_VOTE_DELEGATION_DEPTH`].
+pub fn delegate_votes<S>(
+    storage: &mut S,
+    data: VoteDelegationData,
+) -> StorageResult<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = governance_keys::get_delegation_key(&data.delegator);
+    match data.governor {
+        Some(governor) => {
+            if !is_valid_delegation_chain(storage, &data.delegator, &governor)?
+            {
+                return Err(StorageError::new_const(
+                    "Governance vote delegations must not be circular or \
+                     exceed the maximum delegation depth",
+                ));
+            }
+            storage.write(&key, governor)
+        }
+        None => storage.delete(&key),
+    }
+}
+
+/// Check that delegating the governance votes of `delegator` to `governor`
+/// forms neither a cycle of delegations, nor a chain of delegations longer
+/// than [`MAX_VOTE_DELEGATION_DEPTH`].
+pub fn is_valid_delegation_chain<S>(
+    storage: &S,
+    delegator: &Address,
+    governor: &Address,
+) -> StorageResult<bool>
+where
+    S: StorageRead,
+{
+    let mut governor = governor.clone();
+    for _ in 0..MAX_VOTE_DELEGATION_DEPTH {
+        if &governor == delegator {
+            return Ok(false);
+        }
+        match get_governor(storage, &governor)? {
+            Some(next) => governor = next,
+            None => return Ok(true),
+        }
+    }
+    Ok(false)
+}
+
+/// Get the governor to whom the given account has delegated its governance
+/// votes, if any
+pub fn get_governor<S>(
+    storage: &S,
+    delegator: &Address,
+) -> StorageResult<Option<Address>>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_delegation_key(delegator);
+    storage.read(&key)
+}
+
+/// Get all the governance vote delegations, mapping the delegators to their
+/// governors
+pub fn get_governors<S>(
+    storage: &S,
+) -> StorageResult<BTreeMap<Address, Address>>
+where
+    S: StorageRead,
+{
+    let prefix = governance_keys::get_delegation_prefix();
+    iter_prefix::<Address>(storage, &prefix)?
+        .filter_map(|result| match result {
+            Ok((key, governor)) => governance_keys::is_delegation_key(&key)
+                .map(|delegator| Ok((delegator.clone(), governor))),
+            Err(err) => Some(Err(err)),
+        })
+        .collect()
 }
 
 /// Write the proposal result to storage.
@@ -136,9 +309,81 @@ pub fn write_proposal_result<S>(
 where
     S: StorageRead + StorageWrite,
 {
-    let proposal_result_key =
-        governance_keys::get_proposal_result_key(proposal_id);
-    storage.write(&proposal_result_key, proposal_result)
+    ProposalStorageWriter::new(storage).set_result(proposal_id, proposal_result)
+}
+
+/// Record the id of the proposal concluded earlier in the same epoch that
+/// set the same protocol parameter as a proposal, which was rejected for
+/// conflicting with it
+pub fn write_proposal_conflict<S>(
+    storage: &mut S,
+    proposal_id: u64,
+    conflicting_id: u64,
+) -> StorageResult<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    ProposalStorageWriter::new(storage)
+        .set_conflict(proposal_id, conflicting_id)
+}
+
+/// Get the id of the proposal that a proposal was rejected for conflicting
+/// with, if it was
+pub fn get_proposal_conflict<S>(
+    storage: &S,
+    proposal_id: u64,
+) -> StorageResult<Option<u64>>
+where
+    S: StorageRead,
+{
+    ProposalStorageReader::new(storage).conflict(proposal_id)
+}
+
+/// Record the input of the random draw which broke a tie of the tally of a
+/// proposal, for the draw to be audited
+pub fn write_proposal_tie_break<S>(
+    storage: &mut S,
+    proposal_id: u64,
+    seed: TieBreakSeed,
+) -> StorageResult<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    ProposalStorageWriter::new(storage).set_tie_break(proposal_id, seed)
+}
+
+/// Get the input of the random draw which broke a tie of the tally of a
+/// proposal, if its tally landed on a tie broken at random
+pub fn get_proposal_tie_break<S>(
+    storage: &S,
+    proposal_id: u64,
+) -> StorageResult<Option<TieBreakSeed>>
+where
+    S: StorageRead,
+{
+    ProposalStorageReader::new(storage).tie_break(proposal_id)
+}
+
+/// Archive the result of a proposal decided at the given epoch
+pub fn write_proposal_archive<S>(
+    storage: &mut S,
+    proposal_id: u64,
+    epoch: Epoch,
+    proposal_result: ProposalResult,
+) -> StorageResult<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let archive_key =
+        governance_keys::get_proposal_archive_key(epoch, proposal_id);
+    storage.write(
+        &archive_key,
+        ArchivedProposalResult {
+            id: proposal_id,
+            epoch,
+            result: proposal_result,
+        },
+    )
 }
 
 /// Read a proposal by id from storage
@@ -149,35 +394,42 @@ pub fn get_proposal_by_id<S>(
 where
     S: StorageRead,
 {
-    let author_key = governance_keys::get_author_key(id);
-    let content = governance_keys::get_content_key(id);
-    let start_epoch_key = governance_keys::get_voting_start_epoch_key(id);
-    let end_epoch_key = governance_keys::get_voting_end_epoch_key(id);
-    let grace_epoch_key = governance_keys::get_grace_epoch_key(id);
-    let proposal_type_key = governance_keys::get_proposal_type_key(id);
-
-    let author: Option<Address> = storage.read(&author_key)?;
-    let content: Option<BTreeMap<String, String>> = storage.read(&content)?;
-    let voting_start_epoch: Option<Epoch> = storage.read(&start_epoch_key)?;
-    let voting_end_epoch: Option<Epoch> = storage.read(&end_epoch_key)?;
-    let grace_epoch: Option<Epoch> = storage.read(&grace_epoch_key)?;
-    let proposal_type: Option<ProposalType> =
-        storage.read(&proposal_type_key)?;
+    let proposal_store = ProposalStorageReader::new(storage);
+    let author = proposal_store.author(id)?;
+    let content = proposal_store.content(id)?;
+    let voting_start_epoch = proposal_store.voting_start(id)?;
+    let voting_end_epoch = proposal_store.voting_end(id)?;
+    let grace_epoch = proposal_store.grace_epoch(id)?;
+    let voting_heights = proposal_store.voting_heights(id)?;
+    let priority = proposal_store.priority(id)?;
+    let proposal_type = proposal_store.proposal_type(id)?;
 
+    // proposals voted on over block heights have no epochs
+    let epoch = |epoch: Option<Epoch>| {
+        if voting_heights.is_some() {
+            Epoch::default()
+        } else {
+            epoch.unwrap()
+        }
+    };
     let proposal = proposal_type.map(|proposal_type| StorageProposal {
         id,
         content: content.unwrap(),
         author: author.unwrap(),
         r#type: proposal_type,
-        voting_start_epoch: voting_start_epoch.unwrap(),
-        voting_end_epoch: voting_end_epoch.unwrap(),
-        grace_epoch: grace_epoch.unwrap(),
+        voting_start_epoch: epoch(voting_start_epoch),
+        voting_end_epoch: epoch(voting_end_epoch),
+        grace_epoch: epoch(grace_epoch),
+        voting_heights,
+        priority,
     });
 
     Ok(proposal)
 }
 
-/// Query all the votes for a proposal_id
+/// Query all the votes for a proposal_id. The role of each voter is read
+/// from the layout of its vote key, and the votes still stored under the
+/// legacy layout are included.
 pub fn get_proposal_votes<S>(
     storage: &S,
     proposal_id: u64,
@@ -191,31 +443,136 @@ where
 
     let votes = vote_iter
         .filter_map(|vote_result| {
-            if let Ok((vote_key, vote)) = vote_result {
-                let voter_address =
-                    governance_keys::get_voter_address(&vote_key);
-                let delegator_address =
-                    governance_keys::get_vote_delegation_address(&vote_key);
-                match (voter_address, delegator_address) {
-                    (Some(delegator_address), Some(validator_address)) => {
-                        Some(Vote {
-                            validator: validator_address.to_owned(),
-                            delegator: delegator_address.to_owned(),
-                            data: vote,
-                        })
-                    }
-                    _ => None,
-                }
-            } else {
-                None
-            }
+            let (vote_key, vote) = vote_result.ok()?;
+            let parsed =
+                governance_keys::parse_vote_key(&vote_key).or_else(|| {
+                    governance_keys::parse_legacy_vote_key(&vote_key)
+                })?;
+            Some(Vote {
+                validator: parsed.validator.to_owned(),
+                delegator: parsed.voter.to_owned(),
+                data: vote,
+                voter_type: parsed.voter_type,
+            })
         })
         .collect::<Vec<Vote>>();
 
     Ok(votes)
 }
 
-/// Check if an accepted proposal is being executed
+/// Rewrite the votes stored under the legacy vote key layout, which doesn't
+/// record the role of the voter, to the current layout, for the next batch
+/// of at most `batch_size` proposals. The batch starts at the proposal
+/// recorded by the cursor under
+/// [`governance_keys::get_vote_key_migration_cursor_key`], which is moved
+/// past the batch, such that the cost of a migration is bounded and the
+/// same migration run repeatedly covers all the proposals. The proposals
+/// are migrated in order of id and their votes in order of key, so the
+/// migration is deterministic. Meant to be run by the code of a governance
+/// proposal. Returns the number of votes migrated.
+pub fn migrate_vote_keys<S>(
+    storage: &mut S,
+    batch_size: u64,
+) -> StorageResult<u64>
+where
+    S: StorageRead + StorageWrite,
+{
+    let counter: u64 = storage
+        .read(&governance_keys::get_counter_key())?
+        .unwrap_or_default();
+    let cursor_key = governance_keys::get_vote_key_migration_cursor_key();
+    let from: u64 = storage.read(&cursor_key)?.unwrap_or_default();
+    let to = from.saturating_add(batch_size).min(counter);
+    let mut migrated = 0;
+    for proposal_id in from..to {
+        let vote_prefix_key =
+            governance_keys::get_proposal_vote_prefix_key(proposal_id);
+        let legacy_votes = iter_prefix_bytes(storage, &vote_prefix_key)?
+            .filter_map(|result| match result {
+                Ok((key, vote)) => governance_keys::is_legacy_vote_key(&key)
+                    .then_some(Ok((key, vote))),
+                Err(err) => Some(Err(err)),
+            })
+            .collect::<StorageResult<Vec<_>>>()?;
+        for (legacy_key, vote) in legacy_votes {
+            let Some(parsed) =
+                governance_keys::parse_legacy_vote_key(&legacy_key)
+            else {
+                continue;
+            };
+            let vote_key = governance_keys::get_vote_proposal_key(
+                proposal_id,
+                parsed.voter.clone(),
+                parsed.validator.clone(),
+            );
+            storage.write_bytes(&vote_key, vote)?;
+            storage.delete(&legacy_key)?;
+            migrated += 1;
+        }
+    }
+    if to > from {
+        storage.write(&cursor_key, to)?;
+    }
+    Ok(migrated)
+}
+
+/// Check if the given vote key, of either layout, is being migrated, i.e.
+/// if the vote stored under the legacy key is moved unchanged to the
+/// current key, which didn't hold a vote before.
+pub fn is_vote_key_migration<Pre, Post>(
+    pre: &Pre,
+    post: &Post,
+    key: &Key,
+) -> StorageResult<bool>
+where
+    Pre: StorageRead,
+    Post: StorageRead,
+{
+    let Some(parsed) = governance_keys::parse_vote_key(key)
+        .or_else(|| governance_keys::parse_legacy_vote_key(key))
+    else {
+        return Ok(false);
+    };
+    let legacy_key = governance_keys::get_legacy_vote_proposal_key(
+        parsed.proposal_id,
+        parsed.voter.clone(),
+        parsed.validator.clone(),
+    );
+    let vote_key = governance_keys::get_vote_proposal_key(
+        parsed.proposal_id,
+        parsed.voter.clone(),
+        parsed.validator.clone(),
+    );
+    if pre.has_key(&vote_key)? || post.has_key(&legacy_key)? {
+        return Ok(false);
+    }
+    Ok(match pre.read_bytes(&legacy_key)? {
+        Some(vote) => post.read_bytes(&vote_key)? == Some(vote),
+        None => false,
+    })
+}
+
+/// Get the latest vote of the given voter on each proposal, ordered by
+/// proposal id
+pub fn get_votes_by_voter<S>(
+    storage: &S,
+    voter: &Address,
+) -> StorageResult<Vec<VoteReceipt>>
+where
+    S: StorageRead,
+{
+    let index_prefix = governance_keys::get_voter_index_prefix(voter);
+    let mut receipts = iter_prefix::<VoteReceipt>(storage, &index_prefix)?
+        .map(|result| result.map(|(_key, receipt)| receipt))
+        .collect::<StorageResult<Vec<_>>>()?;
+    // Keys are ordered as strings, hence not by numerical proposal id
+    receipts.sort_by_key(|receipt| receipt.proposal_id);
+    Ok(receipts)
+}
+
+/// Check if an accepted proposal is being executed, i.e. if the tx data
+/// holds the id of a proposal marked as pending execution at the current
+/// block height
 pub fn is_proposal_accepted<S>(
     storage: &S,
     tx_data: &[u8],
@@ -223,16 +580,58 @@ pub fn is_proposal_accepted<S>(
 where
     S: StorageRead,
 {
-    let proposal_id = u64::try_from_slice(tx_data);
-    if let Ok(id) = proposal_id {
-        let proposal_execution_key =
-            governance_keys::get_proposal_execution_key(id);
-        storage.has_key(&proposal_execution_key)
-    } else {
-        Ok(false)
+    let Ok(id) = u64::try_from_slice(tx_data) else {
+        return Ok(false);
+    };
+    is_proposal_executing(storage, id)
+}
+
+/// Check if the proposal with the given id is being executed, i.e. if it's
+/// marked as pending execution at the current block height
+pub fn is_proposal_executing<S>(
+    storage: &S,
+    proposal_id: u64,
+) -> StorageResult<bool>
+where
+    S: StorageRead,
+{
+    match read_pending_execution(storage, proposal_id)? {
+        Some(marker) => Ok(marker.proposal_id == proposal_id
+            && marker.height == storage.get_block_height()?),
+        None => Ok(false),
     }
 }
 
+/// Read the pending execution marker of the proposal with the given id
+pub fn read_pending_execution<S>(
+    storage: &S,
+    proposal_id: u64,
+) -> StorageResult<Option<PendingExecution>>
+where
+    S: StorageRead,
+{
+    storage.read(&governance_keys::get_proposal_execution_key(proposal_id))
+}
+
+/// Mark the proposal with the given id as pending execution at the current
+/// block height
+pub fn write_pending_execution<S>(
+    storage: &mut S,
+    proposal_id: u64,
+) -> StorageResult<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let marker = PendingExecution {
+        proposal_id,
+        height: storage.get_block_height()?,
+    };
+    storage.write(
+        &governance_keys::get_proposal_execution_key(proposal_id),
+        marker,
+    )
+}
+
 /// Get the code associated with a proposal
 pub fn get_proposal_code<S>(
     storage: &S,
@@ -241,8 +640,7 @@ pub fn get_proposal_code<S>(
 where
     S: StorageRead,
 {
-    let proposal_code_key = governance_keys::get_proposal_code_key(proposal_id);
-    storage.read_bytes(&proposal_code_key)
+    ProposalStorageReader::new(storage).code(proposal_id)
 }
 
 /// Get the code associated with a proposal
@@ -253,8 +651,19 @@ pub fn get_proposal_author<S>(
 where
     S: StorageRead,
 {
-    let proposal_author_key = governance_keys::get_author_key(proposal_id);
-    storage.read::<Address>(&proposal_author_key)
+    ProposalStorageReader::new(storage).author(proposal_id)
+}
+
+/// Get the governance voting key registered by the given account, if any
+pub fn get_voting_key<S>(
+    storage: &S,
+    owner: &Address,
+) -> StorageResult<Option<common::PublicKey>>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_voting_key_key(owner);
+    storage.read(&key)
 }
 
 /// Get governance parameters
@@ -284,6 +693,28 @@ where
 
     let max_proposal_period: u64 = get_max_proposal_period(storage)?;
 
+    let max_proposal_lifetime: u64 = get_max_proposal_lifetime(storage)?;
+
+    let (base_deposit, deposit_per_byte) = get_deposit_parameters(storage)?;
+
+    let max_voting_power_fraction = get_max_voting_power_fraction(storage)?;
+    let treasury_high_water_mark = get_treasury_high_water_mark(storage)?;
+    let min_proposal_author_stake = get_min_proposal_author_stake(storage)?;
+    let treasury_history_retention = get_treasury_history_retention(storage)?;
+    let treasury_inflation_rate = get_treasury_inflation_rate(storage)?;
+    let (min_proposal_voting_period_blocks, max_proposal_voting_period_blocks) =
+        get_proposal_voting_period_blocks(storage)?;
+    let emergency_validator_quorum = get_emergency_validator_quorum(storage)?;
+    let max_proposals_per_block = get_max_proposals_per_block(storage)?;
+    let parameter_conflict_policy = get_parameter_conflict_policy(storage)?;
+    let strict_proposal_code_validation =
+        get_strict_proposal_code_validation(storage)?;
+    let rejected_refund_ratio = get_rejected_refund_ratio(storage)?;
+    let rejected_refund_ratios = get_rejected_refund_ratios(storage)?;
+    let council = get_council(storage)?;
+    let max_treasury_balance = get_max_treasury_balance(storage)?;
+    let tie_breaks = get_tie_breaks(storage)?;
+
     Ok(GovernanceParameters {
         min_proposal_fund,
         max_proposal_code_size,
@@ -291,29 +722,2045 @@ where
         max_proposal_period,
         max_proposal_content_size,
         min_proposal_grace_epochs,
+        max_proposal_lifetime,
+        base_deposit,
+        deposit_per_byte,
+        max_voting_power_fraction,
+        treasury_high_water_mark,
+        min_proposal_author_stake,
+        treasury_history_retention,
+        treasury_inflation_rate,
+        min_proposal_voting_period_blocks,
+        max_proposal_voting_period_blocks,
+        emergency_validator_quorum,
+        max_proposals_per_block,
+        parameter_conflict_policy,
+        strict_proposal_code_validation,
+        rejected_refund_ratio,
+        rejected_refund_ratios,
+        council,
+        max_treasury_balance,
+        tie_breaks,
     })
 }
 
-/// Get governance "max_proposal_period" parameter
-pub fn get_max_proposal_period<S>(storage: &S) -> StorageResult<u64>
+/// Get the deposit to be locked by a proposal whose content and code add up
+/// to `proposal_size` bytes, according to the current governance parameters
+pub fn get_required_deposit<S>(
+    storage: &S,
+    proposal_size: u64,
+) -> StorageResult<token::Amount>
 where
     S: StorageRead,
 {
-    let key = governance_keys::get_max_proposal_period_key();
-    let max_proposal_period: u64 =
-        storage.read(&key)?.expect("Parameter should be defined.");
-    Ok(max_proposal_period)
+    get_parameters(storage)?
+        .required_deposit(proposal_size)
+        .ok_or(StorageError::new_const(
+            "Overflow while computing the required proposal deposit",
+        ))
 }
 
-/// Get governance proposal result stored in storage if proposal ended
-pub fn get_proposal_result<S>(
+/// Get governance "base_deposit" and "deposit_per_byte" parameters. Chains
+/// that never set them only require the minimum proposal funds.
+pub fn get_deposit_parameters<S>(
     storage: &S,
-    proposal_id: u64,
-) -> StorageResult<Option<ProposalResult>>
+) -> StorageResult<(token::Amount, token::Amount)>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_base_deposit_key();
+    let base_deposit: Option<token::Amount> = storage.read(&key)?;
+    let key = governance_keys::get_deposit_per_byte_key();
+    let deposit_per_byte: Option<token::Amount> = storage.read(&key)?;
+    Ok((
+        base_deposit.unwrap_or_default(),
+        deposit_per_byte.unwrap_or_default(),
+    ))
+}
+
+/// Get governance "max_voting_power_fraction" parameter. Chains that never
+/// set it don't cap the voting power of any voter.
+pub fn get_max_voting_power_fraction<S>(
+    storage: &S,
+) -> StorageResult<Option<Dec>>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_max_voting_power_fraction_key();
+    let max_voting_power_fraction: Option<Option<Dec>> = storage.read(&key)?;
+    Ok(max_voting_power_fraction.flatten())
+}
+
+/// Get governance "treasury_high_water_mark" parameter. Chains that never
+/// set it have no treasury high water mark.
+pub fn get_treasury_high_water_mark<S>(
+    storage: &S,
+) -> StorageResult<Option<token::Amount>>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_treasury_high_water_mark_key();
+    let treasury_high_water_mark: Option<Option<token::Amount>> =
+        storage.read(&key)?;
+    Ok(treasury_high_water_mark.flatten())
+}
+
+/// Get governance "min_proposal_author_stake" parameter. Chains that never
+/// set it don't require any stake from the authors of proposals.
+pub fn get_min_proposal_author_stake<S>(
+    storage: &S,
+) -> StorageResult<token::Amount>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_min_proposal_author_stake_key();
+    let min_proposal_author_stake: Option<token::Amount> =
+        storage.read(&key)?;
+    Ok(min_proposal_author_stake.unwrap_or_default())
+}
+
+/// Get governance "treasury_history_retention" parameter. Chains that
+/// never set it keep the snapshots of the treasury balance for the default
+/// retention.
+pub fn get_treasury_history_retention<S>(storage: &S) -> StorageResult<u64>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_treasury_history_retention_key();
+    let treasury_history_retention: Option<u64> = storage.read(&key)?;
+    Ok(treasury_history_retention.unwrap_or_else(|| {
+        GovernanceParameters::default().treasury_history_retention
+    }))
+}
+
+/// Get governance "treasury_inflation_rate" parameter. Chains that never
+/// set it don't mint anything to the treasury.
+pub fn get_treasury_inflation_rate<S>(storage: &S) -> StorageResult<Dec>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_treasury_inflation_rate_key();
+    let treasury_inflation_rate: Option<Dec> = storage.read(&key)?;
+    Ok(treasury_inflation_rate.unwrap_or_default())
+}
+
+/// Get governance "min_proposal_voting_period_blocks" and
+/// "max_proposal_voting_period_blocks" parameters. Chains that never set
+/// them bound the voting periods in blocks by the default bounds.
+pub fn get_proposal_voting_period_blocks<S>(
+    storage: &S,
+) -> StorageResult<(u64, u64)>
+where
+    S: StorageRead,
+{
+    let defaults = GovernanceParameters::default();
+    let key = governance_keys::get_min_proposal_voting_period_blocks_key();
+    let min_proposal_voting_period_blocks: Option<u64> = storage.read(&key)?;
+    let key = governance_keys::get_max_proposal_voting_period_blocks_key();
+    let max_proposal_voting_period_blocks: Option<u64> = storage.read(&key)?;
+    Ok((
+        min_proposal_voting_period_blocks
+            .unwrap_or(defaults.min_proposal_voting_period_blocks),
+        max_proposal_voting_period_blocks
+            .unwrap_or(defaults.max_proposal_voting_period_blocks),
+    ))
+}
+
+/// Get governance "emergency_validator_quorum" parameter. Chains that never
+/// set it tally emergency proposals by voting power.
+pub fn get_emergency_validator_quorum<S>(
+    storage: &S,
+) -> StorageResult<Option<u64>>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_emergency_validator_quorum_key();
+    let emergency_validator_quorum: Option<Option<u64>> = storage.read(&key)?;
+    Ok(emergency_validator_quorum.flatten())
+}
+
+/// Get governance "max_proposals_per_block" parameter. Chains that never
+/// set it conclude all the due proposals in a single block.
+pub fn get_max_proposals_per_block<S>(storage: &S) -> StorageResult<Option<u64>>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_max_proposals_per_block_key();
+    let max_proposals_per_block: Option<Option<u64>> = storage.read(&key)?;
+    Ok(max_proposals_per_block.flatten())
+}
+
+/// Get governance "parameter_conflict_policy" parameter. Chains that never
+/// set it follow the default policy.
+pub fn get_parameter_conflict_policy<S>(
+    storage: &S,
+) -> StorageResult<ParameterConflictPolicy>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_parameter_conflict_policy_key();
+    let parameter_conflict_policy: Option<ParameterConflictPolicy> =
+        storage.read(&key)?;
+    Ok(parameter_conflict_policy.unwrap_or_default())
+}
+
+/// Get governance "strict_proposal_code_validation" parameter. Chains that
+/// never set it don't validate the code of proposals at submission.
+pub fn get_strict_proposal_code_validation<S>(
+    storage: &S,
+) -> StorageResult<bool>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_strict_proposal_code_validation_key();
+    let strict_proposal_code_validation: Option<bool> = storage.read(&key)?;
+    Ok(strict_proposal_code_validation.unwrap_or_default())
+}
+
+/// Get governance "rejected_refund_ratio" parameter. Chains that never set
+/// it don't refund anything to the authors of rejected proposals.
+pub fn get_rejected_refund_ratio<S>(storage: &S) -> StorageResult<Dec>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_rejected_refund_ratio_key();
+    let rejected_refund_ratio: Option<Dec> = storage.read(&key)?;
+    Ok(rejected_refund_ratio.unwrap_or_default())
+}
+
+/// Get governance "rejected_refund_ratios" parameter. Chains that never set
+/// it have no refund ratio set by proposal type.
+pub fn get_rejected_refund_ratios<S>(
+    storage: &S,
+) -> StorageResult<BTreeMap<String, Dec>>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_rejected_refund_ratios_key();
+    let rejected_refund_ratios: Option<BTreeMap<String, Dec>> =
+        storage.read(&key)?;
+    Ok(rejected_refund_ratios.unwrap_or_default())
+}
+
+/// Get governance "council" parameter. Chains that never set it have no
+/// council.
+pub fn get_council<S>(storage: &S) -> StorageResult<Option<Council>>
 where
     S: StorageRead,
 {
-    let key = governance_keys::get_proposal_result_key(proposal_id);
-    let proposal_result: Option<ProposalResult> = storage.read(&key)?;
-    Ok(proposal_result)
+    let key = governance_keys::get_council_key();
+    let council: Option<Option<Council>> = storage.read(&key)?;
+    Ok(council.flatten())
+}
+
+/// Get governance "max_treasury_balance" parameter. Chains that never set
+/// it have no maximum treasury balance.
+pub fn get_max_treasury_balance<S>(
+    storage: &S,
+) -> StorageResult<Option<token::Amount>>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_max_treasury_balance_key();
+    let max_treasury_balance: Option<Option<token::Amount>> =
+        storage.read(&key)?;
+    Ok(max_treasury_balance.flatten())
+}
+
+/// Get governance "tie_breaks" parameter. Chains that never set it break
+/// ties by the default tie-breaks.
+pub fn get_tie_breaks<S>(storage: &S) -> StorageResult<TieBreaks>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_tie_breaks_key();
+    let tie_breaks: Option<TieBreaks> = storage.read(&key)?;
+    Ok(tie_breaks.unwrap_or_default())
+}
+
+/// Get the fraction of the funds of a rejected proposal of the given type
+/// returned to its author, i.e. the refund ratio set for its type, falling
+/// back to the default refund ratio. The ratio is clamped between zero and
+/// one.
+pub fn get_refund_ratio_by_type<S>(
+    storage: &S,
+    proposal_type: &ProposalType,
+) -> StorageResult<Dec>
+where
+    S: StorageRead,
+{
+    let ratio = match get_rejected_refund_ratios(storage)?
+        .remove(proposal_type.kind())
+    {
+        Some(ratio) => ratio,
+        None => get_rejected_refund_ratio(storage)?,
+    };
+    Ok(ratio.max(Dec::zero()).min(Dec::one()))
+}
+
+/// Get governance "max_proposal_period" parameter
+pub fn get_max_proposal_period<S>(storage: &S) -> StorageResult<u64>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_max_proposal_period_key();
+    let max_proposal_period: u64 =
+        storage.read(&key)?.expect("Parameter should be defined.");
+    Ok(max_proposal_period)
+}
+
+/// Get governance "max_proposal_code_size" parameter
+pub fn get_max_proposal_code_size<S>(storage: &S) -> StorageResult<u64>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_max_proposal_code_size_key();
+    let max_proposal_code_size: u64 =
+        storage.read(&key)?.expect("Parameter should be defined.");
+    Ok(max_proposal_code_size)
+}
+
+/// Get governance "max_proposal_lifetime" parameter. Chains that never set
+/// it expire proposals after the default lifetime.
+pub fn get_max_proposal_lifetime<S>(storage: &S) -> StorageResult<u64>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_max_proposal_lifetime_key();
+    let max_proposal_lifetime: Option<u64> = storage.read(&key)?;
+    Ok(max_proposal_lifetime.unwrap_or_else(|| {
+        GovernanceParameters::default().max_proposal_lifetime
+    }))
+}
+
+/// Get the ids and grace epochs of the proposals that are still waiting for
+/// their grace epoch, but that have been queued for more than
+/// `max_proposal_lifetime` epochs since their voting start epoch.
+pub fn get_expired_proposals<S>(
+    storage: &S,
+    current_epoch: Epoch,
+) -> StorageResult<Vec<(u64, Epoch)>>
+where
+    S: StorageRead,
+{
+    let max_proposal_lifetime = get_max_proposal_lifetime(storage)?;
+    let prefix = governance_keys::get_all_commiting_proposals_prefix();
+    let mut expired = vec![];
+    for entry in iter_prefix_bytes(storage, &prefix)? {
+        let (key, _) = entry?;
+        let (Some(grace_epoch), Some(id)) = (
+            governance_keys::get_commit_proposal_epoch(&key),
+            governance_keys::get_commit_proposal_id(&key),
+        ) else {
+            continue;
+        };
+        // proposals reaching their grace epoch are decided normally
+        if grace_epoch <= current_epoch.0 {
+            continue;
+        }
+        // malformed proposals are rejected once they reach their grace epoch
+        let voting_start =
+            match ProposalStorageReader::new(storage).voting_start(id) {
+                Ok(Some(voting_start)) => voting_start,
+                _ => continue,
+            };
+        if current_epoch.0.saturating_sub(voting_start.0)
+            > max_proposal_lifetime
+        {
+            expired.push((id, Epoch(grace_epoch)));
+        }
+    }
+    Ok(expired)
+}
+
+/// Get the ids of the proposals whose voting period, expressed in block
+/// heights, ends at the given height. These proposals are concluded at that
+/// height.
+pub fn get_proposals_concluding_at_height<S>(
+    storage: &S,
+    height: BlockHeight,
+) -> StorageResult<Vec<u64>>
+where
+    S: StorageRead,
+{
+    let prefix =
+        governance_keys::get_commiting_proposals_at_height_prefix(height.0);
+    let mut ids = vec![];
+    for entry in iter_prefix_bytes(storage, &prefix)? {
+        let (key, _) = entry?;
+        // skip the keys of heights sharing a string prefix with the height,
+        // which is stored in place of the committing epoch
+        if governance_keys::get_commit_proposal_epoch(&key) != Some(height.0) {
+            continue;
+        }
+        if let Some(id) = governance_keys::get_commit_proposal_id(&key) {
+            ids.push(id);
+        }
+    }
+    Ok(ids)
+}
+
+/// Get the ids of the proposals committed to be concluded at the given
+/// epoch, i.e. whose grace epoch it is.
+pub fn get_proposals_committing_at_epoch<S>(
+    storage: &S,
+    epoch: Epoch,
+) -> StorageResult<BTreeSet<u64>>
+where
+    S: StorageRead,
+{
+    let prefix = governance_keys::get_commiting_proposals_prefix(epoch.0);
+    let mut ids = BTreeSet::new();
+    for entry in iter_prefix_bytes(storage, &prefix)? {
+        let (key, _) = entry?;
+        // skip the keys of epochs sharing a string prefix with the epoch
+        if governance_keys::get_commit_proposal_epoch(&key) != Some(epoch.0) {
+            continue;
+        }
+        if let Some(id) = governance_keys::get_commit_proposal_id(&key) {
+            ids.insert(id);
+        }
+    }
+    Ok(ids)
+}
+
+/// Get the last epoch whose committed proposals were concluded, if any
+pub fn get_concluded_epoch<S>(storage: &S) -> StorageResult<Option<Epoch>>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_concluded_epoch_key();
+    storage.read(&key)
+}
+
+/// Write the last epoch whose committed proposals were concluded
+pub fn write_concluded_epoch<S>(
+    storage: &mut S,
+    epoch: Epoch,
+) -> StorageResult<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = governance_keys::get_concluded_epoch_key();
+    storage.write(&key, epoch)
+}
+
+/// Get the ids of the proposals committed to be concluded at or before the
+/// given epoch, but after the last concluded epoch, whose funds haven't been
+/// released yet. These are the proposals due to be concluded at the next
+/// epoch change. Without a concluded epoch in storage, only the proposals
+/// committed at the given epoch are considered.
+pub fn get_unconcluded_proposals<S>(
+    storage: &S,
+    epoch: Epoch,
+) -> StorageResult<BTreeSet<u64>>
+where
+    S: StorageRead,
+{
+    let from_epoch = get_concluded_epoch(storage)?
+        .map(|concluded| concluded.next())
+        .unwrap_or(epoch);
+    let prefix = governance_keys::get_all_commiting_proposals_prefix();
+    let mut ids = BTreeSet::new();
+    for entry in iter_prefix_bytes(storage, &prefix)? {
+        let (key, _) = entry?;
+        let (Some(grace_epoch), Some(id)) = (
+            governance_keys::get_commit_proposal_epoch(&key),
+            governance_keys::get_commit_proposal_id(&key),
+        ) else {
+            continue;
+        };
+        if grace_epoch < from_epoch.0 || grace_epoch > epoch.0 {
+            continue;
+        }
+        if !is_funds_released(storage, id)? {
+            ids.insert(id);
+        }
+    }
+    Ok(ids)
+}
+
+/// Get the ids of the proposals that were due, but deferred to the next
+/// block as the maximum number of proposals concluded per block was reached
+pub fn get_deferred_proposals<S>(storage: &S) -> StorageResult<BTreeSet<u64>>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_deferred_proposals_key();
+    Ok(storage.read(&key)?.unwrap_or_default())
+}
+
+/// Write the ids of the proposals deferred to the next block, removing the
+/// key once no proposal is deferred anymore
+pub fn write_deferred_proposals<S>(
+    storage: &mut S,
+    ids: &BTreeSet<u64>,
+) -> StorageResult<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = governance_keys::get_deferred_proposals_key();
+    if ids.is_empty() {
+        storage.delete(&key)
+    } else {
+        storage.write(&key, ids)
+    }
+}
+
+/// Get the ids of the proposals concluded in the given epoch that set a
+/// protocol parameter, keyed by the storage key of the parameter
+pub fn get_parameter_changes<S>(
+    storage: &S,
+    epoch: Epoch,
+) -> StorageResult<BTreeMap<String, u64>>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_parameter_changes_key();
+    let changes: Option<(Epoch, BTreeMap<String, u64>)> = storage.read(&key)?;
+    Ok(match changes {
+        Some((changes_epoch, changes)) if changes_epoch == epoch => changes,
+        _ => BTreeMap::new(),
+    })
+}
+
+/// Record that the proposal with the given id, concluded in the given
+/// epoch, set the protocol parameter with the given storage key. The changes
+/// recorded in previous epochs are dropped.
+pub fn write_parameter_change<S>(
+    storage: &mut S,
+    epoch: Epoch,
+    parameter_key: &Key,
+    proposal_id: u64,
+) -> StorageResult<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let mut changes = get_parameter_changes(storage, epoch)?;
+    changes.insert(parameter_key.to_string(), proposal_id);
+    let key = governance_keys::get_parameter_changes_key();
+    storage.write(&key, (epoch, changes))
+}
+
+/// Remove an expired proposal from the queue of the proposals to be
+/// executed at its grace epoch.
+pub fn dequeue_expired_proposal<S>(
+    storage: &mut S,
+    proposal_id: u64,
+    grace_epoch: Epoch,
+) -> StorageResult<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = governance_keys::get_committing_proposals_key(
+        proposal_id,
+        grace_epoch.0,
+    );
+    storage.delete(&key)
+}
+
+/// Get governance proposal result stored in storage if proposal ended
+pub fn get_proposal_result<S>(
+    storage: &S,
+    proposal_id: u64,
+) -> StorageResult<Option<ProposalResult>>
+where
+    S: StorageRead,
+{
+    ProposalStorageReader::new(storage).result(proposal_id)
+}
+
+/// Write the outcome of the execution of the code of a proposal
+pub fn write_proposal_execution_result<S>(
+    storage: &mut S,
+    proposal_id: u64,
+    execution_result: &ProposalExecutionResult,
+) -> StorageResult<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = governance_keys::get_proposal_execution_result_key(proposal_id);
+    storage.write(&key, execution_result)
+}
+
+/// Get the outcome of the execution of the code of a proposal, if its code
+/// was executed
+pub fn get_proposal_execution_result<S>(
+    storage: &S,
+    proposal_id: u64,
+) -> StorageResult<Option<ProposalExecutionResult>>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_proposal_execution_result_key(proposal_id);
+    storage.read(&key)
+}
+
+/// Write the structured result of the code of a proposal, to be reported in
+/// the event of the proposal. Meant to be called by the code of the proposal
+/// with the given id, which gets it as tx data, while being executed.
+pub fn write_proposal_output<S>(
+    storage: &mut S,
+    proposal_id: u64,
+    output: &ProposalOutput,
+) -> StorageResult<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = governance_keys::get_proposal_output_key(proposal_id);
+    storage.write(&key, output)
+}
+
+/// Read and delete the structured result written by the code of a proposal,
+/// if any. An output which can't be decoded is deleted all the same.
+pub fn take_proposal_output<S>(
+    storage: &mut S,
+    proposal_id: u64,
+) -> StorageResult<Option<ProposalOutput>>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = governance_keys::get_proposal_output_key(proposal_id);
+    let output = storage.read(&key);
+    if storage.has_key(&key)? {
+        storage.delete(&key)?;
+    }
+    output
+}
+
+/// Mark the funds of a proposal as released, i.e. returned to its author,
+/// burned or moved to the treasury, such that a replay of its conclusion
+/// doesn't move them again. Where the funds went is recorded along.
+pub fn write_funds_released<S>(
+    storage: &mut S,
+    proposal_id: u64,
+    released: ReleasedFunds,
+) -> StorageResult<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    ProposalStorageWriter::new(storage)
+        .set_funds_released(proposal_id, released)
+}
+
+/// Get where the funds of a proposal went, if they were already released
+pub fn get_released_funds<S>(
+    storage: &S,
+    proposal_id: u64,
+) -> StorageResult<Option<ReleasedFunds>>
+where
+    S: StorageRead,
+{
+    ProposalStorageReader::new(storage).funds_released(proposal_id)
+}
+
+/// Check if the funds of a proposal were already released
+pub fn is_funds_released<S>(
+    storage: &S,
+    proposal_id: u64,
+) -> StorageResult<bool>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_funds_released_key(proposal_id);
+    storage.has_key(&key)
+}
+
+/// Get the archived result of a proposal decided at the given epoch
+pub fn get_proposal_archive<S>(
+    storage: &S,
+    epoch: Epoch,
+    proposal_id: u64,
+) -> StorageResult<Option<ArchivedProposalResult>>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_proposal_archive_key(epoch, proposal_id);
+    storage.read(&key)
+}
+
+/// Get all the proposal results archived at the given epoch
+pub fn get_proposal_archives_at_epoch<S>(
+    storage: &S,
+    epoch: Epoch,
+) -> StorageResult<Vec<ArchivedProposalResult>>
+where
+    S: StorageRead,
+{
+    let prefix = governance_keys::get_proposal_archive_prefix(epoch);
+    iter_prefix::<ArchivedProposalResult>(storage, &prefix)?
+        .map(|entry| entry.map(|(_key, archive)| archive))
+        .collect()
+}
+
+/// Get the ids and outcomes of the proposals decided in the transition to
+/// the given epoch, ordered by proposal id. Only the archive is read, such
+/// that the proposals are reported after their live keys are pruned.
+pub fn get_proposals_decided_in_epoch<S>(
+    storage: &S,
+    epoch: Epoch,
+) -> StorageResult<Vec<(u64, TallyResult)>>
+where
+    S: StorageRead,
+{
+    let mut decided: Vec<(u64, TallyResult)> =
+        get_proposal_archives_at_epoch(storage, epoch)?
+            .into_iter()
+            .map(|archive| (archive.id, archive.result.result))
+            .collect();
+    // the archive keys are ordered by the string representation of the ids
+    decided.sort_unstable_by_key(|(id, _)| *id);
+    Ok(decided)
+}
+
+/// Mint the treasury inflation of the given epoch, a
+/// `treasury_inflation_rate` fraction of the total native token supply, to
+/// the treasury held by the PGF account, and record the minted amount in the
+/// treasury history.
+/// Returns the minted amount and the new total supply, or `None` if nothing
+/// was minted, in which case storage is left untouched.
+pub fn mint_treasury_inflation<S>(
+    storage: &mut S,
+    epoch: Epoch,
+) -> StorageResult<Option<(token::Amount, token::Amount)>>
+where
+    S: StorageRead + StorageWrite,
+{
+    let rate = get_treasury_inflation_rate(storage)?;
+    if rate.is_zero() {
+        return Ok(None);
+    }
+    if rate.is_negative() {
+        tracing::warn!(
+            "The treasury inflation rate {rate} is negative, no inflation is \
+             minted to the treasury."
+        );
+        return Ok(None);
+    }
+    let native_token = storage.get_native_token()?;
+    let total_supply = token::read_total_supply(storage, &native_token)?;
+    let minted = rate * total_supply;
+    if minted.is_zero() {
+        return Ok(None);
+    }
+
+    // crediting the treasury increases the total supply along with its
+    // balance
+    token::credit_tokens(storage, &native_token, &pgf_address, minted)?;
+    storage
+        .write(&governance_keys::get_treasury_inflation_key(epoch), minted)?;
+    let total_supply = token::read_total_supply(storage, &native_token)?;
+    Ok(Some((minted, total_supply)))
+}
+
+/// Get the amount minted to the treasury by inflation at the given epoch,
+/// if any and if it is still within the retention window
+pub fn get_treasury_inflation_at<S>(
+    storage: &S,
+    epoch: Epoch,
+) -> StorageResult<Option<token::Amount>>
+where
+    S: StorageRead,
+{
+    storage.read(&governance_keys::get_treasury_inflation_key(epoch))
+}
+
+/// Snapshot the native token balance of the treasury at the given epoch,
+/// and prune the snapshots and the records of the treasury inflation that
+/// fell out of the retention window set by the `treasury_history_retention`
+/// parameter
+pub fn snapshot_treasury_balance<S>(
+    storage: &mut S,
+    epoch: Epoch,
+) -> StorageResult<token::Amount>
+where
+    S: StorageRead + StorageWrite,
+{
+    let native_token = storage.get_native_token()?;
+    let balance = token::read_balance(storage, &native_token, &pgf_address)?;
+    storage
+        .write(&governance_keys::get_treasury_balance_key(epoch), balance)?;
+
+    let retention = get_treasury_history_retention(storage)?;
+    let oldest_kept = Epoch(epoch.0.saturating_sub(retention));
+    prune_epoched_history(
+        storage,
+        &governance_keys::get_treasury_balance_prefix(),
+        governance_keys::is_treasury_balance_key,
+        oldest_kept,
+    )?;
+    prune_epoched_history(
+        storage,
+        &governance_keys::get_treasury_inflation_prefix(),
+        governance_keys::is_treasury_inflation_key,
+        oldest_kept,
+    )?;
+    Ok(balance)
+}
+
+/// Delete the entries of the history under `prefix` recorded before the
+/// `oldest_kept` epoch, as parsed by `entry_epoch`
+fn prune_epoched_history<S>(
+    storage: &mut S,
+    prefix: &Key,
+    entry_epoch: fn(&Key) -> Option<Epoch>,
+    oldest_kept: Epoch,
+) -> StorageResult<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let pruned: Vec<_> = iter_prefix_bytes(storage, prefix)?
+        .filter_map(|entry| {
+            entry
+                .map(|(key, _)| {
+                    entry_epoch(&key)
+                        .filter(|entry_epoch| *entry_epoch < oldest_kept)
+                        .map(|_| key)
+                })
+                .transpose()
+        })
+        .collect::<StorageResult<_>>()?;
+    for key in pruned {
+        storage.delete(&key)?;
+    }
+    Ok(())
+}
+
+/// Get the snapshot of the treasury balance taken at the given epoch, if
+/// it is still within the retention window
+pub fn get_treasury_balance_at<S>(
+    storage: &S,
+    epoch: Epoch,
+) -> StorageResult<Option<token::Amount>>
+where
+    S: StorageRead,
+{
+    storage.read(&governance_keys::get_treasury_balance_key(epoch))
+}
+
+/// Get the snapshots of the treasury balance taken between the epochs
+/// `from` and `to`, both inclusive, ordered by epoch
+pub fn get_treasury_balance_history<S>(
+    storage: &S,
+    from: Epoch,
+    to: Epoch,
+) -> StorageResult<Vec<(Epoch, token::Amount)>>
+where
+    S: StorageRead,
+{
+    let prefix = governance_keys::get_treasury_balance_prefix();
+    iter_prefix::<token::Amount>(storage, &prefix)?
+        .filter_map(|entry| {
+            entry
+                .map(|(key, balance)| {
+                    governance_keys::is_treasury_balance_key(&key)
+                        .filter(|epoch| (from..=to).contains(epoch))
+                        .map(|epoch| (epoch, balance))
+                })
+                .transpose()
+        })
+        .collect()
+}
+
+/// Record the participation of the validators in a proposal concluded at
+/// the given epoch, and prune the participation recorded before the
+/// retention window set by the `treasury_history_retention` parameter
+pub fn write_participation<S>(
+    storage: &mut S,
+    epoch: Epoch,
+    proposal_id: u64,
+    participation: BTreeMap<Address, ValidatorParticipation>,
+) -> StorageResult<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    record_participation(
+        storage,
+        &governance_keys::get_participation_prefix(),
+        governance_keys::get_participation_key,
+        governance_keys::is_participation_key,
+        epoch,
+        |epoch_participation: &mut EpochParticipation| {
+            epoch_participation.insert(proposal_id, participation);
+        },
+    )
+}
+
+/// Record that the signature of a validator was included in the proof of
+/// the validator set update signed at the given epoch, and prune the
+/// signatures recorded before the retention window set by the
+/// `treasury_history_retention` parameter
+pub fn write_valset_sig_participation<S>(
+    storage: &mut S,
+    epoch: Epoch,
+    validator: Address,
+) -> StorageResult<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    record_participation(
+        storage,
+        &governance_keys::get_valset_sig_participation_prefix(),
+        governance_keys::get_valset_sig_participation_key,
+        governance_keys::is_valset_sig_participation_key,
+        epoch,
+        |signers: &mut ValsetSigParticipation| {
+            signers.insert(validator);
+        },
+    )
+}
+
+/// Apply `update` to the participation recorded at the given epoch under
+/// `prefix`, and prune the participation recorded before the retention
+/// window
+fn record_participation<S, T>(
+    storage: &mut S,
+    prefix: &Key,
+    entry_key: fn(Epoch) -> Key,
+    entry_epoch: fn(&Key) -> Option<Epoch>,
+    epoch: Epoch,
+    update: impl FnOnce(&mut T),
+) -> StorageResult<()>
+where
+    S: StorageRead + StorageWrite,
+    T: BorshSerialize + BorshDeserialize + Default,
+{
+    let key = entry_key(epoch);
+    let mut participation: T = storage.read(&key)?.unwrap_or_default();
+    update(&mut participation);
+    storage.write(&key, participation)?;
+
+    let retention = get_treasury_history_retention(storage)?;
+    prune_epoched_history(
+        storage,
+        prefix,
+        entry_epoch,
+        Epoch(epoch.0.saturating_sub(retention)),
+    )
+}
+
+/// Get the participation of the validators in the proposals concluded at
+/// the given epoch, if it is still within the retention window
+pub fn get_participation_at<S>(
+    storage: &S,
+    epoch: Epoch,
+) -> StorageResult<Option<EpochParticipation>>
+where
+    S: StorageRead,
+{
+    storage.read(&governance_keys::get_participation_key(epoch))
+}
+
+/// Get the validators whose signatures were included in the proof of the
+/// validator set update signed at the given epoch, if it is still within
+/// the retention window
+pub fn get_valset_sig_participation_at<S>(
+    storage: &S,
+    epoch: Epoch,
+) -> StorageResult<Option<ValsetSigParticipation>>
+where
+    S: StorageRead,
+{
+    storage.read(&governance_keys::get_valset_sig_participation_key(epoch))
+}
+
+/// Get the participation of a validator in the proposals concluded at the
+/// last `last_n` epochs with some recorded participation, or at all the
+/// epochs within the retention window if `None`, ordered by epoch and by
+/// proposal id
+pub fn get_validator_participation<S>(
+    storage: &S,
+    validator: &Address,
+    last_n: Option<u64>,
+) -> StorageResult<Vec<(Epoch, u64, ValidatorParticipation)>>
+where
+    S: StorageRead,
+{
+    let prefix = governance_keys::get_participation_prefix();
+    let mut epochs: Vec<(Epoch, EpochParticipation)> =
+        iter_prefix::<EpochParticipation>(storage, &prefix)?
+            .filter_map(|entry| {
+                entry
+                    .map(|(key, participation)| {
+                        governance_keys::is_participation_key(&key)
+                            .map(|epoch| (epoch, participation))
+                    })
+                    .transpose()
+            })
+            .collect::<StorageResult<_>>()?;
+    epochs.sort_unstable_by_key(|(epoch, _)| *epoch);
+    let skipped = match last_n {
+        Some(last_n) => epochs.len().saturating_sub(last_n as usize),
+        None => 0,
+    };
+
+    Ok(epochs
+        .into_iter()
+        .skip(skipped)
+        .flat_map(|(epoch, participation)| {
+            participation.into_iter().filter_map(
+                move |(proposal_id, validators)| {
+                    validators.get(validator).map(|participation| {
+                        (epoch, proposal_id, *participation)
+                    })
+                },
+            )
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use namada_core::borsh::BorshSerializeExt;
+    use namada_core::types::address::testing::{
+        established_address_1, established_address_2, established_address_3,
+        gen_established_address,
+    };
+    use namada_core::types::key::testing::{keypair_1, keypair_3};
+    use namada_core::types::key::RefTo;
+    use namada_core::types::token;
+    use namada_state::testing::TestWlStorage;
+
+    use super::*;
+    use crate::storage::proposal::VotingHeights;
+    use crate::utils::{ProposalVotes, TallyResult, TallyType};
+
+    /// Test that an archived proposal result outlives the live proposal
+    /// keys it was computed from
+    #[test]
+    fn test_proposal_archive_outlives_proposal_keys() {
+        let mut storage = TestWlStorage::default();
+        let proposal_id = 0;
+        let epoch = Epoch(3);
+        let proposal_result = ProposalResult {
+            result: TallyResult::Passed,
+            tally_type: TallyType::TwoThirds,
+            total_voting_power: token::Amount::native_whole(100),
+            total_yay_power: token::Amount::native_whole(70),
+            total_nay_power: token::Amount::native_whole(10),
+            total_abstain_power: token::Amount::zero(),
+        };
+
+        write_proposal_result(&mut storage, proposal_id, proposal_result)
+            .unwrap();
+        write_proposal_archive(
+            &mut storage,
+            proposal_id,
+            epoch,
+            proposal_result,
+        )
+        .unwrap();
+
+        // prune the live proposal data
+        let proposal_key = governance_keys::proposal_prefix()
+            .push(&proposal_id.to_string())
+            .unwrap();
+        storage.delete_prefix(&proposal_key).unwrap();
+        assert!(
+            get_proposal_result(&storage, proposal_id)
+                .unwrap()
+                .is_none()
+        );
+
+        let archive = get_proposal_archive(&storage, epoch, proposal_id)
+            .unwrap()
+            .expect("The archived result should still be in storage");
+        assert_eq!(archive.id, proposal_id);
+        assert_eq!(archive.epoch, epoch);
+        assert!(matches!(archive.result.result, TallyResult::Passed));
+        assert_eq!(
+            archive.result.total_yay_power,
+            token::Amount::native_whole(70)
+        );
+
+        assert!(
+            get_proposal_archive(&storage, epoch.next(), proposal_id)
+                .unwrap()
+                .is_none()
+        );
+        let archives = get_proposal_archives_at_epoch(&storage, epoch).unwrap();
+        assert_eq!(archives.len(), 1);
+    }
+
+    /// Test that the default treasury history retention applies on chains
+    /// that never set one
+    #[test]
+    fn test_unset_treasury_history_retention() {
+        let mut storage = TestWlStorage::default();
+        assert_eq!(
+            get_treasury_history_retention(&storage).unwrap(),
+            GovernanceParameters::default().treasury_history_retention
+        );
+
+        storage
+            .write(
+                &governance_keys::get_treasury_history_retention_key(),
+                3_u64,
+            )
+            .unwrap();
+        assert_eq!(get_treasury_history_retention(&storage).unwrap(), 3);
+    }
+
+    /// Test that nothing is minted to the treasury on chains that never set
+    /// a treasury inflation rate
+    #[test]
+    fn test_unset_treasury_inflation_rate() {
+        let mut storage = TestWlStorage::default();
+        assert_eq!(get_treasury_inflation_rate(&storage).unwrap(), Dec::zero());
+
+        let rate = Dec::new(1, 2).unwrap();
+        storage
+            .write(&governance_keys::get_treasury_inflation_rate_key(), rate)
+            .unwrap();
+        assert_eq!(get_treasury_inflation_rate(&storage).unwrap(), rate);
+    }
+
+    /// Test that the tie-break and the conflict of a proposal are recorded
+    /// along with its result, without changing the layout of the result
+    /// stored by earlier versions
+    #[test]
+    fn test_proposal_result_records() {
+        let mut storage = TestWlStorage::default();
+        let proposal_id = 0;
+        let proposal_result = ProposalResult {
+            result: TallyResult::Rejected,
+            tally_type: TallyType::OneHalfOverOneThird,
+            total_voting_power: token::Amount::native_whole(100),
+            total_yay_power: token::Amount::native_whole(20),
+            total_nay_power: token::Amount::native_whole(20),
+            total_abstain_power: token::Amount::zero(),
+        };
+        let seed = TieBreakSeed {
+            block_hash: Hash::sha256(b"block"),
+            proposal_id,
+        };
+
+        write_proposal_result(&mut storage, proposal_id, proposal_result)
+            .unwrap();
+        assert_eq!(
+            get_proposal_tie_break(&storage, proposal_id).unwrap(),
+            None
+        );
+        assert_eq!(get_proposal_conflict(&storage, proposal_id).unwrap(), None);
+
+        write_proposal_tie_break(&mut storage, proposal_id, seed).unwrap();
+        write_proposal_conflict(&mut storage, proposal_id, 3).unwrap();
+        assert_eq!(
+            get_proposal_tie_break(&storage, proposal_id).unwrap(),
+            Some(seed)
+        );
+        assert_eq!(
+            get_proposal_conflict(&storage, proposal_id).unwrap(),
+            Some(3)
+        );
+        assert_eq!(
+            storage
+                .read_bytes(&governance_keys::get_proposal_result_key(
+                    proposal_id
+                ))
+                .unwrap(),
+            Some(proposal_result.serialize_to_vec())
+        );
+        assert_eq!(
+            get_proposal_result(&storage, proposal_id).unwrap(),
+            Some(proposal_result)
+        );
+    }
+
+    /// Test that the proposals decided in an epoch are listed in id order
+    /// from the archive, separately from those of other epochs
+    #[test]
+    fn test_proposals_decided_in_epoch() {
+        let mut storage = TestWlStorage::default();
+        let archive = |storage: &mut TestWlStorage,
+                       id: u64,
+                       epoch: Epoch,
+                       result: TallyResult| {
+            let proposal_result = ProposalResult {
+                result,
+                tally_type: TallyType::TwoThirds,
+                total_voting_power: token::Amount::from(100),
+                total_yay_power: token::Amount::zero(),
+                total_nay_power: token::Amount::zero(),
+                total_abstain_power: token::Amount::zero(),
+            };
+            write_proposal_archive(storage, id, epoch, proposal_result)
+                .unwrap();
+        };
+        archive(&mut storage, 10, Epoch(3), TallyResult::Rejected);
+        archive(&mut storage, 2, Epoch(3), TallyResult::Passed);
+        archive(&mut storage, 11, Epoch(4), TallyResult::Passed);
+
+        assert_eq!(
+            get_proposals_decided_in_epoch(&storage, Epoch(3)).unwrap(),
+            vec![(2, TallyResult::Passed), (10, TallyResult::Rejected)]
+        );
+        assert_eq!(
+            get_proposals_decided_in_epoch(&storage, Epoch(4)).unwrap(),
+            vec![(11, TallyResult::Passed)]
+        );
+        assert!(
+            get_proposals_decided_in_epoch(&storage, Epoch(5))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    /// Test that the treasury has no maximum balance on chains that never
+    /// set one
+    #[test]
+    fn test_unset_max_treasury_balance() {
+        let mut storage = TestWlStorage::default();
+        assert_eq!(get_max_treasury_balance(&storage).unwrap(), None);
+
+        let max = token::Amount::native_whole(1_000);
+        storage
+            .write(&governance_keys::get_max_treasury_balance_key(), Some(max))
+            .unwrap();
+        assert_eq!(get_max_treasury_balance(&storage).unwrap(), Some(max));
+    }
+
+    /// Test that the parameters added since the genesis of older chains read
+    /// as their defaults while unset, such that the governance parameters of
+    /// a chain upgraded without writing them can still be read
+    #[test]
+    fn test_unset_parameters() {
+        let mut storage = TestWlStorage::default();
+        let defaults = GovernanceParameters::default();
+        assert_eq!(get_max_proposal_lifetime(&storage).unwrap(), 100);
+        assert_eq!(
+            get_deposit_parameters(&storage).unwrap(),
+            (token::Amount::zero(), token::Amount::zero())
+        );
+        assert_eq!(get_max_voting_power_fraction(&storage).unwrap(), None);
+        assert_eq!(get_treasury_high_water_mark(&storage).unwrap(), None);
+        assert_eq!(
+            get_min_proposal_author_stake(&storage).unwrap(),
+            token::Amount::zero()
+        );
+        assert_eq!(
+            get_proposal_voting_period_blocks(&storage).unwrap(),
+            (
+                defaults.min_proposal_voting_period_blocks,
+                defaults.max_proposal_voting_period_blocks
+            )
+        );
+        assert_eq!(get_emergency_validator_quorum(&storage).unwrap(), None);
+        assert_eq!(get_max_proposals_per_block(&storage).unwrap(), None);
+        assert_eq!(
+            get_parameter_conflict_policy(&storage).unwrap(),
+            ParameterConflictPolicy::ApplyInOrder
+        );
+        assert!(!get_strict_proposal_code_validation(&storage).unwrap());
+        assert_eq!(get_rejected_refund_ratio(&storage).unwrap(), Dec::zero());
+        assert!(get_rejected_refund_ratios(&storage).unwrap().is_empty());
+
+        // Only the parameters written at genesis by older versions
+        storage
+            .write(
+                &governance_keys::get_min_proposal_fund_key(),
+                defaults.min_proposal_fund,
+            )
+            .unwrap();
+        storage
+            .write(
+                &governance_keys::get_max_proposal_code_size_key(),
+                defaults.max_proposal_code_size,
+            )
+            .unwrap();
+        storage
+            .write(
+                &governance_keys::get_min_proposal_voting_period_key(),
+                defaults.min_proposal_voting_period,
+            )
+            .unwrap();
+        storage
+            .write(
+                &governance_keys::get_max_proposal_period_key(),
+                defaults.max_proposal_period,
+            )
+            .unwrap();
+        storage
+            .write(
+                &governance_keys::get_max_proposal_content_key(),
+                defaults.max_proposal_content_size,
+            )
+            .unwrap();
+        storage
+            .write(
+                &governance_keys::get_min_proposal_grace_epoch_key(),
+                defaults.min_proposal_grace_epochs,
+            )
+            .unwrap();
+        assert_eq!(get_parameters(&storage).unwrap(), defaults);
+        assert_eq!(
+            get_required_deposit(&storage, 1_000).unwrap(),
+            defaults.min_proposal_fund
+        );
+    }
+
+    /// Test that the default tie-breaks apply on chains that never set any
+    #[test]
+    fn test_unset_tie_breaks() {
+        let storage = TestWlStorage::default();
+        assert_eq!(get_tie_breaks(&storage).unwrap(), TieBreaks::default());
+    }
+
+    /// Test that the treasury balance snapshots follow the transfers made
+    /// over several epochs, and that the snapshots older than the retention
+    /// window are pruned
+    #[test]
+    fn test_treasury_balance_history() {
+        let mut storage = TestWlStorage::default();
+        GovernanceParameters {
+            treasury_history_retention: 3,
+            ..Default::default()
+        }
+        .init_storage(&mut storage)
+        .unwrap();
+        let native_token = storage.get_native_token().unwrap();
+        let payee = established_address_1();
+        // the funds locked by proposals aren't part of the treasury
+        namada_trans_token::credit_tokens(
+            &mut storage,
+            &native_token,
+            &governance_address,
+            token::Amount::native_whole(7),
+        )
+        .unwrap();
+
+        // credit the treasury with 100 tokens at even epochs, and pay out
+        // 30 tokens at odd epochs
+        let mut expected = vec![];
+        let mut balance = token::Amount::zero();
+        for epoch in (0..8).map(Epoch) {
+            if epoch.0 % 2 == 0 {
+                let amount = token::Amount::native_whole(100);
+                namada_trans_token::credit_tokens(
+                    &mut storage,
+                    &native_token,
+                    &pgf_address,
+                    amount,
+                )
+                .unwrap();
+                balance += amount;
+            } else {
+                let amount = token::Amount::native_whole(30);
+                namada_trans_token::transfer(
+                    &mut storage,
+                    &native_token,
+                    &pgf_address,
+                    &payee,
+                    amount,
+                )
+                .unwrap();
+                balance -= amount;
+            }
+            let snapshot =
+                snapshot_treasury_balance(&mut storage, epoch).unwrap();
+            assert_eq!(snapshot, balance);
+            expected.push((epoch, balance));
+        }
+
+        // only the snapshots of the last 3 epochs and of the current one
+        // are kept
+        let history =
+            get_treasury_balance_history(&storage, Epoch(0), Epoch(7)).unwrap();
+        assert_eq!(history, expected[4..].to_vec());
+        assert_eq!(
+            history.last().unwrap().1,
+            token::Amount::native_whole(4 * 100 - 4 * 30)
+        );
+        assert!(
+            get_treasury_balance_at(&storage, Epoch(3))
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(
+            get_treasury_balance_at(&storage, Epoch(5)).unwrap(),
+            Some(expected[5].1)
+        );
+        assert_eq!(
+            get_treasury_balance_history(&storage, Epoch(5), Epoch(6)).unwrap(),
+            expected[5..7].to_vec()
+        );
+    }
+
+    /// Test that the participation of a validator voting on one of two
+    /// proposals concluded in an epoch is recorded, along with the voting
+    /// power of the delegators that overrode its vote, and that the
+    /// participation older than the retention window is pruned
+    #[test]
+    fn test_validator_participation() {
+        let mut storage = TestWlStorage::default();
+        GovernanceParameters {
+            treasury_history_retention: 2,
+            ..Default::default()
+        }
+        .init_storage(&mut storage)
+        .unwrap();
+        let voter = established_address_1();
+        let absent = established_address_2();
+        let delegator = established_address_3();
+        let validators = || [voter.clone(), absent.clone()];
+
+        let mut votes = ProposalVotes::default();
+        votes.add_validator(
+            &voter,
+            token::Amount::native_whole(100),
+            ProposalVote::Yay.into(),
+        );
+        votes.add_delegator(
+            &delegator,
+            &voter,
+            token::Amount::native_whole(20),
+            ProposalVote::Nay.into(),
+        );
+        write_participation(
+            &mut storage,
+            Epoch(4),
+            0,
+            votes.validators_participation(validators()),
+        )
+        .unwrap();
+        write_participation(
+            &mut storage,
+            Epoch(4),
+            1,
+            ProposalVotes::default().validators_participation(validators()),
+        )
+        .unwrap();
+
+        let voted = ValidatorParticipation {
+            voted: true,
+            overridden_power: token::Amount::native_whole(20),
+        };
+        let epoch_participation =
+            get_participation_at(&storage, Epoch(4)).unwrap().unwrap();
+        assert_eq!(epoch_participation.len(), 2);
+        assert_eq!(epoch_participation[&0][&voter], voted);
+        assert_eq!(
+            epoch_participation[&0][&absent],
+            ValidatorParticipation::default()
+        );
+        assert_eq!(
+            get_validator_participation(&storage, &voter, None).unwrap(),
+            vec![
+                (Epoch(4), 0, voted),
+                (Epoch(4), 1, ValidatorParticipation::default()),
+            ]
+        );
+
+        // the participation of epoch 4 falls out of the retention window
+        for epoch in [5, 7] {
+            write_participation(
+                &mut storage,
+                Epoch(epoch),
+                epoch,
+                votes.validators_participation(validators()),
+            )
+            .unwrap();
+        }
+        assert!(get_participation_at(&storage, Epoch(4)).unwrap().is_none());
+        assert_eq!(
+            get_validator_participation(&storage, &voter, None).unwrap(),
+            vec![(Epoch(5), 5, voted), (Epoch(7), 7, voted)]
+        );
+        assert_eq!(
+            get_validator_participation(&storage, &voter, Some(1)).unwrap(),
+            vec![(Epoch(7), 7, voted)]
+        );
+    }
+
+    /// Test that the treasury inflation compounds over epochs, that the
+    /// minted tokens are added to the total supply, which matches the
+    /// balances of all the holders, and that the minted amounts are recorded
+    /// in the treasury history
+    #[test]
+    fn test_treasury_inflation() {
+        let mut storage = TestWlStorage::default();
+        let rate = Dec::new(5, 2).unwrap();
+        GovernanceParameters {
+            treasury_history_retention: 3,
+            treasury_inflation_rate: rate,
+            ..Default::default()
+        }
+        .init_storage(&mut storage)
+        .unwrap();
+        let native_token = storage.get_native_token().unwrap();
+        let holder = established_address_1();
+        namada_trans_token::credit_tokens(
+            &mut storage,
+            &native_token,
+            &holder,
+            token::Amount::native_whole(1_000_000),
+        )
+        .unwrap();
+
+        let mut supply = token::Amount::native_whole(1_000_000);
+        let mut prev_minted = token::Amount::zero();
+        for epoch in (0..6).map(Epoch) {
+            let (minted, total_supply) =
+                mint_treasury_inflation(&mut storage, epoch)
+                    .unwrap()
+                    .unwrap();
+            // the inflation is computed over the supply that includes the
+            // inflation of the previous epochs
+            assert_eq!(minted, rate * supply);
+            assert!(minted > prev_minted);
+            supply += minted;
+            prev_minted = minted;
+            assert_eq!(total_supply, supply);
+            assert_eq!(
+                namada_trans_token::read_total_supply(&storage, &native_token)
+                    .unwrap(),
+                namada_trans_token::read_balance(
+                    &storage,
+                    &native_token,
+                    &holder
+                )
+                .unwrap()
+                    + namada_trans_token::read_balance(
+                        &storage,
+                        &native_token,
+                        &pgf_address
+                    )
+                    .unwrap()
+            );
+            assert_eq!(
+                get_treasury_inflation_at(&storage, epoch).unwrap(),
+                Some(minted)
+            );
+            snapshot_treasury_balance(&mut storage, epoch).unwrap();
+        }
+        assert_eq!(
+            namada_trans_token::read_balance(
+                &storage,
+                &native_token,
+                &pgf_address
+            )
+            .unwrap(),
+            supply - token::Amount::native_whole(1_000_000)
+        );
+        // the records older than the retention window are pruned along with
+        // the balance snapshots
+        assert!(
+            get_treasury_inflation_at(&storage, Epoch(1))
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            get_treasury_inflation_at(&storage, Epoch(2))
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    /// Test that a zero treasury inflation rate leaves storage untouched
+    #[test]
+    fn test_zero_treasury_inflation_is_noop() {
+        let mut storage = TestWlStorage::default();
+        GovernanceParameters::default()
+            .init_storage(&mut storage)
+            .unwrap();
+        let native_token = storage.get_native_token().unwrap();
+        namada_trans_token::credit_tokens(
+            &mut storage,
+            &native_token,
+            &established_address_1(),
+            token::Amount::native_whole(1_000_000),
+        )
+        .unwrap();
+
+        let modifications =
+            storage.write_log.iter_block_modifications().count();
+        assert_eq!(
+            mint_treasury_inflation(&mut storage, Epoch(1)).unwrap(),
+            None
+        );
+        assert_eq!(
+            storage.write_log.iter_block_modifications().count(),
+            modifications
+        );
+        assert!(
+            get_treasury_inflation_at(&storage, Epoch(1))
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    /// Test that the hashes of the code of a proposal and of its chunks are
+    /// written along with them
+    #[test]
+    fn test_init_proposal_writes_code_hash() {
+        let mut storage = TestWlStorage::default();
+        GovernanceParameters::default()
+            .init_storage(&mut storage)
+            .unwrap();
+        let author = established_address_1();
+        let native_token = storage.get_native_token().unwrap();
+        namada_trans_token::credit_tokens(
+            &mut storage,
+            &native_token,
+            &author,
+            token::Amount::native_whole(1_000_000),
+        )
+        .unwrap();
+
+        let code = vec![0xab; 64];
+        let data = InitProposalData {
+            id: 0,
+            content: Hash::default(),
+            author,
+            r#type: ProposalType::Default(Some(Hash::sha256(&code))),
+            voting_start_epoch: Epoch(3),
+            voting_end_epoch: Epoch(6),
+            grace_epoch: Epoch(9),
+            voting_heights: None,
+            priority: None,
+            code_chunks: vec![],
+        };
+        let chunks = vec![vec![0xcd; 32], vec![0xef; 16]];
+        init_proposal(
+            &mut storage,
+            data,
+            vec![],
+            Some(code.clone()),
+            chunks.clone(),
+        )
+        .unwrap();
+
+        let reader = ProposalStorageReader::new(&storage);
+        assert_eq!(reader.code(0).unwrap(), Some(code.clone()));
+        assert_eq!(reader.code_hash(0).unwrap(), Some(Hash::sha256(&code)));
+        assert_eq!(reader.code_chunks(0).unwrap(), chunks);
+        for (idx, chunk) in chunks.iter().enumerate() {
+            assert_eq!(
+                reader.code_chunk_hash(0, idx as u64).unwrap(),
+                Some(Hash::sha256(chunk))
+            );
+        }
+    }
+
+    /// Test that a proposal voted on over block heights is stored without
+    /// epochs, and that it's queued to be concluded at its voting end height
+    #[test]
+    fn test_init_proposal_with_voting_heights() {
+        let mut storage = TestWlStorage::default();
+        GovernanceParameters::default()
+            .init_storage(&mut storage)
+            .unwrap();
+        let author = established_address_1();
+        let native_token = storage.get_native_token().unwrap();
+        namada_trans_token::credit_tokens(
+            &mut storage,
+            &native_token,
+            &author,
+            token::Amount::native_whole(1_000_000),
+        )
+        .unwrap();
+
+        let voting_heights = VotingHeights {
+            start: BlockHeight(10),
+            end: BlockHeight(700),
+        };
+        let data = InitProposalData {
+            id: 0,
+            content: Hash::default(),
+            author,
+            r#type: ProposalType::Default(None),
+            voting_start_epoch: Epoch(3),
+            voting_end_epoch: Epoch(6),
+            grace_epoch: Epoch(9),
+            voting_heights: Some(voting_heights),
+            priority: None,
+            code_chunks: vec![],
+        };
+        init_proposal(&mut storage, data, vec![], None, vec![]).unwrap();
+
+        let reader = ProposalStorageReader::new(&storage);
+        assert_eq!(reader.voting_heights(0).unwrap(), Some(voting_heights));
+        assert!(reader.voting_start(0).unwrap().is_none());
+        assert!(reader.voting_end(0).unwrap().is_none());
+        assert!(reader.grace_epoch(0).unwrap().is_none());
+        assert!(
+            !storage
+                .has_key(&governance_keys::get_committing_proposals_key(0, 9))
+                .unwrap()
+        );
+
+        // only the exact end height concludes the proposal
+        for height in [7, 70, 7000] {
+            assert!(
+                get_proposals_concluding_at_height(
+                    &storage,
+                    BlockHeight(height)
+                )
+                .unwrap()
+                .is_empty()
+            );
+        }
+        assert_eq!(
+            get_proposals_concluding_at_height(&storage, BlockHeight(700))
+                .unwrap(),
+            vec![0]
+        );
+
+        let proposal = get_proposal_by_id(&storage, 0).unwrap().unwrap();
+        assert_eq!(proposal.voting_heights, Some(voting_heights));
+        assert_eq!(proposal.voting_end_epoch, Epoch::default());
+    }
+
+    /// Test that only Ed25519 keys can be registered as governance voting
+    /// keys
+    #[test]
+    fn test_register_voting_key() {
+        let mut storage = TestWlStorage::default();
+        let owner = established_address_1();
+        assert!(get_voting_key(&storage, &owner).unwrap().is_none());
+
+        let secp256k1_key = keypair_3().ref_to();
+        assert!(
+            register_voting_key(&mut storage, &owner, &secp256k1_key).is_err()
+        );
+        assert!(get_voting_key(&storage, &owner).unwrap().is_none());
+
+        let ed25519_key = keypair_1().ref_to();
+        register_voting_key(&mut storage, &owner, &ed25519_key).unwrap();
+        assert_eq!(
+            get_voting_key(&storage, &owner).unwrap(),
+            Some(ed25519_key)
+        );
+
+        unregister_voting_key(&mut storage, &owner).unwrap();
+        assert!(get_voting_key(&storage, &owner).unwrap().is_none());
+    }
+
+    /// Test that governance vote delegations can be set, revoked and listed,
+    /// and that self-delegations are rejected
+    #[test]
+    fn test_delegate_votes() {
+        let mut storage = TestWlStorage::default();
+        let delegator = established_address_1();
+        let governor = established_address_2();
+        let delegate = |governor: Option<&Address>| VoteDelegationData {
+            delegator: delegator.clone(),
+            governor: governor.cloned(),
+        };
+
+        assert!(
+            delegate_votes(&mut storage, delegate(Some(&delegator))).is_err()
+        );
+        assert!(get_governor(&storage, &delegator).unwrap().is_none());
+
+        delegate_votes(&mut storage, delegate(Some(&governor))).unwrap();
+        assert_eq!(
+            get_governor(&storage, &delegator).unwrap(),
+            Some(governor.clone())
+        );
+        assert_eq!(
+            get_governors(&storage).unwrap(),
+            BTreeMap::from([(delegator.clone(), governor)])
+        );
+
+        delegate_votes(&mut storage, delegate(None)).unwrap();
+        assert!(get_governor(&storage, &delegator).unwrap().is_none());
+        assert!(get_governors(&storage).unwrap().is_empty());
+    }
+
+    /// Test that circular delegations and delegation chains exceeding the
+    /// maximum depth are rejected
+    #[test]
+    fn test_delegate_votes_rejects_invalid_chains() {
+        let mut storage = TestWlStorage::default();
+        let accounts: Vec<Address> = (0..MAX_VOTE_DELEGATION_DEPTH + 2)
+            .map(|_| gen_established_address())
+            .collect();
+        let delegate = |delegator: usize, governor: usize| VoteDelegationData {
+            delegator: accounts[delegator].clone(),
+            governor: Some(accounts[governor].clone()),
+        };
+        // chain the first accounts up to the maximum delegation depth
+        for delegator in 0..MAX_VOTE_DELEGATION_DEPTH {
+            delegate_votes(&mut storage, delegate(delegator, delegator + 1))
+                .unwrap();
+        }
+
+        // closing a cycle is rejected
+        assert!(delegate_votes(&mut storage, delegate(2, 0)).is_err());
+        assert_eq!(
+            get_governor(&storage, &accounts[2]).unwrap(),
+            Some(accounts[3].clone())
+        );
+
+        // extending the chain past the maximum depth is rejected
+        let extra = MAX_VOTE_DELEGATION_DEPTH + 1;
+        assert!(delegate_votes(&mut storage, delegate(extra, 0)).is_err());
+        assert!(get_governor(&storage, &accounts[extra]).unwrap().is_none());
+
+        // a chain of the maximum depth is accepted
+        delegate_votes(&mut storage, delegate(extra, 1)).unwrap();
+        assert_eq!(
+            get_governor(&storage, &accounts[extra]).unwrap(),
+            Some(accounts[1].clone())
+        );
+    }
+
+    /// Test that the votes index of a voter points at the latest vote on
+    /// each proposal, ordered by proposal id
+    #[test]
+    fn test_votes_by_voter_index() {
+        let mut storage = TestWlStorage::default();
+        let voter = established_address_1();
+        let validator = established_address_2();
+        let vote = |id, vote| VoteProposalData {
+            id,
+            vote,
+            voter: voter.clone(),
+            delegations: vec![validator.clone()],
+        };
+
+        storage.storage.block.epoch = Epoch(2);
+        vote_proposal(&mut storage, vote(10, ProposalVote::Yay)).unwrap();
+        vote_proposal(&mut storage, vote(2, ProposalVote::Nay)).unwrap();
+
+        // overwrite the vote on proposal 10 at a later epoch
+        storage.storage.block.epoch = Epoch(3);
+        vote_proposal(&mut storage, vote(10, ProposalVote::Abstain)).unwrap();
+
+        let receipts = get_votes_by_voter(&storage, &voter).unwrap();
+        assert_eq!(
+            receipts,
+            vec![
+                VoteReceipt {
+                    proposal_id: 2,
+                    vote: ProposalVote::Nay,
+                    epoch: Epoch(2),
+                },
+                VoteReceipt {
+                    proposal_id: 10,
+                    vote: ProposalVote::Abstain,
+                    epoch: Epoch(3),
+                },
+            ]
+        );
+
+        // a vote without delegations is not indexed
+        let other_voter = established_address_3();
+        vote_proposal(
+            &mut storage,
+            VoteProposalData {
+                id: 2,
+                vote: ProposalVote::Yay,
+                voter: other_voter.clone(),
+                delegations: vec![],
+            },
+        )
+        .unwrap();
+        assert!(
+            get_votes_by_voter(&storage, &other_voter)
+                .unwrap()
+                .is_empty()
+        );
+    }
+    /// Test that the pending execution marker of a proposal only authorizes
+    /// txs carrying the id of the proposal, at the height it was written at
+    #[test]
+    fn test_pending_execution_scope() {
+        let mut storage = TestWlStorage::default();
+        storage.storage.block.height = BlockHeight(5);
+        let data = 0_u64.serialize_to_vec();
+        assert!(!is_proposal_accepted(&storage, &data).unwrap());
+
+        write_pending_execution(&mut storage, 0).unwrap();
+        assert_eq!(
+            read_pending_execution(&storage, 0).unwrap(),
+            Some(PendingExecution {
+                proposal_id: 0,
+                height: BlockHeight(5),
+            })
+        );
+        assert!(is_proposal_accepted(&storage, &data).unwrap());
+        assert!(
+            !is_proposal_accepted(&storage, &1_u64.serialize_to_vec()).unwrap()
+        );
+        assert!(!is_proposal_accepted(&storage, &[0xff]).unwrap());
+
+        // a marker left over from a previous block is stale
+        storage.storage.block.height = BlockHeight(6);
+        assert!(!is_proposal_accepted(&storage, &data).unwrap());
+
+        // a marker must be scoped to the proposal it's stored under
+        storage
+            .write(
+                &governance_keys::get_proposal_execution_key(0),
+                PendingExecution {
+                    proposal_id: 1,
+                    height: BlockHeight(6),
+                },
+            )
+            .unwrap();
+        assert!(!is_proposal_accepted(&storage, &data).unwrap());
+    }
+
+    /// Write the given votes under the legacy vote key layout
+    fn write_legacy_votes(
+        storage: &mut TestWlStorage,
+        votes: &[(u64, &Address, &Address, ProposalVote)],
+    ) {
+        for (id, voter, validator, vote) in votes {
+            let key = governance_keys::get_legacy_vote_proposal_key(
+                *id,
+                (*voter).clone(),
+                (*validator).clone(),
+            );
+            storage.write(&key, vote.clone()).unwrap();
+        }
+    }
+
+    /// Test that the migration of the vote keys moves the legacy votes of
+    /// every proposal to keys recording the role of the voter, leaving the
+    /// votes read for the tally unchanged
+    #[test]
+    fn test_migrate_vote_keys() {
+        let validator = established_address_1();
+        let delegator = established_address_2();
+        let other = established_address_3();
+        let legacy_votes = [
+            (0, &validator, &validator, ProposalVote::Yay),
+            (0, &delegator, &validator, ProposalVote::Nay),
+            (1, &other, &validator, ProposalVote::Abstain),
+            // beyond the counter, hence not a proposal
+            (7, &delegator, &validator, ProposalVote::Yay),
+        ];
+        let setup = || {
+            let mut storage = TestWlStorage::default();
+            storage
+                .write(&governance_keys::get_counter_key(), 2_u64)
+                .unwrap();
+            write_legacy_votes(&mut storage, &legacy_votes);
+            storage
+        };
+
+        let mut storage = setup();
+        let votes_before =
+            [0, 1].map(|id| get_proposal_votes(&storage, id).unwrap());
+        assert_eq!(votes_before[0].len(), 2);
+        assert_eq!(votes_before[1].len(), 1);
+
+        // only the proposals of the batch are migrated
+        assert_eq!(migrate_vote_keys(&mut storage, 1).unwrap(), 2);
+        let legacy_key = governance_keys::get_legacy_vote_proposal_key(
+            1,
+            other.clone(),
+            validator.clone(),
+        );
+        assert!(storage.has_key(&legacy_key).unwrap());
+        // the next batch resumes from the cursor, and stops at the counter
+        assert_eq!(migrate_vote_keys(&mut storage, 10).unwrap(), 1);
+        assert_eq!(
+            storage
+                .read::<u64>(
+                    &governance_keys::get_vote_key_migration_cursor_key()
+                )
+                .unwrap(),
+            Some(2)
+        );
+        for (id, voter, validator, vote) in &legacy_votes[..3] {
+            let legacy_key = governance_keys::get_legacy_vote_proposal_key(
+                *id,
+                (*voter).clone(),
+                (*validator).clone(),
+            );
+            assert!(!storage.has_key(&legacy_key).unwrap());
+            let key = governance_keys::get_vote_proposal_key(
+                *id,
+                (*voter).clone(),
+                (*validator).clone(),
+            );
+            assert_eq!(
+                storage.read::<ProposalVote>(&key).unwrap().as_ref(),
+                Some(vote)
+            );
+        }
+        let votes_after =
+            [0, 1].map(|id| get_proposal_votes(&storage, id).unwrap());
+        let sorted = |votes: &[Vote]| {
+            let mut votes = votes
+                .iter()
+                .map(|vote| {
+                    (
+                        vote.validator.clone(),
+                        vote.delegator.clone(),
+                        vote.data.clone(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            votes.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+            votes
+        };
+        for (before, after) in votes_before.iter().zip(&votes_after) {
+            assert_eq!(sorted(before), sorted(after));
+        }
+
+        // running the migration again is a no-op
+        assert_eq!(migrate_vote_keys(&mut storage, 10).unwrap(), 0);
+
+        // the migration is deterministic, whichever the batches
+        let mut other_storage = setup();
+        assert_eq!(migrate_vote_keys(&mut other_storage, 2).unwrap(), 3);
+        let prefix = governance_keys::proposal_prefix();
+        let dump = |storage: &TestWlStorage| {
+            iter_prefix_bytes(storage, &prefix)
+                .unwrap()
+                .map(Result::unwrap)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(dump(&storage), dump(&other_storage));
+    }
+
+    /// Test that a vote moved unchanged from its legacy key is recognized as
+    /// a migration, unlike a changed or duplicated vote
+    #[test]
+    fn test_is_vote_key_migration() {
+        let validator = established_address_1();
+        let delegator = established_address_2();
+        let legacy_key = governance_keys::get_legacy_vote_proposal_key(
+            0,
+            delegator.clone(),
+            validator.clone(),
+        );
+        let vote_key = governance_keys::get_vote_proposal_key(
+            0,
+            delegator.clone(),
+            validator.clone(),
+        );
+        let mut pre = TestWlStorage::default();
+        pre.write(&governance_keys::get_counter_key(), 1_u64)
+            .unwrap();
+        write_legacy_votes(
+            &mut pre,
+            &[(0, &delegator, &validator, ProposalVote::Yay)],
+        );
+
+        let mut post = TestWlStorage::default();
+        post.write(&governance_keys::get_counter_key(), 1_u64)
+            .unwrap();
+        write_legacy_votes(
+            &mut post,
+            &[(0, &delegator, &validator, ProposalVote::Yay)],
+        );
+        migrate_vote_keys(&mut post, 1).unwrap();
+        assert!(is_vote_key_migration(&pre, &post, &vote_key).unwrap());
+        assert!(is_vote_key_migration(&pre, &post, &legacy_key).unwrap());
+
+        // a changed vote is not a migration
+        post.write(&vote_key, ProposalVote::Nay).unwrap();
+        assert!(!is_vote_key_migration(&pre, &post, &vote_key).unwrap());
+
+        // nor is a copy keeping the legacy key
+        post.write(&vote_key, ProposalVote::Yay).unwrap();
+        post.write(&legacy_key, ProposalVote::Yay).unwrap();
+        assert!(!is_vote_key_migration(&pre, &post, &vote_key).unwrap());
+    }
 }
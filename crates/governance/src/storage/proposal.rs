@@ -1,12 +1,15 @@
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Display;
 
-use borsh::{BorshDeserialize, BorshSerialize};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use itertools::Itertools;
 use namada_core::ibc::core::host::types::identifiers::{ChannelId, PortId};
 use namada_core::types::address::Address;
+use namada_core::types::ethereum_events::EthAddress;
 use namada_core::types::hash::Hash;
-use namada_core::types::storage::Epoch;
+use namada_core::types::key::common;
+use namada_core::types::storage::{BlockHeight, Epoch, Key};
+use namada_core::types::uint::Uint;
 use namada_trans_token::Amount;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -32,6 +35,7 @@ pub enum ProposalError {
     PartialEq,
     BorshSerialize,
     BorshDeserialize,
+    BorshSchema,
     Serialize,
     Deserialize,
 )]
@@ -50,18 +54,95 @@ pub struct InitProposalData {
     pub voting_end_epoch: Epoch,
     /// The epoch from which this changes are executed
     pub grace_epoch: Epoch,
+    /// The block heights bounding the voting period, if it's expressed in
+    /// blocks. The epoch fields are then not used, and the proposal is
+    /// concluded at the voting end height.
+    pub voting_heights: Option<VotingHeights>,
+    /// The execution priority of the proposal, if any. The proposals
+    /// concluded in the same block are executed by decreasing priority,
+    /// then by increasing id, a proposal without a priority having the
+    /// lowest one.
+    pub priority: Option<u8>,
+    /// The hashes of the extra data sections holding the chunks of the wasm
+    /// code of a default or council proposal. They are executed in order
+    /// after its code, atomically with it.
+    pub code_chunks: Vec<Hash>,
 }
 
 impl InitProposalData {
     /// Get the hash of the corresponding extra data section
     pub fn get_section_code_hash(&self) -> Option<Hash> {
         match self.r#type {
-            ProposalType::Default(hash) => hash,
+            ProposalType::Default(hash) | ProposalType::Council(hash) => hash,
             _ => None,
         }
     }
 }
 
+/// The voting period of a proposal, expressed in block heights
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct VotingHeights {
+    /// The height from which voting is allowed
+    pub start: BlockHeight,
+    /// The height from which voting is stopped, and at which the proposal
+    /// is concluded
+    pub end: BlockHeight,
+}
+
+impl VotingHeights {
+    /// Check if a vote cast at the given height falls in the voting period.
+    /// Validators can only vote during the first 2/3 of the period.
+    pub fn can_be_voted(
+        &self,
+        height: BlockHeight,
+        is_validator: bool,
+    ) -> bool {
+        if is_validator {
+            self.start <= height
+                && height.0 * 3 <= self.start.0 + self.end.0 * 2
+        } else {
+            self.start <= height && height < self.end
+        }
+    }
+
+    /// Get the duration of the voting period in blocks
+    pub fn duration(&self) -> u64 {
+        self.end.0.saturating_sub(self.start.0)
+    }
+}
+
+/// The marker of a proposal whose code is being executed, which authorizes
+/// the governance changes made by the code. It is scoped to the proposal and
+/// to the height of the block executing it, such that a marker left over in
+/// storage can't authorize any other tx.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+)]
+pub struct PendingExecution {
+    /// The id of the proposal being executed
+    pub proposal_id: u64,
+    /// The height of the block executing the proposal
+    pub height: BlockHeight,
+}
+
 /// A tx data type to hold vote proposal data
 #[derive(
     Debug,
@@ -69,6 +150,7 @@ impl InitProposalData {
     PartialEq,
     BorshSerialize,
     BorshDeserialize,
+    BorshSchema,
     Serialize,
     Deserialize,
 )]
@@ -95,6 +177,9 @@ impl TryFrom<DefaultProposal> for InitProposalData {
             voting_start_epoch: value.proposal.voting_start_epoch,
             voting_end_epoch: value.proposal.voting_end_epoch,
             grace_epoch: value.proposal.grace_epoch,
+            voting_heights: value.proposal.voting_heights,
+            priority: value.proposal.priority,
+            code_chunks: vec![],
         })
     }
 }
@@ -114,6 +199,9 @@ impl TryFrom<PgfStewardProposal> for InitProposalData {
             voting_start_epoch: value.proposal.voting_start_epoch,
             voting_end_epoch: value.proposal.voting_end_epoch,
             grace_epoch: value.proposal.grace_epoch,
+            voting_heights: value.proposal.voting_heights,
+            priority: value.proposal.priority,
+            code_chunks: vec![],
         })
     }
 }
@@ -154,6 +242,9 @@ impl TryFrom<PgfFundingProposal> for InitProposalData {
             voting_start_epoch: value.proposal.voting_start_epoch,
             voting_end_epoch: value.proposal.voting_end_epoch,
             grace_epoch: value.proposal.grace_epoch,
+            voting_heights: value.proposal.voting_heights,
+            priority: value.proposal.priority,
+            code_chunks: vec![],
         })
     }
 }
@@ -167,6 +258,7 @@ impl TryFrom<PgfFundingProposal> for InitProposalData {
     PartialOrd,
     BorshSerialize,
     BorshDeserialize,
+    BorshSchema,
     Serialize,
     Deserialize,
 )]
@@ -191,6 +283,7 @@ impl StoragePgfFunding {
     PartialEq,
     BorshSerialize,
     BorshDeserialize,
+    BorshSchema,
     Serialize,
     Deserialize,
 )]
@@ -201,6 +294,280 @@ pub enum ProposalType {
     PGFSteward(BTreeSet<AddRemove<Address>>),
     /// PGF funding proposal
     PGFPayment(BTreeSet<PGFAction>),
+    /// Ethereum bridge contracts update proposal
+    ETHBridgeContracts(BridgeContractsUpdate),
+    /// Ethereum bridge pause or resume proposal
+    ETHBridgePause(BridgePauseAction),
+    /// Wasm code allowlist update proposal
+    UpdateWasmAllowlist(UpdateWasmAllowlist),
+    /// Emergency rotation of the Ethereum hot key of a validator
+    ETHHotKeyRotation(EthHotKeyRotation),
+    /// Council proposal with the optional wasm code, passed by the yay votes
+    /// of the members of the council
+    Council(Option<Hash>),
+    /// Transfer of funds out of the treasury to a named account
+    TreasurySpend(TreasurySpend),
+    /// Change of some protocol parameters, written without any wasm code
+    ParameterChange(ParameterChange),
+}
+
+/// New addresses of the Ethereum bridge contracts, in effect from the
+/// activation epoch onwards
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct BridgeContractsUpdate {
+    /// The new address of the native ERC20 contract
+    pub native_erc20: EthAddress,
+    /// The new address of the bridge contract
+    pub bridge: EthAddress,
+    /// The epoch from which the new addresses are in effect
+    pub activation_epoch: Epoch,
+    /// The transfer nonce of a redeployed bridge contract, adopted by the
+    /// Bridge pool from the activation epoch onwards
+    pub bridge_pool_nonce: Option<Uint>,
+}
+
+impl Display for BridgeContractsUpdate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Native ERC20: {}\n  Bridge: {}\n  Activation epoch: {}",
+            self.native_erc20, self.bridge, self.activation_epoch
+        )?;
+        if let Some(nonce) = self.bridge_pool_nonce {
+            write!(f, "\n  Bridge pool nonce: {}", nonce)?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether to pause or resume the Ethereum bridge
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub enum BridgePauseAction {
+    /// Pause the Ethereum bridge
+    Pause,
+    /// Resume the Ethereum bridge
+    Resume,
+}
+
+impl BridgePauseAction {
+    /// Check if the bridge is paused after executing this action
+    pub fn is_pause(&self) -> bool {
+        matches!(self, BridgePauseAction::Pause)
+    }
+}
+
+impl Display for BridgePauseAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BridgePauseAction::Pause => write!(f, "Pause"),
+            BridgePauseAction::Resume => write!(f, "Resume"),
+        }
+    }
+}
+
+/// The kind of wasm code governed by an allowlist
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub enum TxOrVp {
+    /// Transaction code
+    Tx,
+    /// Validity predicate code
+    Vp,
+}
+
+impl Display for TxOrVp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TxOrVp::Tx => write!(f, "tx"),
+            TxOrVp::Vp => write!(f, "vp"),
+        }
+    }
+}
+
+/// Changes to the allowlist of tx or VP code hashes. Note that an empty
+/// allowlist allows any code, hence removing all of its hashes lifts the
+/// restrictions on the code that may be run.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct UpdateWasmAllowlist {
+    /// The code hashes to add to the allowlist
+    pub add: Vec<Hash>,
+    /// The code hashes to remove from the allowlist
+    pub remove: Vec<Hash>,
+    /// The allowlist to update
+    pub kind: TxOrVp,
+}
+
+impl UpdateWasmAllowlist {
+    /// Check that the update changes at least one hash, that no hash is
+    /// zeroed, repeated, or both added and removed
+    pub fn is_valid(&self) -> bool {
+        let mut hashes = BTreeSet::new();
+        !(self.add.is_empty() && self.remove.is_empty())
+            && self
+                .add
+                .iter()
+                .chain(self.remove.iter())
+                .all(|hash| *hash != Hash::zero() && hashes.insert(*hash))
+    }
+}
+
+impl Display for UpdateWasmAllowlist {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Allowlist: {}", self.kind)?;
+        for hash in &self.add {
+            write!(f, "\n  Add: {}", hash)?;
+        }
+        for hash in &self.remove {
+            write!(f, "\n  Remove: {}", hash)?;
+        }
+        Ok(())
+    }
+}
+
+/// Emergency rotation of the Ethereum hot key of a validator, in effect
+/// from the activation epoch onwards. This allows recovering a validator
+/// that lost control of its hot key, which it would otherwise have to
+/// rotate itself.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct EthHotKeyRotation {
+    /// The validator whose hot key is rotated
+    pub validator: Address,
+    /// The new Ethereum hot key of the validator
+    pub eth_hot_key: common::PublicKey,
+    /// The epoch from which the new hot key is in effect
+    pub activation_epoch: Epoch,
+}
+
+impl EthHotKeyRotation {
+    /// Check that the new hot key is a `secp256k1` key, the only kind of
+    /// key that can sign data verified on Ethereum
+    pub fn has_eth_key(&self) -> bool {
+        matches!(self.eth_hot_key, common::PublicKey::Secp256k1(_))
+    }
+}
+
+impl Display for EthHotKeyRotation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Validator: {}\n  Ethereum hot key: {}\n  Activation epoch: {}",
+            self.validator, self.eth_hot_key, self.activation_epoch
+        )
+    }
+}
+
+/// A transfer of funds out of the treasury, authorized by governance. The
+/// spend is not applied if the treasury can't cover it once the proposal
+/// passes.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct TreasurySpend {
+    /// The account receiving the funds
+    pub recipient: Address,
+    /// The amount of funds transferred
+    pub amount: Amount,
+    /// The token of the funds
+    pub token: Address,
+}
+
+impl Display for TreasurySpend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Recipient: {}\n  Amount: {}\n  Token: {}",
+            self.recipient, self.amount, self.token
+        )
+    }
+}
+
+/// A change of some protocol parameters, written directly to storage once
+/// the proposal passes. Every key must be a protocol parameter key and its
+/// value the Borsh encoding of the type of the parameter.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct ParameterChange {
+    /// The parameter keys with their new values
+    pub changes: Vec<(Key, Vec<u8>)>,
+}
+
+impl Display for ParameterChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Changes:")?;
+        for (key, value) in &self.changes {
+            write!(f, "\n  {}: {} bytes", key, value.len())?;
+        }
+        Ok(())
+    }
 }
 
 /// An add or remove action for PGF
@@ -214,6 +581,7 @@ pub enum ProposalType {
     Ord,
     BorshSerialize,
     BorshDeserialize,
+    BorshSchema,
     Serialize,
     Deserialize,
 )]
@@ -243,6 +611,7 @@ where
     PartialEq,
     BorshSerialize,
     BorshDeserialize,
+    BorshSchema,
     Serialize,
     Deserialize,
     Ord,
@@ -294,6 +663,7 @@ impl Display for PGFTarget {
     PartialEq,
     BorshSerialize,
     BorshDeserialize,
+    BorshSchema,
     Serialize,
     Deserialize,
     Ord,
@@ -393,6 +763,7 @@ impl borsh::BorshSchema for PGFIbcTarget {
     PartialEq,
     BorshSerialize,
     BorshDeserialize,
+    BorshSchema,
     Serialize,
     Deserialize,
     Eq,
@@ -412,10 +783,46 @@ impl ProposalType {
         matches!(self, ProposalType::Default(_))
     }
 
+    /// Check if proposals of this type may carry some wasm code, executed
+    /// once they pass
+    pub fn may_have_code(&self) -> bool {
+        matches!(self, ProposalType::Default(_) | ProposalType::Council(_))
+    }
+
+    /// The same proposal type, without the hash of its wasm code, which is
+    /// stored apart from the type
+    pub fn without_code(&self) -> Self {
+        match self {
+            ProposalType::Default(_) => ProposalType::Default(None),
+            ProposalType::Council(_) => ProposalType::Council(None),
+            other => other.clone(),
+        }
+    }
+
+    /// The name of the kind of the proposal type, regardless of its data,
+    /// under which governance parameters are set per proposal type
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ProposalType::Default(_) => "default",
+            ProposalType::PGFSteward(_) => "pgf_steward",
+            ProposalType::PGFPayment(_) => "pgf_payment",
+            ProposalType::ETHBridgeContracts(_) => "eth_bridge_contracts",
+            ProposalType::ETHBridgePause(_) => "eth_bridge_pause",
+            ProposalType::UpdateWasmAllowlist(_) => "update_wasm_allowlist",
+            ProposalType::ETHHotKeyRotation(_) => "eth_hot_key_rotation",
+            ProposalType::Council(_) => "council",
+            ProposalType::TreasurySpend(_) => "treasury_spend",
+            ProposalType::ParameterChange(_) => "parameter_change",
+        }
+    }
+
     fn format_data(&self) -> String {
         match self {
-            ProposalType::Default(Some(hash)) => format!("Hash: {}", &hash),
-            ProposalType::Default(None) => "".to_string(),
+            ProposalType::Default(Some(hash))
+            | ProposalType::Council(Some(hash)) => format!("Hash: {}", &hash),
+            ProposalType::Default(None) | ProposalType::Council(None) => {
+                "".to_string()
+            }
             ProposalType::PGFSteward(addresses) => format!(
                 "Addresses:{}",
                 addresses
@@ -430,6 +837,16 @@ impl ProposalType {
                     .map(|action| format!("\n  {}", &action))
                     .join("")
             ),
+            ProposalType::ETHBridgeContracts(update) => {
+                format!("Contracts:\n  {}", update)
+            }
+            ProposalType::ETHBridgePause(action) => {
+                format!("Action: {}", action)
+            }
+            ProposalType::UpdateWasmAllowlist(update) => update.to_string(),
+            ProposalType::ETHHotKeyRotation(rotation) => rotation.to_string(),
+            ProposalType::TreasurySpend(spend) => spend.to_string(),
+            ProposalType::ParameterChange(change) => change.to_string(),
         }
     }
 }
@@ -440,6 +857,23 @@ impl Display for ProposalType {
             ProposalType::Default(_) => write!(f, "Default"),
             ProposalType::PGFSteward(_) => write!(f, "PGF steward"),
             ProposalType::PGFPayment(_) => write!(f, "PGF funding"),
+            ProposalType::ETHBridgeContracts(_) => {
+                write!(f, "Ethereum bridge contracts")
+            }
+            ProposalType::ETHBridgePause(_) => {
+                write!(f, "Ethereum bridge pause")
+            }
+            ProposalType::UpdateWasmAllowlist(_) => {
+                write!(f, "Wasm allowlist update")
+            }
+            ProposalType::ETHHotKeyRotation(_) => {
+                write!(f, "Ethereum hot key rotation")
+            }
+            ProposalType::Council(_) => write!(f, "Council"),
+            ProposalType::TreasurySpend(_) => write!(f, "Treasury spend"),
+            ProposalType::ParameterChange(_) => {
+                write!(f, "Parameter change")
+            }
         }
     }
 }
@@ -508,7 +942,15 @@ impl Display for PGFAction {
     }
 }
 
-#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+#[derive(
+    Debug,
+    Clone,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
 /// Proposal representation when fetched from the storage
 pub struct StorageProposal {
     /// The proposal id
@@ -525,6 +967,11 @@ pub struct StorageProposal {
     pub voting_end_epoch: Epoch,
     /// The epoch from which this changes are executed
     pub grace_epoch: Epoch,
+    /// The block heights bounding the voting period, if it's expressed in
+    /// blocks. The epoch fields are then left to their default value.
+    pub voting_heights: Option<VotingHeights>,
+    /// The execution priority of the proposal, if any
+    pub priority: Option<u8>,
 }
 
 impl StorageProposal {
@@ -546,8 +993,18 @@ impl StorageProposal {
     }
 
     /// Return the type of tally for the proposal
-    pub fn get_tally_type(&self, is_steward: bool) -> TallyType {
-        TallyType::from(self.r#type.clone(), is_steward)
+    pub fn get_tally_type(
+        &self,
+        is_steward: bool,
+        emergency_validator_quorum: Option<u64>,
+        council_threshold: Option<u64>,
+    ) -> TallyType {
+        TallyType::from(
+            self.r#type.clone(),
+            is_steward,
+            emergency_validator_quorum,
+            council_threshold,
+        )
     }
 
     /// Return the status of a proposal
@@ -565,24 +1022,32 @@ impl StorageProposal {
 
     /// Serialize a proposal to string
     pub fn to_string_with_status(&self, current_epoch: Epoch) -> String {
+        // The status of proposals voted on over block heights can't be
+        // inferred from the current epoch
+        let voting_period = match self.voting_heights {
+            Some(VotingHeights { start, end }) => {
+                format!("Start Height: {}\nEnd Height: {}", start, end)
+            }
+            None => format!(
+                "Start Epoch: {}\nEnd Epoch: {}\nGrace Epoch: {}\nStatus: {}",
+                self.voting_start_epoch,
+                self.voting_end_epoch,
+                self.grace_epoch,
+                self.get_status(current_epoch),
+            ),
+        };
         format!(
             "Proposal Id: {}
 Type: {}
 Author: {}
 Content: {:?}
-Start Epoch: {}
-End Epoch: {}
-Grace Epoch: {}
-Status: {}
+{}
 Data: {}",
             self.id,
             self.r#type,
             self.author,
             self.content,
-            self.voting_start_epoch,
-            self.voting_end_epoch,
-            self.grace_epoch,
-            self.get_status(current_epoch),
+            voting_period,
             self.r#type.format_data()
         )
     }
@@ -590,6 +1055,18 @@ Data: {}",
 
 impl Display for StorageProposal {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(VotingHeights { start, end }) = self.voting_heights {
+            return write!(
+                f,
+                "Proposal Id: {}
+            {:2}Type: {}
+            {:2}Author: {}
+            {:2}Start Height: {}
+            {:2}End Height: {}
+            ",
+                self.id, "", self.r#type, "", self.author, "", start, "", end
+            );
+        }
         write!(
             f,
             "Proposal Id: {}
@@ -618,8 +1095,11 @@ impl Display for StorageProposal {
 /// Testing helpers and and strategies for governance proposals
 pub mod testing {
     use namada_core::types::address::testing::arb_non_internal_address;
+    use namada_core::types::dec::testing::arb_uint;
+    use namada_core::types::ethereum_events::testing::arb_eth_address;
     use namada_core::types::hash::testing::arb_hash;
-    use namada_core::types::storage::testing::arb_epoch;
+    use namada_core::types::key::testing::arb_common_pk;
+    use namada_core::types::storage::testing::{arb_epoch, arb_key};
     use namada_core::types::token::testing::arb_amount;
     use proptest::prelude::*;
     use proptest::{collection, option, prop_compose};
@@ -675,9 +1155,111 @@ pub mod testing {
             .prop_map(ProposalType::PGFSteward),
             collection::btree_set(arb_pgf_action(), 0..10)
                 .prop_map(ProposalType::PGFPayment),
+            arb_bridge_contracts_update()
+                .prop_map(ProposalType::ETHBridgeContracts),
+            arb_bridge_pause_action().prop_map(ProposalType::ETHBridgePause),
+            arb_update_wasm_allowlist()
+                .prop_map(ProposalType::UpdateWasmAllowlist),
+            arb_eth_hot_key_rotation()
+                .prop_map(ProposalType::ETHHotKeyRotation),
+            option::of(arb_hash()).prop_map(ProposalType::Council),
+            arb_treasury_spend().prop_map(ProposalType::TreasurySpend),
+            arb_parameter_change().prop_map(ProposalType::ParameterChange),
+        ]
+    }
+
+    prop_compose! {
+        /// Generate an arbitrary parameter change
+        pub fn arb_parameter_change()(
+            changes in collection::vec(
+                (arb_key(), collection::vec(any::<u8>(), 0..32)),
+                0..4,
+            ),
+        ) -> ParameterChange {
+            ParameterChange { changes }
+        }
+    }
+
+    prop_compose! {
+        /// Generate an arbitrary treasury spend
+        pub fn arb_treasury_spend()(
+            recipient in arb_non_internal_address(),
+            amount in arb_amount(),
+            token in arb_non_internal_address(),
+        ) -> TreasurySpend {
+            TreasurySpend {
+                recipient,
+                amount,
+                token,
+            }
+        }
+    }
+
+    prop_compose! {
+        /// Generate an arbitrary Ethereum hot key rotation
+        pub fn arb_eth_hot_key_rotation()(
+            validator in arb_non_internal_address(),
+            eth_hot_key in arb_common_pk(),
+            activation_epoch in arb_epoch(),
+        ) -> EthHotKeyRotation {
+            EthHotKeyRotation {
+                validator,
+                eth_hot_key,
+                activation_epoch,
+            }
+        }
+    }
+
+    prop_compose! {
+        /// Generate an arbitrary wasm allowlist update
+        pub fn arb_update_wasm_allowlist()(
+            add in collection::vec(arb_hash(), 0..10),
+            remove in collection::vec(arb_hash(), 0..10),
+            kind in prop_oneof![Just(TxOrVp::Tx), Just(TxOrVp::Vp)],
+        ) -> UpdateWasmAllowlist {
+            UpdateWasmAllowlist { add, remove, kind }
+        }
+    }
+
+    /// Generate an arbitrary Ethereum bridge pause action
+    pub fn arb_bridge_pause_action() -> impl Strategy<Value = BridgePauseAction>
+    {
+        prop_oneof![
+            Just(BridgePauseAction::Pause),
+            Just(BridgePauseAction::Resume),
         ]
     }
 
+    prop_compose! {
+        /// Generate an arbitrary Ethereum bridge contracts update
+        pub fn arb_bridge_contracts_update()(
+            native_erc20 in arb_eth_address(),
+            bridge in arb_eth_address(),
+            activation_epoch in arb_epoch(),
+            bridge_pool_nonce in option::of(arb_uint()),
+        ) -> BridgeContractsUpdate {
+            BridgeContractsUpdate {
+                native_erc20,
+                bridge,
+                activation_epoch,
+                bridge_pool_nonce,
+            }
+        }
+    }
+
+    prop_compose! {
+        /// Generate an arbitrary voting period in block heights
+        pub fn arb_voting_heights()(
+            start in 1..u64::MAX / 2,
+            len in 1..u64::MAX / 2,
+        ) -> VotingHeights {
+            VotingHeights {
+                start: BlockHeight(start),
+                end: BlockHeight(start + len),
+            }
+        }
+    }
+
     prop_compose! {
         /// Generate a proposal initialization
         pub fn arb_init_proposal()(
@@ -688,6 +1270,9 @@ pub mod testing {
             voting_start_epoch in arb_epoch(),
             voting_end_epoch in arb_epoch(),
             grace_epoch in arb_epoch(),
+            voting_heights in option::of(arb_voting_heights()),
+            priority in option::of(any::<u8>()),
+            code_chunks in collection::vec(arb_hash(), 0..3),
         ) -> InitProposalData {
             InitProposalData {
                 id,
@@ -697,6 +1282,9 @@ pub mod testing {
                 voting_start_epoch,
                 voting_end_epoch,
                 grace_epoch,
+                voting_heights,
+                priority,
+                code_chunks,
             }
         }
     }
@@ -1,6 +1,8 @@
 use std::fmt::Display;
 
-use borsh::{BorshDeserialize, BorshSerialize};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use namada_core::types::address::Address;
+use namada_core::types::storage::Epoch;
 use serde::{Deserialize, Serialize};
 
 #[derive(
@@ -9,6 +11,7 @@ use serde::{Deserialize, Serialize};
     PartialEq,
     BorshSerialize,
     BorshDeserialize,
+    BorshSchema,
     Eq,
     Serialize,
     Deserialize,
@@ -63,6 +66,49 @@ impl TryFrom<String> for ProposalVote {
     }
 }
 
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+/// The latest vote cast by a voter on a proposal, as recorded in the votes
+/// index of the voter
+pub struct VoteReceipt {
+    /// The id of the proposal
+    pub proposal_id: u64,
+    /// The vote
+    pub vote: ProposalVote,
+    /// The epoch at which the vote was cast
+    pub epoch: Epoch,
+}
+
+/// A tx data type to delegate the governance votes of an account to a
+/// governor, or to revoke that delegation
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub struct VoteDelegationData {
+    /// The account delegating its governance votes
+    pub delegator: Address,
+    /// The governor voting on behalf of the delegator, or `None` to revoke
+    /// the delegation
+    pub governor: Option<Address>,
+}
+
 #[cfg(any(test, feature = "testing"))]
 /// Testing helpers and and strategies for governance proposals
 pub mod testing {
@@ -1,15 +1,21 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt::Display;
 
-use namada_core::borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use namada_core::types::address::Address;
-use namada_core::types::dec::Dec;
-use namada_core::types::storage::Epoch;
+use namada_core::types::dec::{Dec, POS_DECIMAL_PRECISION};
+use namada_core::types::hash::{Hash, HASH_LENGTH};
+use namada_core::types::storage::{BlockHash, BlockHeight, Epoch, Key};
 use namada_core::types::token;
+use namada_core::types::uint::Uint;
+use serde::{Deserialize, Serialize};
 
 use super::cli::offline::OfflineVote;
+use super::parameters::Council;
+use super::storage::keys::VoterType;
 use super::storage::proposal::ProposalType;
 use super::storage::vote::ProposalVote;
+use crate::storage::MAX_VOTE_DELEGATION_DEPTH;
 
 /// Proposal status
 pub enum ProposalStatus {
@@ -35,7 +41,15 @@ impl Display for ProposalStatus {
 pub type VotePower = token::Amount;
 
 /// Structure rappresenting a proposal vote
-#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+#[derive(
+    Debug,
+    Clone,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
 pub struct Vote {
     /// Field holding the address of the validator
     pub validator: Address,
@@ -43,6 +57,8 @@ pub struct Vote {
     pub delegator: Address,
     /// Field holding vote data
     pub data: ProposalVote,
+    /// The role of the voter, recorded in the vote key
+    pub voter_type: VoterType,
 }
 
 impl Display for Vote {
@@ -55,12 +71,23 @@ impl Display for Vote {
 impl Vote {
     /// Check if a vote is from a validator
     pub fn is_validator(&self) -> bool {
-        self.validator.eq(&self.delegator)
+        self.voter_type == VoterType::Validator
     }
 }
 
 /// Represent a tally type
-#[derive(Copy, Debug, Clone, BorshSerialize, BorshDeserialize)]
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
 pub enum TallyType {
     /// Represent a tally type for proposal requiring 2/3 of the total voting
     /// power to be yay
@@ -71,14 +98,49 @@ pub enum TallyType {
     /// Represent a tally type for proposal requiring less than 1/2 of nay
     /// votes over at least 1/3 of the voting power
     LessOneHalfOverOneThirdNay,
+    /// Represent a tally type for proposal requiring at least the given
+    /// number of distinct validators to vote yay, regardless of their voting
+    /// power
+    ValidatorCount(u64),
+    /// Represent a tally type for proposal requiring the council members
+    /// voting yay to weigh at least the given threshold in total, regardless
+    /// of their voting power. Members weigh 1 unless set otherwise in the
+    /// council. The votes of non-members don't count. A zero threshold, as
+    /// for a proposal tallied without any council, never passes.
+    Council(u64),
 }
 
 impl TallyType {
-    /// Compute the type of tally for a proposal
-    pub fn from(proposal_type: ProposalType, is_steward: bool) -> Self {
+    /// Compute the type of tally for a proposal. Emergency proposals are
+    /// tallied by the number of validators voting yay, if the
+    /// `emergency_validator_quorum` governance parameter is set. Council
+    /// proposals are tallied by the weight of the council members voting
+    /// yay, against the threshold of the `council` governance parameter.
+    pub fn from(
+        proposal_type: ProposalType,
+        is_steward: bool,
+        emergency_validator_quorum: Option<u64>,
+        council_threshold: Option<u64>,
+    ) -> Self {
+        if let Some(quorum) = emergency_validator_quorum
+            .filter(|_| Self::is_emergency(&proposal_type))
+        {
+            return TallyType::ValidatorCount(quorum);
+        }
         match (proposal_type, is_steward) {
             (ProposalType::Default(_), _) => TallyType::TwoThirds,
+            (ProposalType::Council(_), _) => {
+                TallyType::Council(council_threshold.unwrap_or_default())
+            }
             (ProposalType::PGFSteward(_), _) => TallyType::OneHalfOverOneThird,
+            (ProposalType::ETHBridgeContracts(_), _) => TallyType::TwoThirds,
+            (ProposalType::ETHBridgePause(_), _) => TallyType::TwoThirds,
+            (ProposalType::UpdateWasmAllowlist(_), _) => TallyType::TwoThirds,
+            (ProposalType::ETHHotKeyRotation(_), _) => TallyType::TwoThirds,
+            (ProposalType::TreasurySpend(_), _) => {
+                TallyType::OneHalfOverOneThird
+            }
+            (ProposalType::ParameterChange(_), _) => TallyType::TwoThirds,
             (ProposalType::PGFPayment(_), true) => {
                 TallyType::LessOneHalfOverOneThirdNay
             }
@@ -87,93 +149,398 @@ impl TallyType {
             }
         }
     }
+
+    /// Check if proposals of the given type are emergency actions, which may
+    /// be tallied by the number of validators voting yay
+    pub fn is_emergency(proposal_type: &ProposalType) -> bool {
+        matches!(
+            proposal_type,
+            ProposalType::ETHBridgePause(_)
+                | ProposalType::ETHHotKeyRotation(_)
+        )
+    }
 }
 
-/// The result of a proposal
-#[derive(Copy, Clone, Debug, BorshSerialize, BorshDeserialize)]
-pub enum TallyResult {
-    /// Proposal was accepted with the associated value
-    Passed,
-    /// Proposal was rejected
-    Rejected,
+/// The policy resolving a tally which lands exactly on a tie
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub enum TiePolicy {
+    /// A tie passes the proposal
+    TieBreaksToPass,
+    /// A tie rejects the proposal
+    #[default]
+    TieBreaksToReject,
+    /// A tie passes or rejects the proposal as drawn from the given seed
+    TieBreaksAtRandom(TieBreakSeed),
 }
 
-impl Display for TallyResult {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl TiePolicy {
+    /// The result of a tally which landed on a tie
+    pub fn resolve(&self) -> TallyResult {
         match self {
-            TallyResult::Passed => write!(f, "passed"),
-            TallyResult::Rejected => write!(f, "rejected"),
+            TiePolicy::TieBreaksToPass => TallyResult::Passed,
+            TiePolicy::TieBreaksToReject => TallyResult::Rejected,
+            TiePolicy::TieBreaksAtRandom(seed) if seed.passes() => {
+                TallyResult::Passed
+            }
+            TiePolicy::TieBreaksAtRandom(_) => TallyResult::Rejected,
         }
     }
 }
 
-impl TallyResult {
-    /// Create a new tally result
-    pub fn new(
-        tally_type: &TallyType,
-        yay_voting_power: VotePower,
-        nay_voting_power: VotePower,
-        abstain_voting_power: VotePower,
-        total_voting_power: VotePower,
-    ) -> Self {
-        let passed = match tally_type {
+/// The input of the random draw breaking a tie, derived from the block
+/// concluding the proposal such that every node draws the same result
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct TieBreakSeed {
+    /// The hash of the block concluding the proposal
+    pub block_hash: Hash,
+    /// The id of the proposal
+    pub proposal_id: u64,
+}
+
+impl TieBreakSeed {
+    /// The seed of the tie-break of the given proposal, concluded in the
+    /// block with the given hash
+    pub fn new(block_hash: &BlockHash, proposal_id: u64) -> Self {
+        Self {
+            block_hash: Hash(block_hash.0),
+            proposal_id,
+        }
+    }
+
+    /// The SHA-256 digest of the block hash followed by the little-endian
+    /// bytes of the proposal id
+    pub fn digest(&self) -> Hash {
+        let mut data = self.block_hash.0.to_vec();
+        data.extend_from_slice(&self.proposal_id.to_le_bytes());
+        Hash::sha256(data)
+    }
+
+    /// Draw whether a tie passes the proposal, which it does if the last
+    /// byte of the digest is odd
+    pub fn passes(&self) -> bool {
+        self.digest().0[HASH_LENGTH - 1] % 2 == 1
+    }
+}
+
+/// The votes of a proposal, as counted by the tally
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TalliedVotes {
+    /// The total voting power from yay votes
+    pub yay_voting_power: VotePower,
+    /// The total voting power from nay votes
+    pub nay_voting_power: VotePower,
+    /// The total voting power from abstained votes
+    pub abstain_voting_power: VotePower,
+    /// The total voting power during the proposal tally
+    pub total_voting_power: VotePower,
+    /// The number of distinct validators with some stake that voted yay
+    pub yay_validators: u64,
+    /// The number of distinct council members that voted yay
+    pub yay_council_members: u64,
+    /// The total weight of the council members that voted yay
+    pub yay_council_weight: u64,
+}
+
+impl TalliedVotes {
+    fn total_voted_power(&self) -> VotePower {
+        self.yay_voting_power
+            + self.nay_voting_power
+            + self.abstain_voting_power
+    }
+
+    /// Check if yay and nay votes carry the same, non-zero, voting power
+    fn is_yay_nay_tie(&self) -> bool {
+        !self.yay_voting_power.is_zero()
+            && self.yay_voting_power == self.nay_voting_power
+    }
+
+    /// The shares of the yay, nay and abstain votes in the voting power cast
+    pub fn basis_points(&self) -> TallyBasisPoints {
+        TallyBasisPoints::new(
+            self.yay_voting_power,
+            self.nay_voting_power,
+            self.abstain_voting_power,
+        )
+    }
+}
+
+/// The shares of the yay, nay and abstain votes in the voting power cast on
+/// a proposal, in basis points, i.e. hundredths of a percent
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct TallyBasisPoints {
+    /// The share of the yay votes
+    pub yay: u16,
+    /// The share of the nay votes
+    pub nay: u16,
+    /// The share of the abstain votes
+    pub abstain: u16,
+}
+
+impl TallyBasisPoints {
+    /// The basis points of the whole voting power cast
+    pub const WHOLE: u16 = 10_000;
+
+    /// Compute the shares of the given voting powers in their sum.
+    ///
+    /// Each share is rounded down, and the basis points left over are
+    /// handed out one at a time to the shares with the largest remainders,
+    /// ties going to yay, then nay, then abstain. The shares thus sum up to
+    /// [`Self::WHOLE`], unless no voting power was cast at all, in which case
+    /// they are all zero.
+    pub fn new(yay: VotePower, nay: VotePower, abstain: VotePower) -> Self {
+        let powers = [yay, nay, abstain].map(|power| power.raw_amount());
+        let Some(total) = powers
+            .iter()
+            .try_fold(Uint::zero(), |total, power| total.checked_add(*power))
+        else {
+            return Self::default();
+        };
+        if total.is_zero() {
+            return Self::default();
+        }
+        let mut shares = powers.map(|power| {
+            power
+                .checked_mul_div(Uint::from(Self::WHOLE), total)
+                .expect("The total voting power cast can't be zero")
+        });
+        let rounded: u64 =
+            shares.iter().map(|(share, _)| share.low_u64()).sum();
+        let mut by_remainder = [0, 1, 2];
+        by_remainder.sort_by(|&a, &b| shares[b].1.cmp(&shares[a].1));
+        for i in by_remainder
+            .into_iter()
+            .take((u64::from(Self::WHOLE) - rounded) as usize)
+        {
+            shares[i].0 += Uint::one();
+        }
+        let [yay, nay, abstain] =
+            shares.map(|(share, _)| share.low_u64() as u16);
+        Self { yay, nay, abstain }
+    }
+}
+
+impl Display for TallyBasisPoints {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let percent = |basis_points: u16| {
+            format!("{}.{:02}%", basis_points / 100, basis_points % 100)
+        };
+        write!(
+            f,
+            "yay {}, nay {}, abstain {}",
+            percent(self.yay),
+            percent(self.nay),
+            percent(self.abstain)
+        )
+    }
+}
+
+/// A strategy deciding the result of a proposal from its tallied votes
+pub trait TallyStrategy {
+    /// The tally type recorded in the result of the proposal
+    fn tally_type(&self) -> TallyType;
+
+    /// Decide the result of the tallied votes, or return `None` if they land
+    /// exactly on a tie
+    fn decide(&self, votes: &TalliedVotes) -> Option<TallyResult>;
+
+    /// The policy resolving the ties of this strategy. Ties are rejected by
+    /// default.
+    fn tie_policy(&self) -> TiePolicy {
+        TiePolicy::default()
+    }
+
+    /// Compute the result of the tallied votes, resolving ties with the
+    /// policy of the strategy
+    fn result(&self, votes: &TalliedVotes) -> TallyResult {
+        self.decide(votes)
+            .unwrap_or_else(|| self.tie_policy().resolve())
+    }
+
+    /// The seed of the random draw resolving the tallied votes, if they
+    /// land on a tie broken at random
+    fn tie_break_seed(&self, votes: &TalliedVotes) -> Option<TieBreakSeed> {
+        match self.tie_policy() {
+            TiePolicy::TieBreaksAtRandom(seed)
+                if self.decide(votes).is_none() =>
+            {
+                Some(seed)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl TallyStrategy for TallyType {
+    fn tally_type(&self) -> TallyType {
+        *self
+    }
+
+    fn decide(&self, votes: &TalliedVotes) -> Option<TallyResult> {
+        let passed = match self {
             TallyType::TwoThirds => {
-                let at_least_two_third_voted = Self::get_total_voted_power(
-                    yay_voting_power,
-                    nay_voting_power,
-                    abstain_voting_power,
-                ) >= total_voting_power
-                    .mul_ceil(Dec::two() / 3);
+                let at_least_two_third_voted = votes.total_voted_power()
+                    >= threshold(votes.total_voting_power, 2, 3);
 
-                let at_least_two_third_voted_yay = yay_voting_power
-                    >= (nay_voting_power + yay_voting_power)
-                        .mul_ceil(Dec::two() / 3);
+                let at_least_two_third_voted_yay = votes.yay_voting_power
+                    >= threshold(
+                        votes.nay_voting_power + votes.yay_voting_power,
+                        2,
+                        3,
+                    );
 
                 at_least_two_third_voted && at_least_two_third_voted_yay
             }
             TallyType::OneHalfOverOneThird => {
-                let at_least_one_third_voted = Self::get_total_voted_power(
-                    yay_voting_power,
-                    nay_voting_power,
-                    abstain_voting_power,
-                ) >= total_voting_power
-                    .mul_ceil(Dec::one() / 3);
+                let at_least_one_third_voted = votes.total_voted_power()
+                    >= threshold(votes.total_voting_power, 1, 3);
+                if !at_least_one_third_voted {
+                    return Some(TallyResult::Rejected);
+                }
+                if votes.is_yay_nay_tie() {
+                    return None;
+                }
 
                 // Yay votes must be more than half of the total votes
-                let more_than_half_voted_yay =
-                    yay_voting_power > nay_voting_power;
-                at_least_one_third_voted && more_than_half_voted_yay
+                votes.yay_voting_power > votes.nay_voting_power
             }
             TallyType::LessOneHalfOverOneThirdNay => {
-                let less_than_one_third = Self::get_total_voted_power(
-                    yay_voting_power,
-                    nay_voting_power,
-                    abstain_voting_power,
-                ) < total_voting_power
-                    .mul_ceil(Dec::one() / 3);
+                let less_than_one_third = votes.total_voted_power()
+                    < threshold(votes.total_voting_power, 1, 3);
+                if less_than_one_third {
+                    return Some(TallyResult::Passed);
+                }
+                if votes.is_yay_nay_tie() {
+                    return None;
+                }
 
                 // Nay votes must be less than half of the total votes
-                let more_than_half_voted_yay =
-                    yay_voting_power > nay_voting_power;
-
-                less_than_one_third || more_than_half_voted_yay
+                votes.yay_voting_power > votes.nay_voting_power
+            }
+            TallyType::ValidatorCount(quorum) => {
+                votes.yay_validators >= *quorum
+            }
+            TallyType::Council(threshold) => {
+                *threshold > 0 && votes.yay_council_weight >= *threshold
             }
         };
 
-        if passed { Self::Passed } else { Self::Rejected }
+        Some(if passed {
+            TallyResult::Passed
+        } else {
+            TallyResult::Rejected
+        })
+    }
+}
+
+/// A tally strategy whose ties are resolved by the given policy, rather
+/// than by the policy of the wrapped strategy
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct WithTiePolicy<S> {
+    /// The wrapped strategy
+    pub strategy: S,
+    /// The policy resolving ties
+    pub tie_policy: TiePolicy,
+}
+
+impl<S: TallyStrategy> TallyStrategy for WithTiePolicy<S> {
+    fn tally_type(&self) -> TallyType {
+        self.strategy.tally_type()
+    }
+
+    fn decide(&self, votes: &TalliedVotes) -> Option<TallyResult> {
+        self.strategy.decide(votes)
+    }
+
+    fn tie_policy(&self) -> TiePolicy {
+        self.tie_policy
+    }
+}
+
+/// The result of a proposal
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub enum TallyResult {
+    /// Proposal was accepted with the associated value
+    Passed,
+    /// Proposal was rejected
+    Rejected,
+}
+
+impl Display for TallyResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TallyResult::Passed => write!(f, "passed"),
+            TallyResult::Rejected => write!(f, "rejected"),
+        }
     }
+}
 
-    fn get_total_voted_power(
-        yay_voting_power: VotePower,
-        nay_voting_power: VotePower,
-        abstain_voting_power: VotePower,
-    ) -> VotePower {
-        yay_voting_power + nay_voting_power + abstain_voting_power
+impl TallyResult {
+    /// Create a new tally result with the given strategy
+    pub fn new(strategy: &impl TallyStrategy, votes: &TalliedVotes) -> Self {
+        strategy.result(votes)
     }
 }
 
 /// The result with votes of a proposal
-#[derive(Clone, Copy, BorshDeserialize, BorshSerialize)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
 pub struct ProposalResult {
     /// The result of a proposal
     pub result: TallyResult,
@@ -196,11 +563,10 @@ impl ProposalResult {
         let at_least_two_third_voted = self.total_yay_power
             + self.total_nay_power
             + self.total_abstain_power
-            >= self.total_voting_power.mul_ceil(Dec::two() / 3);
+            >= threshold(self.total_voting_power, 2, 3);
 
         let at_least_two_thirds_voted_nay = self.total_nay_power
-            >= (self.total_yay_power + self.total_nay_power)
-                .mul_ceil(Dec::two() / 3);
+            >= threshold(self.total_yay_power + self.total_nay_power, 2, 3);
 
         at_least_two_third_voted && at_least_two_thirds_voted_nay
     }
@@ -208,11 +574,40 @@ impl ProposalResult {
 
 impl Display for ProposalResult {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let threshold = match self.tally_type {
-            TallyType::TwoThirds => {
-                self.total_voting_power.mul_ceil(Dec::two() / 3)
+        match self.tally_type {
+            TallyType::ValidatorCount(quorum) => {
+                return write!(
+                    f,
+                    "{} with {} yay votes, {} nay votes and {} abstain votes, \
+                     total voting power: {}, number of validators required to \
+                     vote yay: {}",
+                    self.result,
+                    self.total_yay_power.to_string_native(),
+                    self.total_nay_power.to_string_native(),
+                    self.total_abstain_power.to_string_native(),
+                    self.total_voting_power.to_string_native(),
+                    quorum
+                );
             }
-            _ => self.total_voting_power.mul_ceil(Dec::one() / 3),
+            TallyType::Council(threshold) => {
+                return write!(
+                    f,
+                    "{} with {} yay votes, {} nay votes and {} abstain votes, \
+                     total voting power: {}, weight of council members \
+                     required to vote yay: {}",
+                    self.result,
+                    self.total_yay_power.to_string_native(),
+                    self.total_nay_power.to_string_native(),
+                    self.total_abstain_power.to_string_native(),
+                    self.total_voting_power.to_string_native(),
+                    threshold
+                );
+            }
+            _ => {}
+        }
+        let threshold = match self.tally_type {
+            TallyType::TwoThirds => threshold(self.total_voting_power, 2, 3),
+            _ => threshold(self.total_voting_power, 1, 3),
         };
 
         let thresh_frac =
@@ -234,6 +629,311 @@ impl Display for ProposalResult {
     }
 }
 
+/// The vote of an account on a proposal, along with the result that the
+/// proposal would have if it was tallied with the votes cast so far
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct ProposalVoteProjection {
+    /// The vote of the account, if it voted on the proposal
+    pub vote: Option<ProposalVote>,
+    /// The projected result of the proposal
+    pub projection: ProposalResult,
+    /// Whether the proposal was already tallied, in which case the
+    /// projection is its final result
+    pub is_final: bool,
+}
+
+/// A proposal result archived at the epoch in which it was decided. Unlike
+/// the live proposal keys, archived results are never pruned.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct ArchivedProposalResult {
+    /// The id of the archived proposal
+    pub id: u64,
+    /// The epoch in which the proposal was tallied
+    pub epoch: Epoch,
+    /// The tally of the proposal
+    pub result: ProposalResult,
+}
+
+/// The participation of a validator in the tally of a proposal
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct ValidatorParticipation {
+    /// Whether the validator voted on the proposal
+    pub voted: bool,
+    /// The voting power of the delegations to the validator whose
+    /// delegators, or their governors, voted on a different side than the
+    /// validator
+    pub overridden_power: VotePower,
+}
+
+/// The participation of the validators in the proposals concluded at an
+/// epoch, by proposal id and by validator
+pub type EpochParticipation =
+    BTreeMap<u64, BTreeMap<Address, ValidatorParticipation>>;
+
+/// The validators whose signatures were included in the proof of the
+/// validator set update signed at an epoch
+pub type ValsetSigParticipation = BTreeSet<Address>;
+
+/// The verdict of a validity predicate triggered by the code of a proposal
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct VpVerdict {
+    /// The address whose validity predicate was run
+    pub address: Address,
+    /// Whether the validity predicate accepted the changes of the code
+    pub accepted: bool,
+    /// The error the validity predicate failed with, if any
+    pub error: Option<String>,
+}
+
+/// The detailed outcome of the execution of the code of a passed proposal
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct ProposalExecutionResult {
+    /// Whether the code was applied and its changes committed
+    pub accepted: bool,
+    /// The gas used by the code and by the validity predicates it
+    /// triggered, in sub-units of gas
+    pub gas_used: u64,
+    /// The validity predicates triggered by the code, with their verdicts
+    pub vps: Vec<VpVerdict>,
+    /// The error of the code, if it failed before its changes could be
+    /// validated
+    pub error: Option<String>,
+    /// Whether the execution of the code panicked, the panic having been
+    /// contained and the code rejected
+    pub panicked: bool,
+}
+
+impl ProposalExecutionResult {
+    /// The result of a proposal code which couldn't be applied, failing
+    /// with the given error
+    pub fn failed(gas_used: u64, error: impl ToString) -> Self {
+        Self {
+            accepted: false,
+            gas_used,
+            vps: vec![],
+            error: Some(error.to_string()),
+            panicked: false,
+        }
+    }
+
+    /// Iterate over the validity predicates which rejected the code
+    pub fn rejected_vps(&self) -> impl Iterator<Item = &VpVerdict> {
+        self.vps.iter().filter(|verdict| !verdict.accepted)
+    }
+}
+
+impl Display for ProposalExecutionResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Proposal code {}",
+            if self.accepted {
+                "accepted"
+            } else if self.panicked {
+                "panicked"
+            } else {
+                "rejected"
+            }
+        )?;
+        if let Some(error) = &self.error {
+            writeln!(f, "Error: {error}")?;
+        }
+        for verdict in &self.vps {
+            write!(
+                f,
+                "VP of {}: {}",
+                verdict.address,
+                if verdict.accepted {
+                    "accepted"
+                } else {
+                    "rejected"
+                }
+            )?;
+            match &verdict.error {
+                Some(error) => writeln!(f, " ({error})")?,
+                None => writeln!(f)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Where the funds locked by a concluded proposal went, recorded once they
+/// are released
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct ReleasedFunds {
+    /// The amount returned to the author of the proposal
+    pub returned: token::Amount,
+    /// The amount burned, kept from the author of a rejected proposal
+    pub burned: token::Amount,
+    /// The amount moved to the treasury
+    pub to_treasury: token::Amount,
+}
+
+/// The structured result the code of a proposal may write under
+/// [`crate::storage::keys::get_proposal_output_key`] while being executed,
+/// by name, e.g. the amount of tokens actually minted by the proposal
+pub type ProposalOutput = BTreeMap<String, String>;
+
+/// The maximum size in bytes, counting the names and the values of its
+/// entries, of the output of a proposal reported in its event
+pub const MAX_PROPOSAL_OUTPUT_SIZE: usize = 1024;
+
+/// Bound the output of a proposal to [`MAX_PROPOSAL_OUTPUT_SIZE`], keeping
+/// its entries in order of name for as long as they fit. Returns the bounded
+/// output, along with whether some entries were dropped.
+pub fn bound_proposal_output(output: ProposalOutput) -> (ProposalOutput, bool) {
+    let len = output.len();
+    let mut size = 0_usize;
+    let bounded: ProposalOutput = output
+        .into_iter()
+        .take_while(|(name, value)| {
+            size = size.saturating_add(name.len()).saturating_add(value.len());
+            size <= MAX_PROPOSAL_OUTPUT_SIZE
+        })
+        .collect();
+    let truncated = bounded.len() < len;
+    (bounded, truncated)
+}
+
+/// A protocol parameter written by a passed parameter change proposal, with
+/// the Borsh encoding of its value before and after the change
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AppliedParameterChange {
+    /// The storage key of the parameter
+    pub key: Key,
+    /// The value of the parameter before the change, if it was set
+    pub old_value: Option<Vec<u8>>,
+    /// The value written by the proposal
+    pub new_value: Vec<u8>,
+}
+
+/// A storage key written by the code of a proposal when dry-run
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct WrittenKey {
+    /// The written key
+    pub key: Key,
+    /// Whether the key falls under a prefix protected from proposal code
+    pub protected: bool,
+    /// Whether the key falls outside of the parameters of the protocol and
+    /// of its native modules
+    pub outside_parameters: bool,
+}
+
+/// The storage keys the code of a proposal writes, found by applying it
+/// against a scratch write log, without committing anything
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct ProposalWriteSurface {
+    /// The hash of the dry-run code
+    pub code_hash: Hash,
+    /// The height of the last committed block when the code was dry-run
+    pub height: BlockHeight,
+    /// The keys written by the code, ordered
+    pub keys: Vec<WrittenKey>,
+    /// Whether the code would have been executed at `height`, i.e. it was
+    /// applied and accepted by all the validity predicates it triggered
+    pub execution_allowed: bool,
+    /// The error of the code, if it failed before its changes could be
+    /// validated
+    pub error: Option<String>,
+}
+
+impl ProposalWriteSurface {
+    /// Iterate over the written keys falling under a protected prefix
+    pub fn protected_keys(&self) -> impl Iterator<Item = &Key> {
+        self.keys
+            .iter()
+            .filter(|written| written.protected)
+            .map(|written| &written.key)
+    }
+}
+
 /// General representation of a vote
 #[derive(Debug, Clone)]
 pub enum TallyVote {
@@ -295,6 +995,61 @@ impl TallyVote {
     }
 }
 
+/// A vote counted by the tally, weighted by the voting power of its voter
+#[derive(Debug, Clone)]
+pub struct WeightedVote {
+    /// The vote
+    pub vote: TallyVote,
+    /// The voting power counted for the vote
+    pub voting_power: VotePower,
+    /// Whether the vote was cast by a validator with some stake, which
+    /// counts towards a quorum of validators
+    pub staked_validator: bool,
+    /// The weight of the vote towards the threshold of a council tally,
+    /// which is zero unless it was cast by a member of the council
+    pub council_weight: u64,
+}
+
+/// The parameters of a tally
+#[derive(Debug, Clone)]
+pub struct TallyParams<S> {
+    /// The strategy deciding the result of the tally
+    pub strategy: S,
+    /// The total voting power during the proposal tally
+    pub total_voting_power: VotePower,
+    /// The maximum fraction of the total voting power counted for any
+    /// single vote, if any
+    pub max_voting_power_fraction: Option<Dec>,
+}
+
+/// The outcome of a tally
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TallyOutcome {
+    /// The result of the tally
+    pub result: TallyResult,
+    /// The type of tally of the strategy which decided the result
+    pub tally_type: TallyType,
+    /// The tallied votes
+    pub votes: TalliedVotes,
+    /// The input of the random draw which broke a tie of the tally, if any
+    pub tie_break: Option<TieBreakSeed>,
+    /// The shares of the tallied votes in the voting power cast
+    pub basis_points: TallyBasisPoints,
+}
+
+impl From<TallyOutcome> for ProposalResult {
+    fn from(outcome: TallyOutcome) -> Self {
+        ProposalResult {
+            result: outcome.result,
+            tally_type: outcome.tally_type,
+            total_voting_power: outcome.votes.total_voting_power,
+            total_yay_power: outcome.votes.yay_voting_power,
+            total_nay_power: outcome.votes.nay_voting_power,
+            total_abstain_power: outcome.votes.abstain_voting_power,
+        }
+    }
+}
+
 /// Proposal structure holding votes information necessary to compute the
 /// outcome
 #[derive(Default, Debug, Clone)]
@@ -307,6 +1062,9 @@ pub struct ProposalVotes {
     pub delegators_vote: HashMap<Address, TallyVote>,
     /// Map from delegator address to the corresponding validator voting power
     pub delegator_voting_power: HashMap<Address, HashMap<Address, VotePower>>,
+    /// Map from delegator address to the governor to whom it has delegated
+    /// its governance votes
+    pub governors: HashMap<Address, Address>,
 }
 
 impl ProposalVotes {
@@ -337,99 +1095,357 @@ impl ProposalVotes {
         validator_address: &Address,
         voting_power: VotePower,
         vote: TallyVote,
+    ) {
+        self.add_delegation(address, validator_address, voting_power);
+        self.delegators_vote.insert(address.clone(), vote);
+    }
+
+    /// Add the voting power of a delegation whose delegator hasn't voted,
+    /// which follows the vote of the governor of the delegator, if any
+    pub fn add_delegation(
+        &mut self,
+        address: &Address,
+        validator_address: &Address,
+        voting_power: VotePower,
     ) {
         self.delegator_voting_power
             .entry(address.clone())
             .or_default()
             .insert(validator_address.clone(), voting_power);
-        self.delegators_vote.insert(address.clone(), vote);
     }
-}
 
-/// Compute the result of a proposal
-pub fn compute_proposal_result(
-    votes: ProposalVotes,
-    total_voting_power: VotePower,
-    tally_type: TallyType,
-) -> ProposalResult {
-    let mut yay_voting_power = VotePower::default();
-    let mut nay_voting_power = VotePower::default();
-    let mut abstain_voting_power = VotePower::default();
-
-    for (address, vote_power) in votes.validator_voting_power {
-        let vote_type = votes.validators_vote.get(&address);
-        if let Some(vote) = vote_type {
-            if vote.is_yay() {
-                yay_voting_power += vote_power;
-            } else if vote.is_nay() {
-                nay_voting_power += vote_power;
-            } else if vote.is_abstain() {
-                abstain_voting_power += vote_power;
+    /// Add the governor to whom a delegator has delegated its governance
+    /// votes
+    pub fn add_governor(&mut self, address: &Address, governor: &Address) {
+        self.governors.insert(address.clone(), governor.clone());
+    }
+
+    /// Compute the participation of the given validators in the proposal,
+    /// along with that of any other validator that voted on it
+    pub fn validators_participation(
+        &self,
+        validators: impl IntoIterator<Item = Address>,
+    ) -> BTreeMap<Address, ValidatorParticipation> {
+        let mut participation: BTreeMap<Address, ValidatorParticipation> =
+            validators
+                .into_iter()
+                .chain(self.validators_vote.keys().cloned())
+                .map(|validator| {
+                    let voted = self.validators_vote.contains_key(&validator);
+                    (
+                        validator,
+                        ValidatorParticipation {
+                            voted,
+                            ..Default::default()
+                        },
+                    )
+                })
+                .collect();
+
+        for (delegator, delegations) in &self.delegator_voting_power {
+            let delegator_vote = match self.delegator_vote(delegator) {
+                Some(vote) => vote,
+                None => continue,
+            };
+            for (validator, voting_power) in delegations {
+                match self.validators_vote.get(validator) {
+                    Some(validator_vote)
+                        if !validator_vote.is_same_side(delegator_vote) =>
+                    {
+                        if let Some(participation) =
+                            participation.get_mut(validator)
+                        {
+                            participation.overridden_power += *voting_power;
+                        }
+                    }
+                    _ => {}
+                }
             }
         }
+        participation
     }
 
-    for (delegator, delegations) in votes.delegator_voting_power {
-        let delegator_vote = match votes.delegators_vote.get(&delegator) {
-            Some(vote) => vote,
-            None => continue,
-        };
-        for (validator, voting_power) in delegations {
-            let validator_vote = votes.validators_vote.get(&validator);
-            if let Some(validator_vote) = validator_vote {
-                let validator_vote_is_same_side =
-                    validator_vote.is_same_side(delegator_vote);
-
-                if !validator_vote_is_same_side {
-                    if delegator_vote.is_yay() {
-                        yay_voting_power += voting_power;
-                        if validator_vote.is_nay() {
-                            nay_voting_power -= voting_power;
-                        } else if validator_vote.is_abstain() {
-                            abstain_voting_power -= voting_power;
-                        }
-                    } else if delegator_vote.is_nay() {
-                        nay_voting_power += voting_power;
-                        if validator_vote.is_yay() {
-                            yay_voting_power -= voting_power;
-                        } else if validator_vote.is_abstain() {
-                            abstain_voting_power -= voting_power;
-                        }
-                    } else if delegator_vote.is_abstain() {
-                        abstain_voting_power += voting_power;
-                        if validator_vote.is_yay() {
-                            yay_voting_power -= voting_power;
-                        } else if validator_vote.is_nay() {
-                            nay_voting_power -= voting_power;
+    /// Resolve the votes into the voting power counted for each voter. The
+    /// voting power of a validator is net of the delegations whose
+    /// delegators (or their governors) voted on a different side, which is
+    /// counted for the delegators instead, along with their delegations to
+    /// validators that didn't vote.
+    pub fn weighted_votes(&self) -> Vec<WeightedVote> {
+        // The votes counted for the delegations, in order of precedence the
+        // direct votes of the delegators, or the votes of their governors
+        let delegators_vote: HashMap<&Address, &TallyVote> = self
+            .delegator_voting_power
+            .keys()
+            .filter_map(|delegator| {
+                self.delegator_vote(delegator).map(|vote| (delegator, vote))
+            })
+            .collect();
+
+        // The voting power of each validator that voted, net of the
+        // delegations whose delegators voted on a different side
+        let mut validators_power: HashMap<&Address, VotePower> = self
+            .validator_voting_power
+            .iter()
+            .filter(|(address, _)| self.validators_vote.contains_key(*address))
+            .map(|(address, voting_power)| (address, *voting_power))
+            .collect();
+        // The voting power of each delegator that voted, from the
+        // delegations to validators that either didn't vote or voted on a
+        // different side
+        let mut delegators_power: HashMap<&Address, VotePower> = HashMap::new();
+
+        for (delegator, delegations) in &self.delegator_voting_power {
+            let delegator_vote = match delegators_vote.get(delegator) {
+                Some(vote) => vote,
+                None => continue,
+            };
+            for (validator, voting_power) in delegations {
+                match self.validators_vote.get(validator) {
+                    Some(validator_vote)
+                        if validator_vote.is_same_side(delegator_vote) => {}
+                    Some(_) => {
+                        if let Some(validator_power) =
+                            validators_power.get_mut(validator)
+                        {
+                            *validator_power -= *voting_power;
                         }
+                        *delegators_power.entry(delegator).or_default() +=
+                            *voting_power;
+                    }
+                    None => {
+                        *delegators_power.entry(delegator).or_default() +=
+                            *voting_power;
                     }
                 }
-            } else if delegator_vote.is_yay() {
-                yay_voting_power += voting_power;
-            } else if delegator_vote.is_nay() {
-                nay_voting_power += voting_power;
-            } else if delegator_vote.is_abstain() {
-                abstain_voting_power += voting_power;
             }
         }
+
+        let validators = validators_power.into_iter().filter_map(
+            |(validator, voting_power)| {
+                let vote = self.validators_vote.get(validator)?;
+                let staked_validator = self
+                    .validator_voting_power
+                    .get(validator)
+                    .map_or(false, |stake| !stake.is_zero());
+                Some(WeightedVote {
+                    vote: vote.clone(),
+                    voting_power,
+                    staked_validator,
+                    council_weight: 0,
+                })
+            },
+        );
+        let delegators = delegators_power.into_iter().filter_map(
+            |(delegator, voting_power)| {
+                let vote = delegators_vote.get(delegator)?;
+                Some(WeightedVote {
+                    vote: (*vote).clone(),
+                    voting_power,
+                    staked_validator: false,
+                    council_weight: 0,
+                })
+            },
+        );
+        validators.chain(delegators).collect()
+    }
+
+    /// Resolve the votes cast directly by the members of the given council,
+    /// one per member that voted, weighted by the weight of the member. They
+    /// carry no voting power, such that they only count towards the
+    /// threshold of a council tally, on top of the
+    /// [`ProposalVotes::weighted_votes`] of the members.
+    pub fn council_votes(&self, council: &Council) -> Vec<WeightedVote> {
+        council
+            .members
+            .iter()
+            .filter_map(|member| {
+                let vote = self.direct_vote(member)?;
+                Some(WeightedVote {
+                    vote: vote.clone(),
+                    voting_power: VotePower::zero(),
+                    staked_validator: false,
+                    council_weight: council.weight(member),
+                })
+            })
+            .collect()
+    }
+
+    /// Get the vote cast directly by the given voter, as either a validator
+    /// or a delegator
+    fn direct_vote(&self, voter: &Address) -> Option<&TallyVote> {
+        self.delegators_vote
+            .get(voter)
+            .or_else(|| self.validators_vote.get(voter))
+    }
+
+    /// Get the vote counted for the delegations of the given delegator. A
+    /// direct vote of the delegator always prevails. Otherwise, the vote
+    /// of its governor is followed, or that of the governor of its
+    /// governor, and so on up to [`MAX_VOTE_DELEGATION_DEPTH`] delegations.
+    /// If none of them voted, the delegations follow the votes of their
+    /// validators, and `None` is returned.
+    pub fn delegator_vote(&self, delegator: &Address) -> Option<&TallyVote> {
+        if let Some(vote) = self.direct_vote(delegator) {
+            return Some(vote);
+        }
+        let mut voter = delegator;
+        for _ in 0..MAX_VOTE_DELEGATION_DEPTH {
+            voter = self.governors.get(voter)?;
+            if voter == delegator {
+                return None;
+            }
+            if let Some(vote) = self.direct_vote(voter) {
+                return Some(vote);
+            }
+        }
+        None
+    }
+}
+
+/// Compute the result of a proposal
+pub fn compute_proposal_result(
+    votes: ProposalVotes,
+    total_voting_power: VotePower,
+    strategy: impl TallyStrategy,
+) -> ProposalResult {
+    compute_capped_proposal_result(votes, total_voting_power, strategy, None)
+}
+
+/// Compute the result of a proposal, where the voting power counted for any
+/// single voter is capped at `max_voting_power_fraction` of the total voting
+/// power. The voting power above the cap is discarded, rather than
+/// redistributed among the other voters.
+pub fn compute_capped_proposal_result(
+    votes: ProposalVotes,
+    total_voting_power: VotePower,
+    strategy: impl TallyStrategy,
+    max_voting_power_fraction: Option<Dec>,
+) -> ProposalResult {
+    tally(
+        votes.weighted_votes(),
+        TallyParams {
+            strategy,
+            total_voting_power,
+            max_voting_power_fraction,
+        },
+    )
+    .into()
+}
+
+/// Tally the given weighted votes, deciding the result of a proposal with
+/// the thresholds, quorums and tie policy of the strategy in `params`.
+///
+/// The voting power counted for any single vote is capped at the
+/// `max_voting_power_fraction` of `params`, if any. Unlike
+/// [`compute_capped_proposal_result`], the tally doesn't depend on the
+/// storage or on the delegations of the voters, which must have been
+/// resolved into the voting power of each vote beforehand.
+pub fn tally<S: TallyStrategy>(
+    votes: impl IntoIterator<Item = WeightedVote>,
+    params: TallyParams<S>,
+) -> TallyOutcome {
+    let max_voting_power =
+        params.max_voting_power_fraction.and_then(|fraction| {
+            dec_fraction_of(params.total_voting_power, fraction)
+        });
+
+    let mut tallied = TalliedVotes {
+        total_voting_power: params.total_voting_power,
+        ..Default::default()
+    };
+    for WeightedVote {
+        vote,
+        voting_power,
+        staked_validator,
+        council_weight,
+    } in votes
+    {
+        // Only the validators voting yay with some stake count towards a
+        // validator quorum, irrespective of their delegators' votes
+        if staked_validator && vote.is_yay() {
+            tallied.yay_validators += 1;
+        }
+        if council_weight > 0 && vote.is_yay() {
+            tallied.yay_council_members += 1;
+            tallied.yay_council_weight =
+                tallied.yay_council_weight.saturating_add(council_weight);
+        }
+        let voting_power = match max_voting_power {
+            Some(max_voting_power) => voting_power.min(max_voting_power),
+            None => voting_power,
+        };
+        if vote.is_yay() {
+            tallied.yay_voting_power += voting_power;
+        } else if vote.is_nay() {
+            tallied.nay_voting_power += voting_power;
+        } else if vote.is_abstain() {
+            tallied.abstain_voting_power += voting_power;
+        }
+    }
+
+    TallyOutcome {
+        result: TallyResult::new(&params.strategy, &tallied),
+        tally_type: params.strategy.tally_type(),
+        tie_break: params.strategy.tie_break_seed(&tallied),
+        basis_points: tallied.basis_points(),
+        votes: tallied,
     }
+}
 
-    let tally_result = TallyResult::new(
-        &tally_type,
-        yay_voting_power,
-        nay_voting_power,
-        abstain_voting_power,
-        total_voting_power,
-    );
+/// Compute `numerator / denominator` of the given `amount`, rounded up to
+/// the nearest unit of the amount.
+///
+/// All the fractions of the tally thresholds, of the voting power caps and
+/// of the proposal refunds go through this rounding, so that every node
+/// computes them identically. Rounding up makes the thresholds exact:
+/// `power >= fraction_of(amount, n, d)` holds if and only if
+/// `power * d >= amount * n`. The product is computed over 512 bits and
+/// never overflows. Returns `None` if the `denominator` is zero, or if the
+/// result doesn't fit in an amount, which is only possible for fractions
+/// greater than one.
+pub fn fraction_of(
+    amount: token::Amount,
+    numerator: u64,
+    denominator: u64,
+) -> Option<token::Amount> {
+    mul_div_ceil(amount, Uint::from(numerator), Uint::from(denominator))
+}
 
-    ProposalResult {
-        result: tally_result,
-        tally_type,
-        total_voting_power,
-        total_yay_power: yay_voting_power,
-        total_nay_power: nay_voting_power,
-        total_abstain_power: abstain_voting_power,
+/// Compute the given decimal `fraction` of the `amount`, with the rounding
+/// of [`fraction_of`]. Returns `None` if the `fraction` is negative, or if
+/// the result doesn't fit in an amount.
+pub fn dec_fraction_of(
+    amount: token::Amount,
+    fraction: Dec,
+) -> Option<token::Amount> {
+    if fraction.is_negative() {
+        return None;
     }
+    let scale = Uint::from(10u64.pow(POS_DECIMAL_PRECISION as u32));
+    mul_div_ceil(amount, fraction.abs(), scale)
+}
+
+fn mul_div_ceil(
+    amount: token::Amount,
+    numerator: Uint,
+    denominator: Uint,
+) -> Option<token::Amount> {
+    let (quotient, remainder) = amount
+        .raw_amount()
+        .checked_mul_div(numerator, denominator)?;
+    let raw = if remainder.is_zero() {
+        quotient
+    } else {
+        quotient.checked_add(Uint::one())?
+    };
+    token::Amount::from_uint(raw, 0).ok()
+}
+
+/// The voting power required by a tally threshold of `numerator /
+/// denominator` of the given voting power
+fn threshold(power: VotePower, numerator: u64, denominator: u64) -> VotePower {
+    fraction_of(power, numerator, denominator)
+        .expect("A fraction of at most one of an amount must fit in it")
 }
 
 /// Calculate the valid voting window for validator given a proposal epoch
@@ -450,11 +1466,84 @@ pub fn is_valid_validator_voting_period(
 
 #[cfg(test)]
 mod test {
+    use std::collections::BTreeSet;
     use std::ops::{Add, Sub};
+    use std::str::FromStr;
 
+    use namada_core::borsh::BorshSerializeExt;
     use namada_core::types::address;
+    use proptest::collection;
+    use proptest::prelude::*;
 
     use super::*;
+    use crate::storage::vote::testing::arb_proposal_vote;
+
+    /// Pin the Borsh and JSON layouts of a proposal result, which are
+    /// decoded by clients outside of this repository, and from the results
+    /// stored by earlier versions of the ledger
+    #[test]
+    fn test_proposal_result_encoding() {
+        let result = ProposalResult {
+            result: TallyResult::Passed,
+            tally_type: TallyType::OneHalfOverOneThird,
+            total_voting_power: token::Amount::from(100),
+            total_yay_power: token::Amount::from(60),
+            total_nay_power: token::Amount::from(10),
+            total_abstain_power: token::Amount::zero(),
+        };
+
+        // amounts are encoded as 256-bit little-endian integers
+        let amount_bytes = |amount: u8| {
+            let mut bytes = [0u8; 32];
+            bytes[0] = amount;
+            bytes
+        };
+        let expected_borsh = [
+            &[0, 1][..],
+            &amount_bytes(100),
+            &amount_bytes(60),
+            &amount_bytes(10),
+            &amount_bytes(0),
+        ]
+        .concat();
+        assert_eq!(result.serialize_to_vec(), expected_borsh);
+        assert_eq!(
+            ProposalResult::try_from_slice(&expected_borsh).unwrap(),
+            result
+        );
+
+        let expected_json = serde_json::json!({
+            "result": "Passed",
+            "tally_type": "OneHalfOverOneThird",
+            "total_voting_power": "100",
+            "total_yay_power": "60",
+            "total_nay_power": "10",
+            "total_abstain_power": "0",
+        });
+        assert_eq!(serde_json::to_value(result).unwrap(), expected_json);
+        assert_eq!(
+            serde_json::from_value::<ProposalResult>(expected_json).unwrap(),
+            result
+        );
+    }
+
+    /// Test that the results of council tallies display the weight the
+    /// council members voting yay needed, rather than a fraction of the
+    /// voting power
+    #[test]
+    fn test_council_proposal_result_display() {
+        let result = ProposalResult {
+            result: TallyResult::Rejected,
+            tally_type: TallyType::Council(3),
+            total_voting_power: token::Amount::from(100),
+            total_yay_power: token::Amount::from(60),
+            total_nay_power: token::Amount::from(10),
+            total_abstain_power: token::Amount::zero(),
+        };
+        let displayed = result.to_string();
+        assert!(displayed.ends_with("council members required to vote yay: 3"));
+        assert!(!displayed.contains("threshold"));
+    }
 
     #[test]
     fn test_proposal_result_no_votes_should_fail() {
@@ -1401,4 +2490,1245 @@ mod test {
 
         assert!(!proposal_result.two_thirds_nay_over_two_thirds_total())
     }
+
+    #[test]
+    fn test_proposal_capped_dominant_validator() {
+        let mut proposal_votes = ProposalVotes::default();
+
+        let validator_address = address::testing::established_address_1();
+        proposal_votes.add_validator(
+            &validator_address,
+            token::Amount::from_u64(60),
+            ProposalVote::Yay.into(),
+        );
+
+        let validator_address_two = address::testing::established_address_2();
+        proposal_votes.add_validator(
+            &validator_address_two,
+            token::Amount::from_u64(40),
+            ProposalVote::Nay.into(),
+        );
+
+        let uncapped_result = compute_proposal_result(
+            proposal_votes.clone(),
+            token::Amount::from(100),
+            TallyType::OneHalfOverOneThird,
+        );
+
+        assert!(matches!(uncapped_result.result, TallyResult::Passed));
+        assert_eq!(
+            uncapped_result.total_yay_power,
+            token::Amount::from(60),
+            "yay"
+        );
+
+        let capped_result = compute_capped_proposal_result(
+            proposal_votes,
+            token::Amount::from(100),
+            TallyType::OneHalfOverOneThird,
+            Some(Dec::new(3, 1).unwrap()),
+        );
+
+        assert!(matches!(capped_result.result, TallyResult::Rejected));
+        assert_eq!(
+            capped_result.total_voting_power,
+            token::Amount::from(100),
+            "total"
+        );
+        assert_eq!(
+            capped_result.total_yay_power,
+            token::Amount::from(30),
+            "yay"
+        );
+        assert_eq!(
+            capped_result.total_nay_power,
+            token::Amount::from(30),
+            "nay"
+        );
+        assert_eq!(
+            capped_result.total_abstain_power,
+            token::Amount::zero(),
+            "abstain"
+        );
+    }
+
+    #[test]
+    fn test_proposal_capped_after_delegator_override() {
+        let mut proposal_votes = ProposalVotes::default();
+
+        let validator_address = address::testing::established_address_1();
+        proposal_votes.add_validator(
+            &validator_address,
+            token::Amount::from_u64(70),
+            ProposalVote::Yay.into(),
+        );
+
+        let validator_address_two = address::testing::established_address_2();
+        proposal_votes.add_validator(
+            &validator_address_two,
+            token::Amount::from_u64(30),
+            ProposalVote::Nay.into(),
+        );
+
+        let delegator_address = address::testing::established_address_3();
+        proposal_votes.add_delegator(
+            &delegator_address,
+            &validator_address,
+            token::Amount::from_u64(20),
+            ProposalVote::Nay.into(),
+        );
+
+        let uncapped_result = compute_proposal_result(
+            proposal_votes.clone(),
+            token::Amount::from(100),
+            TallyType::TwoThirds,
+        );
+
+        assert_eq!(
+            uncapped_result.total_yay_power,
+            token::Amount::from(50),
+            "yay"
+        );
+        assert_eq!(
+            uncapped_result.total_nay_power,
+            token::Amount::from(50),
+            "nay"
+        );
+
+        // the cap applies to the validator's voting power net of the
+        // delegation whose delegator voted differently
+        let capped_result = compute_capped_proposal_result(
+            proposal_votes,
+            token::Amount::from(100),
+            TallyType::TwoThirds,
+            Some(Dec::new(4, 1).unwrap()),
+        );
+
+        assert!(matches!(capped_result.result, TallyResult::Rejected));
+        assert_eq!(
+            capped_result.total_yay_power,
+            token::Amount::from(40),
+            "yay"
+        );
+        assert_eq!(
+            capped_result.total_nay_power,
+            token::Amount::from(50),
+            "nay"
+        );
+    }
+
+    /// Test that the fractions of amounts are rounded up and that exact
+    /// fractions are not rounded
+    #[test]
+    fn test_fraction_of_rounding() {
+        let amount = token::Amount::from(100);
+        assert_eq!(fraction_of(amount, 2, 3), Some(token::Amount::from(67)));
+        assert_eq!(fraction_of(amount, 1, 3), Some(token::Amount::from(34)));
+        assert_eq!(fraction_of(amount, 1, 4), Some(token::Amount::from(25)));
+        assert_eq!(fraction_of(amount, 0, 3), Some(token::Amount::zero()));
+        assert_eq!(fraction_of(amount, 1, 0), None);
+        assert_eq!(
+            dec_fraction_of(amount, Dec::new(1, 1).unwrap()),
+            Some(token::Amount::from(10))
+        );
+        assert_eq!(dec_fraction_of(amount, -Dec::one()), None);
+
+        // The fraction of the largest amount doesn't overflow
+        let max = token::Amount::max();
+        assert_eq!(fraction_of(max, 1, 1), Some(max));
+        assert!(fraction_of(max, 2, 3).unwrap() < max);
+        assert_eq!(fraction_of(max, 2, 1), None);
+    }
+
+    /// Test that emergency proposals are tallied by the number of
+    /// validators voting yay only when the validator quorum is set.
+    #[test]
+    fn test_emergency_tally_type() {
+        let pause = ProposalType::ETHBridgePause(
+            crate::storage::proposal::BridgePauseAction::Pause,
+        );
+        assert_eq!(
+            TallyType::from(pause.clone(), false, None, None),
+            TallyType::TwoThirds
+        );
+        assert_eq!(
+            TallyType::from(pause, false, Some(4), None),
+            TallyType::ValidatorCount(4)
+        );
+        assert_eq!(
+            TallyType::from(ProposalType::Default(None), false, Some(4), None),
+            TallyType::TwoThirds
+        );
+    }
+
+    /// Test that a proposal backed by a single validator holding most of
+    /// the stake passes a stake-weighted tally but fails a validator-count
+    /// tally, and that delegators voting yay don't add to the count.
+    #[test]
+    fn test_validator_count_rejects_dominant_validator() {
+        let mut proposal_votes = ProposalVotes::default();
+
+        let validator_address = address::testing::established_address_1();
+        proposal_votes.add_validator(
+            &validator_address,
+            token::Amount::from_u64(90),
+            ProposalVote::Yay.into(),
+        );
+        let validator_address_two = address::testing::established_address_2();
+        proposal_votes.add_validator(
+            &validator_address_two,
+            token::Amount::from_u64(5),
+            ProposalVote::Nay.into(),
+        );
+        let validator_address_three = address::testing::established_address_3();
+        proposal_votes.add_validator(
+            &validator_address_three,
+            token::Amount::from_u64(5),
+            ProposalVote::Nay.into(),
+        );
+        for delegator_address in [
+            address::testing::established_address_4(),
+            address::testing::gen_established_address(),
+        ] {
+            proposal_votes.add_delegator(
+                &delegator_address,
+                &validator_address_two,
+                token::Amount::from_u64(1),
+                ProposalVote::Yay.into(),
+            );
+        }
+
+        let stake_result = compute_proposal_result(
+            proposal_votes.clone(),
+            token::Amount::from(100),
+            TallyType::TwoThirds,
+        );
+        assert!(matches!(stake_result.result, TallyResult::Passed));
+
+        let count_result = compute_proposal_result(
+            proposal_votes,
+            token::Amount::from(100),
+            TallyType::ValidatorCount(2),
+        );
+        assert!(matches!(count_result.result, TallyResult::Rejected));
+        assert_eq!(count_result.tally_type, TallyType::ValidatorCount(2));
+        assert_eq!(count_result.total_yay_power, token::Amount::from(92));
+    }
+
+    /// Test that a proposal backed by many validators holding little stake
+    /// fails a stake-weighted tally but passes a validator-count tally, and
+    /// that validators without stake don't add to the count.
+    #[test]
+    fn test_validator_count_accepts_many_small_validators() {
+        let mut proposal_votes = ProposalVotes::default();
+
+        proposal_votes.add_validator(
+            &address::testing::established_address_1(),
+            token::Amount::from_u64(80),
+            ProposalVote::Nay.into(),
+        );
+        for validator_address in [
+            address::testing::established_address_2(),
+            address::testing::established_address_3(),
+        ] {
+            proposal_votes.add_validator(
+                &validator_address,
+                token::Amount::from_u64(10),
+                ProposalVote::Yay.into(),
+            );
+        }
+        proposal_votes.add_validator(
+            &address::testing::established_address_4(),
+            token::Amount::zero(),
+            ProposalVote::Yay.into(),
+        );
+
+        let stake_result = compute_proposal_result(
+            proposal_votes.clone(),
+            token::Amount::from(100),
+            TallyType::TwoThirds,
+        );
+        assert!(matches!(stake_result.result, TallyResult::Rejected));
+
+        let count_result = compute_proposal_result(
+            proposal_votes.clone(),
+            token::Amount::from(100),
+            TallyType::ValidatorCount(2),
+        );
+        assert!(matches!(count_result.result, TallyResult::Passed));
+
+        // the validator without stake doesn't count towards the quorum
+        let count_result = compute_proposal_result(
+            proposal_votes,
+            token::Amount::from(100),
+            TallyType::ValidatorCount(3),
+        );
+        assert!(matches!(count_result.result, TallyResult::Rejected));
+    }
+
+    /// Tally the given votes with a council tally, whose council is made
+    /// of the given members, each weighing 1
+    fn tally_council(
+        votes: &ProposalVotes,
+        members: &BTreeSet<Address>,
+        threshold: u64,
+    ) -> TallyOutcome {
+        let council = Council {
+            members: members.clone(),
+            threshold,
+            weights: BTreeMap::new(),
+        };
+        tally_weighted_council(votes, &council)
+    }
+
+    /// Tally the given votes with a council tally of the given council
+    fn tally_weighted_council(
+        votes: &ProposalVotes,
+        council: &Council,
+    ) -> TallyOutcome {
+        tally(
+            votes
+                .weighted_votes()
+                .into_iter()
+                .chain(votes.council_votes(council)),
+            TallyParams {
+                strategy: TallyType::Council(council.threshold),
+                total_voting_power: token::Amount::from(100),
+                max_voting_power_fraction: None,
+            },
+        )
+    }
+
+    /// Test that council proposals pass once the yay votes of the council
+    /// members reach the threshold, regardless of their stake, and that the
+    /// votes of non-members don't count.
+    #[test]
+    fn test_council_tally() {
+        let member_validator = address::testing::established_address_1();
+        let member_delegator = address::testing::established_address_2();
+        let other_member = address::testing::established_address_3();
+        let non_member = address::testing::established_address_4();
+        let members = BTreeSet::from([
+            member_validator.clone(),
+            member_delegator.clone(),
+            other_member.clone(),
+        ]);
+
+        let mut proposal_votes = ProposalVotes::default();
+        proposal_votes.add_validator(
+            &non_member,
+            token::Amount::from_u64(90),
+            ProposalVote::Yay.into(),
+        );
+        proposal_votes.add_validator(
+            &member_validator,
+            token::Amount::from_u64(5),
+            ProposalVote::Yay.into(),
+        );
+        proposal_votes.add_validator(
+            &other_member,
+            token::Amount::from_u64(5),
+            ProposalVote::Nay.into(),
+        );
+
+        // A single member voted yay, falling short of the threshold, even
+        // though the proposal would pass a stake-weighted tally
+        let outcome = tally_council(&proposal_votes, &members, 2);
+        assert_eq!(outcome.result, TallyResult::Rejected);
+        assert_eq!(outcome.tally_type, TallyType::Council(2));
+        assert_eq!(outcome.votes.yay_council_members, 1);
+        assert_eq!(outcome.votes.yay_voting_power, token::Amount::from(95));
+        assert_eq!(
+            TallyType::TwoThirds.result(&outcome.votes),
+            TallyResult::Passed
+        );
+
+        // A member without much stake crosses the threshold
+        proposal_votes.add_delegator(
+            &member_delegator,
+            &other_member,
+            token::Amount::from_u64(1),
+            ProposalVote::Yay.into(),
+        );
+        let outcome = tally_council(&proposal_votes, &members, 2);
+        assert_eq!(outcome.result, TallyResult::Passed);
+        assert_eq!(outcome.votes.yay_council_members, 2);
+
+        // The threshold may require every member
+        let outcome = tally_council(&proposal_votes, &members, 3);
+        assert_eq!(outcome.result, TallyResult::Rejected);
+
+        // Non-members voting yay never count, nor does a zero threshold pass
+        let outcome = tally_council(&proposal_votes, &BTreeSet::new(), 1);
+        assert_eq!(outcome.result, TallyResult::Rejected);
+        assert_eq!(outcome.votes.yay_council_members, 0);
+        let outcome = tally_council(&proposal_votes, &members, 0);
+        assert_eq!(outcome.result, TallyResult::Rejected);
+
+        assert_eq!(
+            TallyType::from(
+                ProposalType::Council(None),
+                false,
+                Some(4),
+                Some(2)
+            ),
+            TallyType::Council(2)
+        );
+        assert_eq!(
+            TallyType::from(ProposalType::Council(None), false, None, None),
+            TallyType::Council(0)
+        );
+    }
+
+    /// Test that council proposals pass once the total weight of the council
+    /// members voting yay reaches the threshold, diverging from a count of
+    /// the members if some of them weigh more than 1.
+    #[test]
+    fn test_weighted_council_tally() {
+        let founder = address::testing::established_address_1();
+        let member = address::testing::established_address_2();
+        let other_member = address::testing::established_address_3();
+        let members =
+            BTreeSet::from([founder.clone(), member.clone(), other_member]);
+        let unweighted = Council {
+            members: members.clone(),
+            threshold: 2,
+            weights: BTreeMap::new(),
+        };
+        let weighted = Council {
+            weights: BTreeMap::from([(founder.clone(), 2)]),
+            ..unweighted.clone()
+        };
+        assert_eq!(unweighted.total_weight(), Some(3));
+        assert_eq!(weighted.total_weight(), Some(4));
+        assert_eq!(weighted.weight(&founder), 2);
+        assert_eq!(weighted.weight(&member), 1);
+        assert_eq!(
+            weighted.weight(&address::testing::established_address_4()),
+            0
+        );
+
+        // The founder voting yay alone meets the threshold only if their
+        // seat counts double
+        let mut proposal_votes = ProposalVotes::default();
+        proposal_votes.add_validator(
+            &founder,
+            token::Amount::from_u64(5),
+            ProposalVote::Yay.into(),
+        );
+        let outcome = tally_council(&proposal_votes, &members, 2);
+        assert_eq!(outcome.result, TallyResult::Rejected);
+        assert_eq!(outcome.votes.yay_council_weight, 1);
+        let outcome = tally_weighted_council(&proposal_votes, &weighted);
+        assert_eq!(outcome.result, TallyResult::Passed);
+        assert_eq!(outcome.votes.yay_council_members, 1);
+        assert_eq!(outcome.votes.yay_council_weight, 2);
+
+        // With a threshold of 3, two members voting yay pass an unweighted
+        // council, but not a weighted one without the founder
+        proposal_votes.add_validator(
+            &member,
+            token::Amount::from_u64(5),
+            ProposalVote::Yay.into(),
+        );
+        let mut proposal_votes_without_founder = ProposalVotes::default();
+        proposal_votes_without_founder.add_validator(
+            &member,
+            token::Amount::from_u64(5),
+            ProposalVote::Yay.into(),
+        );
+        let outcome = tally_council(&proposal_votes, &members, 3);
+        assert_eq!(outcome.result, TallyResult::Rejected);
+        let outcome = tally_weighted_council(
+            &proposal_votes,
+            &Council {
+                threshold: 3,
+                ..weighted.clone()
+            },
+        );
+        assert_eq!(outcome.result, TallyResult::Passed);
+        assert_eq!(outcome.votes.yay_council_weight, 3);
+        let outcome = tally_weighted_council(
+            &proposal_votes_without_founder,
+            &Council {
+                threshold: 3,
+                ..weighted.clone()
+            },
+        );
+        assert_eq!(outcome.result, TallyResult::Rejected);
+
+        // Unset weights default to 1, matching the count of the members
+        for votes in [&proposal_votes, &proposal_votes_without_founder] {
+            let counted = tally_council(votes, &members, 2);
+            let outcome = tally_weighted_council(votes, &unweighted);
+            assert_eq!(counted, outcome);
+            assert_eq!(
+                outcome.votes.yay_council_weight,
+                outcome.votes.yay_council_members
+            );
+        }
+
+        // The threshold must be met by the total weight, and the weights
+        // must be positive and belong to members
+        assert!(weighted.is_valid());
+        assert!(
+            Council {
+                threshold: 4,
+                ..weighted.clone()
+            }
+            .is_valid()
+        );
+        assert!(
+            !Council {
+                threshold: 5,
+                ..weighted.clone()
+            }
+            .is_valid()
+        );
+        assert!(
+            !Council {
+                weights: BTreeMap::from([(founder.clone(), 0)]),
+                ..weighted.clone()
+            }
+            .is_valid()
+        );
+        assert!(
+            !Council {
+                weights: BTreeMap::from([(
+                    address::testing::established_address_4(),
+                    2
+                )]),
+                ..weighted.clone()
+            }
+            .is_valid()
+        );
+        assert!(
+            !Council {
+                weights: BTreeMap::from([(founder, u64::MAX)]),
+                ..weighted
+            }
+            .is_valid()
+        );
+    }
+
+    /// The tallied votes of a proposal where yay and nay votes carry the
+    /// same voting power, over half of the total voting power
+    fn yay_nay_tie() -> TalliedVotes {
+        TalliedVotes {
+            yay_voting_power: token::Amount::from(30),
+            nay_voting_power: token::Amount::from(30),
+            abstain_voting_power: token::Amount::from(10),
+            total_voting_power: token::Amount::from(100),
+            yay_validators: 1,
+            yay_council_members: 0,
+            yay_council_weight: 0,
+        }
+    }
+
+    /// Test that simple-majority tallies landing exactly on a tie are
+    /// resolved by the tie policy, rejecting them by default
+    #[test]
+    fn test_simple_majority_tie() {
+        let votes = yay_nay_tie();
+        for tally_type in [
+            TallyType::OneHalfOverOneThird,
+            TallyType::LessOneHalfOverOneThirdNay,
+        ] {
+            assert_eq!(tally_type.decide(&votes), None);
+            assert_eq!(tally_type.tie_policy(), TiePolicy::TieBreaksToReject);
+            assert_eq!(tally_type.result(&votes), TallyResult::Rejected);
+            for (tie_policy, expected) in [
+                (TiePolicy::TieBreaksToPass, TallyResult::Passed),
+                (TiePolicy::TieBreaksToReject, TallyResult::Rejected),
+            ] {
+                let strategy = WithTiePolicy {
+                    strategy: tally_type,
+                    tie_policy,
+                };
+                // the resolution is deterministic
+                assert_eq!(strategy.result(&votes), expected);
+                assert_eq!(strategy.result(&votes), expected);
+            }
+
+            // one more yay vote breaks the tie, regardless of the policy
+            let votes = TalliedVotes {
+                yay_voting_power: votes.yay_voting_power
+                    + token::Amount::from(1),
+                ..votes
+            };
+            let strategy = WithTiePolicy {
+                strategy: tally_type,
+                tie_policy: TiePolicy::TieBreaksToReject,
+            };
+            assert_eq!(strategy.result(&votes), TallyResult::Passed);
+        }
+
+        // no quorum, or no yay and nay votes at all, isn't a tie
+        let no_quorum = TalliedVotes {
+            abstain_voting_power: token::Amount::zero(),
+            total_voting_power: token::Amount::from(200),
+            ..yay_nay_tie()
+        };
+        assert_eq!(
+            TallyType::OneHalfOverOneThird.decide(&no_quorum),
+            Some(TallyResult::Rejected)
+        );
+        let abstained = TalliedVotes {
+            yay_voting_power: token::Amount::zero(),
+            nay_voting_power: token::Amount::zero(),
+            abstain_voting_power: token::Amount::from(50),
+            ..yay_nay_tie()
+        };
+        assert_eq!(
+            TallyType::OneHalfOverOneThird.decide(&abstained),
+            Some(TallyResult::Rejected)
+        );
+    }
+
+    /// Test that validator-count tallies reaching exactly the quorum pass,
+    /// regardless of the tie policy, as they never land on a tie
+    #[test]
+    fn test_validator_count_quorum_reached() {
+        let votes = TalliedVotes {
+            yay_validators: 3,
+            ..TalliedVotes::default()
+        };
+        for (quorum, expected) in [
+            (2, TallyResult::Passed),
+            (3, TallyResult::Passed),
+            (4, TallyResult::Rejected),
+        ] {
+            let strategy = WithTiePolicy {
+                strategy: TallyType::ValidatorCount(quorum),
+                tie_policy: TiePolicy::TieBreaksToReject,
+            };
+            assert_eq!(strategy.decide(&votes), Some(expected));
+            assert_eq!(strategy.result(&votes), expected);
+        }
+    }
+
+    /// Pin the derivation of the random draw breaking a tie from the block
+    /// hash and the proposal id, which every node must agree on
+    #[test]
+    fn test_tie_break_seed_derivation() {
+        let seed = |block_hash_byte: u8, proposal_id: u64| {
+            TieBreakSeed::new(
+                &BlockHash([block_hash_byte; HASH_LENGTH]),
+                proposal_id,
+            )
+        };
+
+        let zero_hash_seed = seed(0, 0);
+        assert_eq!(zero_hash_seed.block_hash, Hash::default());
+        assert_eq!(zero_hash_seed.proposal_id, 0);
+        assert_eq!(
+            zero_hash_seed.digest(),
+            Hash::from_str(
+                "2C34CE1DF23B838C5ABF2A7F6437CCA3D3067ED509FF25F11DF6B11B582B51EB"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            seed(1, 2).digest(),
+            Hash::from_str(
+                "0A80A3C95FA16666AED2F49AAD505ADBB2DA6D689434BBFF6B615421DB5454C9"
+            )
+            .unwrap()
+        );
+
+        // the draw passes the proposal iff the last byte of the digest is odd
+        for (block_hash_byte, proposal_id, passes) in [
+            (0, 0, true),
+            (0, 1, true),
+            (0, 2, false),
+            (0, 3, false),
+            (1, 0, false),
+            (1, 1, false),
+            (1, 2, true),
+            (1, 3, true),
+        ] {
+            let seed = seed(block_hash_byte, proposal_id);
+            assert_eq!(seed.passes(), passes);
+            let expected = if passes {
+                TallyResult::Passed
+            } else {
+                TallyResult::Rejected
+            };
+            assert_eq!(TiePolicy::TieBreaksAtRandom(seed).resolve(), expected);
+        }
+
+        // the seed is recorded in the outcome only if it broke a tie
+        let votes = |yay: u64| {
+            vec![
+                WeightedVote {
+                    vote: ProposalVote::Yay.into(),
+                    voting_power: token::Amount::from(yay),
+                    staked_validator: true,
+                    council_weight: 0,
+                },
+                WeightedVote {
+                    vote: ProposalVote::Nay.into(),
+                    voting_power: token::Amount::from(30),
+                    staked_validator: true,
+                    council_weight: 0,
+                },
+            ]
+        };
+        let strategy = WithTiePolicy {
+            strategy: TallyType::OneHalfOverOneThird,
+            tie_policy: TiePolicy::TieBreaksAtRandom(seed(0, 0)),
+        };
+        let outcome =
+            tally_uncapped(votes(30), token::Amount::from(100), strategy);
+        assert_eq!(outcome.result, TallyResult::Passed);
+        assert_eq!(outcome.tie_break, Some(seed(0, 0)));
+        let outcome =
+            tally_uncapped(votes(31), token::Amount::from(100), strategy);
+        assert_eq!(outcome.result, TallyResult::Passed);
+        assert_eq!(outcome.tie_break, None);
+    }
+
+    /// Tally the votes of a validator with 100 voting power voting yay, of
+    /// which 30 are delegated by a delegator who hasn't voted, returning
+    /// the yay and nay voting power
+    fn tally_with_governance(
+        build: impl FnOnce(&mut ProposalVotes, &Address, &Address),
+    ) -> (VotePower, VotePower) {
+        let mut proposal_votes = ProposalVotes::default();
+        let validator = address::testing::established_address_1();
+        let delegator = address::testing::established_address_2();
+        proposal_votes.add_validator(
+            &validator,
+            token::Amount::from_u64(100),
+            ProposalVote::Yay.into(),
+        );
+        proposal_votes.add_delegation(
+            &delegator,
+            &validator,
+            token::Amount::from_u64(30),
+        );
+        build(&mut proposal_votes, &validator, &delegator);
+
+        let result = compute_proposal_result(
+            proposal_votes,
+            token::Amount::from(200),
+            TallyType::OneHalfOverOneThird,
+        );
+        (result.total_yay_power, result.total_nay_power)
+    }
+
+    /// Test that the delegations of a delegator who hasn't voted follow the
+    /// vote of its validator when it has no governor
+    #[test]
+    fn test_tally_without_governor() {
+        let (yay, nay) = tally_with_governance(|_, _, _| {});
+        assert_eq!(yay, token::Amount::from(100));
+        assert_eq!(nay, token::Amount::zero());
+    }
+
+    /// Test that the vote of a governor prevails over the vote of the
+    /// validator of the delegator
+    #[test]
+    fn test_tally_governor_vote_overrides_validator_vote() {
+        let (yay, nay) =
+            tally_with_governance(|votes, validator, delegator| {
+                let governor = address::testing::established_address_3();
+                votes.add_delegator(
+                    &governor,
+                    validator,
+                    token::Amount::from_u64(10),
+                    ProposalVote::Nay.into(),
+                );
+                votes.add_governor(delegator, &governor);
+            });
+        assert_eq!(yay, token::Amount::from(60));
+        assert_eq!(nay, token::Amount::from(40));
+    }
+
+    /// Test that a direct vote of a delegator prevails over the vote of its
+    /// governor
+    #[test]
+    fn test_tally_direct_vote_overrides_governor_vote() {
+        let (yay, nay) =
+            tally_with_governance(|votes, validator, delegator| {
+                let governor = address::testing::established_address_3();
+                votes.add_delegator(
+                    &governor,
+                    validator,
+                    token::Amount::from_u64(10),
+                    ProposalVote::Yay.into(),
+                );
+                votes.add_governor(delegator, &governor);
+                votes.add_delegator(
+                    delegator,
+                    validator,
+                    token::Amount::from_u64(30),
+                    ProposalVote::Nay.into(),
+                );
+            });
+        assert_eq!(yay, token::Amount::from(70));
+        assert_eq!(nay, token::Amount::from(30));
+    }
+
+    /// Test that the delegations of a delegator follow the vote of its
+    /// validator when its governor hasn't voted
+    #[test]
+    fn test_tally_governor_without_vote() {
+        let (yay, nay) = tally_with_governance(|votes, _, delegator| {
+            votes.add_governor(
+                delegator,
+                &address::testing::established_address_3(),
+            );
+        });
+        assert_eq!(yay, token::Amount::from(100));
+        assert_eq!(nay, token::Amount::zero());
+    }
+
+    /// Test that a validator can act as the governor of a delegator, whose
+    /// delegations to other validators then follow its vote
+    #[test]
+    fn test_tally_validator_as_governor() {
+        let (yay, nay) = tally_with_governance(|votes, _, delegator| {
+            let governor = address::testing::established_address_3();
+            votes.add_validator(
+                &governor,
+                token::Amount::from_u64(50),
+                ProposalVote::Nay.into(),
+            );
+            votes.add_governor(delegator, &governor);
+        });
+        assert_eq!(yay, token::Amount::from(70));
+        assert_eq!(nay, token::Amount::from(80));
+    }
+
+    /// Test that the votes of the governors of governors are followed, when
+    /// the governors in between haven't voted
+    #[test]
+    fn test_tally_transitive_governors() {
+        let (yay, nay) =
+            tally_with_governance(|votes, validator, delegator| {
+                let governor = address::testing::established_address_3();
+                let governor_of_governor =
+                    address::testing::established_address_4();
+                votes.add_governor(delegator, &governor);
+                votes.add_governor(&governor, &governor_of_governor);
+                votes.add_delegator(
+                    &governor_of_governor,
+                    validator,
+                    token::Amount::from_u64(10),
+                    ProposalVote::Nay.into(),
+                );
+            });
+        assert_eq!(yay, token::Amount::from(60));
+        assert_eq!(nay, token::Amount::from(40));
+    }
+
+    /// Test that the chains of governors are followed up to the maximum
+    /// delegation depth only
+    #[test]
+    fn test_tally_governors_beyond_max_depth() {
+        let tally_with_depth = |depth: usize| {
+            tally_with_governance(|votes, validator, delegator| {
+                let mut delegator = delegator.clone();
+                for _ in 0..depth {
+                    let governor = address::testing::gen_established_address();
+                    votes.add_governor(&delegator, &governor);
+                    delegator = governor;
+                }
+                votes.add_delegator(
+                    &delegator,
+                    validator,
+                    token::Amount::from_u64(10),
+                    ProposalVote::Nay.into(),
+                );
+            })
+        };
+
+        let (yay, nay) = tally_with_depth(MAX_VOTE_DELEGATION_DEPTH);
+        assert_eq!(yay, token::Amount::from(60));
+        assert_eq!(nay, token::Amount::from(40));
+
+        let (yay, nay) = tally_with_depth(MAX_VOTE_DELEGATION_DEPTH + 1);
+        assert_eq!(yay, token::Amount::from(90));
+        assert_eq!(nay, token::Amount::from(10));
+    }
+
+    /// Test that circular delegations of votes don't count as votes
+    #[test]
+    fn test_tally_circular_governors() {
+        let (yay, nay) = tally_with_governance(|votes, _, delegator| {
+            let governor = address::testing::established_address_3();
+            votes.add_governor(delegator, &governor);
+            votes.add_governor(&governor, delegator);
+        });
+        assert_eq!(yay, token::Amount::from(100));
+        assert_eq!(nay, token::Amount::zero());
+    }
+
+    /// Test that a validator voting directly isn't overridden by its
+    /// governor on its self-bond
+    #[test]
+    fn test_tally_validator_direct_vote_overrides_governor() {
+        let (yay, nay) =
+            tally_with_governance(|votes, validator, delegator| {
+                votes.add_delegation(
+                    validator,
+                    validator,
+                    token::Amount::from_u64(20),
+                );
+                votes.add_governor(validator, delegator);
+                votes.add_delegator(
+                    delegator,
+                    validator,
+                    token::Amount::from_u64(30),
+                    ProposalVote::Nay.into(),
+                );
+            });
+        assert_eq!(yay, token::Amount::from(70));
+        assert_eq!(nay, token::Amount::from(30));
+    }
+
+    /// Generate an arbitrary weighted vote
+    fn arb_weighted_vote() -> impl Strategy<Value = WeightedVote> {
+        (arb_proposal_vote(), 0..1_000_000u64, any::<bool>()).prop_map(
+            |(vote, voting_power, staked_validator)| WeightedVote {
+                vote: vote.into(),
+                voting_power: token::Amount::from_u64(voting_power),
+                staked_validator,
+                council_weight: 0,
+            },
+        )
+    }
+
+    /// Generate arbitrary weighted votes, along with a total voting power
+    /// at least as large as the voting power of the votes
+    fn arb_tally_votes() -> impl Strategy<Value = (Vec<WeightedVote>, VotePower)>
+    {
+        (collection::vec(arb_weighted_vote(), 0..20), 0..1_000_000u64).prop_map(
+            |(votes, not_voted)| {
+                let total_voting_power = votes
+                    .iter()
+                    .fold(token::Amount::from_u64(not_voted), |total, vote| {
+                        total + vote.voting_power
+                    });
+                (votes, total_voting_power)
+            },
+        )
+    }
+
+    /// Generate an arbitrary tally type whose result can't be flipped to
+    /// rejected by more yay votes. This excludes
+    /// [`TallyType::LessOneHalfOverOneThirdNay`], which passes proposals
+    /// below its quorum, such that yay votes reaching the quorum along
+    /// with a majority of nay votes reject the proposal.
+    fn arb_monotonic_tally_type() -> impl Strategy<Value = TallyType> {
+        prop_oneof![
+            Just(TallyType::TwoThirds),
+            Just(TallyType::OneHalfOverOneThird),
+            (0..10u64).prop_map(TallyType::ValidatorCount),
+        ]
+    }
+
+    /// Generate an arbitrary tally type
+    fn arb_tally_type() -> impl Strategy<Value = TallyType> {
+        prop_oneof![
+            arb_monotonic_tally_type(),
+            Just(TallyType::LessOneHalfOverOneThirdNay),
+        ]
+    }
+
+    /// Generate an arbitrary tie policy
+    fn arb_tie_policy() -> impl Strategy<Value = TiePolicy> {
+        prop_oneof![
+            Just(TiePolicy::TieBreaksToPass),
+            Just(TiePolicy::TieBreaksToReject),
+            any::<u64>().prop_map(|proposal_id| {
+                TiePolicy::TieBreaksAtRandom(TieBreakSeed {
+                    block_hash: Hash::default(),
+                    proposal_id,
+                })
+            }),
+        ]
+    }
+
+    /// Tally the given votes with no cap on their voting power
+    fn tally_uncapped(
+        votes: Vec<WeightedVote>,
+        total_voting_power: VotePower,
+        strategy: impl TallyStrategy,
+    ) -> TallyOutcome {
+        tally(
+            votes,
+            TallyParams {
+                strategy,
+                total_voting_power,
+                max_voting_power_fraction: None,
+            },
+        )
+    }
+
+    /// Test that the output of a proposal is bounded by dropping its last
+    /// entries, in order of name
+    #[test]
+    fn test_bound_proposal_output() {
+        let output = ProposalOutput::from([
+            ("minted".to_string(), "100".to_string()),
+            ("token".to_string(), "nam".to_string()),
+        ]);
+        assert_eq!(bound_proposal_output(output.clone()), (output, false));
+
+        let large_value = "x".repeat(MAX_PROPOSAL_OUTPUT_SIZE);
+        let output = ProposalOutput::from([
+            ("minted".to_string(), "100".to_string()),
+            ("receipt".to_string(), large_value),
+            ("token".to_string(), "nam".to_string()),
+        ]);
+        let (bounded, truncated) = bound_proposal_output(output);
+        assert!(truncated);
+        assert_eq!(
+            bounded,
+            ProposalOutput::from([("minted".to_string(), "100".to_string())])
+        );
+    }
+
+    /// Test the shares of the votes cast on a proposal, in basis points
+    #[test]
+    fn test_tally_basis_points() {
+        let shares = |yay: u64, nay: u64, abstain: u64| {
+            let bps = TallyBasisPoints::new(
+                token::Amount::from_u64(yay),
+                token::Amount::from_u64(nay),
+                token::Amount::from_u64(abstain),
+            );
+            (bps.yay, bps.nay, bps.abstain)
+        };
+        assert_eq!(shares(1, 0, 0), (10_000, 0, 0));
+        assert_eq!(shares(3, 1, 0), (7_500, 2_500, 0));
+        // the basis points left over go to the largest remainders
+        assert_eq!(shares(2, 1, 0), (6_667, 3_333, 0));
+        assert_eq!(shares(1, 2, 0), (3_333, 6_667, 0));
+        // and then to yay, nay and abstain, in this order
+        assert_eq!(shares(1, 1, 1), (3_334, 3_333, 3_333));
+        assert_eq!(shares(0, 1, 1), (0, 5_000, 5_000));
+        // nothing is shared if no voting power was cast
+        assert_eq!(shares(0, 0, 0), (0, 0, 0));
+
+        let bps = TallyBasisPoints::new(
+            token::Amount::from_u64(2),
+            token::Amount::from_u64(1),
+            token::Amount::zero(),
+        );
+        assert_eq!(bps.to_string(), "yay 66.67%, nay 33.33%, abstain 0.00%");
+    }
+
+    proptest! {
+        /// Test that adding the yay vote of a staked validator never
+        /// flips a passed proposal to rejected
+        #[test]
+        fn test_tally_monotonic_in_yay(
+            (votes, total_voting_power) in arb_tally_votes(),
+            tally_type in arb_monotonic_tally_type(),
+            tie_policy in arb_tie_policy(),
+            yay_power in 1..1_000_000u64,
+        ) {
+            let strategy = WithTiePolicy {
+                strategy: tally_type,
+                tie_policy,
+            };
+            let before =
+                tally_uncapped(votes.clone(), total_voting_power, strategy);
+
+            let yay_power = token::Amount::from_u64(yay_power);
+            let mut votes = votes;
+            votes.push(WeightedVote {
+                vote: ProposalVote::Yay.into(),
+                voting_power: yay_power,
+                staked_validator: true,
+                council_weight: 0,
+            });
+            let after =
+                tally_uncapped(votes, total_voting_power + yay_power, strategy);
+
+            if before.result == TallyResult::Passed {
+                prop_assert_eq!(after.result, TallyResult::Passed);
+            }
+        }
+
+        /// Test that the outcome of a tally doesn't depend on the order of
+        /// its votes
+        #[test]
+        fn test_tally_order_independent(
+            (votes, total_voting_power) in arb_tally_votes(),
+            tally_type in arb_tally_type(),
+        ) {
+            let mut reversed = votes.clone();
+            reversed.reverse();
+            prop_assert_eq!(
+                tally_uncapped(votes, total_voting_power, tally_type),
+                tally_uncapped(reversed, total_voting_power, tally_type),
+            );
+        }
+
+        /// Test that the tie policy only decides the result of tallies
+        /// landing exactly on a tie
+        #[test]
+        fn test_tally_tie_policy_only_resolves_ties(
+            (votes, total_voting_power) in arb_tally_votes(),
+            tally_type in arb_tally_type(),
+        ) {
+            let outcome = |tie_policy| {
+                tally_uncapped(
+                    votes.clone(),
+                    total_voting_power,
+                    WithTiePolicy {
+                        strategy: tally_type,
+                        tie_policy,
+                    },
+                )
+            };
+            let pass = outcome(TiePolicy::TieBreaksToPass);
+            let reject = outcome(TiePolicy::TieBreaksToReject);
+            match tally_type.decide(&pass.votes) {
+                Some(result) => {
+                    prop_assert_eq!(pass.result, result);
+                    prop_assert_eq!(reject.result, result);
+                }
+                None => {
+                    prop_assert_eq!(pass.result, TallyResult::Passed);
+                    prop_assert_eq!(reject.result, TallyResult::Rejected);
+                }
+            }
+        }
+
+        /// Test that capping the voting power of the votes never increases
+        /// the tallied voting power, nor changes the number of validators
+        /// voting yay
+        #[test]
+        fn test_tally_cap_never_increases_power(
+            (votes, total_voting_power) in arb_tally_votes(),
+            tally_type in arb_tally_type(),
+            cap_percent in 1..=100u64,
+        ) {
+            let uncapped =
+                tally_uncapped(votes.clone(), total_voting_power, tally_type);
+            let cap = Dec::new(cap_percent.into(), 2).expect("Test failed");
+            let capped = tally(
+                votes,
+                TallyParams {
+                    strategy: tally_type,
+                    total_voting_power,
+                    max_voting_power_fraction: Some(cap),
+                },
+            );
+            prop_assert!(
+                capped.votes.yay_voting_power
+                    <= uncapped.votes.yay_voting_power
+            );
+            prop_assert!(
+                capped.votes.nay_voting_power
+                    <= uncapped.votes.nay_voting_power
+            );
+            prop_assert!(
+                capped.votes.abstain_voting_power
+                    <= uncapped.votes.abstain_voting_power
+            );
+            prop_assert_eq!(
+                capped.votes.yay_validators,
+                uncapped.votes.yay_validators
+            );
+        }
+
+        /// Test that the fraction of any amount never panics, and that a
+        /// fraction of at most one always fits in the amount
+        #[test]
+        fn test_fraction_of_never_panics(
+            raw in any::<[u64; 4]>(),
+            numerator in any::<u64>(),
+            denominator in any::<u64>(),
+        ) {
+            let amount = token::Amount::from_uint(Uint(raw), 0)
+                .expect("Test failed");
+            let fraction = fraction_of(amount, numerator, denominator);
+            if denominator == 0 {
+                prop_assert_eq!(fraction, None);
+            } else if numerator <= denominator {
+                prop_assert!(fraction.expect("Test failed") <= amount);
+            }
+        }
+
+        /// Test that the fraction of an amount is the exact fraction rounded
+        /// up, for amounts whose product with the numerator fits in 256 bits
+        #[test]
+        fn test_fraction_of_rounds_up(
+            raw in any::<[u64; 3]>(),
+            numerator in any::<u64>(),
+            denominator in 1..=u64::MAX,
+        ) {
+            let amount = Uint([raw[0], raw[1], raw[2], 0]);
+            let fraction = fraction_of(
+                token::Amount::from_uint(amount, 0).expect("Test failed"),
+                numerator,
+                denominator,
+            )
+            .expect("Test failed")
+            .raw_amount();
+            let exact = amount * Uint::from(numerator);
+            let denominator = Uint::from(denominator);
+            // The smallest amount whose product with the denominator is
+            // at least the exact fraction
+            prop_assert!(fraction * denominator >= exact);
+            prop_assert!(fraction * denominator < exact + denominator);
+        }
+
+        /// Test that a decimal fraction of an amount is rounded like the
+        /// same fraction given by its numerator and denominator
+        #[test]
+        fn test_dec_fraction_of_matches_fraction_of(
+            raw in any::<[u64; 4]>(),
+            percent in 0..=100u64,
+        ) {
+            let amount = token::Amount::from_uint(Uint(raw), 0)
+                .expect("Test failed");
+            let fraction = Dec::new(percent.into(), 2).expect("Test failed");
+            prop_assert_eq!(
+                dec_fraction_of(amount, fraction),
+                fraction_of(amount, percent, 100)
+            );
+        }
+
+        /// Test that the shares of the votes cast on a tallied proposal sum
+        /// up to the whole, and that each of them is off by less than one
+        /// basis point from its exact value
+        #[test]
+        fn test_tally_basis_points_sum_to_whole(
+            (votes, total_voting_power) in arb_tally_votes(),
+            tally_type in arb_tally_type(),
+        ) {
+            let outcome = tally_uncapped(votes, total_voting_power, tally_type);
+            let tallied = outcome.votes;
+            let cast = tallied.total_voted_power();
+            prop_assume!(!cast.is_zero());
+            let shares = outcome.basis_points;
+            prop_assert_eq!(
+                shares.yay + shares.nay + shares.abstain,
+                TallyBasisPoints::WHOLE
+            );
+            let whole = Uint::from(TallyBasisPoints::WHOLE);
+            for (share, power) in [
+                (shares.yay, tallied.yay_voting_power),
+                (shares.nay, tallied.nay_voting_power),
+                (shares.abstain, tallied.abstain_voting_power),
+            ] {
+                // |share - power * WHOLE / cast| < 1
+                let share = Uint::from(share) * cast.raw_amount();
+                let exact = power.raw_amount() * whole;
+                let diff = if share > exact {
+                    share - exact
+                } else {
+                    exact - share
+                };
+                prop_assert!(diff < cast.raw_amount());
+            }
+        }
+    }
 }
@@ -37,6 +37,9 @@ impl InitProposal {
             voting_start_epoch,
             voting_end_epoch,
             grace_epoch,
+            voting_heights: None,
+            priority: None,
+            code_chunks: vec![],
         };
 
         Self(transaction::build_tx(
@@ -5,14 +5,29 @@ pub mod utils;
 use std::collections::BTreeSet;
 
 use borsh::BorshDeserialize;
+use namada_gas::GasMetering;
+use namada_governance::parameters::Council;
+use namada_governance::storage::facade::ProposalStorageReader;
 use namada_governance::storage::proposal::{
-    AddRemove, PGFAction, ProposalType,
+    AddRemove, EthHotKeyRotation, PGFAction, ParameterChange, ProposalType,
+    TreasurySpend,
+};
+use namada_governance::storage::vote::VoteReceipt;
+use namada_governance::storage::{
+    code as gov_code, get_council, get_min_proposal_author_stake,
+    get_proposal_voting_period_blocks, get_required_deposit,
+    get_strict_proposal_code_validation, is_proposal_accepted,
+    is_proposal_executing, is_valid_delegation_chain, is_vote_key_migration,
+    keys as gov_storage,
 };
-use namada_governance::storage::{is_proposal_accepted, keys as gov_storage};
 use namada_governance::utils::is_valid_validator_voting_period;
 use namada_governance::ProposalVote;
 use namada_proof_of_stake::is_validator;
 use namada_proof_of_stake::queries::find_delegations;
+use namada_proof_of_stake::storage::{
+    read_pos_params, validator_eth_cold_key_handle,
+    validator_eth_hot_key_handle,
+};
 use namada_state::StorageRead;
 use namada_tx::Tx;
 use namada_vp_env::VpEnv;
@@ -23,8 +38,10 @@ use crate::ledger::native_vp::{Ctx, NativeVp};
 use crate::ledger::{native_vp, pos};
 use crate::token;
 use crate::types::address::{Address, InternalAddress};
-use crate::types::storage::{Epoch, Key};
-use crate::vm::WasmCacheAccess;
+use crate::types::hash::Hash;
+use crate::types::key::common;
+use crate::types::storage::{BlockHeight, Epoch, Key};
+use crate::vm::{validate_untrusted_wasm, WasmCacheAccess};
 
 /// for handling Governance NativeVP errors
 pub type Result<T> = std::result::Result<T, Error>;
@@ -85,8 +102,15 @@ where
             let key_type = KeyType::from_key(key, &native_token);
 
             let result = match (key_type, proposal_id) {
-                (KeyType::VOTE, Some(proposal_id)) => {
-                    self.is_valid_vote_key(proposal_id, key, verifiers)
+                (KeyType::VOTE, Some(proposal_id)) => self.is_valid_vote_key(
+                    tx_data,
+                    proposal_id,
+                    key,
+                    keys_changed,
+                    verifiers,
+                ),
+                (KeyType::LEGACY_VOTE, Some(_)) => {
+                    self.is_valid_legacy_vote_key(key, keys_changed)
                 }
                 (KeyType::CONTENT, Some(proposal_id)) => {
                     self.is_valid_content_key(proposal_id)
@@ -97,6 +121,18 @@ where
                 (KeyType::PROPOSAL_CODE, Some(proposal_id)) => {
                     self.is_valid_proposal_code(proposal_id)
                 }
+                (KeyType::PROPOSAL_CODE_HASH, Some(proposal_id)) => {
+                    self.is_valid_proposal_code_hash(proposal_id)
+                }
+                (KeyType::PROPOSAL_CODE_CHUNK(idx), Some(proposal_id)) => {
+                    self.is_valid_proposal_code_chunk(proposal_id, idx)
+                }
+                (KeyType::PROPOSAL_CODE_CHUNK_HASH(idx), Some(proposal_id)) => {
+                    self.is_valid_proposal_code_chunk_hash(proposal_id, idx)
+                }
+                (KeyType::PROPOSAL_CODE_CHUNK_COUNT, Some(proposal_id)) => {
+                    self.is_valid_proposal_code_chunk_count(proposal_id)
+                }
                 (KeyType::GRACE_EPOCH, Some(proposal_id)) => {
                     self.is_valid_grace_epoch(proposal_id)
                 }
@@ -106,6 +142,16 @@ where
                 (KeyType::END_EPOCH, Some(proposal_id)) => {
                     self.is_valid_end_epoch(proposal_id)
                 }
+                (
+                    KeyType::START_HEIGHT | KeyType::END_HEIGHT,
+                    Some(proposal_id),
+                ) => self.is_valid_voting_heights(proposal_id),
+                (KeyType::PRIORITY, Some(proposal_id)) => {
+                    self.is_valid_priority(proposal_id)
+                }
+                (KeyType::PROPOSAL_OUTPUT, Some(proposal_id)) => {
+                    self.is_valid_proposal_output(proposal_id)
+                }
                 (KeyType::FUNDS, Some(proposal_id)) => {
                     self.is_valid_funds(proposal_id, &native_token)
                 }
@@ -117,6 +163,18 @@ where
                     self.is_valid_proposal_commit()
                 }
                 (KeyType::PARAMETER, _) => self.is_valid_parameter(tx_data),
+                (KeyType::VOTE_KEY_MIGRATION_CURSOR, _) => {
+                    self.is_valid_vote_key_migration_cursor(tx_data)
+                }
+                (KeyType::VOTING_KEY, _) => {
+                    self.is_valid_voting_key(key, verifiers)
+                }
+                (KeyType::VOTER_INDEX, _) => {
+                    self.is_valid_voter_index_key(key, keys_changed)
+                }
+                (KeyType::VOTE_DELEGATION, _) => {
+                    self.is_valid_vote_delegation(key, verifiers)
+                }
                 (KeyType::BALANCE, _) => self.is_valid_balance(&native_token),
                 (KeyType::UNKNOWN_GOVERNANCE, _) => Ok(false),
                 (KeyType::UNKNOWN, _) => Ok(true),
@@ -157,16 +215,37 @@ where
             // Construct the set of expected keys
             // NOTE: we don't check the existence of committing_epoch because
             // it's going to be checked later into the VP
-            let mandatory_keys = BTreeSet::from([
+            let mut mandatory_keys = BTreeSet::from([
                 counter_key.clone(),
                 gov_storage::get_content_key(counter),
                 gov_storage::get_author_key(counter),
                 gov_storage::get_proposal_type_key(counter),
                 gov_storage::get_funds_key(counter),
+            ]);
+
+            // The voting period is expressed either in block heights or in
+            // epochs, but not both
+            let height_keys = [
+                gov_storage::get_voting_start_height_key(counter),
+                gov_storage::get_voting_end_height_key(counter),
+            ];
+            let epoch_keys = [
                 gov_storage::get_voting_start_epoch_key(counter),
                 gov_storage::get_voting_end_epoch_key(counter),
                 gov_storage::get_grace_epoch_key(counter),
-            ]);
+            ];
+            if height_keys.iter().any(|key| keys.contains(key)) {
+                if epoch_keys.iter().any(|key| keys.contains(key)) {
+                    tracing::info!(
+                        "The voting period of proposal {counter} can't be \
+                         expressed both in block heights and in epochs."
+                    );
+                    return Ok((false, 0));
+                }
+                mandatory_keys.extend(height_keys);
+            } else {
+                mandatory_keys.extend(epoch_keys);
+            }
 
             // Check that expected set is a subset of the actual one
             if !keys.is_superset(&mandatory_keys) {
@@ -179,35 +258,46 @@ where
 
     fn is_valid_vote_key(
         &self,
+        tx_data: &Tx,
         proposal_id: u64,
         key: &Key,
+        keys_changed: &BTreeSet<Key>,
         verifiers: &BTreeSet<Address>,
     ) -> Result<bool> {
+        // The code of an accepted proposal may move the votes stored under
+        // the legacy key layout, unchanged
+        if is_vote_key_migration(&self.ctx.pre(), &self.ctx.post(), key)
+            .map_err(Error::NativeVpError)?
+            && self.is_valid_parameter(tx_data)?
+        {
+            return Ok(true);
+        }
+
         let counter_key = gov_storage::get_counter_key();
-        let voting_start_epoch_key =
-            gov_storage::get_voting_start_epoch_key(proposal_id);
-        let voting_end_epoch_key =
-            gov_storage::get_voting_end_epoch_key(proposal_id);
 
         let current_epoch = self.ctx.get_block_epoch()?;
 
         let pre_counter: u64 = self.force_read(&counter_key, ReadType::Pre)?;
-        let pre_voting_start_epoch: Epoch =
-            self.force_read(&voting_start_epoch_key, ReadType::Pre)?;
-        let pre_voting_end_epoch: Epoch =
-            self.force_read(&voting_end_epoch_key, ReadType::Pre)?;
-
-        let voter = gov_storage::get_voter_address(key);
-        let delegation_address = gov_storage::get_vote_delegation_address(key);
-
-        let (voter_address, delegation_address) =
-            match (voter, delegation_address) {
-                (Some(voter_address), Some(delegator_address)) => {
-                    (voter_address, delegator_address)
+
+        let (voter_type, voter_address, delegation_address) =
+            match gov_storage::parse_vote_key(key) {
+                Some(vote_key) => {
+                    (vote_key.voter_type, vote_key.voter, vote_key.validator)
                 }
-                _ => return Err(Error::InvalidVoteKey(key.to_string())),
+                None => return Err(Error::InvalidVoteKey(key.to_string())),
             };
 
+        // The votes index of the voter must be updated along with the vote
+        let voter_index_key =
+            gov_storage::get_voter_index_key(voter_address, proposal_id);
+        if !keys_changed.contains(&voter_index_key) {
+            tracing::info!(
+                "Vote on proposal {proposal_id} by {voter_address} without \
+                 updating the votes index of the voter."
+            );
+            return Ok(false);
+        }
+
         // Invalid proposal id
         if pre_counter <= proposal_id {
             tracing::info!(
@@ -230,12 +320,20 @@ where
             return Err(Error::InvalidVoteKey(key.to_string()));
         }
 
+        // Members of the council may vote on council proposals without any
+        // stake, under their own address
+        let is_council_member_vote =
+            self.is_council_member_vote(proposal_id, voter_address)?;
+
         // TODO: We should refactor this by modifying the vote proposal tx
         let all_delegations_are_valid = if let Ok(delegations) =
             find_delegations(&self.ctx.pre(), voter_address, &current_epoch)
         {
             if delegations.is_empty() {
-                return Ok(false);
+                if !is_council_member_vote {
+                    return Ok(false);
+                }
+                true
             } else {
                 delegations.iter().all(|(address, _)| {
                     let vote_key = gov_storage::get_vote_proposal_key(
@@ -253,50 +351,155 @@ where
             return Ok(false);
         }
 
-        // Voted outside of voting window. We dont check for validator because
-        // if the proposal type is validator, we need to let
-        // them vote for the entire voting window.
-        if !self.is_valid_voting_window(
-            current_epoch,
-            pre_voting_start_epoch,
-            pre_voting_end_epoch,
-            false,
-        ) {
-            tracing::info!(
-                "Voted outside voting window. Current epoch: {current_epoch}, \
-                 start: {pre_voting_start_epoch}, end: {pre_voting_end_epoch}."
-            );
-            return Ok(false);
+        let pre = self.ctx.pre();
+        let (stake_epoch, valid_validator_voting_period) =
+            match ProposalStorageReader::new(&pre)
+                .voting_heights(proposal_id)?
+            {
+                Some(voting_heights) => {
+                    let current_height = self.ctx.get_block_height()?;
+                    // Voted outside of voting window
+                    if !voting_heights.can_be_voted(current_height, false) {
+                        tracing::info!(
+                            "Voted outside voting window. Current height: \
+                             {current_height}, start: {}, end: {}.",
+                            voting_heights.start,
+                            voting_heights.end
+                        );
+                        return Ok(false);
+                    }
+                    // The stake of voters is snapshotted at the epoch
+                    // containing the voting start height
+                    let Some(start_epoch) = self
+                        .ctx
+                        .get_pred_epochs()?
+                        .get_epoch(voting_heights.start)
+                    else {
+                        return Ok(false);
+                    };
+                    (
+                        start_epoch,
+                        voting_heights.can_be_voted(current_height, true),
+                    )
+                }
+                None => {
+                    let pre_voting_start_epoch: Epoch = self.force_read(
+                        &gov_storage::get_voting_start_epoch_key(proposal_id),
+                        ReadType::Pre,
+                    )?;
+                    let pre_voting_end_epoch: Epoch = self.force_read(
+                        &gov_storage::get_voting_end_epoch_key(proposal_id),
+                        ReadType::Pre,
+                    )?;
+                    // Voted outside of voting window. We dont check for
+                    // validator because if the proposal type is validator, we
+                    // need to let them vote for the entire voting window.
+                    if !self.is_valid_voting_window(
+                        current_epoch,
+                        pre_voting_start_epoch,
+                        pre_voting_end_epoch,
+                        false,
+                    ) {
+                        tracing::info!(
+                            "Voted outside voting window. Current epoch: \
+                             {current_epoch}, start: \
+                             {pre_voting_start_epoch}, end: \
+                             {pre_voting_end_epoch}."
+                        );
+                        return Ok(false);
+                    }
+                    (
+                        pre_voting_start_epoch,
+                        is_valid_validator_voting_period(
+                            current_epoch,
+                            pre_voting_start_epoch,
+                            pre_voting_end_epoch,
+                        ),
+                    )
+                }
+            };
+
+        // the role of the voter is recorded in the vote key
+        match voter_type {
+            gov_storage::VoterType::Validator => {
+                let is_validator = self
+                    .is_validator(
+                        stake_epoch,
+                        verifiers,
+                        voter_address,
+                        delegation_address,
+                    )
+                    .unwrap_or(false);
+                if !is_validator && is_council_member_vote {
+                    // the vote of a member without stake carries no voting
+                    // power, it only counts towards the council threshold
+                    return Ok(verifiers.contains(voter_address));
+                }
+                Ok(is_validator && valid_validator_voting_period)
+            }
+            gov_storage::VoterType::Delegator => Ok(self
+                .is_delegator(
+                    stake_epoch,
+                    verifiers,
+                    voter_address,
+                    delegation_address,
+                )
+                .unwrap_or(false)),
         }
+    }
 
-        // first check if validator, then check if delegator
-        let is_validator = self
-            .is_validator(
-                pre_voting_start_epoch,
-                verifiers,
-                voter_address,
-                delegation_address,
-            )
-            .unwrap_or(false);
+    /// Check if the given voter is a member of the council, voting on a
+    /// council proposal
+    fn is_council_member_vote(
+        &self,
+        proposal_id: u64,
+        voter: &Address,
+    ) -> Result<bool> {
+        let proposal_type: ProposalType = self.force_read(
+            &gov_storage::get_proposal_type_key(proposal_id),
+            ReadType::Pre,
+        )?;
+        if !matches!(proposal_type, ProposalType::Council(_)) {
+            return Ok(false);
+        }
+        Ok(get_council(&self.ctx.pre())?
+            .map_or(false, |council| council.members.contains(voter)))
+    }
 
-        if is_validator {
-            let valid_voting_period = is_valid_validator_voting_period(
-                current_epoch,
-                pre_voting_start_epoch,
-                pre_voting_end_epoch,
-            );
-            return Ok(valid_voting_period);
+    /// Validate a change to a vote key of the legacy layout. Legacy vote
+    /// keys may only be deleted, when the vote is written to the current
+    /// key layout by the same transaction.
+    fn is_valid_legacy_vote_key(
+        &self,
+        key: &Key,
+        keys_changed: &BTreeSet<Key>,
+    ) -> Result<bool> {
+        let Some(legacy) = gov_storage::parse_legacy_vote_key(key) else {
+            return Err(Error::InvalidVoteKey(key.to_string()));
+        };
+        if self.ctx.has_key_post(key)? {
+            return Ok(false);
         }
+        let vote_key = gov_storage::get_vote_proposal_key(
+            legacy.proposal_id,
+            legacy.voter.clone(),
+            legacy.validator.clone(),
+        );
+        Ok(keys_changed.contains(&vote_key))
+    }
 
-        let is_delegator = self
-            .is_delegator(
-                pre_voting_start_epoch,
-                verifiers,
-                voter_address,
-                delegation_address,
-            )
-            .unwrap_or(false);
-        Ok(is_delegator)
+    /// Validate a change to the cursor of the migration of the vote keys,
+    /// which may only be moved forward by the code of an accepted proposal
+    fn is_valid_vote_key_migration_cursor(&self, tx_data: &Tx) -> Result<bool> {
+        let cursor_key = gov_storage::get_vote_key_migration_cursor_key();
+        let pre_cursor: Option<u64> = self.ctx.pre().read(&cursor_key)?;
+        let post_cursor: Option<u64> = self.ctx.post().read(&cursor_key)?;
+        let is_moved_forward = match (pre_cursor, post_cursor) {
+            (_, None) => false,
+            (None, Some(_)) => true,
+            (Some(pre_cursor), Some(post_cursor)) => post_cursor > pre_cursor,
+        };
+        Ok(is_moved_forward && self.is_valid_parameter(tx_data)?)
     }
 
     /// Validate a content key
@@ -427,35 +630,377 @@ where
                     && are_continuous_fundings_unique
                     && are_targets_unique)
             }
+            ProposalType::ETHBridgeContracts(update) => {
+                // the new contracts can't be activated before the proposal
+                // is executed
+                let grace_epoch_key =
+                    gov_storage::get_grace_epoch_key(proposal_id);
+                let grace_epoch: Epoch =
+                    self.force_read(&grace_epoch_key, ReadType::Post)?;
+                let is_valid_activation_epoch =
+                    update.activation_epoch >= grace_epoch;
+                if !is_valid_activation_epoch {
+                    tracing::info!(
+                        "Bridge contracts activation epoch {} is before the \
+                         grace epoch {}.",
+                        update.activation_epoch,
+                        grace_epoch
+                    );
+                }
+                Ok(is_valid_activation_epoch)
+            }
+            ProposalType::UpdateWasmAllowlist(update) => {
+                let is_valid_update = update.is_valid();
+                if !is_valid_update {
+                    tracing::info!(
+                        "Wasm allowlist update must change at least one code \
+                         hash, and its hashes must be non-zero and unique."
+                    );
+                }
+                Ok(is_valid_update)
+            }
+            ProposalType::ETHHotKeyRotation(rotation) => {
+                self.is_valid_eth_hot_key_rotation(proposal_id, &rotation)
+            }
+            ProposalType::Council(_) => {
+                // council proposals can only be passed by a council that is
+                // able to meet its threshold
+                let council = get_council(&self.ctx.pre())?;
+                let is_valid_council =
+                    council.as_ref().map_or(false, Council::is_valid);
+                if !is_valid_council {
+                    tracing::info!(
+                        "Council proposals require a council whose threshold \
+                         can be met by the weight of its members, found {:?}.",
+                        council
+                    );
+                }
+                Ok(is_valid_council)
+            }
+            ProposalType::TreasurySpend(spend) => {
+                Ok(self.is_valid_treasury_spend(&spend))
+            }
+            ProposalType::ParameterChange(change) => {
+                Ok(self.is_valid_parameter_change(&change))
+            }
             _ => Ok(true), // default proposal
         }
     }
 
+    /// Validate a treasury spend, which must move a non-zero amount out of
+    /// the treasury. Whether the treasury can cover it is only known once
+    /// the proposal passes.
+    fn is_valid_treasury_spend(&self, spend: &TreasurySpend) -> bool {
+        if spend.amount.is_zero() {
+            tracing::info!("Treasury spends must have a non-zero amount.");
+            return false;
+        }
+        if spend.recipient == namada_governance::pgf::ADDRESS {
+            tracing::info!("Treasury spends can't pay the treasury itself.");
+            return false;
+        }
+        true
+    }
+
+    /// Validate a parameter change, which must change at least one parameter
+    /// and each parameter at most once. Every key must be in the registry of
+    /// protocol parameters changeable by governance, and its new value must
+    /// decode as the type of the parameter.
+    fn is_valid_parameter_change(&self, change: &ParameterChange) -> bool {
+        if change.changes.is_empty() {
+            tracing::info!("Parameter changes must change some parameter.");
+            return false;
+        }
+        let mut keys = BTreeSet::new();
+        for (key, value) in &change.changes {
+            if !keys.insert(key) {
+                tracing::info!("Parameter {} is changed more than once.", key);
+                return false;
+            }
+            if !namada_parameters::storage::is_valid_parameter_change(
+                key, value,
+            ) {
+                tracing::info!(
+                    "Key {} is not a parameter changeable by governance, or \
+                     its new value doesn't decode as the type of the \
+                     parameter.",
+                    key
+                );
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Validate an Ethereum hot key rotation. As it overrides a key that is
+    /// otherwise only managed by the validator, the rotation must target an
+    /// existing validator, replace its hot key with a different `secp256k1`
+    /// key that isn't its cold key, and take effect exactly at the pipeline
+    /// epoch of the grace epoch, such that the validator set updates signed
+    /// before the proposal is executed remain valid.
+    fn is_valid_eth_hot_key_rotation(
+        &self,
+        proposal_id: u64,
+        rotation: &EthHotKeyRotation,
+    ) -> Result<bool> {
+        if !rotation.has_eth_key() {
+            tracing::info!(
+                "The new Ethereum hot key of validator {} is not a secp256k1 \
+                 key.",
+                rotation.validator
+            );
+            return Ok(false);
+        }
+        let pre = self.ctx.pre();
+        if !is_validator(&pre, &rotation.validator)? {
+            tracing::info!(
+                "Can't rotate the Ethereum hot key of {}, which is not a \
+                 validator.",
+                rotation.validator
+            );
+            return Ok(false);
+        }
+
+        let grace_epoch_key = gov_storage::get_grace_epoch_key(proposal_id);
+        let grace_epoch: Epoch =
+            self.force_read(&grace_epoch_key, ReadType::Post)?;
+        let params = read_pos_params(&pre)?;
+        let pipeline_epoch = grace_epoch + params.pipeline_len;
+        if rotation.activation_epoch != pipeline_epoch {
+            tracing::info!(
+                "Ethereum hot key rotation activation epoch {} is not the \
+                 pipeline epoch {} of the grace epoch {}.",
+                rotation.activation_epoch,
+                pipeline_epoch,
+                grace_epoch
+            );
+            return Ok(false);
+        }
+
+        let hot_key = validator_eth_hot_key_handle(&rotation.validator).get(
+            &pre,
+            pipeline_epoch,
+            &params,
+        )?;
+        let cold_key = validator_eth_cold_key_handle(&rotation.validator).get(
+            &pre,
+            pipeline_epoch,
+            &params,
+        )?;
+        let is_new_key = hot_key.as_ref() != Some(&rotation.eth_hot_key)
+            && cold_key.as_ref() != Some(&rotation.eth_hot_key);
+        if !is_new_key {
+            tracing::info!(
+                "The new Ethereum hot key of validator {} must differ from \
+                 both its current hot and cold keys.",
+                rotation.validator
+            );
+        }
+        Ok(is_new_key)
+    }
+
     /// Validate a proposal code
     pub fn is_valid_proposal_code(&self, proposal_id: u64) -> Result<bool> {
         let proposal_type_key = gov_storage::get_proposal_type_key(proposal_id);
         let proposal_type: ProposalType =
             self.force_read(&proposal_type_key, ReadType::Post)?;
 
-        if !proposal_type.is_default() {
+        if !proposal_type.may_have_code() {
             return Ok(false);
         }
 
         let code_key = gov_storage::get_proposal_code_key(proposal_id);
-        let max_code_size_parameter_key =
-            gov_storage::get_max_proposal_code_size_key();
 
         let has_pre_code: bool = self.ctx.has_key_pre(&code_key)?;
         if has_pre_code {
             return Ok(false);
         }
 
-        let max_proposal_length: usize =
-            self.force_read(&max_code_size_parameter_key, ReadType::Pre)?;
         let post_code: Vec<u8> =
             self.ctx.read_bytes_post(&code_key)?.unwrap_or_default();
+        if !self.is_valid_proposal_code_bytes(proposal_id, &post_code)? {
+            return Ok(false);
+        }
+
+        self.is_valid_proposal_code_hash(proposal_id)
+    }
+
+    /// Validate a chunk of the code of a proposal, which must be written
+    /// along with the code of the proposal and its own hash, within the
+    /// number of its chunks
+    pub fn is_valid_proposal_code_chunk(
+        &self,
+        proposal_id: u64,
+        idx: u64,
+    ) -> Result<bool> {
+        let chunk_key =
+            gov_storage::get_proposal_code_chunk_key(proposal_id, idx);
+        let code_key = gov_storage::get_proposal_code_key(proposal_id);
+        let chunk_count_key =
+            gov_storage::get_proposal_code_chunk_count_key(proposal_id);
 
-        Ok(post_code.len() <= max_proposal_length)
+        let has_pre_chunk = self.ctx.has_key_pre(&chunk_key)?;
+        if has_pre_chunk || !self.ctx.has_key_post(&code_key)? {
+            return Ok(false);
+        }
+
+        let chunk_count: u64 =
+            self.force_read(&chunk_count_key, ReadType::Post)?;
+        if idx >= chunk_count {
+            return Ok(false);
+        }
+
+        let post_chunk: Vec<u8> =
+            self.ctx.read_bytes_post(&chunk_key)?.unwrap_or_default();
+        if !self.is_valid_proposal_code_bytes(proposal_id, &post_chunk)? {
+            return Ok(false);
+        }
+
+        self.is_valid_proposal_code_chunk_hash(proposal_id, idx)
+    }
+
+    /// Validate the hash of a chunk of the code of a proposal, which must be
+    /// written along with the chunk it commits to
+    pub fn is_valid_proposal_code_chunk_hash(
+        &self,
+        proposal_id: u64,
+        idx: u64,
+    ) -> Result<bool> {
+        let chunk_key =
+            gov_storage::get_proposal_code_chunk_key(proposal_id, idx);
+        let chunk_hash_key =
+            gov_storage::get_proposal_code_chunk_hash_key(proposal_id, idx);
+
+        let has_pre_chunk_hash = self.ctx.has_key_pre(&chunk_hash_key)?;
+        if has_pre_chunk_hash {
+            return Ok(false);
+        }
+
+        let post_chunk_hash: Option<Hash> =
+            self.ctx.post().read(&chunk_hash_key)?;
+        let post_chunk = self.ctx.read_bytes_post(&chunk_key)?;
+
+        Ok(matches!(
+            (post_chunk_hash, post_chunk),
+            (Some(hash), Some(chunk)) if hash == Hash::sha256(chunk)
+        ))
+    }
+
+    /// Validate the number of chunks of the code of a proposal, all of which
+    /// must be written along with the code of the proposal
+    pub fn is_valid_proposal_code_chunk_count(
+        &self,
+        proposal_id: u64,
+    ) -> Result<bool> {
+        let code_key = gov_storage::get_proposal_code_key(proposal_id);
+        let chunk_count_key =
+            gov_storage::get_proposal_code_chunk_count_key(proposal_id);
+
+        let has_pre_chunk_count = self.ctx.has_key_pre(&chunk_count_key)?;
+        if has_pre_chunk_count || !self.ctx.has_key_post(&code_key)? {
+            return Ok(false);
+        }
+
+        let chunk_count: u64 =
+            self.force_read(&chunk_count_key, ReadType::Post)?;
+        if chunk_count == 0 {
+            return Ok(false);
+        }
+        for idx in 0..chunk_count {
+            let chunk_key =
+                gov_storage::get_proposal_code_chunk_key(proposal_id, idx);
+            if !self.ctx.has_key_post(&chunk_key)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Check if some code of a proposal doesn't exceed the maximum proposal
+    /// code size, once decompressed if it's compressed, and, under strict
+    /// proposal code validation, that it's a well-formed wasm module
+    fn is_valid_proposal_code_bytes(
+        &self,
+        proposal_id: u64,
+        code: &[u8],
+    ) -> Result<bool> {
+        let max_code_size_parameter_key =
+            gov_storage::get_max_proposal_code_size_key();
+        let max_proposal_length: u64 =
+            self.force_read(&max_code_size_parameter_key, ReadType::Pre)?;
+        let code =
+            match gov_code::decode_code(code.to_vec(), max_proposal_length) {
+                Ok(code) => code,
+                Err(err) => {
+                    tracing::info!(
+                        "The code of proposal {proposal_id} is rejected: {err}"
+                    );
+                    return Ok(false);
+                }
+            };
+
+        let strict_code_validation =
+            get_strict_proposal_code_validation(&self.ctx.pre())?;
+        if strict_code_validation
+            && !self.is_well_formed_proposal_code(proposal_id, &code)?
+        {
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// Check if the code of a proposal is a well-formed wasm module, only
+    /// using the features allowed in transactions. The validation is
+    /// charged the same gas as the validation of the code of a transaction.
+    fn is_well_formed_proposal_code(
+        &self,
+        proposal_id: u64,
+        code: &[u8],
+    ) -> Result<bool> {
+        self.ctx
+            .gas_meter
+            .borrow_mut()
+            .add_wasm_validation_gas(code.len() as u64)
+            .map_err(|_| {
+                native_vp::Error::SimpleMessage(
+                    "Gas limit exceeded in native vp",
+                )
+            })?;
+        match validate_untrusted_wasm(code) {
+            Ok(()) => Ok(true),
+            Err(err) => {
+                tracing::info!(
+                    "The code of proposal {proposal_id} isn't a well-formed \
+                     wasm module: {err}"
+                );
+                Ok(false)
+            }
+        }
+    }
+
+    /// Validate a proposal code hash, which must be written along with the
+    /// code it commits to
+    pub fn is_valid_proposal_code_hash(
+        &self,
+        proposal_id: u64,
+    ) -> Result<bool> {
+        let code_key = gov_storage::get_proposal_code_key(proposal_id);
+        let code_hash_key =
+            gov_storage::get_proposal_code_hash_key(proposal_id);
+
+        let has_pre_code_hash = self.ctx.has_key_pre(&code_hash_key)?;
+        if has_pre_code_hash {
+            return Ok(false);
+        }
+
+        let post_code_hash: Option<Hash> =
+            self.ctx.post().read(&code_hash_key)?;
+        let post_code = self.ctx.read_bytes_post(&code_key)?;
+
+        Ok(matches!(
+            (post_code_hash, post_code),
+            (Some(hash), Some(code)) if hash == Hash::sha256(code)
+        ))
     }
 
     /// Validate a grace_epoch key
@@ -469,7 +1014,7 @@ where
             gov_storage::get_min_proposal_grace_epoch_key();
 
         let has_pre_grace_epoch = self.ctx.has_key_pre(&grace_epoch_key)?;
-        if has_pre_grace_epoch {
+        if has_pre_grace_epoch || self.has_voting_heights(proposal_id)? {
             return Ok(false);
         }
 
@@ -533,7 +1078,10 @@ where
         let has_pre_start_epoch = self.ctx.has_key_pre(&start_epoch_key)?;
         let has_pre_end_epoch = self.ctx.has_key_pre(&end_epoch_key)?;
 
-        if has_pre_start_epoch || has_pre_end_epoch {
+        if has_pre_start_epoch
+            || has_pre_end_epoch
+            || self.has_voting_heights(proposal_id)?
+        {
             return Ok(false);
         }
 
@@ -567,7 +1115,10 @@ where
         let has_pre_start_epoch = self.ctx.has_key_pre(&start_epoch_key)?;
         let has_pre_end_epoch = self.ctx.has_key_pre(&end_epoch_key)?;
 
-        if has_pre_start_epoch || has_pre_end_epoch {
+        if has_pre_start_epoch
+            || has_pre_end_epoch
+            || self.has_voting_heights(proposal_id)?
+        {
             return Ok(false);
         }
 
@@ -593,6 +1144,99 @@ where
             && (end_epoch - start_epoch).0 <= max_period)
     }
 
+    /// Validate the voting start and end height keys of a proposal voted on
+    /// over block heights
+    fn is_valid_voting_heights(&self, proposal_id: u64) -> Result<bool> {
+        let start_height_key =
+            gov_storage::get_voting_start_height_key(proposal_id);
+        let end_height_key =
+            gov_storage::get_voting_end_height_key(proposal_id);
+
+        let has_pre_start_height = self.ctx.has_key_pre(&start_height_key)?;
+        let has_pre_end_height = self.ctx.has_key_pre(&end_height_key)?;
+
+        if has_pre_start_height || has_pre_end_height {
+            return Ok(false);
+        }
+
+        // The voting period can't be expressed in epochs as well
+        for epoch_key in [
+            gov_storage::get_voting_start_epoch_key(proposal_id),
+            gov_storage::get_voting_end_epoch_key(proposal_id),
+            gov_storage::get_grace_epoch_key(proposal_id),
+        ] {
+            if self.ctx.has_key_post(&epoch_key)? {
+                tracing::info!(
+                    "Proposal {proposal_id} has a voting period expressed \
+                     both in block heights and in epochs."
+                );
+                return Ok(false);
+            }
+        }
+
+        let start_height: BlockHeight =
+            self.force_read(&start_height_key, ReadType::Post)?;
+        let end_height: BlockHeight =
+            self.force_read(&end_height_key, ReadType::Post)?;
+        let (min_period, max_period) =
+            get_proposal_voting_period_blocks(&self.ctx.pre())?;
+
+        let current_height = self.ctx.get_block_height()?;
+
+        if end_height <= start_height || start_height <= current_height {
+            tracing::info!(
+                "Proposal end height ({end_height}) must be after the start \
+                 height ({start_height}), and the start height must be after \
+                 the current height ({current_height})."
+            );
+            return Ok(false);
+        }
+
+        let committing_height_key =
+            gov_storage::get_committing_proposals_at_height_key(
+                proposal_id,
+                end_height.0,
+            );
+        if !self.ctx.has_key_post(&committing_height_key)? {
+            tracing::info!(
+                "Proposal {proposal_id} isn't committed to be concluded at \
+                 its end height ({end_height})."
+            );
+            return Ok(false);
+        }
+
+        let voting_period = end_height.0 - start_height.0;
+        Ok(voting_period >= min_period && voting_period <= max_period)
+    }
+
+    /// Validate the execution priority key of a proposal, which may only be
+    /// written along with the proposal it belongs to
+    fn is_valid_priority(&self, proposal_id: u64) -> Result<bool> {
+        let priority_key = gov_storage::get_priority_key(proposal_id);
+        let content_key = gov_storage::get_content_key(proposal_id);
+
+        let is_new_proposal = !self.ctx.has_key_pre(&content_key)?
+            && self.ctx.has_key_post(&content_key)?;
+        let has_pre_priority = self.ctx.has_key_pre(&priority_key)?;
+        if !is_new_proposal || has_pre_priority {
+            return Ok(false);
+        }
+
+        let priority: Option<u8> = self.ctx.post().read(&priority_key)?;
+        Ok(priority.is_some())
+    }
+
+    /// Check if a proposal has a voting period expressed in block heights,
+    /// after the tx is applied
+    fn has_voting_heights(&self, proposal_id: u64) -> Result<bool> {
+        let start_height_key =
+            gov_storage::get_voting_start_height_key(proposal_id);
+        let end_height_key =
+            gov_storage::get_voting_end_height_key(proposal_id);
+        Ok(self.ctx.has_key_post(&start_height_key)?
+            || self.ctx.has_key_post(&end_height_key)?)
+    }
+
     /// Validate a funds key
     pub fn is_valid_funds(
         &self,
@@ -604,10 +1248,25 @@ where
             native_token_address,
             self.ctx.address,
         );
-        let min_funds_parameter_key = gov_storage::get_min_proposal_fund_key();
+        let content_key = gov_storage::get_content_key(proposal_id);
+        let code_key = gov_storage::get_proposal_code_key(proposal_id);
+
+        // The deposit scales with the size of the proposal
+        let content_len = self
+            .ctx
+            .read_bytes_post(&content_key)?
+            .map(|content| content.len())
+            .unwrap_or_default();
+        let code_len = self
+            .ctx
+            .read_bytes_post(&code_key)?
+            .map(|code| code.len())
+            .unwrap_or_default();
+        let required_deposit = get_required_deposit(
+            &self.ctx.pre(),
+            (content_len + code_len) as u64,
+        )?;
 
-        let min_funds_parameter: token::Amount =
-            self.force_read(&min_funds_parameter_key, ReadType::Pre)?;
         let pre_balance: Option<token::Amount> =
             self.ctx.pre().read(&balance_key)?;
         let post_balance: token::Amount =
@@ -615,14 +1274,22 @@ where
         let post_funds: token::Amount =
             self.force_read(&funds_key, ReadType::Post)?;
 
+        let is_post_funds_greater_than_required =
+            post_funds >= required_deposit;
+        if !is_post_funds_greater_than_required {
+            tracing::info!(
+                "Proposal deposit {} is lower than the required deposit {}.",
+                post_funds.to_string_native(),
+                required_deposit.to_string_native()
+            );
+        }
         if let Some(pre_balance) = pre_balance {
-            let is_post_funds_greater_than_minimum =
-                post_funds >= min_funds_parameter;
             let is_valid_funds = post_balance >= pre_balance
                 && post_balance - pre_balance == post_funds;
-            Ok(is_post_funds_greater_than_minimum && is_valid_funds)
+            Ok(is_post_funds_greater_than_required && is_valid_funds)
         } else {
-            Ok(post_funds >= min_funds_parameter && post_balance == post_funds)
+            Ok(is_post_funds_greater_than_required
+                && post_balance == post_funds)
         }
     }
 
@@ -666,8 +1333,37 @@ where
         let author = self.force_read(&author_key, ReadType::Post)?;
         let author_exists =
             namada_account::exists(&self.ctx.pre(), &author).unwrap_or(false);
+        if !(author_exists && verifiers.contains(&author)) {
+            return Ok(false);
+        }
+
+        self.is_sufficiently_staked(&author)
+    }
+
+    /// Check that the author of a proposal has bonded at least the minimum
+    /// stake required to submit proposals, counting both its self-bonds and
+    /// its delegations at the current epoch
+    fn is_sufficiently_staked(&self, author: &Address) -> Result<bool> {
+        let min_author_stake = get_min_proposal_author_stake(&self.ctx.pre())?;
+        if min_author_stake.is_zero() {
+            return Ok(true);
+        }
 
-        Ok(author_exists && verifiers.contains(&author))
+        let current_epoch = self.ctx.get_block_epoch()?;
+        let author_stake: token::Amount =
+            find_delegations(&self.ctx.pre(), author, &current_epoch)?
+                .into_values()
+                .sum();
+        let is_sufficiently_staked = author_stake >= min_author_stake;
+        if !is_sufficiently_staked {
+            tracing::info!(
+                "Proposal author {author} has bonded {}, which is lower than \
+                 the required stake {}.",
+                author_stake.to_string_native(),
+                min_author_stake.to_string_native()
+            );
+        }
+        Ok(is_sufficiently_staked)
     }
 
     /// Validate a counter key
@@ -693,6 +1389,13 @@ where
         Ok(pre_counter < post_counter)
     }
 
+    /// Validate the output of the code of a proposal, which may only be
+    /// written while the proposal is being executed
+    pub fn is_valid_proposal_output(&self, proposal_id: u64) -> Result<bool> {
+        is_proposal_executing(&self.ctx.pre(), proposal_id)
+            .map_err(Error::NativeVpError)
+    }
+
     /// Validate a governance parameter
     pub fn is_valid_parameter(&self, tx: &Tx) -> Result<bool> {
         match tx.data() {
@@ -702,6 +1405,61 @@ where
         }
     }
 
+    /// Validate a governance voting key registration. Only the owner of the
+    /// key may register or remove it, and only Ed25519 keys are supported.
+    pub fn is_valid_voting_key(
+        &self,
+        key: &Key,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<bool> {
+        let owner = match gov_storage::is_voting_key_key(key) {
+            Some(owner) => owner,
+            None => return Err(Error::InvalidVoteKey(key.to_string())),
+        };
+        if !verifiers.contains(owner) {
+            return Ok(false);
+        }
+
+        let voting_key: Option<common::PublicKey> =
+            self.ctx.post().read(key)?;
+        Ok(match voting_key {
+            Some(voting_key) => {
+                matches!(voting_key, common::PublicKey::Ed25519(_))
+            }
+            // the owner has removed its voting key
+            None => true,
+        })
+    }
+
+    /// Validate a governance vote delegation. Only the delegator may set or
+    /// revoke its delegation, and the delegation must neither be circular
+    /// nor exceed the maximum delegation depth.
+    pub fn is_valid_vote_delegation(
+        &self,
+        key: &Key,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<bool> {
+        let delegator = match gov_storage::is_delegation_key(key) {
+            Some(delegator) => delegator,
+            None => return Err(Error::InvalidVoteKey(key.to_string())),
+        };
+        if !verifiers.contains(delegator) {
+            return Ok(false);
+        }
+
+        let governor: Option<Address> = self.ctx.post().read(key)?;
+        match governor {
+            Some(governor) => is_valid_delegation_chain(
+                &self.ctx.post(),
+                delegator,
+                &governor,
+            )
+            .map_err(Error::NativeVpError),
+            // the delegator has revoked its delegation
+            None => Ok(true),
+        }
+    }
+
     /// Check if a vote is from a validator
     pub fn is_validator(
         &self,
@@ -724,6 +1482,41 @@ where
         Ok(is_validator && verifiers.contains(address))
     }
 
+    /// Validate an entry of the votes index of a voter. The entry must
+    /// record the vote written by the same transaction on the indexed
+    /// proposal, at the current epoch.
+    pub fn is_valid_voter_index_key(
+        &self,
+        key: &Key,
+        keys_changed: &BTreeSet<Key>,
+    ) -> Result<bool> {
+        let (voter, proposal_id) = match gov_storage::is_voter_index_key(key) {
+            Some(index) => index,
+            None => return Err(Error::InvalidVoteKey(key.to_string())),
+        };
+        let receipt: VoteReceipt = self.force_read(key, ReadType::Post)?;
+        let current_epoch = self.ctx.get_block_epoch()?;
+        if receipt.proposal_id != proposal_id || receipt.epoch != current_epoch
+        {
+            return Ok(false);
+        }
+
+        let mut has_vote = false;
+        for vote_key in keys_changed.iter().filter(|changed| {
+            gov_storage::is_vote_key(changed)
+                && gov_storage::get_proposal_id(changed) == Some(proposal_id)
+                && gov_storage::get_voter_address(changed) == Some(voter)
+        }) {
+            let vote: ProposalVote =
+                self.force_read(vote_key, ReadType::Post)?;
+            if vote != receipt.vote {
+                return Ok(false);
+            }
+            has_vote = true;
+        }
+        Ok(has_vote)
+    }
+
     /// Private method to read from storage data that are 100% in storage.
     fn force_read<T>(&self, key: &Key, read_type: ReadType) -> Result<T>
     where
@@ -785,10 +1578,20 @@ enum KeyType {
     #[allow(non_camel_case_types)]
     VOTE,
     #[allow(non_camel_case_types)]
+    LEGACY_VOTE,
+    #[allow(non_camel_case_types)]
     CONTENT,
     #[allow(non_camel_case_types)]
     PROPOSAL_CODE,
     #[allow(non_camel_case_types)]
+    PROPOSAL_CODE_HASH,
+    #[allow(non_camel_case_types)]
+    PROPOSAL_CODE_CHUNK(u64),
+    #[allow(non_camel_case_types)]
+    PROPOSAL_CODE_CHUNK_HASH(u64),
+    #[allow(non_camel_case_types)]
+    PROPOSAL_CODE_CHUNK_COUNT,
+    #[allow(non_camel_case_types)]
     TYPE,
     #[allow(non_camel_case_types)]
     PROPOSAL_COMMIT,
@@ -799,6 +1602,14 @@ enum KeyType {
     #[allow(non_camel_case_types)]
     END_EPOCH,
     #[allow(non_camel_case_types)]
+    START_HEIGHT,
+    #[allow(non_camel_case_types)]
+    END_HEIGHT,
+    #[allow(non_camel_case_types)]
+    PRIORITY,
+    #[allow(non_camel_case_types)]
+    PROPOSAL_OUTPUT,
+    #[allow(non_camel_case_types)]
     FUNDS,
     #[allow(non_camel_case_types)]
     BALANCE,
@@ -807,6 +1618,14 @@ enum KeyType {
     #[allow(non_camel_case_types)]
     PARAMETER,
     #[allow(non_camel_case_types)]
+    VOTING_KEY,
+    #[allow(non_camel_case_types)]
+    VOTER_INDEX,
+    #[allow(non_camel_case_types)]
+    VOTE_DELEGATION,
+    #[allow(non_camel_case_types)]
+    VOTE_KEY_MIGRATION_CURSOR,
+    #[allow(non_camel_case_types)]
     UNKNOWN_GOVERNANCE,
     #[allow(non_camel_case_types)]
     UNKNOWN,
@@ -816,20 +1635,42 @@ impl KeyType {
     fn from_key(key: &Key, native_token: &Address) -> Self {
         if gov_storage::is_vote_key(key) {
             Self::VOTE
+        } else if gov_storage::is_legacy_vote_key(key) {
+            Self::LEGACY_VOTE
         } else if gov_storage::is_content_key(key) {
             KeyType::CONTENT
         } else if gov_storage::is_proposal_type_key(key) {
             Self::TYPE
         } else if gov_storage::is_proposal_code_key(key) {
             Self::PROPOSAL_CODE
+        } else if gov_storage::is_proposal_code_hash_key(key) {
+            Self::PROPOSAL_CODE_HASH
+        } else if let Some(idx) = gov_storage::is_proposal_code_chunk_key(key) {
+            Self::PROPOSAL_CODE_CHUNK(idx)
+        } else if let Some(idx) =
+            gov_storage::is_proposal_code_chunk_hash_key(key)
+        {
+            Self::PROPOSAL_CODE_CHUNK_HASH(idx)
+        } else if gov_storage::is_proposal_code_chunk_count_key(key) {
+            Self::PROPOSAL_CODE_CHUNK_COUNT
         } else if gov_storage::is_grace_epoch_key(key) {
             KeyType::GRACE_EPOCH
         } else if gov_storage::is_start_epoch_key(key) {
             KeyType::START_EPOCH
-        } else if gov_storage::is_commit_proposal_key(key) {
+        } else if gov_storage::is_commit_proposal_key(key)
+            || gov_storage::is_commit_proposal_at_height_key(key)
+        {
             KeyType::PROPOSAL_COMMIT
         } else if gov_storage::is_end_epoch_key(key) {
             KeyType::END_EPOCH
+        } else if gov_storage::is_start_height_key(key) {
+            KeyType::START_HEIGHT
+        } else if gov_storage::is_end_height_key(key) {
+            KeyType::END_HEIGHT
+        } else if gov_storage::is_priority_key(key) {
+            KeyType::PRIORITY
+        } else if gov_storage::is_proposal_output_key(key) {
+            KeyType::PROPOSAL_OUTPUT
         } else if gov_storage::is_balance_key(key) {
             KeyType::FUNDS
         } else if gov_storage::is_author_key(key) {
@@ -838,6 +1679,14 @@ impl KeyType {
             KeyType::COUNTER
         } else if gov_storage::is_parameter_key(key) {
             KeyType::PARAMETER
+        } else if gov_storage::is_voting_key_key(key).is_some() {
+            KeyType::VOTING_KEY
+        } else if gov_storage::is_voter_index_key(key).is_some() {
+            KeyType::VOTER_INDEX
+        } else if gov_storage::is_delegation_key(key).is_some() {
+            KeyType::VOTE_DELEGATION
+        } else if gov_storage::is_vote_key_migration_cursor_key(key) {
+            KeyType::VOTE_KEY_MIGRATION_CURSOR
         } else if token::storage_key::is_balance_key(native_token, key)
             .is_some()
         {
@@ -849,3 +1698,788 @@ impl KeyType {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use borsh_ext::BorshSerializeExt;
+    use namada_ethereum_bridge::test_utils;
+    use namada_gas::TxGasMeter;
+    use namada_governance::parameters::GovernanceParameters;
+    use namada_state::testing::TestWlStorage;
+    use namada_state::StorageWrite;
+    use namada_tx::data::TxType;
+    use namada_tx::{Code, Data, Section, Signature};
+
+    use super::*;
+    use crate::core::types::address::testing::{
+        established_address_1, established_address_2, established_address_3,
+    };
+    use crate::ledger::gas::VpGasMeter;
+    use crate::types::key::testing::keypair_1;
+    use crate::types::storage::TxIndex;
+    use crate::vm::wasm::compilation_cache::common::testing::cache as wasm_cache;
+
+    fn dummy_tx(wl_storage: &TestWlStorage) -> Tx {
+        let mut tx = Tx::from_type(TxType::Raw);
+        tx.header.chain_id = wl_storage.storage.chain_id.clone();
+        tx.set_code(Code::new(vec![], None));
+        tx.set_data(Data::new(vec![]));
+        tx.add_section(Section::Signature(Signature::new(
+            tx.sechashes(),
+            [(0, keypair_1())].into_iter().collect(),
+            None,
+        )));
+        tx
+    }
+
+    /// Validate the author key of a new proposal submitted by `author`,
+    /// given the minimum stake required from proposal authors. The default
+    /// validator has a self-bond of 100 NAM, whereas
+    /// [`established_address_2`] has no bonds.
+    fn is_valid_author_with_min_stake(
+        author: &Address,
+        min_proposal_author_stake: token::Amount,
+    ) -> bool {
+        let (mut wl_storage, _) = test_utils::setup_default_storage();
+        GovernanceParameters {
+            min_proposal_author_stake,
+            ..Default::default()
+        }
+        .init_storage(&mut wl_storage)
+        .expect("Test failed");
+        for address in
+            [test_utils::default_validator().0, established_address_2()]
+        {
+            wl_storage
+                .write(&Key::validity_predicate(&address), Hash::zero())
+                .expect("Test failed");
+        }
+        wl_storage.commit_block().expect("Test failed");
+
+        let author_key = gov_storage::get_author_key(0);
+        wl_storage
+            .write_log
+            .write(&author_key, author.serialize_to_vec())
+            .expect("Test failed");
+        let keys_changed = BTreeSet::from([author_key]);
+        let verifiers = BTreeSet::from([author.clone()]);
+
+        let tx_index = TxIndex::default();
+        let tx = dummy_tx(&wl_storage);
+        let gas_meter = VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+        );
+        let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+        let ctx = Ctx::new(
+            &ADDRESS,
+            &wl_storage.storage,
+            &wl_storage.write_log,
+            &tx,
+            &tx_index,
+            gas_meter,
+            &keys_changed,
+            &verifiers,
+            vp_wasm_cache,
+        );
+        let vp = GovernanceVp { ctx };
+        vp.is_valid_author(0, &verifiers).expect("Test failed")
+    }
+
+    /// Test that proposals are only accepted from authors who have bonded
+    /// at least the minimum proposal author stake.
+    #[test]
+    fn test_min_proposal_author_stake() {
+        let (validator, stake) = test_utils::default_validator();
+        let unbonded = established_address_2();
+
+        // without a minimum stake, anyone may submit a proposal
+        assert!(is_valid_author_with_min_stake(
+            &validator,
+            token::Amount::zero()
+        ));
+        assert!(is_valid_author_with_min_stake(
+            &unbonded,
+            token::Amount::zero()
+        ));
+
+        // an author with enough stake may submit a proposal
+        assert!(is_valid_author_with_min_stake(&validator, stake));
+        assert!(is_valid_author_with_min_stake(
+            &validator,
+            token::Amount::native_whole(1)
+        ));
+
+        // under-staked authors are rejected
+        assert!(!is_valid_author_with_min_stake(
+            &validator,
+            stake + token::Amount::native_whole(1)
+        ));
+        assert!(!is_valid_author_with_min_stake(
+            &unbonded,
+            token::Amount::native_whole(1)
+        ));
+    }
+
+    /// Validate the voting heights of a new proposal, submitted at height
+    /// 100. The proposal may also be committed to be concluded at its end
+    /// height, and have a voting start epoch.
+    fn is_valid_voting_heights_at(
+        start: u64,
+        end: u64,
+        is_committed: bool,
+        has_start_epoch: bool,
+    ) -> bool {
+        let (mut wl_storage, _) = test_utils::setup_default_storage();
+        GovernanceParameters::default()
+            .init_storage(&mut wl_storage)
+            .expect("Test failed");
+        wl_storage.commit_block().expect("Test failed");
+        wl_storage.storage.block.height = BlockHeight(100);
+
+        let mut keys_changed = BTreeSet::new();
+        let mut write = |key: Key, value: Vec<u8>| {
+            wl_storage
+                .write_log
+                .write(&key, value)
+                .expect("Test failed");
+            keys_changed.insert(key);
+        };
+        write(
+            gov_storage::get_voting_start_height_key(0),
+            BlockHeight(start).serialize_to_vec(),
+        );
+        write(
+            gov_storage::get_voting_end_height_key(0),
+            BlockHeight(end).serialize_to_vec(),
+        );
+        if is_committed {
+            write(
+                gov_storage::get_committing_proposals_at_height_key(0, end),
+                ().serialize_to_vec(),
+            );
+        }
+        if has_start_epoch {
+            write(
+                gov_storage::get_voting_start_epoch_key(0),
+                Epoch(3).serialize_to_vec(),
+            );
+        }
+        let verifiers = BTreeSet::new();
+
+        let tx_index = TxIndex::default();
+        let tx = dummy_tx(&wl_storage);
+        let gas_meter = VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+        );
+        let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+        let ctx = Ctx::new(
+            &ADDRESS,
+            &wl_storage.storage,
+            &wl_storage.write_log,
+            &tx,
+            &tx_index,
+            gas_meter,
+            &keys_changed,
+            &verifiers,
+            vp_wasm_cache,
+        );
+        let vp = GovernanceVp { ctx };
+        vp.is_valid_voting_heights(0).expect("Test failed")
+    }
+
+    /// Test the validation of voting periods expressed in block heights,
+    /// which must be within the bounds set by governance and can't be
+    /// combined with a voting period in epochs.
+    #[test]
+    fn test_voting_heights_validation() {
+        let GovernanceParameters {
+            min_proposal_voting_period_blocks: min,
+            max_proposal_voting_period_blocks: max,
+            ..
+        } = GovernanceParameters::default();
+
+        assert!(is_valid_voting_heights_at(101, 101 + min, true, false));
+        assert!(is_valid_voting_heights_at(101, 101 + max, true, false));
+
+        // the voting period must be within bounds
+        assert!(!is_valid_voting_heights_at(101, 100 + min, true, false));
+        assert!(!is_valid_voting_heights_at(101, 102 + max, true, false));
+        // voting can't start before the next block
+        assert!(!is_valid_voting_heights_at(100, 100 + min, true, false));
+        // the proposal must be committed to be concluded at its end height
+        assert!(!is_valid_voting_heights_at(101, 101 + min, false, false));
+        // the voting period can't also be expressed in epochs
+        assert!(!is_valid_voting_heights_at(101, 101 + min, true, true));
+    }
+
+    /// Validate the given parameter changes
+    fn is_valid_parameter_change_of(changes: Vec<(Key, Vec<u8>)>) -> bool {
+        let (wl_storage, _) = test_utils::setup_default_storage();
+        let keys_changed = BTreeSet::new();
+        let verifiers = BTreeSet::new();
+
+        let tx_index = TxIndex::default();
+        let tx = dummy_tx(&wl_storage);
+        let gas_meter = VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+        );
+        let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+        let ctx = Ctx::new(
+            &ADDRESS,
+            &wl_storage.storage,
+            &wl_storage.write_log,
+            &tx,
+            &tx_index,
+            gas_meter,
+            &keys_changed,
+            &verifiers,
+            vp_wasm_cache,
+        );
+        let vp = GovernanceVp { ctx };
+        vp.is_valid_parameter_change(&ParameterChange { changes })
+    }
+
+    /// Test that parameter changes are only valid for the protocol
+    /// parameters in the registry, with values of the type of the parameter
+    #[test]
+    fn test_parameter_change_validation() {
+        use namada_parameters::{storage as params_storage, EpochDuration};
+
+        use crate::types::dec::Dec;
+        use crate::types::time::DurationSecs;
+
+        let epoch_duration_key =
+            params_storage::get_epoch_duration_storage_key();
+        let epoch_duration = EpochDuration {
+            min_num_of_blocks: 10,
+            min_duration: DurationSecs(60),
+        }
+        .serialize_to_vec();
+        let max_block_gas_key = params_storage::get_max_block_gas_key();
+
+        assert!(is_valid_parameter_change_of(vec![
+            (epoch_duration_key.clone(), epoch_duration.clone()),
+            (max_block_gas_key.clone(), 1_000_u64.serialize_to_vec()),
+        ]));
+
+        // some parameter must be changed
+        assert!(!is_valid_parameter_change_of(vec![]));
+        // at most once
+        assert!(!is_valid_parameter_change_of(vec![
+            (epoch_duration_key.clone(), epoch_duration.clone()),
+            (epoch_duration_key.clone(), epoch_duration.clone()),
+        ]));
+        // with a value of the type of the parameter
+        assert!(!is_valid_parameter_change_of(vec![(
+            epoch_duration_key,
+            1_000_u64.serialize_to_vec()
+        )]));
+        assert!(!is_valid_parameter_change_of(vec![(
+            max_block_gas_key,
+            epoch_duration.clone()
+        )]));
+        // only for the parameters in the registry
+        assert!(!is_valid_parameter_change_of(vec![(
+            params_storage::get_staked_ratio_key(),
+            Dec::one().serialize_to_vec()
+        )]));
+        assert!(!is_valid_parameter_change_of(vec![(
+            gov_storage::get_max_proposal_code_size_key(),
+            1_000_u64.serialize_to_vec()
+        )]));
+    }
+
+    /// Validate the code of a new proposal, under the given strict proposal
+    /// code validation parameter
+    fn is_valid_proposal_code_with(
+        strict_proposal_code_validation: bool,
+        code: &[u8],
+    ) -> bool {
+        let (mut wl_storage, _) = test_utils::setup_default_storage();
+        GovernanceParameters {
+            strict_proposal_code_validation,
+            ..Default::default()
+        }
+        .init_storage(&mut wl_storage)
+        .expect("Test failed");
+        wl_storage.commit_block().expect("Test failed");
+
+        let code_hash = Hash::sha256(code);
+        let proposal_type_key = gov_storage::get_proposal_type_key(0);
+        let code_key = gov_storage::get_proposal_code_key(0);
+        let code_hash_key = gov_storage::get_proposal_code_hash_key(0);
+        wl_storage
+            .write_log
+            .write(
+                &proposal_type_key,
+                ProposalType::Default(Some(code_hash)).serialize_to_vec(),
+            )
+            .expect("Test failed");
+        wl_storage
+            .write_log
+            .write(&code_key, code.to_vec())
+            .expect("Test failed");
+        wl_storage
+            .write_log
+            .write(&code_hash_key, code_hash.serialize_to_vec())
+            .expect("Test failed");
+        let keys_changed =
+            BTreeSet::from([proposal_type_key, code_key, code_hash_key]);
+        let verifiers = BTreeSet::new();
+
+        let tx_index = TxIndex::default();
+        let tx = dummy_tx(&wl_storage);
+        let gas_meter = VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+        );
+        let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+        let ctx = Ctx::new(
+            &ADDRESS,
+            &wl_storage.storage,
+            &wl_storage.write_log,
+            &tx,
+            &tx_index,
+            gas_meter,
+            &keys_changed,
+            &verifiers,
+            vp_wasm_cache,
+        );
+        let vp = GovernanceVp { ctx };
+        vp.is_valid_proposal_code(0).expect("Test failed")
+    }
+
+    /// Test that malformed proposal code is only rejected at submission
+    /// under strict proposal code validation
+    #[test]
+    fn test_strict_proposal_code_validation() {
+        // the smallest well-formed wasm module
+        let well_formed = b"\0asm\x01\0\0\0";
+        let malformed = b"not a wasm module";
+
+        // malformed code is accepted, failing once executed
+        assert!(is_valid_proposal_code_with(false, well_formed));
+        assert!(is_valid_proposal_code_with(false, malformed));
+
+        // malformed code is rejected up front
+        assert!(is_valid_proposal_code_with(true, well_formed));
+        assert!(!is_valid_proposal_code_with(true, malformed));
+    }
+
+    /// Test that compressed proposal code is validated once decompressed,
+    /// rejecting code exceeding the maximum proposal code size once
+    /// decompressed
+    #[test]
+    fn test_compressed_proposal_code_validation() {
+        let well_formed = gov_code::compress_code(b"\0asm\x01\0\0\0");
+        let malformed = gov_code::compress_code(b"not a wasm module");
+        assert!(is_valid_proposal_code_with(true, &well_formed));
+        assert!(!is_valid_proposal_code_with(true, &malformed));
+        assert!(is_valid_proposal_code_with(false, &malformed));
+
+        // small once compressed, but too large once decompressed
+        let max_proposal_code_size =
+            GovernanceParameters::default().max_proposal_code_size;
+        let code = vec![0; max_proposal_code_size as usize + 1];
+        let bomb = gov_code::compress_code(&code);
+        assert!((bomb.len() as u64) < max_proposal_code_size);
+        assert!(!is_valid_proposal_code_with(false, &bomb));
+    }
+
+    /// Validate the count and the chunks of the code of a new proposal,
+    /// given the number of chunks written along with them, the maximum
+    /// proposal code size and whether the hash of the last chunk is
+    /// tampered with
+    fn is_valid_proposal_code_chunks_with(
+        chunk_count: u64,
+        chunks: &[&[u8]],
+        max_proposal_code_size: u64,
+        tampered_hash: bool,
+    ) -> (bool, Vec<bool>) {
+        let (mut wl_storage, _) = test_utils::setup_default_storage();
+        GovernanceParameters {
+            max_proposal_code_size,
+            ..Default::default()
+        }
+        .init_storage(&mut wl_storage)
+        .expect("Test failed");
+        wl_storage.commit_block().expect("Test failed");
+
+        let code_key = gov_storage::get_proposal_code_key(0);
+        let chunk_count_key = gov_storage::get_proposal_code_chunk_count_key(0);
+        wl_storage
+            .write_log
+            .write(&code_key, b"\0asm\x01\0\0\0".to_vec())
+            .expect("Test failed");
+        wl_storage
+            .write_log
+            .write(&chunk_count_key, chunk_count.serialize_to_vec())
+            .expect("Test failed");
+        let mut keys_changed = BTreeSet::from([code_key, chunk_count_key]);
+        for (idx, chunk) in chunks.iter().enumerate() {
+            let chunk_key =
+                gov_storage::get_proposal_code_chunk_key(0, idx as u64);
+            let chunk_hash_key =
+                gov_storage::get_proposal_code_chunk_hash_key(0, idx as u64);
+            let chunk_hash = if tampered_hash && idx + 1 == chunks.len() {
+                Hash::sha256(b"")
+            } else {
+                Hash::sha256(chunk)
+            };
+            wl_storage
+                .write_log
+                .write(&chunk_key, chunk.to_vec())
+                .expect("Test failed");
+            wl_storage
+                .write_log
+                .write(&chunk_hash_key, chunk_hash.serialize_to_vec())
+                .expect("Test failed");
+            keys_changed.insert(chunk_key);
+            keys_changed.insert(chunk_hash_key);
+        }
+        let verifiers = BTreeSet::new();
+
+        let tx_index = TxIndex::default();
+        let tx = dummy_tx(&wl_storage);
+        let gas_meter = VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+        );
+        let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+        let ctx = Ctx::new(
+            &ADDRESS,
+            &wl_storage.storage,
+            &wl_storage.write_log,
+            &tx,
+            &tx_index,
+            gas_meter,
+            &keys_changed,
+            &verifiers,
+            vp_wasm_cache,
+        );
+        let vp = GovernanceVp { ctx };
+        let count_valid = vp
+            .is_valid_proposal_code_chunk_count(0)
+            .expect("Test failed");
+        let chunks_valid = (0..chunks.len() as u64)
+            .map(|idx| {
+                vp.is_valid_proposal_code_chunk(0, idx)
+                    .expect("Test failed")
+            })
+            .collect();
+        (count_valid, chunks_valid)
+    }
+
+    /// Test that the chunks of the code of a proposal are only accepted
+    /// within their count and the maximum proposal code size
+    #[test]
+    fn test_proposal_code_chunks_validation() {
+        let chunk: &[u8] = b"\0asm\x01\0\0\0";
+        assert_eq!(
+            is_valid_proposal_code_chunks_with(2, &[chunk, chunk], 100, false),
+            (true, vec![true, true])
+        );
+        // a missing chunk invalidates the count
+        assert_eq!(
+            is_valid_proposal_code_chunks_with(3, &[chunk, chunk], 100, false),
+            (false, vec![true, true])
+        );
+        // a chunk beyond the count is rejected
+        assert_eq!(
+            is_valid_proposal_code_chunks_with(1, &[chunk, chunk], 100, false),
+            (true, vec![true, false])
+        );
+        // an empty bundle must not write a count
+        assert!(!is_valid_proposal_code_chunks_with(0, &[], 100, false).0);
+        // each chunk is subject to the maximum proposal code size
+        let oversized = [0_u8; 101];
+        assert_eq!(
+            is_valid_proposal_code_chunks_with(
+                2,
+                &[chunk, &oversized],
+                100,
+                false
+            ),
+            (true, vec![true, false])
+        );
+        // each chunk must be written along with its own hash
+        assert_eq!(
+            is_valid_proposal_code_chunks_with(2, &[chunk, chunk], 100, true),
+            (true, vec![true, false])
+        );
+    }
+
+    /// Validate the governance vote delegation of `delegator` to `governor`
+    /// (or its revocation), given the delegations already in storage and
+    /// whether the delegator is a verifier of the tx
+    fn is_valid_vote_delegation_with(
+        delegations: &[(&Address, &Address)],
+        delegator: &Address,
+        governor: Option<&Address>,
+        is_verifier: bool,
+    ) -> bool {
+        let (mut wl_storage, _) = test_utils::setup_default_storage();
+        for (delegator, governor) in delegations {
+            wl_storage
+                .write(&gov_storage::get_delegation_key(delegator), *governor)
+                .expect("Test failed");
+        }
+        wl_storage.commit_block().expect("Test failed");
+
+        let delegation_key = gov_storage::get_delegation_key(delegator);
+        match governor {
+            Some(governor) => wl_storage
+                .write_log
+                .write(&delegation_key, governor.serialize_to_vec()),
+            None => wl_storage.write_log.delete(&delegation_key),
+        }
+        .expect("Test failed");
+        let keys_changed = BTreeSet::from([delegation_key.clone()]);
+        let verifiers = if is_verifier {
+            BTreeSet::from([delegator.clone()])
+        } else {
+            BTreeSet::new()
+        };
+
+        let tx_index = TxIndex::default();
+        let tx = dummy_tx(&wl_storage);
+        let gas_meter = VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+        );
+        let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+        let ctx = Ctx::new(
+            &ADDRESS,
+            &wl_storage.storage,
+            &wl_storage.write_log,
+            &tx,
+            &tx_index,
+            gas_meter,
+            &keys_changed,
+            &verifiers,
+            vp_wasm_cache,
+        );
+        let vp = GovernanceVp { ctx };
+        vp.is_valid_vote_delegation(&delegation_key, &verifiers)
+            .expect("Test failed")
+    }
+
+    /// Test that governance vote delegations are only accepted from the
+    /// delegator, and are rejected when circular
+    #[test]
+    fn test_vote_delegation_validation() {
+        let alice = established_address_1();
+        let bob = established_address_2();
+        let carol = established_address_3();
+
+        assert!(is_valid_vote_delegation_with(&[], &alice, Some(&bob), true));
+        // only the delegator may delegate its votes
+        assert!(!is_valid_vote_delegation_with(
+            &[],
+            &alice,
+            Some(&bob),
+            false
+        ));
+        // self-delegations are circular
+        assert!(!is_valid_vote_delegation_with(
+            &[],
+            &alice,
+            Some(&alice),
+            true
+        ));
+        // bob -> carol -> alice -> bob is circular
+        assert!(!is_valid_vote_delegation_with(
+            &[(&bob, &carol), (&carol, &alice)],
+            &alice,
+            Some(&bob),
+            true
+        ));
+        // a delegation can always be revoked by the delegator
+        assert!(is_valid_vote_delegation_with(
+            &[(&alice, &bob)],
+            &alice,
+            None,
+            true
+        ));
+        assert!(!is_valid_vote_delegation_with(
+            &[(&alice, &bob)],
+            &alice,
+            None,
+            false
+        ));
+    }
+
+    /// Validate a tx moving a yay vote from its legacy key to the current
+    /// key layout, writing `migrated_vote` to the current key, in the code
+    /// of proposal 0 if `accepted`
+    fn is_valid_vote_key_migration_with(
+        accepted: bool,
+        migrated_vote: ProposalVote,
+    ) -> bool {
+        let (mut wl_storage, _) = test_utils::setup_default_storage();
+        let validator = test_utils::default_validator().0;
+        let delegator = established_address_2();
+        GovernanceParameters::default()
+            .init_storage(&mut wl_storage)
+            .expect("Test failed");
+        wl_storage
+            .write(&gov_storage::get_counter_key(), 1_u64)
+            .expect("Test failed");
+        let legacy_key = gov_storage::get_legacy_vote_proposal_key(
+            0,
+            delegator.clone(),
+            validator.clone(),
+        );
+        wl_storage
+            .write(&legacy_key, ProposalVote::Yay)
+            .expect("Test failed");
+        if accepted {
+            namada_governance::storage::write_pending_execution(
+                &mut wl_storage,
+                0,
+            )
+            .expect("Test failed");
+        }
+        wl_storage.commit_block().expect("Test failed");
+
+        let vote_key =
+            gov_storage::get_vote_proposal_key(0, delegator, validator);
+        wl_storage
+            .write_log
+            .write(&vote_key, migrated_vote.serialize_to_vec())
+            .expect("Test failed");
+        wl_storage
+            .write_log
+            .delete(&legacy_key)
+            .expect("Test failed");
+        let keys_changed = BTreeSet::from([vote_key, legacy_key]);
+        let verifiers = BTreeSet::new();
+
+        let tx_index = TxIndex::default();
+        let mut tx = dummy_tx(&wl_storage);
+        tx.set_data(Data::new(0_u64.serialize_to_vec()));
+        let gas_meter = VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+        );
+        let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+        let ctx = Ctx::new(
+            &ADDRESS,
+            &wl_storage.storage,
+            &wl_storage.write_log,
+            &tx,
+            &tx_index,
+            gas_meter,
+            &keys_changed,
+            &verifiers,
+            vp_wasm_cache,
+        );
+        let vp = GovernanceVp { ctx };
+        vp.validate_tx(&tx, &keys_changed, &verifiers)
+            .expect("Test failed")
+    }
+
+    /// Test that the votes stored under the legacy key layout may only be
+    /// moved unchanged to the current layout, by the code of an accepted
+    /// proposal
+    #[test]
+    fn test_vote_key_migration_validation() {
+        assert!(is_valid_vote_key_migration_with(true, ProposalVote::Yay));
+        // outside of an accepted proposal, the vote must be signed by the
+        // voter, and must update its votes index
+        assert!(!is_valid_vote_key_migration_with(false, ProposalVote::Yay));
+        // the vote can't be changed when migrated
+        assert!(!is_valid_vote_key_migration_with(true, ProposalVote::Nay));
+    }
+
+    /// Validate a yay vote of `voter` on proposal 0 of the given type,
+    /// stored under its own address. The council is made of
+    /// [`established_address_2`], which has no bonds.
+    fn is_valid_council_vote_with(
+        voter: &Address,
+        proposal_type: ProposalType,
+    ) -> bool {
+        let (mut wl_storage, _) = test_utils::setup_default_storage();
+        let member = established_address_2();
+        GovernanceParameters {
+            council: Some(Council {
+                members: BTreeSet::from([member]),
+                threshold: 1,
+                weights: Default::default(),
+            }),
+            ..Default::default()
+        }
+        .init_storage(&mut wl_storage)
+        .expect("Test failed");
+        wl_storage
+            .write(&gov_storage::get_counter_key(), 1_u64)
+            .expect("Test failed");
+        wl_storage
+            .write(&gov_storage::get_proposal_type_key(0), proposal_type)
+            .expect("Test failed");
+        wl_storage
+            .write(&gov_storage::get_voting_start_epoch_key(0), Epoch(0))
+            .expect("Test failed");
+        wl_storage
+            .write(&gov_storage::get_voting_end_epoch_key(0), Epoch(10))
+            .expect("Test failed");
+        wl_storage.commit_block().expect("Test failed");
+
+        let vote_key =
+            gov_storage::get_vote_proposal_key(0, voter.clone(), voter.clone());
+        let voter_index_key = gov_storage::get_voter_index_key(voter, 0);
+        wl_storage
+            .write_log
+            .write(&vote_key, ProposalVote::Yay.serialize_to_vec())
+            .expect("Test failed");
+        let receipt = VoteReceipt {
+            proposal_id: 0,
+            vote: ProposalVote::Yay,
+            epoch: Epoch(0),
+        };
+        wl_storage
+            .write_log
+            .write(&voter_index_key, receipt.serialize_to_vec())
+            .expect("Test failed");
+        let keys_changed = BTreeSet::from([vote_key.clone(), voter_index_key]);
+        let verifiers = BTreeSet::from([voter.clone()]);
+
+        let tx_index = TxIndex::default();
+        let tx = dummy_tx(&wl_storage);
+        let gas_meter = VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+        );
+        let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+        let ctx = Ctx::new(
+            &ADDRESS,
+            &wl_storage.storage,
+            &wl_storage.write_log,
+            &tx,
+            &tx_index,
+            gas_meter,
+            &keys_changed,
+            &verifiers,
+            vp_wasm_cache,
+        );
+        let vp = GovernanceVp { ctx };
+        vp.is_valid_vote_key(&tx, 0, &vote_key, &keys_changed, &verifiers)
+            .expect("Test failed")
+    }
+
+    /// Test that members of the council may vote on council proposals
+    /// without any stake, and only on council proposals
+    #[test]
+    fn test_council_member_vote_validation() {
+        let member = established_address_2();
+        assert!(is_valid_council_vote_with(
+            &member,
+            ProposalType::Council(None)
+        ));
+        // other proposals still require the voter to have stake
+        assert!(!is_valid_council_vote_with(
+            &member,
+            ProposalType::Default(None)
+        ));
+        // as does voting on council proposals from outside of the council
+        assert!(!is_valid_council_vote_with(
+            &established_address_3(),
+            ProposalType::Council(None)
+        ));
+    }
+}
@@ -2,11 +2,21 @@
 
 use std::collections::HashMap;
 
-use namada_governance::utils::TallyResult;
-use namada_sdk::events::{Event, EventLevel};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use borsh_ext::BorshSerializeExt;
+use namada_governance::storage::proposal::{
+    BridgeContractsUpdate, BridgePauseAction, EthHotKeyRotation, TreasurySpend,
+    TxOrVp,
+};
+use namada_governance::utils::{AppliedParameterChange, TallyResult};
+use namada_sdk::events::{Event, EventBuilder, EventLevel};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::ledger::events::EventType;
+use crate::types::ethereum_events::EthAddress;
+use crate::types::hash::Hash;
+use crate::types::token;
 
 pub(super) enum ReadType {
     Pre,
@@ -28,10 +38,22 @@ pub enum Error {
 }
 
 /// Proposal event definition
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
 pub struct ProposalEvent {
     /// Proposal event type
     pub event_type: String,
-    /// Proposal event attributes
+    /// Proposal event attributes, keyed by the names in
+    /// [`attributes`](crate::ledger::events::attributes)
     pub attributes: HashMap<String, String>,
 }
 
@@ -45,42 +67,91 @@ impl From<ProposalEvent> for Event {
     }
 }
 
-impl ProposalEvent {
-    /// Create a proposal event
-    pub fn new(
-        event_type: String,
-        tally: TallyResult,
-        id: u64,
-        has_proposal_code: bool,
-        proposal_code_exit_status: bool,
-    ) -> Self {
-        let attributes = HashMap::from([
-            ("tally_result".to_string(), tally.to_string()),
-            ("proposal_id".to_string(), id.to_string()),
-            (
-                "has_proposal_code".to_string(),
-                (!has_proposal_code as u64).to_string(),
-            ),
-            (
-                "proposal_code_exit_status".to_string(),
-                (!proposal_code_exit_status as u64).to_string(),
-            ),
-        ]);
+impl From<EventBuilder> for ProposalEvent {
+    fn from(builder: EventBuilder) -> Self {
+        let event = builder.build();
         Self {
-            event_type,
-            attributes,
+            event_type: event.event_type.to_string(),
+            attributes: event.attributes,
         }
     }
+}
 
+/// Start building the event of a concluded proposal
+fn concluded_proposal(
+    proposal_id: u64,
+    tally: TallyResult,
+    has_code: bool,
+    executed: bool,
+) -> EventBuilder {
+    Event::proposal(proposal_id)
+        .tally(tally)
+        .has_code(has_code)
+        .executed(executed)
+}
+
+impl ProposalEvent {
     /// Create a new proposal event for rejected proposal
     pub fn rejected_proposal_event(proposal_id: u64) -> Self {
-        ProposalEvent::new(
-            EventType::Proposal.to_string(),
-            TallyResult::Rejected,
-            proposal_id,
-            false,
-            false,
-        )
+        concluded_proposal(proposal_id, TallyResult::Rejected, false, false)
+            .into()
+    }
+
+    /// Create a new proposal event for a proposal that got force-rejected
+    /// after having been queued for longer than its maximum lifetime
+    pub fn expired_proposal_event(proposal_id: u64) -> Self {
+        concluded_proposal(proposal_id, TallyResult::Rejected, false, false)
+            .expired()
+            .into()
+    }
+
+    /// Create a new proposal event for a proposal that got rejected without
+    /// being tallied, as the given field of the proposal is either missing
+    /// from storage or can't be decoded
+    pub fn malformed_proposal_event(proposal_id: u64, field: &str) -> Self {
+        concluded_proposal(proposal_id, TallyResult::Rejected, false, false)
+            .malformed(field)
+            .into()
+    }
+
+    /// Create a new proposal event for a proposal that passed, but got
+    /// rejected for setting the same protocol parameter as the proposal with
+    /// id `conflicting_id`, concluded earlier in the same epoch
+    pub fn conflicting_proposal_event(
+        proposal_id: u64,
+        conflicting_id: u64,
+    ) -> Self {
+        concluded_proposal(proposal_id, TallyResult::Rejected, false, false)
+            .conflict(conflicting_id)
+            .into()
+    }
+
+    /// Create an advisory proposal event for a proposal that overrode the
+    /// protocol parameter set by the proposal with id `conflicting_id`,
+    /// concluded earlier in the same epoch
+    pub fn parameter_conflict_event(
+        proposal_id: u64,
+        conflicting_id: u64,
+    ) -> Self {
+        Event::proposal(proposal_id)
+            .warning("parameter_conflict")
+            .conflict(conflicting_id)
+            .into()
+    }
+
+    /// Create a new proposal event for a proposal whose processing failed
+    /// unexpectedly. The proposal is left undecided, with its funds locked.
+    pub fn processing_error_event(proposal_id: u64, error: &str) -> Self {
+        Event::proposal(proposal_id).processing_error(error).into()
+    }
+
+    /// Create an advisory proposal event for a proposal concluded without
+    /// any locked funds, although the governance parameters require a
+    /// deposit for it
+    pub fn unexpected_zero_funds_event(proposal_id: u64) -> Self {
+        Event::proposal(proposal_id)
+            .warning("unexpected_zero_funds")
+            .into()
     }
 
     /// Create a new proposal event for default proposal
@@ -89,45 +160,414 @@ impl ProposalEvent {
         has_code: bool,
         execution_status: bool,
     ) -> Self {
-        ProposalEvent::new(
-            EventType::Proposal.to_string(),
-            TallyResult::Passed,
+        concluded_proposal(
             proposal_id,
+            TallyResult::Passed,
             has_code,
             execution_status,
         )
+        .into()
+    }
+
+    /// Create a new proposal event for a default proposal whose code
+    /// panicked while being executed, and was rejected
+    pub fn panicked_proposal_event(proposal_id: u64) -> Self {
+        concluded_proposal(proposal_id, TallyResult::Passed, true, false)
+            .panicked()
+            .into()
     }
 
     /// Create a new proposal event for pgf stewards proposal
     pub fn pgf_steward_proposal_event(proposal_id: u64, result: bool) -> Self {
-        ProposalEvent::new(
-            EventType::Proposal.to_string(),
-            TallyResult::Passed,
-            proposal_id,
-            false,
-            result,
-        )
+        concluded_proposal(proposal_id, TallyResult::Passed, false, result)
+            .into()
     }
 
     /// Create a new proposal event for pgf payments proposal
     pub fn pgf_payments_proposal_event(proposal_id: u64, result: bool) -> Self {
-        ProposalEvent::new(
-            EventType::Proposal.to_string(),
-            TallyResult::Passed,
-            proposal_id,
-            false,
-            result,
-        )
+        concluded_proposal(proposal_id, TallyResult::Passed, false, result)
+            .into()
     }
 
     /// Create a new proposal event for eth proposal
     pub fn eth_proposal_event(proposal_id: u64, result: bool) -> Self {
-        ProposalEvent::new(
-            EventType::Proposal.to_string(),
-            TallyResult::Passed,
-            proposal_id,
-            false,
-            result,
+        concluded_proposal(proposal_id, TallyResult::Passed, false, result)
+            .into()
+    }
+
+    /// Create a new proposal event for Ethereum bridge contracts proposal,
+    /// recording both the replaced and the new contract addresses
+    pub fn eth_bridge_contracts_proposal_event(
+        proposal_id: u64,
+        old_native_erc20: EthAddress,
+        old_bridge: EthAddress,
+        update: &BridgeContractsUpdate,
+    ) -> Self {
+        let event =
+            concluded_proposal(proposal_id, TallyResult::Passed, false, true)
+                .attr("old_native_erc20", old_native_erc20)
+                .attr("new_native_erc20", &update.native_erc20)
+                .attr("old_bridge_contract", old_bridge)
+                .attr("new_bridge_contract", &update.bridge)
+                .attr("activation_epoch", update.activation_epoch);
+        match update.bridge_pool_nonce {
+            Some(nonce) => event.attr("bridge_pool_nonce", nonce).into(),
+            None => event.into(),
+        }
+    }
+
+    /// Create a new proposal event for Ethereum bridge pause proposal,
+    /// recording whether the bridge was paused or resumed
+    pub fn eth_bridge_pause_proposal_event(
+        proposal_id: u64,
+        action: BridgePauseAction,
+    ) -> Self {
+        concluded_proposal(proposal_id, TallyResult::Passed, false, true)
+            .attr("bridge_action", action)
+            .into()
+    }
+
+    /// Create a new proposal event for wasm allowlist update proposal,
+    /// recording the hash of the Borsh encoding of the resulting allowlist
+    pub fn wasm_allowlist_proposal_event(
+        proposal_id: u64,
+        kind: TxOrVp,
+        allowlist: &[String],
+    ) -> Self {
+        concluded_proposal(proposal_id, TallyResult::Passed, false, true)
+            .attr("allowlist_kind", kind)
+            .attr("allowlist_hash", Hash::sha256(allowlist.serialize_to_vec()))
+            .into()
+    }
+
+    /// Create a new proposal event for Ethereum hot key rotation proposal,
+    /// recording the rotation, whether or not it was applied
+    pub fn eth_hot_key_rotation_proposal_event(
+        proposal_id: u64,
+        rotation: &EthHotKeyRotation,
+        result: bool,
+    ) -> Self {
+        concluded_proposal(proposal_id, TallyResult::Passed, false, result)
+            .attr("validator", &rotation.validator)
+            .attr("eth_hot_key", &rotation.eth_hot_key)
+            .attr("activation_epoch", rotation.activation_epoch)
+            .into()
+    }
+
+    /// Create a new proposal event for treasury spend proposal, recording
+    /// the spend and the balance of the treasury before it. A spend that
+    /// wasn't applied is flagged with a warning, as the treasury couldn't
+    /// cover it.
+    pub fn treasury_spend_proposal_event(
+        proposal_id: u64,
+        spend: &TreasurySpend,
+        treasury_balance: token::Amount,
+        result: bool,
+    ) -> Self {
+        let event =
+            concluded_proposal(proposal_id, TallyResult::Passed, false, result)
+                .attr("recipient", &spend.recipient)
+                .attr("token", &spend.token)
+                .attr("amount", spend.amount)
+                .attr("treasury_balance", treasury_balance);
+        if result {
+            event.into()
+        } else {
+            event.warning("insufficient_treasury_funds").into()
+        }
+    }
+
+    /// Create a new proposal event for parameter change proposal, recording
+    /// the value of each parameter before and after the change
+    pub fn parameter_change_proposal_event(
+        proposal_id: u64,
+        changes: &[AppliedParameterChange],
+    ) -> Self {
+        concluded_proposal(proposal_id, TallyResult::Passed, false, true)
+            .parameter_changes(changes)
+            .into()
+    }
+}
+
+/// Create an advisory event, emitted when moving the funds of proposals to
+/// the treasury raised its balance to the given high-water mark or above
+pub fn treasury_high_water_mark_event(
+    treasury_balance: token::Amount,
+    high_water_mark: token::Amount,
+) -> Event {
+    EventBuilder::new(EventType::Proposal, EventLevel::Block)
+        .warning("treasury_high_water_mark")
+        .attr("treasury_balance", treasury_balance.to_string_native())
+        .attr("high_water_mark", high_water_mark.to_string_native())
+        .build()
+}
+
+/// Create an event for the funds moved to the treasury in excess of its
+/// maximum balance, which were burned instead
+pub fn treasury_overflow_burned_event(
+    burned: token::Amount,
+    treasury_balance: token::Amount,
+    max_treasury_balance: token::Amount,
+) -> Event {
+    EventBuilder::new(EventType::Proposal, EventLevel::Block)
+        .attr("treasury_overflow_burned", burned.to_string_native())
+        .attr("treasury_balance", treasury_balance.to_string_native())
+        .attr(
+            "max_treasury_balance",
+            max_treasury_balance.to_string_native(),
         )
+        .build()
+}
+
+/// Create an event for the inflation minted to the treasury at an epoch
+pub fn treasury_inflation_event(
+    minted: token::Amount,
+    total_supply: token::Amount,
+) -> Event {
+    EventBuilder::new(EventType::Proposal, EventLevel::Block)
+        .attr("treasury_inflation", minted.to_string_native())
+        .attr("total_supply", total_supply.to_string_native())
+        .build()
+}
+
+#[cfg(test)]
+mod test {
+    use namada_governance::utils::{ProposalOutput, TallyBasisPoints};
+    use namada_sdk::error::EventError;
+    use namada_sdk::events::ProposalOutcome;
+
+    use super::*;
+    use crate::ledger::events::attributes;
+    use crate::tendermint::abci::Event as TmEvent;
+    use crate::tendermint_proto::v0_37::abci::Event as ProtoEvent;
+    use crate::types::address::testing::established_address_1;
+    use crate::types::key::testing::keypair_1;
+    use crate::types::key::RefTo;
+
+    /// Test that the proposal id and tally result of a proposal event are
+    /// flagged as indexable across a round trip through Tendermint types
+    #[test]
+    fn test_proposal_event_index_flags() {
+        let mut event: Event =
+            ProposalEvent::rejected_proposal_event(42).into();
+        event[attributes::EPOCH] = "7".to_string();
+
+        let proto = ProtoEvent::from(event);
+        let tm_event = TmEvent::try_from(proto).expect("Test failed");
+        assert_eq!(tm_event.kind, EventType::Proposal.to_string());
+        let flags: HashMap<_, _> = ProtoEvent::from(tm_event)
+            .attributes
+            .into_iter()
+            .map(|attr| (attr.key, (attr.value, attr.index)))
+            .collect();
+
+        assert_eq!(flags[attributes::PROPOSAL_ID], ("42".to_string(), true));
+        assert_eq!(
+            flags[attributes::TALLY_RESULT],
+            (TallyResult::Rejected.to_string(), true)
+        );
+        assert_eq!(flags[attributes::EPOCH], ("7".to_string(), true));
+        assert!(!flags[attributes::HAS_PROPOSAL_CODE].1);
+        assert!(!flags[attributes::PROPOSAL_CODE_EXIT_STATUS].1);
+    }
+
+    /// Test that the events of every kind of concluded proposal are parsed
+    /// back to the outcome they were emitted with
+    #[test]
+    fn test_proposal_outcome_round_trip() {
+        let outcome = |tally, has_code, executed| ProposalOutcome {
+            id: 3,
+            tally,
+            has_code,
+            executed,
+            expired: false,
+            malformed: None,
+            conflicts_with: None,
+            basis_points: None,
+            output: None,
+            output_truncated: false,
+            parameter_changes: vec![],
+            funds: None,
+        };
+        let passed = |executed| outcome(TallyResult::Passed, false, executed);
+        let rejected = outcome(TallyResult::Rejected, false, false);
+        let update = BridgeContractsUpdate {
+            native_erc20: EthAddress([1; 20]),
+            bridge: EthAddress([2; 20]),
+            activation_epoch: 5.into(),
+            bridge_pool_nonce: Some(7.into()),
+        };
+        let rotation = EthHotKeyRotation {
+            validator: established_address_1(),
+            eth_hot_key: keypair_1().ref_to(),
+            activation_epoch: 5.into(),
+        };
+        let parameter_changes = vec![
+            AppliedParameterChange {
+                key: namada_parameters::storage::get_max_block_gas_key(),
+                old_value: Some(vec![1, 2]),
+                new_value: vec![3, 4],
+            },
+            AppliedParameterChange {
+                key: namada_parameters::storage::get_gas_cost_key(),
+                old_value: None,
+                new_value: vec![],
+            },
+        ];
+
+        let cases = [
+            (ProposalEvent::rejected_proposal_event(3), rejected.clone()),
+            (
+                ProposalEvent::expired_proposal_event(3),
+                ProposalOutcome {
+                    expired: true,
+                    ..rejected.clone()
+                },
+            ),
+            (
+                ProposalEvent::malformed_proposal_event(3, "voting_end_epoch"),
+                ProposalOutcome {
+                    malformed: Some("voting_end_epoch".to_string()),
+                    ..rejected
+                },
+            ),
+            (
+                ProposalEvent::default_proposal_event(3, true, true),
+                outcome(TallyResult::Passed, true, true),
+            ),
+            (
+                ProposalEvent::default_proposal_event(3, true, false),
+                outcome(TallyResult::Passed, true, false),
+            ),
+            (
+                ProposalEvent::default_proposal_event(3, false, false),
+                passed(false),
+            ),
+            (
+                ProposalEvent::pgf_steward_proposal_event(3, true),
+                passed(true),
+            ),
+            (
+                ProposalEvent::pgf_payments_proposal_event(3, false),
+                passed(false),
+            ),
+            (ProposalEvent::eth_proposal_event(3, true), passed(true)),
+            (
+                ProposalEvent::eth_bridge_contracts_proposal_event(
+                    3,
+                    EthAddress([3; 20]),
+                    EthAddress([4; 20]),
+                    &update,
+                ),
+                passed(true),
+            ),
+            (
+                ProposalEvent::eth_bridge_pause_proposal_event(
+                    3,
+                    BridgePauseAction::Pause,
+                ),
+                passed(true),
+            ),
+            (
+                ProposalEvent::wasm_allowlist_proposal_event(
+                    3,
+                    TxOrVp::Tx,
+                    &["abcd".to_string()],
+                ),
+                passed(true),
+            ),
+            (
+                ProposalEvent::eth_hot_key_rotation_proposal_event(
+                    3, &rotation, false,
+                ),
+                passed(false),
+            ),
+            (
+                ProposalEvent::parameter_change_proposal_event(
+                    3,
+                    &parameter_changes,
+                ),
+                ProposalOutcome {
+                    parameter_changes: parameter_changes.clone(),
+                    ..passed(true)
+                },
+            ),
+            (
+                ProposalEvent::conflicting_proposal_event(3, 2),
+                ProposalOutcome {
+                    conflicts_with: Some(2),
+                    ..outcome(TallyResult::Rejected, false, false)
+                },
+            ),
+        ];
+        for (event, expected) in cases {
+            let event: Event = event.into();
+            assert_eq!(
+                ProposalOutcome::try_from(event).expect("Test failed"),
+                expected
+            );
+        }
+
+        // the shares of the votes cast are parsed along with the outcome
+        let basis_points = TallyBasisPoints::new(
+            token::Amount::native_whole(2),
+            token::Amount::native_whole(1),
+            token::Amount::zero(),
+        );
+        let mut event: Event = ProposalEvent::rejected_proposal_event(3).into();
+        event.attributes.extend(
+            Event::proposal(3)
+                .basis_points(basis_points)
+                .build()
+                .attributes,
+        );
+        assert_eq!(
+            ProposalOutcome::try_from(event).expect("Test failed"),
+            ProposalOutcome {
+                basis_points: Some(basis_points),
+                ..outcome(TallyResult::Rejected, false, false)
+            }
+        );
+
+        // and so is the output written by the code of the proposal
+        let output =
+            ProposalOutput::from([("minted".to_string(), "100".to_string())]);
+        let mut event: Event =
+            ProposalEvent::default_proposal_event(3, true, true).into();
+        event.attributes.extend(
+            Event::proposal(3).output(output.clone()).build().attributes,
+        );
+        assert_eq!(
+            ProposalOutcome::try_from(event).expect("Test failed"),
+            ProposalOutcome {
+                output: Some(output),
+                ..outcome(TallyResult::Passed, true, true)
+            }
+        );
+
+        // events that don't conclude a proposal aren't outcomes
+        for event in [
+            ProposalEvent::processing_error_event(3, "error").into(),
+            ProposalEvent::unexpected_zero_funds_event(3).into(),
+            ProposalEvent::parameter_conflict_event(3, 2).into(),
+            treasury_high_water_mark_event(
+                token::Amount::native_whole(2),
+                token::Amount::native_whole(1),
+            ),
+            treasury_overflow_burned_event(
+                token::Amount::native_whole(1),
+                token::Amount::native_whole(2),
+                token::Amount::native_whole(2),
+            ),
+        ] {
+            assert!(ProposalOutcome::try_from(event).is_err());
+        }
+        let mut event: Event =
+            ProposalEvent::eth_proposal_event(3, true).into();
+        event[attributes::PROPOSAL_CODE_EXIT_STATUS] = "true".to_string();
+        assert!(matches!(
+            ProposalOutcome::try_from(event),
+            Err(EventError::InvalidValue(..))
+        ));
     }
 }
@@ -15,6 +15,10 @@ pub mod vp_host_fns;
 #[cfg(feature = "wasm-runtime")]
 pub use dry_run_tx::dry_run_tx;
 pub use namada_core::ledger::replay_protection;
+#[cfg(feature = "wasm-runtime")]
+pub use write_surface::{
+    proposal_write_surface, write_surface_proposal_id, WriteSurfaceCache,
+};
 pub use {
     namada_gas as gas, namada_parameters as parameters,
     namada_tx_env as tx_env, namada_vp_env as vp_env,
@@ -131,13 +135,215 @@ mod dry_run_tx {
     }
 }
 
+#[cfg(feature = "wasm-runtime")]
+mod write_surface {
+    use std::collections::{BTreeSet, HashMap};
+    use std::sync::{Arc, Mutex};
+
+    use borsh_ext::BorshSerializeExt;
+    use namada_gas::TxGasMeter;
+    use namada_governance::pgf::storage::keys as pgf_storage;
+    use namada_governance::storage::facade::ProposalStorageReader;
+    use namada_governance::storage::{
+        code as gov_code, get_max_proposal_code_size, keys as gov_storage,
+    };
+    use namada_governance::utils::{ProposalWriteSurface, WrittenKey};
+    use namada_sdk::queries::{EncodedResponseQuery, RequestCtx};
+    use namada_state::{DBIter, ResultExt, StorageHasher, TempWlStorage, DB};
+    use namada_tx::data::{DecryptedTx, TxType};
+    use namada_tx::{Code, Data, Tx};
+
+    use super::protocol;
+    use crate::ledger::protocol::ShellParams;
+    use crate::types::hash::Hash;
+    use crate::types::storage::{BlockHeight, Key, TxIndex};
+    use crate::vm::wasm::{TxCache, VpCache};
+    use crate::vm::WasmCacheAccess;
+
+    /// The write surfaces of the proposals dry-run so far, by proposal id
+    /// and code hash. Whether the execution of the code would be allowed
+    /// depends on the state, hence a surface is only served at the height
+    /// it was dry-run at, and the code is dry-run again at later heights.
+    #[derive(Debug, Default, Clone)]
+    pub struct WriteSurfaceCache(
+        Arc<Mutex<HashMap<(u64, Hash), ProposalWriteSurface>>>,
+    );
+
+    impl WriteSurfaceCache {
+        fn get(
+            &self,
+            id: u64,
+            code_hash: &Hash,
+            height: BlockHeight,
+        ) -> Option<ProposalWriteSurface> {
+            self.0
+                .lock()
+                .expect("The write surface cache must not be poisoned")
+                .get(&(id, *code_hash))
+                .filter(|surface| surface.height == height)
+                .cloned()
+        }
+
+        fn insert(&self, id: u64, surface: ProposalWriteSurface) {
+            self.0
+                .lock()
+                .expect("The write surface cache must not be poisoned")
+                .insert((id, surface.code_hash), surface);
+        }
+    }
+
+    /// Get the id of the proposal whose write surface is queried at the
+    /// given path, if any
+    pub fn write_surface_proposal_id(path: &str) -> Option<u64> {
+        path.strip_prefix("/vp/governance/proposal/")?
+            .strip_suffix("/write_surface")?
+            .parse()
+            .ok()
+    }
+
+    /// Dry run the code of the proposal with the given id against a scratch
+    /// write log, and get the storage keys it writes. Keys falling under the
+    /// governance protected prefixes, or outside of the parameters of the
+    /// protocol and of its native modules, are flagged. Returns `None` if
+    /// the proposal doesn't carry any code.
+    pub fn proposal_write_surface<D, H, CA>(
+        mut ctx: RequestCtx<'_, D, H, VpCache<CA>, TxCache<CA>>,
+        cache: &WriteSurfaceCache,
+        id: u64,
+    ) -> namada_state::StorageResult<EncodedResponseQuery>
+    where
+        D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+        H: 'static + StorageHasher + Sync,
+        CA: 'static + WasmCacheAccess + Sync,
+    {
+        let proposal_store = ProposalStorageReader::new(ctx.wl_storage);
+        let surface = match proposal_store.code_hash(id)? {
+            Some(code_hash) => match cache.get(
+                id,
+                &code_hash,
+                ctx.wl_storage.storage.get_last_block_height(),
+            ) {
+                Some(surface) => Some(surface),
+                None => {
+                    let code = proposal_store.code(id)?.ok_or(
+                        namada_state::StorageError::new_const(
+                            "Missing proposal code",
+                        ),
+                    )?;
+                    let max_code_size =
+                        get_max_proposal_code_size(ctx.wl_storage)?;
+                    let code = gov_code::decode_code(code, max_code_size)
+                        .into_storage_result()?;
+                    let surface =
+                        dry_run_proposal_code(&mut ctx, id, code, code_hash)?;
+                    cache.insert(id, surface.clone());
+                    Some(surface)
+                }
+            },
+            None => None,
+        };
+        Ok(EncodedResponseQuery {
+            data: surface.serialize_to_vec(),
+            proof: None,
+            info: Default::default(),
+        })
+    }
+
+    /// Apply the code of a proposal as it would be at execution, with its
+    /// execution marker, without committing anything
+    fn dry_run_proposal_code<D, H, CA>(
+        ctx: &mut RequestCtx<'_, D, H, VpCache<CA>, TxCache<CA>>,
+        id: u64,
+        code: Vec<u8>,
+        code_hash: Hash,
+    ) -> namada_state::StorageResult<ProposalWriteSurface>
+    where
+        D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+        H: 'static + StorageHasher + Sync,
+        CA: 'static + WasmCacheAccess + Sync,
+    {
+        let mut temp_wl_storage = TempWlStorage::new(&ctx.wl_storage.storage);
+        namada_governance::storage::write_pending_execution(
+            &mut temp_wl_storage,
+            id,
+        )?;
+
+        let mut tx = Tx::from_type(TxType::Decrypted(DecryptedTx::Decrypted));
+        tx.header.chain_id = ctx.wl_storage.storage.chain_id.clone();
+        tx.set_data(Data::new(id.serialize_to_vec()));
+        tx.set_code(Code::new(code, None));
+        // A query may not spend more gas than a block
+        let max_block_gas =
+            namada_parameters::get_max_block_gas(ctx.wl_storage)?;
+        let mut tx_gas_meter =
+            TxGasMeter::new_from_sub_limit(max_block_gas.into());
+        let tx_result = protocol::apply_wasm_tx(
+            tx,
+            &TxIndex::default(),
+            ShellParams::new(
+                &mut tx_gas_meter,
+                &mut temp_wl_storage,
+                &mut ctx.vp_wasm_cache,
+                &mut ctx.tx_wasm_cache,
+            ),
+        );
+        let height = ctx.wl_storage.storage.get_last_block_height();
+        Ok(match tx_result {
+            Ok(tx_result) => ProposalWriteSurface {
+                code_hash,
+                height,
+                keys: written_keys(id, &tx_result.changed_keys),
+                execution_allowed: tx_result.is_accepted(),
+                error: None,
+            },
+            Err(err) => ProposalWriteSurface {
+                code_hash,
+                height,
+                keys: vec![],
+                execution_allowed: false,
+                error: Some(err.to_string()),
+            },
+        })
+    }
+
+    /// Flag the keys written by the code of the proposal with the given id
+    pub(super) fn written_keys(
+        id: u64,
+        changed_keys: &BTreeSet<Key>,
+    ) -> Vec<WrittenKey> {
+        changed_keys
+            .iter()
+            // The execution marker isn't written by the code
+            .filter(|key| **key != gov_storage::get_proposal_execution_key(id))
+            .map(|key| WrittenKey {
+                key: key.clone(),
+                protected: gov_storage::is_protected_key(key),
+                outside_parameters: !is_parameter_key(key),
+            })
+            .collect()
+    }
+
+    /// Check if the key is a parameter of the protocol or of one of its
+    /// native modules
+    fn is_parameter_key(key: &Key) -> bool {
+        namada_parameters::storage::is_parameter_key(key)
+            || gov_storage::is_parameter_key(key)
+            || namada_proof_of_stake::storage_key::is_params_key(key)
+            || pgf_storage::is_pgf_inflation_rate_key(key)
+            || pgf_storage::is_steward_inflation_rate_key(key)
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use std::collections::BTreeSet;
+
     use borsh::BorshDeserialize;
     use borsh_ext::BorshSerializeExt;
     use namada_core::types::address;
     use namada_core::types::hash::Hash;
     use namada_core::types::storage::{BlockHeight, Key};
+    use namada_governance::storage::keys as gov_storage;
     use namada_sdk::queries::{
         EncodedResponseQuery, RequestCtx, RequestQuery, Router, RPC,
     };
@@ -150,6 +356,7 @@ mod test {
     use namada_tx::{Code, Data, Tx};
     use tempfile::TempDir;
 
+    use super::WriteSurfaceCache;
     use crate::ledger::events::log::EventLog;
     use crate::ledger::queries::Client;
     use crate::token;
@@ -175,6 +382,8 @@ mod test {
         pub vp_cache_dir: TempDir,
         /// tx wasm compilation cache directory
         pub tx_cache_dir: TempDir,
+        /// cache of the write surfaces of proposals
+        pub write_surface_cache: WriteSurfaceCache,
     }
 
     impl<RPC> TestClient<RPC>
@@ -212,6 +421,7 @@ mod test {
                 tx_wasm_cache: tx_wasm_cache.read_only(),
                 vp_cache_dir,
                 tx_cache_dir,
+                write_surface_cache: WriteSurfaceCache::default(),
             }
         }
     }
@@ -247,11 +457,20 @@ mod test {
                 vp_wasm_cache: self.vp_wasm_cache.clone(),
                 tx_wasm_cache: self.tx_wasm_cache.clone(),
                 storage_read_past_height_limit: None,
+                enable_valset_preview: false,
             };
             // TODO: this is a hack to propagate errors to the caller, we should
             // really permit error types other than [`std::io::Error`]
             if request.path == "/shell/dry_run_tx" {
                 super::dry_run_tx(ctx, &request)
+            } else if let Some(id) =
+                super::write_surface_proposal_id(&request.path)
+            {
+                super::proposal_write_surface(
+                    ctx,
+                    &self.write_surface_cache,
+                    id,
+                )
             } else {
                 self.rpc.handle(ctx, &request)
             }
@@ -369,4 +588,190 @@ mod test {
 
         Ok(())
     }
+
+    /// Write the given code as the code of the proposal with the given id
+    fn write_proposal_code<RPC: Router>(
+        client: &mut TestClient<RPC>,
+        id: u64,
+        code: &[u8],
+        code_hash: Hash,
+    ) {
+        StorageWrite::write_bytes(
+            &mut client.wl_storage,
+            &gov_storage::get_proposal_code_key(id),
+            code,
+        )
+        .unwrap();
+        StorageWrite::write(
+            &mut client.wl_storage,
+            &gov_storage::get_proposal_code_hash_key(id),
+            code_hash,
+        )
+        .unwrap();
+        client.wl_storage.commit_tx();
+        client.wl_storage.commit_block().unwrap();
+    }
+
+    /// Test that the write surface of a proposal reports the keys its code
+    /// writes, flagged, and that it's only computed once per code
+    #[tokio::test]
+    async fn test_proposal_write_surface() {
+        let mut client = TestClient::new(RPC);
+        let proposal_code = TestWasms::TxProposalCode.read_bytes();
+        let masp_rewards_code = TestWasms::TxProposalMaspRewards.read_bytes();
+        write_proposal_code(
+            &mut client,
+            0,
+            &proposal_code,
+            Hash::sha256(&proposal_code),
+        );
+        write_proposal_code(
+            &mut client,
+            1,
+            &masp_rewards_code,
+            Hash::sha256(&masp_rewards_code),
+        );
+
+        // Proposals without code have no write surface
+        let surface = RPC
+            .vp()
+            .gov()
+            .proposal_write_surface(&client, &2)
+            .await
+            .unwrap();
+        assert!(surface.is_none());
+
+        // Parameter changes are neither protected nor outside of the
+        // parameters
+        let surface = RPC
+            .vp()
+            .gov()
+            .proposal_write_surface(&client, &0)
+            .await
+            .unwrap()
+            .expect("Test failed");
+        assert_eq!(surface.code_hash, Hash::sha256(&proposal_code));
+        assert!(surface.error.is_none());
+        let keys: Vec<_> =
+            surface.keys.iter().map(|written| &written.key).collect();
+        let mut expected = vec![
+            gov_storage::get_min_proposal_grace_epoch_key(),
+            namada_parameters::storage::get_tx_allowlist_storage_key(),
+        ];
+        expected.sort();
+        assert_eq!(keys, expected.iter().collect::<Vec<_>>());
+        assert!(
+            surface.keys.iter().all(
+                |written| !written.protected && !written.outside_parameters
+            )
+        );
+
+        // Token keys fall outside of the parameters
+        let surface = RPC
+            .vp()
+            .gov()
+            .proposal_write_surface(&client, &1)
+            .await
+            .unwrap()
+            .expect("Test failed");
+        assert_eq!(surface.keys.len(), 1);
+        assert!(surface.keys[0].outside_parameters);
+        assert!(!surface.keys[0].protected);
+        assert_eq!(surface.protected_keys().count(), 0);
+
+        // The surface is cached by code hash, so the code isn't dry-run
+        // again under the same hash
+        write_proposal_code(
+            &mut client,
+            0,
+            &masp_rewards_code,
+            Hash::sha256(&proposal_code),
+        );
+        let cached = RPC
+            .vp()
+            .gov()
+            .proposal_write_surface(&client, &0)
+            .await
+            .unwrap()
+            .expect("Test failed");
+        assert_eq!(cached.keys.len(), 2);
+        write_proposal_code(
+            &mut client,
+            0,
+            &masp_rewards_code,
+            Hash::sha256(&masp_rewards_code),
+        );
+        let surface = RPC
+            .vp()
+            .gov()
+            .proposal_write_surface(&client, &0)
+            .await
+            .unwrap()
+            .expect("Test failed");
+        assert_eq!(surface.keys.len(), 1);
+
+        // The code is dry-run again at the next height, as whether its
+        // execution would be allowed depends on the state
+        write_proposal_code(
+            &mut client,
+            0,
+            &proposal_code,
+            Hash::sha256(&masp_rewards_code),
+        );
+        let cached = RPC
+            .vp()
+            .gov()
+            .proposal_write_surface(&client, &0)
+            .await
+            .unwrap()
+            .expect("Test failed");
+        assert_eq!(cached.keys.len(), 1);
+        client.wl_storage.storage.block.height =
+            client.wl_storage.storage.block.height.next_height();
+        client.wl_storage.commit_block().unwrap();
+        let surface = RPC
+            .vp()
+            .gov()
+            .proposal_write_surface(&client, &0)
+            .await
+            .unwrap()
+            .expect("Test failed");
+        assert_eq!(surface.keys.len(), 2);
+        assert_eq!(surface.height, cached.height.next_height());
+    }
+
+    /// Test that the writes of the code of a proposal to protected keys
+    /// are reported, and that its execution marker is left out
+    #[test]
+    fn test_written_keys() {
+        let changed_keys = BTreeSet::from([
+            gov_storage::get_counter_key(),
+            gov_storage::get_proposal_execution_key(3),
+            gov_storage::get_min_proposal_grace_epoch_key(),
+        ]);
+        let written = super::write_surface::written_keys(3, &changed_keys);
+        assert_eq!(written.len(), 2);
+        let counter = written
+            .iter()
+            .find(|written| written.key == gov_storage::get_counter_key())
+            .expect("Test failed");
+        assert!(counter.protected);
+        let grace_epoch = written
+            .iter()
+            .find(|written| {
+                written.key == gov_storage::get_min_proposal_grace_epoch_key()
+            })
+            .expect("Test failed");
+        assert!(!grace_epoch.protected);
+        assert!(!grace_epoch.outside_parameters);
+    }
+
+    /// Test that the paths of the write surface queries are recognized
+    #[test]
+    fn test_write_surface_proposal_id() {
+        let path = RPC.vp().gov().proposal_write_surface_path(&7);
+        assert_eq!(super::write_surface_proposal_id(&path), Some(7));
+        let path = RPC.vp().gov().proposal_id_path(&7);
+        assert_eq!(super::write_surface_proposal_id(&path), None);
+    }
 }
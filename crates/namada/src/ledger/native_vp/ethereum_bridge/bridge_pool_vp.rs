@@ -20,10 +20,13 @@ use eyre::eyre;
 use namada_core::hints;
 use namada_core::types::eth_bridge_pool::erc20_token_address;
 use namada_ethereum_bridge::storage::bridge_pool::{
-    get_pending_key, is_bridge_pool_key, BRIDGE_POOL_ADDRESS,
+    get_pending_key, get_pending_per_sender_key, is_bridge_pool_key,
+    BRIDGE_POOL_ADDRESS,
 };
 use namada_ethereum_bridge::storage::parameters::read_native_erc20_address;
-use namada_ethereum_bridge::storage::whitelist;
+use namada_ethereum_bridge::storage::{
+    max_pending_transfers_per_sender_key, whitelist,
+};
 use namada_ethereum_bridge::ADDRESS as BRIDGE_ADDRESS;
 use namada_state::{DBIter, StorageHasher, DB};
 use namada_tx::Tx;
@@ -121,6 +124,44 @@ where
         })
     }
 
+    /// Check that the transfer was counted against the pending
+    /// transfers of its sender, and that these do not exceed the
+    /// maximum set by governance.
+    fn check_pending_per_sender(
+        &self,
+        transfer: &PendingTransfer,
+    ) -> Result<bool, Error> {
+        let sender = &transfer.transfer.sender;
+        let per_sender_key = get_pending_per_sender_key(sender);
+        let pre: u64 = (&self.ctx)
+            .read_pre_value(&per_sender_key)?
+            .unwrap_or_default();
+        let post: u64 = (&self.ctx)
+            .read_post_value(&per_sender_key)?
+            .unwrap_or_default();
+        if pre.checked_add(1) != Some(post) {
+            tracing::debug!(
+                %sender,
+                pre,
+                post,
+                "The transfer was not counted against the pending transfers \
+                 of its sender."
+            );
+            return Ok(false);
+        }
+        let max_pending: Option<u64> = (&self.ctx)
+            .read_pre_value(&max_pending_transfers_per_sender_key())?;
+        match max_pending {
+            Some(max_pending) if post > max_pending => Err(eyre!(
+                "Rejecting transaction as the sender {sender} already has \
+                 {pre} pending transfers in the Ethereum bridge pool, the \
+                 maximum allowed being {max_pending}"
+            )
+            .into()),
+            _ => Ok(true),
+        }
+    }
+
     /// Check that the correct amount of tokens were sent
     /// from the correct account into escrow.
     #[inline]
@@ -564,14 +605,17 @@ where
             }
             _ => {}
         }
+        let per_sender_key =
+            get_pending_per_sender_key(&transfer.transfer.sender);
         for key in keys_changed.iter().filter(|k| is_bridge_pool_key(k)) {
-            if *key != pending_key {
+            if *key != pending_key && *key != per_sender_key {
                 tracing::debug!(
                     "Rejecting transaction as it is attempting to change an \
                      incorrect key in the Ethereum bridge pool: {}.\n \
-                     Expected key: {}",
+                     Expected keys: {}, {}",
                     key,
-                    pending_key
+                    pending_key,
+                    per_sender_key
                 );
                 return Ok(false);
             }
@@ -590,6 +634,10 @@ where
             );
             return Ok(false);
         }
+        // check that the sender stays within its pending transfers cap
+        if !self.check_pending_per_sender(&transfer)? {
+            return Ok(false);
+        }
         // The deltas in the escrowed amounts we must check.
         let wnam_address = read_native_erc20_address(&self.ctx.pre())?;
         let escrow_checks =
@@ -646,11 +694,12 @@ mod test_bridge_pool_vp {
     use namada_core::types::address;
     use namada_ethereum_bridge::storage::bridge_pool::get_signed_root_key;
     use namada_ethereum_bridge::storage::parameters::{
-        Contracts, EthereumBridgeParams, UpgradeableContract,
+        set_max_pending_transfers_per_sender, Contracts, EthereumBridgeParams,
+        UpgradeableContract,
     };
     use namada_ethereum_bridge::storage::wrapped_erc20s;
     use namada_gas::TxGasMeter;
-    use namada_state::StorageWrite;
+    use namada_state::{StorageRead, StorageWrite};
     use namada_tx::data::TxType;
 
     use super::*;
@@ -931,6 +980,21 @@ mod test_bridge_pool_vp {
         wl_storage
     }
 
+    /// Count a transfer added to the pool against the pending transfers of
+    /// its sender, as the tx adding it does, and return the changed key
+    fn count_pending_transfer(
+        wl_storage: &mut WlStorage<MockDB, Sha256Hasher>,
+        transfer: &PendingTransfer,
+    ) -> Key {
+        let key = get_pending_per_sender_key(&transfer.transfer.sender);
+        let pending: u64 = wl_storage
+            .read(&key)
+            .expect("Test failed")
+            .unwrap_or_default();
+        wl_storage.write(&key, pending + 1).expect("Test failed");
+        key
+    }
+
     /// Setup a ctx for running native vps
     fn setup_ctx<'a>(
         tx: &'a Tx,
@@ -994,6 +1058,7 @@ mod test_bridge_pool_vp {
         // add transfer to pool
         let mut keys_changed =
             insert_transfer(&mut transfer, &mut wl_storage.write_log);
+        keys_changed.insert(count_pending_transfer(&mut wl_storage, &transfer));
 
         // change Bertha's balances
         let mut new_keys_changed = update_balances(
@@ -1334,7 +1399,10 @@ mod test_bridge_pool_vp {
                 .write_log
                 .write(&get_pending_key(&transfer), transfer.serialize_to_vec())
                 .unwrap();
-            BTreeSet::from([get_pending_key(&transfer)])
+            BTreeSet::from([
+                get_pending_key(&transfer),
+                count_pending_transfer(&mut wl_storage, &transfer),
+            ])
         };
 
         // update Bertha's balances
@@ -1416,7 +1484,10 @@ mod test_bridge_pool_vp {
                 .write_log
                 .write(&get_pending_key(&transfer), transfer.serialize_to_vec())
                 .unwrap();
-            BTreeSet::from([get_pending_key(&transfer)])
+            BTreeSet::from([
+                get_pending_key(&transfer),
+                count_pending_transfer(&mut wl_storage, &transfer),
+            ])
         };
         // We escrow 0 tokens
         keys_changed.insert(balance_key(
@@ -1481,7 +1552,10 @@ mod test_bridge_pool_vp {
                 .write_log
                 .write(&get_pending_key(&transfer), transfer.serialize_to_vec())
                 .unwrap();
-            BTreeSet::from([get_pending_key(&transfer)])
+            BTreeSet::from([
+                get_pending_key(&transfer),
+                count_pending_transfer(&mut wl_storage, &transfer),
+            ])
         };
         // We escrow 100 Nam into the bridge pool VP
         // and 100 Nam in the Eth bridge VP
@@ -1566,7 +1640,10 @@ mod test_bridge_pool_vp {
                 .write_log
                 .write(&get_pending_key(&transfer), transfer.serialize_to_vec())
                 .unwrap();
-            BTreeSet::from([get_pending_key(&transfer)])
+            BTreeSet::from([
+                get_pending_key(&transfer),
+                count_pending_transfer(&mut wl_storage, &transfer),
+            ])
         };
         // We escrow 100 Nam into the bridge pool VP
         // and 100 Nam in the Eth bridge VP
@@ -1656,7 +1733,10 @@ mod test_bridge_pool_vp {
                 .write_log
                 .write(&get_pending_key(&transfer), transfer.serialize_to_vec())
                 .unwrap();
-            BTreeSet::from([get_pending_key(&transfer)])
+            BTreeSet::from([
+                get_pending_key(&transfer),
+                count_pending_transfer(&mut wl_storage, &transfer),
+            ])
         };
         // We escrow 100 Nam into the bridge pool VP
         // and 100 Nam in the Eth bridge VP
@@ -1736,7 +1816,10 @@ mod test_bridge_pool_vp {
                 .write_log
                 .write(&get_pending_key(&transfer), transfer.serialize_to_vec())
                 .unwrap();
-            BTreeSet::from([get_pending_key(&transfer)])
+            BTreeSet::from([
+                get_pending_key(&transfer),
+                count_pending_transfer(&mut wl_storage, &transfer),
+            ])
         };
 
         // update Daewon's balances
@@ -1850,6 +1933,147 @@ mod test_bridge_pool_vp {
         );
     }
 
+    /// Run the Bridge pool VP on Bertha adding a transfer to the pool,
+    /// while she already has `pending` transfers in it, out of a maximum
+    /// of [`MAX_PENDING`] set by governance.
+    fn validate_with_pending_transfers(pending: u64) -> Result<bool, Error> {
+        const MAX_PENDING: u64 = 3;
+
+        // setup
+        let mut wl_storage = setup_storage();
+        set_max_pending_transfers_per_sender(&mut wl_storage, MAX_PENDING)
+            .expect("Test failed");
+        if pending > 0 {
+            wl_storage
+                .write(&get_pending_per_sender_key(&bertha_address()), pending)
+                .expect("Test failed");
+        }
+        wl_storage.write_log.commit_tx();
+        let tx = Tx::from_type(TxType::Raw);
+
+        // the transfer to be added to the pool
+        let transfer = PendingTransfer {
+            transfer: TransferToEthereum {
+                kind: TransferToEthereumKind::Erc20,
+                asset: ASSET,
+                sender: bertha_address(),
+                recipient: EthAddress([1; 20]),
+                amount: TOKENS.into(),
+            },
+            gas_fee: GasFee {
+                token: nam(),
+                amount: GAS_FEE.into(),
+                payer: bertha_address(),
+            },
+        };
+
+        // add transfer to pool
+        let mut keys_changed = {
+            wl_storage
+                .write_log
+                .write(&get_pending_key(&transfer), transfer.serialize_to_vec())
+                .unwrap();
+            BTreeSet::from([
+                get_pending_key(&transfer),
+                count_pending_transfer(&mut wl_storage, &transfer),
+            ])
+        };
+
+        // update Bertha's balances
+        let mut new_keys_changed = update_balances(
+            &mut wl_storage.write_log,
+            Balance {
+                asset: ASSET,
+                kind: TransferToEthereumKind::Erc20,
+                owner: bertha_address(),
+                gas: BERTHA_WEALTH.into(),
+                token: BERTHA_TOKENS.into(),
+            },
+            SignedAmount::Negative(GAS_FEE.into()),
+            SignedAmount::Negative(TOKENS.into()),
+        );
+        keys_changed.append(&mut new_keys_changed);
+
+        // update the bridge pool balances
+        let mut new_keys_changed = update_balances(
+            &mut wl_storage.write_log,
+            Balance {
+                asset: ASSET,
+                kind: TransferToEthereumKind::Erc20,
+                owner: BRIDGE_POOL_ADDRESS,
+                gas: ESCROWED_AMOUNT.into(),
+                token: ESCROWED_TOKENS.into(),
+            },
+            SignedAmount::Positive(GAS_FEE.into()),
+            SignedAmount::Positive(TOKENS.into()),
+        );
+        keys_changed.append(&mut new_keys_changed);
+        let verifiers = BTreeSet::default();
+
+        // create the data to be given to the vp
+        let vp = BridgePoolVp {
+            ctx: setup_ctx(
+                &tx,
+                &wl_storage.storage,
+                &wl_storage.write_log,
+                &keys_changed,
+                &verifiers,
+            ),
+        };
+
+        let mut tx = Tx::new(wl_storage.storage.chain_id.clone(), None);
+        tx.add_data(transfer);
+
+        vp.validate_tx(&tx, &keys_changed, &verifiers)
+    }
+
+    /// Test that a sender who filled its cap of pending transfers may
+    /// not add more transfers to the pool, until one of them expires.
+    #[test]
+    fn test_pending_transfers_per_sender_cap() {
+        // below the cap
+        for pending in 0..2 {
+            assert!(
+                validate_with_pending_transfers(pending).expect("Test failed")
+            );
+        }
+
+        // the cap is filled
+        let err = validate_with_pending_transfers(3)
+            .expect_err("Test failed")
+            .to_string();
+        assert!(err.contains("already has 3 pending transfers"));
+        assert!(err.contains("the maximum allowed being 3"));
+
+        // one of the pending transfers expired, which brings the sender
+        // back under the cap
+        assert!(validate_with_pending_transfers(2).expect("Test failed"));
+    }
+
+    /// Test that a transfer which is not counted against the pending
+    /// transfers of its sender is rejected.
+    #[test]
+    fn test_transfer_not_counted_rejected() {
+        assert_bridge_pool(
+            SignedAmount::Negative(GAS_FEE.into()),
+            SignedAmount::Positive(GAS_FEE.into()),
+            SignedAmount::Negative(TOKENS.into()),
+            SignedAmount::Positive(TOKENS.into()),
+            |transfer, log| {
+                log.write(
+                    &get_pending_key(transfer),
+                    transfer.serialize_to_vec(),
+                )
+                .unwrap();
+                // count the transfer twice
+                let key = get_pending_per_sender_key(&transfer.transfer.sender);
+                log.write(&key, 1u64.serialize_to_vec()).unwrap();
+                BTreeSet::from([get_pending_key(transfer), key])
+            },
+            Expect::False,
+        );
+    }
+
     /// Test that the Bridge pool native VP validates transfers that
     /// do not contain gas fees and no associated changed keys.
     #[test]
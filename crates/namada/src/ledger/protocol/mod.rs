@@ -683,9 +683,9 @@ where
     use namada_vote_ext::{ethereum_events, validator_set_update};
 
     let Some(data) = data else {
-        return Err(Error::ProtocolTxError(
-            eyre!("Protocol tx data must be present")),
-        );
+        return Err(Error::ProtocolTxError(eyre!(
+            "Protocol tx data must be present"
+        )));
     };
     let ethereum_tx_data = EthereumTxData::deserialize(&tx, &data)
         .wrap_err_with(|| {
@@ -726,6 +726,10 @@ where
             )
             .map_err(Error::ProtocolTxError)
         }
+        EthereumTxData::ContractAddressesVext(ext) => {
+            transactions::contract_addresses::aggregate_votes(storage, ext)
+                .map_err(Error::ProtocolTxError)
+        }
         EthereumTxData::EthereumEvents(_)
         | EthereumTxData::BridgePool(_)
         | EthereumTxData::ValidatorSetUpdate(_) => {
@@ -1212,10 +1216,16 @@ mod tests {
                 receiver: address::testing::established_address_4(),
             }],
         };
+        let bridge_contract = wl_storage
+            .ethbridge_queries()
+            .get_contracts_at(wl_storage.storage.last_epoch)?
+            .bridge
+            .address;
         let vext = EthereumEventsVext {
             block_height: BlockHeight(100),
             validator_addr: address::testing::established_address_2(),
             ethereum_events: vec![event.clone()],
+            bridge_contract,
         };
         let signing_key = key::testing::keypair_1();
         let signed = vext.sign(&signing_key);
@@ -1278,6 +1288,7 @@ mod tests {
         let sig = Signed::<_, SignableEthMessage>::new(hot_key, to_sign).sig;
         let vext = BridgePoolRootVext {
             block_height: BlockHeight(100),
+            root_height: None,
             validator_addr: address::testing::established_address_2(),
             sig,
         }
@@ -828,6 +828,11 @@ where
     H: StorageHasher,
     CA: WasmCacheAccess,
 {
+    #[cfg(feature = "testing")]
+    if testing::tx_write_panics() {
+        panic!("tx_write deliberately panicked for testing");
+    }
+
     let (key, gas) = env
         .memory
         .read_string(key_ptr, key_len as _)
@@ -2733,6 +2738,7 @@ where
 /// A helper module for testing
 #[cfg(feature = "testing")]
 pub mod testing {
+    use std::cell::Cell;
     use std::collections::BTreeSet;
 
     use namada_state::StorageHasher;
@@ -2741,6 +2747,26 @@ pub mod testing {
     use crate::vm::memory::testing::NativeMemory;
     use crate::vm::wasm::memory::WasmMemory;
 
+    std::thread_local! {
+        /// Whether [`tx_write`] panics when called from the current thread
+        static TX_WRITE_PANICS: Cell<bool> = Cell::new(false);
+    }
+
+    /// Run `f` with [`tx_write`] panicking whenever it's called from the
+    /// current thread, to test the containment of panics raised while
+    /// executing a tx.
+    pub fn with_tx_write_panicking<T>(f: impl FnOnce() -> T) -> T {
+        TX_WRITE_PANICS.with(|panics| panics.set(true));
+        let result = f();
+        TX_WRITE_PANICS.with(|panics| panics.set(false));
+        result
+    }
+
+    /// Check if [`tx_write`] panics when called from the current thread.
+    pub(super) fn tx_write_panics() -> bool {
+        TX_WRITE_PANICS.with(Cell::get)
+    }
+
     /// Setup a transaction environment
     #[allow(clippy::too_many_arguments)]
     pub fn tx_env<DB, H, CA>(
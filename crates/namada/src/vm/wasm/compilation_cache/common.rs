@@ -10,7 +10,7 @@ use std::fs;
 use std::marker::PhantomData;
 use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, PoisonError, RwLock};
 use std::thread::sleep;
 use std::time::Duration;
 
@@ -454,6 +454,54 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
         }
     }
 
+    /// Evict a WASM module from the in-memory cache and remove its compiled
+    /// file, such that it has to be compiled again if it's ever needed. It
+    /// does nothing with a read-only access.
+    pub fn evict(&mut self, hash: &Hash) {
+        if !A::is_read_write() {
+            return;
+        }
+        tracing::info!("Evicting {} {}.", N::name(), hash.to_string());
+        self.in_memory.write().unwrap().pop(hash);
+        self.progress.write().unwrap().remove(hash);
+        let path = self.dir.join(hash.to_string().to_lowercase());
+        if let Err(err) = fs::remove_dir_all(path) {
+            if err.kind() != std::io::ErrorKind::NotFound {
+                tracing::error!(
+                    "Failed to remove the compiled file of {} {}: {err}.",
+                    N::name(),
+                    hash.to_string()
+                );
+            }
+        }
+    }
+
+    /// Replace the in-memory state of the cache with an empty one if a
+    /// panic poisoned its locks, e.g. while compiling a module, such that
+    /// the next executions don't panic in turn. The compiled modules of the
+    /// file system cache are kept. Handles cloned before the recovery keep
+    /// the poisoned state.
+    pub fn recover_poisoned(&mut self) {
+        if !self.in_memory.is_poisoned() && !self.progress.is_poisoned() {
+            return;
+        }
+        tracing::warn!(
+            "The in-memory {} has been poisoned by a panic, resetting it.",
+            N::name()
+        );
+        let capacity = self
+            .in_memory
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .capacity();
+        let cache = CLruCache::with_config(
+            CLruCacheConfig::new(NonZeroUsize::new(capacity).unwrap())
+                .with_scale(ModuleCacheScale),
+        );
+        self.in_memory = Arc::new(RwLock::new(cache));
+        self.progress = Default::default();
+    }
+
     /// Get a read-only cache handle.
     pub fn read_only(&self) -> Cache<N, WasmCacheRoAccess> {
         Cache {
@@ -1037,6 +1085,68 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_evict_wasm() {
+        let tx_no_op = load_wasm(TestWasms::TxNoOp.path());
+        let (mut cache, _) = testing::cache::<TestCache>();
+
+        cache.compile_or_fetch(&tx_no_op.code).unwrap().unwrap();
+        assert!(module_file_exists(&cache.dir, &tx_no_op.hash));
+
+        cache.evict(&tx_no_op.hash);
+        assert_matches!(
+            cache.in_memory.read().unwrap().peek(&tx_no_op.hash),
+            None,
+            "The module must be evicted from memory"
+        );
+        assert_matches!(
+            cache.progress.read().unwrap().get(&tx_no_op.hash),
+            None,
+            "The progress must be removed"
+        );
+        assert!(
+            !module_file_exists(&cache.dir, &tx_no_op.hash),
+            "The file must be removed"
+        );
+        assert_matches!(cache.fetch(&tx_no_op.hash).unwrap(), None);
+
+        // evicting a module that isn't cached does nothing
+        cache.evict(&tx_no_op.hash);
+
+        // the module may be compiled again
+        assert_matches!(
+            cache.compile_or_fetch(&tx_no_op.code).unwrap(),
+            Some(_)
+        );
+    }
+
+    #[test]
+    fn test_recover_poisoned_cache() {
+        let tx_no_op = load_wasm(TestWasms::TxNoOp.path());
+        let (mut cache, _tmp_dir) = cache(100);
+        assert_matches!(
+            cache.compile_or_fetch(&tx_no_op.code).unwrap(),
+            Some(_)
+        );
+
+        // a panic while holding the lock poisons the in-memory cache
+        let in_memory = cache.in_memory.clone();
+        std::thread::spawn(move || {
+            let _guard = in_memory.write().unwrap();
+            panic!("poisoning the cache");
+        })
+        .join()
+        .unwrap_err();
+        assert!(cache.in_memory.is_poisoned());
+
+        cache.recover_poisoned();
+        assert!(!cache.in_memory.is_poisoned());
+        assert_eq!(cache.get_size(), 0);
+        // the module is loaded back from the file system cache
+        assert_matches!(cache.fetch(&tx_no_op.hash).unwrap(), Some(_));
+        assert_eq!(cache.get_size(), 1);
+    }
+
     /// Get the WASM code bytes, its hash and find the compiled module's size
     fn load_wasm(file: impl AsRef<Path>) -> WasmWithMeta {
         // When `WeightScale` calls `loupe::size_of_val` in the cache, for some
@@ -14,7 +14,9 @@ use namada_core::types::token;
 use namada_storage::{self, ResultExt, StorageRead, StorageWrite};
 pub use storage::get_max_block_gas;
 use thiserror::Error;
-pub use wasm_allowlist::{is_tx_allowed, is_vp_allowed};
+pub use wasm_allowlist::{
+    is_tx_allowed, is_vp_allowed, update_tx_allowlist, update_vp_allowlist,
+};
 
 /// The internal address for storage keys representing parameters than
 /// can be changed via governance.
@@ -1,7 +1,15 @@
 //! Parameters storage
 
+use std::collections::BTreeMap;
+
+use borsh::BorshDeserialize;
 use namada_core::types::address::Address;
+use namada_core::types::chain::ProposalBytes;
+use namada_core::types::hash::Hash;
+use namada_core::types::parameters::EpochDuration;
 use namada_core::types::storage::{DbKeySeg, Key};
+use namada_core::types::time::DurationSecs;
+use namada_core::types::token;
 use namada_macros::StorageKeys;
 use namada_storage::StorageRead;
 
@@ -24,6 +32,36 @@ struct Keys {
     native_erc20: &'static str,
     /// Sub-lkey for storing the Ethereum address of the bridge contract.
     bridge_contract_address: &'static str,
+    /// Sub-key for storing an update of the Ethereum bridge contracts,
+    /// scheduled by governance to take effect at some future epoch.
+    scheduled_contracts_update: &'static str,
+    /// Sub-key for storing the Ethereum bridge contracts superseded by the
+    /// last update scheduled by governance, along with its activation epoch.
+    superseded_contracts: &'static str,
+    /// Sub-key for storing whether the Ethereum bridge was paused by
+    /// governance.
+    bridge_paused: &'static str,
+    /// Sub-key for storing the epoch from which the Bridge pool roots are
+    /// signed along with the height at which they were computed, set by
+    /// governance.
+    bp_root_height_binding_epoch: &'static str,
+    /// Sub-key for storing the epoch from which the validator set updates
+    /// are signed over a message separated to the domain of the chain,
+    /// set by governance.
+    valset_upd_domain_epoch: &'static str,
+    /// Sub-key for storing the maximum number of bytes of the vote
+    /// extension protocol txs included in a block, set by governance.
+    max_vext_digest_bytes: &'static str,
+    /// Sub-key for storing the block height at which the minted supply of
+    /// the wrapped Ethereum assets is backfilled from their balances, set
+    /// by governance along with the upgrade that tracks it.
+    wrapped_supply_backfill_height: &'static str,
+    /// Sub-key for storing the model of the Ethereum gas cost of relaying
+    /// proofs to the bridge contracts, calibrated by governance.
+    relay_gas_model: &'static str,
+    /// Sub-key for storing the maximum number of transfers a single sender
+    /// may have pending in the Bridge pool, set by governance.
+    max_pending_transfers_per_sender: &'static str,
     // ========================================
     // PoS parameters
     // ========================================
@@ -65,6 +103,52 @@ pub fn is_protocol_parameter_key(key: &Key) -> bool {
     Keys::ALL.binary_search(&segment).is_ok()
 }
 
+/// Check that a value may be written under the given protocol parameter key
+/// by a parameter change proposal, i.e. that the key is in the registry of
+/// parameters changeable by governance and the value is the Borsh encoding
+/// of the type of the parameter. The parameters of the Ethereum bridge and
+/// the wasm allowlists have their own proposal types, while the PoS
+/// parameters are overwritten by the protocol every epoch, so none of these
+/// are in the registry.
+pub fn is_valid_parameter_change(key: &Key, value: &[u8]) -> bool {
+    fn decodes<T: BorshDeserialize>(value: &[u8]) -> bool {
+        T::try_from_slice(value).is_ok()
+    }
+
+    let segment = match &key.segments[..] {
+        [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(segment)]
+            if addr == &ADDRESS =>
+        {
+            segment.as_str()
+        }
+        _ => return false,
+    };
+    let keys = Keys::VALUES;
+    if segment == keys.epoch_duration {
+        decodes::<EpochDuration>(value)
+    } else if segment == keys.max_expected_time_per_block {
+        decodes::<DurationSecs>(value)
+    } else if segment == keys.implicit_vp {
+        decodes::<Hash>(value)
+    } else if segment == keys.max_proposal_bytes {
+        decodes::<ProposalBytes>(value)
+    } else if segment == keys.max_tx_bytes {
+        decodes::<u32>(value)
+    } else if segment == keys.max_signatures_per_transaction {
+        decodes::<u8>(value)
+    } else if segment == keys.minimum_gas_price {
+        decodes::<BTreeMap<Address, token::Amount>>(value)
+    } else if segment == keys.epochs_per_year
+        || segment == keys.max_block_gas
+        || segment == keys.fee_unshielding_gas_limit
+        || segment == keys.fee_unshielding_descriptions_limit
+    {
+        decodes::<u64>(value)
+    } else {
+        false
+    }
+}
+
 /// Returns if the key is an epoch storage key.
 pub fn is_epoch_duration_storage_key(key: &Key) -> bool {
     is_epoch_duration_key_at_addr(key, &ADDRESS)
@@ -1,6 +1,6 @@
 use namada_core::types::hash::Hash;
 use namada_core::types::storage;
-use namada_storage::{Result, StorageRead};
+use namada_storage::{Result, StorageRead, StorageWrite};
 
 use crate::storage::{
     get_tx_allowlist_storage_key, get_vp_allowlist_storage_key,
@@ -26,6 +26,71 @@ where
     is_allowed(storage, key, vp_hash)
 }
 
+/// Add and remove the given code hashes to and from the tx allowlist, and
+/// return the resulting allowlist. Hashes that are already present aren't
+/// added again, and hashes that aren't present are ignored on removal. An
+/// update that would empty the allowlist, allowing any code, is refused.
+pub fn update_tx_allowlist<S>(
+    storage: &mut S,
+    add: &[Hash],
+    remove: &[Hash],
+) -> Result<Vec<String>>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = get_tx_allowlist_storage_key();
+    update_allowlist(storage, key, add, remove)
+}
+
+/// Add and remove the given code hashes to and from the VP allowlist, and
+/// return the resulting allowlist. Hashes that are already present aren't
+/// added again, and hashes that aren't present are ignored on removal. An
+/// update that would empty the allowlist, allowing any code, is refused.
+pub fn update_vp_allowlist<S>(
+    storage: &mut S,
+    add: &[Hash],
+    remove: &[Hash],
+) -> Result<Vec<String>>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = get_vp_allowlist_storage_key();
+    update_allowlist(storage, key, add, remove)
+}
+
+/// Update the allowlist under the given key. As an empty allowlist allows
+/// any code, the update is refused if it would remove every hash of the
+/// allowlist, in which case the allowlist is returned unchanged.
+fn update_allowlist<S>(
+    storage: &mut S,
+    allowlist_key: storage::Key,
+    add: &[Hash],
+    remove: &[Hash],
+) -> Result<Vec<String>>
+where
+    S: StorageRead + StorageWrite,
+{
+    let prev_allowlist: Vec<String> =
+        storage.read(&allowlist_key)?.unwrap_or_default();
+    let mut allowlist = prev_allowlist.clone();
+    for hash in add {
+        let hash = hash.to_string().to_lowercase();
+        if !allowlist.contains(&hash) {
+            allowlist.push(hash);
+        }
+    }
+    let remove: Vec<String> = remove
+        .iter()
+        .map(|hash| hash.to_string().to_lowercase())
+        .collect();
+    allowlist.retain(|hash| !remove.contains(hash));
+    if allowlist.is_empty() && !prev_allowlist.is_empty() {
+        return Ok(prev_allowlist);
+    }
+    storage.write(&allowlist_key, &allowlist)?;
+    Ok(allowlist)
+}
+
 fn is_allowed<S>(
     storage: &S,
     allowlist_key: storage::Key,
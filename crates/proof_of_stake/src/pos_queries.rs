@@ -11,10 +11,11 @@ use namada_storage::StorageRead;
 use thiserror::Error;
 
 use crate::storage::find_validator_by_raw_hash;
-use crate::types::WeightedValidator;
+use crate::types::{ValidatorState, WeightedValidator};
 use crate::{
     consensus_validator_set_handle, get_total_consensus_stake, read_pos_params,
     validator_eth_cold_key_handle, validator_eth_hot_key_handle,
+    validator_protocol_key_handle, validator_state_handle,
     ConsensusValidatorSet, PosParams,
 };
 
@@ -49,6 +50,24 @@ pub enum Error {
 /// Result type returned by [`PosQueries`] operations.
 pub type Result<T> = ::std::result::Result<T, Error>;
 
+/// The metadata of a consensus validator at some epoch, as returned by
+/// [`PosQueriesHook::consensus_validator_details`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidatorDetails {
+    /// The address of the validator.
+    pub address: Address,
+    /// The bonded stake of the validator.
+    pub stake: token::Amount,
+    /// The Ethereum hot key of the validator.
+    pub eth_hot_key: Option<key::common::PublicKey>,
+    /// The Ethereum cold key of the validator.
+    pub eth_cold_key: Option<key::common::PublicKey>,
+    /// The protocol signing key of the validator.
+    pub protocol_key: Option<key::common::PublicKey>,
+    /// The state of the validator.
+    pub state: Option<ValidatorState>,
+}
+
 /// Methods used to query blockchain proof-of-stake related state,
 /// such as the current set of consensus validators.
 pub trait PosQueries {
@@ -121,6 +140,57 @@ where
         }
     }
 
+    /// Get the [`ValidatorDetails`] of the consensus validators at a given
+    /// epoch (defaulting to the epoch of the current yet-to-be-committed
+    /// block), read in a single pass over storage.
+    ///
+    /// The returned validators are sorted by descending stake, and then by
+    /// ascending address, such that callers may build deterministic
+    /// encodings out of them. The result only depends on the given epoch,
+    /// therefore it may be cached by callers that look up several
+    /// validators of the same epoch.
+    pub fn consensus_validator_details(
+        self,
+        epoch: Option<Epoch>,
+    ) -> Vec<ValidatorDetails> {
+        let epoch =
+            epoch.unwrap_or_else(|| self.storage.get_block_epoch().unwrap());
+        let params = self.get_pos_params();
+        let mut validators: Vec<_> = self
+            .get_consensus_validators(Some(epoch))
+            .iter()
+            .map(|validator| {
+                let address = validator.address;
+                let eth_hot_key = validator_eth_hot_key_handle(&address)
+                    .get(self.storage, epoch, &params)
+                    .expect("Must be able to read Ethereum hot keys");
+                let eth_cold_key = validator_eth_cold_key_handle(&address)
+                    .get(self.storage, epoch, &params)
+                    .expect("Must be able to read Ethereum cold keys");
+                let protocol_key = validator_protocol_key_handle(&address)
+                    .get(self.storage, epoch, &params)
+                    .expect("Must be able to read protocol keys");
+                let state = validator_state_handle(&address)
+                    .get(self.storage, epoch, &params)
+                    .expect("Must be able to read validator states");
+                ValidatorDetails {
+                    address,
+                    stake: validator.bonded_stake,
+                    eth_hot_key,
+                    eth_cold_key,
+                    protocol_key,
+                    state,
+                }
+            })
+            .collect();
+        validators.sort_by(|a, b| {
+            b.stake
+                .cmp(&a.stake)
+                .then_with(|| a.address.cmp(&b.address))
+        });
+        validators
+    }
+
     /// Lookup the total voting power for an epoch (defaulting to the
     /// epoch of the current yet-to-be-committed block).
     pub fn get_total_voting_power(self, epoch: Option<Epoch>) -> token::Amount {
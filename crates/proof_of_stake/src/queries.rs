@@ -8,16 +8,24 @@ use namada_core::types::address::Address;
 use namada_core::types::dec::Dec;
 use namada_core::types::storage::Epoch;
 use namada_core::types::token;
+use namada_governance::storage::keys::VoterType;
+use namada_governance::utils::{
+    tally, ProposalVotes, TallyOutcome, TallyParams, TallyStrategy, TallyVote,
+    VotePower,
+};
 use namada_storage::collections::lazy_map::{NestedSubKey, SubKey};
 use namada_storage::{self, StorageRead};
 
 use crate::slashing::{find_validator_slashes, get_slashed_amount};
-use crate::storage::{bond_handle, read_pos_params, unbond_handle};
+use crate::storage::{
+    bond_handle, read_pos_params, read_total_stake, read_validator_stake,
+    unbond_handle,
+};
 use crate::types::{
     BondDetails, BondId, BondsAndUnbondsDetail, BondsAndUnbondsDetails, Slash,
     UnbondDetails,
 };
-use crate::{storage_key, PosParams};
+use crate::{bond_amount, storage_key, PosParams};
 
 /// Find all validators to which a given bond `owner` (or source) has a
 /// delegation
@@ -460,3 +468,164 @@ fn make_unbond_details(
         slashed_amount,
     }
 }
+
+/// Compute the voting power of the votes cast on a governance proposal, as
+/// of the given epoch. The votes of validators, or of bonds, left without
+/// stake at the epoch, e.g. after being slashed to zero, are kept with no
+/// voting power: they count towards the participation in the proposal, but
+/// not towards its result.
+pub fn compute_proposal_votes<S>(
+    storage: &S,
+    params: &PosParams,
+    proposal_id: u64,
+    epoch: Epoch,
+) -> namada_storage::Result<ProposalVotes>
+where
+    S: StorageRead,
+{
+    let votes =
+        namada_governance::storage::get_proposal_votes(storage, proposal_id)?;
+
+    let mut validators_vote: HashMap<Address, TallyVote> = HashMap::default();
+    let mut validator_voting_power: HashMap<Address, VotePower> =
+        HashMap::default();
+    let mut delegators_vote: HashMap<Address, TallyVote> = HashMap::default();
+    let mut delegator_voting_power: HashMap<
+        Address,
+        HashMap<Address, VotePower>,
+    > = HashMap::default();
+
+    for vote in votes {
+        // the role of the voter is recorded in its vote key, sparing a PoS
+        // read per vote
+        if vote.voter_type == VoterType::Validator {
+            let validator = vote.validator.clone();
+            let vote_data = vote.data.clone();
+
+            let validator_stake =
+                read_validator_stake(storage, params, &validator, epoch)
+                    .unwrap_or_default();
+            // A validator slashed to zero since voting still participated in
+            // the proposal, but its vote carries no voting power
+            if validator_stake.is_zero() {
+                tracing::info!(
+                    "Validator {} voted on governance proposal {} but has no \
+                     stake at epoch {}. The vote is counted as cast, with no \
+                     voting power.",
+                    validator,
+                    proposal_id,
+                    epoch
+                );
+            }
+
+            validators_vote.insert(validator.clone(), vote_data.into());
+            validator_voting_power.insert(validator, validator_stake);
+        } else {
+            let validator = vote.validator.clone();
+            let delegator = vote.delegator.clone();
+            let vote_data = vote.data.clone();
+
+            let bond_id = BondId {
+                source: delegator.clone(),
+                validator: validator.clone(),
+            };
+            let delegator_stake = bond_amount(storage, &bond_id, epoch);
+
+            if let Ok(stake) = delegator_stake {
+                if stake.is_zero() {
+                    tracing::info!(
+                        "Delegator {} voted on governance proposal {} but its \
+                         bond to validator {} has no stake at epoch {}. The \
+                         vote is counted as cast, with no voting power.",
+                        delegator,
+                        proposal_id,
+                        validator,
+                        epoch
+                    );
+                }
+                delegators_vote.insert(delegator.clone(), vote_data.into());
+                delegator_voting_power
+                    .entry(delegator)
+                    .or_default()
+                    .insert(validator, stake);
+            } else {
+                continue;
+            }
+        }
+    }
+
+    // The delegations of the accounts that haven't voted follow the votes
+    // of their governors, if any
+    let mut governors: HashMap<Address, Address> = HashMap::default();
+    for (delegator, governor) in
+        namada_governance::storage::get_governors(storage)?
+    {
+        if !delegators_vote.contains_key(&delegator)
+            && !validators_vote.contains_key(&delegator)
+        {
+            let delegations = find_delegations(storage, &delegator, &epoch)?;
+            let voting_power =
+                delegator_voting_power.entry(delegator.clone()).or_default();
+            for (validator, stake) in delegations {
+                voting_power.entry(validator).or_insert(stake);
+            }
+        }
+        governors.insert(delegator, governor);
+    }
+
+    Ok(ProposalVotes {
+        validators_vote,
+        validator_voting_power,
+        delegators_vote,
+        delegator_voting_power,
+        governors,
+    })
+}
+
+/// Tally the votes cast on a governance proposal with the given strategy,
+/// resolving the voting power of the voters and the total voting power as of
+/// the given epoch
+pub fn compute_proposal_tally<S>(
+    storage: &S,
+    params: &PosParams,
+    proposal_id: u64,
+    epoch: Epoch,
+    strategy: impl TallyStrategy,
+) -> namada_storage::Result<TallyOutcome>
+where
+    S: StorageRead,
+{
+    let votes = compute_proposal_votes(storage, params, proposal_id, epoch)?;
+    tally_proposal_votes(storage, params, &votes, epoch, strategy)
+}
+
+/// Tally the given votes on a governance proposal with the given strategy,
+/// against the total voting power as of the given epoch. The voting power of
+/// the voters must have been resolved at the same epoch, e.g. with
+/// [`compute_proposal_votes`]. The votes of the members of the council, if
+/// any, count towards the threshold of a council tally.
+pub fn tally_proposal_votes<S>(
+    storage: &S,
+    params: &PosParams,
+    votes: &ProposalVotes,
+    epoch: Epoch,
+    strategy: impl TallyStrategy,
+) -> namada_storage::Result<TallyOutcome>
+where
+    S: StorageRead,
+{
+    let total_voting_power = read_total_stake(storage, params, epoch)?;
+    let max_voting_power_fraction =
+        namada_governance::storage::get_max_voting_power_fraction(storage)?;
+    let council_votes = namada_governance::storage::get_council(storage)?
+        .map(|council| votes.council_votes(&council))
+        .unwrap_or_default();
+    Ok(tally(
+        votes.weighted_votes().into_iter().chain(council_votes),
+        TallyParams {
+            strategy,
+            total_voting_power,
+            max_voting_power_fraction,
+        },
+    ))
+}
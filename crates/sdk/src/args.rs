@@ -423,6 +423,9 @@ pub struct InitProposal<C: NamadaTypes = SdkTypes> {
     pub is_pgf_stewards: bool,
     /// Flag if proposal is of type Pgf funding
     pub is_pgf_funding: bool,
+    /// The code chunks of a default proposal, executed in order after those
+    /// of its data
+    pub code_chunks: Vec<C::Data>,
     /// Path to the tx WASM file
     pub tx_code_path: PathBuf,
 }
@@ -469,6 +472,14 @@ impl<C: NamadaTypes> InitProposal<C> {
         }
     }
 
+    /// The code chunks of a default proposal
+    pub fn code_chunks(self, code_chunks: Vec<C::Data>) -> Self {
+        Self {
+            code_chunks,
+            ..self
+        }
+    }
+
     /// Path to the tx WASM file
     pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
         Self {
@@ -532,7 +543,7 @@ impl InitProposal {
 
             tx::build_pgf_stewards_proposal(context, self, proposal).await
         } else {
-            let proposal = DefaultProposal::try_from(
+            let mut proposal = DefaultProposal::try_from(
                 self.proposal_data.as_ref(),
             )
             .map_err(|e| {
@@ -540,6 +551,9 @@ impl InitProposal {
                     e.to_string(),
                 )
             })?;
+            proposal
+                .code_chunks
+                .extend(self.code_chunks.iter().cloned());
             let nam_address = context.native_token();
             let author_balance = rpc::get_token_balance(
                 context.client(),
@@ -1140,6 +1154,73 @@ pub struct QueryProposal<C: NamadaTypes = SdkTypes> {
     pub proposal_id: Option<u64>,
 }
 
+/// Query the wasm code of a proposal
+#[derive(Clone, Debug)]
+pub struct QueryProposalCode<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Proposal id
+    pub proposal_id: u64,
+    /// Path of the file the code is written to
+    pub output: PathBuf,
+}
+
+/// Query the outcome of the execution of the code of a proposal
+#[derive(Clone, Debug)]
+pub struct QueryProposalExecution<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Proposal id
+    pub proposal_id: u64,
+}
+
+/// Wait for a proposal to conclude
+#[derive(Clone, Debug)]
+pub struct WatchProposal<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Proposal id
+    pub proposal_id: u64,
+    /// How long to wait for the proposal to conclude
+    pub timeout: Option<StdDuration>,
+}
+
+/// The format of the votes of a proposal exported to a file
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VoteExportFormat {
+    /// One comma separated line per vote, after a header line
+    Csv,
+    /// A JSON array with one object per vote
+    Json,
+}
+
+impl std::str::FromStr for VoteExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            _ => Err(format!(
+                "Unknown vote export format {s}, expected csv or json"
+            )),
+        }
+    }
+}
+
+/// Export the weighted votes of a proposal
+#[derive(Clone, Debug)]
+pub struct ExportProposalVotes<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Proposal id
+    pub proposal_id: u64,
+    /// The format of the exported votes
+    pub format: VoteExportFormat,
+    /// The file the votes are written to
+    pub output: PathBuf,
+}
+
 /// Query protocol parameters
 #[derive(Clone, Debug)]
 pub struct QueryProtocolParameters<C: NamadaTypes = SdkTypes> {
@@ -2437,6 +2518,17 @@ pub struct GovernanceValidatorSet<C: NamadaTypes = SdkTypes> {
     pub epoch: Option<Epoch>,
 }
 
+/// Validator set check arguments.
+#[derive(Debug, Clone)]
+pub struct ValidatorSetCheck<C: NamadaTypes = SdkTypes> {
+    /// Common query args, with the address of the remote node
+    pub query: Query<C>,
+    /// The address of the local ledger node as host:port
+    pub local_ledger_address: C::ConfigRpcTendermintAddress,
+    /// The epoch at which the validator set is signed over.
+    pub epoch: Option<Epoch>,
+}
+
 /// Validator set proof arguments.
 #[derive(Debug, Clone)]
 pub struct ValidatorSetProof<C: NamadaTypes = SdkTypes> {
@@ -11,6 +11,7 @@ use tendermint_rpc::Error as RpcError;
 use thiserror::Error;
 
 use crate::error::Error::Pinned;
+use crate::queries::EthBridgeQueryError;
 
 /// The standard Result type that most code ought to return
 pub type Result<T> = std::result::Result<T, Error>;
@@ -75,6 +76,9 @@ pub enum EventError {
     /// Missing value in attributes.
     #[error("Attributes missing value: {0}")]
     MissingValue(String),
+    /// Invalid value of an attribute.
+    #[error("Invalid value of attribute {0}: {1}")]
+    InvalidValue(String, String),
 }
 
 /// Errors that deal with querying some kind of data
@@ -96,6 +100,9 @@ pub enum QueryError {
     /// synchronizing with the network.
     #[error("Node is still catching up with the network")]
     CatchingUp,
+    /// Timed out waiting for a governance proposal to conclude
+    #[error("Timed out waiting for proposal {0} to conclude")]
+    ProposalTimeout(u64),
 }
 
 /// Errors that deal with Decoding, Encoding, or Conversions
@@ -383,6 +390,15 @@ pub enum EthereumBridgeError {
     /// Transfer already in pool error.
     #[error("An identical transfer is already present in the Bridge pool")]
     TransferAlreadyInPool,
+    /// Pending transfers per sender cap reached error.
+    #[error(
+        "The sender {0} already has the maximum number of {1} pending \
+         transfers in the Bridge pool"
+    )]
+    PendingTransfersCapReached(Address, u64),
+    /// Error returned by a versioned Ethereum bridge query.
+    #[error("Ethereum bridge query failed: {0}")]
+    Query(EthBridgeQueryError),
 }
 
 /// Checks if the given error is an invalid viewing key
@@ -5,6 +5,7 @@ use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+use borsh::BorshDeserialize;
 use borsh_ext::BorshSerializeExt;
 use ethbridge_bridge_contract::Bridge;
 use ethers::providers::Middleware;
@@ -17,8 +18,12 @@ use namada_core::types::eth_bridge_pool::{
 };
 use namada_core::types::ethereum_events::EthAddress;
 use namada_core::types::keccak::KeccakHash;
+use namada_core::types::storage::BlockHeight;
 use namada_core::types::voting_power::FractionalVotingPower;
-use namada_ethereum_bridge::storage::bridge_pool::get_pending_key;
+use namada_ethereum_bridge::storage::bridge_pool::{
+    get_pending_key, get_pending_per_sender_key,
+};
+use namada_ethereum_bridge::storage::max_pending_transfers_per_sender_key;
 use namada_token::storage_key::balance_key;
 use namada_token::Amount;
 use namada_tx::Tx;
@@ -39,7 +44,10 @@ use crate::queries::{
     Client, GenBridgePoolProofReq, GenBridgePoolProofRsp, TransferToErcArgs,
     TransferToEthereumStatus, RPC,
 };
-use crate::rpc::{query_storage_value, query_wasm_code_hash, validate_amount};
+use crate::rpc::{
+    query_storage_value, query_storage_value_bytes, query_wasm_code_hash,
+    validate_amount,
+};
 use crate::signing::{aux_signing_data, validate_fee_and_gen_unshield};
 use crate::tx::prepare_tx;
 use crate::{
@@ -201,6 +209,33 @@ async fn validate_bridge_pool_tx(
         ));
     }
 
+    // check that the sender didn't fill its cap of pending transfers
+    let read_count = |key| async move {
+        let (bytes, _) =
+            query_storage_value_bytes(context.client(), &key, None, false)
+                .await?;
+        bytes
+            .map(|bytes| u64::try_from_slice(&bytes))
+            .transpose()
+            .map_err(|e| Error::from(EncodingError::Decoding(e.to_string())))
+    };
+    if let Some(max_pending) =
+        read_count(max_pending_transfers_per_sender_key()).await?
+    {
+        let pending =
+            read_count(get_pending_per_sender_key(&transfer.transfer.sender))
+                .await?
+                .unwrap_or_default();
+        if pending >= max_pending {
+            return Err(Error::EthereumBridge(
+                EthereumBridgeError::PendingTransfersCapReached(
+                    transfer.transfer.sender,
+                    max_pending,
+                ),
+            ));
+        }
+    }
+
     let wnam_addr = RPC
         .shell()
         .eth_bridge()
@@ -516,6 +551,8 @@ struct BridgePoolProofResponse {
     relayer_address: Address,
     total_fees: HashMap<Address, Amount>,
     abi_encoded_args: Vec<u8>,
+    root_height: BlockHeight,
+    root_height_bound: bool,
 }
 
 /// Construct a merkle proof of a batch of transfers in
@@ -529,6 +566,8 @@ pub async fn construct_proof(
     let GenBridgePoolProofRsp {
         abi_encoded_args,
         appendices,
+        root_height,
+        root_height_bound,
     } = construct_bridge_pool_proof(
         client,
         io,
@@ -561,6 +600,8 @@ pub async fn construct_proof(
             .transpose()?
             .unwrap_or_default(),
         abi_encoded_args,
+        root_height,
+        root_height_bound,
     };
     display_line!(
         io,
@@ -744,7 +785,6 @@ mod recommendations {
     use std::collections::BTreeSet;
 
     use borsh::BorshDeserialize;
-    use namada_core::types::ethereum_events::Uint as EthUint;
     use namada_core::types::storage::BlockHeight;
     use namada_core::types::uint::{self, Uint, I256};
     use namada_vote_ext::validator_set_update::{
@@ -753,11 +793,10 @@ mod recommendations {
 
     use super::*;
     use crate::edisplay_line;
-    use crate::eth_bridge::storage::bridge_pool::{
-        get_nonce_key, get_signed_root_key,
-    };
+    use crate::eth_bridge::storage::bridge_pool::get_signed_root_key;
     use crate::eth_bridge::storage::proof::BridgePoolRootProof;
     use crate::io::Io;
+    use crate::rpc::eth_bridge::read_bridge_pool_nonce;
 
     const fn unsigned_transfer_fee() -> Uint {
         Uint::from_u64(37_500_u64)
@@ -884,30 +923,14 @@ mod recommendations {
             })?;
 
         // get the latest bridge pool nonce
-        let latest_bp_nonce = EthUint::try_from_slice(
-            &RPC.shell()
-                .storage_value(
-                    context.client(),
-                    None,
-                    None,
-                    false,
-                    &get_nonce_key(),
-                )
-                .await
-                .map_err(|err| {
-                    Error::Query(QueryError::General(echo_error!(
-                        context.io(),
-                        "Failed to query Bridge pool nonce: {err}"
-                    )))
-                })?
-                .data,
-        )
-        .map_err(|err| {
-            Error::Encode(EncodingError::Decoding(echo_error!(
-                context.io(),
-                "Failed to decode Bridge pool nonce: {err}"
-            )))
-        })?;
+        let latest_bp_nonce = read_bridge_pool_nonce(context.client())
+            .await
+            .map_err(|err| {
+                Error::Query(QueryError::General(echo_error!(
+                    context.io(),
+                    "Failed to query Bridge pool nonce: {err}"
+                )))
+            })?;
 
         if latest_bp_nonce != bp_root.data.1 {
             edisplay_line!(
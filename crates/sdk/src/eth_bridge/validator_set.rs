@@ -1,6 +1,7 @@
 //! Validator set updates SDK functionality.
 
 use std::cmp::Ordering;
+use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
@@ -10,13 +11,18 @@ use data_encoding::HEXLOWER;
 use ethbridge_bridge_contract::Bridge;
 use ethers::providers::Middleware;
 use futures::future::{self, FutureExt};
+use namada_core::borsh::BorshSerializeExt;
 use namada_core::hints;
 use namada_core::types::eth_abi::EncodeCell;
 use namada_core::types::ethereum_events::EthAddress;
+use namada_core::types::keccak::keccak_hash;
+use namada_core::types::key::common;
 use namada_core::types::storage::Epoch;
+use namada_core::types::token;
+use namada_ethereum_bridge::protocol::validation::VoteExtensionError;
 use namada_ethereum_bridge::storage::proof::EthereumProof;
 use namada_vote_ext::validator_set_update::{
-    ValidatorSetArgs, VotingPowersMap,
+    self, encode_voting_powers, EthAddrBook, ValidatorSetArgs, VotingPowersMap,
 };
 
 use super::{block_on_eth_sync, eth_sync_or, eth_sync_or_exit, BlockOnEthSync};
@@ -28,7 +34,7 @@ use crate::eth_bridge::ethers::core::types::TransactionReceipt;
 use crate::eth_bridge::structs::Signature;
 use crate::internal_macros::{echo_error, trace_error};
 use crate::io::Io;
-use crate::queries::{Client, RPC};
+use crate::queries::{Client, ValsetUpdVextDryRunReq, RPC};
 use crate::{args, display_line, edisplay_line};
 
 /// Relayer related errors.
@@ -284,8 +290,9 @@ pub async fn query_validator_set_update_proof(
     let encoded_proof = RPC
         .shell()
         .eth_bridge()
-        .read_valset_upd_proof(client, &epoch)
+        .read_valset_upd_proof(client, None, None, false, &epoch)
         .await
+        .map(|response| response.data)
         .map_err(|err| {
             SdkError::Query(QueryError::General(echo_error!(
                 io,
@@ -297,6 +304,233 @@ pub async fn query_validator_set_update_proof(
     Ok(encoded_proof)
 }
 
+/// Check if a validator set update vote extension would be accepted by
+/// the queried node, before gossiping it.
+///
+/// If no signature is provided, the signature verification step is
+/// skipped, such that the extension can be checked before it is signed.
+pub async fn dry_run_valset_upd_vext(
+    client: &(impl Client + Sync),
+    vext: validator_set_update::Vext,
+    sig: Option<common::Signature>,
+) -> Result<Result<(), VoteExtensionError>, SdkError> {
+    let data = ValsetUpdVextDryRunReq { vext, sig }.serialize_to_vec();
+    let response = RPC
+        .shell()
+        .eth_bridge()
+        .dry_run_valset_upd_vext(client, Some(data), None, false)
+        .await
+        .map_err(|err| {
+            SdkError::Query(QueryError::General(format!(
+                "Failed to dry run validator set update vote extension: {err}"
+            )))
+        })?;
+    Ok(response.data)
+}
+
+/// A divergence between the voting powers of a validator set, as computed
+/// by the local node, and as computed by a remote node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidatorSetDivergence {
+    /// A validator is only part of the set computed by the remote node.
+    MissingLocally {
+        /// The Ethereum addresses of the validator.
+        validator: EthAddrBook,
+        /// The voting power of the validator at the remote node.
+        remote_power: token::Amount,
+    },
+    /// A validator is only part of the set computed by the local node.
+    MissingRemotely {
+        /// The Ethereum addresses of the validator.
+        validator: EthAddrBook,
+        /// The voting power of the validator at the local node.
+        local_power: token::Amount,
+    },
+    /// The nodes disagree on the voting power of a validator.
+    PowerMismatch {
+        /// The Ethereum addresses of the validator.
+        validator: EthAddrBook,
+        /// The voting power of the validator at the local node.
+        local_power: token::Amount,
+        /// The voting power of the validator at the remote node.
+        remote_power: token::Amount,
+    },
+}
+
+impl ValidatorSetDivergence {
+    /// Get the Ethereum addresses of the diverging validator.
+    pub fn validator(&self) -> &EthAddrBook {
+        match self {
+            Self::MissingLocally { validator, .. }
+            | Self::MissingRemotely { validator, .. }
+            | Self::PowerMismatch { validator, .. } => validator,
+        }
+    }
+}
+
+impl fmt::Display for ValidatorSetDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingLocally {
+                validator,
+                remote_power,
+            } => write!(
+                f,
+                "Validator with hot key {} and cold key {} is missing from \
+                 the local set (remote voting power: {})",
+                validator.hot_key_addr,
+                validator.cold_key_addr,
+                remote_power.to_string_native(),
+            ),
+            Self::MissingRemotely {
+                validator,
+                local_power,
+            } => write!(
+                f,
+                "Validator with hot key {} and cold key {} is missing from \
+                 the remote set (local voting power: {})",
+                validator.hot_key_addr,
+                validator.cold_key_addr,
+                local_power.to_string_native(),
+            ),
+            Self::PowerMismatch {
+                validator,
+                local_power,
+                remote_power,
+            } => write!(
+                f,
+                "Validator with hot key {} and cold key {} has a local voting \
+                 power of {}, but a remote voting power of {}",
+                validator.hot_key_addr,
+                validator.cold_key_addr,
+                local_power.to_string_native(),
+                remote_power.to_string_native(),
+            ),
+        }
+    }
+}
+
+/// Compare the voting powers of a validator set computed by the local
+/// node against the ones computed by a remote node.
+///
+/// The divergences are returned sorted by the Ethereum addresses of the
+/// validators. An empty result means both nodes agree on the validator
+/// set, and thus on its canonical ABI encoding.
+pub fn diff_voting_powers(
+    local: &VotingPowersMap,
+    remote: &VotingPowersMap,
+) -> Vec<ValidatorSetDivergence> {
+    let mut divergences: Vec<_> = local
+        .iter()
+        .filter_map(|(validator, &local_power)| match remote.get(validator) {
+            None => Some(ValidatorSetDivergence::MissingRemotely {
+                validator: validator.clone(),
+                local_power,
+            }),
+            Some(&remote_power) if remote_power != local_power => {
+                Some(ValidatorSetDivergence::PowerMismatch {
+                    validator: validator.clone(),
+                    local_power,
+                    remote_power,
+                })
+            }
+            Some(_) => None,
+        })
+        .chain(remote.iter().filter_map(|(validator, &remote_power)| {
+            (!local.contains_key(validator)).then(|| {
+                ValidatorSetDivergence::MissingLocally {
+                    validator: validator.clone(),
+                    remote_power,
+                }
+            })
+        }))
+        .collect();
+    divergences.sort_by(|a, b| a.validator().cmp(b.validator()));
+    divergences
+}
+
+/// Check the validator set the local node expects validators to sign
+/// over, at the epoch following the given one, against the validator set
+/// computed by a remote node.
+///
+/// The hash of the canonical ABI encoding of both sets is displayed,
+/// followed by every divergence between them, which are also returned.
+pub async fn check_validator_set(
+    local_client: &(impl Client + Sync),
+    remote_client: &(impl Client + Sync),
+    io: &impl Io,
+    epoch: Option<Epoch>,
+) -> Result<Vec<ValidatorSetDivergence>, SdkError> {
+    let epoch = if let Some(epoch) = epoch {
+        epoch
+    } else {
+        RPC.shell().epoch(local_client).await.map_err(|err| {
+            SdkError::Query(QueryError::General(echo_error!(
+                io,
+                "Failed to query the current epoch: {err}"
+            )))
+        })?
+    };
+    let signed_epoch = epoch.next();
+
+    let local =
+        query_voting_powers(local_client, io, signed_epoch, "local").await?;
+    let remote =
+        query_voting_powers(remote_client, io, signed_epoch, "remote").await?;
+
+    display_line!(
+        io,
+        "Validator set of epoch {signed_epoch}, signed over at epoch {epoch}:"
+    );
+    display_line!(
+        io,
+        "  Local canonical encoding hash: {}",
+        keccak_hash(encode_voting_powers(&local))
+    );
+    display_line!(
+        io,
+        "  Remote canonical encoding hash: {}",
+        keccak_hash(encode_voting_powers(&remote))
+    );
+
+    let divergences = diff_voting_powers(&local, &remote);
+    if divergences.is_empty() {
+        display_line!(io, "The local and remote validator sets match.");
+    } else {
+        edisplay_line!(
+            io,
+            "Found {} divergence(s) between the local and remote validator \
+             sets:",
+            divergences.len()
+        );
+        for divergence in &divergences {
+            edisplay_line!(io, "  {divergence}");
+        }
+    }
+    Ok(divergences)
+}
+
+/// Query the voting powers of the validator set at the given epoch, from
+/// the `node` reached by `client`.
+async fn query_voting_powers(
+    client: &(impl Client + Sync),
+    io: &impl Io,
+    epoch: Epoch,
+    node: &str,
+) -> Result<VotingPowersMap, SdkError> {
+    RPC.shell()
+        .eth_bridge()
+        .voting_powers_at_epoch(client, &epoch)
+        .await
+        .map_err(|err| {
+            SdkError::Query(QueryError::General(echo_error!(
+                io,
+                "Failed to fetch the {node} validator set at epoch {epoch}: \
+                 {err}"
+            )))
+        })
+}
+
 /// Query an ABI encoding of the Bridge validator set at a given epoch.
 pub async fn query_bridge_validator_set(
     client: &(impl Client + Sync),
@@ -678,9 +912,9 @@ where
 
     let shell = RPC.shell().eth_bridge();
     let encoded_proof_fut = shell
-        .read_valset_upd_proof(nam_client, &epoch_to_relay)
+        .read_valset_upd_proof(nam_client, None, None, false, &epoch_to_relay)
         .map(|result| {
-            result.map_err(|err| {
+            result.map(|response| response.data).map_err(|err| {
                 let msg = format!(
                     "Failed to fetch validator set update proof: {err}"
                 );
@@ -785,6 +1019,48 @@ where
 mod tests {
     use super::*;
 
+    /// Test that all kinds of divergences between two validator sets are
+    /// found, in a deterministic order.
+    #[test]
+    fn test_diff_voting_powers() {
+        let addr_book = |byte| EthAddrBook {
+            hot_key_addr: EthAddress([byte; 20]),
+            cold_key_addr: EthAddress([byte + 1; 20]),
+        };
+        let power = token::Amount::native_whole;
+        let local = VotingPowersMap::from([
+            (addr_book(1), power(100)),
+            (addr_book(3), power(200)),
+            (addr_book(5), power(300)),
+        ]);
+        assert!(diff_voting_powers(&local, &local.clone()).is_empty());
+
+        let remote = VotingPowersMap::from([
+            (addr_book(1), power(100)),
+            (addr_book(3), power(250)),
+            (addr_book(7), power(300)),
+        ]);
+        assert_eq!(
+            diff_voting_powers(&local, &remote),
+            vec![
+                ValidatorSetDivergence::PowerMismatch {
+                    validator: addr_book(3),
+                    local_power: power(200),
+                    remote_power: power(250),
+                },
+                ValidatorSetDivergence::MissingRemotely {
+                    validator: addr_book(5),
+                    local_power: power(300),
+                },
+                ValidatorSetDivergence::MissingLocally {
+                    validator: addr_book(7),
+                    remote_power: power(300),
+                },
+            ]
+        );
+        assert_ne!(encode_voting_powers(&local), encode_voting_powers(&remote));
+    }
+
     /// Test [`GetStatus`] on various values.
     #[test]
     fn test_relay_op_statuses() {
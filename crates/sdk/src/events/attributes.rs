@@ -0,0 +1,155 @@
+//! Names of the attributes of the events emitted by the ledger.
+//!
+//! Attribute names are shared between the ledger, which emits events, and
+//! clients, which subscribe to them. Tendermint queries address an
+//! attribute as `{event_type}.{attribute_name}`, e.g.
+//! `proposal.proposal_id`.
+//!
+//! Tendermint only indexes the attributes flagged as indexable. Events of
+//! type [`EventType::Proposal`], [`EventType::EthereumBridge`] and
+//! [`EventType::StorageChange`] only flag the attributes listed in
+//! [`INDEXED`], whereas every attribute of the other event types is flagged.
+//!
+//! [`EventType::Proposal`]: super::EventType::Proposal
+//! [`EventType::EthereumBridge`]: super::EventType::EthereumBridge
+//! [`EventType::StorageChange`]: super::EventType::StorageChange
+
+/// The id of a governance proposal.
+pub const PROPOSAL_ID: &str = "proposal_id";
+
+/// The outcome of the tally of a governance proposal.
+pub const TALLY_RESULT: &str = "tally_result";
+
+/// The share of the yay votes in the voting power cast on a governance
+/// proposal, in basis points.
+pub const YAY_BASIS_POINTS: &str = "yay_basis_points";
+
+/// The share of the nay votes in the voting power cast on a governance
+/// proposal, in basis points.
+pub const NAY_BASIS_POINTS: &str = "nay_basis_points";
+
+/// The share of the abstain votes in the voting power cast on a governance
+/// proposal, in basis points.
+pub const ABSTAIN_BASIS_POINTS: &str = "abstain_basis_points";
+
+/// The shares of the votes cast on a governance proposal, as human readable
+/// percentages.
+pub const TALLY_PERCENTAGES: &str = "tally_percentages";
+
+/// The structured result written by the code of a governance proposal, as
+/// a JSON object of its entries by name.
+pub const PROPOSAL_OUTPUT: &str = "proposal_output";
+
+/// Flag set if the output of a governance proposal was truncated to fit in
+/// its event.
+pub const PROPOSAL_OUTPUT_TRUNCATED: &str = "proposal_output_truncated";
+
+/// The protocol parameters written by a parameter change proposal, as a
+/// JSON array of the key of each parameter along with its hex encoded
+/// values before and after the change.
+pub const PARAMETER_CHANGES: &str = "parameter_changes";
+
+/// The epoch at which an event was emitted.
+pub const EPOCH: &str = "epoch";
+
+/// The epoch of the validator set an event relates to.
+pub const VALSET_EPOCH: &str = "valset_epoch";
+
+/// The hash of an Ethereum bridge transfer.
+pub const TX_HASH: &str = "tx_hash";
+
+/// The kind of an Ethereum bridge event.
+pub const KIND: &str = "kind";
+
+/// The value of [`KIND`] for the activation of the Ethereum bridge
+/// contracts scheduled by governance.
+pub const CONTRACTS_ACTIVATED: &str = "contracts_activated";
+
+/// The epoch from which the Ethereum bridge contracts scheduled by
+/// governance are in effect.
+pub const ACTIVATION_EPOCH: &str = "activation_epoch";
+
+/// The address of the native ERC20 contract superseded by governance.
+pub const OLD_NATIVE_ERC20: &str = "old_native_erc20";
+
+/// The address of the native ERC20 contract set by governance.
+pub const NEW_NATIVE_ERC20: &str = "new_native_erc20";
+
+/// The address of the bridge contract superseded by governance.
+pub const OLD_BRIDGE_CONTRACT: &str = "old_bridge_contract";
+
+/// The address of the bridge contract set by governance.
+pub const NEW_BRIDGE_CONTRACT: &str = "new_bridge_contract";
+
+/// The address of the validator an event relates to.
+pub const VALIDATOR: &str = "validator";
+
+/// The epoch in which a validator set update was signed.
+pub const SIGNING_EPOCH: &str = "signing_epoch";
+
+/// A fraction of the voting power, as a `/` separated pair of numbers.
+pub const VOTING_POWER: &str = "voting_power";
+
+/// The gas consumed by a tx or by the code of a governance proposal.
+pub const GAS_USED: &str = "gas_used";
+
+/// The storage key whose value changed.
+pub const STORAGE_KEY: &str = "storage_key";
+
+/// Whether a governance proposal had no wasm code attached, as `1`, or
+/// had some, as `0`.
+pub const HAS_PROPOSAL_CODE: &str = "has_proposal_code";
+
+/// Whether a governance proposal failed to be applied, as `1`, or was
+/// applied successfully, as `0`.
+pub const PROPOSAL_CODE_EXIT_STATUS: &str = "proposal_code_exit_status";
+
+/// Set on the governance proposals rejected once queued for longer than
+/// their maximum lifetime.
+pub const EXPIRED: &str = "expired";
+
+/// The field of a governance proposal that couldn't be read, causing it to
+/// be rejected without being tallied.
+pub const MALFORMED: &str = "malformed";
+
+/// Set on the passed governance proposals whose code panicked while being
+/// executed.
+pub const PANICKED: &str = "panicked";
+
+/// The error that left a governance proposal undecided.
+pub const PROCESSING_ERROR: &str = "processing_error";
+
+/// The id of the governance proposal concluded earlier in the same epoch,
+/// that set the same protocol parameter as the proposal of the event.
+pub const CONFLICT: &str = "conflict";
+
+/// An advisory warning about a governance proposal or the treasury.
+pub const WARNING: &str = "warning";
+
+/// The value of [`KIND`] for relayed Bridge pool transfers.
+pub const BRIDGE_POOL_RELAYED: &str = "bridge_pool_relayed";
+
+/// The value of [`KIND`] for expired Bridge pool transfers.
+pub const BRIDGE_POOL_EXPIRED: &str = "bridge_pool_expired";
+
+/// The value of [`KIND`] for the signatures of validators included in the
+/// proof of a validator set update.
+pub const VALSET_SIG_INCLUDED: &str = "valset_sig_included";
+
+/// The attributes flagged as indexable, in events that don't flag all of
+/// their attributes.
+pub const INDEXED: &[&str] = &[
+    PROPOSAL_ID,
+    TALLY_RESULT,
+    EPOCH,
+    VALSET_EPOCH,
+    TX_HASH,
+    KIND,
+    VALIDATOR,
+    STORAGE_KEY,
+];
+
+/// Check if the given attribute is in [`INDEXED`].
+pub fn is_indexed(name: &str) -> bool {
+    INDEXED.contains(&name)
+}
@@ -0,0 +1,168 @@
+//! Typed construction of the events emitted by the ledger.
+//!
+//! Events are started with a constructor of [`Event`], such as
+//! [`Event::proposal`], and their attributes are set with the methods of
+//! [`EventBuilder`], which take care of the names and encoding of the
+//! attributes expected by the parsers of the events, e.g.
+//! [`ProposalOutcome`](super::ProposalOutcome).
+
+use std::collections::HashMap;
+
+use namada_core::types::address::Address;
+use namada_core::types::keccak::KeccakHash;
+use namada_governance::utils::{
+    bound_proposal_output, AppliedParameterChange, ProposalOutput,
+    TallyBasisPoints, TallyResult,
+};
+
+use super::{attributes, Event, EventLevel, EventType, ParameterChangeAttr};
+
+impl Event {
+    /// Start building the event of the governance proposal with the given
+    /// id.
+    pub fn proposal(id: u64) -> EventBuilder {
+        EventBuilder::new(EventType::Proposal, EventLevel::Block)
+            .attr(attributes::PROPOSAL_ID, id)
+    }
+
+    /// Start building an Ethereum bridge event of the given kind, which is
+    /// one of the values of [`attributes::KIND`].
+    pub fn eth_bridge(kind: &str) -> EventBuilder {
+        EventBuilder::new(EventType::EthereumBridge, EventLevel::Tx)
+            .attr(attributes::KIND, kind)
+    }
+}
+
+/// Builder of an [`Event`], with typed setters of its attributes.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct EventBuilder {
+    event: Event,
+}
+
+impl EventBuilder {
+    /// Start building an event of the given type and level, without any
+    /// attributes.
+    pub fn new(event_type: EventType, level: EventLevel) -> Self {
+        Self {
+            event: Event {
+                event_type,
+                level,
+                attributes: HashMap::new(),
+            },
+        }
+    }
+
+    /// Set the attribute with the given name. Prefer the typed setters,
+    /// for the attributes parsed by clients.
+    pub fn attr(mut self, name: &str, value: impl ToString) -> Self {
+        self.event
+            .attributes
+            .insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Set the outcome of the tally of a proposal.
+    pub fn tally(self, tally: TallyResult) -> Self {
+        self.attr(attributes::TALLY_RESULT, tally)
+    }
+
+    /// Set the shares of the votes cast on a proposal, both in basis points
+    /// and as human readable percentages.
+    pub fn basis_points(self, basis_points: TallyBasisPoints) -> Self {
+        self.attr(attributes::YAY_BASIS_POINTS, basis_points.yay)
+            .attr(attributes::NAY_BASIS_POINTS, basis_points.nay)
+            .attr(attributes::ABSTAIN_BASIS_POINTS, basis_points.abstain)
+            .attr(attributes::TALLY_PERCENTAGES, basis_points)
+    }
+
+    /// Set the structured result written by the code of a proposal, bounded
+    /// to [`MAX_PROPOSAL_OUTPUT_SIZE`], and flag it as truncated if some of
+    /// its entries didn't fit.
+    ///
+    /// [`MAX_PROPOSAL_OUTPUT_SIZE`]: namada_governance::utils::MAX_PROPOSAL_OUTPUT_SIZE
+    pub fn output(self, output: ProposalOutput) -> Self {
+        let (output, truncated) = bound_proposal_output(output);
+        let output = serde_json::to_string(&output)
+            .expect("Serializing a map of strings shouldn't fail");
+        let builder = self.attr(attributes::PROPOSAL_OUTPUT, output);
+        if truncated {
+            builder.attr(attributes::PROPOSAL_OUTPUT_TRUNCATED, true)
+        } else {
+            builder
+        }
+    }
+
+    /// Set the protocol parameters written by a parameter change proposal,
+    /// with their values before and after the change.
+    pub fn parameter_changes(self, changes: &[AppliedParameterChange]) -> Self {
+        let changes: Vec<_> =
+            changes.iter().map(ParameterChangeAttr::from).collect();
+        let changes = serde_json::to_string(&changes)
+            .expect("Serializing parameter changes shouldn't fail");
+        self.attr(attributes::PARAMETER_CHANGES, changes)
+    }
+
+    /// Set whether a proposal had wasm code attached.
+    pub fn has_code(self, has_code: bool) -> Self {
+        self.attr(attributes::HAS_PROPOSAL_CODE, !has_code as u64)
+    }
+
+    /// Set whether a proposal was applied successfully.
+    pub fn executed(self, executed: bool) -> Self {
+        self.attr(attributes::PROPOSAL_CODE_EXIT_STATUS, !executed as u64)
+    }
+
+    /// Flag a proposal as rejected for having expired.
+    pub fn expired(self) -> Self {
+        self.attr(attributes::EXPIRED, true)
+    }
+
+    /// Record the field of a proposal that couldn't be read.
+    pub fn malformed(self, field: &str) -> Self {
+        self.attr(attributes::MALFORMED, field)
+    }
+
+    /// Flag a proposal whose code panicked while being executed.
+    pub fn panicked(self) -> Self {
+        self.attr(attributes::PANICKED, true)
+    }
+
+    /// Record the error that left a proposal undecided.
+    pub fn processing_error(self, error: &str) -> Self {
+        self.attr(attributes::PROCESSING_ERROR, error)
+    }
+
+    /// Record the id of the proposal that set the same protocol parameter
+    /// earlier in the epoch.
+    pub fn conflict(self, proposal_id: u64) -> Self {
+        self.attr(attributes::CONFLICT, proposal_id)
+    }
+
+    /// Record an advisory warning.
+    pub fn warning(self, warning: &str) -> Self {
+        self.attr(attributes::WARNING, warning)
+    }
+
+    /// Set the hash of an Ethereum bridge transfer.
+    pub fn tx_hash(self, tx_hash: &KeccakHash) -> Self {
+        self.attr(attributes::TX_HASH, tx_hash)
+    }
+
+    /// Set the address of the validator an event relates to.
+    pub fn validator(self, validator: &Address) -> Self {
+        self.attr(attributes::VALIDATOR, validator)
+    }
+
+    /// Return the built event.
+    pub fn build(self) -> Event {
+        self.event
+    }
+}
+
+impl From<EventBuilder> for Event {
+    #[inline]
+    fn from(builder: EventBuilder) -> Event {
+        builder.build()
+    }
+}
@@ -1,4 +1,6 @@
 //! Logic to do with events emitted by the ledger.
+pub mod attributes;
+pub mod builder;
 pub mod log;
 
 use std::collections::HashMap;
@@ -8,14 +10,25 @@ use std::ops::{Index, IndexMut};
 use std::str::FromStr;
 
 use borsh::{BorshDeserialize, BorshSerialize};
+use data_encoding::HEXLOWER;
+use namada_core::types::address::Address;
 use namada_core::types::ethereum_structs::{BpTransferStatus, EthBridgeEvent};
 use namada_core::types::ibc::IbcEvent;
+use namada_core::types::storage::{Epoch, Key};
+use namada_core::types::token;
+use namada_governance::utils::{
+    AppliedParameterChange, ProposalOutput, ReleasedFunds, TallyBasisPoints,
+    TallyResult,
+};
 use namada_tx::data::TxType;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+pub use self::builder::EventBuilder;
 // use crate::ledger::governance::utils::ProposalEvent;
 use crate::error::{EncodingError, Error, EventError};
 use crate::tendermint_proto::v0_37::abci::EventAttribute;
+use crate::tendermint_rpc::query::{EventType as TmEventType, Query};
 
 impl From<EthBridgeEvent> for Event {
     #[inline]
@@ -27,27 +40,97 @@ impl From<EthBridgeEvent> for Event {
 impl From<&EthBridgeEvent> for Event {
     fn from(event: &EthBridgeEvent) -> Event {
         match event {
-            EthBridgeEvent::BridgePool { tx_hash, status } => Event {
-                event_type: EventType::EthereumBridge,
-                level: EventLevel::Tx,
-                attributes: {
-                    let mut attrs = HashMap::new();
-                    attrs.insert(
-                        "kind".into(),
-                        match status {
-                            BpTransferStatus::Relayed => "bridge_pool_relayed",
-                            BpTransferStatus::Expired => "bridge_pool_expired",
-                        }
-                        .into(),
-                    );
-                    attrs.insert("tx_hash".into(), tx_hash.to_string());
-                    attrs
-                },
-            },
+            EthBridgeEvent::BridgePool { tx_hash, status } => {
+                let kind = match status {
+                    BpTransferStatus::Relayed => {
+                        attributes::BRIDGE_POOL_RELAYED
+                    }
+                    BpTransferStatus::Expired => {
+                        attributes::BRIDGE_POOL_EXPIRED
+                    }
+                };
+                Event::eth_bridge(kind).tx_hash(tx_hash).build()
+            }
+            EthBridgeEvent::ContractsActivated {
+                activation_epoch,
+                old_native_erc20,
+                new_native_erc20,
+                old_bridge,
+                new_bridge,
+            } => Event::eth_bridge(attributes::CONTRACTS_ACTIVATED)
+                .attr(attributes::ACTIVATION_EPOCH, activation_epoch.0)
+                .attr(attributes::OLD_NATIVE_ERC20, old_native_erc20)
+                .attr(attributes::NEW_NATIVE_ERC20, new_native_erc20)
+                .attr(attributes::OLD_BRIDGE_CONTRACT, old_bridge)
+                .attr(attributes::NEW_BRIDGE_CONTRACT, new_bridge)
+                .build(),
+            EthBridgeEvent::ValsetSigIncluded {
+                validator,
+                signing_epoch,
+                voting_power,
+            } => Event::eth_bridge(attributes::VALSET_SIG_INCLUDED)
+                .validator(validator)
+                .attr(attributes::SIGNING_EPOCH, signing_epoch.0)
+                .attr(attributes::VOTING_POWER, voting_power)
+                .build(),
         }
     }
 }
 
+impl TryFrom<&Event> for EthBridgeEvent {
+    type Error = EventError;
+
+    fn try_from(event: &Event) -> Result<Self, Self::Error> {
+        if event.event_type != EventType::EthereumBridge {
+            return Err(EventError::InvalidEventType);
+        }
+        let status = match required_attr(event, attributes::KIND)? {
+            attributes::BRIDGE_POOL_RELAYED => BpTransferStatus::Relayed,
+            attributes::BRIDGE_POOL_EXPIRED => BpTransferStatus::Expired,
+            attributes::VALSET_SIG_INCLUDED => {
+                return Ok(EthBridgeEvent::ValsetSigIncluded {
+                    validator: parsed_attr(event, attributes::VALIDATOR)?,
+                    signing_epoch: Epoch(parsed_attr(
+                        event,
+                        attributes::SIGNING_EPOCH,
+                    )?),
+                    voting_power: parsed_attr(event, attributes::VOTING_POWER)?,
+                });
+            }
+            attributes::CONTRACTS_ACTIVATED => {
+                return Ok(EthBridgeEvent::ContractsActivated {
+                    activation_epoch: Epoch(parsed_attr(
+                        event,
+                        attributes::ACTIVATION_EPOCH,
+                    )?),
+                    old_native_erc20: parsed_attr(
+                        event,
+                        attributes::OLD_NATIVE_ERC20,
+                    )?,
+                    new_native_erc20: parsed_attr(
+                        event,
+                        attributes::NEW_NATIVE_ERC20,
+                    )?,
+                    old_bridge: parsed_attr(
+                        event,
+                        attributes::OLD_BRIDGE_CONTRACT,
+                    )?,
+                    new_bridge: parsed_attr(
+                        event,
+                        attributes::NEW_BRIDGE_CONTRACT,
+                    )?,
+                });
+            }
+            kind => return Err(invalid_attr(attributes::KIND, kind)),
+        };
+        let tx_hash = required_attr(event, attributes::TX_HASH)?;
+        let tx_hash = tx_hash
+            .try_into()
+            .map_err(|_| invalid_attr(attributes::TX_HASH, tx_hash))?;
+        Ok(EthBridgeEvent::BridgePool { tx_hash, status })
+    }
+}
+
 /// Indicates if an event is emitted do to
 /// an individual Tx or the nature of a finalized block
 #[derive(Clone, Debug, Eq, PartialEq, BorshSerialize, BorshDeserialize)]
@@ -86,6 +169,8 @@ pub enum EventType {
     PgfPayment,
     /// Ethereum Bridge event
     EthereumBridge,
+    /// The change of a watched storage key
+    StorageChange,
 }
 
 impl Display for EventType {
@@ -97,11 +182,26 @@ impl Display for EventType {
             EventType::Proposal => write!(f, "proposal"),
             EventType::PgfPayment => write!(f, "pgf_payment"),
             EventType::EthereumBridge => write!(f, "ethereum_bridge"),
+            EventType::StorageChange => write!(f, "storage_change"),
         }?;
         Ok(())
     }
 }
 
+impl EventType {
+    /// Check if all the attributes of events of this type are flagged as
+    /// indexable. Otherwise, only the attributes in
+    /// [`attributes::INDEXED`] are.
+    pub fn indexes_all_attributes(&self) -> bool {
+        !matches!(
+            self,
+            EventType::Proposal
+                | EventType::EthereumBridge
+                | EventType::StorageChange
+        )
+    }
+}
+
 impl FromStr for EventType {
     type Err = EventError;
 
@@ -118,6 +218,7 @@ impl FromStr for EventType {
                 Ok(EventType::Ibc("write_acknowledgement".to_string()))
             }
             "ethereum_bridge" => Ok(EventType::EthereumBridge),
+            "storage_change" => Ok(EventType::StorageChange),
             _ => Err(EventError::InvalidEventType),
         }
     }
@@ -176,6 +277,11 @@ impl Event {
     pub fn get(&self, key: &str) -> Option<&String> {
         self.attributes.get(key)
     }
+
+    /// Check if the attribute with the given key is flagged as indexable
+    pub fn is_indexed(&self, key: &str) -> bool {
+        self.event_type.indexes_all_attributes() || attributes::is_indexed(key)
+    }
 }
 
 impl Index<&str> for Event {
@@ -208,21 +314,267 @@ impl From<IbcEvent> for Event {
 /// Convert our custom event into the necessary tendermint proto type
 impl From<Event> for crate::tendermint_proto::v0_37::abci::Event {
     fn from(event: Event) -> Self {
+        let index_all = event.event_type.indexes_all_attributes();
         Self {
             r#type: event.event_type.to_string(),
             attributes: event
                 .attributes
                 .into_iter()
                 .map(|(key, value)| EventAttribute {
+                    index: index_all || attributes::is_indexed(&key),
                     key,
                     value,
-                    index: true,
                 })
                 .collect(),
         }
     }
 }
 
+/// Build the Tendermint query matching the blocks in which the governance
+/// proposal with the given id concluded, either by being executed or
+/// rejected
+pub fn proposal_concluded(id: u64) -> String {
+    proposal_concluded_query(id).to_string()
+}
+
+/// Build the Tendermint query matching the blocks in which the governance
+/// proposal with the given id concluded, as passed to `block_search`
+pub fn proposal_concluded_query(id: u64) -> Query {
+    Query::from(TmEventType::NewBlock).and_eq(
+        format!("{}.{}", EventType::Proposal, attributes::PROPOSAL_ID),
+        id.to_string(),
+    )
+}
+
+/// The outcome of a governance proposal, parsed from the event emitted by
+/// the ledger once the proposal concluded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProposalOutcome {
+    /// The id of the proposal.
+    pub id: u64,
+    /// The outcome of the tally of the proposal.
+    pub tally: TallyResult,
+    /// Whether the proposal had wasm code attached.
+    pub has_code: bool,
+    /// Whether the proposal was applied successfully. Always `false` for
+    /// rejected proposals.
+    pub executed: bool,
+    /// Whether the proposal was rejected for having expired.
+    pub expired: bool,
+    /// The field of the proposal that couldn't be read, if it was rejected
+    /// without being tallied.
+    pub malformed: Option<String>,
+    /// The id of the proposal that set the same protocol parameter earlier
+    /// in the epoch, if the proposal was rejected for conflicting with it.
+    pub conflicts_with: Option<u64>,
+    /// The shares of the votes cast on the proposal, if it was tallied.
+    pub basis_points: Option<TallyBasisPoints>,
+    /// The structured result written by the code of the proposal, if any,
+    /// possibly truncated.
+    pub output: Option<ProposalOutput>,
+    /// Whether the output of the proposal was truncated to fit in its
+    /// event.
+    pub output_truncated: bool,
+    /// The protocol parameters written by the proposal, if it was a passed
+    /// parameter change proposal.
+    pub parameter_changes: Vec<AppliedParameterChange>,
+    /// Where the funds locked by the proposal went. Events don't carry it,
+    /// it's filled in by [`wait_proposal_concluded`] from storage.
+    ///
+    /// [`wait_proposal_concluded`]: crate::rpc::wait_proposal_concluded
+    pub funds: Option<ProposalFunds>,
+}
+
+/// Where the funds locked by a governance proposal went once it concluded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProposalFunds {
+    /// The author of the proposal, to whom its funds are returned.
+    pub author: Address,
+    /// The amount returned to the author.
+    pub returned: token::Amount,
+    /// The amount burned, kept from the author of a rejected proposal.
+    pub burned: token::Amount,
+    /// The amount moved to the treasury, for expired proposals or the ones
+    /// whose code panicked.
+    pub to_treasury: token::Amount,
+}
+
+impl ProposalFunds {
+    /// The funds of a concluded proposal, split as recorded by the ledger
+    /// when it released them.
+    pub fn new(author: Address, released: ReleasedFunds) -> Self {
+        let ReleasedFunds {
+            returned,
+            burned,
+            to_treasury,
+        } = released;
+        Self {
+            author,
+            returned,
+            burned,
+            to_treasury,
+        }
+    }
+}
+
+impl TryFrom<&Event> for ProposalOutcome {
+    type Error = EventError;
+
+    /// Parse the outcome of a proposal. Proposal events that don't conclude
+    /// a proposal, such as processing errors and warnings, lack a tally
+    /// result and are rejected.
+    fn try_from(event: &Event) -> Result<Self, Self::Error> {
+        if event.event_type != EventType::Proposal {
+            return Err(EventError::InvalidEventType);
+        }
+        let id = required_attr(event, attributes::PROPOSAL_ID)?;
+        let id = id
+            .parse()
+            .map_err(|_| invalid_attr(attributes::PROPOSAL_ID, id))?;
+        let tally = match required_attr(event, attributes::TALLY_RESULT)? {
+            "passed" => TallyResult::Passed,
+            "rejected" => TallyResult::Rejected,
+            tally => return Err(invalid_attr(attributes::TALLY_RESULT, tally)),
+        };
+        Ok(ProposalOutcome {
+            id,
+            tally,
+            has_code: inverted_flag(event, attributes::HAS_PROPOSAL_CODE)?,
+            executed: inverted_flag(
+                event,
+                attributes::PROPOSAL_CODE_EXIT_STATUS,
+            )?,
+            expired: event
+                .get(attributes::EXPIRED)
+                .map(|expired| expired == "true")
+                .unwrap_or_default(),
+            malformed: event.get(attributes::MALFORMED).cloned(),
+            conflicts_with: event
+                .get(attributes::CONFLICT)
+                .map(|id| {
+                    id.parse()
+                        .map_err(|_| invalid_attr(attributes::CONFLICT, id))
+                })
+                .transpose()?,
+            basis_points: event
+                .contains_key(attributes::YAY_BASIS_POINTS)
+                .then(|| {
+                    Ok::<_, EventError>(TallyBasisPoints {
+                        yay: parsed_attr(event, attributes::YAY_BASIS_POINTS)?,
+                        nay: parsed_attr(event, attributes::NAY_BASIS_POINTS)?,
+                        abstain: parsed_attr(
+                            event,
+                            attributes::ABSTAIN_BASIS_POINTS,
+                        )?,
+                    })
+                })
+                .transpose()?,
+            output: event
+                .get(attributes::PROPOSAL_OUTPUT)
+                .map(|output| {
+                    serde_json::from_str(output).map_err(|_| {
+                        invalid_attr(attributes::PROPOSAL_OUTPUT, output)
+                    })
+                })
+                .transpose()?,
+            output_truncated: event
+                .get(attributes::PROPOSAL_OUTPUT_TRUNCATED)
+                .map(|truncated| truncated == "true")
+                .unwrap_or_default(),
+            parameter_changes: event
+                .get(attributes::PARAMETER_CHANGES)
+                .map(|changes| {
+                    serde_json::from_str::<Vec<ParameterChangeAttr>>(changes)
+                        .ok()
+                        .and_then(|changes| {
+                            changes
+                                .into_iter()
+                                .map(ParameterChangeAttr::parse)
+                                .collect::<Option<Vec<_>>>()
+                        })
+                        .ok_or_else(|| {
+                            invalid_attr(attributes::PARAMETER_CHANGES, changes)
+                        })
+                })
+                .transpose()?
+                .unwrap_or_default(),
+            funds: None,
+        })
+    }
+}
+
+impl TryFrom<Event> for ProposalOutcome {
+    type Error = EventError;
+
+    #[inline]
+    fn try_from(event: Event) -> Result<Self, Self::Error> {
+        Self::try_from(&event)
+    }
+}
+
+/// A parameter change, as encoded in [`attributes::PARAMETER_CHANGES`]
+#[derive(Serialize, Deserialize)]
+struct ParameterChangeAttr {
+    key: String,
+    old_value: Option<String>,
+    new_value: String,
+}
+
+impl From<&AppliedParameterChange> for ParameterChangeAttr {
+    fn from(change: &AppliedParameterChange) -> Self {
+        Self {
+            key: change.key.to_string(),
+            old_value: change.old_value.as_ref().map(|v| HEXLOWER.encode(v)),
+            new_value: HEXLOWER.encode(&change.new_value),
+        }
+    }
+}
+
+impl ParameterChangeAttr {
+    fn parse(self) -> Option<AppliedParameterChange> {
+        let decode = |value: String| HEXLOWER.decode(value.as_bytes()).ok();
+        Some(AppliedParameterChange {
+            key: Key::parse(self.key).ok()?,
+            old_value: match self.old_value {
+                Some(value) => Some(decode(value)?),
+                None => None,
+            },
+            new_value: decode(self.new_value)?,
+        })
+    }
+}
+
+/// Get the value of an attribute that must be present in an event
+fn required_attr<'ev>(
+    event: &'ev Event,
+    name: &str,
+) -> Result<&'ev str, EventError> {
+    event
+        .get(name)
+        .map(String::as_str)
+        .ok_or_else(|| EventError::MissingKey(name.to_string()))
+}
+
+/// Parse the value of an attribute that must be present in an event
+fn parsed_attr<T: FromStr>(event: &Event, name: &str) -> Result<T, EventError> {
+    let value = required_attr(event, name)?;
+    value.parse().map_err(|_| invalid_attr(name, value))
+}
+
+fn invalid_attr(name: &str, value: &str) -> EventError {
+    EventError::InvalidValue(name.to_string(), value.to_string())
+}
+
+/// Parse a flag encoded as `0` when set and `1` otherwise, such as the
+/// flags of proposal events
+fn inverted_flag(event: &Event, name: &str) -> Result<bool, EventError> {
+    match required_attr(event, name)? {
+        "0" => Ok(true),
+        "1" => Ok(false),
+        value => Err(invalid_attr(name, value)),
+    }
+}
+
 /// A thin wrapper around a HashMap for parsing event JSONs
 /// returned in tendermint subscription responses.
 #[derive(Debug)]
@@ -287,3 +639,129 @@ where
         ))),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use namada_core::types::address::testing::established_address_1;
+    use namada_core::types::ethereum_events::EthAddress;
+    use namada_core::types::keccak::KeccakHash;
+    use namada_core::types::voting_power::FractionalVotingPower;
+
+    use super::*;
+    use crate::tendermint::abci::Event as TmEvent;
+    use crate::tendermint_proto::v0_37::abci::Event as ProtoEvent;
+
+    /// Convert an event to its Tendermint representation and back to
+    /// protobuf, returning the index flag of each attribute
+    fn tendermint_round_trip(event: Event) -> HashMap<String, bool> {
+        let proto = ProtoEvent::from(event);
+        let tm_event = TmEvent::try_from(proto).expect("Test failed");
+        ProtoEvent::from(tm_event)
+            .attributes
+            .into_iter()
+            .map(|attr| (attr.key, attr.index))
+            .collect()
+    }
+
+    /// Test that only the key attributes of Ethereum bridge events are
+    /// flagged as indexable
+    #[test]
+    fn test_bridge_event_index_flags() {
+        let mut event: Event =
+            EthBridgeEvent::new_bridge_pool_relayed(KeccakHash([1; 32])).into();
+        event["height"] = "1".to_string();
+
+        let flags = tendermint_round_trip(event);
+        assert_eq!(flags.len(), 3);
+        assert!(flags[attributes::KIND]);
+        assert!(flags[attributes::TX_HASH]);
+        assert!(!flags["height"]);
+
+        let event: Event = EthBridgeEvent::new_valset_sig_included(
+            established_address_1(),
+            Epoch(3),
+            FractionalVotingPower::ONE_THIRD,
+        )
+        .into();
+        let flags = tendermint_round_trip(event);
+        assert_eq!(flags.len(), 4);
+        assert!(flags[attributes::KIND]);
+        assert!(flags[attributes::VALIDATOR]);
+        assert!(!flags[attributes::SIGNING_EPOCH]);
+        assert!(!flags[attributes::VOTING_POWER]);
+    }
+
+    /// Test that Ethereum bridge events are parsed back to the events they
+    /// were emitted from
+    #[test]
+    fn test_bridge_event_round_trip() {
+        for bridge_event in [
+            EthBridgeEvent::new_bridge_pool_relayed(KeccakHash([1; 32])),
+            EthBridgeEvent::new_bridge_pool_expired(KeccakHash([2; 32])),
+            EthBridgeEvent::new_contracts_activated(
+                Epoch(5),
+                EthAddress([1; 20]),
+                EthAddress([2; 20]),
+                EthAddress([3; 20]),
+                EthAddress([4; 20]),
+            ),
+            EthBridgeEvent::new_valset_sig_included(
+                established_address_1(),
+                Epoch(3),
+                FractionalVotingPower::new_u64(3, 4).expect("Test failed"),
+            ),
+        ] {
+            let event = Event::from(&bridge_event);
+            assert_eq!(event.level, EventLevel::Tx);
+            assert_eq!(
+                EthBridgeEvent::try_from(&event).expect("Test failed"),
+                bridge_event
+            );
+        }
+
+        let mut event = Event::eth_bridge("bridge_pool_pending")
+            .tx_hash(&KeccakHash([1; 32]))
+            .build();
+        assert!(matches!(
+            EthBridgeEvent::try_from(&event),
+            Err(EventError::InvalidValue(..))
+        ));
+        event[attributes::KIND] = attributes::BRIDGE_POOL_RELAYED.to_string();
+        event.attributes.remove(attributes::TX_HASH);
+        assert!(matches!(
+            EthBridgeEvent::try_from(&event),
+            Err(EventError::MissingKey(_))
+        ));
+        assert!(matches!(
+            ProposalOutcome::try_from(&event),
+            Err(EventError::InvalidEventType)
+        ));
+    }
+
+    /// Test that all the attributes of transaction events remain flagged
+    /// as indexable
+    #[test]
+    fn test_tx_event_index_flags() {
+        let event = Event {
+            event_type: EventType::Applied,
+            level: EventLevel::Tx,
+            attributes: HashMap::from([
+                ("hash".to_string(), "abcd".to_string()),
+                ("log".to_string(), String::new()),
+            ]),
+        };
+
+        let flags = tendermint_round_trip(event);
+        assert_eq!(flags.len(), 2);
+        assert!(flags.values().all(|index| *index));
+    }
+
+    /// Test the query of the conclusion of a proposal
+    #[test]
+    fn test_proposal_concluded_query() {
+        assert_eq!(
+            proposal_concluded(42),
+            "tm.event = 'NewBlock' AND proposal.proposal_id = '42'"
+        );
+    }
+}
@@ -275,6 +275,7 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             is_offline: false,
             is_pgf_stewards: false,
             is_pgf_funding: false,
+            code_chunks: vec![],
             tx_code_path: PathBuf::from(TX_INIT_PROPOSAL),
             tx: self.tx_builder(),
         }
@@ -1143,7 +1144,7 @@ pub mod testing {
             let mut tx = Tx { header, sections: vec![] };
             let content_hash = tx.add_section(Section::ExtraData(content_extra_data)).get_hash();
             init_proposal.content = content_hash;
-            if let ProposalType::Default(Some(hash)) = &mut init_proposal.r#type {
+            if let ProposalType::Default(Some(hash)) | ProposalType::Council(Some(hash)) = &mut init_proposal.r#type {
                 let type_hash = tx.add_section(Section::ExtraData(type_extra_data)).get_hash();
                 *hash = type_hash;
             }
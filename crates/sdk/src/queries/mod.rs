@@ -4,17 +4,18 @@
 // Re-export to show in rustdoc!
 use namada_core::types::storage::BlockHeight;
 use namada_state::{DBIter, StorageHasher, DB};
-pub use shell::Shell;
 use shell::SHELL;
+pub use shell::{Shell, PREFIX_PAGE_SIZE};
 pub use types::{
-    EncodedResponseQuery, Error, RequestCtx, RequestQuery, ResponseQuery,
-    Router,
+    resolve_query_height, EncodedResponseQuery, Error, QueryHeightError,
+    RequestCtx, RequestQuery, ResponseQuery, Router, StateAtHeight,
 };
 use vp::{Vp, VP};
 
 pub use self::shell::eth_bridge::{
-    Erc20FlowControl, GenBridgePoolProofReq, GenBridgePoolProofRsp,
-    TransferToErcArgs, TransferToEthereumStatus,
+    Erc20FlowControl, EthBridgeQueryError, GenBridgePoolProofReq,
+    GenBridgePoolProofRsp, TransferToErcArgs, TransferToEthereumStatus,
+    ValsetUpdVextDryRunReq,
 };
 use crate::MaybeSend;
 
@@ -116,6 +117,8 @@ mod testing {
         pub wl_storage: TestWlStorage,
         /// event log
         pub event_log: EventLog,
+        /// whether validator set previews are enabled
+        pub enable_valset_preview: bool,
     }
 
     impl<RPC> TestClient<RPC>
@@ -145,6 +148,7 @@ mod testing {
                 rpc,
                 wl_storage,
                 event_log,
+                enable_valset_preview: false,
             }
         }
     }
@@ -184,6 +188,7 @@ mod testing {
                 vp_wasm_cache: (),
                 tx_wasm_cache: (),
                 storage_read_past_height_limit: None,
+                enable_valset_preview: self.enable_valset_preview,
             };
             // TODO: this is a hack to propagate errors to the caller, we should
             // really permit error types other than [`std::io::Error`]
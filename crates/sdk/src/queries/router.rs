@@ -1036,6 +1036,7 @@ mod test {
             vp_wasm_cache: (),
             tx_wasm_cache: (),
             storage_read_past_height_limit: None,
+            enable_valset_preview: false,
         };
         let result = TEST_RPC.handle(ctx, &request);
         assert!(result.is_err());
@@ -1053,6 +1054,7 @@ mod test {
             vp_wasm_cache: (),
             tx_wasm_cache: (),
             storage_read_past_height_limit: None,
+            enable_valset_preview: false,
         };
         let result = TEST_RPC.handle(ctx, &request);
         assert!(matches!(
@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 
 pub(super) mod eth_bridge;
 
-use borsh::BorshDeserialize;
+use borsh::{BorshDeserialize, BorshSerialize};
 use borsh_ext::BorshSerializeExt;
 use masp_primitives::asset_type::AssetType;
 use masp_primitives::merkle_tree::MerklePath;
@@ -17,6 +17,7 @@ use namada_core::types::storage::{
 };
 use namada_core::types::token::{Denomination, MaspDigitPos};
 use namada_core::types::uint::Uint;
+use namada_gas::BlockGasMeter;
 use namada_state::{DBIter, LastBlock, StorageHasher, DB};
 use namada_storage::{self, ResultExt, StorageRead};
 #[cfg(any(test, feature = "async-client"))]
@@ -50,6 +51,26 @@ type Conversion = (
     MerklePath<Node>,
 );
 
+/// Maximum number of bytes of a storage value returned by a single
+/// `value_chunk` query.
+pub const VALUE_CHUNK_SIZE: usize = 512 * 1024;
+
+/// Maximum number of storage values returned by a single `prefix_page` query.
+pub const PREFIX_PAGE_SIZE: usize = 100;
+
+/// A chunk of a storage value too large to be returned in a single response.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct ValueChunk {
+    /// The length of the whole value, in bytes
+    pub total_len: u64,
+    /// The hash of the whole value, such that clients can detect if the
+    /// value changed in between queries of its chunks
+    pub value_hash: Hash,
+    /// The bytes of the chunk, of [`VALUE_CHUNK_SIZE`] bytes unless this is
+    /// the last chunk
+    pub bytes: Vec<u8>,
+}
+
 router! {SHELL,
     // Shell provides storage read access, block metadata and can dry-run a tx
 
@@ -75,6 +96,11 @@ router! {SHELL,
     ( "value" / [storage_key: storage::Key] )
         -> Vec<u8> = (with_options storage_value),
 
+    // Raw storage access - read a chunk of a value, for values too large to
+    // be returned in a single response
+    ( "value_chunk" / [storage_key: storage::Key] / [chunk: u64] )
+        -> Option<ValueChunk> = storage_value_chunk,
+
     // Dry run a transaction
     ( "dry_run_tx" ) -> TxResult = (with_options dry_run_tx),
 
@@ -82,6 +108,11 @@ router! {SHELL,
     ( "prefix" / [storage_key: storage::Key] )
         -> Vec<PrefixValue> = (with_options storage_prefix),
 
+    // Raw storage access - a page of the prefix iterator, for prefixes with
+    // too many values to be returned in a single response
+    ( "prefix_page" / [storage_key: storage::Key] / [page: u32] )
+        -> Vec<PrefixValue> = storage_prefix_page,
+
     // Raw storage access - is given storage key present?
     ( "has_key" / [storage_key: storage::Key] )
         -> bool = storage_has_key,
@@ -98,6 +129,10 @@ router! {SHELL,
     // Block results access - read bit-vec
     ( "results" ) -> Vec<BlockResults> = read_results,
 
+    // Block results access - read the gas consumed by each tx and governance
+    // proposal of a committed block, and their sum
+    ( "block_results" / [height: BlockHeight] / "gas" ) -> Option<BlockGasMeter> = read_block_gas,
+
     // was the transaction accepted?
     ( "accepted" / [tx_hash: Hash] ) -> Option<Event> = accepted,
 
@@ -167,6 +202,23 @@ where
     Ok(results)
 }
 
+/// Query to read the gas consumed by the block at the given height. Returns
+/// `None` if no block was committed at this height.
+fn read_block_gas<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    height: BlockHeight,
+) -> namada_storage::Result<Option<BlockGasMeter>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    ctx.wl_storage
+        .storage
+        .db
+        .read_block_gas(height)
+        .into_storage_result()
+}
+
 /// Query to read the conversion state
 fn read_conversions<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -447,6 +499,35 @@ where
     }
 }
 
+fn storage_value_chunk<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    storage_key: storage::Key,
+    chunk: u64,
+) -> namada_storage::Result<Option<ValueChunk>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let Some(value) = ctx.wl_storage.read_bytes(&storage_key)? else {
+        return Ok(None);
+    };
+    let total_len = value.len() as u64;
+    let start = chunk.saturating_mul(VALUE_CHUNK_SIZE as u64);
+    if start > total_len || (start == total_len && chunk > 0) {
+        return Err(namada_storage::Error::new(format!(
+            "Chunk {chunk} is out of range of the value of {storage_key}, \
+             which is {total_len} bytes long"
+        )));
+    }
+    let start = start as usize;
+    let end = value.len().min(start + VALUE_CHUNK_SIZE);
+    Ok(Some(ValueChunk {
+        total_len,
+        value_hash: Hash::sha256(&value),
+        bytes: value[start..end].to_vec(),
+    }))
+}
+
 fn storage_prefix<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
     request: &RequestQuery,
@@ -503,6 +584,28 @@ where
     })
 }
 
+/// Iterate the values with the given prefix in the order of their keys,
+/// skipping the given number of pages of [`PREFIX_PAGE_SIZE`] values
+fn storage_prefix_page<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    storage_key: storage::Key,
+    page: u32,
+) -> namada_storage::Result<Vec<PrefixValue>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let page = page as usize;
+    namada_storage::iter_prefix_bytes(ctx.wl_storage, &storage_key)?
+        .skip(page.saturating_mul(PREFIX_PAGE_SIZE))
+        .take(PREFIX_PAGE_SIZE)
+        .map(|iter_result| {
+            let (key, value) = iter_result?;
+            Ok(PrefixValue { key, value })
+        })
+        .collect()
+}
+
 fn storage_has_key<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
     storage_key: storage::Key,
@@ -639,9 +742,15 @@ where
 #[cfg(test)]
 mod test {
     use namada_core::types::address;
+    use namada_core::types::storage::Key;
+    use namada_gas::Gas;
+    use namada_storage::StorageWrite;
     use namada_token::storage_key::balance_key;
 
+    use super::{PREFIX_PAGE_SIZE, VALUE_CHUNK_SIZE};
+    use crate::queries::testing::TestClient;
     use crate::queries::RPC;
+    use crate::rpc::{query_block_gas, query_storage_value_chunked};
 
     #[test]
     fn test_shell_queries_router_paths() {
@@ -663,4 +772,117 @@ mod test {
         let path = RPC.shell().storage_has_key_path(&key);
         assert_eq!(format!("/shell/has_key/{}", key), path);
     }
+
+    /// Test that values spanning several chunks are fetched whole
+    #[tokio::test]
+    async fn test_storage_value_chunks() {
+        let mut client = TestClient::new(RPC);
+        let key = Key::parse("large_value").unwrap();
+        let value: Vec<u8> = (0..2 * VALUE_CHUNK_SIZE + 10)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        client.wl_storage.write_bytes(&key, &value).unwrap();
+
+        let chunk = RPC
+            .shell()
+            .storage_value_chunk(&client, &key, &2)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(chunk.total_len, value.len() as u64);
+        assert_eq!(chunk.bytes, &value[2 * VALUE_CHUNK_SIZE..]);
+        // there is no fourth chunk
+        assert!(
+            RPC.shell()
+                .storage_value_chunk(&client, &key, &3)
+                .await
+                .is_err()
+        );
+
+        let fetched = query_storage_value_chunked(&client, &key).await.unwrap();
+        assert_eq!(fetched, Some(value));
+
+        // empty values are returned in a single chunk
+        let empty_key = Key::parse("empty_value").unwrap();
+        client.wl_storage.write_bytes(&empty_key, []).unwrap();
+        let fetched = query_storage_value_chunked(&client, &empty_key)
+            .await
+            .unwrap();
+        assert_eq!(fetched, Some(vec![]));
+
+        let missing_key = Key::parse("missing_value").unwrap();
+        let fetched = query_storage_value_chunked(&client, &missing_key)
+            .await
+            .unwrap();
+        assert_eq!(fetched, None);
+    }
+
+    /// Test that the values with a prefix are paged in the order of their
+    /// keys
+    #[tokio::test]
+    async fn test_storage_prefix_pages() {
+        let mut client = TestClient::new(RPC);
+        let prefix = Key::parse("paged").unwrap();
+        let count = PREFIX_PAGE_SIZE + PREFIX_PAGE_SIZE / 2;
+        for i in 0..count {
+            let key = prefix.push(&format!("{i:04}")).unwrap();
+            client.wl_storage.write(&key, i as u64).unwrap();
+        }
+
+        let mut values = vec![];
+        for page in 0..3 {
+            let data = RPC
+                .shell()
+                .storage_prefix_page(&client, &prefix, &page)
+                .await
+                .unwrap();
+            let expected_len = match page {
+                0 => PREFIX_PAGE_SIZE,
+                1 => PREFIX_PAGE_SIZE / 2,
+                _ => 0,
+            };
+            assert_eq!(data.len(), expected_len);
+            values.extend(data);
+        }
+        let expected: Vec<_> = (0..count)
+            .map(|i| prefix.push(&format!("{i:04}")).unwrap())
+            .collect();
+        let keys: Vec<_> = values.into_iter().map(|value| value.key).collect();
+        assert_eq!(keys, expected);
+    }
+
+    /// Test that the gas of the txs and proposals of a committed block sums
+    /// up to the block total
+    #[tokio::test]
+    async fn test_read_block_gas() {
+        let mut client = TestClient::new(RPC);
+        client.wl_storage.storage.block.height = 1.into();
+        let gas = &mut client.wl_storage.storage.block.gas;
+        gas.add_proposal_gas(3, 1_000.into()).unwrap();
+        gas.add_tx_gas(0, 20_000.into()).unwrap();
+        gas.add_tx_gas(1, Gas::default()).unwrap();
+        gas.add_tx_gas(2, 35_000.into()).unwrap();
+        client.wl_storage.commit_block().unwrap();
+
+        let block_gas = query_block_gas(&client, 1.into())
+            .await
+            .unwrap()
+            .expect("The gas of a committed block must be present");
+        let txs_gas: Vec<_> = block_gas.iter_txs_gas().collect();
+        assert_eq!(
+            txs_gas,
+            vec![(0, 20_000.into()), (1, Gas::default()), (2, 35_000.into())]
+        );
+        let sum = block_gas
+            .iter_txs_gas()
+            .map(|(_, gas)| gas)
+            .chain(block_gas.iter_proposals_gas().map(|(_, gas)| gas))
+            .try_fold(Gas::default(), |acc, gas| acc.checked_add(gas))
+            .unwrap();
+        assert_eq!(sum, block_gas.get_block_consumed_gas());
+        assert_eq!(sum, 56_000.into());
+
+        // no block was committed at this height
+        assert!(query_block_gas(&client, 2.into()).await.unwrap().is_none());
+    }
 }
@@ -10,39 +10,55 @@ use namada_core::hints;
 use namada_core::types::address::Address;
 use namada_core::types::eth_abi::{Encode, EncodeCell};
 use namada_core::types::eth_bridge_pool::{
-    PendingTransfer, PendingTransferAppendix,
+    PendingTransfer, PendingTransferAppendix, BRIDGE_POOL_ADDRESS,
 };
 use namada_core::types::ethereum_events::{
-    EthAddress, EthereumEvent, TransferToEthereum,
+    EthAddress, EthereumEvent, TransferToEthereum, Uint,
+};
+use namada_core::types::ethereum_structs::{
+    self, BpTransferStatus, EthBridgeEvent,
 };
-use namada_core::types::ethereum_structs;
 use namada_core::types::keccak::KeccakHash;
+use namada_core::types::key::common;
 use namada_core::types::storage::{BlockHeight, DbKeySeg, Epoch, Key};
 use namada_core::types::token::Amount;
 use namada_core::types::voting_power::FractionalVotingPower;
 use namada_ethereum_bridge::protocol::transactions::votes::{
     EpochedVotingPower, EpochedVotingPowerExt,
 };
-use namada_ethereum_bridge::storage::bridge_pool::get_key_from_hash;
+use namada_ethereum_bridge::protocol::validation::validator_set_update::{
+    validate_unsigned_valset_upd_vext, validate_valset_upd_vext,
+};
+use namada_ethereum_bridge::protocol::validation::VoteExtensionError;
+use namada_ethereum_bridge::storage::bridge_pool::{
+    get_key_from_hash, get_nonce_key,
+};
 use namada_ethereum_bridge::storage::eth_bridge_queries::EthBridgeQueries;
-use namada_ethereum_bridge::storage::parameters::UpgradeableContract;
+use namada_ethereum_bridge::storage::parameters::{
+    RelayGasModel, UpgradeableContract,
+};
 use namada_ethereum_bridge::storage::proof::{sort_sigs, EthereumProof};
 use namada_ethereum_bridge::storage::vote_tallies::{eth_msgs_prefix, Keys};
 use namada_ethereum_bridge::storage::{
-    bridge_contract_key, native_erc20_key, vote_tallies,
+    active_key, bridge_contract_key, native_erc20_key, vote_tallies,
+    wrapped_erc20s,
 };
 use namada_proof_of_stake::pos_queries::PosQueries;
 use namada_state::MembershipProof::BridgePool;
-use namada_state::{DBIter, StorageHasher, StoreRef, StoreType, DB};
+use namada_state::{DBIter, StorageHasher, StoreRef, StoreType, WlStorage, DB};
 use namada_storage::{self, CustomError, ResultExt, StorageRead};
+use namada_token::storage_key::balance_key;
+use namada_tx::Signed;
 use namada_vote_ext::validator_set_update::{
-    ValidatorSetArgs, VotingPowersMap,
+    self, ValidatorSetArgs, VotingPowersMap,
 };
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 use crate::eth_bridge::ethers::abi::AbiDecode;
-use crate::events::EventType;
-use crate::queries::{EncodedResponseQuery, RequestCtx, RequestQuery};
+use crate::queries::{
+    require_no_proof, EncodedResponseQuery, RequestCtx, RequestQuery,
+};
 
 /// Container for the status of queried transfers to Ethereum.
 #[derive(
@@ -92,6 +108,44 @@ pub struct Erc20FlowControl {
     pub cap: Amount,
 }
 
+/// A preview of the validator set update that validators are expected to
+/// sign for some epoch, computed from the current state of PoS.
+///
+/// Previews of the next epoch are provisional, as the validator set may
+/// still change until the epoch boundary.
+#[derive(Debug, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct ValsetPreview {
+    /// The epoch of the previewed validator set.
+    pub epoch: Epoch,
+    /// The height of the last committed block, at which the preview was
+    /// computed.
+    pub height: BlockHeight,
+    /// Whether the preview may still change before the epoch boundary.
+    pub is_provisional: bool,
+    /// The voting powers of the previewed validator set.
+    pub voting_powers: VotingPowersMap,
+    /// The keccak digest signed by validators in their validator set
+    /// update vote extensions for the previewed epoch.
+    pub digest: KeccakHash,
+}
+
+/// The health status of the Ethereum bridge, as seen by the queried node.
+#[derive(Debug, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct BridgeHealthStatus {
+    /// Whether the Ethereum bridge is active.
+    pub is_active: bool,
+    /// The epoch of the last committed block.
+    pub epoch: Epoch,
+    /// The height of the last committed block.
+    pub height: BlockHeight,
+    /// Whether a complete proof of the validator set update for the next
+    /// epoch is available.
+    pub next_valset_upd_seen: bool,
+    /// The digest of the provisional validator set update for the next
+    /// epoch, if validator set previews are enabled on the queried node.
+    pub next_valset_preview_digest: Option<KeccakHash>,
+}
+
 impl Erc20FlowControl {
     /// Check if the `transferred_amount` exceeds the token caps of some ERC20
     /// asset.
@@ -101,6 +155,21 @@ impl Erc20FlowControl {
     }
 }
 
+/// Errors returned by the versioned Ethereum bridge queries, as part of
+/// their responses.
+#[derive(
+    Error, Debug, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize,
+)]
+pub enum EthBridgeQueryError {
+    /// There is no validator set update to the first epoch.
+    #[error("There is no validator set update to the first epoch")]
+    NoValsetUpdForFirstEpoch,
+    /// The Ethereum bridge storage key is missing, e.g. because the
+    /// bridge was never enabled.
+    #[error("The Ethereum bridge storage key {0} is not initialized")]
+    NotInitialized(String),
+}
+
 /// Request data to pass to `generate_bridge_pool_proof`.
 #[derive(Debug, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize)]
 pub struct GenBridgePoolProofReq<'transfers, 'relayer> {
@@ -112,6 +181,46 @@ pub struct GenBridgePoolProofReq<'transfers, 'relayer> {
     pub with_appendix: bool,
 }
 
+/// Request data to pass to `dry_run_valset_upd_vext`.
+#[derive(Debug, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct ValsetUpdVextDryRunReq {
+    /// The validator set update vote extension to validate.
+    pub vext: validator_set_update::Vext,
+    /// The signature over `vext`, issued with the validator's
+    /// Ethereum hot key. If absent, the signature verification
+    /// step is skipped.
+    pub sig: Option<common::Signature>,
+}
+
+/// The kind of proof whose relay gas is estimated by `estimate_relay_gas`.
+#[derive(Debug, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize)]
+pub enum RelayProofKind {
+    /// The proof of the validator set update to the given epoch.
+    ValidatorSetUpdate(Epoch),
+    /// A Bridge pool batch of the transfers with the given hashes, covered
+    /// by the latest signed Bridge pool root.
+    BridgePool(Vec<KeccakHash>),
+}
+
+/// Response data returned by `estimate_relay_gas`.
+///
+/// Along with the estimate, the raw inputs of the model are returned, such
+/// that relayers may apply their own model.
+#[derive(Debug, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct RelayGasEstimate {
+    /// The estimated Ethereum gas cost of relaying the proof.
+    pub gas: u64,
+    /// The number of signatures in the stored proof.
+    pub signatures: u64,
+    /// The number of relayed transfers, which is zero for validator set
+    /// updates.
+    pub transfers: u64,
+    /// The size in bytes of the ABI encoded arguments of the relay call.
+    pub calldata_size: u64,
+    /// The model of the relay gas cost the estimate was derived from.
+    pub model: RelayGasModel,
+}
+
 /// Arguments to pass to `transfer_to_erc`.
 pub type TransferToErcArgs = (
     ethereum_structs::ValidatorSetArgs,
@@ -126,6 +235,13 @@ pub struct GenBridgePoolProofRsp {
     pub abi_encoded_args: Vec<u8>,
     /// Appendix data of all requested pending transfers.
     pub appendices: Option<Vec<PendingTransferAppendix<'static>>>,
+    /// The height at which the signed Bridge pool root was computed.
+    /// Relayers must not relay a root older than the last relayed one.
+    pub root_height: BlockHeight,
+    /// Whether the root height is bound to the signatures of the root,
+    /// such that the bridge contract can enforce the monotonicity of the
+    /// relayed roots itself.
+    pub root_height_bound: bool,
 }
 
 impl GenBridgePoolProofRsp {
@@ -172,9 +288,39 @@ router! {ETH_BRIDGE,
     // the given epoch.
     //
     // The request may fail if a proof is not considered complete yet.
+    // The proof is read from the state at the height of the request.
     ( "validator_set" / "proof" / [epoch: Epoch] )
         -> EncodeCell<EthereumProof<(Epoch, VotingPowersMap)>>
-        = read_valset_upd_proof,
+        = (with_options read_valset_upd_proof),
+
+    // Run all the validation checks of a validator set update vote
+    // extension against the current state, without gossiping it.
+    //
+    // The request data must be a borsh encoded `ValsetUpdVextDryRunReq`.
+    ( "validator_set" / "dry_run" )
+        -> Result<(), VoteExtensionError>
+        = (with_options dry_run_valset_upd_vext),
+
+    // Preview the validator set update that validators are expected to
+    // sign for the given epoch, along with its digest, such that operators
+    // can compare it across nodes before the epoch begins.
+    //
+    // The request fails unless validator set previews are enabled in the
+    // config of the queried node, or if the epoch is past the next one.
+    ( "preview_valset" / [epoch: Epoch] )
+        -> ValsetPreview = preview_valset,
+
+    // Estimate the Ethereum gas cost of relaying a validator set update
+    // or a Bridge pool batch, with the model calibrated by governance.
+    //
+    // The request data must be a borsh encoded `RelayProofKind`. The
+    // request fails if the requested proof is not available.
+    ( "estimate_relay_gas" )
+        -> RelayGasEstimate = (with_options estimate_relay_gas),
+
+    // Read the health status of the Ethereum bridge.
+    ( "status" )
+        -> BridgeHealthStatus = read_bridge_health_status,
 
     // Request the set of bridge validators at the given epoch.
     //
@@ -212,6 +358,40 @@ router! {ETH_BRIDGE,
     // ERC20 token in Namada.
     ( "erc20" / "flow_control" / [asset: EthAddress] )
         -> Erc20FlowControl = get_erc20_flow_control,
+
+    // Read the minted supply of some wrapped ERC20 token in Namada,
+    // which is zero if it was never minted.
+    ( "wrapped_supply" / [asset: EthAddress] )
+        -> Amount = read_wrapped_supply,
+
+    // Queries whose paths and responses are versioned, such that clients
+    // don't depend on the storage layout of the Ethereum bridge.
+    ( "v1" ) = (sub ETH_BRIDGE_V1),
+}
+
+// Version 1 of the Ethereum bridge queries. Every response is a
+// `Result` whose error is an `EthBridgeQueryError`.
+//
+// Changes to the paths, arguments or responses of these queries must go to
+// a new version, leaving this one as is.
+router! {ETH_BRIDGE_V1,
+    // Check if the proof of the validator set update to the given epoch
+    // is complete.
+    ( "valset_upd_seen" / [epoch: Epoch] )
+        -> Result<bool, EthBridgeQueryError> = valset_upd_seen,
+
+    // Read the nonce of the next Bridge pool root.
+    ( "bridge_pool_nonce" )
+        -> Result<Uint, EthBridgeQueryError> = read_bridge_pool_nonce,
+
+    // Check if the Ethereum bridge is active at the current epoch.
+    ( "active" )
+        -> Result<bool, EthBridgeQueryError> = bridge_active,
+
+    // Read the balance of some wrapped ERC20 token held by the given
+    // owner, which is zero if the owner never held it.
+    ( "wrapped_erc20_balance" / [owner: Address] / [asset: EthAddress] )
+        -> Result<Amount, EthBridgeQueryError> = wrapped_erc20_balance,
 }
 
 /// Given a list of keccak hashes, check whether they have been
@@ -281,25 +461,14 @@ where
     // INVARIANT: transfers that are in the event log will have already
     // been processed and therefore removed from the Bridge pool at the
     // time of this query
-    let kind_key: String = "kind".into();
     let completed_transfers = ctx.event_log.iter().filter_map(|ev| {
-        if !matches!(&ev.event_type, EventType::EthereumBridge) {
+        // skip the events of other types
+        let Ok(EthBridgeEvent::BridgePool { tx_hash, status }) =
+            EthBridgeEvent::try_from(ev)
+        else {
             return None;
-        }
-        let eth_event_kind =
-            ev.attributes.get(&kind_key).map(|k| k.as_str())?;
-        let is_relayed = match eth_event_kind {
-            "bridge_pool_relayed" => true,
-            "bridge_pool_expired" => false,
-            _ => return None,
         };
-        let tx_hash: KeccakHash = ev
-            .attributes
-            .get("tx_hash")
-            .expect("The transfer hash must be available")
-            .as_str()
-            .try_into()
-            .expect("We must have a valid KeccakHash");
+        let is_relayed = matches!(status, BpTransferStatus::Relayed);
         if !transfer_hashes.remove(&tx_hash) {
             return None;
         }
@@ -356,6 +525,88 @@ where
     })
 }
 
+/// Read the minted supply of some wrapped ERC20 token in Namada.
+fn read_wrapped_supply<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    asset: EthAddress,
+) -> namada_storage::Result<Amount>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    Ok(ctx
+        .wl_storage
+        .ethbridge_queries()
+        .get_token_supply(&asset)
+        .unwrap_or_default())
+}
+
+/// Check if the proof of the validator set update to the given epoch is
+/// complete.
+fn valset_upd_seen<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    epoch: Epoch,
+) -> namada_storage::Result<Result<bool, EthBridgeQueryError>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    if epoch.0 == 0 {
+        return Ok(Err(EthBridgeQueryError::NoValsetUpdForFirstEpoch));
+    }
+    let seen_key = vote_tallies::Keys::from(&epoch).seen();
+    let seen = StorageRead::read(ctx.wl_storage, &seen_key)?;
+    Ok(Ok(seen.unwrap_or(false)))
+}
+
+/// Read the nonce of the next Bridge pool root.
+fn read_bridge_pool_nonce<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<Result<Uint, EthBridgeQueryError>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let nonce_key = get_nonce_key();
+    let nonce = StorageRead::read(ctx.wl_storage, &nonce_key)?;
+    Ok(nonce.ok_or_else(|| {
+        EthBridgeQueryError::NotInitialized(nonce_key.to_string())
+    }))
+}
+
+/// Check if the Ethereum bridge is active at the current epoch.
+fn bridge_active<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<Result<bool, EthBridgeQueryError>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let active_key = active_key();
+    if !StorageRead::has_key(ctx.wl_storage, &active_key)? {
+        return Ok(Err(EthBridgeQueryError::NotInitialized(
+            active_key.to_string(),
+        )));
+    }
+    Ok(Ok(ctx.wl_storage.ethbridge_queries().is_bridge_active()))
+}
+
+/// Read the balance of some wrapped ERC20 token held by the given owner.
+fn wrapped_erc20_balance<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    owner: Address,
+    asset: EthAddress,
+) -> namada_storage::Result<Result<Amount, EthBridgeQueryError>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let token = wrapped_erc20s::token(&asset);
+    let balance_key = balance_key(&token, &owner);
+    let balance = StorageRead::read(ctx.wl_storage, &balance_key)?;
+    Ok(Ok(balance.unwrap_or_default()))
+}
+
 /// Helper function to read a smart contract from storage.
 fn read_contract<T, D, H, V, U>(
     key: &Key,
@@ -368,8 +619,8 @@ where
 {
     let Some(contract) = StorageRead::read(ctx.wl_storage, key)? else {
         return Err(namada_storage::Error::SimpleMessage(
-            "Failed to read contract: The Ethereum bridge \
-             storage is not initialized",
+            "Failed to read contract: The Ethereum bridge storage is not \
+             initialized",
         ));
     };
     Ok(contract)
@@ -491,119 +742,138 @@ where
         with_appendix,
     }) = BorshDeserialize::try_from_slice(&request.data)
     {
-        // get the latest signed merkle root of the Ethereum bridge pool
-        let (signed_root, height) = ctx
-            .wl_storage
-            .ethbridge_queries()
-            .get_signed_bridge_pool_root()
-            .ok_or(namada_storage::Error::SimpleMessage(
-                "No signed root for the Ethereum bridge pool exists in \
-                 storage.",
-            ))
-            .into_storage_result()?;
+        let rsp = bridge_pool_proof(
+            ctx.wl_storage,
+            &transfer_hashes,
+            &relayer,
+            with_appendix,
+        )?;
+        Ok(EncodedResponseQuery {
+            data: rsp.serialize_to_vec(),
+            ..Default::default()
+        })
+    } else {
+        Err(namada_storage::Error::SimpleMessage(
+            "Could not deserialize transfers",
+        ))
+    }
+}
 
-        // make sure a relay attempt won't happen before the new signed
-        // root has had time to be generated
-        let latest_bp_nonce =
-            ctx.wl_storage.ethbridge_queries().get_bridge_pool_nonce();
-        if latest_bp_nonce != signed_root.data.1 {
-            return Err(namada_storage::Error::Custom(CustomError(
-                format!(
-                    "Mismatch between the nonce in the Bridge pool root proof \
-                     ({}) and the latest Bridge pool nonce in storage ({})",
-                    signed_root.data.1, latest_bp_nonce,
-                )
-                .into(),
-            )));
-        }
+/// Build the arguments to relay the requested transfers of the Ethereum
+/// bridge pool, covered by its latest signed root.
+fn bridge_pool_proof<D, H>(
+    wl_storage: &WlStorage<D, H>,
+    transfer_hashes: &[KeccakHash],
+    relayer: &Address,
+    with_appendix: bool,
+) -> namada_storage::Result<GenBridgePoolProofRsp>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    // get the latest signed merkle root of the Ethereum bridge pool
+    let (signed_root, height) = wl_storage
+        .ethbridge_queries()
+        .get_signed_bridge_pool_root()
+        .ok_or(namada_storage::Error::SimpleMessage(
+            "No signed root for the Ethereum bridge pool exists in storage.",
+        ))
+        .into_storage_result()?;
 
-        // get the merkle tree corresponding to the above root.
-        let tree = ctx
-            .wl_storage
-            .storage
-            .get_merkle_tree(height, Some(StoreType::BridgePool))
-            .into_storage_result()?;
-        // from the hashes of the transfers, get the actual values.
-        let mut missing_hashes = vec![];
-        let (keys, values): (Vec<_>, Vec<Vec<u8>>) = transfer_hashes
-            .iter()
-            .filter_map(|hash| {
-                let key = get_key_from_hash(hash);
-                match ctx.wl_storage.read_bytes(&key) {
-                    Ok(Some(bytes)) => Some((key, bytes)),
-                    _ => {
-                        missing_hashes.push(hash);
-                        None
-                    }
-                }
-            })
-            .unzip();
-        if !missing_hashes.is_empty() {
-            return Err(namada_storage::Error::Custom(CustomError(
-                format!(
-                    "One or more of the provided hashes had no corresponding \
-                     transfer in storage: {:?}",
-                    missing_hashes
-                )
-                .into(),
-            )));
-        }
-        let (transfers, appendices) = values.iter().fold(
-            (vec![], vec![]),
-            |(mut transfers, mut appendices), bytes| {
-                let pending = PendingTransfer::try_from_slice(bytes)
-                    .expect("Deserializing storage shouldn't fail");
-                let eth_transfer = (&pending).into();
-                if with_appendix {
-                    appendices.push(pending.into_appendix());
+    // make sure a relay attempt won't happen before the new signed
+    // root has had time to be generated
+    let latest_bp_nonce =
+        wl_storage.ethbridge_queries().get_bridge_pool_nonce();
+    if latest_bp_nonce != signed_root.data.1 {
+        return Err(namada_storage::Error::Custom(CustomError(
+            format!(
+                "Mismatch between the nonce in the Bridge pool root proof \
+                 ({}) and the latest Bridge pool nonce in storage ({})",
+                signed_root.data.1, latest_bp_nonce,
+            )
+            .into(),
+        )));
+    }
+
+    // get the merkle tree corresponding to the above root.
+    let tree = wl_storage
+        .storage
+        .get_merkle_tree(height, Some(StoreType::BridgePool))
+        .into_storage_result()?;
+    // from the hashes of the transfers, get the actual values.
+    let mut missing_hashes = vec![];
+    let (keys, values): (Vec<_>, Vec<Vec<u8>>) = transfer_hashes
+        .iter()
+        .filter_map(|hash| {
+            let key = get_key_from_hash(hash);
+            match wl_storage.read_bytes(&key) {
+                Ok(Some(bytes)) => Some((key, bytes)),
+                _ => {
+                    missing_hashes.push(hash);
+                    None
                 }
-                transfers.push(eth_transfer);
-                (transfers, appendices)
-            },
-        );
-        // get the membership proof
-        match tree.get_sub_tree_existence_proof(
-            &keys,
-            values.iter().map(|v| v.as_slice()).collect(),
-        ) {
-            Ok(BridgePool(proof)) => {
-                let (validator_args, voting_powers) = ctx
-                    .wl_storage
-                    .ethbridge_queries()
-                    .get_bridge_validator_set(None);
-                let relay_proof = ethereum_structs::RelayProof {
-                    transfers,
-                    pool_root: signed_root.data.0.0,
-                    proof: proof.proof.into_iter().map(|hash| hash.0).collect(),
-                    proof_flags: proof.flags,
-                    batch_nonce: signed_root.data.1.into(),
-                    relayer_address: relayer.to_string(),
-                };
-                let validator_set: ethereum_structs::ValidatorSetArgs =
-                    validator_args.into();
-                let signatures =
-                    sort_sigs(&voting_powers, &signed_root.signatures);
-                let rsp = GenBridgePoolProofRsp {
-                    abi_encoded_args: ethers::abi::AbiEncode::encode((
-                        validator_set,
-                        signatures,
-                        relay_proof,
-                    )),
-                    appendices: with_appendix.then_some(appendices),
-                };
-                let data = rsp.serialize_to_vec();
-                Ok(EncodedResponseQuery {
-                    data,
-                    ..Default::default()
-                })
             }
-            Ok(_) => unreachable!(),
-            Err(e) => Err(namada_storage::Error::new(e)),
+        })
+        .unzip();
+    if !missing_hashes.is_empty() {
+        return Err(namada_storage::Error::Custom(CustomError(
+            format!(
+                "One or more of the provided hashes had no corresponding \
+                 transfer in storage: {:?}",
+                missing_hashes
+            )
+            .into(),
+        )));
+    }
+    let (transfers, appendices) = values.iter().fold(
+        (vec![], vec![]),
+        |(mut transfers, mut appendices), bytes| {
+            let pending = PendingTransfer::try_from_slice(bytes)
+                .expect("Deserializing storage shouldn't fail");
+            let eth_transfer = (&pending).into();
+            if with_appendix {
+                appendices.push(pending.into_appendix());
+            }
+            transfers.push(eth_transfer);
+            (transfers, appendices)
+        },
+    );
+    // get the membership proof
+    match tree.get_sub_tree_existence_proof(
+        &keys,
+        values.iter().map(|v| v.as_slice()).collect(),
+    ) {
+        Ok(BridgePool(proof)) => {
+            let (validator_args, voting_powers) = wl_storage
+                .ethbridge_queries()
+                .get_bridge_validator_set(None);
+            let relay_proof = ethereum_structs::RelayProof {
+                transfers,
+                pool_root: signed_root.data.0.0,
+                proof: proof.proof.into_iter().map(|hash| hash.0).collect(),
+                proof_flags: proof.flags,
+                batch_nonce: signed_root.data.1.into(),
+                relayer_address: relayer.to_string(),
+            };
+            let validator_set: ethereum_structs::ValidatorSetArgs =
+                validator_args.into();
+            let signatures = sort_sigs(&voting_powers, &signed_root.signatures);
+            let rsp = GenBridgePoolProofRsp {
+                abi_encoded_args: ethers::abi::AbiEncode::encode((
+                    validator_set,
+                    signatures,
+                    relay_proof,
+                )),
+                appendices: with_appendix.then_some(appendices),
+                root_height: height,
+                root_height_bound: wl_storage
+                    .ethbridge_queries()
+                    .is_bp_root_height_bound_at(height),
+            };
+            Ok(rsp)
         }
-    } else {
-        Err(namada_storage::Error::SimpleMessage(
-            "Could not deserialize transfers",
-        ))
+        Ok(_) => unreachable!(),
+        Err(e) => Err(namada_storage::Error::new(e)),
     }
 }
 
@@ -686,11 +956,30 @@ where
 /// 2/3 of the total voting power behind it) is not available yet.
 fn read_valset_upd_proof<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
+    request: &RequestQuery,
     epoch: Epoch,
-) -> namada_storage::Result<EncodeCell<EthereumProof<(Epoch, VotingPowersMap)>>>
+) -> namada_storage::Result<EncodedResponseQuery>
 where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
     H: 'static + StorageHasher + Sync,
+{
+    require_no_proof(request)?;
+    let state = ctx.state_at_height(request)?;
+    let proof = read_valset_upd_proof_at(&state, epoch)?;
+    Ok(EncodedResponseQuery {
+        data: proof.serialize_to_vec(),
+        ..Default::default()
+    })
+}
+
+/// Read the proof of the validator set update to the given [`Epoch`] from
+/// the given state.
+fn read_valset_upd_proof_at<S>(
+    state: &S,
+    epoch: Epoch,
+) -> namada_storage::Result<EncodeCell<EthereumProof<(Epoch, VotingPowersMap)>>>
+where
+    S: StorageRead,
 {
     if epoch.0 == 0 {
         return Err(namada_storage::Error::Custom(CustomError(
@@ -699,7 +988,7 @@ where
                 .into(),
         )));
     }
-    let current_epoch = ctx.wl_storage.storage.last_epoch;
+    let current_epoch = state.get_block_epoch()?;
     if epoch > current_epoch.next() {
         return Err(namada_storage::Error::Custom(CustomError(
             format!(
@@ -710,7 +999,9 @@ where
         )));
     }
 
-    if !ctx.wl_storage.ethbridge_queries().valset_upd_seen(epoch) {
+    let valset_upd_keys = vote_tallies::Keys::from(&epoch);
+    let seen: bool = state.read(&valset_upd_keys.seen())?.unwrap_or(false);
+    if !seen {
         return Err(namada_storage::Error::Custom(CustomError(
             format!(
                 "Validator set update proof is not yet available for the \
@@ -720,16 +1011,220 @@ where
         )));
     }
 
-    let valset_upd_keys = vote_tallies::Keys::from(&epoch);
-    let proof: EthereumProof<VotingPowersMap> =
-        StorageRead::read(ctx.wl_storage, &valset_upd_keys.body())?.expect(
-            "EthereumProof is seen in storage, therefore it must exist",
-        );
+    let proof: EthereumProof<VotingPowersMap> = state
+        .read(&valset_upd_keys.body())?
+        .expect("EthereumProof is seen in storage, therefore it must exist");
 
     // NOTE: we pass the epoch of the new set of validators
     Ok(proof.map(|set| (epoch, set)).encode())
 }
 
+/// Estimate the Ethereum gas cost of relaying a proof to the bridge
+/// contracts, from the number of signatures in the stored proof and the
+/// number of relayed transfers.
+fn estimate_relay_gas<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    request: &RequestQuery,
+) -> namada_storage::Result<EncodedResponseQuery>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let kind = RelayProofKind::try_from_slice(&request.data).map_err(|_| {
+        namada_storage::Error::SimpleMessage(
+            "Could not deserialize the kind of the relayed proof",
+        )
+    })?;
+    let wl_storage = ctx.wl_storage;
+    let (signatures, transfers, calldata_size) = match kind {
+        RelayProofKind::ValidatorSetUpdate(epoch) => {
+            let calldata =
+                read_valset_upd_proof_at(wl_storage, epoch)?.into_inner();
+            let proof: EthereumProof<VotingPowersMap> = StorageRead::read(
+                wl_storage,
+                &vote_tallies::Keys::from(&epoch).body(),
+            )?
+            .expect(
+                "EthereumProof is seen in storage, therefore it must exist",
+            );
+            (proof.signatures.len(), 0, calldata.len())
+        }
+        RelayProofKind::BridgePool(transfer_hashes) => {
+            // the relayer is encoded as a string of the same length for
+            // all addresses, so it doesn't affect the size of the calldata
+            let rsp = bridge_pool_proof(
+                wl_storage,
+                &transfer_hashes,
+                &BRIDGE_POOL_ADDRESS,
+                false,
+            )?;
+            let (signed_root, _) = wl_storage
+                .ethbridge_queries()
+                .get_signed_bridge_pool_root()
+                .expect("The Bridge pool proof was built from a signed root");
+            (
+                signed_root.signatures.len(),
+                transfer_hashes.len(),
+                rsp.abi_encoded_args.len(),
+            )
+        }
+    };
+    let model = wl_storage.ethbridge_queries().get_relay_gas_model();
+    let signatures = signatures as u64;
+    let transfers = transfers as u64;
+    let rsp = RelayGasEstimate {
+        gas: model.estimate(signatures, transfers),
+        signatures,
+        transfers,
+        calldata_size: calldata_size as u64,
+        model,
+    };
+    Ok(EncodedResponseQuery {
+        data: rsp.serialize_to_vec(),
+        ..Default::default()
+    })
+}
+
+/// Validate a validator set update vote extension against the current
+/// state, returning the precise error it would be rejected with, if any.
+///
+/// The signature verification step is skipped, if no signature is
+/// included in the request.
+fn dry_run_valset_upd_vext<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    request: &RequestQuery,
+) -> namada_storage::Result<EncodedResponseQuery>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let ValsetUpdVextDryRunReq { vext, sig } =
+        BorshDeserialize::try_from_slice(&request.data).map_err(|_| {
+            namada_storage::Error::SimpleMessage(
+                "Could not deserialize validator set update vote extension",
+            )
+        })?;
+    let last_epoch = ctx.wl_storage.storage.get_current_epoch().0;
+    let result = match sig {
+        Some(sig) => validate_valset_upd_vext(
+            ctx.wl_storage,
+            &validator_set_update::SignedVext(Signed::new_from(vext, sig)),
+            last_epoch,
+        ),
+        None => {
+            validate_unsigned_valset_upd_vext(ctx.wl_storage, &vext, last_epoch)
+        }
+    };
+    Ok(EncodedResponseQuery {
+        data: result.serialize_to_vec(),
+        ..Default::default()
+    })
+}
+
+/// Compute a preview of the validator set update of the given epoch, from
+/// the current state of PoS.
+fn compute_valset_preview<D, H>(
+    wl_storage: &WlStorage<D, H>,
+    epoch: Epoch,
+) -> ValsetPreview
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let voting_powers: VotingPowersMap = wl_storage
+        .ethbridge_queries()
+        .get_consensus_eth_addresses(Some(epoch))
+        .iter()
+        .map(|(eth_addr_book, _, voting_power)| (eth_addr_book, voting_power))
+        .collect();
+    // validator set updates are signed over during the preceding epoch
+    let signing_epoch = epoch.prev();
+    let digest = match wl_storage
+        .ethbridge_queries()
+        .valset_upd_signing_domain(signing_epoch)
+    {
+        Some(chain_id) => {
+            validator_set_update::compute_domain_separated_digest(
+                &voting_powers,
+                signing_epoch,
+                &chain_id,
+            )
+        }
+        None => validator_set_update::compute_signable_digest(
+            &voting_powers,
+            signing_epoch,
+        ),
+    };
+    ValsetPreview {
+        epoch,
+        height: wl_storage.storage.get_last_block_height(),
+        is_provisional: epoch > wl_storage.storage.get_current_epoch().0,
+        voting_powers,
+        digest,
+    }
+}
+
+/// Preview the validator set update of the given epoch.
+///
+/// This method fails unless validator set previews are enabled in
+/// the config of the node, or if the epoch is past the next one.
+fn preview_valset<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    epoch: Epoch,
+) -> namada_storage::Result<ValsetPreview>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    if !ctx.enable_valset_preview {
+        return Err(namada_storage::Error::SimpleMessage(
+            "Validator set previews are disabled on this node",
+        ));
+    }
+    if epoch.0 == 0 {
+        return Err(namada_storage::Error::SimpleMessage(
+            "There are no validator set updates for the first epoch",
+        ));
+    }
+    let current_epoch = ctx.wl_storage.storage.get_current_epoch().0;
+    if epoch > current_epoch.next() {
+        return Err(namada_storage::Error::Custom(CustomError(
+            format!(
+                "Requesting a validator set preview for {epoch:?}, but only \
+                 epochs up to {:?} can be previewed",
+                current_epoch.next()
+            )
+            .into(),
+        )));
+    }
+    Ok(compute_valset_preview(ctx.wl_storage, epoch))
+}
+
+/// Read the health status of the Ethereum bridge.
+fn read_bridge_health_status<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<BridgeHealthStatus>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let epoch = ctx.wl_storage.storage.get_current_epoch().0;
+    let next_epoch = epoch.next();
+    let next_valset_preview_digest = ctx
+        .enable_valset_preview
+        .then(|| compute_valset_preview(ctx.wl_storage, next_epoch).digest);
+    Ok(BridgeHealthStatus {
+        is_active: ctx.wl_storage.ethbridge_queries().is_bridge_active(),
+        epoch,
+        height: ctx.wl_storage.storage.get_last_block_height(),
+        next_valset_upd_seen: ctx
+            .wl_storage
+            .ethbridge_queries()
+            .valset_upd_seen(next_epoch),
+        next_valset_preview_digest,
+    })
+}
+
 /// Request the set of bridge validators at the given epoch.
 ///
 /// This method may fail if no set of validators exists yet,
@@ -844,18 +1339,24 @@ mod test_ethbridge_router {
         GasFee, PendingTransfer, TransferToEthereum, TransferToEthereumKind,
     };
     use namada_core::types::ethereum_events::EthAddress;
+    use namada_core::types::key::Signable;
     use namada_core::types::storage::BlockHeight;
     use namada_core::types::voting_power::{
         EthBridgeVotingPower, FractionalVotingPower,
     };
     use namada_ethereum_bridge::protocol::transactions::validator_set_update::aggregate_votes;
+    use namada_ethereum_bridge::protocol::validation::{
+        ExtensionKind, InvalidVextReason,
+    };
     use namada_ethereum_bridge::storage::bridge_pool::{
         get_pending_key, get_signed_root_key, BridgePoolTree,
     };
+    use namada_ethereum_bridge::storage::parameters::set_relay_gas_model;
     use namada_ethereum_bridge::storage::proof::BridgePoolRootProof;
-    use namada_ethereum_bridge::storage::whitelist;
+    use namada_ethereum_bridge::storage::{whitelist, wrapped_erc20s};
     use namada_proof_of_stake::pos_queries::PosQueries;
     use namada_state::mockdb::MockDBWriteBatch;
+    use namada_state::testing::TestWlStorage;
     use namada_storage::StorageWrite;
     use namada_vote_ext::validator_set_update;
     use namada_vote_ext::validator_set_update::{
@@ -1001,9 +1502,10 @@ mod test_ethbridge_router {
         let proof = RPC
             .shell()
             .eth_bridge()
-            .read_valset_upd_proof(&client, &Epoch(1))
+            .read_valset_upd_proof(&client, None, None, false, &Epoch(1))
             .await
-            .unwrap();
+            .unwrap()
+            .data;
         let expected = {
             let mut proof =
                 EthereumProof::new((1.into(), vext.0.data.voting_powers));
@@ -1021,6 +1523,175 @@ mod test_ethbridge_router {
         assert_eq!(proof, expected);
     }
 
+    /// Test that the relay gas of a validator set update is estimated from
+    /// the signatures of its stored proof, with the model calibrated by
+    /// governance, and that proofs that aren't available can't be estimated
+    #[tokio::test]
+    async fn test_estimate_valset_upd_relay_gas() {
+        let mut client = TestClient::new(RPC);
+        let keys = test_utils::init_default_storage(&mut client.wl_storage);
+        let vext = validator_set_update::Vext {
+            voting_powers: VotingPowersMap::new(),
+            validator_addr: established_address_1(),
+            signing_epoch: 0.into(),
+        }
+        .sign(
+            &keys
+                .get(&established_address_1())
+                .expect("Test failed")
+                .eth_bridge,
+        );
+        aggregate_votes(
+            &mut client.wl_storage,
+            validator_set_update::VextDigest::singleton(vext),
+            0.into(),
+        )
+        .expect("Test failed");
+        client
+            .wl_storage
+            .storage
+            .commit_block(MockDBWriteBatch)
+            .expect("Test failed");
+
+        let request = |epoch: u64| {
+            Some(
+                RelayProofKind::ValidatorSetUpdate(Epoch(epoch))
+                    .serialize_to_vec(),
+            )
+        };
+        let calldata_size = RPC
+            .shell()
+            .eth_bridge()
+            .read_valset_upd_proof(&client, None, None, false, &Epoch(1))
+            .await
+            .unwrap()
+            .data
+            .into_inner()
+            .len() as u64;
+
+        let rsp = RPC
+            .shell()
+            .eth_bridge()
+            .estimate_relay_gas(&client, request(1), None, false)
+            .await
+            .unwrap()
+            .data;
+        let model = RelayGasModel::default();
+        assert_eq!(
+            rsp,
+            RelayGasEstimate {
+                gas: model.estimate(1, 0),
+                signatures: 1,
+                transfers: 0,
+                calldata_size,
+                model,
+            }
+        );
+
+        // governance calibrates the model
+        let model = RelayGasModel {
+            base_gas: 1,
+            gas_per_signature: 10,
+            gas_per_transfer: 100,
+        };
+        set_relay_gas_model(&mut client.wl_storage, &model)
+            .expect("Test failed");
+        let rsp = RPC
+            .shell()
+            .eth_bridge()
+            .estimate_relay_gas(&client, request(1), None, false)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(rsp.gas, 11);
+        assert_eq!(rsp.model, model);
+
+        // no proof exists for the next epochs
+        let result = RPC
+            .shell()
+            .eth_bridge()
+            .estimate_relay_gas(&client, request(2), None, false)
+            .await;
+        assert!(result.is_err());
+    }
+
+    /// Test that validator set previews are only served when enabled, for
+    /// epochs up to the next one, and that their digest is the one signed
+    /// over by validators.
+    #[tokio::test]
+    async fn test_preview_valset() {
+        let mut client = TestClient::new(RPC);
+        test_utils::init_default_storage(&mut client.wl_storage);
+        client
+            .wl_storage
+            .storage
+            .commit_block(MockDBWriteBatch)
+            .expect("Test failed");
+
+        // previews are disabled by default
+        let result = RPC
+            .shell()
+            .eth_bridge()
+            .preview_valset(&client, &Epoch(1))
+            .await;
+        assert!(result.unwrap_err().to_string().contains("disabled"));
+        let status = RPC
+            .shell()
+            .eth_bridge()
+            .read_bridge_health_status(&client)
+            .await
+            .unwrap();
+        assert_eq!(status.next_valset_preview_digest, None);
+
+        client.enable_valset_preview = true;
+        let preview = RPC
+            .shell()
+            .eth_bridge()
+            .preview_valset(&client, &Epoch(1))
+            .await
+            .unwrap();
+        assert_eq!(preview.epoch, Epoch(1));
+        assert!(preview.is_provisional);
+        assert_eq!(
+            preview.voting_powers,
+            client
+                .wl_storage
+                .ethbridge_queries()
+                .get_bridge_validator_set(Some(Epoch(1)))
+                .1
+        );
+        // the digest is the one signed over by validators, in their
+        // vote extensions issued at the preceding epoch
+        let vext = validator_set_update::Vext {
+            voting_powers: preview.voting_powers.clone(),
+            validator_addr: established_address_1(),
+            signing_epoch: 0.into(),
+        };
+        assert_eq!(
+            preview.digest,
+            validator_set_update::SerializeWithAbiEncode::as_signable(&vext)
+        );
+
+        // the digest of the next epoch is reported in the bridge status
+        let status = RPC
+            .shell()
+            .eth_bridge()
+            .read_bridge_health_status(&client)
+            .await
+            .unwrap();
+        assert_eq!(status.next_valset_preview_digest, Some(preview.digest));
+
+        // epochs past the next one can't be previewed
+        for epoch in [Epoch(0), Epoch(2)] {
+            let result = RPC
+                .shell()
+                .eth_bridge()
+                .preview_valset(&client, &epoch)
+                .await;
+            assert!(result.is_err());
+        }
+    }
+
     /// Test that when reading a validator set proof too far ahead,
     /// RPC clients are met with an error.
     #[tokio::test]
@@ -1042,7 +1713,7 @@ mod test_ethbridge_router {
         let result = RPC
             .shell()
             .eth_bridge()
-            .read_valset_upd_proof(&client, &Epoch(999_999))
+            .read_valset_upd_proof(&client, None, None, false, &Epoch(999_999))
             .await;
         let Err(err) = result else {
             panic!("Test failed");
@@ -1055,6 +1726,129 @@ mod test_ethbridge_router {
         );
     }
 
+    /// Build a validator set update vote extension for the
+    /// next epoch, with the voting powers found in storage.
+    fn valset_upd_vext(
+        wl_storage: &TestWlStorage,
+    ) -> validator_set_update::Vext {
+        let voting_powers = wl_storage
+            .ethbridge_queries()
+            .get_consensus_eth_addresses(Some(1.into()))
+            .iter()
+            .map(|(addr_book, _, power)| (addr_book, power))
+            .collect();
+        validator_set_update::Vext {
+            voting_powers,
+            validator_addr: established_address_1(),
+            signing_epoch: 0.into(),
+        }
+    }
+
+    /// Test that dry running the validation of a valid validator set
+    /// update vote extension succeeds, with or without a signature.
+    #[tokio::test]
+    async fn test_dry_run_valset_upd_vext() {
+        let mut client = TestClient::new(RPC);
+        let keys = test_utils::init_default_storage(&mut client.wl_storage);
+        client
+            .wl_storage
+            .storage
+            .commit_block(MockDBWriteBatch)
+            .expect("Test failed");
+
+        let vext = valset_upd_vext(&client.wl_storage);
+        let signed = vext.sign(
+            &keys
+                .get(&established_address_1())
+                .expect("Test failed")
+                .eth_bridge,
+        );
+
+        for sig in [None, Some(signed.0.sig)] {
+            let result = RPC
+                .shell()
+                .eth_bridge()
+                .dry_run_valset_upd_vext(
+                    &client,
+                    Some(
+                        ValsetUpdVextDryRunReq {
+                            vext: vext.clone(),
+                            sig,
+                        }
+                        .serialize_to_vec(),
+                    ),
+                    None,
+                    false,
+                )
+                .await
+                .unwrap();
+            assert_eq!(result.data, Ok(()));
+        }
+    }
+
+    /// Test that dry running the validation of a faulty validator set
+    /// update vote extension returns the precise validation error.
+    #[tokio::test]
+    async fn test_dry_run_valset_upd_vext_errors() {
+        let mut client = TestClient::new(RPC);
+        let keys = test_utils::init_default_storage(&mut client.wl_storage);
+        client
+            .wl_storage
+            .storage
+            .commit_block(MockDBWriteBatch)
+            .expect("Test failed");
+
+        let dry_run = |vext: validator_set_update::Vext, sig| {
+            let data = ValsetUpdVextDryRunReq { vext, sig }.serialize_to_vec();
+            let client = &client;
+            async move {
+                RPC.shell()
+                    .eth_bridge()
+                    .dry_run_valset_upd_vext(client, Some(data), None, false)
+                    .await
+                    .unwrap()
+                    .data
+            }
+        };
+
+        // the extension is signed over a future epoch
+        let mut vext = valset_upd_vext(&client.wl_storage);
+        vext.signing_epoch = 1.into();
+        assert_eq!(
+            dry_run(vext, None).await,
+            Err(InvalidVextReason::UnexpectedEpoch
+                .of(ExtensionKind::ValidatorSetUpdate))
+        );
+
+        // the voting powers diverge from storage
+        let mut vext = valset_upd_vext(&client.wl_storage);
+        for power in vext.voting_powers.values_mut() {
+            *power = *power + Amount::native_whole(1);
+        }
+        assert_eq!(
+            dry_run(vext, None).await,
+            Err(InvalidVextReason::DivergesFromStorage
+                .of(ExtensionKind::ValidatorSetUpdate))
+        );
+
+        // the signature was issued with the wrong key
+        let vext = valset_upd_vext(&client.wl_storage);
+        let bad_sig = vext
+            .sign(
+                &keys
+                    .get(&established_address_1())
+                    .expect("Test failed")
+                    .eth_gov,
+            )
+            .0
+            .sig;
+        assert_eq!(
+            dry_run(vext, Some(bad_sig)).await,
+            Err(InvalidVextReason::VerifySigFailed
+                .of(ExtensionKind::ValidatorSetUpdate))
+        );
+    }
+
     /// Test that reading the bridge pool works
     #[tokio::test]
     async fn test_read_bridge_pool() {
@@ -1667,6 +2461,119 @@ mod test_ethbridge_router {
         );
     }
 
+    /// Test reading the minted supply of a wrapped ERC20 token, which
+    /// is kept in line with its balances when minting and burning.
+    #[tokio::test]
+    async fn test_read_wrapped_supply() {
+        const ERC20_TOKEN: EthAddress = EthAddress([0; 20]);
+
+        let mut client = TestClient::new(RPC);
+        test_utils::init_default_storage(&mut client.wl_storage);
+
+        let result = RPC
+            .shell()
+            .eth_bridge()
+            .read_wrapped_supply(&client, &ERC20_TOKEN)
+            .await;
+        assert_matches!(result, Ok(supply) if supply.is_zero());
+
+        let token = wrapped_erc20s::token(&ERC20_TOKEN);
+        let owner = established_address_1();
+        namada_token::credit_tokens(
+            &mut client.wl_storage,
+            &token,
+            &owner,
+            Amount::from(10),
+        )
+        .expect("Test failed");
+        namada_token::burn_exact_tokens(
+            &mut client.wl_storage,
+            &token,
+            &owner,
+            Amount::from(4),
+        )
+        .expect("Test failed");
+
+        let result = RPC
+            .shell()
+            .eth_bridge()
+            .read_wrapped_supply(&client, &ERC20_TOKEN)
+            .await;
+        assert_matches!(result, Ok(supply) if supply == Amount::from(6));
+        wrapped_erc20s::check_supply_invariant(&client.wl_storage, &token)
+            .expect("Test failed");
+    }
+
+    /// Test the versioned queries of the Ethereum bridge, before and after
+    /// the bridge is initialized.
+    #[tokio::test]
+    async fn test_eth_bridge_v1_queries() {
+        const ERC20_TOKEN: EthAddress = EthAddress([0; 20]);
+
+        let mut client = TestClient::new(RPC);
+        let queries = RPC.shell().eth_bridge().eth_bridge_v1();
+
+        // nothing was initialized yet
+        let result = queries.read_bridge_pool_nonce(&client).await;
+        assert_eq!(
+            result.unwrap(),
+            Err(EthBridgeQueryError::NotInitialized(
+                get_nonce_key().to_string()
+            ))
+        );
+        let result = queries.bridge_active(&client).await;
+        assert_matches!(
+            result,
+            Ok(Err(EthBridgeQueryError::NotInitialized(_)))
+        );
+        let result = queries.valset_upd_seen(&client, &Epoch(0)).await;
+        assert_matches!(
+            result,
+            Ok(Err(EthBridgeQueryError::NoValsetUpdForFirstEpoch))
+        );
+
+        test_utils::init_default_storage(&mut client.wl_storage);
+
+        let expected_nonce = client
+            .wl_storage
+            .ethbridge_queries()
+            .get_bridge_pool_nonce();
+        let result = queries.read_bridge_pool_nonce(&client).await;
+        assert_matches!(result, Ok(Ok(nonce)) if nonce == expected_nonce);
+        let result = queries.bridge_active(&client).await;
+        assert_matches!(result, Ok(Ok(true)));
+
+        // the proof of the valset update to the next epoch is complete
+        // once it's seen
+        let epoch = Epoch(1);
+        let result = queries.valset_upd_seen(&client, &epoch).await;
+        assert_matches!(result, Ok(Ok(false)));
+        client
+            .wl_storage
+            .write(&vote_tallies::Keys::from(&epoch).seen(), true)
+            .expect("Test failed");
+        let result = queries.valset_upd_seen(&client, &epoch).await;
+        assert_matches!(result, Ok(Ok(true)));
+
+        // wrapped balances default to zero
+        let owner = established_address_1();
+        let result = queries
+            .wrapped_erc20_balance(&client, &owner, &ERC20_TOKEN)
+            .await;
+        assert_matches!(result, Ok(Ok(balance)) if balance.is_zero());
+        namada_token::credit_tokens(
+            &mut client.wl_storage,
+            &wrapped_erc20s::token(&ERC20_TOKEN),
+            &owner,
+            Amount::from(10),
+        )
+        .expect("Test failed");
+        let result = queries
+            .wrapped_erc20_balance(&client, &owner, &ERC20_TOKEN)
+            .await;
+        assert_matches!(result, Ok(Ok(balance)) if balance == Amount::from(10));
+    }
+
     /// Test that querying the status of the Bridge pool
     /// returns the expected keccak hashes.
     #[tokio::test]
@@ -1,7 +1,12 @@
 use std::fmt::Debug;
 
-use namada_core::types::storage::BlockHeight;
-use namada_state::{DBIter, StorageHasher, WlStorage, DB};
+use namada_core::types::address::Address;
+use namada_core::types::storage::{
+    self, BlockHash, BlockHeight, Epoch, Epochs, Header, TxIndex,
+};
+use namada_state::{
+    DBIter, ResultExt, State, StorageHasher, StorageRead, WlStorage, DB,
+};
 use thiserror::Error;
 
 use crate::events::log::EventLog;
@@ -27,6 +32,214 @@ where
     /// limit the how many block heights in the past can the storage be
     /// queried for reading values.
     pub storage_read_past_height_limit: Option<u64>,
+    /// Taken from config `ethereum_bridge.enable_valset_preview`. When set,
+    /// enables the previews of the validator set updates of the bridge.
+    pub enable_valset_preview: bool,
+}
+
+impl<'shell, D, H, VpCache, TxCache> RequestCtx<'shell, D, H, VpCache, TxCache>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    /// Get a read-only handle on the state committed at the height of the
+    /// given request, where `0` stands for the last committed height. Fails
+    /// if the storage no longer retains the state at that height, or if no
+    /// block was committed at that height yet.
+    pub fn state_at_height(
+        &self,
+        request: &RequestQuery,
+    ) -> namada_storage::Result<StateAtHeight<'shell, D, H>> {
+        let height = resolve_query_height(
+            request.height.into(),
+            self.wl_storage.storage.get_last_block_height(),
+            self.storage_read_past_height_limit,
+        )
+        .map_err(namada_storage::Error::new)?;
+        Ok(StateAtHeight {
+            storage: &self.wl_storage.storage,
+            height,
+        })
+    }
+}
+
+/// Error resolving the height of a query to a committed state
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum QueryHeightError {
+    /// The state at the queried height was pruned
+    #[error(
+        "Height pruned: the state at height {height} is no longer retained, \
+         the oldest retained height being {oldest} (configured via \
+         `shell.storage_read_past_height_limit`)"
+    )]
+    HeightPruned {
+        /// The queried height
+        height: BlockHeight,
+        /// The oldest height whose state is retained
+        oldest: BlockHeight,
+    },
+    /// No block was committed at the queried height yet
+    #[error(
+        "No block was committed at height {height} yet, the last committed \
+         height being {last_committed}"
+    )]
+    NotCommitted {
+        /// The queried height
+        height: BlockHeight,
+        /// The last committed height
+        last_committed: BlockHeight,
+    },
+}
+
+/// Resolve the height of a query, where `0` stands for the last committed
+/// height, to a committed height whose state the storage still retains,
+/// i.e. no more than `past_height_limit` blocks below the last committed
+/// height, if set.
+pub fn resolve_query_height(
+    height: BlockHeight,
+    last_committed: BlockHeight,
+    past_height_limit: Option<u64>,
+) -> Result<BlockHeight, QueryHeightError> {
+    if height.0 == 0 {
+        return Ok(last_committed);
+    }
+    if height > last_committed {
+        return Err(QueryHeightError::NotCommitted {
+            height,
+            last_committed,
+        });
+    }
+    if let Some(past_height_limit) = past_height_limit {
+        let oldest =
+            BlockHeight(last_committed.0.saturating_sub(past_height_limit));
+        if height < oldest {
+            return Err(QueryHeightError::HeightPruned { height, oldest });
+        }
+    }
+    Ok(height)
+}
+
+/// A read-only handle on the state committed at some block height, which the
+/// storage still retains. Queries supporting past heights run their read
+/// logic against it, rather than against the live [`WlStorage`].
+///
+/// Values are read from the diffs of the storage at past heights. Iterating
+/// over a storage prefix is only supported at the last committed height, as
+/// the storage doesn't retain past iteration orders.
+#[derive(Debug)]
+pub struct StateAtHeight<'shell, D, H>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    storage: &'shell State<D, H>,
+    height: BlockHeight,
+}
+
+impl<'shell, D, H> StateAtHeight<'shell, D, H>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    /// The height of the state read by this handle
+    pub fn height(&self) -> BlockHeight {
+        self.height
+    }
+
+    /// Check if this handle reads the state at the last committed height
+    pub fn is_last_committed(&self) -> bool {
+        self.height == self.storage.get_last_block_height()
+    }
+}
+
+impl<'shell, D, H> StorageRead for StateAtHeight<'shell, D, H>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    type PrefixIter<'iter> = <D as DBIter<'iter>>::PrefixIter where Self: 'iter;
+
+    fn read_bytes(
+        &self,
+        key: &storage::Key,
+    ) -> namada_storage::Result<Option<Vec<u8>>> {
+        let (value, _gas) = self
+            .storage
+            .read_with_height(key, self.height)
+            .into_storage_result()?;
+        Ok(value)
+    }
+
+    fn has_key(&self, key: &storage::Key) -> namada_storage::Result<bool> {
+        Ok(self.read_bytes(key)?.is_some())
+    }
+
+    fn iter_prefix<'iter>(
+        &'iter self,
+        prefix: &storage::Key,
+    ) -> namada_storage::Result<Self::PrefixIter<'iter>> {
+        if !self.is_last_committed() {
+            return Err(namada_storage::Error::new_const(
+                "Iterating over a storage prefix is only supported at the \
+                 last committed height",
+            ));
+        }
+        Ok(self.storage.db.iter_prefix(Some(prefix)))
+    }
+
+    fn iter_next<'iter>(
+        &'iter self,
+        iter: &mut Self::PrefixIter<'iter>,
+    ) -> namada_storage::Result<Option<(String, Vec<u8>)>> {
+        Ok(iter.next().map(|(key, val, _gas)| (key, val)))
+    }
+
+    fn get_chain_id(&self) -> namada_storage::Result<String> {
+        Ok(self.storage.chain_id.to_string())
+    }
+
+    fn get_block_height(&self) -> namada_storage::Result<BlockHeight> {
+        Ok(self.height)
+    }
+
+    fn get_block_header(
+        &self,
+        height: BlockHeight,
+    ) -> namada_storage::Result<Option<Header>> {
+        self.storage
+            .db
+            .read_block_header(height)
+            .into_storage_result()
+    }
+
+    fn get_block_hash(&self) -> namada_storage::Result<BlockHash> {
+        if !self.is_last_committed() {
+            return Err(namada_storage::Error::new_const(
+                "The hash of a past block is not retained in storage",
+            ));
+        }
+        Ok(self.storage.block.hash.clone())
+    }
+
+    fn get_block_epoch(&self) -> namada_storage::Result<Epoch> {
+        self.storage.block.pred_epochs.get_epoch(self.height).ok_or(
+            namada_storage::Error::SimpleMessage(
+                "The epoch of the queried height is not known",
+            ),
+        )
+    }
+
+    fn get_pred_epochs(&self) -> namada_storage::Result<Epochs> {
+        Ok(self.storage.block.pred_epochs.clone())
+    }
+
+    fn get_tx_index(&self) -> namada_storage::Result<TxIndex> {
+        Ok(TxIndex::default())
+    }
+
+    fn get_native_token(&self) -> namada_storage::Result<Address> {
+        Ok(self.storage.native_token.clone())
+    }
 }
 
 /// A `Router` handles parsing read-only query requests and dispatching them to
@@ -94,3 +307,47 @@ pub struct ResponseQuery<T> {
 
 /// [`ResponseQuery`] with borsh-encoded `data` field
 pub type EncodedResponseQuery = ResponseQuery<Vec<u8>>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Test resolving the heights of queries against the retained heights
+    #[test]
+    fn test_resolve_query_height() {
+        let last = BlockHeight(100);
+
+        // `0` stands for the last committed height
+        assert_eq!(
+            resolve_query_height(BlockHeight(0), last, Some(10)),
+            Ok(last)
+        );
+        assert_eq!(resolve_query_height(last, last, Some(10)), Ok(last));
+
+        // past heights are served while retained
+        assert_eq!(
+            resolve_query_height(BlockHeight(90), last, Some(10)),
+            Ok(BlockHeight(90))
+        );
+        assert_eq!(
+            resolve_query_height(BlockHeight(89), last, Some(10)),
+            Err(QueryHeightError::HeightPruned {
+                height: BlockHeight(89),
+                oldest: BlockHeight(90),
+            })
+        );
+        assert_eq!(
+            resolve_query_height(BlockHeight(1), last, None),
+            Ok(BlockHeight(1))
+        );
+
+        // heights that weren't committed yet are rejected
+        assert_eq!(
+            resolve_query_height(BlockHeight(101), last, None),
+            Err(QueryHeightError::NotCommitted {
+                height: BlockHeight(101),
+                last_committed: last,
+            })
+        );
+    }
+}
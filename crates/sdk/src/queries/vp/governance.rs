@@ -1,30 +1,68 @@
 // cd namada && cargo expand ledger::queries::vp::governance
 
+use namada_core::borsh::BorshSerializeExt;
+use namada_core::types::address::Address;
+use namada_core::types::hash::Hash;
+use namada_core::types::storage::Epoch;
+use namada_core::types::token;
 use namada_governance::parameters::GovernanceParameters;
+use namada_governance::storage::facade::ProposalStorageReader;
 use namada_governance::storage::proposal::StorageProposal;
-use namada_governance::utils::{ProposalResult, Vote};
+use namada_governance::storage::vote::VoteReceipt;
+use namada_governance::utils::{
+    ArchivedProposalResult, EpochParticipation, ProposalExecutionResult,
+    ProposalResult, ProposalVoteProjection, ProposalWriteSurface,
+    ReleasedFunds, TallyResult, TieBreakSeed, ValidatorParticipation,
+    ValsetSigParticipation, Vote,
+};
+use namada_proof_of_stake::queries::compute_proposal_tally;
+use namada_proof_of_stake::storage::read_pos_params;
 use namada_state::{DBIter, StorageHasher, DB};
 
-use crate::queries::types::RequestCtx;
+use crate::queries::types::{RequestCtx, RequestQuery};
+use crate::queries::{require_no_proof, EncodedResponseQuery};
 
 // Governance queries
 router! {GOV,
-    ( "proposal" / [id: u64 ] ) -> Option<StorageProposal> = proposal_id,
+    ( "proposal" / [id: u64 ] ) -> Option<StorageProposal> = (with_options proposal_id),
     ( "proposal" / [id: u64 ] / "votes" ) -> Vec<Vote> = proposal_id_votes,
+    ( "proposal" / [id: u64 ] / "vote_projection" / [voter: Address] ) -> Option<ProposalVoteProjection> = proposal_vote_projection,
+    ( "proposal" / [id: u64 ] / "write_surface" ) -> Option<ProposalWriteSurface> = proposal_write_surface,
+    ( "proposal" / [id: u64 ] / "code_hash" ) -> Option<Hash> = proposal_code_hash,
+    ( "proposal" / [id: u64 ] / "funds_released" ) -> Option<ReleasedFunds> = proposal_funds_released,
     ( "parameters" ) -> GovernanceParameters = parameters,
     ( "stored_proposal_result" / [id: u64] ) -> Option<ProposalResult> = proposal_result,
+    ( "proposal_execution_result" / [id: u64] ) -> Option<ProposalExecutionResult> = proposal_execution_result,
+    ( "archive" / [epoch: Epoch] / [id: u64] ) -> Option<ArchivedProposalResult> = proposal_archive,
+    ( "decided" / [epoch: Epoch] ) -> Vec<(u64, TallyResult)> = proposals_decided_in_epoch,
+    ( "votes_by_voter" / [voter: Address] / [page: opt u32] ) -> Vec<VoteReceipt> = votes_by_voter,
+    ( "treasury_balance" / [epoch: Epoch] ) -> Option<token::Amount> = treasury_balance,
+    ( "treasury_history" / [from: Epoch] / [to: Epoch] ) -> Vec<(Epoch, token::Amount)> = treasury_history,
+    ( "participation" / "validator" / [validator: Address] / [last_n: opt u64] ) -> Vec<(Epoch, u64, ValidatorParticipation)> = validator_participation,
+    ( "participation" / "valset" / [epoch: Epoch] ) -> Option<ValsetSigParticipation> = valset_sig_participation,
+    ( "participation" / [epoch: Epoch] ) -> Option<EpochParticipation> = participation,
 }
 
-/// Query the provided proposal id
+/// The maximum number of votes returned by a single `votes_by_voter` query
+pub const VOTES_BY_VOTER_PAGE_SIZE: usize = 100;
+
+/// Query the provided proposal id, at the height of the request if any
 fn proposal_id<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
+    request: &RequestQuery,
     id: u64,
-) -> namada_storage::Result<Option<StorageProposal>>
+) -> namada_storage::Result<EncodedResponseQuery>
 where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
     H: 'static + StorageHasher + Sync,
 {
-    namada_governance::storage::get_proposal_by_id(ctx.wl_storage, id)
+    require_no_proof(request)?;
+    let state = ctx.state_at_height(request)?;
+    let proposal = namada_governance::storage::get_proposal_by_id(&state, id)?;
+    Ok(EncodedResponseQuery {
+        data: proposal.serialize_to_vec(),
+        ..Default::default()
+    })
 }
 
 /// Query all the votes for the given proposal id
@@ -39,6 +77,115 @@ where
     namada_governance::storage::get_proposal_votes(ctx.wl_storage, id)
 }
 
+/// Get the vote of the given voter on a proposal, along with the result of
+/// the proposal if it was tallied with the votes cast so far. Once the
+/// proposal has been tallied, its stored result is returned instead.
+fn proposal_vote_projection<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    id: u64,
+    voter: Address,
+) -> namada_storage::Result<Option<ProposalVoteProjection>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let proposal = match namada_governance::storage::get_proposal_by_id(
+        ctx.wl_storage,
+        id,
+    )? {
+        Some(proposal) => proposal,
+        None => return Ok(None),
+    };
+    let vote =
+        namada_governance::storage::get_proposal_votes(ctx.wl_storage, id)?
+            .into_iter()
+            .find(|vote| vote.delegator == voter)
+            .map(|vote| vote.data);
+
+    if let Some(result) =
+        namada_governance::storage::get_proposal_result(ctx.wl_storage, id)?
+    {
+        return Ok(Some(ProposalVoteProjection {
+            vote,
+            projection: result,
+            is_final: true,
+        }));
+    }
+
+    // Height-based proposals snapshot the stake of voters at the epoch
+    // containing their voting start height
+    let tally_epoch = proposal
+        .voting_heights
+        .and_then(|voting_heights| {
+            ctx.wl_storage
+                .storage
+                .block
+                .pred_epochs
+                .get_epoch(voting_heights.start)
+        })
+        .unwrap_or(proposal.voting_end_epoch);
+    let is_steward = namada_governance::pgf::storage::is_steward(
+        ctx.wl_storage,
+        &proposal.author,
+    )?;
+    let params = read_pos_params(ctx.wl_storage)?;
+    let emergency_validator_quorum =
+        namada_governance::storage::get_emergency_validator_quorum(
+            ctx.wl_storage,
+        )?;
+    let council_threshold =
+        namada_governance::storage::get_council(ctx.wl_storage)?
+            .map(|council| council.threshold);
+    let tally_type = proposal.get_tally_type(
+        is_steward,
+        emergency_validator_quorum,
+        council_threshold,
+    );
+    // A tie broken at random is projected from the hash of the last block, as
+    // the block that will conclude the proposal is not known yet
+    let tie_break_seed =
+        TieBreakSeed::new(&ctx.wl_storage.storage.block.hash, id);
+    let strategy = namada_governance::storage::get_tie_breaks(ctx.wl_storage)?
+        .strategy(tally_type, tie_break_seed);
+    let projection = compute_proposal_tally(
+        ctx.wl_storage,
+        &params,
+        id,
+        tally_epoch,
+        strategy,
+    )?
+    .into();
+    Ok(Some(ProposalVoteProjection {
+        vote,
+        projection,
+        is_final: false,
+    }))
+}
+
+/// Get the hash of the wasm code of a proposal, if it has code
+fn proposal_code_hash<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    id: u64,
+) -> namada_storage::Result<Option<Hash>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    ProposalStorageReader::new(ctx.wl_storage).code_hash(id)
+}
+
+/// Get where the funds of a proposal went, if they were already released
+fn proposal_funds_released<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    id: u64,
+) -> namada_storage::Result<Option<ReleasedFunds>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    ProposalStorageReader::new(ctx.wl_storage).funds_released(id)
+}
+
 /// Get the governance parameters
 fn parameters<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -61,3 +208,458 @@ where
 {
     namada_governance::storage::get_proposal_result(ctx.wl_storage, id)
 }
+
+/// Get the outcome of the execution of the code of a proposal
+fn proposal_execution_result<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    id: u64,
+) -> namada_storage::Result<Option<ProposalExecutionResult>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::storage::get_proposal_execution_result(
+        ctx.wl_storage,
+        id,
+    )
+}
+
+/// Dry run the code of a proposal to get the storage keys it writes
+fn proposal_write_surface<D, H, V, T>(
+    _ctx: RequestCtx<'_, D, H, V, T>,
+    _id: u64,
+) -> namada_storage::Result<Option<ProposalWriteSurface>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    unimplemented!(
+        "Dry running the code of a proposal requires \"wasm-runtime\" feature."
+    )
+}
+
+/// Get the governance proposal result archived at the given epoch
+fn proposal_archive<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    epoch: Epoch,
+    id: u64,
+) -> namada_storage::Result<Option<ArchivedProposalResult>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::storage::get_proposal_archive(ctx.wl_storage, epoch, id)
+}
+
+/// Get the ids and outcomes of the proposals decided in the transition to
+/// the given epoch, ordered by proposal id, from the archive
+fn proposals_decided_in_epoch<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    epoch: Epoch,
+) -> namada_storage::Result<Vec<(u64, TallyResult)>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::storage::get_proposals_decided_in_epoch(
+        ctx.wl_storage,
+        epoch,
+    )
+}
+
+/// Get the latest vote of the given voter on each proposal, ordered by
+/// proposal id. Votes are returned in pages of
+/// [`VOTES_BY_VOTER_PAGE_SIZE`] items, starting from the first page if no
+/// page is given.
+fn votes_by_voter<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    voter: Address,
+    page: Option<u32>,
+) -> namada_storage::Result<Vec<VoteReceipt>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let page = page.unwrap_or_default() as usize;
+    let receipts =
+        namada_governance::storage::get_votes_by_voter(ctx.wl_storage, &voter)?;
+    Ok(receipts
+        .into_iter()
+        .skip(page.saturating_mul(VOTES_BY_VOTER_PAGE_SIZE))
+        .take(VOTES_BY_VOTER_PAGE_SIZE)
+        .collect())
+}
+
+/// Get the treasury balance snapshotted at the given epoch, if it is still
+/// within the retention window
+fn treasury_balance<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    epoch: Epoch,
+) -> namada_storage::Result<Option<token::Amount>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::storage::get_treasury_balance_at(ctx.wl_storage, epoch)
+}
+
+/// Get the treasury balance snapshots taken between the given epochs, both
+/// inclusive, ordered by epoch
+fn treasury_history<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    from: Epoch,
+    to: Epoch,
+) -> namada_storage::Result<Vec<(Epoch, token::Amount)>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::storage::get_treasury_balance_history(
+        ctx.wl_storage,
+        from,
+        to,
+    )
+}
+
+/// Get the participation of the validators in the proposals concluded at
+/// the given epoch, if it is still within the retention window
+fn participation<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    epoch: Epoch,
+) -> namada_storage::Result<Option<EpochParticipation>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::storage::get_participation_at(ctx.wl_storage, epoch)
+}
+
+/// Get the validators whose signatures were included in the proof of the
+/// validator set update signed at the given epoch, if it is still within
+/// the retention window
+fn valset_sig_participation<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    epoch: Epoch,
+) -> namada_storage::Result<Option<ValsetSigParticipation>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::storage::get_valset_sig_participation_at(
+        ctx.wl_storage,
+        epoch,
+    )
+}
+
+/// Get the participation of a validator in the proposals concluded at the
+/// last `last_n` epochs with some recorded participation, or at all the
+/// epochs within the retention window
+fn validator_participation<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    validator: Address,
+    last_n: Option<u64>,
+) -> namada_storage::Result<Vec<(Epoch, u64, ValidatorParticipation)>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::storage::get_validator_participation(
+        ctx.wl_storage,
+        &validator,
+        last_n,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeMap, HashMap};
+
+    use namada_core::borsh::BorshSerializeExt;
+    use namada_core::types::address::testing::{
+        established_address_1, established_address_2,
+    };
+    use namada_core::types::token;
+    use namada_ethereum_bridge::test_utils;
+    use namada_governance::storage::facade::ProposalStorageWriter;
+    use namada_governance::storage::proposal::{
+        ProposalType, VoteProposalData,
+    };
+    use namada_governance::utils::TallyType;
+    use namada_governance::ProposalVote;
+
+    use super::*;
+    use crate::queries::testing::TestClient;
+    use crate::queries::RPC;
+
+    /// Test that the votes of a voter are paginated in proposal id order,
+    /// and that an overwritten vote is only reported once
+    #[tokio::test]
+    async fn test_votes_by_voter_pagination() {
+        let mut client = TestClient::new(RPC);
+        let voter = established_address_1();
+        let validator = established_address_2();
+        let total_votes = VOTES_BY_VOTER_PAGE_SIZE as u64 + 5;
+
+        for id in 0..total_votes {
+            namada_governance::vote_proposal(
+                &mut client.wl_storage,
+                VoteProposalData {
+                    id,
+                    vote: ProposalVote::Yay,
+                    voter: voter.clone(),
+                    delegations: vec![validator.clone()],
+                },
+            )
+            .unwrap();
+        }
+        // overwrite the vote on the last proposal
+        client.wl_storage.storage.block.epoch = Epoch(1);
+        namada_governance::vote_proposal(
+            &mut client.wl_storage,
+            VoteProposalData {
+                id: total_votes - 1,
+                vote: ProposalVote::Nay,
+                voter: voter.clone(),
+                delegations: vec![validator],
+            },
+        )
+        .unwrap();
+
+        let first_page = RPC
+            .vp()
+            .gov()
+            .votes_by_voter(&client, &voter, &None)
+            .await
+            .unwrap();
+        assert_eq!(first_page.len(), VOTES_BY_VOTER_PAGE_SIZE);
+        assert!(
+            first_page
+                .iter()
+                .zip(0..)
+                .all(|(receipt, id)| receipt.proposal_id == id)
+        );
+
+        let second_page = RPC
+            .vp()
+            .gov()
+            .votes_by_voter(&client, &voter, &Some(1))
+            .await
+            .unwrap();
+        assert_eq!(second_page.len(), 5);
+        assert_eq!(
+            second_page.last(),
+            Some(&VoteReceipt {
+                proposal_id: total_votes - 1,
+                vote: ProposalVote::Nay,
+                epoch: Epoch(1),
+            })
+        );
+
+        let third_page = RPC
+            .vp()
+            .gov()
+            .votes_by_voter(&client, &voter, &Some(2))
+            .await
+            .unwrap();
+        assert!(third_page.is_empty());
+    }
+
+    /// Test that the proposals decided in two epochs are reported
+    /// separately, and that epochs without governance activity report none
+    #[tokio::test]
+    async fn test_proposals_decided_in_epoch() {
+        let mut client = TestClient::new(RPC);
+        let decided = [
+            (Epoch(1), 0, TallyResult::Passed),
+            (Epoch(1), 1, TallyResult::Rejected),
+            (Epoch(2), 2, TallyResult::Rejected),
+        ];
+        for (epoch, id, result) in decided {
+            namada_governance::storage::write_proposal_archive(
+                &mut client.wl_storage,
+                id,
+                epoch,
+                ProposalResult {
+                    result,
+                    tally_type: TallyType::TwoThirds,
+                    total_voting_power: token::Amount::native_whole(100),
+                    total_yay_power: token::Amount::zero(),
+                    total_nay_power: token::Amount::zero(),
+                    total_abstain_power: token::Amount::zero(),
+                },
+            )
+            .unwrap();
+        }
+
+        let query = |epoch: Epoch| {
+            let client = &client;
+            async move {
+                RPC.vp()
+                    .gov()
+                    .proposals_decided_in_epoch(client, &epoch)
+                    .await
+                    .unwrap()
+            }
+        };
+        assert!(query(Epoch(0)).await.is_empty());
+        assert_eq!(
+            query(Epoch(1)).await,
+            vec![(0, TallyResult::Passed), (1, TallyResult::Rejected)]
+        );
+        assert_eq!(query(Epoch(2)).await, vec![(2, TallyResult::Rejected)]);
+        assert!(query(Epoch(3)).await.is_empty());
+    }
+
+    /// Test that the projected result of a proposal is returned along with
+    /// the vote of an account, whether or not the account voted
+    #[tokio::test]
+    async fn test_proposal_vote_projection() {
+        let mut client = TestClient::new(RPC);
+        let voter = established_address_1();
+        let non_voter = established_address_2();
+        test_utils::init_storage_with_validators(
+            &mut client.wl_storage,
+            HashMap::from([
+                (voter.clone(), token::Amount::native_whole(200)),
+                (non_voter.clone(), token::Amount::native_whole(100)),
+            ]),
+        );
+        GovernanceParameters::default()
+            .init_storage(&mut client.wl_storage)
+            .unwrap();
+
+        let id = 0;
+        let mut writer = ProposalStorageWriter::new(&mut client.wl_storage);
+        writer.set_author(id, &voter).unwrap();
+        writer
+            .set_content_bytes(
+                id,
+                BTreeMap::<String, String>::new().serialize_to_vec(),
+            )
+            .unwrap();
+        writer
+            .set_proposal_type(id, &ProposalType::Default(None))
+            .unwrap();
+        writer.set_voting_start(id, Epoch(0)).unwrap();
+        writer.set_voting_end(id, Epoch(1)).unwrap();
+        writer.set_grace_epoch(id, Epoch(2)).unwrap();
+        namada_governance::vote_proposal(
+            &mut client.wl_storage,
+            VoteProposalData {
+                id,
+                vote: ProposalVote::Yay,
+                voter: voter.clone(),
+                delegations: vec![voter.clone()],
+            },
+        )
+        .unwrap();
+
+        let voted = RPC
+            .vp()
+            .gov()
+            .proposal_vote_projection(&client, &id, &voter)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(voted.vote, Some(ProposalVote::Yay));
+        assert!(!voted.is_final);
+        assert_eq!(voted.projection.tally_type, TallyType::TwoThirds);
+        assert_eq!(
+            voted.projection.total_voting_power,
+            token::Amount::native_whole(300)
+        );
+        assert_eq!(
+            voted.projection.total_yay_power,
+            token::Amount::native_whole(200)
+        );
+        assert_eq!(voted.projection.total_nay_power, token::Amount::zero());
+
+        let not_voted = RPC
+            .vp()
+            .gov()
+            .proposal_vote_projection(&client, &id, &non_voter)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(not_voted.vote, None);
+        assert_eq!(not_voted.projection, voted.projection);
+
+        // once tallied, the stored result is returned
+        let result = ProposalResult {
+            result: TallyResult::Passed,
+            ..voted.projection
+        };
+        ProposalStorageWriter::new(&mut client.wl_storage)
+            .set_result(id, result)
+            .unwrap();
+        let tallied = RPC
+            .vp()
+            .gov()
+            .proposal_vote_projection(&client, &id, &non_voter)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(tallied.vote, None);
+        assert_eq!(tallied.projection, result);
+        assert!(tallied.is_final);
+
+        // there's nothing to project for unknown proposals
+        let unknown = RPC
+            .vp()
+            .gov()
+            .proposal_vote_projection(&client, &(id + 1), &voter)
+            .await
+            .unwrap();
+        assert_eq!(unknown, None);
+    }
+
+    /// Test that the code hash and the released funds of a proposal are
+    /// read back as written, and are missing until they are written
+    #[tokio::test]
+    async fn test_proposal_code_hash_and_funds_released() {
+        let mut client = TestClient::new(RPC);
+        let id = 3;
+        assert_eq!(
+            RPC.vp()
+                .gov()
+                .proposal_code_hash(&client, &id)
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            RPC.vp()
+                .gov()
+                .proposal_funds_released(&client, &id)
+                .await
+                .unwrap(),
+            None
+        );
+
+        let released = ReleasedFunds {
+            returned: token::Amount::native_whole(10),
+            burned: token::Amount::zero(),
+            to_treasury: token::Amount::native_whole(2),
+        };
+        let mut writer = ProposalStorageWriter::new(&mut client.wl_storage);
+        writer.set_code_hash(id, Hash::sha256([1, 2, 3])).unwrap();
+        writer.set_funds_released(id, released).unwrap();
+
+        assert_eq!(
+            RPC.vp()
+                .gov()
+                .proposal_code_hash(&client, &id)
+                .await
+                .unwrap(),
+            Some(Hash::sha256([1, 2, 3]))
+        );
+        assert_eq!(
+            RPC.vp()
+                .gov()
+                .proposal_funds_released(&client, &id)
+                .await
+                .unwrap(),
+            Some(released)
+        );
+    }
+}
@@ -3,6 +3,7 @@
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use borsh_ext::BorshSerializeExt;
 use namada_core::types::address::Address;
 use namada_core::types::key::common;
 use namada_core::types::storage::Epoch;
@@ -32,9 +33,10 @@ use namada_proof_of_stake::types::{
 use namada_proof_of_stake::{self, bond_amount, query_reward_tokens};
 use namada_state::{DBIter, StorageHasher, DB};
 use namada_storage::collections::lazy_map;
-use namada_storage::OptionExt;
+use namada_storage::{OptionExt, ResultExt};
 
-use crate::queries::types::RequestCtx;
+use crate::queries::types::{RequestCtx, RequestQuery};
+use crate::queries::EncodedResponseQuery;
 
 // PoS validity predicate queries
 router! {POS,
@@ -94,6 +96,9 @@ router! {POS,
 
     ( "bond" / [source: Address] / [validator: Address] / [epoch: opt Epoch] )
         -> token::Amount = bond,
+    // The request data must be a borsh encoded `Vec<BondId>`
+    ( "bonds_at_epoch" / [epoch: Epoch] )
+        -> Vec<token::Amount> = (with_options bonds_at_epoch),
 
     ( "rewards" / [validator: Address] / [source: opt Address] )
         -> token::Amount = rewards,
@@ -452,6 +457,45 @@ where
         .ok_or_err_msg("Cannot find bond")
 }
 
+/// Get the stake of each of the requested bonds at the given epoch, in the
+/// order of the request. The stake of a self-bond is the stake of its
+/// validator, including its delegations, like the voting power of the
+/// votes of validators on governance proposals. Bonds that don't exist have
+/// no stake.
+fn bonds_at_epoch<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    request: &RequestQuery,
+    epoch: Epoch,
+) -> namada_storage::Result<EncodedResponseQuery>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let bond_ids: Vec<BondId> = BorshDeserialize::try_from_slice(&request.data)
+        .into_storage_result()?;
+    let params = read_pos_params(ctx.wl_storage)?;
+    let stakes = bond_ids
+        .iter()
+        .map(|bond_id| {
+            let stake = if bond_id.source == bond_id.validator {
+                read_validator_stake(
+                    ctx.wl_storage,
+                    &params,
+                    &bond_id.validator,
+                    epoch,
+                )
+            } else {
+                bond_amount(ctx.wl_storage, bond_id, epoch)
+            };
+            stake.unwrap_or_default()
+        })
+        .collect::<Vec<token::Amount>>();
+    Ok(EncodedResponseQuery {
+        data: stakes.serialize_to_vec(),
+        ..Default::default()
+    })
+}
+
 fn bond_with_slashing<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
     source: Address,
@@ -6,6 +6,7 @@ use std::ops::ControlFlow;
 use std::str::FromStr;
 
 use borsh::BorshDeserialize;
+use borsh_ext::BorshSerializeExt;
 use masp_primitives::asset_type::AssetType;
 use masp_primitives::merkle_tree::MerklePath;
 use masp_primitives::sapling::Node;
@@ -20,12 +21,19 @@ use namada_core::types::token::{
     Amount, DenominatedAmount, Denomination, MaspDigitPos,
 };
 use namada_core::types::{storage, token};
+use namada_gas::BlockGasMeter;
 use namada_governance::parameters::GovernanceParameters;
 use namada_governance::pgf::parameters::PgfParameters;
 use namada_governance::pgf::storage::steward::StewardDetail;
+use namada_governance::storage::code::decode_code;
+use namada_governance::storage::keys as governance_storage;
 use namada_governance::storage::proposal::StorageProposal;
+use namada_governance::storage::vote::{ProposalVote, VoteReceipt};
 use namada_governance::utils::{
-    compute_proposal_result, ProposalResult, ProposalVotes, Vote,
+    tally, ArchivedProposalResult, EpochParticipation, ProposalExecutionResult,
+    ProposalResult, ProposalVoteProjection, ProposalVotes,
+    ProposalWriteSurface, ReleasedFunds, TallyParams, TallyResult,
+    TieBreakSeed, ValidatorParticipation, ValsetSigParticipation, Vote,
 };
 use namada_ibc::storage::{
     ibc_denom_key, ibc_denom_key_prefix, is_ibc_denom_key,
@@ -33,21 +41,25 @@ use namada_ibc::storage::{
 use namada_parameters::{storage as params_storage, EpochDuration};
 use namada_proof_of_stake::parameters::PosParams;
 use namada_proof_of_stake::types::{
-    BondsAndUnbondsDetails, CommissionPair, ValidatorMetaData, ValidatorState,
+    BondId, BondsAndUnbondsDetails, CommissionPair, ValidatorMetaData,
+    ValidatorState,
 };
 use namada_state::LastBlock;
 use namada_tx::data::{ResultCode, TxResult};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::args::InputAmount;
+use crate::args::{InputAmount, VoteExportFormat};
 use crate::control_flow::time;
 use crate::error::{EncodingError, Error, QueryError, TxSubmitError};
-use crate::events::Event;
+use crate::events::{
+    self, attributes, Event, EventLevel, EventType, ProposalFunds,
+    ProposalOutcome,
+};
 use crate::internal_macros::echo_error;
 use crate::io::Io;
 use crate::masp::MaspTokenRewardData;
 use crate::queries::vp::pos::EnrichedBondsAndUnbondsDetails;
-use crate::queries::{Client, RPC};
+use crate::queries::{Client, PREFIX_PAGE_SIZE, RPC};
 use crate::tendermint::block::Height;
 use crate::tendermint::merkle::proof::ProofOps;
 use crate::tendermint_rpc::error::Error as TError;
@@ -55,6 +67,8 @@ use crate::tendermint_rpc::query::Query;
 use crate::tendermint_rpc::Order;
 use crate::{display_line, edisplay_line, error, Namada, Tx};
 
+pub mod eth_bridge;
+
 /// Query the status of a given transaction.
 ///
 /// If a response is not delivered until `deadline`, we exit the cli with an
@@ -171,6 +185,15 @@ pub async fn query_results<C: crate::queries::Client + Sync>(
     convert_response::<C, _>(RPC.shell().read_results(client).await)
 }
 
+/// Query the gas consumed by each tx and governance proposal of the block at
+/// the given height, along with the block total
+pub async fn query_block_gas<C: crate::queries::Client + Sync>(
+    client: &C,
+    height: BlockHeight,
+) -> Result<Option<BlockGasMeter>, Error> {
+    convert_response::<C, _>(RPC.shell().read_block_gas(client, &height).await)
+}
+
 /// Query token amount of owner.
 pub async fn get_token_balance<C: crate::queries::Client + Sync>(
     client: &C,
@@ -412,6 +435,57 @@ pub async fn query_storage_value_bytes<C: crate::queries::Client + Sync>(
     })
 }
 
+/// Query a storage value without decoding it, one chunk at a time. Unlike
+/// [`query_storage_value_bytes`], values of any size can be fetched, as they
+/// are not returned in a single response. Returns `None` if the key is not
+/// present.
+pub async fn query_storage_value_chunked<C: crate::queries::Client + Sync>(
+    client: &C,
+    key: &storage::Key,
+) -> Result<Option<Vec<u8>>, error::Error> {
+    let Some(first) = convert_response::<C, _>(
+        RPC.shell().storage_value_chunk(client, key, &0).await,
+    )?
+    else {
+        return Ok(None);
+    };
+    let total_len = usize::try_from(first.total_len).map_err(|_| {
+        Error::from(QueryError::General(format!(
+            "The value of {key} is too large to be fetched"
+        )))
+    })?;
+    let value_hash = first.value_hash;
+    let mut value = Vec::with_capacity(total_len);
+    value.extend(first.bytes);
+
+    let mut chunk = 1;
+    while value.len() < total_len {
+        let next = convert_response::<C, _>(
+            RPC.shell().storage_value_chunk(client, key, &chunk).await,
+        )?;
+        match next {
+            Some(next)
+                if next.value_hash == value_hash && !next.bytes.is_empty() =>
+            {
+                value.extend(next.bytes)
+            }
+            _ => {
+                return Err(Error::from(QueryError::General(format!(
+                    "The value of {key} changed while it was being fetched"
+                ))));
+            }
+        }
+        chunk += 1;
+    }
+
+    if value.len() != total_len || Hash::sha256(&value) != value_hash {
+        return Err(Error::from(QueryError::General(format!(
+            "The fetched value of {key} doesn't match its hash"
+        ))));
+    }
+    Ok(Some(value))
+}
+
 /// Query a range of storage values with a matching prefix and decode them with
 /// [`BorshDeserialize`]. Returns an iterator of the storage keys paired with
 /// their associated values.
@@ -585,6 +659,20 @@ pub struct TxResponse {
     pub code: ResultCode,
     /// Gas used. If there's an `inner_tx`, its gas is equal to this value.
     pub gas_used: String,
+    /// Ids of the governance proposals initialized by the tx, if any
+    pub proposal_ids: Vec<u64>,
+}
+
+/// Parse the comma separated ids of the governance proposals initialized
+/// by a tx, from the attribute of its event.
+fn parse_proposal_ids(attr: Option<&str>) -> Result<Vec<u64>, String> {
+    attr.filter(|ids| !ids.is_empty())
+        .map(|ids| {
+            ids.split(',')
+                .map(|id| u64::from_str(id).map_err(|e| e.to_string()))
+                .collect()
+        })
+        .unwrap_or_else(|| Ok(vec![]))
 }
 
 /// Determines a result of an inner tx from [`TxResponse::inner_tx_result`].
@@ -634,6 +722,9 @@ impl TryFrom<Event> for TxResponse {
             .get("gas_used")
             .ok_or_else(|| missing_field_err("gas_used"))?
             .clone();
+        let proposal_ids = parse_proposal_ids(
+            event.get(attributes::PROPOSAL_ID).map(String::as_str),
+        )?;
 
         Ok(TxResponse {
             inner_tx,
@@ -643,6 +734,7 @@ impl TryFrom<Event> for TxResponse {
             height,
             code,
             gas_used,
+            proposal_ids,
         })
     }
 }
@@ -738,6 +830,11 @@ pub async fn query_tx_response<C: crate::queries::Client + Sync>(
         .map_err(|_| TError::parse("Error parsing ResultCode".to_string()))?;
     let height = BlockHeight::from_str(event_map["height"])
         .map_err(|_| TError::parse("Error parsing BlockHeight".to_string()))?;
+    let proposal_ids =
+        parse_proposal_ids(event_map.get(attributes::PROPOSAL_ID).copied())
+            .map_err(|_| {
+                TError::parse("Error parsing proposal ids".to_string())
+            })?;
     let result = TxResponse {
         inner_tx,
         info: event_map["info"].to_string(),
@@ -746,6 +843,7 @@ pub async fn query_tx_response<C: crate::queries::Client + Sync>(
         hash: event_map["hash"].to_string(),
         code,
         gas_used: event_map["gas_used"].to_string(),
+        proposal_ids,
     };
     Ok(result)
 }
@@ -837,10 +935,70 @@ pub async fn get_delegators_delegation_at<C: crate::queries::Client + Sync>(
 pub async fn query_proposal_by_id<C: crate::queries::Client + Sync>(
     client: &C,
     proposal_id: u64,
+) -> Result<Option<StorageProposal>, Error> {
+    query_proposal_by_id_at_height(client, proposal_id, None).await
+}
+
+/// Query proposal by Id, as it was stored at the given height, or at the
+/// last committed height if `None`. Fails if the node no longer retains the
+/// state at that height.
+pub async fn query_proposal_by_id_at_height<
+    C: crate::queries::Client + Sync,
+>(
+    client: &C,
+    proposal_id: u64,
+    height: Option<BlockHeight>,
 ) -> Result<Option<StorageProposal>, Error> {
     convert_response::<C, _>(
-        RPC.vp().gov().proposal_id(client, &proposal_id).await,
+        RPC.vp()
+            .gov()
+            .proposal_id(client, None, height, false, &proposal_id)
+            .await,
     )
+    .map(|response| response.data)
+}
+
+/// Query the wasm code of a proposal, verified against the code hash written
+/// at its submission, and decompressed if it's stored compressed. Returns
+/// `None` if the proposal has no code.
+pub async fn query_proposal_code<C: crate::queries::Client + Sync>(
+    client: &C,
+    proposal_id: u64,
+) -> Result<Option<Vec<u8>>, Error> {
+    // The code may not fit in a single response, so it's read from storage
+    // in chunks rather than through the governance queries
+    let code_key = governance_storage::get_proposal_code_key(proposal_id);
+    let Some(code) = query_storage_value_chunked(client, &code_key).await?
+    else {
+        return Ok(None);
+    };
+    let code_hash = convert_response::<C, Option<Hash>>(
+        RPC.vp()
+            .gov()
+            .proposal_code_hash(client, &proposal_id)
+            .await,
+    )?
+    .ok_or_else(|| {
+        Error::from(QueryError::General(format!(
+            "The code hash of proposal {proposal_id} is missing"
+        )))
+    })?;
+    if Hash::sha256(&code) != code_hash {
+        return Err(Error::from(QueryError::General(format!(
+            "The code of proposal {proposal_id} doesn't match its hash \
+             {code_hash}"
+        ))));
+    }
+    let max_code_size = convert_response::<C, GovernanceParameters>(
+        RPC.vp().gov().parameters(client).await,
+    )?
+    .max_proposal_code_size;
+    let code = decode_code(code, max_code_size).map_err(|err| {
+        Error::from(QueryError::General(format!(
+            "The code of proposal {proposal_id} can't be decoded: {err}"
+        )))
+    })?;
+    Ok(Some(code))
 }
 
 /// Query and return validator's commission rate and max commission rate change
@@ -968,14 +1126,43 @@ pub async fn query_proposal_result<C: crate::queries::Client + Sync>(
     let proposal_result = match stored_proposal_result {
         Some(proposal_result) => proposal_result,
         None => {
-            let tally_epoch = proposal.voting_end_epoch;
+            let tally_epoch = match proposal.voting_heights {
+                Some(voting_heights) => {
+                    query_epoch_at_height(client, voting_heights.start)
+                        .await?
+                        .unwrap_or(proposal.voting_end_epoch)
+                }
+                None => proposal.voting_end_epoch,
+            };
 
             let is_author_pgf_steward =
                 is_steward(client, &proposal.author).await;
             let votes = query_proposal_votes(client, proposal_id)
                 .await
                 .unwrap_or_default();
-            let tally_type = proposal.get_tally_type(is_author_pgf_steward);
+            let governance_parameters =
+                convert_response::<C, GovernanceParameters>(
+                    RPC.vp().gov().parameters(client).await,
+                )?;
+            let tally_type = proposal.get_tally_type(
+                is_author_pgf_steward,
+                governance_parameters.emergency_validator_quorum,
+                governance_parameters
+                    .council
+                    .as_ref()
+                    .map(|council| council.threshold),
+            );
+            // A tie broken at random is projected from the hash of the last
+            // block, as the block that will conclude the proposal is not
+            // known yet
+            let last_block_hash = query_block(client)
+                .await?
+                .map(|last_block| last_block.hash)
+                .unwrap_or_default();
+            let strategy = governance_parameters.tie_breaks.strategy(
+                tally_type,
+                TieBreakSeed::new(&last_block_hash, proposal_id),
+            );
             let total_staked_token =
                 get_total_staked_tokens(client, tally_epoch)
                     .await
@@ -1019,16 +1206,234 @@ pub async fn query_proposal_result<C: crate::queries::Client + Sync>(
                     }
                 }
             }
-            compute_proposal_result(
-                proposal_votes,
-                total_staked_token,
-                tally_type,
+            let council_votes = governance_parameters
+                .council
+                .map(|council| proposal_votes.council_votes(&council))
+                .unwrap_or_default();
+            tally(
+                proposal_votes
+                    .weighted_votes()
+                    .into_iter()
+                    .chain(council_votes),
+                TallyParams {
+                    strategy,
+                    total_voting_power: total_staked_token,
+                    max_voting_power_fraction: governance_parameters
+                        .max_voting_power_fraction,
+                },
             )
+            .into()
         }
     };
     Ok(Some(proposal_result))
 }
 
+/// Wait for the governance proposal with the given id to conclude, and get
+/// its outcome along with where its funds went.
+///
+/// The block concluding the proposal is looked up through the Tendermint
+/// event index every block. Should the node fail to answer, e.g. as the
+/// connection dropped or it doesn't index events, the storage key of the
+/// result of the proposal is polled every block instead. Once the result is
+/// written, the blocks committed since it was last found missing are
+/// searched for the event of the proposal.
+pub async fn wait_proposal_concluded<C: crate::queries::Client + Sync>(
+    client: &C,
+    proposal_id: u64,
+    timeout: time::Duration,
+) -> Result<ProposalOutcome, Error> {
+    let deadline = time::Instant::now() + timeout;
+    let use_event_index = Cell::new(true);
+    // The first height not known to precede the conclusion of the proposal
+    let scan_from = Cell::new(None);
+    let outcome = time::Sleep {
+        strategy: time::Constant(time::Duration::from_secs(1)),
+    }
+    .timeout(deadline, || async {
+        if use_event_index.get() {
+            match search_proposal_outcome(client, proposal_id).await {
+                Ok(Some(outcome)) => return ControlFlow::Break(Ok(outcome)),
+                Ok(None) => return ControlFlow::Continue(()),
+                Err(err) => {
+                    tracing::debug!(
+                        proposal_id,
+                        %err,
+                        "Tendermint event index unavailable, polling the \
+                         result of the proposal instead",
+                    );
+                    use_event_index.set(false);
+                }
+            }
+        }
+        poll_proposal_outcome(client, proposal_id, &scan_from).await
+    })
+    .await
+    .map_err(|_| Error::from(QueryError::ProposalTimeout(proposal_id)))??;
+    Ok(ProposalOutcome {
+        funds: Some(query_proposal_funds(client, &outcome).await?),
+        ..outcome
+    })
+}
+
+/// Find the outcome of a concluded proposal through the Tendermint event
+/// index. Returns `None` if the proposal hasn't concluded yet.
+async fn search_proposal_outcome<C: crate::queries::Client + Sync>(
+    client: &C,
+    proposal_id: u64,
+) -> Result<Option<ProposalOutcome>, Error> {
+    let blocks = client
+        .block_search(
+            events::proposal_concluded_query(proposal_id),
+            1,
+            255,
+            Order::Ascending,
+        )
+        .await
+        .map_err(|err| Error::from(QueryError::General(err.to_string())))?
+        .blocks;
+    for block in blocks {
+        let height = BlockHeight(block.block.header.height.value());
+        if let Some(outcome) =
+            find_proposal_outcome(client, proposal_id, height).await?
+        {
+            return Ok(Some(outcome));
+        }
+    }
+    Ok(None)
+}
+
+/// Check whether the result of a proposal has been written, and if so,
+/// search the blocks committed since `scan_from` for its outcome. Blocks
+/// preceding the first poll are not known, in which case the blocks of
+/// the current epoch are searched, as proposals conclude at the start of
+/// an epoch.
+async fn poll_proposal_outcome<C: crate::queries::Client + Sync>(
+    client: &C,
+    proposal_id: u64,
+    scan_from: &Cell<Option<BlockHeight>>,
+) -> ControlFlow<Result<ProposalOutcome, Error>> {
+    macro_rules! retry_on_err {
+        ($result:expr) => {
+            match $result {
+                Ok(value) => value,
+                Err(err) => {
+                    tracing::debug!(
+                        proposal_id,
+                        %err,
+                        "Failed to poll the result of the proposal, \
+                         retrying after timeout",
+                    );
+                    return ControlFlow::Continue(());
+                }
+            }
+        };
+    }
+
+    // The last block is looked up before the result, such that if the
+    // result is missing, the proposal concludes in a later block
+    let last_height = retry_on_err!(query_block(client).await)
+        .map(|block| block.height)
+        .unwrap_or_default();
+    let result = retry_on_err!(convert_response::<C, Option<ProposalResult>>(
+        RPC.vp().gov().proposal_result(client, &proposal_id).await,
+    ));
+    if result.is_none() {
+        scan_from.set(Some(last_height.next_height()));
+        return ControlFlow::Continue(());
+    }
+    let last_height = retry_on_err!(query_block(client).await)
+        .map(|block| block.height)
+        .unwrap_or_default();
+    let scan_from = match scan_from.get() {
+        Some(height) => height,
+        None => retry_on_err!(convert_response::<C, BlockHeight>(
+            RPC.shell()
+                .first_block_height_of_current_epoch(client)
+                .await,
+        )),
+    };
+    for height in scan_from.0..=last_height.0 {
+        let outcome = retry_on_err!(
+            find_proposal_outcome(client, proposal_id, BlockHeight(height))
+                .await
+        );
+        if let Some(outcome) = outcome {
+            return ControlFlow::Break(Ok(outcome));
+        }
+    }
+    ControlFlow::Break(Err(Error::from(QueryError::General(format!(
+        "The result of proposal {proposal_id} was written, but the event \
+         concluding it wasn't found in blocks {scan_from} to {last_height}. \
+         It may have concluded in an earlier epoch."
+    )))))
+}
+
+/// Find the event concluding a proposal in the results of the block at the
+/// given height
+async fn find_proposal_outcome<C: crate::queries::Client + Sync>(
+    client: &C,
+    proposal_id: u64,
+    height: BlockHeight,
+) -> Result<Option<ProposalOutcome>, Error> {
+    let height = Height::try_from(height.0)
+        .map_err(|err| Error::from(QueryError::General(err.to_string())))?;
+    let events = client
+        .block_results(height)
+        .await
+        .map_err(|err| Error::from(QueryError::General(err.to_string())))?
+        .end_block_events
+        .unwrap_or_default();
+    Ok(events.into_iter().find_map(|event| {
+        if event.kind != EventType::Proposal.to_string() {
+            return None;
+        }
+        let event = Event {
+            event_type: EventType::Proposal,
+            level: EventLevel::Block,
+            attributes: event
+                .attributes
+                .into_iter()
+                .map(|attr| (attr.key, attr.value))
+                .collect(),
+        };
+        // Events that don't conclude the proposal, such as warnings, fail
+        // to be parsed as an outcome
+        ProposalOutcome::try_from(&event)
+            .ok()
+            .filter(|outcome| outcome.id == proposal_id)
+    }))
+}
+
+/// Find where the funds of a concluded proposal went
+async fn query_proposal_funds<C: crate::queries::Client + Sync>(
+    client: &C,
+    outcome: &ProposalOutcome,
+) -> Result<ProposalFunds, Error> {
+    let proposal =
+        query_proposal_by_id(client, outcome.id)
+            .await?
+            .ok_or_else(|| {
+                Error::from(QueryError::General(format!(
+                    "Proposal {} not found",
+                    outcome.id
+                )))
+            })?;
+    // The split of the funds is recorded when they are released
+    let released = convert_response::<C, Option<ReleasedFunds>>(
+        RPC.vp()
+            .gov()
+            .proposal_funds_released(client, &outcome.id)
+            .await,
+    )?
+    .ok_or_else(|| {
+        Error::from(QueryError::General(format!(
+            "The funds of proposal {} weren't released",
+            outcome.id
+        )))
+    })?;
+    Ok(ProposalFunds::new(proposal.author, released))
+}
+
 /// Query a validator's unbonds for a given epoch
 pub async fn query_and_print_unbonds(
     context: &impl Namada,
@@ -1123,6 +1528,412 @@ pub async fn query_proposal_votes<C: crate::queries::Client + Sync>(
     )
 }
 
+/// Get the vote of a voter on a proposal, along with the projected result of
+/// the proposal. Returns `None` if the proposal doesn't exist.
+pub async fn query_proposal_vote_projection<
+    C: crate::queries::Client + Sync,
+>(
+    client: &C,
+    proposal_id: u64,
+    voter: &Address,
+) -> Result<Option<ProposalVoteProjection>, error::Error> {
+    convert_response::<C, Option<ProposalVoteProjection>>(
+        RPC.vp()
+            .gov()
+            .proposal_vote_projection(client, &proposal_id, voter)
+            .await,
+    )
+}
+
+/// Get a page of the latest votes cast by a voter, ordered by proposal id
+pub async fn query_votes_by_voter<C: crate::queries::Client + Sync>(
+    client: &C,
+    voter: &Address,
+    page: Option<u32>,
+) -> Result<Vec<VoteReceipt>, error::Error> {
+    convert_response::<C, Vec<VoteReceipt>>(
+        RPC.vp().gov().votes_by_voter(client, voter, &page).await,
+    )
+}
+
+/// Get a page of the storage values under the given prefix, of at most
+/// [`PREFIX_PAGE_SIZE`] values ordered by their keys
+pub async fn query_storage_prefix_page<C: crate::queries::Client + Sync>(
+    client: &C,
+    key: &storage::Key,
+    page: u32,
+) -> Result<Vec<PrefixValue>, error::Error> {
+    convert_response::<C, Vec<PrefixValue>>(
+        RPC.shell().storage_prefix_page(client, key, &page).await,
+    )
+}
+
+/// Get the stake of each of the given bonds at the given epoch, in the same
+/// order. The stake of a self-bond is the stake of its validator.
+pub async fn query_bonds_at_epoch<C: crate::queries::Client + Sync>(
+    client: &C,
+    bond_ids: &[BondId],
+    epoch: Epoch,
+) -> Result<Vec<token::Amount>, error::Error> {
+    let data = bond_ids.to_vec().serialize_to_vec();
+    let response = convert_response::<C, _>(
+        RPC.vp()
+            .pos()
+            .bonds_at_epoch(client, Some(data), None, false, &epoch)
+            .await,
+    )?;
+    Ok(response.data)
+}
+
+/// Whether an exported vote is the vote of a validator or of a delegator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportedVoteKind {
+    /// The vote of a validator, counted by default for the delegations to
+    /// the validator
+    Default,
+    /// The vote of a delegator, overriding the vote of its validator for
+    /// its delegation
+    Override,
+}
+
+impl std::fmt::Display for ExportedVoteKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportedVoteKind::Default => write!(f, "default"),
+            ExportedVoteKind::Override => write!(f, "override"),
+        }
+    }
+}
+
+/// A vote on a proposal weighted by the stake of its voter, as written by
+/// [`export_proposal_votes`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportedVote {
+    /// The voter
+    pub voter: Address,
+    /// The validator the vote was cast for, the voter itself for a
+    /// validator
+    pub validator: Address,
+    /// The vote
+    pub vote: ProposalVote,
+    /// The stake of the voter at the snapshot epoch of the proposal: the
+    /// stake of the validator or the bond of the delegator
+    pub stake: token::Amount,
+    /// The voting power counted for the vote, the stake of a validator net
+    /// of the bonds of its delegators who voted themselves
+    pub weight: token::Amount,
+    /// Whether this is the default vote of a validator or the override of
+    /// a delegator
+    pub kind: ExportedVoteKind,
+}
+
+/// Writes the exported votes of a proposal as they are found
+struct ExportedVoteWriter<W> {
+    writer: W,
+    format: VoteExportFormat,
+    count: u64,
+}
+
+impl<W: std::io::Write> ExportedVoteWriter<W> {
+    fn new(mut writer: W, format: VoteExportFormat) -> std::io::Result<Self> {
+        match format {
+            VoteExportFormat::Csv => {
+                writeln!(writer, "voter,validator,vote,stake,weight,kind")?
+            }
+            VoteExportFormat::Json => write!(writer, "[")?,
+        }
+        Ok(Self {
+            writer,
+            format,
+            count: 0,
+        })
+    }
+
+    fn write(&mut self, vote: &ExportedVote) -> std::io::Result<()> {
+        match self.format {
+            VoteExportFormat::Csv => writeln!(
+                self.writer,
+                "{},{},{},{},{},{}",
+                vote.voter,
+                vote.validator,
+                vote.vote,
+                vote.stake,
+                vote.weight,
+                vote.kind
+            )?,
+            VoteExportFormat::Json => {
+                if self.count > 0 {
+                    write!(self.writer, ",")?;
+                }
+                serde_json::to_writer(&mut self.writer, vote)?;
+            }
+        }
+        self.count += 1;
+        Ok(())
+    }
+
+    fn finish(mut self) -> std::io::Result<u64> {
+        if self.format == VoteExportFormat::Json {
+            writeln!(self.writer, "]")?;
+        }
+        self.writer.flush()?;
+        Ok(self.count)
+    }
+}
+
+/// The vote of a validator, whose weight is known once the votes of all its
+/// delegators have been read
+struct PendingValidatorVote {
+    validator: Address,
+    vote: Option<(ProposalVote, token::Amount)>,
+    /// The sum of the bonds of the delegators who voted
+    overridden: token::Amount,
+}
+
+impl PendingValidatorVote {
+    fn into_exported(self) -> Option<ExportedVote> {
+        let (vote, stake) = self.vote?;
+        Some(ExportedVote {
+            voter: self.validator.clone(),
+            validator: self.validator,
+            vote,
+            stake,
+            weight: stake.checked_sub(self.overridden).unwrap_or_default(),
+            kind: ExportedVoteKind::Default,
+        })
+    }
+}
+
+/// Write the votes cast on a proposal to the given writer, weighted by the
+/// stake of their voters at the snapshot epoch of the proposal. Returns the
+/// number of votes written.
+///
+/// The votes are read a page at a time and the votes of delegators are
+/// written as they are found. The votes of validators are written once all
+/// the votes have been read, when the votes of all their delegators are
+/// known, such that the weights of the votes of each side add up to its
+/// tallied voting power. The delegations following the votes of governors
+/// are not reflected.
+pub async fn export_proposal_votes<C, W>(
+    client: &C,
+    proposal_id: u64,
+    format: VoteExportFormat,
+    writer: W,
+) -> Result<u64, Error>
+where
+    C: crate::queries::Client + Sync,
+    W: std::io::Write,
+{
+    let io_error = |err: std::io::Error| {
+        Error::Other(format!("Failed to write votes: {err}"))
+    };
+    let proposal = query_proposal_by_id(client, proposal_id)
+        .await?
+        .ok_or_else(|| {
+            Error::from(QueryError::General(format!(
+                "Proposal {proposal_id} doesn't exist"
+            )))
+        })?;
+    let epoch = match proposal.voting_heights {
+        Some(voting_heights) => {
+            query_epoch_at_height(client, voting_heights.start)
+                .await?
+                .unwrap_or(proposal.voting_end_epoch)
+        }
+        None => proposal.voting_end_epoch,
+    };
+
+    let mut writer =
+        ExportedVoteWriter::new(writer, format).map_err(io_error)?;
+    let prefix = governance_storage::get_proposal_vote_prefix_key(proposal_id);
+    let mut pending: BTreeMap<Address, PendingValidatorVote> = BTreeMap::new();
+    let mut page = 0;
+    loop {
+        let values = query_storage_prefix_page(client, &prefix, page).await?;
+        let votes: Vec<(BondId, ProposalVote)> = values
+            .iter()
+            .filter_map(|PrefixValue { key, value }| {
+                let vote_key = governance_storage::parse_vote_key(key)
+                    .or_else(|| {
+                        governance_storage::parse_legacy_vote_key(key)
+                    })?;
+                let vote = ProposalVote::try_from_slice(value).ok()?;
+                let bond_id = BondId {
+                    source: vote_key.voter.clone(),
+                    validator: vote_key.validator.clone(),
+                };
+                Some((bond_id, vote))
+            })
+            .collect();
+        let bond_ids: Vec<BondId> =
+            votes.iter().map(|(bond_id, _)| bond_id.clone()).collect();
+        let stakes = query_bonds_at_epoch(client, &bond_ids, epoch).await?;
+
+        for ((bond_id, vote), stake) in votes.into_iter().zip(stakes) {
+            let validator_vote = pending
+                .entry(bond_id.validator.clone())
+                .or_insert_with(|| PendingValidatorVote {
+                    validator: bond_id.validator.clone(),
+                    vote: None,
+                    overridden: token::Amount::zero(),
+                });
+            if bond_id.source == bond_id.validator {
+                validator_vote.vote = Some((vote, stake));
+            } else {
+                validator_vote.overridden += stake;
+                writer
+                    .write(&ExportedVote {
+                        voter: bond_id.source,
+                        validator: bond_id.validator,
+                        vote,
+                        stake,
+                        weight: stake,
+                        kind: ExportedVoteKind::Override,
+                    })
+                    .map_err(io_error)?;
+            }
+        }
+
+        if values.len() < PREFIX_PAGE_SIZE {
+            break;
+        }
+        page += 1;
+    }
+    for vote in pending
+        .into_values()
+        .filter_map(PendingValidatorVote::into_exported)
+    {
+        writer.write(&vote).map_err(io_error)?;
+    }
+    writer.finish().map_err(io_error)
+}
+
+/// Get the outcome of the execution of the code of a proposal, including
+/// the verdicts of the VPs it triggered
+pub async fn query_proposal_execution_result<
+    C: crate::queries::Client + Sync,
+>(
+    client: &C,
+    proposal_id: u64,
+) -> Result<Option<ProposalExecutionResult>, error::Error> {
+    convert_response::<C, Option<ProposalExecutionResult>>(
+        RPC.vp()
+            .gov()
+            .proposal_execution_result(client, &proposal_id)
+            .await,
+    )
+}
+
+/// Get the storage keys the code of a proposal writes, found by dry running
+/// it, along with whether it would currently be allowed to execute
+pub async fn query_proposal_write_surface<C: crate::queries::Client + Sync>(
+    client: &C,
+    proposal_id: u64,
+) -> Result<Option<ProposalWriteSurface>, error::Error> {
+    convert_response::<C, Option<ProposalWriteSurface>>(
+        RPC.vp()
+            .gov()
+            .proposal_write_surface(client, &proposal_id)
+            .await,
+    )
+}
+
+/// Get the result of a proposal archived at the epoch it was decided in
+pub async fn query_proposal_archive<C: crate::queries::Client + Sync>(
+    client: &C,
+    epoch: Epoch,
+    proposal_id: u64,
+) -> Result<Option<ArchivedProposalResult>, error::Error> {
+    convert_response::<C, Option<ArchivedProposalResult>>(
+        RPC.vp()
+            .gov()
+            .proposal_archive(client, &epoch, &proposal_id)
+            .await,
+    )
+}
+
+/// Get the ids and outcomes of the governance proposals decided in the
+/// transition to the given epoch
+pub async fn query_proposals_decided_in_epoch<
+    C: crate::queries::Client + Sync,
+>(
+    client: &C,
+    epoch: Epoch,
+) -> Result<Vec<(u64, TallyResult)>, error::Error> {
+    convert_response::<C, Vec<(u64, TallyResult)>>(
+        RPC.vp()
+            .gov()
+            .proposals_decided_in_epoch(client, &epoch)
+            .await,
+    )
+}
+
+/// Get the treasury balance snapshotted at the given epoch
+pub async fn query_treasury_balance<C: crate::queries::Client + Sync>(
+    client: &C,
+    epoch: Epoch,
+) -> Result<Option<token::Amount>, error::Error> {
+    convert_response::<C, Option<token::Amount>>(
+        RPC.vp().gov().treasury_balance(client, &epoch).await,
+    )
+}
+
+/// Get the treasury balance snapshots taken between the given epochs, both
+/// inclusive
+pub async fn query_treasury_history<C: crate::queries::Client + Sync>(
+    client: &C,
+    from: Epoch,
+    to: Epoch,
+) -> Result<Vec<(Epoch, token::Amount)>, error::Error> {
+    convert_response::<C, Vec<(Epoch, token::Amount)>>(
+        RPC.vp().gov().treasury_history(client, &from, &to).await,
+    )
+}
+
+/// Get the participation of the validators in the governance proposals
+/// concluded at the given epoch
+pub async fn query_participation<C: crate::queries::Client + Sync>(
+    client: &C,
+    epoch: Epoch,
+) -> Result<Option<EpochParticipation>, error::Error> {
+    convert_response::<C, Option<EpochParticipation>>(
+        RPC.vp().gov().participation(client, &epoch).await,
+    )
+}
+
+/// Get the validators whose signatures were included in the proof of the
+/// validator set update signed at the given epoch
+pub async fn query_valset_sig_participation<
+    C: crate::queries::Client + Sync,
+>(
+    client: &C,
+    epoch: Epoch,
+) -> Result<Option<ValsetSigParticipation>, error::Error> {
+    convert_response::<C, Option<ValsetSigParticipation>>(
+        RPC.vp()
+            .gov()
+            .valset_sig_participation(client, &epoch)
+            .await,
+    )
+}
+
+/// Get the participation of a validator in the governance proposals
+/// concluded at the last `last_n` epochs with some recorded participation,
+/// or at all the retained epochs, ordered by epoch and by proposal id
+pub async fn query_validator_participation<C: crate::queries::Client + Sync>(
+    client: &C,
+    validator: &Address,
+    last_n: Option<u64>,
+) -> Result<Vec<(Epoch, u64, ValidatorParticipation)>, error::Error> {
+    convert_response::<C, Vec<(Epoch, u64, ValidatorParticipation)>>(
+        RPC.vp()
+            .gov()
+            .validator_participation(client, validator, &last_n)
+            .await,
+    )
+}
+
 pub async fn query_next_epoch_info<C: crate::queries::Client + Sync>(
     client: &C,
 ) -> Result<(BlockHeight, EpochDuration), error::Error> {
@@ -0,0 +1,80 @@
+//! Ethereum bridge queries, served by versioned routes of the ledger.
+//!
+//! Unlike reading the storage of the Ethereum bridge directly, these
+//! queries don't depend on its storage layout, which may change across
+//! releases.
+
+use namada_core::types::address::Address;
+use namada_core::types::ethereum_events::{EthAddress, Uint};
+use namada_core::types::storage::Epoch;
+use namada_core::types::token;
+
+use super::convert_response;
+use crate::error::{Error, EthereumBridgeError};
+use crate::queries::{Client, EthBridgeQueryError, RPC};
+
+/// Check if the proof of the validator set update to the given epoch is
+/// complete.
+pub async fn valset_upd_seen<C: Client + Sync>(
+    client: &C,
+    epoch: Epoch,
+) -> Result<bool, Error> {
+    convert_query_response::<C, _>(
+        RPC.shell()
+            .eth_bridge()
+            .eth_bridge_v1()
+            .valset_upd_seen(client, &epoch)
+            .await,
+    )
+}
+
+/// Read the nonce of the next Bridge pool root.
+pub async fn read_bridge_pool_nonce<C: Client + Sync>(
+    client: &C,
+) -> Result<Uint, Error> {
+    convert_query_response::<C, _>(
+        RPC.shell()
+            .eth_bridge()
+            .eth_bridge_v1()
+            .read_bridge_pool_nonce(client)
+            .await,
+    )
+}
+
+/// Check if the Ethereum bridge is active at the current epoch.
+pub async fn bridge_active<C: Client + Sync>(
+    client: &C,
+) -> Result<bool, Error> {
+    convert_query_response::<C, _>(
+        RPC.shell()
+            .eth_bridge()
+            .eth_bridge_v1()
+            .bridge_active(client)
+            .await,
+    )
+}
+
+/// Read the balance of the wrapped ERC20 token of the given asset held by
+/// `owner`.
+pub async fn wrapped_erc20_balance<C: Client + Sync>(
+    client: &C,
+    owner: &Address,
+    asset: &EthAddress,
+) -> Result<token::Amount, Error> {
+    convert_query_response::<C, _>(
+        RPC.shell()
+            .eth_bridge()
+            .eth_bridge_v1()
+            .wrapped_erc20_balance(client, owner, asset)
+            .await,
+    )
+}
+
+/// Turn the response of a versioned Ethereum bridge query into an error
+/// type that can be used with `?`.
+fn convert_query_response<C: Client, T>(
+    response: Result<Result<T, EthBridgeQueryError>, C::Error>,
+) -> Result<T, Error> {
+    convert_response::<C, _>(response)?
+        .map_err(|err| Error::EthereumBridge(EthereumBridgeError::Query(err)))
+}
@@ -932,6 +932,33 @@ impl<'a> Display for LedgerProposalType<'a> {
             }
             ProposalType::PGFSteward(_) => write!(f, "PGF Steward"),
             ProposalType::PGFPayment(_) => write!(f, "PGF Payment"),
+            ProposalType::ETHBridgeContracts(_) => {
+                write!(f, "ETH Bridge Contracts")
+            }
+            ProposalType::ETHBridgePause(_) => {
+                write!(f, "ETH Bridge Pause")
+            }
+            ProposalType::UpdateWasmAllowlist(_) => {
+                write!(f, "Wasm Allowlist Update")
+            }
+            ProposalType::ETHHotKeyRotation(_) => {
+                write!(f, "ETH Hot Key Rotation")
+            }
+            ProposalType::TreasurySpend(_) => write!(f, "Treasury Spend"),
+            ProposalType::ParameterChange(_) => {
+                write!(f, "Parameter Change")
+            }
+            ProposalType::Council(None) => write!(f, "Council"),
+            ProposalType::Council(Some(hash)) => {
+                let extra = self
+                    .1
+                    .get_section(hash)
+                    .and_then(|x| Section::extra_data_sec(x.as_ref()))
+                    .expect("unable to load vp code")
+                    .code
+                    .hash();
+                write!(f, "Council {}", HEXLOWER.encode(&extra.0))
+            }
         }
     }
 }
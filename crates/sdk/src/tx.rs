@@ -39,6 +39,7 @@ use namada_governance::cli::onchain::{
     DefaultProposal, OnChainProposal, PgfFundingProposal, PgfStewardProposal,
 };
 use namada_governance::pgf::cli::steward::Commission;
+use namada_governance::storage::code::compress_code;
 use namada_governance::storage::proposal::{
     InitProposalData, ProposalType, VoteProposalData,
 };
@@ -83,6 +84,10 @@ pub const TX_REACTIVATE_VALIDATOR_WASM: &str = "tx_reactivate_validator.wasm";
 pub const TX_INIT_PROPOSAL: &str = "tx_init_proposal.wasm";
 /// Vote transaction WASM path
 pub const TX_VOTE_PROPOSAL: &str = "tx_vote_proposal.wasm";
+/// Governance vote delegation transaction WASM path
+pub const TX_DELEGATE_VOTES_WASM: &str = "tx_delegate_votes.wasm";
+/// Governance vote keys migration WASM path, run as the code of a proposal
+pub const TX_MIGRATE_VOTE_KEYS_WASM: &str = "tx_migrate_vote_keys.wasm";
 /// Reveal public key transaction WASM path
 pub const TX_REVEAL_PK: &str = "tx_reveal_pk.wasm";
 /// Update validity predicate WASM path
@@ -1874,6 +1879,7 @@ pub async fn build_default_proposal(
         is_offline: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        code_chunks: _,
         tx_code_path,
     }: &args::InitProposal,
     proposal: DefaultProposal,
@@ -1899,12 +1905,27 @@ pub async fn build_default_proposal(
                 .add_extra_section(proposal_to_vec(proposal.proposal)?, None);
             init_proposal_data.content = extra_section_hash;
 
+            // The code is stored as carried by its section
+            let encode = |code: Vec<u8>| {
+                if proposal.compress_code {
+                    compress_code(&code)
+                } else {
+                    code
+                }
+            };
             if let Some(init_proposal_code) = proposal.data {
-                let (_, extra_section_hash) =
-                    tx_builder.add_extra_section(init_proposal_code, None);
+                let (_, extra_section_hash) = tx_builder
+                    .add_extra_section(encode(init_proposal_code), None);
                 init_proposal_data.r#type =
                     ProposalType::Default(Some(extra_section_hash));
             };
+            init_proposal_data.code_chunks = proposal
+                .code_chunks
+                .into_iter()
+                .map(|chunk| {
+                    tx_builder.add_extra_section(encode(chunk), None).1
+                })
+                .collect();
             Ok(())
         };
     // TODO: need to pay the fee to submit a proposal, check enough balance
@@ -1986,6 +2007,20 @@ pub async fn build_vote_proposal(
     .cloned()
     .collect::<Vec<Address>>();
 
+    // Members of the council may vote on council proposals without any
+    // delegations, under their own address
+    let delegations = if delegations.is_empty()
+        && matches!(proposal.r#type, ProposalType::Council(_))
+        && rpc::query_governance_parameters(context.client())
+            .await
+            .council
+            .map_or(false, |council| council.members.contains(voter))
+    {
+        vec![voter.clone()]
+    } else {
+        delegations
+    };
+
     if delegations.is_empty() {
         return Err(Error::Other(
             "Voter address must have delegations".to_string(),
@@ -2022,6 +2057,7 @@ pub async fn build_pgf_funding_proposal(
         is_offline: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        code_chunks: _,
         tx_code_path,
     }: &args::InitProposal,
     proposal: PgfFundingProposal,
@@ -2071,6 +2107,7 @@ pub async fn build_pgf_stewards_proposal(
         is_offline: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        code_chunks: _,
         tx_code_path,
     }: &args::InitProposal,
     proposal: PgfStewardProposal,
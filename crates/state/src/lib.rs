@@ -25,7 +25,7 @@ use namada_core::types::time::DateTimeUtc;
 pub use namada_core::types::token::ConversionState;
 use namada_core::types::{encode, ethereum_structs, storage};
 use namada_gas::{
-    MEMORY_ACCESS_GAS_PER_BYTE, STORAGE_ACCESS_GAS_PER_BYTE,
+    BlockGasMeter, MEMORY_ACCESS_GAS_PER_BYTE, STORAGE_ACCESS_GAS_PER_BYTE,
     STORAGE_WRITE_GAS_PER_BYTE,
 };
 pub use namada_merkle_tree::{
@@ -140,6 +140,9 @@ pub struct BlockStorage<H: StorageHasher> {
     pub epoch: Epoch,
     /// Results of applying transactions
     pub results: BlockResults,
+    /// Gas consumed by the transactions and the governance proposals
+    /// executed in the block
+    pub gas: BlockGasMeter,
     /// Predecessor block epochs
     pub pred_epochs: Epochs,
 }
@@ -186,6 +189,24 @@ where
         cache: Option<&D::Cache>,
         storage_read_past_height_limit: Option<u64>,
         merkle_tree_key_filter: fn(&storage::Key) -> bool,
+    ) -> Self {
+        Self::open_with_db(
+            D::open(db_path, cache),
+            chain_id,
+            native_token,
+            storage_read_past_height_limit,
+            merkle_tree_key_filter,
+        )
+    }
+
+    /// open up a new instance of the storage given an already opened db and
+    /// chain id
+    pub fn open_with_db(
+        db: D,
+        chain_id: ChainId,
+        native_token: Address,
+        storage_read_past_height_limit: Option<u64>,
+        merkle_tree_key_filter: fn(&storage::Key) -> bool,
     ) -> Self {
         let block = BlockStorage {
             tree: MerkleTree::default(),
@@ -194,9 +215,10 @@ where
             epoch: Epoch::default(),
             pred_epochs: Epochs::default(),
             results: BlockResults::default(),
+            gas: BlockGasMeter::default(),
         };
         State::<D, H> {
-            db: D::open(db_path, cache),
+            db,
             chain_id,
             block,
             header: None,
@@ -307,6 +329,7 @@ where
                 .time,
             epoch: self.block.epoch,
             results: &self.block.results,
+            gas: &self.block.gas,
             pred_epochs: &self.block.pred_epochs,
             next_epoch_min_start_height: self.next_epoch_min_start_height,
             next_epoch_min_start_time: self.next_epoch_min_start_time,
@@ -345,8 +368,15 @@ where
     /// Check if the given key is present in storage. Returns the result and the
     /// gas cost.
     pub fn has_key(&self, key: &Key) -> Result<(bool, u64)> {
+        let (len, gas) = self.value_len(key)?;
+        Ok((len.is_some(), gas))
+    }
+
+    /// Returns the length in bytes of a value from the specified subspace,
+    /// without reading the value, and the gas cost.
+    pub fn value_len(&self, key: &Key) -> Result<(Option<usize>, u64)> {
         Ok((
-            self.db.read_subspace_val(key)?.is_some(),
+            self.db.read_subspace_val_len(key)?,
             key.len() as u64 * STORAGE_ACCESS_GAS_PER_BYTE,
         ))
     }
@@ -1129,6 +1159,7 @@ pub mod testing {
                 epoch: Epoch::default(),
                 pred_epochs: Epochs::default(),
                 results: BlockResults::default(),
+                gas: BlockGasMeter::default(),
             };
             Self {
                 db: MockDB::default(),
@@ -401,6 +401,14 @@ impl WriteLog {
             .collect()
     }
 
+    /// Iterate over the storage modifications of the block write log, in no
+    /// particular order.
+    pub fn iter_block_modifications(
+        &self,
+    ) -> impl Iterator<Item = (&storage::Key, &StorageModification)> {
+        self.block_write_log.iter()
+    }
+
     /// Get the storage keys changed in the current transaction (left) and
     /// the addresses of accounts initialized in the current transaction
     /// (right). The first vector excludes keys of validity predicates of
@@ -9,6 +9,7 @@ use namada_core::types::storage::{
 use namada_core::types::time::DateTimeUtc;
 use namada_core::types::token::ConversionState;
 use namada_core::types::{ethereum_events, ethereum_structs};
+use namada_gas::BlockGasMeter;
 use namada_merkle_tree::{
     Error as MerkleTreeError, MerkleTreeStoresRead, MerkleTreeStoresWrite,
     StoreType,
@@ -104,6 +105,9 @@ pub struct BlockStateWrite<'a> {
     pub address_gen: &'a EstablishedAddressGen,
     /// Results of applying transactions
     pub results: &'a BlockResults,
+    /// Gas consumed by the transactions and the governance proposals
+    /// executed in the block
+    pub gas: &'a BlockGasMeter,
     /// The conversion state
     pub conversion_state: &'a ConversionState,
     /// Wrapper txs to be decrypted in the next block proposal
@@ -146,6 +150,12 @@ pub trait DB: Debug {
     /// Read the block header with the given height from the DB
     fn read_block_header(&self, height: BlockHeight) -> Result<Option<Header>>;
 
+    /// Read the gas consumed by the block with the given height from the DB
+    fn read_block_gas(
+        &self,
+        height: BlockHeight,
+    ) -> Result<Option<BlockGasMeter>>;
+
     /// Read the merkle tree stores with the given epoch. If a store_type is
     /// given, it reads only the the specified tree. Otherwise, it reads all
     /// trees.
@@ -162,6 +172,10 @@ pub trait DB: Debug {
     /// Read the latest value for account subspace key from the DB
     fn read_subspace_val(&self, key: &Key) -> Result<Option<Vec<u8>>>;
 
+    /// Read the length in bytes of the latest value for account subspace key
+    /// from the DB, without copying the value out of the DB
+    fn read_subspace_val_len(&self, key: &Key) -> Result<Option<usize>>;
+
     /// Read the value for account subspace key at the given height from the DB.
     /// In our `PersistentStorage` (rocksdb), to find a value from arbitrary
     /// height requires looking for diffs from the given `height`, possibly
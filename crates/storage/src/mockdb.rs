@@ -18,6 +18,7 @@ use namada_core::types::storage::{
 use namada_core::types::time::DateTimeUtc;
 use namada_core::types::token::ConversionState;
 use namada_core::types::{ethereum_events, ethereum_structs};
+use namada_gas::BlockGasMeter;
 use namada_merkle_tree::{
     base_tree_key_prefix, subtree_key_prefix, MerkleTreeStoresRead, StoreType,
 };
@@ -39,8 +40,23 @@ pub struct MockDB(
     // The state is wrapped in `RefCell` to allow modifying it directly from
     // batch write method (which requires immutable self ref).
     RefCell<BTreeMap<String, Vec<u8>>>,
+    // The number of times the value of each subspace key was read, such that
+    // tests can check that a value isn't loaded needlessly.
+    RefCell<BTreeMap<String, u64>>,
 );
 
+impl MockDB {
+    /// Get the number of times the latest value of the given subspace key
+    /// was read with [`DB::read_subspace_val`].
+    pub fn subspace_val_reads(&self, key: &Key) -> u64 {
+        self.1
+            .borrow()
+            .get(&key.to_string())
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
 // The `MockDB` is not `Sync`, but we're sharing it across threads for reading
 // only (for parallelized VP runs). In a different context, this may not be
 // safe.
@@ -267,6 +283,7 @@ impl DB for MockDB {
             update_epoch_blocks_delay,
             address_gen,
             results,
+            gas,
             conversion_state,
             ethereum_height,
             eth_events_queue,
@@ -391,6 +408,11 @@ impl DB for MockDB {
                 .borrow_mut()
                 .insert(results_path, types::encode(&results));
         }
+        // Block gas
+        {
+            let gas_path = format!("gas/{}", height.raw());
+            self.0.borrow_mut().insert(gas_path, types::encode(gas));
+        }
         Ok(())
     }
 
@@ -409,6 +431,19 @@ impl DB for MockDB {
         }
     }
 
+    fn read_block_gas(
+        &self,
+        height: BlockHeight,
+    ) -> Result<Option<BlockGasMeter>> {
+        let gas_path = format!("gas/{}", height.raw());
+        match self.0.borrow().get(gas_path.as_str()) {
+            Some(bytes) => {
+                Ok(Some(types::decode(bytes).map_err(Error::CodingError)?))
+            }
+            None => Ok(None),
+        }
+    }
+
     fn read_merkle_tree_stores(
         &self,
         epoch: Epoch,
@@ -485,10 +520,16 @@ impl DB for MockDB {
     }
 
     fn read_subspace_val(&self, key: &Key) -> Result<Option<Vec<u8>>> {
+        *self.1.borrow_mut().entry(key.to_string()).or_default() += 1;
         let key = Key::parse(SUBSPACE_CF).map_err(Error::KeyError)?.join(key);
         Ok(self.0.borrow().get(&key.to_string()).cloned())
     }
 
+    fn read_subspace_val_len(&self, key: &Key) -> Result<Option<usize>> {
+        let key = Key::parse(SUBSPACE_CF).map_err(Error::KeyError)?.join(key);
+        Ok(self.0.borrow().get(&key.to_string()).map(Vec::len))
+    }
+
     fn read_subspace_val_with_height(
         &self,
         key: &Key,
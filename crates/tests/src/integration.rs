@@ -1,2 +1,4 @@
+mod eth_bridge;
+mod governance;
 mod masp;
 mod setup;
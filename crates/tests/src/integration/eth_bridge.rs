@@ -0,0 +1,87 @@
+use borsh::BorshSerialize;
+use color_eyre::eyre::Result;
+use namada::eth_bridge::storage::bridge_pool::get_nonce_key;
+use namada::eth_bridge::storage::{vote_tallies, wrapped_erc20s};
+use namada::state::StorageWrite;
+use namada::token;
+use namada::types::ethereum_events::{EthAddress, Uint};
+use namada::types::storage::{Epoch, Key};
+use namada_apps::node::ledger::shell::testing::node::MockNode;
+use namada_apps::wallet::defaults::albert_address;
+use namada_sdk::error::{Error, EthereumBridgeError};
+use namada_sdk::queries::EthBridgeQueryError;
+use namada_sdk::rpc::eth_bridge::{
+    bridge_active, read_bridge_pool_nonce, valset_upd_seen,
+    wrapped_erc20_balance,
+};
+use test_log::test;
+
+use super::setup;
+
+/// The asset of the wrapped ERC20 token queried in tests
+const ERC20_TOKEN: EthAddress = EthAddress([1; 20]);
+
+/// Write a value to the storage of the node, as if by a tx
+fn write<T: BorshSerialize>(node: &MockNode, key: &Key, value: T) {
+    let mut locked = node.shell.lock().unwrap();
+    locked.wl_storage.write(key, value).unwrap();
+    locked.wl_storage.commit_tx();
+}
+
+/// Test that the versioned Ethereum bridge queries of the client SDK are
+/// served by the node, with their errors returned as typed values
+#[test]
+fn eth_bridge_v1_queries() -> Result<()> {
+    let (node, _services) = setup::setup()?;
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    // The bridge is disabled at genesis, so it has no Bridge pool nonce
+    assert!(!rt.block_on(bridge_active(&&node)).unwrap());
+    let err = rt.block_on(read_bridge_pool_nonce(&&node)).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::EthereumBridge(EthereumBridgeError::Query(
+            EthBridgeQueryError::NotInitialized(_)
+        ))
+    ));
+    write(&node, &get_nonce_key(), Uint::from(7));
+    assert_eq!(
+        rt.block_on(read_bridge_pool_nonce(&&node)).unwrap(),
+        Uint::from(7)
+    );
+
+    // There's no validator set update to the first epoch
+    let err = rt.block_on(valset_upd_seen(&&node, Epoch(0))).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::EthereumBridge(EthereumBridgeError::Query(
+            EthBridgeQueryError::NoValsetUpdForFirstEpoch
+        ))
+    ));
+    assert!(!rt.block_on(valset_upd_seen(&&node, Epoch(1))).unwrap());
+    write(&node, &vote_tallies::Keys::from(&Epoch(1)).seen(), true);
+    assert!(rt.block_on(valset_upd_seen(&&node, Epoch(1))).unwrap());
+
+    // Wrapped balances default to zero
+    let owner = albert_address();
+    let balance = rt
+        .block_on(wrapped_erc20_balance(&&node, &owner, &ERC20_TOKEN))
+        .unwrap();
+    assert!(balance.is_zero());
+    {
+        let mut locked = node.shell.lock().unwrap();
+        token::credit_tokens(
+            &mut locked.wl_storage,
+            &wrapped_erc20s::token(&ERC20_TOKEN),
+            &owner,
+            token::Amount::from(10),
+        )
+        .unwrap();
+        locked.wl_storage.commit_tx();
+    }
+    let balance = rt
+        .block_on(wrapped_erc20_balance(&&node, &owner, &ERC20_TOKEN))
+        .unwrap();
+    assert_eq!(balance, token::Amount::from(10));
+    Ok(())
+}
@@ -0,0 +1,327 @@
+use std::time::Duration;
+
+use borsh::BorshDeserialize;
+use color_eyre::eyre::Result;
+use namada::governance::storage::proposal::{InitProposalData, ProposalType};
+use namada::governance::storage::vote::ProposalVote;
+use namada::governance::storage::{init_proposal, keys as gov_storage};
+use namada::governance::utils::{ProposalResult, TallyResult};
+use namada::ledger::events::ProposalOutcome;
+use namada::proof_of_stake::bond_tokens;
+use namada::state::{StorageRead, StorageWrite};
+use namada::token;
+use namada::types::hash::Hash;
+use namada::types::storage::{BlockHeight, Key};
+use namada_apps::node::ledger::shell::testing::client::run;
+use namada_apps::node::ledger::shell::testing::node::MockNode;
+use namada_apps::node::ledger::shell::testing::utils::{Bin, CapturedOutput};
+use namada_apps::wallet::defaults::{
+    albert_address, bertha_address, validator_address,
+};
+use namada_sdk::queries::RPC;
+use namada_sdk::rpc::{
+    query_proposal_by_id_at_height, wait_proposal_concluded, ExportedVote,
+    ExportedVoteKind,
+};
+use test_log::test;
+
+use super::setup;
+
+/// Write a proposal by Albert to storage, whose grace epoch is three epochs
+/// ahead, and return its id
+fn write_proposal(node: &MockNode) -> u64 {
+    let mut locked = node.shell.lock().unwrap();
+    let wl_storage = &mut locked.wl_storage;
+    let id: u64 = wl_storage
+        .read(&gov_storage::get_counter_key())
+        .unwrap()
+        .unwrap();
+    let epoch = wl_storage.storage.last_epoch;
+    let data = InitProposalData {
+        id,
+        content: Hash::default(),
+        author: albert_address(),
+        r#type: ProposalType::Default(None),
+        voting_start_epoch: epoch + 1,
+        voting_end_epoch: epoch + 2,
+        grace_epoch: epoch + 3,
+        voting_heights: None,
+        priority: None,
+        code_chunks: vec![],
+    };
+    init_proposal(wl_storage, data, vec![], None, vec![]).unwrap();
+    wl_storage.commit_tx();
+    id
+}
+
+/// Advance epochs until the given proposal is concluded, returning its
+/// locked funds
+fn conclude_proposal(node: &mut MockNode, id: u64) -> token::Amount {
+    let funds = node
+        .shell
+        .lock()
+        .unwrap()
+        .wl_storage
+        .read(&gov_storage::get_funds_key(id))
+        .unwrap()
+        .unwrap();
+    for _ in 0..10 {
+        node.next_epoch();
+        let concluded = node
+            .shell
+            .lock()
+            .unwrap()
+            .wl_storage
+            .has_key(&gov_storage::get_proposal_result_key(id))
+            .unwrap();
+        if concluded {
+            return funds;
+        }
+    }
+    panic!("Proposal {id} didn't conclude");
+}
+
+/// Wait for a proposal to conclude with the client SDK
+fn wait(node: &MockNode, id: u64) -> ProposalOutcome {
+    tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(wait_proposal_concluded(&node, id, Duration::from_secs(10)))
+        .unwrap()
+}
+
+/// Test that the outcome of a concluded proposal is found through the
+/// Tendermint event index, and printed by the `watch-proposal` command
+#[test]
+fn wait_proposal_concluded_from_events() -> Result<()> {
+    // This address doesn't matter for tests. But an argument is required.
+    let validator_one_rpc = "http://127.0.0.1:26567";
+    let (mut node, _services) = setup::setup()?;
+    let id = write_proposal(&node);
+    let locked_funds = conclude_proposal(&mut node, id);
+
+    // Nobody voted, so the proposal got rejected
+    let outcome = wait(&node, id);
+    assert_eq!(outcome.id, id);
+    assert_eq!(outcome.tally, TallyResult::Rejected);
+    assert!(!outcome.executed);
+    assert!(!outcome.expired);
+    let funds = outcome.funds.expect("Test failed");
+    assert_eq!(funds.author, albert_address());
+    assert_eq!(funds.to_treasury, token::Amount::zero());
+    assert_eq!(funds.returned + funds.burned, locked_funds);
+
+    let captured = CapturedOutput::of(|| {
+        run(
+            &node,
+            Bin::Client,
+            vec![
+                "watch-proposal",
+                "--proposal-id",
+                &id.to_string(),
+                "--timeout",
+                "10s",
+                "--node",
+                validator_one_rpc,
+            ],
+        )
+    });
+    assert!(captured.result.is_ok());
+    assert!(captured.contains(&format!("Proposal Id: {id}")));
+    assert!(captured.contains("Tally result: rejected"));
+    Ok(())
+}
+
+/// Test that the outcome of a concluded proposal is found by polling its
+/// result when the node doesn't serve the Tendermint event index
+#[test]
+fn wait_proposal_concluded_from_polling() -> Result<()> {
+    let (mut node, _services) = setup::setup()?;
+    node.index_block_events = false;
+    let id = write_proposal(&node);
+    let locked_funds = conclude_proposal(&mut node, id);
+
+    let outcome = wait(&node, id);
+    assert_eq!(outcome.id, id);
+    assert_eq!(outcome.tally, TallyResult::Rejected);
+    let funds = outcome.funds.expect("Test failed");
+    assert_eq!(funds.returned + funds.burned, locked_funds);
+
+    // A proposal that never concludes times out
+    let pending = write_proposal(&node);
+    let err = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(wait_proposal_concluded(
+            &&node,
+            pending,
+            Duration::from_secs(2),
+        ))
+        .unwrap_err();
+    assert!(err.to_string().contains("Timed out"));
+    Ok(())
+}
+
+/// Test that the votes exported by the `export-proposal-votes` command are
+/// weighted such that the weights of each side add up to its tallied voting
+/// power
+#[test]
+fn export_proposal_votes_match_tally() -> Result<()> {
+    // This address doesn't matter for tests. But an argument is required.
+    let validator_one_rpc = "http://127.0.0.1:26567";
+    let (mut node, _services) = setup::setup()?;
+    let id = write_proposal(&node);
+
+    // Bertha delegates to the validator, effective at the end of the
+    // voting period, and three voters vote: the validator and Bertha yay,
+    // Albert, who also delegates to the validator, nay
+    {
+        let mut locked = node.shell.lock().unwrap();
+        let wl_storage = &mut locked.wl_storage;
+        let epoch = wl_storage.storage.last_epoch;
+        bond_tokens(
+            wl_storage,
+            Some(&bertha_address()),
+            &validator_address(),
+            token::Amount::native_whole(5_000),
+            epoch,
+            None,
+        )
+        .unwrap();
+        for (voter, vote) in [
+            (validator_address(), ProposalVote::Yay),
+            (albert_address(), ProposalVote::Nay),
+            (bertha_address(), ProposalVote::Yay),
+        ] {
+            let key = gov_storage::get_vote_proposal_key(
+                id,
+                voter,
+                validator_address(),
+            );
+            wl_storage.write(&key, vote).unwrap();
+        }
+        wl_storage.commit_tx();
+    }
+    conclude_proposal(&mut node, id);
+
+    let test_dir = tempfile::tempdir().unwrap();
+    let out_path = test_dir.path().join("votes.json");
+    let captured = CapturedOutput::of(|| {
+        run(
+            &node,
+            Bin::Client,
+            vec![
+                "export-proposal-votes",
+                "--proposal-id",
+                &id.to_string(),
+                "--format",
+                "json",
+                "--out",
+                out_path.to_str().unwrap(),
+                "--node",
+                validator_one_rpc,
+            ],
+        )
+    });
+    assert!(captured.result.is_ok());
+    assert!(captured.contains(&format!("Wrote 3 votes of proposal {id}")));
+
+    let votes: Vec<ExportedVote> =
+        serde_json::from_slice(&std::fs::read(&out_path).unwrap()).unwrap();
+    assert_eq!(votes.len(), 3);
+    let validator_vote = votes
+        .iter()
+        .find(|vote| vote.kind == ExportedVoteKind::Default)
+        .expect("Test failed");
+    assert_eq!(validator_vote.voter, validator_address());
+    // The validator's vote counts its stake but for the bonds of the
+    // delegators who voted themselves
+    assert_eq!(
+        validator_vote.weight,
+        validator_vote.stake
+            - token::Amount::native_whole(20_000)
+            - token::Amount::native_whole(5_000)
+    );
+
+    let result: ProposalResult = node
+        .shell
+        .lock()
+        .unwrap()
+        .wl_storage
+        .read(&gov_storage::get_proposal_result_key(id))
+        .unwrap()
+        .unwrap();
+    let weight_of = |side: fn(&ProposalVote) -> bool| {
+        votes
+            .iter()
+            .filter(|vote| side(&vote.vote))
+            .fold(token::Amount::zero(), |sum, vote| sum + vote.weight)
+    };
+    assert_eq!(weight_of(ProposalVote::is_yay), result.total_yay_power);
+    assert_eq!(weight_of(ProposalVote::is_nay), result.total_nay_power);
+    assert_eq!(result.total_nay_power, token::Amount::native_whole(20_000));
+    Ok(())
+}
+
+/// Test that the state of a proposal is served at the past heights that the
+/// node retains, before and after the proposal's conclusion
+#[test]
+fn query_proposal_at_past_heights() -> Result<()> {
+    let (mut node, _services) = setup::setup()?;
+    let last_height = |node: &MockNode| {
+        node.shell
+            .lock()
+            .unwrap()
+            .wl_storage
+            .storage
+            .get_last_block_height()
+    };
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let value_at = |node: &MockNode, key: &Key, height: BlockHeight| {
+        rt.block_on(RPC.shell().storage_value(
+            &node,
+            None,
+            Some(height),
+            false,
+            key,
+        ))
+        .unwrap()
+        .data
+    };
+
+    let before_init = last_height(&node);
+    let id = write_proposal(&node);
+    node.finalize_and_commit();
+    let after_init = last_height(&node);
+    let locked_funds = conclude_proposal(&mut node, id);
+    let after_conclusion = last_height(&node);
+
+    // The funds are locked from the proposal's submission on
+    let funds_key = gov_storage::get_funds_key(id);
+    assert!(value_at(&node, &funds_key, before_init).is_empty());
+    for height in [after_init, after_conclusion] {
+        let value = value_at(&node, &funds_key, height);
+        let funds = token::Amount::try_from_slice(&value).unwrap();
+        assert_eq!(funds, locked_funds);
+    }
+
+    // The result is only found once the proposal is concluded
+    let result_key = gov_storage::get_proposal_result_key(id);
+    assert!(value_at(&node, &result_key, after_init).is_empty());
+    assert!(!value_at(&node, &result_key, after_conclusion).is_empty());
+
+    // The proposal itself is served at the past heights
+    let proposal_at = |height| {
+        rt.block_on(query_proposal_by_id_at_height(&&node, id, Some(height)))
+    };
+    assert!(proposal_at(before_init).unwrap().is_none());
+    let proposal = proposal_at(after_init).unwrap().expect("Test failed");
+    assert_eq!(proposal.id, id);
+    assert_eq!(proposal.author, albert_address());
+    let concluded =
+        proposal_at(after_conclusion).unwrap().expect("Test failed");
+    assert_eq!(concluded.grace_epoch, proposal.grace_epoch);
+
+    // Heights that weren't committed yet aren't served
+    let err = proposal_at(after_conclusion.next_height()).unwrap_err();
+    assert!(err.to_string().contains("No block was committed"));
+    Ok(())
+}
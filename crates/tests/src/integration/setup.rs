@@ -202,6 +202,8 @@ fn create_node(
         services: Arc::new(services),
         results: Arc::new(Mutex::new(vec![])),
         blocks: Arc::new(Mutex::new(HashMap::new())),
+        block_events: Arc::new(Mutex::new(HashMap::new())),
+        index_block_events: true,
         auto_drive_services,
     };
     let init_req =
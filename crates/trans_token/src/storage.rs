@@ -197,6 +197,60 @@ where
     storage.write(&total_supply_key, new_total_supply)
 }
 
+/// Burn exactly `amount` of tokens from some address, adjusting the total
+/// supply of the token. Unlike [`burn_tokens`], returns an `Err` if the
+/// balance of the address is insufficient.
+pub fn burn_exact_tokens<S>(
+    storage: &mut S,
+    token: &Address,
+    source: &Address,
+    amount: token::Amount,
+) -> storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let source_balance_key = balance_key(token, source);
+    let new_balance = read_balance(storage, token, source)?
+        .checked_sub(amount)
+        .ok_or_else(|| {
+            storage::Error::new_const("Insufficient balance to burn")
+        })?;
+
+    let new_supply = read_total_supply(storage, token)?
+        .checked_sub(amount)
+        .ok_or_else(|| {
+            storage::Error::new_const("Total token supply underflowed")
+        })?;
+
+    storage.write(&source_balance_key, new_balance)?;
+    storage.write(&minted_balance_key(token), new_supply)
+}
+
+/// Sum the balances of all the owners of a given token, which must equal
+/// its total supply for tokens minted and burned exclusively through
+/// [`credit_tokens`] and [`burn_exact_tokens`].
+pub fn read_balances_sum<S>(
+    storage: &S,
+    token: &Address,
+) -> storage::Result<token::Amount>
+where
+    S: StorageRead,
+{
+    let mut sum = Amount::zero();
+    for item in storage::iter_prefix::<Amount>(storage, &balance_prefix(token))?
+    {
+        let (key, balance) = item?;
+        // skip the minted supply, which shares the prefix of the balances
+        if is_balance_key(token, &key).is_none() {
+            continue;
+        }
+        sum = sum.checked_add(balance).ok_or_else(|| {
+            storage::Error::new_const("Token balances sum overflow")
+        })?;
+    }
+    Ok(sum)
+}
+
 /// Add denomination info if it exists in storage.
 pub fn denominated(
     amount: token::Amount,
@@ -232,7 +286,10 @@ mod testing {
     use namada_core::types::{address, token};
     use namada_storage::testing::TestStorage;
 
-    use super::{burn_tokens, credit_tokens, read_balance, read_total_supply};
+    use super::{
+        burn_exact_tokens, burn_tokens, credit_tokens, read_balance,
+        read_balances_sum, read_total_supply,
+    };
 
     #[test]
     fn test_burn_native_tokens() {
@@ -290,4 +347,53 @@ mod testing {
         let total_supply = read_total_supply(&storage, &native_token).unwrap();
         assert_eq!(total_supply, balance3);
     }
+    /// Test that burning exactly some amount of tokens fails on an
+    /// insufficient balance, leaving the total supply equal to the sum of
+    /// the balances
+    #[test]
+    fn test_burn_exact_tokens() {
+        let mut storage = TestStorage::default();
+        let token = address::testing::established_address_1();
+        let addr1 = address::testing::gen_implicit_address();
+        let addr2 = address::testing::gen_implicit_address();
+
+        credit_tokens(&mut storage, &token, &addr1, token::Amount::from(10))
+            .unwrap();
+        credit_tokens(&mut storage, &token, &addr2, token::Amount::from(5))
+            .unwrap();
+        assert_eq!(
+            read_balances_sum(&storage, &token).unwrap(),
+            token::Amount::from(15)
+        );
+
+        burn_exact_tokens(&mut storage, &token, &addr1, token::Amount::from(4))
+            .unwrap();
+        assert_eq!(
+            read_balance(&storage, &token, &addr1).unwrap(),
+            token::Amount::from(6)
+        );
+        assert_eq!(
+            read_total_supply(&storage, &token).unwrap(),
+            token::Amount::from(11)
+        );
+
+        // burning more than the balance fails, without writing anything
+        assert!(
+            burn_exact_tokens(
+                &mut storage,
+                &token,
+                &addr2,
+                token::Amount::from(6)
+            )
+            .is_err()
+        );
+        assert_eq!(
+            read_balance(&storage, &token, &addr2).unwrap(),
+            token::Amount::from(5)
+        );
+        assert_eq!(
+            read_total_supply(&storage, &token).unwrap(),
+            read_balances_sum(&storage, &token).unwrap()
+        );
+    }
 }
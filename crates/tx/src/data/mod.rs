@@ -218,6 +218,30 @@ pub struct VpsResult {
     pub invalid_sig: bool,
 }
 
+impl VpsResult {
+    /// Iterate over the verdicts of the triggered VPs, i.e. whether each VP
+    /// accepted the transaction, along with the error it failed with, if any
+    pub fn verdicts(
+        &self,
+    ) -> impl Iterator<Item = (&Address, bool, Option<&str>)> + '_ {
+        let error = |addr: &Address| {
+            self.errors
+                .iter()
+                .find(|(err_addr, _)| err_addr == addr)
+                .map(|(_, err)| err.as_str())
+        };
+        let accepted = self
+            .accepted_vps
+            .iter()
+            .map(move |addr| (addr, true, error(addr)));
+        let rejected = self
+            .rejected_vps
+            .iter()
+            .map(move |addr| (addr, false, error(addr)));
+        accepted.chain(rejected)
+    }
+}
+
 impl fmt::Display for TxResult {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if f.alternate() {
@@ -76,6 +76,8 @@ pub enum ProtocolTxType {
     BridgePoolVext,
     /// Validator set update signed by some validator
     ValSetUpdateVext,
+    /// Addresses of new Ethereum bridge contracts signed by some validator
+    ContractAddressesVext,
 }
 
 impl ProtocolTxType {
@@ -91,6 +93,7 @@ impl ProtocolTxType {
                 | Self::EthEventsVext
                 | Self::BridgePoolVext
                 | Self::ValSetUpdateVext
+                | Self::ContractAddressesVext
         )
     }
 }
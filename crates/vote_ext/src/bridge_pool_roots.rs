@@ -7,10 +7,13 @@ use std::ops::{Deref, DerefMut};
 
 use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use namada_core::types::address::Address;
+use namada_core::types::ethereum_events::Uint;
+use namada_core::types::keccak::{keccak_hash, KeccakHash};
 use namada_core::types::key::common;
 use namada_core::types::key::common::Signature;
 use namada_core::types::storage::BlockHeight;
 use namada_tx::Signed;
+use serde::{Deserialize, Serialize};
 
 /// A vote extension containing a validator's signature
 /// of the current root and nonce of the
@@ -26,6 +29,8 @@ use namada_tx::Signed;
     BorshSerialize,
     BorshDeserialize,
     BorshSchema,
+    Serialize,
+    Deserialize,
 )]
 pub struct BridgePoolRootVext {
     /// The validator sending the vote extension
@@ -40,11 +45,37 @@ pub struct BridgePoolRootVext {
     /// as allowing validators to  query the epoch with
     /// the appropriate validator set to verify signatures
     pub block_height: BlockHeight,
+    /// The height at which the signed Bridge pool root was computed, if it
+    /// is bound to the signature.
+    ///
+    /// Roots signed without their height can be relayed to the bridge
+    /// contract after newer transfers were added to the pool, hence this
+    /// legacy format is only accepted until the height binding epoch set by
+    /// governance.
+    pub root_height: Option<BlockHeight>,
     /// The actual signature being submitted.
-    /// This is a signature over `keccak(eth_header || keccak(root || nonce))`.
+    /// This is a signature over `keccak(eth_header || digest)`, where the
+    /// digest is computed by [`bp_root_digest`].
     pub sig: Signature,
 }
 
+/// Compute the digest of a Bridge pool root and nonce signed by the
+/// validators, i.e. `keccak(root || nonce)` in the legacy format, or
+/// `keccak(root || nonce || root_height)` if the height at which the root
+/// was computed is bound to the signature. The height is encoded like the
+/// nonce, as a 256 bits big endian integer.
+pub fn bp_root_digest(
+    root: &KeccakHash,
+    nonce: Uint,
+    root_height: Option<BlockHeight>,
+) -> KeccakHash {
+    let mut data = [root.0, nonce.to_bytes()].concat();
+    if let Some(height) = root_height {
+        data.extend_from_slice(&Uint::from(height.0).to_bytes());
+    }
+    keccak_hash(data)
+}
+
 /// Alias for [`BridgePoolRootVext`].
 pub type Vext = BridgePoolRootVext;
 
@@ -63,6 +94,8 @@ pub type Vext = BridgePoolRootVext;
     Hash,
     PartialOrd,
     Ord,
+    Serialize,
+    Deserialize,
 )]
 pub struct SignedVext(pub Signed<BridgePoolRootVext>);
 
@@ -99,6 +132,8 @@ impl Vext {
     BorshSerialize,
     BorshDeserialize,
     BorshSchema,
+    Serialize,
+    Deserialize,
 )]
 pub struct MultiSignedVext(pub HashSet<SignedVext>);
 
@@ -0,0 +1,283 @@
+//! Vote extension types for attesting to the addresses of the Ethereum
+//! bridge contracts that governance scheduled to migrate to.
+//!
+//! Much like validator set updates, these vote extensions are signed
+//! with the Ethereum hot key of each validator, such that the signatures
+//! of a quorum of validators can be relayed to Ethereum, as a proof of
+//! the contract migration. As migrating the contracts is a high-value
+//! operation, validators additionally sign the same data with their
+//! Ethereum cold key.
+use std::collections::HashMap;
+use std::ops::Deref;
+
+use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use namada_core::types::address::Address;
+use namada_core::types::ethereum_events::EthAddress;
+use namada_core::types::key::common::{self, Signature};
+use namada_core::types::storage::Epoch;
+use namada_tx::Signed;
+
+// the contract version plugged into the signed contract addresses
+// TODO: ideally, this value should not be hardcoded
+const GOVERNANCE_CONTRACT_VERSION: u8 = 1;
+
+/// Type alias for a [`ContractAddressesVextDigest`].
+pub type VextDigest = ContractAddressesVextDigest;
+
+/// Contains the digest of all signatures from a quorum of
+/// validators for a [`Vext`].
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct ContractAddressesVextDigest {
+    /// A mapping from a consensus validator address to a [`Signature`].
+    pub signatures: HashMap<Address, Signature>,
+    /// A mapping from a consensus validator address to the [`Signature`]
+    /// made with its Ethereum cold key, if any.
+    pub cold_signatures: HashMap<Address, Signature>,
+    /// The addresses of the new contracts signed over.
+    pub contracts: ContractAddresses,
+}
+
+impl VextDigest {
+    /// Build a singleton [`VextDigest`], from the provided [`Vext`].
+    #[inline]
+    pub fn singleton(ext: SignedVext) -> VextDigest {
+        let SignedVext { hot, cold_sig } = ext;
+        let validator = hot.data.validator_addr.clone();
+        VextDigest {
+            cold_signatures: cold_sig
+                .map(|sig| HashMap::from([(validator.clone(), sig)]))
+                .unwrap_or_default(),
+            signatures: HashMap::from([(validator, hot.sig)]),
+            contracts: hot.data.contracts,
+        }
+    }
+
+    /// Decompresses a set of signed [`Vext`] instances.
+    pub fn decompress(self, signing_epoch: Epoch) -> Vec<SignedVext> {
+        let VextDigest {
+            signatures,
+            mut cold_signatures,
+            contracts,
+        } = self;
+
+        signatures
+            .into_iter()
+            .map(|(validator_addr, signature)| {
+                let cold_sig = cold_signatures.remove(&validator_addr);
+                let data = Vext {
+                    validator_addr,
+                    contracts,
+                    signing_epoch,
+                };
+                SignedVext {
+                    hot: Signed::new_from(data, signature),
+                    cold_sig,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Represents a [`Vext`] signed by some validator, with its
+/// Ethereum hot key, and optionally with its Ethereum cold key.
+#[derive(
+    Clone,
+    Debug,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct SignedVext {
+    /// The extension, signed with the Ethereum hot key of the validator.
+    pub hot: Signed<Vext, SerializeWithAbiEncode>,
+    /// The signature of the same data with the Ethereum cold key of the
+    /// validator, if any.
+    pub cold_sig: Option<Signature>,
+}
+
+impl Deref for SignedVext {
+    type Target = Signed<Vext, SerializeWithAbiEncode>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.hot
+    }
+}
+
+/// The Ethereum addresses of the bridge contracts.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct ContractAddresses {
+    /// The Ethereum address of the ERC20 contract that represents
+    /// Namada's native token.
+    pub native_erc20: EthAddress,
+    /// The Ethereum address of the bridge contract.
+    pub bridge: EthAddress,
+}
+
+/// Type alias for a [`ContractAddressesVext`].
+pub type Vext = ContractAddressesVext;
+
+/// Represents the addresses of the new Ethereum bridge contracts,
+/// attested to by some validator.
+#[derive(
+    Eq,
+    PartialEq,
+    Clone,
+    Debug,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct ContractAddressesVext {
+    /// The addresses of the new contracts.
+    pub contracts: ContractAddresses,
+    /// TODO: the validator's address is temporarily being included
+    /// until we're able to map a Tendermint address to a validator
+    /// address (see <https://github.com/anoma/namada/issues/200>)
+    pub validator_addr: Address,
+    /// The value of Namada's [`Epoch`] at the creation of this
+    /// [`Vext`].
+    ///
+    /// It is signed together with the rest of the data to prevent
+    /// replay attacks on contract migrations, and it determines the
+    /// consensus validator set whose signatures are collected.
+    pub signing_epoch: Epoch,
+}
+
+impl Vext {
+    /// Creates a new signed [`Vext`].
+    ///
+    /// For more information, read the docs of [`SignedVext`].
+    #[inline]
+    pub fn sign(&self, sk: &common::SecretKey) -> SignedVext {
+        SignedVext {
+            hot: Signed::new(sk, self.clone()),
+            cold_sig: None,
+        }
+    }
+
+    /// Creates a new [`Vext`] signed with both the Ethereum hot key and
+    /// the Ethereum cold key of a validator.
+    pub fn sign_with_cold_key(
+        &self,
+        hot_sk: &common::SecretKey,
+        cold_sk: &common::SecretKey,
+    ) -> SignedVext {
+        SignedVext {
+            hot: Signed::new(hot_sk, self.clone()),
+            cold_sig: Some(Signed::new(cold_sk, self.clone()).sig),
+        }
+    }
+}
+
+// this is only here so we don't pollute the
+// outer namespace with serde traits
+mod tag {
+    use namada_core::types::eth_abi::{AbiEncode, Token};
+    use namada_core::types::hash::KeccakHasher;
+    use namada_core::types::keccak::KeccakHash;
+    use namada_core::types::key::Signable;
+    use serde::{Deserialize, Serialize};
+
+    use super::{Vext, GOVERNANCE_CONTRACT_VERSION};
+
+    /// Tag type that indicates we should use [`AbiEncode`]
+    /// to sign data in a [`namada_tx::Signed`] wrapper.
+    #[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize)]
+    pub struct SerializeWithAbiEncode;
+
+    impl Signable<Vext> for SerializeWithAbiEncode {
+        type Hasher = KeccakHasher;
+        type Output = KeccakHash;
+
+        fn as_signable(ext: &Vext) -> Self::Output {
+            AbiEncode::signable_keccak256(&[
+                Token::Uint(GOVERNANCE_CONTRACT_VERSION.into()),
+                Token::String("upgradeContracts".into()),
+                Token::Address(ext.contracts.bridge.0.into()),
+                Token::Address(ext.contracts.native_erc20.0.into()),
+                Token::Uint(ext.signing_epoch.0.into()),
+            ])
+        }
+    }
+}
+
+#[doc(inline)]
+pub use tag::SerializeWithAbiEncode;
+
+#[cfg(test)]
+mod tests {
+    use namada_core::types::address::testing::established_address_1;
+    use namada_core::types::key::testing::{keypair_3, keypair_4};
+    use namada_core::types::key::RefTo;
+    use namada_tx::verify_standalone_sig;
+
+    use super::*;
+
+    /// Test that the signatures collected in a [`VextDigest`] verify
+    /// once decompressed.
+    #[test]
+    fn test_digest_roundtrip() {
+        let sk = keypair_3();
+        let ext = Vext {
+            contracts: ContractAddresses {
+                native_erc20: EthAddress([1; 20]),
+                bridge: EthAddress([2; 20]),
+            },
+            validator_addr: established_address_1(),
+            signing_epoch: Epoch(3),
+        };
+        let signed = ext.sign(&sk);
+        let decompressed =
+            VextDigest::singleton(signed.clone()).decompress(Epoch(3));
+        assert_eq!(decompressed, vec![signed.clone()]);
+        assert!(decompressed[0].verify(&sk.ref_to()).is_ok());
+
+        // the signing epoch is part of the signed data
+        let replayed = VextDigest::singleton(signed).decompress(Epoch(4));
+        assert!(replayed[0].verify(&sk.ref_to()).is_err());
+
+        // cold key signatures are carried along with hot key signatures
+        let cold_sk = keypair_4();
+        let signed = ext.sign_with_cold_key(&sk, &cold_sk);
+        let decompressed =
+            VextDigest::singleton(signed.clone()).decompress(Epoch(3));
+        assert_eq!(decompressed, vec![signed]);
+        let cold_sig = decompressed[0].cold_sig.as_ref().expect("Test failed");
+        assert!(
+            verify_standalone_sig::<_, SerializeWithAbiEncode>(
+                &decompressed[0].data,
+                &cold_sk.ref_to(),
+                cold_sig,
+            )
+            .is_ok()
+        );
+    }
+}
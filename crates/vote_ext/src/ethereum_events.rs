@@ -6,17 +6,26 @@ use std::ops::Deref;
 
 use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use namada_core::types::address::Address;
-use namada_core::types::ethereum_events::EthereumEvent;
+use namada_core::types::ethereum_events::{EthAddress, EthereumEvent};
 use namada_core::types::key::common::{self, Signature};
 use namada_core::types::storage::BlockHeight;
 use namada_tx::Signed;
+use serde::{Deserialize, Serialize};
 
 /// Type alias for an [`EthereumEventsVext`].
 pub type Vext = EthereumEventsVext;
 
 /// Represents a [`Vext`] signed by some validator, with
 /// a Namada protocol key.
-#[derive(Clone, Debug, BorshSerialize, BorshSchema, BorshDeserialize)]
+#[derive(
+    Clone,
+    Debug,
+    BorshSerialize,
+    BorshSchema,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
 pub struct SignedVext(pub Signed<Vext>);
 
 impl Deref for SignedVext {
@@ -39,7 +48,15 @@ impl From<Signed<Vext>> for SignedVext {
 /// to be included as a vote extension at the end of a Tendermint PreCommit
 /// phase.
 #[derive(
-    Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
 )]
 pub struct EthereumEventsVext {
     /// The block height for which this [`Vext`] was made.
@@ -51,15 +68,24 @@ pub struct EthereumEventsVext {
     /// The new ethereum events seen. These should be
     /// deterministically ordered.
     pub ethereum_events: Vec<EthereumEvent>,
+    /// The address of the bridge contract the new Ethereum events were
+    /// observed from, which must be the one in effect at the epoch of
+    /// `block_height`.
+    pub bridge_contract: EthAddress,
 }
 
 impl Vext {
     /// Creates a [`Vext`] without any Ethereum events.
-    pub fn empty(block_height: BlockHeight, validator_addr: Address) -> Self {
+    pub fn empty(
+        block_height: BlockHeight,
+        validator_addr: Address,
+        bridge_contract: EthAddress,
+    ) -> Self {
         Self {
             block_height,
             ethereum_events: Vec::new(),
             validator_addr,
+            bridge_contract,
         }
     }
 
@@ -73,7 +99,15 @@ impl Vext {
 /// Aggregates an Ethereum event with the corresponding
 /// validators who saw this event.
 #[derive(
-    Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
 )]
 pub struct MultiSignedEthEvent {
     /// The Ethereum event that was signed.
@@ -89,14 +123,27 @@ pub type VextDigest = EthereumEventsVextDigest;
 /// Compresses a set of signed [`Vext`] instances, to save
 /// space on a block.
 #[derive(
-    Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
 )]
 pub struct EthereumEventsVextDigest {
     /// The signatures, signing address, and signing block height
     /// of each [`Vext`]
+    #[serde(with = "crate::serde_map_as_pairs")]
     pub signatures: HashMap<(Address, BlockHeight), Signature>,
     /// The events that were reported
     pub events: Vec<MultiSignedEthEvent>,
+    /// The bridge contract each [`Vext`] was observed from, keyed like
+    /// its signature
+    #[serde(with = "crate::serde_map_as_pairs")]
+    pub bridge_contracts: HashMap<(Address, BlockHeight), EthAddress>,
 }
 
 impl VextDigest {
@@ -108,6 +155,10 @@ impl VextDigest {
                 (ext.data.validator_addr.clone(), ext.data.block_height),
                 ext.sig,
             )]),
+            bridge_contracts: HashMap::from([(
+                (ext.data.validator_addr.clone(), ext.data.block_height),
+                ext.data.bridge_contract,
+            )]),
             events: ext
                 .data
                 .ethereum_events
@@ -125,12 +176,23 @@ impl VextDigest {
 
     /// Decompresses a set of signed [`Vext`] instances.
     pub fn decompress(self, _: BlockHeight) -> Vec<Signed<Vext>> {
-        let VextDigest { signatures, events } = self;
+        let VextDigest {
+            signatures,
+            events,
+            bridge_contracts,
+        } = self;
 
         let mut extensions = vec![];
 
         for (validator, sig) in signatures.into_iter() {
-            let mut ext = Vext::empty(validator.1, validator.0.clone());
+            // the signature of a vote extension can't be verified without
+            // the bridge contract it was observed from
+            let Some(&bridge_contract) = bridge_contracts.get(&validator)
+            else {
+                continue;
+            };
+            let mut ext =
+                Vext::empty(validator.1, validator.0.clone(), bridge_contract);
 
             for event in events.iter() {
                 if event.signers.contains(&validator) {
@@ -202,9 +264,11 @@ mod tests {
 
         let validator_1 = address::testing::established_address_1();
         let validator_2 = address::testing::established_address_2();
+        let bridge_contract = EthAddress([1; 20]);
 
         let ext = |validator: Address| -> Vext {
-            let mut ext = Vext::empty(last_block_height, validator);
+            let mut ext =
+                Vext::empty(last_block_height, validator, bridge_contract);
 
             ext.ethereum_events.push(ev_1.clone());
             ext.ethereum_events.push(ev_2.clone());
@@ -221,6 +285,7 @@ mod tests {
             let mut ext = Vext::empty(
                 BlockHeight(last_block_height.0 - 1),
                 validator_1.clone(),
+                bridge_contract,
             );
             ext.ethereum_events.push(ev_1.clone());
             ext.ethereum_events.push(ev_2.clone());
@@ -264,7 +329,15 @@ mod tests {
             },
         ];
 
-        let digest = VextDigest { events, signatures };
+        let bridge_contracts = signatures
+            .keys()
+            .map(|key| (key.clone(), bridge_contract))
+            .collect();
+        let digest = VextDigest {
+            events,
+            signatures,
+            bridge_contracts,
+        };
 
         // finally, decompress the `VextDigest` back into a
         // `Vec<Signed<Vext>>`
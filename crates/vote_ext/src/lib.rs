@@ -1,6 +1,7 @@
 //! This module contains types necessary for processing vote extensions.
 
 pub mod bridge_pool_roots;
+pub mod contract_addresses;
 pub mod ethereum_events;
 pub mod validator_set_update;
 
@@ -12,11 +13,56 @@ use namada_core::types::key::common;
 use namada_tx::data::protocol::{ProtocolTx, ProtocolTxType};
 use namada_tx::data::TxType;
 use namada_tx::{Signature, Signed, Tx, TxError};
+use serde::{Deserialize, Serialize};
+
+/// Serde helpers for maps whose keys can't be represented as the keys of
+/// a JSON object. These maps are serialized as a list of key-value pairs,
+/// sorted by key.
+mod serde_map_as_pairs {
+    use std::collections::HashMap;
+    use std::hash::Hash;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<K, V, S>(
+        map: &HashMap<K, V>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        K: Ord + Serialize,
+        V: Serialize,
+        S: Serializer,
+    {
+        let mut pairs: Vec<_> = map.iter().collect();
+        pairs.sort_unstable_by(|(k1, _), (k2, _)| k1.cmp(k2));
+        pairs.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, K, V, D>(
+        deserializer: D,
+    ) -> Result<HashMap<K, V>, D::Error>
+    where
+        K: Eq + Hash + Deserialize<'de>,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let pairs = Vec::<(K, V)>::deserialize(deserializer)?;
+        Ok(pairs.into_iter().collect())
+    }
+}
 
 /// This type represents the data we pass to the extension of
 /// a vote at the PreCommit phase of Tendermint.
 #[derive(
-    Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
 )]
 pub struct VoteExtension {
     /// Vote extension data related with Ethereum events.
@@ -25,6 +71,9 @@ pub struct VoteExtension {
     pub bridge_pool_root: Option<bridge_pool_roots::SignedVext>,
     /// Vote extension data related with validator set updates.
     pub validator_set_update: Option<validator_set_update::SignedVext>,
+    /// Vote extension data related with updates of the Ethereum bridge
+    /// contracts.
+    pub contract_addresses: Option<contract_addresses::SignedVext>,
 }
 
 macro_rules! ethereum_tx_data_deserialize_inner {
@@ -105,6 +154,9 @@ ethereum_tx_data_declare! {
         BridgePoolVext(bridge_pool_roots::SignedVext),
         /// Validator set update signed by some validator
         ValSetUpdateVext(validator_set_update::SignedVext),
+        /// Addresses of new Ethereum bridge contracts signed by some
+        /// validator
+        ContractAddressesVext(contract_addresses::SignedVext),
     }
 }
 
@@ -166,6 +218,7 @@ impl EthereumTxData {
             EthEventsVext,
             BridgePoolVext,
             ValSetUpdateVext,
+            ContractAddressesVext,
         }
     }
 
@@ -199,6 +252,10 @@ impl EthereumTxData {
                 BorshDeserialize::try_from_slice(data)
                     .map(EthereumTxData::ValSetUpdateVext)
             },
+            ProtocolTxType::ContractAddressesVext => |data| {
+                BorshDeserialize::try_from_slice(data)
+                    .map(EthereumTxData::ContractAddressesVext)
+            },
         };
         deserialize(data)
             .map_err(|err| TxError::Deserialization(err.to_string()))
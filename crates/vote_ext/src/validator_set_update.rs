@@ -6,16 +6,24 @@ use std::ops::Deref;
 
 use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use namada_core::types::address::Address;
-use namada_core::types::eth_abi::{AbiEncode, Encode, Token};
+use namada_core::types::chain::ChainId;
+use namada_core::types::eth_abi::{
+    self, AbiEncode, DecodeError, Encode, ParamType, Token,
+};
 use namada_core::types::ethereum_events::EthAddress;
-use namada_core::types::keccak::KeccakHash;
+use namada_core::types::hash::KeccakHasher;
+use namada_core::types::keccak::{keccak_hash, KeccakHash};
 use namada_core::types::key::common::{self, Signature};
+use namada_core::types::key::{
+    SigScheme, Signable, SignableEthMessage, VerifySigError,
+};
 use namada_core::types::storage::Epoch;
 use namada_core::types::voting_power::{
     EthBridgeVotingPower, FractionalVotingPower,
 };
 use namada_core::types::{ethereum_structs, token};
 use namada_tx::Signed;
+use thiserror::Error;
 
 // the contract versions and namespaces plugged into validator set hashes
 // TODO: ideally, these values should not be hardcoded
@@ -30,13 +38,22 @@ pub type VextDigest = ValidatorSetUpdateVextDigest;
 /// Contains the digest of all signatures from a quorum of
 /// validators for a [`Vext`].
 #[derive(
-    Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    serde::Serialize,
+    serde::Deserialize,
 )]
 pub struct ValidatorSetUpdateVextDigest {
     /// A mapping from a consensus validator address to a [`Signature`].
     pub signatures: HashMap<Address, Signature>,
     /// The addresses of the validators in the new [`Epoch`],
     /// and their respective voting power.
+    #[serde(with = "crate::serde_map_as_pairs")]
     pub voting_powers: VotingPowersMap,
 }
 
@@ -78,7 +95,15 @@ impl VextDigest {
 /// Represents a [`Vext`] signed by some validator, with
 /// an Ethereum key.
 #[derive(
-    Clone, Debug, BorshSerialize, BorshDeserialize, BorshSchema, PartialEq, Eq,
+    Clone,
+    Debug,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
 )]
 pub struct SignedVext(pub Signed<Vext, SerializeWithAbiEncode>);
 
@@ -90,12 +115,37 @@ impl Deref for SignedVext {
     }
 }
 
+impl SignedVext {
+    /// Verify the signature of this [`SignedVext`], over the digest of
+    /// its [`Vext`] separated to the domain of `chain_id`, if any.
+    ///
+    /// With no `chain_id`, this is equivalent to [`Signed::verify`].
+    pub fn verify_in_domain(
+        &self,
+        pk: &common::PublicKey,
+        chain_id: Option<&ChainId>,
+    ) -> Result<(), VerifySigError> {
+        let digest = self.data.signable_digest(chain_id);
+        common::SigScheme::verify_signature_with_hasher::<KeccakHasher>(
+            pk, &digest, &self.sig,
+        )
+    }
+}
+
 /// Type alias for a [`ValidatorSetUpdateVext`].
 pub type Vext = ValidatorSetUpdateVext;
 
 /// Represents a validator set update, for some new [`Epoch`].
 #[derive(
-    Eq, PartialEq, Clone, Debug, BorshSerialize, BorshDeserialize, BorshSchema,
+    Eq,
+    PartialEq,
+    Clone,
+    Debug,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    serde::Serialize,
+    serde::Deserialize,
 )]
 pub struct ValidatorSetUpdateVext {
     /// The addresses of the validators in the new [`Epoch`],
@@ -105,6 +155,7 @@ pub struct ValidatorSetUpdateVext {
     /// into two arrays: one for its keys, and another for its
     /// values. The arrays are sorted in descending order based
     /// on the voting power of each validator.
+    #[serde(with = "crate::serde_map_as_pairs")]
     pub voting_powers: VotingPowersMap,
     /// TODO: the validator's address is temporarily being included
     /// until we're able to map a Tendermint address to a validator
@@ -135,6 +186,42 @@ impl Vext {
     pub fn sign(&self, sk: &common::SecretKey) -> SignedVext {
         SignedVext(Signed::new(sk, self.clone()))
     }
+
+    /// Creates a new signed [`Vext`], over a digest separated to the
+    /// domain of `chain_id`, if any.
+    ///
+    /// With no `chain_id`, this is equivalent to [`Vext::sign`].
+    pub fn sign_in_domain(
+        &self,
+        sk: &common::SecretKey,
+        chain_id: Option<&ChainId>,
+    ) -> SignedVext {
+        let digest = self.signable_digest(chain_id);
+        let sig =
+            common::SigScheme::sign_with_hasher::<KeccakHasher>(sk, digest);
+        SignedVext(Signed::new_from(self.clone(), sig))
+    }
+
+    /// Compute the keccak digest signed over in this [`Vext`].
+    ///
+    /// If a `chain_id` is given, the digest is separated to the domain
+    /// of that chain, as per [`compute_domain_separated_digest`].
+    /// Otherwise, this is the digest of [`compute_signable_digest`].
+    #[inline]
+    pub fn signable_digest(&self, chain_id: Option<&ChainId>) -> KeccakHash {
+        signable_message_digest(&self.signable_bytes(chain_id))
+    }
+
+    /// Return the ABI-encoded message signed over in this [`Vext`], as
+    /// per [`encode_signable_message`].
+    #[inline]
+    pub fn signable_bytes(&self, chain_id: Option<&ChainId>) -> Vec<u8> {
+        encode_signable_message(
+            &self.voting_powers,
+            self.signing_epoch,
+            chain_id,
+        )
+    }
 }
 
 /// Container type for both kinds of Ethereum bridge addresses:
@@ -152,6 +239,8 @@ impl Vext {
     BorshSerialize,
     BorshDeserialize,
     BorshSchema,
+    serde::Serialize,
+    serde::Deserialize,
 )]
 pub struct EthAddrBook {
     /// Ethereum address derived from a hot key.
@@ -170,20 +259,16 @@ pub trait VotingPowersMapExt {
     /// sorted in descending order by voting power.
     fn get_sorted(&self) -> Vec<(&EthAddrBook, &token::Amount)>;
 
-    /// Returns the list of Ethereum validator hot and cold addresses and their
-    /// respective voting powers (in this order), with an Ethereum ABI
-    /// compatible encoding. Implementations of this method must be
-    /// deterministic based on `self`. In addition, the returned `Vec`s must be
-    /// sorted in descending order by voting power, as this is more efficient to
-    /// deal with on the Ethereum side when working out if there is enough
-    /// voting power for a given validator set update.
-    fn get_abi_encoded(&self) -> (Vec<Token>, Vec<Token>) {
+    /// Returns a [`Vec`] of pairs of validator addresses and voting powers,
+    /// sorted like [`VotingPowersMapExt::get_sorted`], with the voting
+    /// powers normalized to [`EthBridgeVotingPower`] values. This is the
+    /// data validators sign over.
+    fn get_normalized(&self) -> Vec<(&EthAddrBook, EthBridgeVotingPower)> {
         let sorted = self.get_sorted();
 
         let total_voting_power: token::Amount =
             sorted.iter().map(|&(_, &voting_power)| voting_power).sum();
 
-        // split the vec into two portions
         sorted
             .into_iter()
             .map(|(addr_book, &voting_power)| {
@@ -197,7 +282,23 @@ pub trait VotingPowersMapExt {
                          voting power",
                     )
                     .into();
+                (addr_book, voting_power)
+            })
+            .collect()
+    }
 
+    /// Returns the list of Ethereum validator hot and cold addresses and their
+    /// respective voting powers (in this order), with an Ethereum ABI
+    /// compatible encoding. Implementations of this method must be
+    /// deterministic based on `self`. In addition, the returned `Vec`s must be
+    /// sorted in descending order by voting power, as this is more efficient to
+    /// deal with on the Ethereum side when working out if there is enough
+    /// voting power for a given validator set update.
+    fn get_abi_encoded(&self) -> (Vec<Token>, Vec<Token>) {
+        // split the vec into two portions
+        self.get_normalized()
+            .into_iter()
+            .map(|(addr_book, voting_power)| {
                 let &EthAddrBook {
                     hot_key_addr,
                     cold_key_addr,
@@ -233,6 +334,131 @@ pub trait VotingPowersMapExt {
     }
 }
 
+/// Error decoding the voting powers of a validator set update.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum VotingPowersDecodeError {
+    /// The payload is not a valid ABI encoding of two `bytes32[]`
+    #[error("Invalid ABI encoding of the voting powers: {0}")]
+    Abi(String),
+    /// The bridge and governance validator sets differ in length
+    #[error(
+        "Got {bridge} bridge validators, but {governance} governance \
+         validators"
+    )]
+    LengthMismatch {
+        /// The number of bridge validators
+        bridge: usize,
+        /// The number of governance validators
+        governance: usize,
+    },
+    /// The hot and cold key of a validator were assigned different voting
+    /// powers
+    #[error(
+        "The hot and cold key of validator {0} have different voting powers"
+    )]
+    VotingPowerMismatch(usize),
+    /// The validators are not in the order they are signed in
+    #[error("Validator {0} is not sorted by descending voting power")]
+    NotSorted(usize),
+}
+
+impl From<DecodeError> for VotingPowersDecodeError {
+    fn from(err: DecodeError) -> Self {
+        Self::Abi(err.to_string())
+    }
+}
+
+/// ABI-encode the voting powers of a validator set update, as the
+/// `(bytes32[], bytes32[])` pair of bridge and governance validator sets
+/// signed over by validators.
+///
+/// The voting powers are normalized and sorted exactly as during the
+/// signing and validation of validator set update vote extensions.
+pub fn encode_voting_powers(voting_powers: &VotingPowersMap) -> Vec<u8> {
+    let (bridge_validators, governance_validators) =
+        voting_powers.get_abi_encoded();
+    AbiEncode::encode(&[
+        Token::Array(bridge_validators),
+        Token::Array(governance_validators),
+    ])
+    .into_inner()
+}
+
+/// Decode the voting powers ABI-encoded by [`encode_voting_powers`].
+///
+/// The original voting powers can't be recovered from their normalized
+/// values, therefore the validators are returned along with their
+/// [`EthBridgeVotingPower`], in the order they were signed in.
+pub fn decode_voting_powers(
+    bytes: &[u8],
+) -> Result<Vec<(EthAddrBook, EthBridgeVotingPower)>, VotingPowersDecodeError> {
+    let validator_set = ParamType::Array(Box::new(ParamType::FixedBytes(32)));
+    let mut tokens =
+        eth_abi::decode(&[validator_set.clone(), validator_set], bytes)?
+            .into_iter()
+            .map(decode_validator_set);
+    let (bridge, governance) = match (tokens.next(), tokens.next()) {
+        (Some(bridge), Some(governance)) => (bridge?, governance?),
+        _ => unreachable!("We decoded exactly two tokens"),
+    };
+    if bridge.len() != governance.len() {
+        return Err(VotingPowersDecodeError::LengthMismatch {
+            bridge: bridge.len(),
+            governance: governance.len(),
+        });
+    }
+
+    let mut decoded: Vec<(EthAddrBook, EthBridgeVotingPower)> =
+        Vec::with_capacity(bridge.len());
+    for (index, ((hot_key_addr, hot_power), (cold_key_addr, cold_power))) in
+        bridge.into_iter().zip(governance).enumerate()
+    {
+        if hot_power != cold_power {
+            return Err(VotingPowersDecodeError::VotingPowerMismatch(index));
+        }
+        // NB: validators whose voting powers only differ before being
+        // normalized are not sorted by address
+        let prev_power = decoded.last().map(|&(_, power)| power);
+        if prev_power.is_some_and(|prev_power| prev_power < hot_power) {
+            return Err(VotingPowersDecodeError::NotSorted(index));
+        }
+        let addr_book = EthAddrBook {
+            hot_key_addr,
+            cold_key_addr,
+        };
+        decoded.push((addr_book, hot_power));
+    }
+    Ok(decoded)
+}
+
+/// Decode a `bytes32[]` of validators encoded with
+/// [`encode_validator_data`].
+fn decode_validator_set(
+    token: Token,
+) -> Result<Vec<(EthAddress, EthBridgeVotingPower)>, VotingPowersDecodeError> {
+    let Token::Array(validators) = token else {
+        return Err(VotingPowersDecodeError::Abi(
+            "Expected an array of validators".into(),
+        ));
+    };
+    validators
+        .into_iter()
+        .map(|validator| match validator {
+            Token::FixedBytes(bytes) => {
+                let bytes: [u8; 32] = bytes.try_into().map_err(|_| {
+                    VotingPowersDecodeError::Abi(
+                        "Expected 32 bytes of validator data".into(),
+                    )
+                })?;
+                Ok(decode_validator_data(bytes))
+            }
+            _ => Err(VotingPowersDecodeError::Abi(
+                "Expected fixed bytes of validator data".into(),
+            )),
+        })
+        .collect()
+}
+
 /// Returns the bridge and governance keccak hashes calculated from
 /// the given hot and cold key addresses, and their respective validator's
 /// voting powers, normalized to `2^32`.
@@ -284,6 +510,95 @@ fn epoch_to_token(Epoch(e): Epoch) -> Token {
     Token::Uint(e.into())
 }
 
+/// ABI-encode the message that validators sign over, in a validator set
+/// update vote extension issued at `signing_epoch` with the given voting
+/// powers of the next epoch's validator set.
+///
+/// The voting powers are normalized and sorted as per
+/// [`VotingPowersMapExt::get_abi_encoded`], and hashed into the bridge and
+/// governance validator set hashes. If a `chain_id` is given, it is mixed
+/// into the message right after the purpose string, as described in
+/// [`compute_domain_separated_digest`].
+///
+/// Validators sign over the keccak hash of these bytes, prefixed with the
+/// Ethereum signed message header. Hence, a signature may be checked
+/// against them with Ethereum tooling, as a signature over the keccak hash
+/// of the message.
+pub fn encode_signable_message(
+    voting_powers: &VotingPowersMap,
+    signing_epoch: Epoch,
+    chain_id: Option<&ChainId>,
+) -> Vec<u8> {
+    // NOTE: the smart contract expects us to sign
+    // against the next nonce (i.e. the new epoch)
+    let next_epoch = signing_epoch.next();
+    let (KeccakHash(bridge_hash), KeccakHash(gov_hash)) =
+        voting_powers.get_bridge_and_gov_hashes(next_epoch);
+    match chain_id {
+        Some(chain_id) => AbiEncode::encode(&[
+            Token::Uint(GOVERNANCE_CONTRACT_VERSION.into()),
+            Token::String("updateValidatorSet".into()),
+            Token::String(chain_id.as_str().into()),
+            Token::FixedBytes(bridge_hash.to_vec()),
+            Token::FixedBytes(gov_hash.to_vec()),
+            epoch_to_token(next_epoch),
+        ])
+        .into_inner(),
+        None => AbiEncode::encode(&[
+            Token::Uint(GOVERNANCE_CONTRACT_VERSION.into()),
+            Token::String("updateValidatorSet".into()),
+            Token::FixedBytes(bridge_hash.to_vec()),
+            Token::FixedBytes(gov_hash.to_vec()),
+            epoch_to_token(next_epoch),
+        ])
+        .into_inner(),
+    }
+}
+
+/// Compute the keccak digest signed over for the given message, encoded
+/// with [`encode_signable_message`].
+#[inline]
+fn signable_message_digest(message: &[u8]) -> KeccakHash {
+    SignableEthMessage::as_signable(&keccak_hash(message))
+}
+
+/// Compute the keccak digest that validators sign over, in a validator set
+/// update vote extension issued at `signing_epoch` with the given voting
+/// powers of the next epoch's validator set.
+#[inline]
+pub fn compute_signable_digest(
+    voting_powers: &VotingPowersMap,
+    signing_epoch: Epoch,
+) -> KeccakHash {
+    signable_message_digest(&encode_signable_message(
+        voting_powers,
+        signing_epoch,
+        None,
+    ))
+}
+
+/// Compute the keccak digest that validators sign over, in a validator set
+/// update vote extension issued at `signing_epoch`, separated to the domain
+/// of the chain identified by `chain_id`.
+///
+/// Besides the data of [`compute_signable_digest`], the chain id is mixed
+/// into the signed message, right after the purpose string. Signatures
+/// over this digest can't be replayed on another chain, nor as signatures
+/// over other messages of the bridge contracts, since these are prefixed
+/// with a different purpose string.
+#[inline]
+pub fn compute_domain_separated_digest(
+    voting_powers: &VotingPowersMap,
+    signing_epoch: Epoch,
+    chain_id: &ChainId,
+) -> KeccakHash {
+    signable_message_digest(&encode_signable_message(
+        voting_powers,
+        signing_epoch,
+        Some(chain_id),
+    ))
+}
+
 /// Compute the keccak hash of a validator set update.
 ///
 /// For more information, check the specs of the Ethereum bridge smart
@@ -321,6 +636,23 @@ fn encode_validator_data(
     buffer
 }
 
+/// Inverse of [`encode_validator_data`].
+#[inline]
+fn decode_validator_data(
+    buffer: [u8; 32],
+) -> (EthAddress, EthBridgeVotingPower) {
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&buffer[..20]);
+
+    let mut voting_power = [0u8; 16];
+    voting_power[4..].copy_from_slice(&buffer[20..]);
+    let voting_power = u128::from_be_bytes(voting_power)
+        .try_into()
+        .expect("A 12 byte integer is a valid Ethereum bridge voting power");
+
+    (EthAddress(address), voting_power)
+}
+
 /// Struct for serializing validator set
 /// arguments with ABI for Ethereum smart
 /// contracts.
@@ -384,17 +716,14 @@ impl Encode<1> for ValidatorSetArgs {
 // this is only here so we don't pollute the
 // outer namespace with serde traits
 mod tag {
-    use namada_core::types::eth_abi::{AbiEncode, Encode, Token};
     use namada_core::types::hash::KeccakHasher;
     use namada_core::types::keccak::KeccakHash;
     use namada_core::types::key::Signable;
     use serde::{Deserialize, Serialize};
 
-    use super::{
-        epoch_to_token, Vext, VotingPowersMapExt, GOVERNANCE_CONTRACT_VERSION,
-    };
+    use super::{compute_signable_digest, Vext};
 
-    /// Tag type that indicates we should use [`AbiEncode`]
+    /// Tag type that indicates we should use [`AbiEncode`](super::AbiEncode)
     /// to sign data in a [`namada_tx::Signed`] wrapper.
     #[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize)]
     pub struct SerializeWithAbiEncode;
@@ -404,18 +733,7 @@ mod tag {
         type Output = KeccakHash;
 
         fn as_signable(ext: &Vext) -> Self::Output {
-            // NOTE: the smart contract expects us to sign
-            // against the next nonce (i.e. the new epoch)
-            let next_epoch = ext.signing_epoch.next();
-            let (KeccakHash(bridge_hash), KeccakHash(gov_hash)) =
-                ext.voting_powers.get_bridge_and_gov_hashes(next_epoch);
-            AbiEncode::signable_keccak256(&[
-                Token::Uint(GOVERNANCE_CONTRACT_VERSION.into()),
-                Token::String("updateValidatorSet".into()),
-                Token::FixedBytes(bridge_hash.to_vec()),
-                Token::FixedBytes(gov_hash.to_vec()),
-                epoch_to_token(next_epoch),
-            ])
+            compute_signable_digest(&ext.voting_powers, ext.signing_epoch)
         }
     }
 }
@@ -428,10 +746,65 @@ mod tests {
     use std::str::FromStr;
 
     use data_encoding::HEXLOWER;
+    use namada_core::borsh::BorshSerializeExt;
+    use namada_core::types::address::testing::established_address_1;
     use namada_core::types::ethereum_events::EthAddress;
+    use namada_core::types::key::testing::keypair_3;
+    use namada_core::types::key::RefTo;
 
     use super::*;
 
+    /// Pin the Borsh and JSON layouts of a validator set update vote
+    /// extension, which are decoded by clients outside of this repository
+    #[test]
+    fn test_valset_upd_vext_encoding() {
+        let validator_addr = established_address_1();
+        let vext = Vext {
+            voting_powers: HashMap::from([(
+                EthAddrBook {
+                    hot_key_addr: EthAddress([1; 20]),
+                    cold_key_addr: EthAddress([2; 20]),
+                },
+                token::Amount::from(5),
+            )]),
+            validator_addr: validator_addr.clone(),
+            signing_epoch: Epoch(3),
+        };
+
+        let mut amount = [0u8; 32];
+        amount[0] = 5;
+        let expected_borsh = [
+            // the length of the voting powers map, as a `u32`
+            &[1, 0, 0, 0][..],
+            &[1; 20],
+            &[2; 20],
+            &amount,
+            &validator_addr.serialize_to_vec(),
+            &[3, 0, 0, 0, 0, 0, 0, 0],
+        ]
+        .concat();
+        assert_eq!(vext.serialize_to_vec(), expected_borsh);
+        assert_eq!(Vext::try_from_slice(&expected_borsh).unwrap(), vext);
+
+        // maps with non-string keys are encoded as lists of key-value pairs
+        let expected_json = serde_json::json!({
+            "voting_powers": [[
+                {
+                    "hot_key_addr": format!("0x{}", "01".repeat(20)),
+                    "cold_key_addr": format!("0x{}", "02".repeat(20)),
+                },
+                "5",
+            ]],
+            "validator_addr": validator_addr.encode(),
+            "signing_epoch": 3,
+        });
+        assert_eq!(serde_json::to_value(&vext).unwrap(), expected_json);
+        assert_eq!(
+            serde_json::from_value::<Vext>(expected_json).unwrap(),
+            vext
+        );
+    }
+
     /// Test the keccak hash of a validator set update
     #[test]
     fn test_validator_set_update_keccak_hash() {
@@ -570,4 +943,275 @@ mod tests {
                         6b58b";
         assert_eq!(expected, encoded);
     }
+
+    /// Build a [`VotingPowersMap`] with two validators, holding 3/4 and 1/4
+    /// of the voting power, respectively.
+    fn two_validators() -> VotingPowersMap {
+        VotingPowersMap::from([
+            (
+                EthAddrBook {
+                    hot_key_addr: EthAddress([3; 20]),
+                    cold_key_addr: EthAddress([4; 20]),
+                },
+                100.into(),
+            ),
+            (
+                EthAddrBook {
+                    hot_key_addr: EthAddress([1; 20]),
+                    cold_key_addr: EthAddress([2; 20]),
+                },
+                300.into(),
+            ),
+        ])
+    }
+
+    /// Test that a validator set update signed for one chain can't be
+    /// verified against the domain of another chain, nor as a signature
+    /// over the digest without a domain.
+    #[test]
+    fn test_valset_upd_domain_separation() {
+        let sk = keypair_3();
+        let pk = sk.ref_to();
+        let chain_a = ChainId("chain-a".into());
+        let chain_b = ChainId("chain-b".into());
+        let vext = Vext {
+            voting_powers: two_validators(),
+            validator_addr: established_address_1(),
+            signing_epoch: Epoch(7),
+        };
+
+        let signed = vext.sign_in_domain(&sk, Some(&chain_a));
+        assert!(signed.verify_in_domain(&pk, Some(&chain_a)).is_ok());
+        assert!(signed.verify_in_domain(&pk, Some(&chain_b)).is_err());
+        assert!(signed.verify_in_domain(&pk, None).is_err());
+        assert!(signed.verify(&pk).is_err());
+
+        // without a domain, the signatures are the same as before
+        let legacy = vext.sign_in_domain(&sk, None);
+        assert_eq!(legacy, vext.sign(&sk));
+        assert!(legacy.verify(&pk).is_ok());
+        assert!(legacy.verify_in_domain(&pk, Some(&chain_a)).is_err());
+    }
+
+    /// Test that the message signed over in a validator set update matches
+    /// a known vector, and that its digest is the one signed over.
+    #[test]
+    fn test_valset_upd_signing_bytes_fixed_vector() {
+        // ```js
+        // const ethers = require('ethers');
+        //
+        // const abiEncoder = new ethers.AbiCoder();
+        // const maxVotingPower = (1n << 96n) - 1n;
+        // const validator = (addr, power) =>
+        //     ethers.solidityPacked(['address', 'uint96'], [addr, power]);
+        // const first = maxVotingPower * 3n / 4n;
+        // const second = maxVotingPower - first;
+        // const valsetHash = (namespace, validators) => ethers.keccak256(
+        //     abiEncoder.encode(
+        //         ['uint256', 'string', 'bytes32[]', 'uint256'],
+        //         [1, namespace, validators, 8],
+        //     ),
+        // );
+        //
+        // const output = abiEncoder.encode(
+        //     ['uint256', 'string', 'string', 'bytes32', 'bytes32', 'uint256'],
+        //     [
+        //         1,
+        //         'updateValidatorSet',
+        //         'chain-a',
+        //         valsetHash('bridge', [
+        //             validator('0x' + '01'.repeat(20), first),
+        //             validator('0x' + '03'.repeat(20), second),
+        //         ]),
+        //         valsetHash('governance', [
+        //             validator('0x' + '02'.repeat(20), first),
+        //             validator('0x' + '04'.repeat(20), second),
+        //         ]),
+        //         8,
+        //     ],
+        // );
+        //
+        // console.log(output);
+        // console.log(ethers.hashMessage(ethers.getBytes(ethers.keccak256(output))));
+        // ```
+        const EXPECTED: &str = "\
+            0000000000000000000000000000000000000000000000000000000000000001\
+            00000000000000000000000000000000000000000000000000000000000000c0\
+            0000000000000000000000000000000000000000000000000000000000000100\
+            8643a8cc6616b04c22fc683e480f2a999b9d7d73a86113988477a02f55c94f60\
+            95c0c1b1b284703c7a0b5cbcd62c053928161cab59429e37d20c39ce57c557d8\
+            0000000000000000000000000000000000000000000000000000000000000008\
+            0000000000000000000000000000000000000000000000000000000000000012\
+            75706461746556616c696461746f725365740000000000000000000000000000\
+            0000000000000000000000000000000000000000000000000000000000000007\
+            636861696e2d6100000000000000000000000000000000000000000000000000";
+        const EXPECTED_DIGEST: &str =
+            "e1fd936ac9b4c85b5e7380d3e88603ed2449caa3b95d061484e61068a5cac3b2";
+
+        let chain_id = ChainId("chain-a".into());
+        let vext = Vext {
+            voting_powers: two_validators(),
+            validator_addr: established_address_1(),
+            signing_epoch: Epoch(7),
+        };
+        let bytes = vext.signable_bytes(Some(&chain_id));
+        assert_eq!(HEXLOWER.encode(&bytes), EXPECTED);
+
+        let KeccakHash(digest) = vext.signable_digest(Some(&chain_id));
+        assert_eq!(HEXLOWER.encode(&digest), EXPECTED_DIGEST);
+        assert_eq!(
+            compute_domain_separated_digest(
+                &vext.voting_powers,
+                vext.signing_epoch,
+                &chain_id
+            ),
+            KeccakHash(digest)
+        );
+
+        // without a domain, the chain id is left out of the message
+        let bytes = vext.signable_bytes(None);
+        assert_eq!(
+            HEXLOWER.encode(&bytes),
+            "\
+            0000000000000000000000000000000000000000000000000000000000000001\
+            00000000000000000000000000000000000000000000000000000000000000a0\
+            8643a8cc6616b04c22fc683e480f2a999b9d7d73a86113988477a02f55c94f60\
+            95c0c1b1b284703c7a0b5cbcd62c053928161cab59429e37d20c39ce57c557d8\
+            0000000000000000000000000000000000000000000000000000000000000008\
+            0000000000000000000000000000000000000000000000000000000000000012\
+            75706461746556616c696461746f725365740000000000000000000000000000"
+        );
+        assert_eq!(
+            vext.signable_digest(None),
+            compute_signable_digest(&vext.voting_powers, vext.signing_epoch)
+        );
+    }
+
+    /// Test that the encoded voting powers match the payload expected by
+    /// the Ethereum bridge smart contracts.
+    #[test]
+    fn test_encode_voting_powers_fixed_vector() {
+        // ```js
+        // const ethers = require('ethers');
+        //
+        // const abiEncoder = new ethers.AbiCoder();
+        // const maxVotingPower = (1n << 96n) - 1n;
+        // const validator = (addr, power) =>
+        //     ethers.solidityPacked(['address', 'uint96'], [addr, power]);
+        // const first = maxVotingPower * 3n / 4n;
+        // const second = maxVotingPower - first;
+        //
+        // const output = abiEncoder.encode(
+        //     ['bytes32[]', 'bytes32[]'],
+        //     [
+        //         [validator('0x' + '01'.repeat(20), first),
+        //          validator('0x' + '03'.repeat(20), second)],
+        //         [validator('0x' + '02'.repeat(20), first),
+        //          validator('0x' + '04'.repeat(20), second)],
+        //     ],
+        // );
+        //
+        // console.log(output);
+        // ```
+        const EXPECTED: &str = "\
+            0000000000000000000000000000000000000000000000000000000000000040\
+            00000000000000000000000000000000000000000000000000000000000000a0\
+            0000000000000000000000000000000000000000000000000000000000000002\
+            0101010101010101010101010101010101010101bfffffffffffffffffffffff\
+            0303030303030303030303030303030303030303400000000000000000000000\
+            0000000000000000000000000000000000000000000000000000000000000002\
+            0202020202020202020202020202020202020202bfffffffffffffffffffffff\
+            0404040404040404040404040404040404040404400000000000000000000000";
+
+        let encoded = encode_voting_powers(&two_validators());
+        assert_eq!(HEXLOWER.encode(&encoded), EXPECTED);
+    }
+
+    /// Test that decoding encoded voting powers yields the normalized
+    /// voting powers validators sign over.
+    #[test]
+    fn test_voting_powers_round_trip() {
+        let voting_powers = two_validators();
+        let decoded =
+            decode_voting_powers(&encode_voting_powers(&voting_powers))
+                .expect("Test failed");
+        let expected: Vec<_> = voting_powers
+            .get_normalized()
+            .into_iter()
+            .map(|(addr_book, power)| (addr_book.clone(), power))
+            .collect();
+        assert_eq!(decoded, expected);
+
+        // the decoded voting powers hash to the same values
+        let (bridge_validators, governance_validators) = decoded
+            .iter()
+            .map(|(addr_book, power)| {
+                (
+                    Token::FixedBytes(
+                        encode_validator_data(addr_book.hot_key_addr, *power)
+                            .into(),
+                    ),
+                    Token::FixedBytes(
+                        encode_validator_data(addr_book.cold_key_addr, *power)
+                            .into(),
+                    ),
+                )
+            })
+            .unzip();
+        let epoch = Epoch(3);
+        assert_eq!(
+            valset_upd_toks_to_hashes(
+                epoch,
+                bridge_validators,
+                governance_validators
+            ),
+            voting_powers.get_bridge_and_gov_hashes(epoch),
+        );
+
+        // the empty validator set round trips
+        let empty = encode_voting_powers(&VotingPowersMap::default());
+        assert_eq!(decode_voting_powers(&empty), Ok(vec![]));
+    }
+
+    /// Test that payloads which validators could not have signed over
+    /// are rejected.
+    #[test]
+    fn test_decode_voting_powers_rejects_malformed_payloads() {
+        let validator = |addr: u8, power: u64| {
+            Token::FixedBytes(
+                encode_validator_data(EthAddress([addr; 20]), power.into())
+                    .into(),
+            )
+        };
+        let encode = |bridge: Vec<Token>, governance: Vec<Token>| {
+            AbiEncode::encode(&[Token::Array(bridge), Token::Array(governance)])
+                .into_inner()
+        };
+
+        assert!(matches!(
+            decode_voting_powers(&[0xff; 7]),
+            Err(VotingPowersDecodeError::Abi(_))
+        ));
+        assert_eq!(
+            decode_voting_powers(&encode(vec![validator(1, 1)], vec![])),
+            Err(VotingPowersDecodeError::LengthMismatch {
+                bridge: 1,
+                governance: 0,
+            })
+        );
+        assert_eq!(
+            decode_voting_powers(&encode(
+                vec![validator(1, 2)],
+                vec![validator(2, 1)]
+            )),
+            Err(VotingPowersDecodeError::VotingPowerMismatch(0))
+        );
+        assert_eq!(
+            decode_voting_powers(&encode(
+                vec![validator(1, 1), validator(3, 2)],
+                vec![validator(2, 1), validator(4, 2)]
+            )),
+            Err(VotingPowersDecodeError::NotSorted(1))
+        );
+    }
 }
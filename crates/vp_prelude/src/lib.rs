@@ -21,6 +21,7 @@ use std::marker::PhantomData;
 pub use namada_core::borsh::{
     BorshDeserialize, BorshSerialize, BorshSerializeExt,
 };
+use namada_core::types::account::AccountPublicKeysMap;
 pub use namada_core::types::address::Address;
 use namada_core::types::chain::CHAIN_ID_LENGTH;
 use namada_core::types::hash::{Hash, HASH_LENGTH};
@@ -58,23 +59,53 @@ pub fn log_string<T: AsRef<str>>(msg: T) {
     }
 }
 
-/// Checks if a proposal id is being executed
+/// Checks if a proposal id is being executed at the current block height
 pub fn is_proposal_accepted(ctx: &Ctx, proposal_id: u64) -> VpResult {
-    let proposal_execution_key =
-        gov_storage::keys::get_proposal_execution_key(proposal_id);
-
-    ctx.has_key_pre(&proposal_execution_key)
+    gov_storage::is_proposal_accepted(
+        &ctx.pre(),
+        &proposal_id.serialize_to_vec(),
+    )
 }
 
 /// Verify section signatures
 pub fn verify_signatures(ctx: &Ctx, tx: &Tx, owner: &Address) -> VpResult {
-    let max_signatures_per_transaction =
-        parameters::max_signatures_per_transaction(&ctx.pre())?;
-
     let public_keys_index_map =
         account::public_keys_index_map(&ctx.pre(), owner)?;
     let threshold = account::threshold(&ctx.pre(), owner)?.unwrap_or(1);
 
+    verify_signatures_of_pks(ctx, tx, owner, public_keys_index_map, threshold)
+}
+
+/// Verify that the tx was signed with the governance voting key registered
+/// by the given owner. If the owner has not registered any voting key, the
+/// verification fails and the keys of the account must be used instead.
+pub fn verify_governance_voting_signature(
+    ctx: &Ctx,
+    tx: &Tx,
+    owner: &Address,
+) -> VpResult {
+    match gov_storage::get_voting_key(&ctx.pre(), owner)? {
+        Some(voting_key) => verify_signatures_of_pks(
+            ctx,
+            tx,
+            owner,
+            AccountPublicKeysMap::from_iter([voting_key]),
+            1,
+        ),
+        None => Ok(false),
+    }
+}
+
+fn verify_signatures_of_pks(
+    ctx: &Ctx,
+    tx: &Tx,
+    owner: &Address,
+    public_keys_index_map: AccountPublicKeysMap,
+    threshold: u8,
+) -> VpResult {
+    let max_signatures_per_transaction =
+        parameters::max_signatures_per_transaction(&ctx.pre())?;
+
     // Serialize parameters
     let max_signatures = max_signatures_per_transaction.serialize_to_vec();
     let public_keys_map = public_keys_index_map.serialize_to_vec();
@@ -2,7 +2,7 @@
 //! into the bridge pool.
 use eth_bridge_pool::{GasFee, PendingTransfer, TransferToEthereum};
 use namada_tx_prelude::eth_bridge_pool::{
-    get_pending_key, BRIDGE_POOL_ADDRESS,
+    get_pending_key, get_pending_per_sender_key, BRIDGE_POOL_ADDRESS,
 };
 use namada_tx_prelude::parameters::native_erc20_key;
 use namada_tx_prelude::*;
@@ -60,8 +60,13 @@ fn apply_tx(ctx: &mut Ctx, signed: Tx) -> TxResult {
     log_string("Escrow succeeded");
     // add transfer into the pool
     let pending_key = get_pending_key(&transfer);
-    ctx.write(&pending_key, transfer)
+    ctx.write(&pending_key, &transfer)
         .wrap_err("Could not write transfer to bridge pool")?;
+    // count the transfer against the pending transfers of its sender
+    let per_sender_key = get_pending_per_sender_key(&transfer.transfer.sender);
+    let pending: u64 = ctx.read(&per_sender_key)?.unwrap_or_default();
+    ctx.write(&per_sender_key, pending + 1)
+        .wrap_err("Could not update the pending transfers of the sender")?;
     Ok(())
 }
 
@@ -0,0 +1,18 @@
+//! A tx to delegate the governance votes of an account to a governor
+
+use namada_tx_prelude::*;
+
+#[transaction(gas = 840866)]
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let tx_data = governance::VoteDelegationData::try_from_slice(&data[..])
+        .wrap_err("failed to decode VoteDelegationData")?;
+
+    debug_log!("apply_tx called to delegate governance votes");
+
+    governance::delegate_votes(ctx, tx_data)
+}
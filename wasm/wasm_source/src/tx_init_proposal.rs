@@ -46,7 +46,23 @@ fn apply_tx(ctx: &mut Ctx, tx: Tx) -> TxResult {
         None => None,
     };
 
+    // Get the code chunks from the referred to sections, in order
+    let code_chunks = tx_data
+        .code_chunks
+        .iter()
+        .map(|hash| {
+            tx.get_section(hash)
+                .ok_or_err_msg("Missing proposal code chunk")?
+                .extra_data()
+                .ok_or_err_msg("Missing full proposal code chunk")
+        })
+        .collect::<EnvResult<Vec<_>>>()
+        .map_err(|err| {
+            ctx.set_commitment_sentinel();
+            err
+        })?;
+
     log_string("apply_tx called to create a new governance proposal");
 
-    governance::init_proposal(ctx, tx_data, content, code)
+    governance::init_proposal(ctx, tx_data, content, code, code_chunks)
 }
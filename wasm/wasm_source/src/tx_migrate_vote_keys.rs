@@ -0,0 +1,18 @@
+//! A tx to be submitted as the code of a governance proposal, migrating the
+//! governance vote keys of the next batch of proposals to the layout
+//! recording the role of the voters. The proposal is submitted again until
+//! the votes of all the proposals are migrated.
+
+use namada_tx_prelude::*;
+
+#[transaction(gas = 2500000)] // TODO: needs to be benchmarked
+fn apply_tx(ctx: &mut Ctx, _tx_data: Tx) -> TxResult {
+    debug_log!("apply_tx called to migrate the governance vote keys");
+
+    let migrated = governance::migrate_vote_keys(
+        ctx,
+        gov_storage::VOTE_KEY_MIGRATION_BATCH_SIZE,
+    )?;
+    debug_log!("migrated {migrated} governance votes");
+    Ok(())
+}
@@ -49,7 +49,9 @@ impl<'a> From<&'a storage::Key> for KeyType<'a> {
             Self::PoS
         } else if let Some(address) = pgf_storage::keys::is_stewards_key(key) {
             Self::PgfSteward(address)
-        } else if gov_storage::keys::is_vote_key(key) {
+        } else if gov_storage::keys::is_vote_key(key)
+            || gov_storage::keys::is_legacy_vote_key(key)
+        {
             let voter_address = gov_storage::keys::get_voter_address(key);
             if let Some(address) = voter_address {
                 Self::GovernanceVote(address)
@@ -84,6 +86,12 @@ fn validate_tx(
     let valid_sig = Lazy::new(|| {
         matches!(verify_signatures(ctx, &tx_data, &addr), Ok(true))
     });
+    let valid_gov_sig = Lazy::new(|| {
+        matches!(
+            verify_governance_voting_signature(ctx, &tx_data, &addr),
+            Ok(true)
+        )
+    });
 
     for key in keys_changed.iter() {
         let key_type: KeyType = key.into();
@@ -149,7 +157,17 @@ fn validate_tx(
             KeyType::TokenMinter(minter) => minter != &addr || *valid_sig,
             KeyType::PoS => validate_pos_changes(ctx, &addr, key, &valid_sig)?,
             KeyType::PgfSteward(address) => address != &addr || *valid_sig,
-            KeyType::GovernanceVote(voter) => voter != &addr || *valid_sig,
+            KeyType::GovernanceVote(voter) => {
+                voter != &addr
+                    || *valid_sig
+                    || *valid_gov_sig
+                    // moving the unchanged vote to the current key layout
+                    || gov_storage::is_vote_key_migration(
+                        &ctx.pre(),
+                        &ctx.post(),
+                        key,
+                    )?
+            }
             KeyType::Masp | KeyType::Ibc => true,
             KeyType::Unknown => {
                 // Unknown changes require a valid signature
@@ -56,7 +56,9 @@ impl<'a> From<&'a storage::Key> for KeyType<'a> {
             Self::TokenMinter(minter)
         } else if is_pos_key(key) {
             Self::PoS
-        } else if gov_storage::keys::is_vote_key(key) {
+        } else if gov_storage::keys::is_vote_key(key)
+            || gov_storage::keys::is_legacy_vote_key(key)
+        {
             let voter_address = gov_storage::keys::get_voter_address(key);
             if let Some(address) = voter_address {
                 Self::GovernanceVote(address)
@@ -95,6 +97,12 @@ fn validate_tx(
     let valid_sig = Lazy::new(|| {
         matches!(verify_signatures(ctx, &tx_data, &addr), Ok(true))
     });
+    let valid_gov_sig = Lazy::new(|| {
+        matches!(
+            verify_governance_voting_signature(ctx, &tx_data, &addr),
+            Ok(true)
+        )
+    });
 
     for key in keys_changed.iter() {
         let key_type: KeyType = key.into();
@@ -133,7 +141,17 @@ fn validate_tx(
             KeyType::TokenMinter(minter) => minter != &addr || *valid_sig,
             KeyType::PoS => validate_pos_changes(ctx, &addr, key, &valid_sig)?,
             KeyType::PgfSteward(address) => address != &addr || *valid_sig,
-            KeyType::GovernanceVote(voter) => voter != &addr || *valid_sig,
+            KeyType::GovernanceVote(voter) => {
+                voter != &addr
+                    || *valid_sig
+                    || *valid_gov_sig
+                    // moving the unchanged vote to the current key layout
+                    || gov_storage::is_vote_key_migration(
+                        &ctx.pre(),
+                        &ctx.post(),
+                        key,
+                    )?
+            }
             KeyType::Vp(owner) => {
                 let has_post: bool = ctx.has_key_post(key)?;
                 if owner == &addr {
@@ -548,6 +566,90 @@ mod tests {
         );
     }
 
+    /// Run the VP on a governance vote of the VP owner, signed with the given
+    /// keypair. The owner's account key is `keypair_1` and the optional
+    /// governance voting key is registered before the vote is cast.
+    fn validate_signed_vote(
+        signer: key::common::SecretKey,
+        voting_key: Option<key::common::PublicKey>,
+    ) -> bool {
+        // Initialize a tx environment
+        let mut tx_env = TestTxEnv::default();
+
+        let vp_owner = address::testing::established_address_1();
+        let public_key = key::testing::keypair_1().ref_to();
+        let validator = address::testing::established_address_2();
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([&vp_owner, &validator]);
+        tx_env.init_account_storage(&vp_owner, vec![public_key], 1);
+        if let Some(voting_key) = voting_key {
+            gov_storage::register_voting_key(
+                &mut tx_env.wl_storage,
+                &vp_owner,
+                &voting_key,
+            )
+            .unwrap();
+        }
+
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+            // Cast a vote in a transaction
+            let vote_key = gov_storage::keys::get_vote_proposal_key(
+                0,
+                address.clone(),
+                validator.clone(),
+            );
+            tx::ctx()
+                .write(&vote_key, gov_storage::vote::ProposalVote::Yay)
+                .unwrap();
+        });
+
+        let pks_map = AccountPublicKeysMap::from_iter(vec![signer.ref_to()]);
+
+        let mut vp_env = vp_host_env::take();
+        let mut tx = vp_env.tx.clone();
+        tx.set_data(Data::new(vec![]));
+        tx.set_code(Code::new(vec![], None));
+        tx.add_section(Section::Signature(Signature::new(
+            vec![tx.raw_header_hash()],
+            pks_map.index_secret_keys(vec![signer]),
+            None,
+        )));
+        let signed_tx = tx.clone();
+        vp_env.tx = signed_tx.clone();
+        let keys_changed: BTreeSet<storage::Key> =
+            vp_env.all_touched_storage_keys();
+        let verifiers: BTreeSet<Address> = BTreeSet::default();
+        vp_host_env::set(vp_env);
+        validate_tx(&CTX, signed_tx, vp_owner, keys_changed, verifiers).unwrap()
+    }
+
+    /// Test that a governance vote signed with the account key is accepted
+    /// when no governance voting key is registered.
+    #[test]
+    fn test_vote_signed_with_account_key_accepted() {
+        assert!(validate_signed_vote(key::testing::keypair_1(), None));
+    }
+
+    /// Test that a governance vote signed with the registered governance
+    /// voting key is accepted.
+    #[test]
+    fn test_vote_signed_with_voting_key_accepted() {
+        let voting_key = key::testing::keypair_2();
+        assert!(validate_signed_vote(
+            voting_key.clone(),
+            Some(voting_key.ref_to())
+        ));
+    }
+
+    /// Test that a governance vote signed with a key that is neither the
+    /// account key nor the registered voting key is rejected.
+    #[test]
+    fn test_vote_signed_with_unregistered_key_rejected() {
+        assert!(!validate_signed_vote(key::testing::keypair_2(), None));
+    }
+
     /// Test that a non-validator PoS action that must be authorized is rejected
     /// without a valid signature.
     #[test]